@@ -14,14 +14,38 @@
 // If not, see <https://opensource.org/licenses/MIT>.
 
 use proc_macro2::TokenStream as TokenStream2;
-use syn::{DeriveInput, Result};
+use syn::{DeriveInput, Error, GenericParam, Result, WherePredicate};
+
+use crate::util::{get_amplify_crate, where_clause_with};
 
 pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    // `Any` requires `'static` type, so all type parameters must be `'static`
+    // as well, while types with lifetime parameters can't be `Any` at all
+    let mut predicates = Vec::<WherePredicate>::new();
+    for param in &input.generics.params {
+        match param {
+            GenericParam::Lifetime(lifetime) => {
+                return Err(Error::new_spanned(
+                    lifetime,
+                    "Deriving `AsAny` is not possible for types with lifetime parameters, since \
+                     they can't be `'static` as required by `core::any::Any`",
+                ))
+            }
+            GenericParam::Type(ty) => {
+                let ident = &ty.ident;
+                predicates.push(parse_quote! { #ident: 'static });
+            }
+            GenericParam::Const(_) => {}
+        }
+    }
+
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+    let where_clause = where_clause_with(&input.generics, predicates);
     let ident_name = &input.ident;
+    let amplify_crate = get_amplify_crate(&input);
 
     Ok(quote! {
-        impl #impl_generics ::amplify::AsAny for #ident_name #ty_generics #where_clause {
+        impl #impl_generics #amplify_crate::AsAny for #ident_name #ty_generics #where_clause {
            fn as_any(&self) -> &dyn ::core::any::Any {
                 self as &dyn ::core::any::Any
             }
@@ -0,0 +1,277 @@
+// Rust language amplification derive library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{
+    Data, DeriveInput, Error, Field, Fields, Ident, Index, Lit, LitInt, Member, Meta,
+    MetaNameValue, NestedMeta, Path, Result, Type, TypeArray, TypeSlice, WherePredicate,
+};
+
+use crate::getters::is_known_type;
+use crate::util::{get_amplify_crate, type_uses_generics, where_clause_with};
+
+const NAME: &str = "debug";
+const EXAMPLE: &str = r#"#[debug(hex, max = 16)]"#;
+
+/// Names of the byte array types from `amplify` crate, which are formatted
+/// as hex without explicit `#[debug(hex)]` attribute
+const BYTE_ARRAYS: [&str; 3] = ["Slice20", "Slice32", "Slice64"];
+
+/// Way the field is formatted
+#[derive(Clone)]
+enum FieldFormat {
+    /// Standard `Debug` formatting
+    Debug,
+    /// Lowercase hex string, optionally truncated to `max` bytes
+    Hex { max: Option<LitInt> },
+    /// Field value is replaced with `..`
+    Skip,
+}
+
+impl FieldFormat {
+    fn with(field: &Field) -> Result<Self> {
+        let mut hex = None;
+        let mut max = None;
+        let mut skip = None;
+        for attr in field.attrs.iter().filter(|attr| attr.path.is_ident(NAME)) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                _ => return Err(attr_err!(attr, "arguments must be given as a list")),
+            };
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("hex") => {
+                        if hex.is_some() {
+                            return Err(attr_err!(path, "`hex` argument is repeated"));
+                        }
+                        hex = Some(path.clone());
+                    }
+                    NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("skip") => {
+                        if skip.is_some() {
+                            return Err(attr_err!(path, "`skip` argument is repeated"));
+                        }
+                        skip = Some(path.clone());
+                    }
+                    NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                        ref path,
+                        lit: Lit::Int(ref lit),
+                        ..
+                    })) if path.is_ident("max") => {
+                        if max.is_some() {
+                            return Err(attr_err!(path, "`max` argument is repeated"));
+                        }
+                        lit.base10_parse::<usize>()?;
+                        max = Some(lit.clone());
+                    }
+                    other => {
+                        return Err(attr_err!(
+                            other,
+                            "unknown argument; supported arguments are `hex`, `max = <bytes>` \
+                             and `skip`"
+                        ))
+                    }
+                }
+            }
+        }
+
+        match (skip, hex, max) {
+            (Some(path), Some(_), _) | (Some(path), _, Some(_)) => {
+                Err(attr_err!(path, "skipped field can't be formatted as hex"))
+            }
+            (Some(_), None, None) => Ok(FieldFormat::Skip),
+            (None, Some(_), max) => Ok(FieldFormat::Hex { max }),
+            (None, None, max) if is_byte_container(&field.ty) => Ok(FieldFormat::Hex { max }),
+            (None, None, Some(max)) => Err(attr_err!(
+                max,
+                "`max` can be used only for fields formatted as hex; mark the field with \
+                 `#[debug(hex)]` if its type is a byte container"
+            )),
+            (None, None, None) => Ok(FieldFormat::Debug),
+        }
+    }
+}
+
+/// Detects byte containers formatted as hex by default: `Vec<u8>`, `[u8; N]`,
+/// `Box<[u8]>` and byte arrays from `amplify` crate, like `Slice32`
+fn is_byte_container(ty: &Type) -> bool {
+    let is_u8 = |ty: &Type| match ty {
+        Type::Path(path) => path.qself.is_none() && path.path.is_ident("u8"),
+        _ => false,
+    };
+    match ty {
+        Type::Array(TypeArray { elem, .. }) => is_u8(elem),
+        Type::Path(path) if path.qself.is_none() => {
+            let is_byte_array = path
+                .path
+                .segments
+                .last()
+                .map(|segment| {
+                    segment.arguments.is_empty()
+                        && BYTE_ARRAYS.contains(&segment.ident.to_string().as_str())
+                })
+                .unwrap_or_default();
+            let is_vec = match is_known_type(ty, "Vec", &["vec"]) {
+                Some(Some(ref item)) => is_u8(item),
+                _ => false,
+            };
+            let is_box = match is_known_type(ty, "Box", &["boxed"]) {
+                Some(Some(Type::Slice(TypeSlice { ref elem, .. }))) => is_u8(elem),
+                _ => false,
+            };
+            is_byte_array || is_vec || is_box
+        }
+        _ => false,
+    }
+}
+
+/// Generates `Debug` implementation for fields accessed either via `self` (for
+/// structs) or via pattern bindings (for enum variants), collecting trait
+/// bounds for the field types
+fn fields_fmt<'a>(
+    name: &Ident,
+    fields: &'a Fields,
+    access: impl Fn(usize, &Field) -> TokenStream2,
+    amplify_crate: &Path,
+    bounds: &mut Vec<(&'a Type, TokenStream2)>,
+) -> Result<TokenStream2> {
+    let name_str = name.to_string();
+    let mut calls = Vec::with_capacity(fields.len());
+    for (index, field) in fields.iter().enumerate() {
+        let ty = &field.ty;
+        let value = access(index, field);
+        let value = match FieldFormat::with(field)? {
+            FieldFormat::Debug => {
+                bounds.push((ty, quote! { ::core::fmt::Debug }));
+                quote_spanned! { ty.span() => #value }
+            }
+            FieldFormat::Hex { max } => {
+                bounds.push((ty, quote! { ::core::convert::AsRef<[u8]> }));
+                let truncate = max.map(|max| quote! { .truncated(#max) });
+                quote_spanned! { ty.span() =>
+                    &format_args!(
+                        "{}",
+                        #amplify_crate::hex::DisplayHex::new(
+                            ::core::convert::AsRef::<[u8]>::as_ref(#value)
+                        ) #truncate
+                    )
+                }
+            }
+            FieldFormat::Skip => quote! { &format_args!("..") },
+        };
+        calls.push(match field.ident {
+            Some(ref ident) => {
+                let ident_str = ident.unraw().to_string();
+                quote! { .field(#ident_str, #value) }
+            }
+            None => quote! { .field(#value) },
+        });
+    }
+    Ok(match fields {
+        Fields::Named(_) => quote! { f.debug_struct(#name_str) #( #calls )* .finish() },
+        Fields::Unnamed(_) => quote! { f.debug_tuple(#name_str) #( #calls )* .finish() },
+        Fields::Unit => quote! { f.write_str(#name_str) },
+    })
+}
+
+/// Generates name for a variable binding the field in enum variant pattern
+fn binding(index: usize, field: &Field) -> Ident {
+    match field.ident {
+        Some(ref ident) => Ident::new(&format!("_{}", ident.unraw()), ident.span()),
+        None => Ident::new(&format!("_{}", index), Span::call_site()),
+    }
+}
+
+pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+    let ident_name = &input.ident;
+    let amplify_crate = get_amplify_crate(&input);
+    let mut bounds = vec![];
+
+    let body = match input.data {
+        Data::Struct(ref data) => fields_fmt(
+            ident_name,
+            &data.fields,
+            |index, field| {
+                let member = match field.ident {
+                    Some(ref ident) => Member::Named(ident.clone()),
+                    None => Member::Unnamed(Index::from(index)),
+                };
+                quote! { &self.#member }
+            },
+            &amplify_crate,
+            &mut bounds,
+        )?,
+        Data::Enum(ref data) => {
+            let mut arms = Vec::with_capacity(data.variants.len());
+            for variant in &data.variants {
+                let name = &variant.ident;
+                let bindings = variant
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .map(|(index, field)| binding(index, field))
+                    .collect::<Vec<_>>();
+                let pattern = match variant.fields {
+                    Fields::Named(ref fields) => {
+                        let idents = fields.named.iter().map(|field| &field.ident);
+                        quote! { Self::#name { #( #idents: #bindings ),* } }
+                    }
+                    Fields::Unnamed(_) => quote! { Self::#name( #( #bindings ),* ) },
+                    Fields::Unit => quote! { Self::#name },
+                };
+                let stream = fields_fmt(
+                    name,
+                    &variant.fields,
+                    |index, field| {
+                        let binding = binding(index, field);
+                        quote! { #binding }
+                    },
+                    &amplify_crate,
+                    &mut bounds,
+                )?;
+                arms.push(quote! { #pattern => #stream, });
+            }
+            quote! {
+                match self {
+                    #( #arms )*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                &input,
+                "Deriving `DebugHex` is not supported in unions",
+            ))
+        }
+    };
+
+    let predicates = bounds
+        .into_iter()
+        .filter(|(ty, _)| type_uses_generics(ty, &input.generics))
+        .map(|(ty, tr)| -> WherePredicate {
+            parse_quote! { #ty: #tr }
+        });
+    let where_clause = where_clause_with(&input.generics, predicates);
+
+    Ok(quote! {
+        impl #impl_generics ::core::fmt::Debug for #ident_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #body
+            }
+        }
+    })
+}
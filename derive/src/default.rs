@@ -0,0 +1,357 @@
+// Rust language amplification derive library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::parse::{ParseStream, Parser};
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Data, DataEnum, DataStruct, DeriveInput, Error, Fields, Ident, Result, Type,
+    WherePredicate,
+};
+
+use crate::util::{get_amplify_crate, type_generic_params, where_clause_with};
+
+/// Trait which implementation is derived: both `Default` and `DumbDefault`
+/// derives share the same attribute syntax and differ only in the attribute
+/// name and the method used to construct field values
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum DefaultTrait {
+    /// `core::default::Default`
+    Default,
+    /// `amplify::DumbDefault`
+    DumbDefault,
+}
+
+impl DefaultTrait {
+    fn attr_name(self) -> &'static str {
+        match self {
+            DefaultTrait::Default => "default",
+            DefaultTrait::DumbDefault => "dumb_default",
+        }
+    }
+
+    fn example(self) -> &'static str {
+        match self {
+            DefaultTrait::Default => {
+                "#[default] on a variant, #[default(Variant)] on an enum or #[default(expr)] on \
+                 a field"
+            }
+            DefaultTrait::DumbDefault => {
+                "#[dumb_default] on a variant, #[dumb_default(Variant)] on an enum, \
+                 #[dumb_default(expr)] or #[dumb_default(Default)] on a field"
+            }
+        }
+    }
+
+    fn trait_name(self) -> &'static str {
+        match self {
+            DefaultTrait::Default => "EnumDefault",
+            DefaultTrait::DumbDefault => "DumbDefault",
+        }
+    }
+}
+
+/// Value of a field, as given by the field-level attribute
+enum FieldValue {
+    /// Expression from `#[default = literal]` or `#[default(expr)]`
+    Expr(TokenStream2),
+    /// Field constructed with `Default::default()` instead of `DumbDefault`,
+    /// as requested by `#[dumb_default(Default)]`
+    Default,
+}
+
+struct DeriveDefault<'a> {
+    kind: DefaultTrait,
+    input: &'a DeriveInput,
+    trait_path: TokenStream2,
+    method: Ident,
+    /// Bounds on the type parameters used by the field types
+    predicates: Vec<WherePredicate>,
+}
+
+pub(crate) fn inner(input: DeriveInput, kind: DefaultTrait) -> Result<TokenStream2> {
+    let (trait_path, method) = match kind {
+        DefaultTrait::Default => (quote! { ::core::default::Default }, "default"),
+        DefaultTrait::DumbDefault => {
+            let amplify_crate = get_amplify_crate(&input);
+            (quote! { #amplify_crate::DumbDefault }, "dumb_default")
+        }
+    };
+    let mut derive = DeriveDefault {
+        kind,
+        input: &input,
+        trait_path,
+        method: Ident::new(method, input.ident.span()),
+        predicates: vec![],
+    };
+    derive.derive()
+}
+
+impl<'a> DeriveDefault<'a> {
+    fn derive(&mut self) -> Result<TokenStream2> {
+        let input = self.input;
+        let body = match input.data {
+            Data::Struct(ref data) => self.inner_struct(data)?,
+            Data::Enum(ref data) => self.inner_enum(data)?,
+            Data::Union(_) => {
+                return Err(Error::new_spanned(
+                    input,
+                    format!(
+                        "Deriving `{}` is not supported in unions",
+                        self.kind.trait_name()
+                    ),
+                ))
+            }
+        };
+
+        // Following `core` derive, each of the type parameters is required to
+        // implement `Default`, while `DumbDefault` bounds only parameters
+        // which are used in the constructed fields
+        if self.kind == DefaultTrait::Default {
+            for param in input.generics.type_params() {
+                let ident = &param.ident;
+                self.predicates
+                    .push(parse_quote! { #ident: ::core::default::Default });
+            }
+        }
+
+        let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+        let where_clause = where_clause_with(&input.generics, self.predicates.drain(..));
+        let ident_name = &input.ident;
+        let trait_path = &self.trait_path;
+        let method = &self.method;
+
+        Ok(quote! {
+            impl #impl_generics #trait_path for #ident_name #ty_generics #where_clause {
+                fn #method() -> Self {
+                    #body
+                }
+            }
+        })
+    }
+
+    fn is_attr(&self, attr: &Attribute) -> bool {
+        attr.path.is_ident(self.kind.attr_name())
+    }
+
+    fn attr_err(&self, attr: &Attribute, msg: &str) -> Error {
+        self.err(attr.tokens.span(), msg)
+    }
+
+    fn err(&self, span: Span, msg: &str) -> Error {
+        let name = self.kind.attr_name();
+        let example = self.kind.example();
+        attr_err!(span, name, msg, example)
+    }
+
+    /// Parses value of the field-level `#[default = literal]` or
+    /// `#[default(expr)]` attribute. The expression is taken as-is, so it may
+    /// contain any tokens (including macro calls), which are not parsable as
+    /// an expression without `syn/full`.
+    fn field_value(&self, attrs: &[Attribute]) -> Result<Option<FieldValue>> {
+        let mut value = None::<(&Attribute, FieldValue)>;
+        for attr in attrs.iter().filter(|attr| self.is_attr(attr)) {
+            let expr = (|input: ParseStream| {
+                let expr: TokenStream2 = if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    input.parse()?
+                } else {
+                    let content;
+                    parenthesized!(content in input);
+                    content.parse()?
+                };
+                if expr.is_empty() || !input.is_empty() {
+                    return Err(input.error("a single expression is expected"));
+                }
+                Ok(expr)
+            })
+            .parse2(attr.tokens.clone())
+            .map_err(|_| {
+                let msg = format!(
+                    "field value must be given as `#[{0} = literal]` or `#[{0}(expr)]`",
+                    self.kind.attr_name()
+                );
+                self.attr_err(attr, &msg)
+            })?;
+            if let Some((first, _)) = value {
+                let mut err = self.attr_err(attr, "field value can be given only once");
+                err.combine(Error::new(
+                    first.tokens.span(),
+                    "field value is first given here",
+                ));
+                return Err(err);
+            }
+            let expr = if self.kind == DefaultTrait::DumbDefault
+                && attr.tokens.to_string() == "(Default)"
+            {
+                FieldValue::Default
+            } else {
+                FieldValue::Expr(expr)
+            };
+            value = Some((attr, expr));
+        }
+        Ok(value.map(|(_, expr)| expr))
+    }
+
+    /// Adds bounds for all type parameters used by the field type
+    fn bound(&mut self, ty: &Type, trait_path: &TokenStream2) {
+        for param in type_generic_params(ty, &self.input.generics) {
+            self.predicates.push(parse_quote! { #param: #trait_path });
+        }
+    }
+
+    /// Generates constructor for the struct or enum variant fields, taking
+    /// either values given by the attributes or the trait method value for
+    /// each field
+    fn construct(&mut self, fields: &Fields) -> Result<TokenStream2> {
+        let mut values = Vec::with_capacity(fields.len());
+        for field in fields {
+            let ty = &field.ty;
+            let value = match self.field_value(&field.attrs)? {
+                Some(FieldValue::Expr(expr)) => expr,
+                Some(FieldValue::Default) => {
+                    self.bound(ty, &quote! { ::core::default::Default });
+                    quote_spanned! { ty.span() =>
+                        <#ty as ::core::default::Default>::default()
+                    }
+                }
+                None => {
+                    let trait_path = self.trait_path.clone();
+                    let method = &self.method;
+                    let value = quote_spanned! { ty.span() =>
+                        <#ty as #trait_path>::#method()
+                    };
+                    self.bound(ty, &trait_path);
+                    value
+                }
+            };
+            values.push(match field.ident {
+                Some(ref ident) => quote! { #ident: #value },
+                None => value,
+            });
+        }
+        Ok(match fields {
+            Fields::Named(_) => quote! { { #( #values ),* } },
+            Fields::Unnamed(_) => quote! { ( #( #values ),* ) },
+            Fields::Unit => quote! {},
+        })
+    }
+
+    fn inner_struct(&mut self, data: &DataStruct) -> Result<TokenStream2> {
+        if let Some(attr) = self.input.attrs.iter().find(|attr| self.is_attr(attr)) {
+            let msg = format!(
+                "structures do not support type-level attribute; use `#[{}(expr)]` on fields \
+                 instead",
+                self.kind.attr_name()
+            );
+            return Err(self.attr_err(attr, &msg));
+        }
+        let fields = self.construct(&data.fields)?;
+        Ok(quote! { Self #fields })
+    }
+
+    fn inner_enum(&mut self, data: &DataEnum) -> Result<TokenStream2> {
+        let input = self.input;
+
+        // Default variant as given by the type-level `#[default(Variant)]` or
+        // by variant-level `#[default]` attribute, together with the attribute
+        // itself
+        let mut default = None::<(&Ident, &Attribute)>;
+
+        for attr in input.attrs.iter().filter(|attr| self.is_attr(attr)) {
+            let name = attr.parse_args::<Ident>().map_err(|_| {
+                self.attr_err(attr, "type-level attribute must name the default variant")
+            })?;
+            let variant = data
+                .variants
+                .iter()
+                .find(|v| v.ident == name)
+                .ok_or_else(|| {
+                    let msg = format!("enum `{}` has no variant named `{}`", input.ident, name);
+                    self.err(name.span(), &msg)
+                })?;
+            default = self.select_default(default, &variant.ident, attr)?;
+        }
+
+        for v in &data.variants {
+            for attr in v.attrs.iter().filter(|attr| self.is_attr(attr)) {
+                if !attr.tokens.is_empty() {
+                    return Err(self.attr_err(attr, "variant-level attribute takes no arguments"));
+                }
+                default = self.select_default(default, &v.ident, attr)?;
+            }
+        }
+
+        let variant = match default {
+            Some((ident, _)) => ident,
+            None => {
+                let msg = format!(
+                    "deriving `{}` on enum `{}` requires one of its variants to be marked with \
+                     `#[{}]`",
+                    self.kind.trait_name(),
+                    input.ident,
+                    self.kind.attr_name()
+                );
+                return Err(self.err(input.ident.span(), &msg));
+            }
+        };
+
+        let mut body = None;
+        for v in &data.variants {
+            if &v.ident == variant {
+                let fields = self.construct(&v.fields)?;
+                body = Some(quote! { Self::#variant #fields });
+            } else if let Some(attr) = v
+                .fields
+                .iter()
+                .flat_map(|field| &field.attrs)
+                .find(|attr| self.is_attr(attr))
+            {
+                let msg = format!(
+                    "field values are applicable only to the default variant `{}`",
+                    variant
+                );
+                return Err(self.attr_err(attr, &msg));
+            }
+        }
+        Ok(body.expect("default variant is always present"))
+    }
+
+    /// Registers variant as the default one, failing if some other variant was
+    /// already selected
+    fn select_default<'b>(
+        &self,
+        default: Option<(&'b Ident, &'b Attribute)>,
+        variant: &'b Ident,
+        attr: &'b Attribute,
+    ) -> Result<Option<(&'b Ident, &'b Attribute)>> {
+        match default {
+            None => Ok(Some((variant, attr))),
+            Some((first, first_attr)) => {
+                let msg = format!(
+                    "only a single default variant is allowed, while both `{}` and `{}` are \
+                     marked as default",
+                    first, variant
+                );
+                let mut err = self.err(attr.path.span(), &msg);
+                err.combine(Error::new(
+                    first_attr.path.span(),
+                    "default variant is first marked here",
+                ));
+                Err(err)
+            }
+        }
+    }
+}
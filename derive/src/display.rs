@@ -14,13 +14,22 @@
 // along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
-use proc_macro2::{Span, TokenStream as TokenStream2};
+use std::ops::Range;
+
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::ToTokens;
+use syn::punctuated::Punctuated;
+use syn::ext::IdentExt;
 use syn::spanned::Spanned;
 use syn::{
-    Attribute, Data, DataEnum, DataStruct, DataUnion, DeriveInput, Error, Fields, Ident, Lit,
-    LitStr, Meta, MetaNameValue, NestedMeta, Path, Result, Index,
+    Attribute, Data, DataEnum, DataStruct, DataUnion, DeriveInput, Error, Field, Fields, Ident,
+    Lit, LitInt, LitStr, Meta, MetaNameValue, NestedMeta, Path, Result, Index, Member, Type,
+    TypeReference, WhereClause, WherePredicate,
 };
 
+use crate::getters::is_known_type;
+use crate::util::{get_amplify_crate, ident_or_raw, type_uses_generics, where_clause_with};
+
 const NAME: &str = "display";
 const EXAMPLE: &str = r#"#[display("format {} string" | Trait | Type::function)]"#;
 
@@ -37,6 +46,9 @@ enum FormattingTrait {
 }
 
 impl FormattingTrait {
+    const SUPPORTED: &'static str = "`Debug`, `Octal`, `Binary`, `Pointer`, `LowerHex`, \
+                                     `UpperHex`, `LowerExp`, `UpperExp`";
+
     pub fn from_path(path: &Path, span: Span) -> Result<Option<Self>> {
         path.segments.first().map_or(
             Err(attr_err!(
@@ -55,6 +67,22 @@ impl FormattingTrait {
                     "UpperHex" => Some(FormattingTrait::UpperHex),
                     "LowerExp" => Some(FormattingTrait::LowerExp),
                     "UpperExp" => Some(FormattingTrait::UpperExp),
+                    // Single uppercase identifier can't be a formatting function
+                    other
+                        if path.segments.len() == 1
+                            && other
+                                .chars()
+                                .next()
+                                .map(char::is_uppercase)
+                                .unwrap_or_default() =>
+                    {
+                        let msg = format!(
+                            "unknown formatting trait `{}`; supported traits are {}",
+                            other,
+                            Self::SUPPORTED
+                        );
+                        return Err(attr_err!(path.span(), NAME, msg, EXAMPLE));
+                    }
                     _ => None,
                 })
             },
@@ -79,36 +107,278 @@ impl FormattingTrait {
         quote! { #fmt }
     }
 
-    pub fn into_token_stream2(self, span: Span) -> TokenStream2 {
+    /// Detects whether the trait formats the inner field of the type (or the
+    /// enum variant) rather than the whole value. `Debug` always formats the
+    /// whole value, while the rest of the traits are forwarded to the inner
+    /// field if the one can be selected.
+    pub fn delegates_to_field(self) -> bool {
+        self != FormattingTrait::Debug
+    }
+
+    pub fn trait_path(self) -> TokenStream2 {
+        match self {
+            FormattingTrait::Debug => quote! { ::core::fmt::Debug },
+            FormattingTrait::Octal => quote! { ::core::fmt::Octal },
+            FormattingTrait::Binary => quote! { ::core::fmt::Binary },
+            FormattingTrait::Pointer => quote! { ::core::fmt::Pointer },
+            FormattingTrait::LowerHex => quote! { ::core::fmt::LowerHex },
+            FormattingTrait::UpperHex => quote! { ::core::fmt::UpperHex },
+            FormattingTrait::LowerExp => quote! { ::core::fmt::LowerExp },
+            FormattingTrait::UpperExp => quote! { ::core::fmt::UpperExp },
+        }
+    }
+
+    /// Detects formatting trait used by a format string placeholder from its
+    /// format spec (the part of the placeholder starting with `:`). Returns
+    /// `None` for the `Display` trait.
+    pub fn from_spec(spec: &str) -> Option<Self> {
+        if spec.ends_with('?') {
+            return Some(FormattingTrait::Debug);
+        }
+        match spec.chars().last() {
+            Some('o') => Some(FormattingTrait::Octal),
+            Some('b') => Some(FormattingTrait::Binary),
+            Some('p') => Some(FormattingTrait::Pointer),
+            Some('x') => Some(FormattingTrait::LowerHex),
+            Some('X') => Some(FormattingTrait::UpperHex),
+            Some('e') => Some(FormattingTrait::LowerExp),
+            Some('E') => Some(FormattingTrait::UpperExp),
+            _ => None,
+        }
+    }
+
+    pub fn into_token_stream2(self, target: TokenStream2, span: Span) -> TokenStream2 {
         match self {
             FormattingTrait::Debug => quote_spanned! { span =>
-                ::core::fmt::Debug::fmt(&self, f)
+                ::core::fmt::Debug::fmt(#target, f)
             },
             FormattingTrait::Octal => quote_spanned! { span =>
-                ::core::fmt::Octal::fmt(&self, f)
+                ::core::fmt::Octal::fmt(#target, f)
             },
             FormattingTrait::Binary => quote_spanned! { span =>
-                ::core::fmt::Binary::fmt(&self, f)
+                ::core::fmt::Binary::fmt(#target, f)
             },
             FormattingTrait::Pointer => quote_spanned! { span =>
-                ::core::fmt::Pointer::fmt(&self, f)
+                ::core::fmt::Pointer::fmt(#target, f)
             },
             FormattingTrait::LowerHex => quote_spanned! { span =>
-                ::core::fmt::LowerHex::fmt(&self, f)
+                ::core::fmt::LowerHex::fmt(#target, f)
             },
             FormattingTrait::UpperHex => quote_spanned! { span =>
-                ::core::fmt::UpperHex::fmt(&self, f)
+                ::core::fmt::UpperHex::fmt(#target, f)
             },
             FormattingTrait::LowerExp => quote_spanned! { span =>
-                ::core::fmt::LowerExp::fmt(&self, f)
+                ::core::fmt::LowerExp::fmt(#target, f)
             },
             FormattingTrait::UpperExp => quote_spanned! { span =>
-                ::core::fmt::UpperExp::fmt(&self, f)
+                ::core::fmt::UpperExp::fmt(#target, f)
             },
         }
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum CaseMode {
+    Lower,
+    Upper,
+    Snake,
+    Kebab,
+    ScreamingSnake,
+}
+
+impl CaseMode {
+    const SUPPORTED: &'static str =
+        "`lowercase`, `uppercase`, `snake_case`, `kebab_case`, `SCREAMING_SNAKE_CASE`";
+
+    pub fn from_path(path: &Path) -> Result<Option<Self>> {
+        let ident = match path.get_ident() {
+            Some(ident) => ident.to_string(),
+            None => return Ok(None),
+        };
+        Ok(Some(match ident.as_str() {
+            "lowercase" => CaseMode::Lower,
+            "uppercase" => CaseMode::Upper,
+            "snake_case" => CaseMode::Snake,
+            "kebab_case" => CaseMode::Kebab,
+            "SCREAMING_SNAKE_CASE" => CaseMode::ScreamingSnake,
+            other if other.to_lowercase().ends_with("case") => {
+                let msg = format!(
+                    "unknown case transformation `{}`; supported modes are {}",
+                    other,
+                    Self::SUPPORTED
+                );
+                return Err(attr_err!(path.span(), NAME, msg, EXAMPLE));
+            }
+            _ => return Ok(None),
+        }))
+    }
+
+    /// Splits `CamelCase` identifier into words, keeping acronyms together,
+    /// such that `TLSError` becomes `["TLS", "Error"]`
+    fn split_words(s: &str) -> Vec<String> {
+        let chars = s.chars().collect::<Vec<_>>();
+        let mut words = Vec::<String>::new();
+        let mut word = String::new();
+        for (pos, c) in chars.iter().enumerate() {
+            if *c == '_' {
+                if !word.is_empty() {
+                    words.push(word);
+                    word = String::new();
+                }
+                continue;
+            }
+            let boundary = pos > 0
+                && c.is_uppercase()
+                && (chars[pos - 1].is_lowercase()
+                    || chars[pos - 1].is_numeric()
+                    || (chars[pos - 1].is_uppercase()
+                        && chars
+                            .get(pos + 1)
+                            .map(|c| c.is_lowercase())
+                            .unwrap_or_default()));
+            if boundary && !word.is_empty() {
+                words.push(word);
+                word = String::new();
+            }
+            word.push(*c);
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+        words
+    }
+
+    pub fn apply(self, name: &str) -> String {
+        match self {
+            CaseMode::Lower => name.to_lowercase(),
+            CaseMode::Upper => name.to_uppercase(),
+            CaseMode::Snake => Self::split_words(name).join("_").to_lowercase(),
+            CaseMode::Kebab => Self::split_words(name).join("-").to_lowercase(),
+            CaseMode::ScreamingSnake => Self::split_words(name).join("_").to_uppercase(),
+        }
+    }
+}
+
+/// Decimal formatting options of integer fields, given with `grouped`,
+/// `separator = "..."`, `scale = N` and `unit = "..."` arguments of the
+/// `#[display]` attribute
+#[derive(Clone, Default)]
+struct NumFormat {
+    grouped: bool,
+    separator: Option<LitStr>,
+    scale: Option<LitInt>,
+    unit: Option<LitStr>,
+}
+
+impl NumFormat {
+    /// Separator used for grouping digits if none is given explicitly
+    const DEFAULT_SEPARATOR: &'static str = ",";
+
+    /// Detects whether the attribute argument is one of the decimal
+    /// formatting options
+    fn is_arg(nested: &NestedMeta) -> bool {
+        match nested {
+            NestedMeta::Meta(Meta::Path(path)) => path.is_ident("grouped"),
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, .. })) => {
+                path.is_ident("separator") || path.is_ident("scale") || path.is_ident("unit")
+            }
+            _ => false,
+        }
+    }
+
+    /// Collects decimal formatting options from `#[display]` attributes,
+    /// ignoring other arguments
+    pub fn from_attrs<'a>(attrs: impl IntoIterator<Item = &'a Attribute>) -> Result<Option<Self>> {
+        let mut res = None::<NumFormat>;
+        for attr in attrs {
+            if !attr.path.is_ident(NAME) || bound_lit(attr).is_some() {
+                continue;
+            }
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                _ => continue,
+            };
+            for nested in list.nested.iter().filter(|nested| Self::is_arg(nested)) {
+                let num = res.get_or_insert_with(NumFormat::default);
+                match nested {
+                    NestedMeta::Meta(Meta::Path(path)) => {
+                        if num.grouped {
+                            return Err(attr_err!(path, "`grouped` argument is repeated"));
+                        }
+                        num.grouped = true;
+                    }
+                    NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => {
+                        let name = path.get_ident().map(Ident::to_string).unwrap_or_default();
+                        let repeated = match name.as_str() {
+                            "separator" => num.separator.is_some(),
+                            "scale" => num.scale.is_some(),
+                            _ => num.unit.is_some(),
+                        };
+                        if repeated {
+                            let msg = format!("`{}` argument is repeated", name);
+                            return Err(attr_err!(path, msg));
+                        }
+                        match (name.as_str(), lit) {
+                            ("separator", Lit::Str(s)) => num.separator = Some(s.clone()),
+                            ("unit", Lit::Str(s)) => num.unit = Some(s.clone()),
+                            ("scale", Lit::Int(i)) => {
+                                i.base10_parse::<u8>()?;
+                                num.scale = Some(i.clone());
+                            }
+                            ("scale", _) => {
+                                return Err(attr_err!(
+                                    lit,
+                                    "`scale` must be an integer number of fractional digits"
+                                ))
+                            }
+                            _ => {
+                                let msg = format!("`{}` must be a string literal", name);
+                                return Err(attr_err!(lit, msg));
+                            }
+                        }
+                    }
+                    _ => unreachable!("non-decimal arguments are filtered out"),
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    /// Combines field-level options with the options inherited from the type
+    /// or enum variant; field-level values take precedence
+    fn or(self, parent: &NumFormat) -> NumFormat {
+        NumFormat {
+            grouped: self.grouped || parent.grouped,
+            separator: self.separator.or_else(|| parent.separator.clone()),
+            scale: self.scale.or_else(|| parent.scale.clone()),
+            unit: self.unit.or_else(|| parent.unit.clone()),
+        }
+    }
+
+    /// Generates `DisplayDecimal` adaptor for the provided field value
+    fn to_adaptor(&self, value: TokenStream2, amplify_crate: &Path, span: Span) -> TokenStream2 {
+        let grouped = if self.grouped || self.separator.is_some() {
+            let sep = self.separator.as_ref().map(LitStr::value);
+            let sep = sep.as_deref().unwrap_or(Self::DEFAULT_SEPARATOR);
+            Some(quote_spanned! { span => .grouped(#sep) })
+        } else {
+            None
+        };
+        let scaled = self
+            .scale
+            .as_ref()
+            .map(|scale| LitInt::new(scale.base10_digits(), span))
+            .map(|scale| quote_spanned! { span => .scaled(#scale) });
+        let unit = self
+            .unit
+            .as_ref()
+            .map(|unit| quote_spanned! { span => .with_unit(#unit) });
+        quote_spanned! { span =>
+            #amplify_crate::num::decimal::DisplayDecimal::new(&#value) #grouped #scaled #unit
+        }
+    }
+}
+
 #[derive(Clone)]
 enum Technique {
     FromTrait(FormattingTrait),
@@ -116,31 +386,82 @@ enum Technique {
     WithFormat(LitStr, Option<LitStr>),
     DocComments(String),
     Inner,
-    Lowercase(String),
-    Uppercase(String),
+    InnerDeref,
+    Case(CaseMode, String),
 }
 
 impl Technique {
     pub fn from_attrs<'a>(
         attrs: impl IntoIterator<Item = &'a Attribute> + Clone,
         span: Span,
+    ) -> Result<Option<Self>> {
+        Self::parse_attrs(attrs, None, span)
+    }
+
+    /// Parses enum variant-level attributes, completing variant-level
+    /// alternative format string with the primary format string from the
+    /// enum-level attribute
+    pub fn from_variant_attrs<'a>(
+        attrs: impl IntoIterator<Item = &'a Attribute> + Clone,
+        parent: Option<&Technique>,
+        span: Span,
+    ) -> Result<Option<Self>> {
+        Self::parse_attrs(attrs, parent, span)
+    }
+
+    fn alt_err(span: Span) -> Error {
+        attr_err!(
+            span,
+            "alternative formatting can be given only if the first argument is a format string"
+        )
+    }
+
+    fn parse_attrs<'a>(
+        attrs: impl IntoIterator<Item = &'a Attribute> + Clone,
+        parent: Option<&Technique>,
+        span: Span,
     ) -> Result<Option<Self>> {
         let mut res = match attrs
             .clone()
             .into_iter()
-            .find(|attr| attr.path.is_ident(NAME))
+            .find(|attr| attr.path.is_ident(NAME) && bound_lit(attr).is_none())
             .map(|attr| attr.parse_meta())
             .map_or(Ok(None), |r| r.map(Some))?
         {
             Some(Meta::List(list)) => {
-                if list.nested.len() > 2 {
+                // Decimal formatting options are parsed by `NumFormat`; given
+                // alone they format the inner field
+                let nested = list
+                    .nested
+                    .iter()
+                    .filter(|nested| !NumFormat::is_arg(nested))
+                    .collect::<Vec<_>>();
+                if nested.is_empty() && !list.nested.is_empty() {
+                    return Ok(Some(Technique::Inner));
+                }
+                if nested.len() > 2 {
                     return Err(attr_err!(span, "too many arguments"));
                 }
-                let mut iter = list.nested.iter();
+                let mut iter = nested.into_iter();
                 let mut res = match iter.next() {
                     Some(NestedMeta::Lit(Lit::Str(format))) => {
                         Some(Technique::WithFormat(format.clone(), None))
                     }
+                    Some(NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                        path,
+                        lit: Lit::Str(alt),
+                        ..
+                    }))) if path.is_ident("alt") => {
+                        if iter.len() > 0 {
+                            return Err(attr_err!(span, "excessive arguments"));
+                        }
+                        match parent {
+                            Some(Technique::WithFormat(fmt, _)) => {
+                                Some(Technique::WithFormat(fmt.clone(), Some(alt.clone())))
+                            }
+                            _ => return Err(Self::alt_err(span)),
+                        }
+                    }
                     Some(NestedMeta::Meta(Meta::Path(path)))
                         if path.is_ident("doc_comments") || path.is_ident("docs") =>
                     {
@@ -149,16 +470,21 @@ impl Technique {
                     Some(NestedMeta::Meta(Meta::Path(path))) if path.is_ident("inner") => {
                         Some(Technique::Inner)
                     }
-                    Some(NestedMeta::Meta(Meta::Path(path))) if path.is_ident("lowercase") => {
-                        Some(Technique::Lowercase(String::new()))
+                    Some(NestedMeta::Meta(Meta::Path(path))) if path.is_ident("inner_deref") => {
+                        Some(Technique::InnerDeref)
                     }
-                    Some(NestedMeta::Meta(Meta::Path(path))) if path.is_ident("uppercase") => {
-                        Some(Technique::Uppercase(String::new()))
+                    Some(NestedMeta::Meta(Meta::Path(path))) => {
+                        if let Some(case) = CaseMode::from_path(path)? {
+                            Some(Technique::Case(case, String::new()))
+                        } else {
+                            Some(
+                                FormattingTrait::from_path(path, list.span())?.map_or(
+                                    Technique::FromMethod(path.clone()),
+                                    Technique::FromTrait,
+                                ),
+                            )
+                        }
                     }
-                    Some(NestedMeta::Meta(Meta::Path(path))) => Some(
-                        FormattingTrait::from_path(path, list.span())?
-                            .map_or(Technique::FromMethod(path.clone()), Technique::FromTrait),
-                    ),
                     Some(_) => return Err(attr_err!(span, "argument must be a string literal")),
                     None => return Err(attr_err!(span, "argument is required")),
                 };
@@ -175,13 +501,7 @@ impl Technique {
                             Some(Technique::WithFormat(fmt, _)) => {
                                 Some(Technique::WithFormat(fmt, Some(alt.clone())))
                             }
-                            _ => {
-                                return Err(attr_err!(
-                                    span,
-                                    "alternative formatting can be given only if \
-                                 the first argument is a format string"
-                                ))
-                            }
+                            _ => return Err(Self::alt_err(span)),
                         }
                     }
                     None => res,
@@ -200,9 +520,6 @@ impl Technique {
         if let Some(r) = res.as_mut() {
             r.apply_docs(attrs)
         }
-        if let Some(r) = res.as_mut() {
-            r.fix_fmt()
-        };
 
         Ok(res)
     }
@@ -222,22 +539,21 @@ impl Technique {
                 }
             }
             Technique::DocComments(doc) => quote! { #doc },
-            Technique::Inner => {
+            Technique::Inner | Technique::InnerDeref => {
                 if alt {
                     quote! { "{_0:#}" }
                 } else {
                     quote! { "{_0}" }
                 }
             }
-            Technique::Lowercase(fields_fmt) => quote! { #fields_fmt },
-            Technique::Uppercase(fields_fmt) => quote! { #fields_fmt },
+            Technique::Case(_, fields_fmt) => quote! { #fields_fmt },
         }
     }
 
     #[allow(clippy::unnecessary_unwrap)]
     pub fn into_token_stream2(self, fields: &Fields, span: Span, alt: bool) -> TokenStream2 {
         match self {
-            Technique::FromTrait(fmt) => fmt.into_token_stream2(span),
+            Technique::FromTrait(fmt) => fmt.into_token_stream2(quote! { &self }, span),
             Technique::FromMethod(path) => quote_spanned! { span =>
                 f.write_str(& #path (self))
             },
@@ -254,7 +570,7 @@ impl Technique {
                 let format = quote_spanned! { span => #doc };
                 Self::impl_format(fields, &format, span)
             }
-            Technique::Inner => {
+            Technique::Inner | Technique::InnerDeref => {
                 let format = if alt {
                     quote_spanned! { span => "{_0:#}" }
                 } else {
@@ -262,11 +578,7 @@ impl Technique {
                 };
                 Self::impl_format(fields, &format, span)
             }
-            Technique::Lowercase(fields_fmt) => {
-                let format = quote_spanned! { span => #fields_fmt };
-                Self::impl_format(fields, &format, span)
-            }
-            Technique::Uppercase(fields_fmt) => {
+            Technique::Case(_, fields_fmt) => {
                 let format = quote_spanned! { span => #fields_fmt };
                 Self::impl_format(fields, &format, span)
             }
@@ -308,30 +620,53 @@ impl Technique {
         }
     }
 
+    /// Returns formatting trait, if the technique uses one, which is forwarded
+    /// to the inner field
+    fn delegated_trait(&self) -> Option<FormattingTrait> {
+        match self {
+            Technique::FromTrait(tr) if tr.delegates_to_field() => Some(*tr),
+            _ => None,
+        }
+    }
+
+    /// Replaces doc comment string with the first paragraph of the doc
+    /// comments from the provided attributes, joining its lines with spaces.
+    /// Doc attributes which are not string literals (like
+    /// `#[doc = include_str!(...)]`) can't be read and are skipped.
     fn apply_docs<'a>(&mut self, attrs: impl IntoIterator<Item = &'a Attribute> + Clone) {
         if let Technique::DocComments(ref mut doc) = self {
-            for attr in attrs.into_iter().filter(|attr| attr.path.is_ident("doc")) {
+            let mut lines = Vec::<String>::new();
+            'attrs: for attr in attrs.into_iter().filter(|attr| attr.path.is_ident("doc")) {
                 if let Ok(Meta::NameValue(MetaNameValue {
                     lit: Lit::Str(s), ..
                 })) = attr.parse_meta()
                 {
-                    let fragment = s.value().trim().replace("\\n", "\n");
-                    if fragment.is_empty() || fragment == "\n" {
-                        doc.push('\n');
-                    } else {
-                        doc.push_str(&fragment);
-                        doc.push(' ');
+                    for line in s.value().split('\n').map(str::trim) {
+                        match (line.is_empty(), lines.is_empty()) {
+                            (true, true) => continue,
+                            (true, false) => break 'attrs,
+                            (false, _) => lines.push(line.replace("\\n", "\n")),
+                        }
                     }
                 }
             }
-            *doc = doc.trim().replace(" \n", "\n");
+            *doc = lines.join(" ").replace(" \n", "\n").replace("\n ", "\n");
+        }
+    }
+
+    /// Uses the provided name as a doc comment string if no doc comments were
+    /// found
+    fn docs_or_name(&mut self, name: &str) {
+        if let Technique::DocComments(ref mut doc) = self {
+            if doc.is_empty() {
+                *doc = name.to_owned();
+            }
         }
     }
 
     fn apply_case(&mut self, type_str: &str, fields: &Fields) {
         let (type_str_cased, fields_fmt) = match self {
-            Technique::Lowercase(ref mut f) => (type_str.to_lowercase(), f),
-            Technique::Uppercase(ref mut f) => (type_str.to_uppercase(), f),
+            Technique::Case(case, ref mut f) => (case.apply(type_str), f),
             _ => unreachable!(),
         };
         *fields_fmt = match fields {
@@ -343,14 +678,14 @@ impl Technique {
                     .collect::<Vec<_>>();
                 let inner = idents
                     .iter()
-                    .map(|ident| format!("{}: {{{0}}}", ident.to_string()))
+                    .map(|ident| format!("{}: {{{0}}}", ident.unraw()))
                     .collect::<Vec<_>>()
                     .join(", ");
                 format!("{} {{{{ {} }}}}", type_str_cased, inner)
             }
             Fields::Unnamed(f) => {
                 let inner = (0..f.unnamed.len())
-                    .map(|i| format!("{{_{}}}", i.to_string()))
+                    .map(|i| format!("{{{}}}", i))
                     .collect::<Vec<_>>()
                     .join(", ");
                 format!("{}({})", type_str_cased, inner)
@@ -359,42 +694,911 @@ impl Technique {
         };
     }
 
-    fn fix_fmt(&mut self) {
-        fn fix(s: &str) -> String {
-            s.replace("{0", "{_0")
-                .replace("{1", "{_1")
-                .replace("{2", "{_2")
-                .replace("{3", "{_3")
-                .replace("{4", "{_4")
-                .replace("{5", "{_5")
-                .replace("{6", "{_6")
-                .replace("{7", "{_7")
-                .replace("{8", "{_8")
-                .replace("{9", "{_9")
-        }
-
-        if let Technique::WithFormat(fmt, x) = self {
-            *self = Technique::WithFormat(
-                LitStr::new(&fix(&fmt.value()), Span::call_site()),
-                x.clone(),
-            );
+    /// Returns format string used by the technique, if the technique is based
+    /// on format strings, together with the literal it was originally defined
+    /// with (if any), which is used for error reporting.
+    fn format_str(
+        &self,
+        fields: &Fields,
+        alt: bool,
+        span: Span,
+    ) -> Result<Option<(String, Option<&LitStr>)>> {
+        Ok(match self {
+            Technique::FromTrait(_) | Technique::FromMethod(_) => None,
+            Technique::WithFormat(_, Some(fmt_alt)) if alt => {
+                Some((fmt_alt.value(), Some(fmt_alt)))
+            }
+            Technique::WithFormat(fmt, _) => Some((fmt.value(), Some(fmt))),
+            Technique::DocComments(doc) => Some((doc.clone(), None)),
+            Technique::Case(_, fields_fmt) => Some((fields_fmt.clone(), None)),
+            Technique::Inner | Technique::InnerDeref => {
+                let arg = match inner_field(fields, span)? {
+                    Some(FieldRef::Named(ident)) => ident.unraw().to_string(),
+                    Some(FieldRef::Unnamed(index)) => index.to_string(),
+                    None => return Err(attr_err!(span, "display(inner) requires a field")),
+                };
+                Some((format!("{{{}{}}}", arg, if alt { ":#" } else { "" }), None))
+            }
+        })
+    }
+
+    /// Detects whether `display(inner)` delegates formatting to a smart
+    /// pointer or a reference, or `display(inner_deref)` is used, returning
+    /// the delegated field together with the type the field dereferences to.
+    /// Such fields are formatted with the `Display` implementation of the
+    /// pointee, which may be unsized (like `dyn Display` or `str`).
+    fn deref_target(&self, fields: &Fields, span: Span) -> Result<Option<(FieldRef, Type)>> {
+        let forced = match self {
+            Technique::Inner => false,
+            Technique::InnerDeref => true,
+            _ => return Ok(None),
+        };
+        let field = match inner_field(fields, span)? {
+            Some(field) => field,
+            None if forced => return Err(attr_err!(span, "display(inner_deref) requires a field")),
+            None => return Ok(None),
+        };
+        let ty = field
+            .to_type(fields)
+            .expect("inner field is always present in the fields");
+        Ok(match pointee(ty) {
+            Some(target) => Some((field, target)),
+            None if forced => Some((field, parse_quote! { <#ty as ::core::ops::Deref>::Target })),
+            None => None,
+        })
+    }
+
+    /// Resolves format string of the technique against the provided fields,
+    /// returning `None` for techniques which are not based on format strings.
+    /// Decimal formatting options `num` given for the type or enum variant
+    /// apply to the inner field.
+    fn resolve(
+        &self,
+        fields: &Fields,
+        num: Option<&NumFormat>,
+        alt: bool,
+        span: Span,
+    ) -> Result<Option<ResolvedFmt>> {
+        let nums = field_num_formats(fields, num, span)?;
+        self.format_str(fields, alt, span)?
+            .map(|(fmt, lit)| ResolvedFmt::with(&fmt, lit, fields, &nums, span))
+            .transpose()
+    }
+}
+
+/// Returns type referenced by a reference or a well-known smart pointer
+/// (`Box`, `Rc`, `Arc` or `Cow`)
+fn pointee(ty: &Type) -> Option<Type> {
+    if let Type::Reference(TypeReference { elem, .. }) = ty {
+        return Some(elem.as_ref().clone());
+    }
+    [
+        ("Box", "boxed"),
+        ("Rc", "rc"),
+        ("Arc", "sync"),
+        ("Cow", "borrow"),
+    ]
+    .iter()
+    .find_map(|(name, module)| is_known_type(ty, name, &[module]))
+    .and_then(|target| target)
+}
+
+/// Detects the field to which `display(inner)` delegates formatting: either
+/// the only field of the structure or enum variant, or a field explicitly
+/// marked with `#[display(delegate)]`.
+fn inner_field(fields: &Fields, span: Span) -> Result<Option<FieldRef>> {
+    match delegated_field(fields)? {
+        None if fields.len() > 1 => Err(attr_err!(
+            span,
+            "display(inner) on multiple fields requires one of the fields to be marked with \
+             `#[display(delegate)]`"
+        )),
+        field => Ok(field),
+    }
+}
+
+/// Collects decimal formatting options of the fields, combining options of the
+/// inner field with the options `parent` given for the type or enum variant
+fn field_num_formats(
+    fields: &Fields,
+    parent: Option<&NumFormat>,
+    span: Span,
+) -> Result<Vec<(FieldRef, NumFormat)>> {
+    let inner = match parent {
+        Some(_) => Some(delegated_field(fields)?.ok_or_else(|| {
+            attr_err!(
+                span,
+                "decimal formatting options on multiple fields require one of the fields to be \
+                 marked with `#[display(delegate)]`"
+            )
+        })?),
+        None => None,
+    };
+    let mut nums = vec![];
+    for (index, field) in fields.iter().enumerate() {
+        let field_ref = match field.ident {
+            Some(ref ident) => FieldRef::Named(ident.clone()),
+            None => FieldRef::Unnamed(index),
+        };
+        let num = NumFormat::from_attrs(&field.attrs)?;
+        let num = match (num, parent) {
+            (num, Some(parent)) if inner.as_ref() == Some(&field_ref) => {
+                Some(num.unwrap_or_default().or(parent))
+            }
+            (num, _) => num,
+        };
+        if let Some(num) = num {
+            nums.push((field_ref, num));
         }
-        if let Technique::WithFormat(x, Some(fmt)) = self {
-            *self = Technique::WithFormat(
-                x.clone(),
-                Some(LitStr::new(&fix(&fmt.value()), Span::call_site())),
-            );
+    }
+    Ok(nums)
+}
+
+/// Returns either the only field of the structure or enum variant, or a field
+/// explicitly marked with `#[display(delegate)]`. If there are multiple fields
+/// and none of them is marked returns `None`.
+fn delegated_field(fields: &Fields) -> Result<Option<FieldRef>> {
+    let field_ref = |index: usize, field: &Field| match field.ident {
+        Some(ref ident) => FieldRef::Named(ident.clone()),
+        None => FieldRef::Unnamed(index),
+    };
+
+    let is_delegate = |nested: &NestedMeta| match nested {
+        NestedMeta::Meta(Meta::Path(path)) => path.is_ident("delegate"),
+        _ => false,
+    };
+
+    let mut marked = None;
+    for (index, field) in fields.iter().enumerate() {
+        for attr in field.attrs.iter().filter(|attr| attr.path.is_ident(NAME)) {
+            let delegate = match attr.parse_meta()? {
+                Meta::List(list)
+                    if !list.nested.is_empty()
+                        && list.nested.iter().filter(|n| is_delegate(n)).count() <= 1
+                        && list
+                            .nested
+                            .iter()
+                            .all(|nested| is_delegate(nested) || NumFormat::is_arg(nested)) =>
+                {
+                    list.nested.iter().any(is_delegate)
+                }
+                _ => {
+                    return Err(attr_err!(
+                        attr,
+                        "only `delegate`, `grouped`, `separator`, `scale` and `unit` arguments \
+                         are allowed in `#[display]` attribute for fields"
+                    ))
+                }
+            };
+            if !delegate {
+                continue;
+            }
+            if marked.is_some() {
+                return Err(attr_err!(
+                    attr,
+                    "only a single field can be marked with `#[display(delegate)]`"
+                ));
+            }
+            marked = Some(field_ref(index, field));
+        }
+    }
+
+    match (marked, fields.iter().next()) {
+        (Some(field), _) => Ok(Some(field)),
+        (None, Some(field)) if fields.len() == 1 => Ok(Some(field_ref(0, field))),
+        (None, _) => Ok(None),
+    }
+}
+
+/// Format string with all placeholders referencing fields (or fields of the
+/// fields, like in `{inner.height}`) converted into named format arguments
+#[derive(Clone)]
+struct ResolvedFmt {
+    /// Format string with placeholders renamed into argument names
+    pub fmt: String,
+    /// Named arguments: argument name, root field, the path to the nested
+    /// field within the root field and decimal formatting options for root
+    /// fields formatted with `DisplayDecimal` adaptor
+    pub args: Vec<(Ident, FieldRef, Vec<Ident>, Option<NumFormat>)>,
+    /// Root fields formatted directly (not via their nested fields) together
+    /// with the formatting trait used for them (`None` for `Display`)
+    pub traits: Vec<(FieldRef, Option<FormattingTrait>)>,
+}
+
+/// Reference to a root field of a structure or enum variant
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum FieldRef {
+    Named(Ident),
+    Unnamed(usize),
+}
+
+impl FieldRef {
+    /// Generates member part of the field access expression
+    pub fn to_member(&self) -> Member {
+        match self {
+            FieldRef::Named(ident) => Member::Named(ident.clone()),
+            FieldRef::Unnamed(index) => Member::Unnamed(Index::from(*index)),
+        }
+    }
+
+    /// Returns type of the field
+    pub fn to_type<'a>(&self, fields: &'a Fields) -> Option<&'a Type> {
+        fields
+            .iter()
+            .enumerate()
+            .find(|(index, field)| match (self, &field.ident) {
+                (FieldRef::Named(name), Some(ident)) => name == ident,
+                (FieldRef::Unnamed(no), None) => no == index,
+                _ => false,
+            })
+            .map(|(_, field)| &field.ty)
+    }
+
+    /// Generates name for a variable binding the field in enum variant pattern
+    pub fn to_binding(&self) -> Ident {
+        match self {
+            FieldRef::Named(ident) => Ident::new(&format!("_{}", ident.unraw()), ident.span()),
+            FieldRef::Unnamed(index) => Ident::new(&format!("_{}", index), Span::call_site()),
+        }
+    }
+}
+
+impl ResolvedFmt {
+    /// Parses format string, resolving each of its placeholders (including
+    /// width and precision arguments of the format specs) against the fields.
+    /// Errors are reported at the placeholder location inside the string
+    /// literal `lit`, if the compiler supports it, and at the whole literal
+    /// (or `span`, if there is no literal) otherwise.
+    ///
+    /// Root fields having decimal formatting options in `nums`, as well as
+    /// fields formatted with `{field:grouped}` placeholders, are formatted
+    /// with `DisplayDecimal` adaptor.
+    pub fn with(
+        fmt: &str,
+        lit: Option<&LitStr>,
+        fields: &Fields,
+        nums: &[(FieldRef, NumFormat)],
+        span: Span,
+    ) -> Result<Self> {
+        let err_span = |range: Range<usize>| lit.map(|lit| lit_subspan(lit, range)).unwrap_or(span);
+        let arg_err = |range: Range<usize>, msg: String| {
+            Error::new(err_span(range), format!("Deriving `Display`: {}", msg))
+        };
+
+        let mut res = ResolvedFmt {
+            fmt: String::with_capacity(fmt.len()),
+            args: vec![],
+            traits: vec![],
+        };
+        let mut next_index = 0usize;
+        // Names of the arguments used by the placeholders, since arguments
+        // replaced with `DisplayDecimal` adaptors must be removed
+        let mut used = Vec::<Ident>::new();
+        let mut chars = fmt.char_indices().peekable();
+        while let Some((pos, c)) = chars.next() {
+            match c {
+                '{' if chars.peek().map(|(_, c)| *c) == Some('{') => {
+                    chars.next();
+                    res.fmt.push_str("{{");
+                }
+                '}' if chars.peek().map(|(_, c)| *c) == Some('}') => {
+                    chars.next();
+                    res.fmt.push_str("}}");
+                }
+                '{' => {
+                    let end = chars
+                        .by_ref()
+                        .find(|(_, c)| *c == '}')
+                        .map(|(end, _)| end)
+                        .ok_or_else(|| {
+                            Error::new(
+                                err_span(pos..fmt.len()),
+                                "format string has unterminated placeholder",
+                            )
+                        })?;
+                    let placeholder = &fmt[pos + 1..end];
+                    let (arg, spec) = match placeholder.find(':') {
+                        Some(colon) => (&placeholder[..colon], Some(&placeholder[colon + 1..])),
+                        None => (placeholder, None),
+                    };
+
+                    // Implicit positional placeholders `{}` take the fields
+                    // of tuple structures one by one
+                    let name = if arg.is_empty() {
+                        let index = next_index.to_string();
+                        next_index += 1;
+                        res.add_arg(&index, fields)
+                            .map_err(|(_, msg)| arg_err(pos..end + 1, msg))?
+                    } else {
+                        let offset = pos + 1;
+                        res.add_arg(arg, fields).map_err(|(range, msg)| {
+                            arg_err(offset + range.start..offset + range.end, msg)
+                        })?
+                    };
+
+                    let offset = pos + 1 + arg.len() + 1;
+                    let spec =
+                        spec.map(FormatSpec::parse)
+                            .transpose()
+                            .map_err(|(range, msg)| {
+                                arg_err(offset + range.start..offset + range.end, msg)
+                            })?;
+                    let trait_ = spec.as_ref().and_then(|spec| spec.trait_);
+                    let grouped = spec.as_ref().map(|spec| spec.grouped).unwrap_or_default();
+                    let name = match res.num_arg(&name, trait_, grouped, nums) {
+                        Some(name) => name,
+                        None if grouped => {
+                            return Err(arg_err(
+                                pos..end + 1,
+                                format!(
+                                    "`grouped` format spec can be applied only to fields, not \
+                                     to their nested fields like `{{{}}}`",
+                                    placeholder
+                                ),
+                            ))
+                        }
+                        None => name,
+                    };
+
+                    res.fmt.push('{');
+                    res.fmt.push_str(&name.unraw().to_string());
+                    used.push(name.clone());
+                    if let Some(spec) = spec {
+                        let rest =
+                            res.add_spec(&spec, fields, &mut used)
+                                .map_err(|(range, msg)| {
+                                    arg_err(offset + range.start..offset + range.end, msg)
+                                })?;
+                        if !rest.is_empty() {
+                            res.fmt.push(':');
+                            res.fmt.push_str(&rest);
+                        }
+                    }
+                    res.add_trait(&name, trait_);
+                    res.fmt.push('}');
+                }
+                '}' => {
+                    return Err(Error::new(
+                        err_span(pos..pos + 1),
+                        "format string contains unmatched `}`; use `}}` to escape it",
+                    ))
+                }
+                c => res.fmt.push(c),
+            }
+        }
+        res.args.retain(|(name, ..)| used.contains(name));
+        Ok(res)
+    }
+
+    /// Resolves placeholder argument against the fields, returning the name
+    /// of the format argument. Field names may be given with or without the
+    /// raw identifier prefix, i.e. both `{r#type}` and `{type}` reference
+    /// `r#type` field. Errors contain byte range of the failed part of the
+    /// argument.
+    fn add_arg(
+        &mut self,
+        arg: &str,
+        fields: &Fields,
+    ) -> ::std::result::Result<Ident, (Range<usize>, String)> {
+        let mut path = arg.split('.');
+        let root = path.next().unwrap_or_default();
+        let mut offset = root.len() + 1;
+        let tail = path
+            .map(|segment| {
+                let range = offset..offset + segment.len();
+                offset += segment.len() + 1;
+                // Validating manually, since parsing with `syn` reports errors
+                // on invalid literals like `0x` on its own
+                let name = segment.strip_prefix("r#").unwrap_or(segment);
+                let mut chars = name.chars();
+                match chars.next() {
+                    Some(c)
+                        if (c.is_alphabetic() || c == '_')
+                            && chars.all(|c| c.is_alphanumeric() || c == '_') =>
+                    {
+                        ident_or_raw(name, Span::call_site()).ok_or((
+                            range,
+                            format!("`{}` is not a valid field name in `{{{}}}`", segment, arg),
+                        ))
+                    }
+                    _ => Err((
+                        range,
+                        format!("`{}` is not a valid field name in `{{{}}}`", segment, arg),
+                    )),
+                }
+            })
+            .collect::<::std::result::Result<Vec<_>, _>>()?;
+
+        let index = root
+            .parse::<usize>()
+            .ok()
+            .or_else(|| root.strip_prefix('_').and_then(|i| i.parse().ok()));
+        let root_name = root.strip_prefix("r#").unwrap_or(root);
+        let named = fields
+            .iter()
+            .filter_map(|f| f.ident.as_ref())
+            .find(|ident| ident.unraw() == root_name);
+        let (member, name) = match (fields, index, named) {
+            (Fields::Unnamed(f), Some(index), _) if index < f.unnamed.len() => {
+                (FieldRef::Unnamed(index), format!("_{}", index))
+            }
+            (Fields::Named(_), None, Some(ident)) => {
+                let mut ident = ident.clone();
+                ident.set_span(Span::call_site());
+                (FieldRef::Named(ident), root_name.to_owned())
+            }
+            _ => {
+                return Err((
+                    0..root.len(),
+                    format!(
+                        "unknown field `{}` in format string; {}",
+                        root,
+                        available_fields(fields)
+                    ),
+                ))
+            }
+        };
+
+        let name = tail.iter().fold(name, |name, segment| {
+            format!("{}__{}", name, segment.unraw())
+        });
+        let name = ident_or_raw(&name, Span::call_site())
+            .expect("argument name is composed of valid identifiers");
+        if !self.args.iter().any(|(n, ..)| n == &name) {
+            self.args.push((name.clone(), member, tail, None));
+        }
+        Ok(name)
+    }
+
+    /// Replaces argument referencing a root field formatted with `Display`
+    /// trait by an argument formatting the field with `DisplayDecimal`
+    /// adaptor, if the field has decimal formatting options or the
+    /// placeholder is `grouped`. Returns `None` if the argument references a
+    /// nested field or the field is formatted with other trait, and the name
+    /// of the original argument if the field does not need the adaptor.
+    fn num_arg(
+        &mut self,
+        name: &Ident,
+        tr: Option<FormattingTrait>,
+        grouped: bool,
+        nums: &[(FieldRef, NumFormat)],
+    ) -> Option<Ident> {
+        let field = match self.args.iter().find(|(n, ..)| n == name) {
+            Some((_, field, path, _)) if path.is_empty() && tr.is_none() => field.clone(),
+            _ if grouped => return None,
+            _ => return Some(name.clone()),
+        };
+        let num = nums
+            .iter()
+            .find(|(f, _)| f == &field)
+            .map(|(_, num)| num.clone());
+        let num = match (num, grouped) {
+            (Some(mut num), true) => {
+                num.grouped = true;
+                num
+            }
+            (None, true) => NumFormat {
+                grouped: true,
+                ..NumFormat::default()
+            },
+            (Some(num), false) => num,
+            (None, false) => return Some(name.clone()),
+        };
+        let suffix = if grouped { "grouped" } else { "decimal" };
+        let num_name = Ident::new(&format!("{}__{}", name.unraw(), suffix), Span::call_site());
+        if !self.args.iter().any(|(n, ..)| n == &num_name) {
+            self.args.push((num_name.clone(), field, vec![], Some(num)));
         }
-        if let Technique::DocComments(fmt) = self {
-            *self = Technique::DocComments(fix(fmt))
+        Some(num_name)
+    }
+
+    /// Resolves width and precision arguments of the format spec against the
+    /// fields, returning the spec with the arguments renamed
+    fn add_spec(
+        &mut self,
+        spec: &FormatSpec,
+        fields: &Fields,
+        used: &mut Vec<Ident>,
+    ) -> ::std::result::Result<String, (Range<usize>, String)> {
+        let mut res = String::with_capacity(spec.spec.len());
+        let mut pos = 0;
+        for range in &spec.counts {
+            let name = self
+                .add_arg(&spec.spec[range.clone()], fields)
+                .map_err(|(r, msg)| (range.start + r.start..range.start + r.end, msg))?;
+            used.push(name.clone());
+            res.push_str(&spec.spec[pos..range.start]);
+            res.push_str(&name.unraw().to_string());
+            pos = range.end;
         }
+        res.push_str(&spec.spec[pos..]);
+        Ok(res)
+    }
+
+    fn add_trait(&mut self, name: &Ident, tr: Option<FormattingTrait>) {
+        let field = match self.args.iter().find(|(n, ..)| n == name) {
+            Some((_, field, path, _)) if path.is_empty() => field.clone(),
+            _ => return,
+        };
+        if !self.traits.contains(&(field.clone(), tr)) {
+            self.traits.push((field, tr));
+        }
+    }
+
+    /// Returns types of the fields formatted directly by the format string,
+    /// each accompanied with the formatting trait required from the type
+    pub fn bounds(&self, fields: &Fields) -> Vec<(Type, TokenStream2)> {
+        self.traits
+            .iter()
+            .filter_map(|(field, tr)| {
+                let path = tr.map(FormattingTrait::trait_path).unwrap_or_else(|| {
+                    quote! { ::core::fmt::Display }
+                });
+                field.to_type(fields).map(|ty| (ty.clone(), path))
+            })
+            .collect()
+    }
+
+    /// Returns unique set of the root fields referenced by the format string
+    pub fn field_refs(&self) -> Vec<&FieldRef> {
+        let mut refs = Vec::<&FieldRef>::with_capacity(self.args.len());
+        for (_, field, ..) in &self.args {
+            if !refs.contains(&field) {
+                refs.push(field)
+            }
+        }
+        refs
+    }
+
+    /// Generates `write!` call for a structure, accessing fields via `self`
+    pub fn to_struct_write(&self, amplify_crate: &Path, span: Span) -> TokenStream2 {
+        let fmt = &self.fmt;
+        let args = self.args.iter().map(|(name, field, path, num)| {
+            let member = field.to_member();
+            let value = quote_spanned! { span => self.#member #( .#path )* };
+            let value = match num {
+                Some(num) => num.to_adaptor(value, amplify_crate, span),
+                None => value,
+            };
+            quote_spanned! { span => #name = #value }
+        });
+        quote_spanned! { span => write!(f, #fmt, #( #args, )*) }
+    }
+
+    /// Generates `write!` call for an enum variant, accessing fields via
+    /// bindings produced by [`ResolvedFmt::to_pattern`]
+    pub fn to_variant_write(&self, amplify_crate: &Path, span: Span) -> TokenStream2 {
+        let fmt = &self.fmt;
+        let args = self.args.iter().map(|(name, field, path, num)| {
+            let binding = field.to_binding();
+            let value = quote_spanned! { span => #binding #( .#path )* };
+            let value = match num {
+                Some(num) => num.to_adaptor(value, amplify_crate, span),
+                None => value,
+            };
+            quote_spanned! { span => #name = #value }
+        });
+        quote_spanned! { span => write!(f, #fmt, #( #args, )*) }
+    }
+
+    /// Generates enum variant pattern binding all fields used in the format
+    /// string
+    pub fn to_pattern(&self, variant: &Ident, fields: &Fields, span: Span) -> TokenStream2 {
+        variant_pattern(variant, fields, &self.field_refs(), span)
     }
 }
 
-fn has_formatters(ident: impl ToString, s: &str) -> bool {
-    let m1 = format!("{}{}:", '{', ident.to_string());
-    let m2 = format!("{}{}{}", '{', ident.to_string(), '}');
-    s.contains(&m1) || s.contains(&m2)
+/// Format spec of a placeholder (the part following `:`), parsed according to
+/// the `std::fmt` grammar:
+/// `[[fill]align][sign]['#']['0'][width]['.' precision][type]`
+struct FormatSpec<'a> {
+    /// Original spec string
+    pub spec: &'a str,
+    /// Byte ranges of the width and precision arguments given in `arg$` form
+    pub counts: Vec<Range<usize>>,
+    /// Formatting trait required by the spec (`None` for `Display`)
+    pub trait_: Option<FormattingTrait>,
+    /// Whether the spec has `grouped` type, which is not a part of `std::fmt`
+    /// grammar and is removed from the `spec`
+    pub grouped: bool,
+}
+
+impl<'a> FormatSpec<'a> {
+    /// Parses format spec, returning byte range of the invalid part of the
+    /// spec and error message in case of failure
+    pub fn parse(spec: &'a str) -> ::std::result::Result<Self, (Range<usize>, String)> {
+        let chars = spec.char_indices().collect::<Vec<_>>();
+        let at = |i: usize| chars.get(i).map(|(_, c)| *c);
+        let pos = |i: usize| chars.get(i).map(|(pos, _)| *pos).unwrap_or(spec.len());
+        let is_align = |c: Option<char>| c == Some('<') || c == Some('^') || c == Some('>');
+
+        let mut counts = vec![];
+        // Parses count (either an integer or `arg$`), returning index of
+        // the first char after it
+        let mut count = |start: usize| -> Option<usize> {
+            let mut i = start;
+            while at(i)
+                .map(|c| c.is_alphanumeric() || c == '_')
+                .unwrap_or_default()
+            {
+                i += 1;
+            }
+            if i > start && at(i) == Some('$') {
+                counts.push(pos(start)..pos(i));
+                return Some(i + 1);
+            }
+            i = start;
+            while at(i).map(|c| c.is_ascii_digit()).unwrap_or_default() {
+                i += 1;
+            }
+            if i > start {
+                Some(i)
+            } else {
+                None
+            }
+        };
+
+        let mut i = if is_align(at(1)) {
+            2
+        } else if is_align(at(0)) {
+            1
+        } else {
+            0
+        };
+        if at(i) == Some('+') || at(i) == Some('-') {
+            i += 1;
+        }
+        if at(i) == Some('#') {
+            i += 1;
+        }
+        if at(i) == Some('0') && at(i + 1) != Some('$') {
+            i += 1;
+        }
+        i = count(i).unwrap_or(i);
+        if at(i) == Some('.') {
+            if at(i + 1) == Some('*') {
+                return Err((
+                    pos(i)..pos(i + 2),
+                    "`.*` precision is not supported; use `.field$` form instead".to_owned(),
+                ));
+            }
+            i = count(i + 1).ok_or_else(|| {
+                (
+                    pos(i)..pos(i + 1),
+                    "format spec is missing precision".to_owned(),
+                )
+            })?;
+        }
+
+        let ty = &spec[pos(i)..];
+        let trait_ = FormattingTrait::from_spec(ty);
+        match (ty, trait_) {
+            ("grouped", _) => {
+                return Ok(FormatSpec {
+                    spec: &spec[..pos(i)],
+                    counts,
+                    trait_: None,
+                    grouped: true,
+                })
+            }
+            ("", _) | ("?", _) | ("x?", _) | ("X?", _) => {}
+            (_, Some(_)) if ty.len() == 1 => {}
+            _ => {
+                return Err((
+                    pos(i)..spec.len(),
+                    format!(
+                        "invalid format spec `{}`; expected \
+                         `[[fill]align][sign]['#']['0'][width]['.' precision][type]` with type \
+                         being one of `?`, `x?`, `X?`, `o`, `x`, `X`, `p`, `b`, `e`, `E` or \
+                         `grouped`",
+                        spec
+                    ),
+                ))
+            }
+        }
+
+        Ok(FormatSpec {
+            spec,
+            counts,
+            trait_,
+            grouped: false,
+        })
+    }
+}
+
+/// Describes fields which may be referenced from a format string
+fn available_fields(fields: &Fields) -> String {
+    let names = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match field.ident {
+            Some(ref ident) => format!("`{}`", ident),
+            None => format!("`{}`", index),
+        })
+        .collect::<Vec<_>>();
+    match names.len() {
+        0 => "there are no fields to reference".to_owned(),
+        1 => format!("the only available field is {}", names[0]),
+        _ => format!("available fields are {}", names.join(", ")),
+    }
+}
+
+/// Returns span of the part of the string literal value given by its byte
+/// range. Falls back to the span of the whole literal if the literal contains
+/// escape sequences or the compiler does not support literal sub-spans.
+fn lit_subspan(lit: &LitStr, range: Range<usize>) -> Span {
+    let token = match lit.to_token_stream().into_iter().next() {
+        Some(TokenTree::Literal(token)) => token,
+        _ => return lit.span(),
+    };
+    let repr = token.to_string();
+    let (start, end) = match (repr.find('"'), repr.rfind('"')) {
+        (Some(start), Some(end)) if start < end => (start + 1, end),
+        _ => return lit.span(),
+    };
+    if repr[start..end] != lit.value() {
+        return lit.span();
+    }
+    token
+        .subspan(start + range.start..start + range.end)
+        .unwrap_or_else(|| lit.span())
+}
+
+/// Generates enum variant pattern binding the provided fields with names
+/// given by [`FieldRef::to_binding`]
+fn variant_pattern(
+    variant: &Ident,
+    fields: &Fields,
+    refs: &[&FieldRef],
+    span: Span,
+) -> TokenStream2 {
+    match fields {
+        Fields::Named(_) => {
+            let bindings = refs.iter().map(|field| {
+                let member = field.to_member();
+                let binding = field.to_binding();
+                quote_spanned! { span => #member: #binding }
+            });
+            quote_spanned! { span => Self::#variant { #( #bindings, )* .. } }
+        }
+        Fields::Unnamed(_) => {
+            let max = refs
+                .iter()
+                .filter_map(|field| match field {
+                    FieldRef::Unnamed(index) => Some(index + 1),
+                    FieldRef::Named(_) => None,
+                })
+                .max()
+                .unwrap_or_default();
+            let bindings = (0..max).map(|index| {
+                let field = FieldRef::Unnamed(index);
+                if refs.contains(&&field) {
+                    let binding = field.to_binding();
+                    quote_spanned! { span => #binding }
+                } else {
+                    quote_spanned! { span => _ }
+                }
+            });
+            quote_spanned! { span => Self::#variant ( #( #bindings, )* .. ) }
+        }
+        Fields::Unit => quote_spanned! { span => Self::#variant },
+    }
+}
+
+/// Computes strings produced by the derived `Display` implementation for each
+/// of the enum variants. All variants must be fieldless; otherwise the output
+/// depends on the variant data and can't be known at compile time.
+pub(crate) fn unit_variant_strings(input: &DeriveInput, data: &DataEnum) -> Result<Vec<String>> {
+    let global = Technique::from_attrs(&input.attrs, input.span())?;
+    let mut strings = Vec::with_capacity(data.variants.len());
+    for v in &data.variants {
+        let type_str = v.ident.unraw().to_string();
+        let local = Technique::from_variant_attrs(&v.attrs, global.as_ref(), v.span())?;
+        let is_local = local.is_some();
+        let s = match local.or_else(|| global.clone()) {
+            Some(Technique::WithFormat(_, _)) if !is_local => {
+                return Err(attr_err!(
+                    input.span(),
+                    "enum-level format string produces the same string for all variants"
+                ))
+            }
+            Some(Technique::WithFormat(fmt, _)) => fmt.value(),
+            Some(mut technique @ Technique::DocComments(_)) => {
+                technique.apply_docs(&v.attrs);
+                technique.docs_or_name(&type_str);
+                match technique {
+                    Technique::DocComments(doc) => doc,
+                    _ => unreachable!(),
+                }
+            }
+            Some(Technique::Case(case, _)) => case.apply(&type_str),
+            Some(Technique::Inner) | Some(Technique::InnerDeref) | None => type_str,
+            Some(Technique::FromTrait(_)) | Some(Technique::FromMethod(_)) => {
+                return Err(attr_err!(
+                    v.span(),
+                    "string representation produced by a formatting trait or function is \
+                     not known at compile time"
+                ))
+            }
+        };
+        strings.push(s);
+    }
+    Ok(strings)
+}
+
+/// Detects `#[error(transparent)]` attribute used by `Error` derive macro
+fn is_error_transparent(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path.is_ident("error")
+            && attr
+                .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .map(|args| {
+                    args.iter().any(|arg| match arg {
+                        Meta::Path(path) => path.is_ident("transparent"),
+                        _ => false,
+                    })
+                })
+                .unwrap_or_default()
+    })
+}
+
+/// Returns string with the where predicates if the attribute has form of
+/// `#[display(bound = "...")]`
+fn bound_lit(attr: &Attribute) -> Option<LitStr> {
+    if !attr.path.is_ident(NAME) {
+        return None;
+    }
+    match attr.parse_meta() {
+        Ok(Meta::List(list)) if list.nested.len() == 1 => match list.nested.first() {
+            Some(NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(bound),
+                ..
+            }))) if path.is_ident("bound") => Some(bound.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Parses where predicates given with `#[display(bound = "...")]` attribute,
+/// which replace the trait bounds inferred by the derive macro
+fn bound_attr<'a>(
+    attrs: impl IntoIterator<Item = &'a Attribute>,
+) -> Result<Option<Vec<WherePredicate>>> {
+    let mut res = None;
+    for attr in attrs {
+        let bound = match bound_lit(attr) {
+            Some(bound) => bound,
+            None => continue,
+        };
+        if res.is_some() {
+            return Err(attr_err!(attr, "trait bounds can be given only once"));
+        }
+        let predicates =
+            bound.parse_with(Punctuated::<WherePredicate, Token![,]>::parse_terminated)?;
+        res = Some(predicates.into_iter().collect());
+    }
+    Ok(res)
+}
+
+/// Generates where clause for the `Display` implementation. Types of the
+/// formatted fields which depend on the generic type parameters are bounded
+/// with the formatting traits used for them, unless the bounds are explicitly
+/// given with `#[display(bound = "...")]`.
+fn where_clause(
+    input: &DeriveInput,
+    bounds: Vec<(Type, TokenStream2)>,
+) -> Result<Option<WhereClause>> {
+    let predicates = match bound_attr(&input.attrs)? {
+        Some(predicates) => predicates,
+        None => bounds
+            .into_iter()
+            .filter(|(ty, _)| type_uses_generics(ty, &input.generics))
+            .map(|(ty, tr)| parse_quote! { #ty: #tr })
+            .collect(),
+    };
+    Ok(where_clause_with(&input.generics, predicates))
 }
 
 pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
@@ -406,10 +1610,14 @@ pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
 }
 
 fn inner_struct(input: &DeriveInput, data: &DataStruct) -> Result<TokenStream2> {
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
     let ident_name = &input.ident;
+    let self_ty: Type = parse_quote! { #ident_name #ty_generics };
+    let amplify_crate = get_amplify_crate(input);
+    let mut bounds = vec![];
 
-    let mut technique = Technique::from_attrs(&input.attrs, input.span())?.ok_or_else(|| {
+    let num = NumFormat::from_attrs(&input.attrs)?;
+    let technique = Technique::from_attrs(&input.attrs, input.span())?.ok_or_else(|| {
         Error::new(
             input.span(),
             format!(
@@ -418,113 +1626,112 @@ fn inner_struct(input: &DeriveInput, data: &DataStruct) -> Result<TokenStream2>
             ),
         )
     })?;
-    technique.apply_docs(&input.attrs);
-
-    let tokens_fmt = technique.to_fmt(false);
-    let tokens_alt = technique.to_fmt(true);
-    let str_fmt = tokens_fmt.to_string();
-    let str_alt = tokens_alt.to_string();
+    if let Technique::DocComments(ref doc) = technique {
+        if doc.is_empty() {
+            // Ancient rust versions do not known about `matches!` macro
+            #[allow(clippy::match_like_matches_macro)]
+            let unreadable = input
+                .attrs
+                .iter()
+                .filter(|attr| attr.path.is_ident("doc"))
+                .any(|attr| match attr.parse_meta() {
+                    Ok(Meta::NameValue(MetaNameValue {
+                        lit: Lit::Str(_), ..
+                    })) => false,
+                    Ok(Meta::List(_)) => false,
+                    _ => true,
+                });
+            let note = if unreadable {
+                "; note that doc attributes which are not string literals, like \
+                 `#[doc = include_str!(...)]`, can't be read by the derive macro and are skipped"
+            } else {
+                ""
+            };
+            let msg = format!(
+                "type must have doc comments to be displayed with them{}",
+                note
+            );
+            return Err(attr_err!(input.ident, msg));
+        }
+    }
 
-    let display = match (&data.fields, &technique) {
-        (_, Technique::FromTrait(_)) | (_, Technique::FromMethod(_)) => technique
-            .clone()
-            .into_token_stream2(&data.fields, input.span(), false),
-        (Fields::Named(fields), Technique::Inner) => {
-            if fields.named.len() != 1 {
-                return Err(attr_err!(
-                    fields.span(),
-                    "display(inner) requires only single field in the structure"
-                ));
-            }
-            let field = fields
-                .named
-                .first()
-                .expect("we just checked that there is a single field")
-                .ident
-                .as_ref()
-                .expect("named fields always have ident with the name");
-            quote_spanned! { field.span() =>
-                write!(f, #tokens_fmt, _0 = self.#field)
+    let span = data.fields.span();
+    let deref_target = technique.deref_target(&data.fields, input.span())?;
+    let display = match (
+        &data.fields,
+        technique.format_str(&data.fields, false, input.span())?,
+        technique.delegated_trait(),
+    ) {
+        _ if deref_target.is_some() => {
+            let (field, target) = deref_target.expect("presence is checked in the match guard");
+            bounds.push((target, quote! { ::core::fmt::Display }));
+            let member = field.to_member();
+            quote_spanned! { span =>
+                ::core::fmt::Display::fmt(&*self.#member, f)
             }
         }
-        (Fields::Named(fields), _) => {
-            let f = fields.named.iter().map(|f| f.ident.as_ref().unwrap());
-            let idents = f
-                .clone()
-                .filter(|ident| has_formatters(ident, &str_fmt))
-                .collect::<Vec<_>>();
-            if str_fmt == str_alt {
-                quote_spanned! { fields.span() =>
-                    write!(f, #tokens_fmt, #( #idents = self.#idents, )*)
+        (_, None, Some(tr)) => {
+            let target = match delegated_field(&data.fields)? {
+                Some(field) => {
+                    bounds.extend(
+                        field
+                            .to_type(&data.fields)
+                            .map(|ty| (ty.clone(), tr.trait_path())),
+                    );
+                    let member = field.to_member();
+                    quote_spanned! { span => &self.#member }
                 }
-            } else {
-                let idents_alt = f
-                    .filter(|ident| has_formatters(ident, &str_alt))
-                    .collect::<Vec<_>>();
-                if str_fmt != str_alt {
-                    quote_spanned! { fields.span() =>
-                        if !f.alternate() {
-                            write!(f, #tokens_fmt, #( #idents = self.#idents, )*)
-                        } else {
-                            write!(f, #tokens_alt, #( #idents_alt = self.#idents_alt, )*)
-                        }
-                    }
-                } else {
-                    quote_spanned! { fields.span() =>
-                        write!(f, #tokens_fmt, #( #idents = self.#idents, )*)
-                    }
-                }
-            }
+                None => quote_spanned! { span => &self },
+            };
+            tr.into_token_stream2(target, span)
         }
-        (Fields::Unnamed(fields), _) => {
-            let f = (0..fields.unnamed.len()).map(Index::from);
-            let idents = f
-                .clone()
-                .filter(|ident| has_formatters(format!("_{}", ident.index), &str_fmt));
-            let nums = idents
+        (_, None, None) => {
+            if let Technique::FromTrait(tr) = technique {
+                bounds.push((self_ty.clone(), tr.trait_path()));
+            }
+            technique
                 .clone()
-                .map(|ident| Ident::new(&format!("_{}", ident.index), fields.span()))
-                .collect::<Vec<_>>();
-            let idents = idents.collect::<Vec<_>>();
-            if str_fmt == str_alt {
-                quote_spanned! { fields.span() =>
-                    write!(f, #tokens_fmt, #( #nums = self.#idents, )*)
+                .into_token_stream2(&data.fields, input.span(), false)
+        }
+        (Fields::Unit, Some((fmt, _)), _) => {
+            let (alt, _) = technique
+                .format_str(&data.fields, true, input.span())?
+                .expect("format string is always present for both normal and alternative form");
+            if fmt == alt {
+                quote_spanned! { span =>
+                    f.write_str(#fmt)
                 }
             } else {
-                let idents_alt =
-                    f.filter(|ident| has_formatters(format!("_{}", ident.index), &str_alt));
-                let nums_alt = idents_alt
-                    .clone()
-                    .map(|ident| Ident::new(&format!("_{}", ident.index), fields.span()))
-                    .collect::<Vec<_>>();
-                let idents_alt = idents_alt.collect::<Vec<_>>();
-                if str_fmt != str_alt {
-                    quote_spanned! { fields.span() =>
-                        if !f.alternate() {
-                            write!(f, #tokens_fmt, #( #nums = self.#idents, )*)
-                        } else {
-                            write!(f, #tokens_alt, #( #nums_alt = self.#idents_alt, )*)
-                        }
-                    }
-                } else {
-                    quote_spanned! { fields.span() =>
-                        write!(f, #tokens_fmt, #( #nums = self.#idents, )*)
-                    }
+                quote_spanned! { span =>
+                    f.write_str(if !f.alternate() { #fmt } else { #alt })
                 }
             }
         }
-        (Fields::Unit, _) => {
-            if str_fmt == str_alt {
-                quote_spanned! { data.fields.span() =>
-                    f.write_str(#tokens_fmt)
-                }
+        (fields, Some(_), _) => {
+            let fmt = technique
+                .resolve(fields, num.as_ref(), false, input.span())?
+                .expect("format string presence is already checked");
+            let alt = technique
+                .resolve(fields, num.as_ref(), true, input.span())?
+                .expect("format string presence is already checked");
+            bounds.extend(fmt.bounds(fields));
+            bounds.extend(alt.bounds(fields));
+            let write_fmt = fmt.to_struct_write(&amplify_crate, span);
+            if fmt.fmt == alt.fmt {
+                write_fmt
             } else {
-                quote_spanned! { data.fields.span() =>
-                    f.write_str(if !f.alternate() { #tokens_fmt } else { #tokens_alt })
+                let write_alt = alt.to_struct_write(&amplify_crate, span);
+                quote_spanned! { span =>
+                    if !f.alternate() {
+                        #write_fmt
+                    } else {
+                        #write_alt
+                    }
                 }
             }
         }
     };
+    let where_clause = where_clause(input, bounds)?;
 
     Ok(quote! {
         impl #impl_generics ::core::fmt::Display for #ident_name #ty_generics #where_clause {
@@ -536,25 +1743,53 @@ fn inner_struct(input: &DeriveInput, data: &DataStruct) -> Result<TokenStream2>
 }
 
 fn inner_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream2> {
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
     let ident_name = &input.ident;
     let mut display = TokenStream2::new();
+    let self_ty: Type = parse_quote! { #ident_name #ty_generics };
+    let amplify_crate = get_amplify_crate(input);
+    let mut bounds = vec![];
 
     let global = Technique::from_attrs(&input.attrs, input.span())?;
+    let global_num = NumFormat::from_attrs(&input.attrs)?;
     // Ancient rust versions do not known about `matches!` macro
     #[allow(clippy::match_like_matches_macro)]
     let mut use_global = match global {
-        Some(Technique::Inner) | Some(Technique::Lowercase(_)) | Some(Technique::Uppercase(_)) => {
-            false
-        }
-        _ => true,
+        Some(Technique::FromTrait(tr)) => !tr.delegates_to_field(),
+        Some(Technique::FromMethod(_)) => true,
+        _ => false,
     };
 
     for v in &data.variants {
         let type_name = &v.ident;
-        let type_str = format!("{}", type_name);
+        let type_str = type_name.unraw().to_string();
+        let span = v.span();
 
-        let mut local = Technique::from_attrs(&v.attrs, v.span())?;
+        if let Some(attr) = v.attrs.iter().find(|attr| bound_lit(attr).is_some()) {
+            return Err(attr_err!(
+                attr,
+                "trait bounds can be given only at the type level"
+            ));
+        }
+
+        // Transparent error variants (see `#[derive(Error)]`) forward formatting
+        // to the inner error
+        if let (true, Some(field)) = (is_error_transparent(&v.attrs), delegated_field(&v.fields)?) {
+            use_global = false;
+            bounds.extend(
+                field
+                    .to_type(&v.fields)
+                    .map(|ty| (ty.clone(), quote! { ::core::fmt::Display })),
+            );
+            let pattern = variant_pattern(type_name, &v.fields, &[&field], span);
+            let binding = field.to_binding();
+            display.extend(quote_spanned! { span =>
+                #pattern => ::core::fmt::Display::fmt(#binding, f),
+            });
+            continue;
+        }
+
+        let mut local = Technique::from_variant_attrs(&v.attrs, global.as_ref(), v.span())?;
         let mut parent = global.clone();
         let current = local.as_mut().or_else(|| parent.as_mut());
         let mut current = current
@@ -568,156 +1803,128 @@ fn inner_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream2> {
             use_global = false;
         }
 
-        if let Some(Technique::DocComments(_))
-        | Some(Technique::Lowercase(_))
-        | Some(Technique::Uppercase(_)) = current
-        {
+        if let Some(Technique::DocComments(_)) | Some(Technique::Case(..)) = current {
             use_global = false;
             if let Some(t) = current.as_mut() {
                 match t {
                     Technique::DocComments(_) => {
                         *t = Technique::DocComments(String::new());
                         t.apply_docs(&v.attrs);
-                        t.fix_fmt();
+                        t.docs_or_name(&type_str);
                     }
-                    Technique::Lowercase(_) => {
-                        *t = Technique::Lowercase(String::new());
+                    Technique::Case(case, _) => {
+                        *t = Technique::Case(*case, String::new());
                         t.apply_case(&type_str, &v.fields);
-                        t.fix_fmt();
-                    }
-                    Technique::Uppercase(_) => {
-                        *t = Technique::Uppercase(String::new());
-                        t.apply_case(&type_str, &v.fields);
-                        t.fix_fmt();
                     }
                     _ => unreachable!(),
                 }
             }
         }
 
-        let tokens_fmt = current.as_ref().map(|t| t.to_fmt(false));
-        let tokens_alt = current.as_ref().map(|t| t.to_fmt(true));
+        let any_pattern = match v.fields {
+            Fields::Named(_) => quote_spanned! { span => Self::#type_name { .. } },
+            Fields::Unnamed(_) => quote_spanned! { span => Self::#type_name(..) },
+            Fields::Unit => quote_spanned! { span => Self::#type_name },
+        };
 
-        match (&v.fields, &tokens_fmt, &tokens_alt) {
-            (Fields::Named(_), None, _) => {
-                display.extend(quote_spanned! { v.span() =>
-                    Self::#type_name { .. } => f.write_str(concat!(#type_str, " { .. }")),
-                });
-            }
-            (Fields::Unnamed(_), None, _) => {
-                display.extend(quote_spanned! { v.span() =>
-                    Self::#type_name(..) => f.write_str(concat!(#type_str, "(..)")),
-                });
-            }
-            (Fields::Unit, None, _) => {
-                display.extend(quote_spanned! { v.span() =>
-                    Self::#type_name => f.write_str(#type_str),
+        let technique = match current {
+            None => {
+                let s = match v.fields {
+                    Fields::Named(_) => format!("{} {{ .. }}", type_str),
+                    Fields::Unnamed(_) => format!("{}(..)", type_str),
+                    Fields::Unit => type_str,
+                };
+                display.extend(quote_spanned! { span =>
+                    #any_pattern => f.write_str(#s),
                 });
+                continue;
             }
-            (Fields::Named(fields), Some(tokens_fmt), Some(tokens_alt)) => {
-                if let Some(Technique::Inner) = current {
-                    if fields.named.len() != 1 {
-                        return Err(attr_err!(
-                            fields.span(),
-                            "display(inner) requires only single field in the structure"
-                        ));
+            Some(Technique::FromTrait(tr)) => {
+                let arm = match delegated_field(&v.fields)? {
+                    Some(field) if tr.delegates_to_field() => {
+                        bounds.extend(
+                            field
+                                .to_type(&v.fields)
+                                .map(|ty| (ty.clone(), tr.trait_path())),
+                        );
+                        let pattern = variant_pattern(type_name, &v.fields, &[&field], span);
+                        let stream =
+                            tr.into_token_stream2(field.to_binding().to_token_stream(), span);
+                        quote_spanned! { span => #pattern => { #stream } }
                     }
-                    let field = fields
-                        .named
-                        .first()
-                        .expect("we just checked that there is a single field")
-                        .ident
-                        .as_ref()
-                        .expect("named fields always have ident with the name");
-                    display.extend(quote_spanned! { v.span() =>
-                        Self::#type_name { #field, .. } => {
-                            write!(f, #tokens_fmt, _0 = #field)
-                        }
-                    });
-                } else if let Some(Technique::FromTrait(tr)) = current {
-                    let stream =
-                        Technique::FromTrait(tr).into_token_stream2(&v.fields, v.span(), false);
-                    display.extend(quote_spanned! { v.span() =>
-                        Self::#type_name { .. } => {
-                            #stream
-                        }
-                    })
-                } else {
-                    let f = fields.named.iter().map(|f| f.ident.as_ref().unwrap());
-                    let idents = f
-                        .clone()
-                        .filter(|ident| has_formatters(ident, &tokens_fmt.to_string()))
-                        .collect::<Vec<_>>();
-                    let idents_alt = f
-                        .filter(|ident| has_formatters(ident, &tokens_alt.to_string()))
-                        .collect::<Vec<_>>();
-                    if tokens_fmt.to_string() != tokens_alt.to_string() {
-                        display.extend(quote_spanned! { v.span() =>
-                            Self::#type_name { #( #idents, )* .. } if !f.alternate() => {
-                                write!(f, #tokens_fmt, #( #idents = #idents, )*)
-                            },
-                            Self::#type_name { #( #idents, )* .. } => {
-                                write!(f, #tokens_alt, #( #idents_alt = #idents_alt, )*)
-                            },
-                        });
-                    } else {
-                        display.extend(quote_spanned! { v.span() =>
-                            Self::#type_name { #( #idents, )* .. } => {
-                                write!(f, #tokens_fmt, #( #idents = #idents, )*)
-                            },
-                        });
+                    _ => {
+                        bounds.push((self_ty.clone(), tr.trait_path()));
+                        let stream = tr.into_token_stream2(quote_spanned! { span => &self }, span);
+                        quote_spanned! { span => #any_pattern => { #stream } }
                     }
-                }
+                };
+                display.extend(arm);
+                continue;
             }
-            (Fields::Unnamed(fields), Some(tokens_fmt), Some(tokens_alt)) => {
-                if let Some(Technique::FromTrait(tr)) = current {
-                    let stream =
-                        Technique::FromTrait(tr).into_token_stream2(&v.fields, v.span(), false);
-                    display.extend(quote_spanned! { v.span() =>
-                        Self::#type_name(..) => {
-                            #stream
-                        }
-                    })
-                } else {
-                    let f =
-                        (0..fields.unnamed.len()).map(|i| Ident::new(&format!("_{}", i), v.span()));
-                    let idents = f
-                        .clone()
-                        .filter(|ident| has_formatters(ident, &tokens_fmt.to_string()))
-                        .collect::<Vec<_>>();
-                    let idents_alt = f
-                        .filter(|ident| has_formatters(ident, &tokens_alt.to_string()))
-                        .collect::<Vec<_>>();
-                    if tokens_fmt.to_string() != tokens_alt.to_string() {
-                        display.extend(quote_spanned! { v.span() =>
-                            Self::#type_name ( #( #idents, )* .. ) if !f.alternate() => {
-                                write!(f, #tokens_fmt, #( #idents = #idents, )*)
-                            },
-                            Self::#type_name ( #( #idents, )* .. ) => {
-                                write!(f, #tokens_alt, #( #idents_alt = #idents_alt, )*)
-                            },
-                        });
-                    } else {
-                        display.extend(quote_spanned! { v.span() =>
-                            Self::#type_name ( #( #idents, )* .. ) => {
-                                write!(f, #tokens_fmt, #( #idents = #idents, )*)
-                            },
-                        });
-                    }
-                }
+            Some(Technique::FromMethod(path)) => {
+                display.extend(quote_spanned! { span =>
+                    #any_pattern => f.write_str(& #path (self)),
+                });
+                continue;
             }
-            (Fields::Unit, Some(tokens_fmt), Some(tokens_alt)) => {
-                if let Some(Technique::Inner) = current {
-                    display.extend(quote_spanned! { v.span() =>
-                        Self::#type_name => f.write_str(#type_str),
-                    });
-                } else {
-                    display.extend(quote_spanned! { v.span() =>
-                        Self::#type_name => f.write_str(if !f.alternate() { #tokens_fmt } else { #tokens_alt }),
-                    });
-                }
+            Some(Technique::Inner) | Some(Technique::InnerDeref) if v.fields.is_empty() => {
+                display.extend(quote_spanned! { span =>
+                    #any_pattern => f.write_str(#type_str),
+                });
+                continue;
             }
-            _ => unreachable!(),
+            Some(technique) => technique,
+        };
+
+        if let Fields::Unit = v.fields {
+            let (fmt, _) = technique
+                .format_str(&v.fields, false, span)?
+                .expect("format string presence is already checked");
+            let (alt, _) = technique
+                .format_str(&v.fields, true, span)?
+                .expect("format string presence is already checked");
+            display.extend(quote_spanned! { span =>
+                Self::#type_name => f.write_str(if !f.alternate() { #fmt } else { #alt }),
+            });
+            continue;
+        }
+
+        if let Some((field, target)) = technique.deref_target(&v.fields, span)? {
+            bounds.push((target, quote! { ::core::fmt::Display }));
+            let pattern = variant_pattern(type_name, &v.fields, &[&field], span);
+            let binding = field.to_binding();
+            display.extend(quote_spanned! { span =>
+                #pattern => ::core::fmt::Display::fmt(&**#binding, f),
+            });
+            continue;
+        }
+
+        let num = NumFormat::from_attrs(&v.attrs)?;
+        let num = match (num, global_num.as_ref()) {
+            (Some(num), Some(global)) => Some(num.or(global)),
+            (num, global) => num.or_else(|| global.cloned()),
+        };
+        let fmt = technique
+            .resolve(&v.fields, num.as_ref(), false, span)?
+            .expect("format string presence is already checked");
+        let alt = technique
+            .resolve(&v.fields, num.as_ref(), true, span)?
+            .expect("format string presence is already checked");
+        bounds.extend(fmt.bounds(&v.fields));
+        bounds.extend(alt.bounds(&v.fields));
+        let pattern_fmt = fmt.to_pattern(type_name, &v.fields, span);
+        let write_fmt = fmt.to_variant_write(&amplify_crate, span);
+        if fmt.fmt == alt.fmt {
+            display.extend(quote_spanned! { span =>
+                #pattern_fmt => { #write_fmt },
+            });
+        } else {
+            let pattern_alt = alt.to_pattern(type_name, &v.fields, span);
+            let write_alt = alt.to_variant_write(&amplify_crate, span);
+            display.extend(quote_spanned! { span =>
+                #pattern_fmt if !f.alternate() => { #write_fmt },
+                #pattern_alt => { #write_alt },
+            });
         }
     }
 
@@ -747,6 +1954,7 @@ fn inner_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream2> {
         }
         _ => unreachable!(),
     };
+    let where_clause = where_clause(input, bounds)?;
 
     Ok(quote! {
         impl #impl_generics ::core::fmt::Display for #ident_name #ty_generics #where_clause {
@@ -759,7 +1967,8 @@ fn inner_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream2> {
 }
 
 fn inner_union(input: &DeriveInput, data: &DataUnion) -> Result<TokenStream2> {
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+    let where_clause = where_clause(input, vec![])?;
     let ident_name = &input.ident;
     let mut display = vec![];
 
@@ -770,7 +1979,7 @@ fn inner_union(input: &DeriveInput, data: &DataUnion) -> Result<TokenStream2> {
             .ident
             .clone()
             .expect("named attributes are always named");
-        let type_str = format!("{}", type_name);
+        let type_str = type_name.unraw().to_string();
 
         let format = Technique::from_attrs(&field.attrs, field.span())?
             .or_else(|| global.clone())
@@ -826,3 +2035,158 @@ fn inner_union(input: &DeriveInput, data: &DataUnion) -> Result<TokenStream2> {
         }
     })
 }
+
+#[cfg(test)]
+mod test {
+    use amplify_syn::testing::{assert_derive_err, assert_derive_ok};
+
+    use super::inner;
+
+    #[test]
+    fn unknown_fields() {
+        assert_derive_err(
+            quote! {
+                #[display("{value} of {totl}")]
+                struct Progress { value: u32, total: u32 }
+            },
+            inner,
+            "unknown field `totl` in format string; available fields are `value`, `total`",
+            r#""{value} of {totl}""#,
+        );
+        assert_derive_err(
+            quote! {
+                #[display("{x}", alt = "{x}:{z}")]
+                struct Point { x: u32, y: u32 }
+            },
+            inner,
+            "unknown field `z` in format string; available fields are `x`, `y`",
+            r#""{x}:{z}""#,
+        );
+        assert_derive_err(
+            quote! {
+                #[display("{{value}} is {vlaue}")]
+                struct Value { value: u32 }
+            },
+            inner,
+            "unknown field `vlaue` in format string; the only available field is `value`",
+            r#""{{value}} is {vlaue}""#,
+        );
+        assert_derive_err(
+            quote! {
+                #[display("{value:>width$}")]
+                struct Padded { value: u32, widht: usize }
+            },
+            inner,
+            "unknown field `width` in format string; available fields are `value`, `widht`",
+            r#""{value:>width$}""#,
+        );
+    }
+
+    #[test]
+    fn unknown_indexes() {
+        assert_derive_err(
+            quote! {
+                #[display("{} and {} or {}")]
+                struct Pair(u8, u8);
+            },
+            inner,
+            "unknown field `2` in format string; available fields are `0`, `1`",
+            r#""{} and {} or {}""#,
+        );
+        assert_derive_err(
+            quote! {
+                #[display("{0}")]
+                struct Named { name: String }
+            },
+            inner,
+            "unknown field `0` in format string; the only available field is `name`",
+            r#""{0}""#,
+        );
+        assert_derive_err(
+            quote! {
+                enum Pair {
+                    #[display("{0} and {2}")]
+                    Bytes(u8, u8),
+                }
+            },
+            inner,
+            "unknown field `2` in format string; available fields are `0`, `1`",
+            r#""{0} and {2}""#,
+        );
+    }
+
+    #[test]
+    fn invalid_placeholders() {
+        assert_derive_err(
+            quote! {
+                #[display("{header.0x}")]
+                struct Block { header: Header }
+            },
+            inner,
+            "`0x` is not a valid field name in `{header.0x}`",
+            r#""{header.0x}""#,
+        );
+        assert_derive_err(
+            quote! {
+                #[display("{header.height:grouped}")]
+                struct Block { header: Header }
+            },
+            inner,
+            "`grouped` format spec can be applied only to fields, not to their nested fields \
+             like `{header.height:grouped}`",
+            r#""{header.height:grouped}""#,
+        );
+        assert_derive_err(
+            quote! {
+                #[display("{0:>8z}")]
+                struct Id(u32);
+            },
+            inner,
+            "invalid format spec `>8z`",
+            r#""{0:>8z}""#,
+        );
+        assert_derive_err(
+            quote! {
+                #[display("{0:.*}")]
+                struct Float(f64, usize);
+            },
+            inner,
+            "`.*` precision is not supported; use `.field$` form instead",
+            r#""{0:.*}""#,
+        );
+    }
+
+    #[test]
+    fn unbalanced_braces() {
+        assert_derive_err(
+            quote! {
+                #[display("{x}}")]
+                struct Point { x: u32 }
+            },
+            inner,
+            "format string contains unmatched `}`; use `}}` to escape it",
+            r#""{x}}""#,
+        );
+        assert_derive_err(
+            quote! {
+                #[display("value {x")]
+                struct Point { x: u32 }
+            },
+            inner,
+            "format string has unterminated placeholder",
+            r#""value {x""#,
+        );
+    }
+
+    #[test]
+    fn valid_expansion() {
+        let file = assert_derive_ok(
+            quote! {
+                #[display("{r#type}: {amount:grouped}")]
+                struct Payment { r#type: u8, amount: u64 }
+            },
+            inner,
+        );
+        assert_eq!(file.items.len(), 1);
+    }
+}
@@ -14,21 +14,578 @@
 // along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
-use proc_macro2::TokenStream as TokenStream2;
-use syn::{DeriveInput, Result};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Data, DataEnum, DeriveInput, Error, Field, Fields, GenericArgument, Ident, Index,
+    Lit, LitInt, LitStr, Member, Meta, MetaNameValue, PathArguments, Result, Type, WherePredicate,
+};
+
+use crate::util::{type_uses_generics, where_clause_with};
+
+const NAME: &str = "error";
+const EXAMPLE: &str =
+    r#"#[error(transparent | not_source | code = 1, category = "io" | bound = "T: Debug")]"#;
+
+/// Integer types which can be used for error codes
+const CODE_TYPES: [(&str, u64); 3] = [
+    ("u8", u8::MAX as u64),
+    ("u16", u16::MAX as u64),
+    ("u32", u32::MAX as u64),
+];
+
+/// Level at which `#[error(...)]` attribute is given
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum AttrLevel {
+    Type,
+    Variant,
+    Field,
+}
+
+/// Parsed `#[error(...)]` attributes
+#[derive(Default)]
+struct ErrorAttr {
+    /// Span of the `transparent` flag, if present
+    pub transparent: Option<Span>,
+    pub not_source: bool,
+    pub bound: Option<Vec<WherePredicate>>,
+    /// Error code of the enum variant
+    pub code: Option<LitInt>,
+    /// Error category of the enum variant or the default category for the
+    /// variants of the enum
+    pub category: Option<LitStr>,
+    /// Code of the first enum variant, enabling automatic assignment of codes
+    /// to unit variants
+    pub code_offset: Option<LitInt>,
+    /// Integer type used for error codes
+    pub code_type: Option<Ident>,
+}
+
+impl ErrorAttr {
+    fn with(attrs: &[Attribute], level: AttrLevel) -> Result<Self> {
+        let mut res = ErrorAttr::default();
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident(NAME)) {
+            let args = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+            for arg in args {
+                match arg {
+                    Meta::Path(ref path)
+                        if path.is_ident("transparent") && level == AttrLevel::Variant =>
+                    {
+                        res.transparent = Some(path.span())
+                    }
+                    Meta::Path(ref path)
+                        if path.is_ident("not_source") && level != AttrLevel::Type =>
+                    {
+                        res.not_source = true
+                    }
+                    Meta::NameValue(MetaNameValue {
+                        ref path,
+                        lit: Lit::Str(ref bound),
+                        ..
+                    }) if path.is_ident("bound") && level == AttrLevel::Type => {
+                        if res.bound.is_some() {
+                            return Err(attr_err!(arg, "trait bounds can be given only once"));
+                        }
+                        let predicates = bound.parse_with(
+                            Punctuated::<WherePredicate, Token![,]>::parse_terminated,
+                        )?;
+                        res.bound = Some(predicates.into_iter().collect());
+                    }
+                    Meta::NameValue(MetaNameValue {
+                        ref path,
+                        lit: Lit::Int(ref code),
+                        ..
+                    }) if (path.is_ident("code") && level == AttrLevel::Variant)
+                        || (path.is_ident("code_offset") && level == AttrLevel::Type) =>
+                    {
+                        let target = if path.is_ident("code") {
+                            &mut res.code
+                        } else {
+                            &mut res.code_offset
+                        };
+                        if target.is_some() {
+                            return Err(attr_err!(arg, "error code can be given only once"));
+                        }
+                        code.base10_parse::<u32>()?;
+                        *target = Some(code.clone());
+                    }
+                    Meta::NameValue(MetaNameValue {
+                        ref path,
+                        lit: Lit::Str(ref category),
+                        ..
+                    }) if path.is_ident("category") && level != AttrLevel::Field => {
+                        if res.category.is_some() {
+                            return Err(attr_err!(arg, "error category can be given only once"));
+                        }
+                        res.category = Some(category.clone());
+                    }
+                    Meta::NameValue(MetaNameValue {
+                        ref path,
+                        lit: Lit::Str(ref ty),
+                        ..
+                    }) if path.is_ident("code_type") && level == AttrLevel::Type => {
+                        if res.code_type.is_some() {
+                            return Err(attr_err!(arg, "error code type can be given only once"));
+                        }
+                        if !CODE_TYPES.iter().any(|(name, _)| ty.value() == *name) {
+                            return Err(attr_err!(
+                                ty,
+                                "error code type must be one of `u8`, `u16` or `u32`"
+                            ));
+                        }
+                        res.code_type = Some(Ident::new(&ty.value(), ty.span()));
+                    }
+                    _ => {
+                        let msg = match level {
+                            AttrLevel::Type => {
+                                "only `bound = \"...\"`, `code_offset = <int>`, `code_type = \
+                                 \"...\"` and `category = \"...\"` arguments are allowed at the \
+                                 type level"
+                            }
+                            AttrLevel::Variant => {
+                                "only `transparent`, `not_source`, `code = <int>` and `category \
+                                 = \"...\"` arguments are allowed at the variant level"
+                            }
+                            AttrLevel::Field => {
+                                "only `not_source` argument is allowed at the field level"
+                            }
+                        };
+                        return Err(attr_err!(arg, msg));
+                    }
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// Field returned from `std::error::Error::source`, or, for transparent enum
+/// variants, the field to which the source is delegated
+struct Source<'a> {
+    pub member: Member,
+    pub field: &'a Field,
+    pub transparent: bool,
+}
+
+impl<'a> Source<'a> {
+    /// Detects source field: either the one explicitly marked with
+    /// `#[source]`, or, if none is marked, the one which is converted from
+    /// with `#[from]` attribute (which may be given at the enum variant level
+    /// for single-field variants). Fields and variants marked with
+    /// `#[error(not_source)]` never provide a source.
+    pub fn with(fields: &'a Fields, variant_attrs: &[Attribute]) -> Result<Option<Self>> {
+        let member = |index: usize, field: &Field| match field.ident {
+            Some(ref ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(index)),
+        };
+        let source = |index: usize, field: &'a Field| Source {
+            member: member(index, field),
+            field,
+            transparent: false,
+        };
+
+        let variant_attr = ErrorAttr::with(variant_attrs, AttrLevel::Variant)?;
+        if let Some(span) = variant_attr.transparent {
+            return match (fields.len(), fields.iter().next()) {
+                (1, Some(field)) => Ok(Some(Source {
+                    transparent: true,
+                    ..source(0, field)
+                })),
+                _ => Err(attr_err!(
+                    span,
+                    "transparent enum variants must have exactly one field"
+                )),
+            };
+        }
+
+        let mut candidates = vec![];
+        let mut marked = None;
+        for (index, field) in fields.iter().enumerate() {
+            if ErrorAttr::with(&field.attrs, AttrLevel::Field)?.not_source {
+                continue;
+            }
+            candidates.push((index, field));
+            for attr in field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path.is_ident("source"))
+            {
+                if !attr.tokens.is_empty() {
+                    return Err(attr_err!(
+                        attr.span(),
+                        "source",
+                        "attribute does not take arguments",
+                        "#[source]"
+                    ));
+                }
+                if marked.is_some() {
+                    return Err(attr_err!(
+                        attr.span(),
+                        "source",
+                        "only a single field can be marked as an error source",
+                        "#[source]"
+                    ));
+                }
+                marked = Some(source(index, field));
+            }
+        }
+        if variant_attr.not_source || marked.is_some() {
+            return Ok(marked.filter(|_| !variant_attr.not_source));
+        }
+
+        let mut converted = candidates
+            .iter()
+            .filter(|(_, field)| field.attrs.iter().any(is_from_conversion));
+        match (converted.next(), converted.next()) {
+            (Some((index, field)), None) => return Ok(Some(source(*index, field))),
+            (Some(_), Some(_)) => return Ok(None),
+            (None, _) => {}
+        }
+
+        match (fields.len(), candidates.first()) {
+            (1, Some((_, field))) if variant_attrs.iter().any(is_from_conversion) => {
+                Ok(Some(source(0, field)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns type which must implement `std::error::Error`
+    pub fn error_type(&self) -> &'a Type {
+        match (self.transparent, option_inner(&self.field.ty)) {
+            (false, Some(ty)) => ty,
+            _ => &self.field.ty,
+        }
+    }
+
+    /// Generates expression returning the source from the provided reference
+    /// to the source field
+    pub fn to_source(&self, target: TokenStream2) -> TokenStream2 {
+        let span = self.field.ty.span();
+        if self.transparent {
+            quote_spanned! { span =>
+                ::std::error::Error::source(#target)
+            }
+        } else if option_inner(&self.field.ty).is_some() {
+            quote_spanned! { span =>
+                (#target).as_ref().map(|e| e as &(dyn ::std::error::Error + 'static))
+            }
+        } else {
+            quote_spanned! { span =>
+                Some(#target as &(dyn ::std::error::Error + 'static))
+            }
+        }
+    }
+}
+
+/// Detects `#[from]` attribute (or its form with explicit source types)
+/// requesting conversion into the field
+fn is_from_conversion(attr: &Attribute) -> bool {
+    attr.path.is_ident("from")
+        && attr
+            .parse_args_with(|input: syn::parse::ParseStream| {
+                let is_default = input.peek(Token![default]) && input.peek2(Token![=]);
+                input.parse::<TokenStream2>()?;
+                Ok(!is_default)
+            })
+            .unwrap_or(true)
+}
+
+/// Returns `T` if the type is an `Option<T>`
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Option" {
+        return None;
+    }
+    match segment.arguments {
+        PathArguments::AngleBracketed(ref args) if args.args.len() == 1 => {
+            match args.args.first() {
+                Some(GenericArgument::Type(ty)) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Generates inherent `code`, `category` and `from_code` methods of an enum
+/// from the error codes and categories given with `#[error(...)]` attributes.
+/// Returns `None` if no codes and categories are given.
+fn codes_impl(input: &DeriveInput, global: &ErrorAttr) -> Result<Option<TokenStream2>> {
+    let data = match input.data {
+        Data::Enum(ref data) => data,
+        _ if global.code_offset.is_some()
+            || global.code_type.is_some()
+            || global.category.is_some() =>
+        {
+            return Err(attr_err!(
+                input.ident,
+                "error codes and categories are supported only for enums"
+            ))
+        }
+        _ => return Ok(None),
+    };
+    let attrs = data
+        .variants
+        .iter()
+        .map(|v| ErrorAttr::with(&v.attrs, AttrLevel::Variant))
+        .collect::<Result<Vec<_>>>()?;
+    let has_codes = global.code_offset.is_some()
+        || global.code_type.is_some()
+        || attrs.iter().any(|attr| attr.code.is_some());
+    let has_categories =
+        global.category.is_some() || attrs.iter().any(|attr| attr.category.is_some());
 
-pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
     let ident_name = &input.ident;
+    let code_fns = if has_codes {
+        Some(code_fns(input, data, global, &attrs)?)
+    } else {
+        None
+    };
+    let category_fn = if has_categories {
+        let arms = data.variants.iter().zip(&attrs).map(|(v, attr)| {
+            let pattern = any_pattern(&v.ident, &v.fields);
+            let category = attr
+                .category
+                .as_ref()
+                .or(global.category.as_ref())
+                .map(LitStr::value)
+                .unwrap_or_default();
+            quote! { #pattern => #category, }
+        });
+        Some(quote! {
+            /// Returns category of the error
+            pub fn category(&self) -> &'static str {
+                match self {
+                    #( #arms )*
+                }
+            }
+        })
+    } else {
+        None
+    };
+    if code_fns.is_none() && category_fn.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(quote! {
+        impl #impl_generics #ident_name #ty_generics #where_clause {
+            #code_fns
+            #category_fn
+        }
+    }))
+}
+
+/// Generates `code` and `from_code` methods. Unit variants without explicit
+/// codes get the code of the previous variant incremented by one (or the
+/// `code_offset` for the first variant), if `code_offset` is given.
+fn code_fns(
+    input: &DeriveInput,
+    data: &DataEnum,
+    global: &ErrorAttr,
+    attrs: &[ErrorAttr],
+) -> Result<TokenStream2> {
+    let code_type = global
+        .code_type
+        .clone()
+        .unwrap_or_else(|| Ident::new("u16", input.ident.span()));
+    let max = CODE_TYPES
+        .iter()
+        .find(|(name, _)| code_type == name)
+        .map(|(_, max)| *max)
+        .expect("code type is checked during attribute parsing");
+
+    let mut next = match global.code_offset {
+        Some(ref offset) => Some(offset.base10_parse::<u64>()?),
+        None => None,
+    };
+    let mut codes = Vec::<(&Ident, u64)>::with_capacity(data.variants.len());
+    let mut code_arms = Vec::with_capacity(data.variants.len());
+    let mut from_arms = vec![];
+    for (v, attr) in data.variants.iter().zip(attrs) {
+        let is_unit = match v.fields {
+            Fields::Unit => true,
+            Fields::Named(_) | Fields::Unnamed(_) => false,
+        };
+        let (code, span) = match (&attr.code, next) {
+            (Some(code), _) => (code.base10_parse::<u64>()?, code.span()),
+            (None, Some(code)) if is_unit => (code, v.ident.span()),
+            (None, _) if is_unit => {
+                return Err(attr_err!(
+                    v.ident,
+                    "unit variants must have error code given with `#[error(code = ...)]` \
+                     unless `#[error(code_offset = ...)]` is given for the enum"
+                ))
+            }
+            (None, _) => {
+                return Err(attr_err!(
+                    v.ident,
+                    "variants with data must have error code given with `#[error(code = ...)]`"
+                ))
+            }
+        };
+        if code > max {
+            let msg = format!(
+                "error code {} of `{}` variant exceeds maximal value {} of `{}` type",
+                code, v.ident, max, code_type
+            );
+            return Err(Error::new(span, msg));
+        }
+        if let Some((first, _)) = codes.iter().find(|(_, c)| *c == code) {
+            let msg = format!(
+                "variants `{}` and `{}` have the same error code {}",
+                first, v.ident, code
+            );
+            let mut err = Error::new(first.span(), &msg);
+            err.combine(Error::new(span, &msg));
+            return Err(err);
+        }
+        codes.push((&v.ident, code));
+        next = next.map(|_| code + 1);
+
+        let lit = LitInt::new(&code.to_string(), span);
+        let pattern = any_pattern(&v.ident, &v.fields);
+        code_arms.push(quote! { #pattern => #lit, });
+        if is_unit {
+            let variant = &v.ident;
+            from_arms.push(quote! { #lit => Some(Self::#variant), });
+        }
+    }
+
+    Ok(quote! {
+        /// Returns numeric code of the error
+        pub fn code(&self) -> #code_type {
+            match self {
+                #( #code_arms )*
+            }
+        }
+
+        /// Constructs error from its numeric code. Only unit variants can be
+        /// constructed; codes of the variants with data produce `None`.
+        pub fn from_code(code: #code_type) -> Option<Self> {
+            match code {
+                #( #from_arms )*
+                _ => None,
+            }
+        }
+    })
+}
+
+/// Generates enum variant pattern which ignores the variant fields
+fn any_pattern(variant: &Ident, fields: &Fields) -> TokenStream2 {
+    match fields {
+        Fields::Named(_) => quote! { Self::#variant { .. } },
+        Fields::Unnamed(_) => quote! { Self::#variant(..) },
+        Fields::Unit => quote! { Self::#variant },
+    }
+}
+
+pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+    let ident_name = &input.ident;
+    let global = ErrorAttr::with(&input.attrs, AttrLevel::Type)?;
+    let codes = codes_impl(&input, &global)?;
+    let mut sources = vec![];
+
+    let source = match input.data {
+        Data::Struct(ref data) => Source::with(&data.fields, &[])?.map(|source| {
+            let member = &source.member;
+            let stream = source.to_source(quote! { &self.#member });
+            sources.push(source);
+            stream
+        }),
+        Data::Enum(ref data) => {
+            let mut arms = TokenStream2::new();
+            for v in &data.variants {
+                let source = match Source::with(&v.fields, &v.attrs)? {
+                    Some(source) => source,
+                    None => continue,
+                };
+                let variant = &v.ident;
+                let member = &source.member;
+                let binding = Ident::new("source", Span::call_site());
+                let pattern = match member {
+                    Member::Named(_) => quote! { Self::#variant { #member: #binding, .. } },
+                    Member::Unnamed(index) => {
+                        let skip = (0..index.index).map(|_| quote! { _ });
+                        quote! { Self::#variant ( #( #skip, )* #binding, .. ) }
+                    }
+                };
+                let source_expr = source.to_source(quote! { #binding });
+                arms.extend(quote! { #pattern => #source_expr, });
+                sources.push(source);
+            }
+            if sources.is_empty() {
+                None
+            } else {
+                Some(quote! {
+                    match self {
+                        #arms
+                        _ => None,
+                    }
+                })
+            }
+        }
+        Data::Union(_) => None,
+    };
+
+    let source = source.map(|source| {
+        quote! {
+            fn source(&self) -> Option<&(dyn ::std::error::Error + 'static)> {
+                #source
+            }
+        }
+    });
+
+    // Generic types get bounds only on the traits actually required: the type
+    // itself must be `Debug` and `Display` (which is bounded by the respective
+    // derives), while `Error` is required only from the fields returned as
+    // error sources
+    let predicates: Vec<WherePredicate> = match global.bound {
+        Some(bound) => bound,
+        None if input.generics.type_params().next().is_none() => vec![],
+        None => {
+            let mut predicates: Vec<WherePredicate> = vec![parse_quote! {
+                Self: ::core::fmt::Debug + ::core::fmt::Display
+            }];
+            predicates.extend(
+                sources
+                    .iter()
+                    .map(Source::error_type)
+                    .filter(|ty| type_uses_generics(ty, &input.generics))
+                    .map(|ty| -> WherePredicate {
+                        parse_quote! { #ty: ::std::error::Error + 'static }
+                    }),
+            );
+            predicates
+        }
+    };
+    let where_clause = where_clause_with(&input.generics, predicates);
+    let display_clause = where_clause_with(
+        &input.generics,
+        input
+            .generics
+            .type_params()
+            .next()
+            .map(|_| parse_quote! { #ident_name #ty_generics: ::core::fmt::Display }),
+    );
 
     Ok(quote! {
         impl #impl_generics ::std::error::Error for #ident_name #ty_generics #where_clause {
+            #source
         }
 
-        impl #impl_generics From<#ident_name #ty_generics> for String #where_clause {
+        impl #impl_generics From<#ident_name #ty_generics> for String #display_clause {
             fn from(err: #ident_name #ty_generics) -> Self {
                 err.to_string()
             }
         }
+
+        #codes
     })
 }
@@ -15,22 +15,99 @@
 // If not, see <https://opensource.org/licenses/MIT>.
 
 use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::parse::ParseStream;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::{
-    Attribute, Data, DataEnum, DataStruct, DataUnion, DeriveInput, Error, Field, Fields,
-    FieldsNamed, FieldsUnnamed, Ident, Result, Type,
+    Attribute, Data, DataEnum, DataStruct, DataUnion, DeriveInput, Error, Expr, Field, Fields,
+    FieldsNamed, FieldsUnnamed, Ident, Result, Type, WherePredicate,
 };
 
+use crate::util::where_clause_with;
+
 const NAME: &str = "from";
 const EXAMPLE: &str = r#"#[from(::std::fmt::Error)]"#;
+const SKIP: &str = "skip";
+const ALL_SINGLE_FIELD: &str = "all_single_field";
+
+/// Checks whether the attribute has form of `#[from(flag)]`
+fn is_flag(attr: &Attribute, flag: &str) -> bool {
+    !attr.tokens.is_empty()
+        && attr
+            .parse_args::<Ident>()
+            .map(|ident| ident == flag)
+            .unwrap_or_default()
+}
+
+/// Parses `#[from(default = expr)]` attribute, returning `None` for all other
+/// forms of the `#[from]` attribute
+fn default_expr(attr: &Attribute) -> Result<Option<Expr>> {
+    if attr.tokens.is_empty() {
+        return Ok(None);
+    }
+    attr.parse_args_with(|input: ParseStream| {
+        if !input.peek(Token![default]) || !input.peek2(Token![=]) {
+            input.parse::<TokenStream2>()?;
+            return Ok(None);
+        }
+        input.parse::<Token![default]>()?;
+        input.parse::<Token![=]>()?;
+        input.parse().map(Some)
+    })
+}
+
+/// Parses `#[from(...)]` attribute arguments: list of the source types,
+/// optionally including `ref` flag requesting conversions from references to
+/// the source types. Returns the types and span of the `ref` flag, if present.
+fn sources(attr: &Attribute) -> Result<(Vec<Type>, Option<Span>)> {
+    attr.parse_args_with(|input: ParseStream| {
+        let mut types = vec![];
+        let mut by_ref = None;
+        while !input.is_empty() {
+            if input.peek(Token![ref]) {
+                let token = input.parse::<Token![ref]>()?;
+                if by_ref.is_some() {
+                    return Err(attr_err!(token.span, "`ref` flag can be given only once"));
+                }
+                by_ref = Some(token.span);
+            } else {
+                types.push(input.parse()?);
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        Ok((types, by_ref))
+    })
+}
 
-#[derive(Clone, PartialEq, Eq, Debug)]
+/// Generates initializer for a field which is not converted from the source
+/// type: either an expression given with `#[from(default = expr)]`, or the
+/// field type `Default` implementation. The latter is spanned to the field
+/// type, such that missing `Default` implementation is reported for the field.
+fn field_default(field: &Field) -> Result<TokenStream2> {
+    let mut res = None;
+    for attr in field.attrs.iter().filter(|attr| attr.path.is_ident(NAME)) {
+        if let Some(expr) = default_expr(attr)? {
+            if res.is_some() {
+                return Err(attr_err!(attr, "default value can be given only once"));
+            }
+            res = Some(quote! { #expr });
+        }
+    }
+    let ty = &field.ty;
+    Ok(res.unwrap_or_else(|| {
+        quote_spanned! { ty.span() => <#ty as ::core::default::Default>::default() }
+    }))
+}
+
+#[derive(Clone, Debug)]
 enum InstructionEntity {
     Default,
     DefaultEnumFields {
         variant: Ident,
-        fields: Vec<Ident>,
+        fields: Vec<(Ident, TokenStream2)>,
     },
     Unit {
         variant: Option<Ident>,
@@ -38,12 +115,12 @@ enum InstructionEntity {
     Named {
         variant: Option<Ident>,
         field: Ident,
-        other: Vec<Ident>,
+        other: Vec<(Ident, TokenStream2)>,
     },
     Unnamed {
         variant: Option<Ident>,
         index: usize,
-        total: usize,
+        other: Vec<TokenStream2>,
     },
 }
 
@@ -57,16 +134,11 @@ impl InstructionEntity {
         ) {
             (0, Some(v), ..) => InstructionEntity::Unit { variant: Some(v) },
             (_, variant, Fields::Unit, ..) => InstructionEntity::Unit { variant },
-            (1, variant, Fields::Named(f), Some(Field { ident: Some(i), .. })) => {
+            (1, variant, Fields::Named(_), Some(Field { ident: Some(i), .. })) => {
                 InstructionEntity::Named {
                     variant,
-                    field: i.clone(),
-                    other: f
-                        .named
-                        .iter()
-                        .filter_map(|f| f.ident.clone())
-                        .filter(|ident| ident != &i)
-                        .collect(),
+                    field: i,
+                    other: vec![],
                 }
             }
             (1, _, Fields::Named(_), ..) => {
@@ -74,42 +146,66 @@ impl InstructionEntity {
             }
             (_, Some(variant), Fields::Named(f), ..) => InstructionEntity::DefaultEnumFields {
                 variant,
-                fields: f.named.iter().filter_map(|f| f.ident.clone()).collect(),
+                fields: f
+                    .named
+                    .iter()
+                    .map(|f| Ok((f.ident.clone().expect("named field"), field_default(f)?)))
+                    .collect::<Result<_>>()?,
             },
-            (len, variant, Fields::Unnamed(_), ..) => InstructionEntity::Unnamed {
+            (_, variant, Fields::Unnamed(f), ..) => InstructionEntity::Unnamed {
                 variant,
                 index: 0,
-                total: len,
+                other: f
+                    .unnamed
+                    .iter()
+                    .skip(1)
+                    .map(field_default)
+                    .collect::<Result<_>>()?,
             },
             (_, None, ..) => InstructionEntity::Default,
         };
         Ok(res)
     }
 
+    /// Returns name of the enum variant constructed by the conversion
+    pub fn variant(&self) -> Option<&Ident> {
+        match self {
+            InstructionEntity::Default => None,
+            InstructionEntity::DefaultEnumFields { variant, .. } => Some(variant),
+            InstructionEntity::Unit { variant }
+            | InstructionEntity::Named { variant, .. }
+            | InstructionEntity::Unnamed { variant, .. } => variant.as_ref(),
+        }
+    }
+
     pub fn with_field(
         index: usize,
-        total: usize,
         field: &Field,
         fields: &Fields,
         variant: Option<Ident>,
-    ) -> Self {
-        if let Some(ref ident) = field.ident {
+    ) -> Result<Self> {
+        Ok(if let Some(ref ident) = field.ident {
             InstructionEntity::Named {
                 variant,
                 field: ident.clone(),
                 other: fields
                     .iter()
-                    .filter_map(|f| f.ident.clone())
-                    .filter(|i| ident != i)
-                    .collect(),
+                    .filter(|f| f.ident.as_ref() != Some(ident))
+                    .map(|f| Ok((f.ident.clone().expect("named field"), field_default(f)?)))
+                    .collect::<Result<_>>()?,
             }
         } else {
             InstructionEntity::Unnamed {
                 variant,
                 index,
-                total,
+                other: fields
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i != index)
+                    .map(|(_, f)| field_default(f))
+                    .collect::<Result<_>>()?,
             }
-        }
+        })
     }
 
     pub fn into_token_stream2(self) -> TokenStream2 {
@@ -122,52 +218,42 @@ impl InstructionEntity {
                 quote! { Self #var }
             }
             InstructionEntity::Named {
-                variant: None,
-                field,
-                ..
-            } => {
-                quote! {
-                    Self { #field: v.into(), ..Default::default() }
-                }
-            }
-            InstructionEntity::Named {
-                variant: Some(var),
+                variant,
                 field,
                 other,
             } => {
+                let var = variant.map_or(quote! {}, |v| quote! {:: #v});
+                let (idents, defaults): (Vec<_>, Vec<_>) = other.into_iter().unzip();
                 quote! {
-                    Self :: #var { #field: v.into(), #( #other: Default::default(), )* }
+                    Self #var { #field: v.into(), #( #idents: #defaults, )* }
                 }
             }
             InstructionEntity::Unnamed {
                 variant,
                 index,
-                total,
+                other,
             } => {
                 let var = variant.map_or(quote! {}, |v| quote! {:: #v});
-                let prefix = (0..index).fold(TokenStream2::new(), |mut stream, _| {
-                    stream.extend(quote! {Default::default(),});
-                    stream
-                });
-                let suffix = ((index + 1)..total).fold(TokenStream2::new(), |mut stream, _| {
-                    stream.extend(quote! {Default::default(),});
-                    stream
-                });
+                let (prefix, suffix) = other.split_at(index);
                 quote! {
-                    Self #var ( #prefix v.into(), #suffix )
+                    Self #var ( #( #prefix, )* v.into(), #( #suffix, )* )
                 }
             }
             InstructionEntity::DefaultEnumFields { variant, fields } => {
+                let (idents, defaults): (Vec<_>, Vec<_>) = fields.into_iter().unzip();
                 quote! {
-                    Self #variant { #( #fields: Default::default() )* }
+                    Self :: #variant { #( #idents: #defaults, )* }
                 }
             }
         }
     }
 }
 
+/// Conversion source type, the way the type is converted and, if the
+/// conversion from the reference to the source type is also requested, span
+/// of the `ref` flag
 #[derive(Clone)]
-struct InstructionEntry(pub Type, pub InstructionEntity);
+struct InstructionEntry(pub Type, pub InstructionEntity, pub Option<Span>);
 
 impl PartialEq for InstructionEntry {
     // Ugly way, but with current `syn` version no other way is possible
@@ -181,8 +267,8 @@ impl PartialEq for InstructionEntry {
 }
 
 impl InstructionEntry {
-    pub fn with_type(ty: &Type, entity: &InstructionEntity) -> Self {
-        Self(ty.clone(), entity.clone())
+    pub fn with_type(ty: &Type, entity: &InstructionEntity, by_ref: Option<Span>) -> Self {
+        Self(ty.clone(), entity.clone(), by_ref)
     }
 
     pub fn parse(
@@ -192,21 +278,37 @@ impl InstructionEntry {
     ) -> Result<Vec<InstructionEntry>> {
         let mut list = Vec::<InstructionEntry>::new();
         for attr in attrs.iter().filter(|attr| attr.path.is_ident(NAME)) {
-            // #[from]
-            if attr.tokens.is_empty() {
+            // #[from(default = expr)] and #[from(skip)] are not conversions
+            if default_expr(attr)?.is_some() || is_flag(attr, SKIP) {
+                continue;
+            }
+            // #[from] or #[from(ref)]
+            let (types, by_ref) = if attr.tokens.is_empty() {
+                (vec![], None)
+            } else {
+                sources(attr)?
+            };
+            if !types.is_empty() {
+                // #[from(Type1, Type2, ...)], optionally with `ref` flag
+                for ty in types {
+                    list.push(InstructionEntry::with_type(&ty, &entity, by_ref));
+                }
+            } else if !attr.tokens.is_empty() && by_ref.is_none() {
+                return Err(attr_err!(attr, "list of source types must not be empty"));
+            } else {
                 match (fields.len(), fields.iter().next()) {
-                    (1, Some(field)) => list.push(InstructionEntry::with_type(&field.ty, &entity)),
+                    (1, Some(field)) => {
+                        list.push(InstructionEntry::with_type(&field.ty, &entity, by_ref))
+                    }
                     _ => {
                         return Err(attr_err!(
                             attr,
-                            "empty attribute is allowed only for entities \
-                         with a single field; for multi-field entities \
-                         specify the attribute right ahead of the target field"
+                            "attribute without source types is allowed only for entities \
+                             with a single field; for multi-field entities \
+                             specify the attribute right ahead of the target field"
                         ))
                     }
                 }
-            } else {
-                list.push(InstructionEntry::with_type(&attr.parse_args()?, &entity));
             }
         }
         Ok(list)
@@ -246,7 +348,7 @@ impl InstructionTable {
                     },
                 ),
                 &field.attrs,
-                InstructionEntity::with_field(index, fields.len(), field, fields, variant.clone()),
+                InstructionEntity::with_field(index, field, fields, variant.clone())?,
             )?)?;
         }
         if variant.is_none() && fields.len() == 1 && self.0.is_empty() {
@@ -254,7 +356,7 @@ impl InstructionTable {
                 .into_iter()
                 .next()
                 .expect("we know we have at least one item");
-            self.push(InstructionEntry::with_type(&field.ty, &entity));
+            self.push(InstructionEntry::with_type(&field.ty, &entity, None));
         }
         Ok(self)
     }
@@ -269,27 +371,87 @@ impl InstructionTable {
     {
         let mut count = 0;
         for entry in list {
-            self.0.iter().find(|e| *e == &entry).map_or(Ok(()), |_| {
-                Err(Error::new(
-                    Span::call_site(),
-                    format!(
-                        "Attribute `#[{}]`: repeated use of type `{}`",
-                        NAME,
-                        quote! {ty}
-                    ),
-                ))
-            })?;
+            self.0
+                .iter()
+                .find(|e| *e == &entry)
+                .map_or(Ok(()), |prev| {
+                    let ty = &entry.0;
+                    let mut err = Error::new(
+                        ty.span(),
+                        format!(
+                            "Attribute `#[{}]`: repeated use of type `{}`, which would result in \
+                         conflicting `From` implementations",
+                            NAME,
+                            quote! {#ty}
+                        ),
+                    );
+                    err.combine(Error::new(
+                        prev.0.span(),
+                        format!(
+                            "Attribute `#[{}]`: type `{}` is first used here",
+                            NAME,
+                            quote! {#ty}
+                        ),
+                    ));
+                    Err(err)
+                })?;
             self.0.push(entry);
             count += 1;
         }
         Ok(count)
     }
 
+    /// Adds conversion into an enum variant derived with
+    /// `#[from(all_single_field)]`, reporting conflicts with the conversions
+    /// into other variants
+    fn push_variant(&mut self, entry: InstructionEntry) -> Result<()> {
+        if let Some(prev) = self.0.iter().find(|e| *e == &entry) {
+            let ty = &entry.0;
+            let name = |entry: &InstructionEntry| {
+                entry.1.variant().map(Ident::to_string).unwrap_or_default()
+            };
+            let mut err = Error::new(
+                ty.span(),
+                format!(
+                    "Attribute `#[{}({})]`: variant `{}` wraps type `{}`, conversion from which \
+                     is already derived for variant `{}`; mark one of the variants with \
+                     `#[{}({})]` to avoid conflicting `From` implementations",
+                    NAME,
+                    ALL_SINGLE_FIELD,
+                    name(&entry),
+                    quote! {#ty},
+                    name(prev),
+                    NAME,
+                    SKIP
+                ),
+            );
+            err.combine(Error::new(
+                prev.0.span(),
+                format!(
+                    "Attribute `#[{}({})]`: type `{}` is first used here by variant `{}`",
+                    NAME,
+                    ALL_SINGLE_FIELD,
+                    quote! {#ty},
+                    name(prev)
+                ),
+            ));
+            return Err(err);
+        }
+        self.push(entry);
+        Ok(())
+    }
+
     pub fn into_token_stream2(self, input: &DeriveInput) -> TokenStream2 {
         let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
         let ident_name = &input.ident;
 
-        self.0.into_iter().fold(TokenStream2::new(), |mut stream, InstructionEntry(from, entity)| {
+        // Reference conversions are universally quantified over the lifetime
+        // of the reference
+        let mut ref_generics = input.generics.clone();
+        ref_generics.params.insert(0, parse_quote! { '__from });
+        let (ref_impl_generics, ..) = ref_generics.split_for_impl();
+
+        self.0.into_iter().fold(TokenStream2::new(), |mut stream, InstructionEntry(from, entity, by_ref)| {
             let convert = entity.into_token_stream2();
             stream.extend(quote! {
                 impl #impl_generics ::core::convert::From<#from> for #ident_name #ty_generics #where_clause {
@@ -298,6 +460,20 @@ impl InstructionTable {
                     }
                 }
             });
+            if let Some(span) = by_ref {
+                let bound = quote_spanned! { span => #from: ::core::clone::Clone };
+                let bound = syn::parse2::<WherePredicate>(bound)
+                    .expect("type with a trait bound is always a valid where predicate");
+                let where_clause = where_clause_with(&input.generics, Some(bound));
+                stream.extend(quote! {
+                    impl #ref_impl_generics ::core::convert::From<&'__from #from> for #ident_name #ty_generics #where_clause {
+                        fn from(v: &'__from #from) -> Self {
+                            let v = ::core::clone::Clone::clone(v);
+                            #convert
+                        }
+                    }
+                });
+            }
             stream
         })
     }
@@ -318,22 +494,49 @@ fn inner_struct(input: &DeriveInput, data: &DataStruct) -> Result<TokenStream2>
 }
 
 fn inner_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream2> {
-    // Do not let top-level `from` on enums
-    input
-        .attrs
-        .iter()
-        .find(|attr| attr.path.is_ident(NAME))
-        .map_or(Ok(()), |a| {
-            Err(attr_err!(
-                a,
+    // Do not let top-level `from` on enums, except the request for blanket
+    // conversions into all single-field variants
+    let mut all_single_field = false;
+    for attr in input.attrs.iter().filter(|attr| attr.path.is_ident(NAME)) {
+        if !is_flag(attr, ALL_SINGLE_FIELD) {
+            return Err(attr_err!(
+                attr,
                 "top-level attribute is not allowed, use it for specific fields or variants"
-            ))
-        })?;
+            ));
+        }
+        if all_single_field {
+            return Err(attr_err!(
+                attr,
+                "blanket conversions can be requested only once"
+            ));
+        }
+        all_single_field = true;
+    }
 
     let mut instructions = InstructionTable::new();
     for v in &data.variants {
         instructions.parse(&v.fields, &v.attrs, Some(v.ident.clone()))?;
     }
+
+    if all_single_field {
+        for v in &data.variants {
+            let field = match (v.fields.len(), v.fields.iter().next()) {
+                (1, Some(field)) => field,
+                _ => continue,
+            };
+            // Variants with explicit `#[from]` attributes, including
+            // `#[from(skip)]`, are left as is
+            if v.attrs
+                .iter()
+                .chain(&field.attrs)
+                .any(|attr| attr.path.is_ident(NAME))
+            {
+                continue;
+            }
+            let entity = InstructionEntity::with_fields(&v.fields, Some(v.ident.clone()))?;
+            instructions.push_variant(InstructionEntry::with_type(&field.ty, &entity, None))?;
+        }
+    }
     Ok(instructions.into_token_stream2(input))
 }
 
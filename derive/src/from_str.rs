@@ -0,0 +1,157 @@
+// Rust language amplification derive library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use proc_macro2::TokenStream as TokenStream2;
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Data, DataEnum, DeriveInput, Error, Fields, Lit, Meta, MetaNameValue, NestedMeta,
+    Result,
+};
+
+use crate::display::unit_variant_strings;
+
+const NAME: &str = "from_str";
+const EXAMPLE: &str = r#"#[from_str(alias = "name", case_insensitive)]"#;
+
+#[derive(Clone, Default)]
+struct FromStrAttr {
+    pub aliases: Vec<String>,
+    pub case_insensitive: bool,
+}
+
+impl FromStrAttr {
+    fn with(attrs: &[Attribute], global: bool) -> Result<Self> {
+        let mut res = FromStrAttr::default();
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident(NAME)) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                _ => return Err(attr_err!(attr, "arguments must be given as a list")),
+            };
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                        path,
+                        lit: Lit::Str(alias),
+                        ..
+                    })) if path.is_ident("alias") && !global => res.aliases.push(alias.value()),
+                    NestedMeta::Meta(Meta::Path(path))
+                        if path.is_ident("case_insensitive") && global =>
+                    {
+                        res.case_insensitive = true
+                    }
+                    other if global => {
+                        return Err(attr_err!(
+                            other,
+                            "only `case_insensitive` argument is allowed at the type level"
+                        ))
+                    }
+                    other => {
+                        return Err(attr_err!(
+                            other,
+                            "only `alias = \"...\"` arguments are allowed at the variant level"
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
+    match input.data {
+        Data::Enum(ref data) => inner_enum(&input, data),
+        Data::Struct(_) => Err(Error::new_spanned(
+            &input,
+            "Deriving `FromStr` is supported only for enums",
+        )),
+        Data::Union(_) => Err(Error::new_spanned(
+            &input,
+            "Deriving `FromStr` is not supported in unions",
+        )),
+    }
+}
+
+fn inner_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream2> {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let ident_name = &input.ident;
+    let global = FromStrAttr::with(&input.attrs, true)?;
+
+    for v in &data.variants {
+        match v.fields {
+            Fields::Unit => {}
+            _ => {
+                return Err(Error::new_spanned(
+                    v,
+                    "Deriving `FromStr` is possible only for enums with fieldless variants, \
+                     since data contained in the variant fields can't be parsed from the string",
+                ))
+            }
+        }
+    }
+
+    let normalize = |s: String| {
+        if global.case_insensitive {
+            s.to_lowercase()
+        } else {
+            s
+        }
+    };
+
+    let strings = unit_variant_strings(input, data)?;
+    let mut seen = Vec::<String>::new();
+    let mut arms = Vec::with_capacity(data.variants.len());
+    for (v, s) in data.variants.iter().zip(strings) {
+        let local = FromStrAttr::with(&v.attrs, false)?;
+        let mut names = vec![normalize(s)];
+        names.extend(local.aliases.into_iter().map(normalize));
+        for name in &names {
+            if seen.contains(name) {
+                return Err(Error::new_spanned(
+                    v,
+                    format!(
+                        "Deriving `FromStr`: string `{}` matches more than a single variant",
+                        name
+                    ),
+                ));
+            }
+            seen.push(name.clone());
+        }
+        let type_name = &v.ident;
+        arms.push(quote_spanned! { v.span() =>
+            #( #names )|* => Ok(Self::#type_name),
+        });
+    }
+
+    let matched = if global.case_insensitive {
+        quote! { s.to_lowercase().as_str() }
+    } else {
+        quote! { s }
+    };
+    let ident_str = ident_name.to_string();
+
+    Ok(quote! {
+        impl #impl_generics ::core::str::FromStr for #ident_name #ty_generics #where_clause {
+            type Err = ::std::string::String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match #matched {
+                    #( #arms )*
+                    _ => Err(format!("unrecognized `{}` value `{}`", #ident_str, s)),
+                }
+            }
+        }
+    })
+}
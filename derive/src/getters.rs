@@ -18,30 +18,49 @@ use std::iter::FromIterator;
 use std::convert::TryInto;
 use proc_macro2::{TokenStream as TokenStream2, Span, Ident};
 use quote::ToTokens;
+use syn::ext::IdentExt;
 use syn::spanned::Spanned;
 use syn::{
-    Data, DeriveInput, Error, Fields, Result, LitStr, Attribute, DataStruct, ImplGenerics,
-    TypeGenerics, WhereClause, Field,
+    Data, DeriveInput, Error, Fields, Result, LitStr, LitInt, Attribute, DataStruct, ImplGenerics,
+    TypeGenerics, WhereClause, Field, Type, TypePath, PathArguments, GenericArgument, GenericParam,
+    Index, Member,
 };
 
-use amplify_syn::{ParametrizedAttr, AttrReq, ArgValueReq, ArgValue, ValueClass};
+use amplify_syn::{ParametrizedAttr, AttrReq, ArgValueReq, ArgValue, ValueClass, ExtractAttr};
+
+use crate::util::{ident_or_raw, type_uses_ident};
+
+const NAME: &str = "getter";
+
+/// Maximal number of fields returned by `into_parts` as a tuple, unless
+/// specified with `#[getter(parts = ...)]`
+const PARTS_MAX_ARITY: usize = 12;
 
 pub(crate) fn derive(input: DeriveInput) -> Result<TokenStream2> {
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
     let struct_name = &input.ident;
 
-    let mut global_param = ParametrizedAttr::with("getter", &input.attrs)?;
-    let _ = GetterDerive::try_from(&mut global_param, true)?;
+    let (global_param, parts) = global_param(&input)?;
 
     match input.data {
-        Data::Struct(data) => derive_struct_impl(
-            data,
-            struct_name,
-            global_param,
-            impl_generics,
-            ty_generics,
-            where_clause,
-        ),
+        Data::Struct(ref data) => {
+            let getters = derive_struct_impl(
+                data,
+                struct_name,
+                global_param,
+                parts.is_some(),
+                impl_generics,
+                ty_generics,
+                where_clause,
+            )?;
+            let parts = parts
+                .map(|config| derive_parts(&input, data, config))
+                .transpose()?;
+            Ok(quote! {
+                #getters
+                #parts
+            })
+        }
         Data::Enum(_) => Err(Error::new_spanned(
             &input,
             "Deriving getters is not supported in enums",
@@ -59,30 +78,80 @@ struct GetterDerive {
     // pub doc: Attribute,
     pub skip: bool,
     pub copy: bool,
+    pub deref: bool,
     pub base: Option<LitStr>,
+    pub rename: Option<LitStr>,
     pub main: Option<LitStr>,
     pub as_ref: Option<LitStr>,
     pub as_mut: Option<LitStr>,
 }
 
 impl GetterDerive {
-    #[allow(clippy::blocks_in_if_conditions)]
-    fn try_from(attr: &mut ParametrizedAttr, global: bool) -> Result<GetterDerive> {
+    fn attr_req(global: bool) -> AttrReq {
         let mut map = HashMap::from_iter(vec![
             ("prefix", ArgValueReq::with_default("")),
             ("all", ArgValueReq::Prohibited),
+            ("deref", ArgValueReq::Prohibited),
+            ("copy", ArgValueReq::with_default("")),
             ("as_copy", ArgValueReq::with_default("")),
             ("as_clone", ArgValueReq::with_default("")),
             ("as_ref", ArgValueReq::with_default("")),
             ("as_mut", ArgValueReq::with_default("_mut")),
+            ("mut", ArgValueReq::with_default("_mut")),
         ]);
 
-        if !global {
+        if global {
+            map.insert("parts", ArgValueReq::with_default(PARTS_MAX_ARITY));
+            map.insert("parts_struct", ArgValueReq::Prohibited);
+        } else {
             map.insert("skip", ArgValueReq::Prohibited);
             map.insert("base_name", ArgValueReq::Optional(ValueClass::str()));
+            map.insert("rename", ArgValueReq::Optional(ValueClass::str()));
         }
 
-        attr.check(AttrReq::with(map))?;
+        AttrReq::with(map)
+    }
+
+    /// Extracts checked `#[getter]` attribute from a structure or a field,
+    /// returning an empty attribute if it is absent
+    fn extract(item: &impl ExtractAttr, global: bool) -> Result<ParametrizedAttr> {
+        Ok(item
+            .parametrized_attr(NAME, &GetterDerive::attr_req(global))?
+            .unwrap_or_else(|| ParametrizedAttr::new(NAME)))
+    }
+
+    #[allow(clippy::blocks_in_if_conditions)]
+    fn try_from(attr: &mut ParametrizedAttr, global: bool) -> Result<GetterDerive> {
+        attr.check(GetterDerive::attr_req(global))?;
+
+        // `mut` is a shorthand for `as_mut`
+        if let Some(mutable) = attr.args.remove("mut") {
+            if attr.args.contains_key("as_mut") {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "`mut` and `as_mut` attributes can't be present together",
+                ));
+            }
+            attr.args.insert("as_mut".to_owned(), mutable);
+        }
+
+        // `copy` is a shorthand for `as_copy`
+        if let Some(copy) = attr.args.remove("copy") {
+            if attr.args.contains_key("as_copy") {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "`copy` and `as_copy` attributes can't be present together",
+                ));
+            }
+            attr.args.insert("as_copy".to_owned(), copy);
+        }
+
+        if attr.args.contains_key("rename") && attr.args.contains_key("base_name") {
+            return Err(Error::new(
+                Span::call_site(),
+                "`rename` and `base_name` attributes can't be present together",
+            ));
+        }
 
         if attr.args.contains_key("all") {
             if attr.args.contains_key("as_clone")
@@ -146,11 +215,17 @@ impl GetterDerive {
                 .unwrap_or_else(|| LitStr::new("", Span::call_site())),
             skip: attr.args.get("skip").is_some(),
             copy: attr.args.contains_key("as_copy"),
+            deref: attr.args.contains_key("deref"),
             base: attr
                 .args
                 .get("base_name")
                 .map(|a| a.clone().try_into())
                 .transpose()?,
+            rename: attr
+                .args
+                .get("rename")
+                .map(|a| a.clone().try_into())
+                .transpose()?,
             main: attr
                 .args
                 .get("as_copy")
@@ -171,6 +246,39 @@ impl GetterDerive {
     }
 }
 
+/// Configuration of `into_parts` and `from_parts` methods, requested with
+/// type-level `parts` and `parts_struct` arguments
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+struct PartsConfig {
+    /// Maximal number of fields returned as a tuple
+    pub max_arity: usize,
+    /// Whether the fields are always returned as a generated structure
+    pub as_struct: bool,
+}
+
+impl PartsConfig {
+    /// Removes `parts` and `parts_struct` arguments from the type-level
+    /// attribute, returning `None` if none of them is present
+    fn take(attr: &mut ParametrizedAttr) -> Result<Option<PartsConfig>> {
+        let max_arity = attr.args.remove("parts");
+        let as_struct = attr.args.remove("parts_struct").is_some();
+        if max_arity.is_none() && !as_struct {
+            return Ok(None);
+        }
+        let max_arity = match max_arity {
+            Some(value) => {
+                let lit: LitInt = value.try_into()?;
+                lit.base10_parse()?
+            }
+            None => PARTS_MAX_ARITY,
+        };
+        Ok(Some(PartsConfig {
+            max_arity,
+            as_struct,
+        }))
+    }
+}
+
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
 enum GetterMethod {
     Main { copy: bool },
@@ -237,17 +345,24 @@ impl GetterDerive {
         field_name: Option<&Ident>,
         span: Span,
     ) -> Result<Ident> {
-        let base_string = self
-            .base
-            .as_ref()
-            .map(LitStr::value)
-            .or_else(|| field_name.map(Ident::to_string))
-            .ok_or_else(|| {
-                Error::new(
-                span,
-                "Unnamed fields must be equipped with `#[getter(base_name = \"name\"]` attribute",
-            )
-            })?;
+        // Renamed getters do not use type-level prefix
+        let (prefix, base_string) = match self.rename {
+            Some(ref rename) => (String::new(), rename.value()),
+            None => (
+                self.prefix.value(),
+                self.base
+                    .as_ref()
+                    .map(LitStr::value)
+                    .or_else(|| field_name.map(|ident| ident.unraw().to_string()))
+                    .ok_or_else(|| {
+                        Error::new(
+                            span,
+                            "Unnamed fields must be equipped with `#[getter(base_name = \"name\"]` \
+                             attribute",
+                        )
+                    })?,
+            ),
+        };
 
         let name_lit = match method {
             GetterMethod::Main { .. } => &self.main,
@@ -257,9 +372,10 @@ impl GetterDerive {
         .clone()
         .expect("Internal inconsistency in getter derivation macro implementation");
 
-        let s = format!("{}{}{}", self.prefix.value(), base_string, name_lit.value());
+        let s = format!("{}{}{}", prefix, base_string, name_lit.value());
 
-        Ok(Ident::new(&s, span))
+        ident_or_raw(&s, span)
+            .ok_or_else(|| Error::new(span, format!("`{}` is not a valid getter name", s)))
     }
 
     pub fn getter_fn_doc(
@@ -268,7 +384,7 @@ impl GetterDerive {
         struct_name: &Ident,
         field_name: Option<&Ident>,
         field_index: usize,
-        field_doc: Option<&Attribute>,
+        field_doc: &[&Attribute],
     ) -> TokenStream2 {
         let fn_doc = format!(
             "Method {} [`{}::{}`] field.\n",
@@ -279,39 +395,51 @@ impl GetterDerive {
                 .unwrap_or_else(|| field_index.to_string())
         );
 
-        if let Some(field_doc) = field_doc {
-            quote! {
-                #[doc = #fn_doc]
-                #field_doc
-            }
-        } else {
-            quote! {
-                #[doc = #fn_doc]
-            }
+        quote! {
+            #[doc = #fn_doc]
+            #( #field_doc )*
         }
     }
 }
 
+/// Extracts type-level `#[getter]` attribute, separating configuration of
+/// `into_parts` and `from_parts` methods from the arguments applied to all
+/// fields
+fn global_param(input: &DeriveInput) -> Result<(ParametrizedAttr, Option<PartsConfig>)> {
+    let mut global_param = GetterDerive::extract(input, true)?;
+    let parts = PartsConfig::take(&mut global_param)?;
+    let _ = GetterDerive::try_from(&mut global_param, true)?;
+    Ok((global_param, parts))
+}
+
+/// Returns names of the getter methods derived for the structure fields,
+/// together with their spans used for reporting name collisions
+pub(crate) fn method_names(input: &DeriveInput, data: &DataStruct) -> Result<Vec<(Ident, Span)>> {
+    let (global_param, _) = global_param(input)?;
+
+    let mut names = vec![];
+    for (index, field) in data.fields.iter().enumerate() {
+        for (name, span, _) in derive_field_methods(field, index, &input.ident, &global_param)? {
+            names.push((name, span));
+        }
+    }
+    Ok(names)
+}
+
 fn derive_struct_impl(
-    data: DataStruct,
+    data: &DataStruct,
     struct_name: &Ident,
     global_param: ParametrizedAttr,
+    with_parts: bool,
     impl_generics: ImplGenerics,
     ty_generics: TypeGenerics,
     where_clause: Option<&WhereClause>,
 ) -> Result<TokenStream2> {
-    let mut methods = Vec::with_capacity(data.fields.len());
     match data.fields {
-        Fields::Named(ref fields) => {
-            for (index, field) in fields.named.iter().enumerate() {
-                methods.extend(derive_field_methods(
-                    field,
-                    index,
-                    struct_name,
-                    &global_param,
-                )?)
-            }
-        }
+        Fields::Named(_) => {}
+        // Tuple structs are supported only for deriving `into_parts` and
+        // `from_parts`; getters are derived for the fields with explicit names
+        Fields::Unnamed(_) if with_parts => {}
         Fields::Unnamed(_) => {
             return Err(Error::new(
                 Span::call_site(),
@@ -326,6 +454,24 @@ fn derive_struct_impl(
         }
     };
 
+    let mut methods = Vec::<(Ident, Span, TokenStream2)>::with_capacity(data.fields.len());
+    for (index, field) in data.fields.iter().enumerate() {
+        for (name, span, method) in derive_field_methods(field, index, struct_name, &global_param)?
+        {
+            if let Some((_, first, _)) = methods.iter().find(|(n, ..)| *n == name) {
+                let mut err =
+                    Error::new(span, format!("getter method `{}` is already defined", name));
+                err.combine(Error::new(
+                    *first,
+                    format!("getter method `{}` is first defined here", name),
+                ));
+                return Err(err);
+            }
+            methods.push((name, span, method));
+        }
+    }
+
+    let methods = methods.into_iter().map(|(_, _, method)| method);
     Ok(quote! {
         impl #impl_generics #struct_name #ty_generics #where_clause {
             #( #methods )*
@@ -338,18 +484,34 @@ fn derive_field_methods(
     index: usize,
     struct_name: &Ident,
     global_param: &ParametrizedAttr,
-) -> Result<Vec<TokenStream2>> {
-    let mut local_param = ParametrizedAttr::with("getter", &field.attrs)?;
+) -> Result<Vec<(Ident, Span, TokenStream2)>> {
+    let mut local_param = GetterDerive::extract(field, false)?;
+
+    // Method arguments given at the field level override global ones. Since the
+    // conversion below adds default arguments, we have to detect explicitly
+    // provided ones in advance
+    let explicit = ["copy", "as_copy", "as_clone", "as_ref", "all"]
+        .iter()
+        .any(|name| local_param.args.contains_key(*name));
+
+    // Unlike type-level `deref`, field-level one requires field type to be
+    // supported
+    let explicit_deref = local_param.args.contains_key("deref");
 
     // First, test individual attribute
     let _ = GetterDerive::try_from(&mut local_param, false)?;
+    if !explicit
+        && ["as_copy", "as_clone", "as_ref"]
+            .iter()
+            .any(|name| global_param.args.contains_key(*name))
+    {
+        // Removing default borrowing method, so global methods are used
+        local_param.args.remove("as_ref");
+    }
     // Second, combine global and local together
     let mut local_args = local_param.args.clone();
     let mut params = global_param.clone().merged(local_param)?;
-    if local_args
-        .keys()
-        .any(|k| k == "as_copy" || k == "as_clone" || k == "as_ref")
-    {
+    if explicit {
         // we have to use local arguments since they do override globals
         params.args.remove("as_copy");
         params.args.remove("as_clone");
@@ -366,30 +528,304 @@ fn derive_field_methods(
     }
     let getter = GetterDerive::try_from(&mut params, false)?;
 
-    if getter.skip {
+    let field_name = field.ident.as_ref();
+    if getter.skip || (field_name.is_none() && getter.base.is_none() && getter.rename.is_none()) {
         return Ok(Vec::new());
     }
+    let member = match field.ident {
+        Some(ref ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index::from(index)),
+    };
 
-    let field_name = field.ident.as_ref();
     let ty = &field.ty;
-    let doc = field.attrs.iter().find(|a| a.path.is_ident("doc"));
+    let doc = field
+        .attrs
+        .iter()
+        .filter(|a| a.path.is_ident("doc"))
+        .collect::<Vec<_>>();
+
+    // Name collisions are reported at the getter attribute, if any
+    let span = field
+        .attrs
+        .iter()
+        .find(|a| a.path.is_ident(NAME))
+        .map(|a| a.tokens.span())
+        .unwrap_or_else(|| field.span());
 
     let mut res = Vec::with_capacity(3);
     for method in getter.all_methods() {
         let fn_name = getter.getter_fn_ident(method, field_name, field.span())?;
-        let fn_doc = getter.getter_fn_doc(method, struct_name, field_name, index, doc);
+        let fn_doc = getter.getter_fn_doc(method, struct_name, field_name, index, &doc);
         let ret_prefix = method.ret_prefix();
         let ret_suffix = method.ret_suffix();
         let mut_prefix = method.mut_prefix();
+        let body = match method {
+            // Copying is done through a function with explicit `Copy` bound,
+            // so non-copy field types are reported as a missing trait
+            // implementation at the field type
+            GetterMethod::Main { copy: true } => quote_spanned! { ty.span() =>
+                fn copy<T: ::core::marker::Copy>(value: &T) -> T {
+                    *value
+                }
+                copy(&self.#member)
+            },
+            _ => quote! { #ret_prefix self.#member#ret_suffix },
+        };
+        let (ret_ty, body) = match (method, getter.deref, deref_conversion(ty)) {
+            (GetterMethod::AsRef, true, Some((ret_ty, conversion))) => {
+                (ret_ty, quote! { self.#member.#conversion() })
+            }
+            (GetterMethod::AsRef, true, None) if explicit_deref => return Err(Error::new(
+                ty.span(),
+                "`#[getter(deref)]` supports only `String`, `Vec<T>`, `PathBuf` and `Option<T>` \
+                     field types",
+            )),
+            _ => (quote! { #ret_prefix #ty }, body),
+        };
+
+        res.push((
+            fn_name.clone(),
+            span,
+            quote_spanned! { field.span() =>
+                #fn_doc
+                #[inline]
+                pub fn #fn_name(&#mut_prefix self) -> #ret_ty {
+                    #body
+                }
+            },
+        ))
+    }
+
+    Ok(res)
+}
+
+/// Derives `into_parts` and `from_parts` methods, together with the structure
+/// holding the parts if they are not returned as a tuple
+fn derive_parts(
+    input: &DeriveInput,
+    data: &DataStruct,
+    config: PartsConfig,
+) -> Result<TokenStream2> {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let struct_name = &input.ident;
+    let vis = &input.vis;
+
+    let mut parts = Vec::with_capacity(data.fields.len());
+    let mut skipped = vec![];
+    for (index, field) in data.fields.iter().enumerate() {
+        let member = match field.ident {
+            Some(ref ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(index)),
+        };
+        if GetterDerive::extract(field, false)?
+            .args
+            .contains_key("skip")
+        {
+            skipped.push((member, &field.ty));
+        } else {
+            parts.push((member, field));
+        }
+    }
+
+    let members = parts.iter().map(|(member, _)| member).collect::<Vec<_>>();
+    let tys = parts.iter().map(|(_, field)| &field.ty).collect::<Vec<_>>();
+    // Missing `Default` implementations are reported at the skipped field types
+    let defaults = skipped.iter().map(|(member, ty)| {
+        quote_spanned! { ty.span() => #member: ::core::default::Default::default() }
+    });
+
+    let mut into_doc = format!(
+        "Decomposes [`{}`] into its fields, following the order of their declaration.",
+        struct_name
+    );
+    let mut from_doc = format!(
+        "Composes [`{0}`] from its fields, following the order of their declaration; an inverse \
+         of [`{0}::into_parts`].",
+        struct_name
+    );
+    if !skipped.is_empty() {
+        into_doc.push_str("\n\nFields marked with `#[getter(skip)]` are dropped.");
+        from_doc.push_str(
+            "\n\nFields marked with `#[getter(skip)]` are initialized with their default values.",
+        );
+    }
+
+    let (parts_struct, parts_ty, into_body, part_members) =
+        if config.as_struct || parts.len() > config.max_arity {
+            let parts_name = Ident::new(&format!("{}Parts", struct_name), struct_name.span());
+            let names = members
+                .iter()
+                .map(|member| match member {
+                    Member::Named(ident) => ident.clone(),
+                    Member::Unnamed(index) => {
+                        Ident::new(&format!("_{}", index.index), Span::call_site())
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            for param in &input.generics.params {
+                let (ident, name) = match param {
+                    GenericParam::Type(param) => (&param.ident, param.ident.to_string()),
+                    GenericParam::Lifetime(param) => {
+                        (&param.lifetime.ident, param.lifetime.to_string())
+                    }
+                    GenericParam::Const(param) => (&param.ident, param.ident.to_string()),
+                };
+                if !tys.iter().any(|ty| type_uses_ident(ty, ident)) {
+                    return Err(Error::new(
+                        ident.span(),
+                        format!(
+                            "generic parameter `{}` is used only by the fields marked with \
+                             `#[getter(skip)]` and can't be used in the generated `{}` structure",
+                            name, parts_name
+                        ),
+                    ));
+                }
+            }
+
+            let fields = parts.iter().zip(&names).map(|((_, field), name)| {
+                let ty = &field.ty;
+                let doc = field.attrs.iter().filter(|a| a.path.is_ident("doc"));
+                quote! {
+                    #( #doc )*
+                    #vis #name: #ty
+                }
+            });
+            let struct_doc = format!(
+                "Fields of [`{0}`] returned by [`{0}::into_parts`] and consumed by \
+                 [`{0}::from_parts`].",
+                struct_name
+            );
+            let generics = &input.generics;
+            let where_clause = &input.generics.where_clause;
+            let parts_struct = quote! {
+                #[doc = #struct_doc]
+                #vis struct #parts_name #generics #where_clause {
+                    #( #fields, )*
+                }
+            };
+            let into_body = quote! { #parts_name { #( #names: self.#members, )* } };
+            let part_members = names.into_iter().map(Member::Named).collect::<Vec<_>>();
+            (
+                parts_struct,
+                quote! { #parts_name #ty_generics },
+                into_body,
+                part_members,
+            )
+        } else {
+            let part_members = (0..parts.len())
+                .map(|index| Member::Unnamed(Index::from(index)))
+                .collect::<Vec<_>>();
+            (
+                quote! {},
+                quote! { ( #( #tys, )* ) },
+                quote! { ( #( self.#members, )* ) },
+                part_members,
+            )
+        };
+
+    // Structures without fields besides skipped ones do not use the argument
+    let parts_arg = match parts.len() {
+        0 => quote! { _parts },
+        _ => quote! { parts },
+    };
 
-        res.push(quote_spanned! { field.span() =>
-            #fn_doc
+    Ok(quote! {
+        #parts_struct
+
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            #[doc = #into_doc]
             #[inline]
-            pub fn #fn_name(&#mut_prefix self) -> #ret_prefix #ty {
-                #ret_prefix self.#field_name#ret_suffix
+            #vis fn into_parts(self) -> #parts_ty {
+                #into_body
             }
-        })
+
+            #[doc = #from_doc]
+            #[inline]
+            #vis fn from_parts(#parts_arg: #parts_ty) -> Self {
+                Self {
+                    #( #members: #parts_arg.#part_members, )*
+                    #( #defaults, )*
+                }
+            }
+        }
+    })
+}
+
+/// Checks whether the type path matches the name of a well-known type, which
+/// may be given with or without full path to the type. Lifetime arguments of
+/// the type are ignored.
+// Ancient rust versions do not known about `matches!` macro
+#[allow(clippy::match_like_matches_macro)]
+pub(crate) fn is_known_type(ty: &Type, name: &str, modules: &[&str]) -> Option<Option<Type>> {
+    let path = match ty {
+        Type::Path(TypePath { qself: None, path }) => path,
+        _ => return None,
+    };
+    let segments = path.segments.iter().collect::<Vec<_>>();
+    let (last, prefix) = segments.split_last()?;
+    if last.ident != name {
+        return None;
+    }
+    match prefix {
+        [] => {}
+        [krate, module]
+            if ["std", "alloc", "core"].contains(&krate.ident.to_string().as_str())
+                && modules.contains(&module.ident.to_string().as_str()) => {}
+        _ => return None,
+    }
+    match last.arguments {
+        PathArguments::None => Some(None),
+        PathArguments::AngleBracketed(ref args) => {
+            let mut args = args.args.iter().filter(|arg| match arg {
+                GenericArgument::Lifetime(_) => false,
+                _ => true,
+            });
+            match (args.next(), args.next()) {
+                (Some(GenericArgument::Type(ty)), None) => Some(Some(ty.clone())),
+                _ => None,
+            }
+        }
+        _ => None,
     }
+}
 
-    Ok(res)
+/// Returns type borrowed by a deref-style getter together with the name of
+/// the method performing conversion of the field value into it
+fn deref_target(ty: &Type) -> Option<(TokenStream2, Ident)> {
+    if let Some(None) = is_known_type(ty, "String", &["string"]) {
+        Some((quote! { str }, Ident::new("as_str", Span::call_site())))
+    } else if let Some(Some(item)) = is_known_type(ty, "Vec", &["vec"]) {
+        Some((
+            quote! { [#item] },
+            Ident::new("as_slice", Span::call_site()),
+        ))
+    } else if let Some(None) = is_known_type(ty, "PathBuf", &["path"]) {
+        Some((
+            quote! { ::std::path::Path },
+            Ident::new("as_path", Span::call_site()),
+        ))
+    } else {
+        None
+    }
+}
+
+/// Returns return type and conversion method for deref-style getters
+fn deref_conversion(ty: &Type) -> Option<(TokenStream2, Ident)> {
+    if let Some((target, method)) = deref_target(ty) {
+        return Some((quote! { &#target }, method));
+    }
+    match is_known_type(ty, "Option", &["option"]) {
+        Some(Some(inner)) => Some(match deref_target(&inner) {
+            Some((target, _)) => (
+                quote! { ::core::option::Option<&#target> },
+                Ident::new("as_deref", Span::call_site()),
+            ),
+            None => (
+                quote! { ::core::option::Option<&#inner> },
+                Ident::new("as_ref", Span::call_site()),
+            ),
+        }),
+        _ => None,
+    }
 }
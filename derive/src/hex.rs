@@ -0,0 +1,158 @@
+// Rust language amplification derive library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use proc_macro2::TokenStream as TokenStream2;
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Error, Field, Index, Member, Meta, NestedMeta, Result};
+
+use crate::util::get_amplify_crate;
+
+const NAME: &str = "hex";
+const EXAMPLE: &str = r#"#[hex(reversed)]"#;
+
+#[derive(Clone, Default)]
+struct HexAttr {
+    pub reversed: bool,
+}
+
+impl HexAttr {
+    fn with(input: &DeriveInput) -> Result<Self> {
+        let mut res = HexAttr::default();
+        for attr in input.attrs.iter().filter(|attr| attr.path.is_ident(NAME)) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                _ => return Err(attr_err!(attr, "arguments must be given as a list")),
+            };
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("reversed") => {
+                        res.reversed = true
+                    }
+                    other => {
+                        return Err(attr_err!(
+                            other,
+                            "unknown argument; the only supported argument is `reversed`"
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
+    let field = match input.data {
+        Data::Struct(ref data) => match (data.fields.len(), data.fields.iter().next()) {
+            (1, Some(field)) => field,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input,
+                    "Deriving `Hex` is supported only for newtypes (structures with a single \
+                     field) over byte containers",
+                ))
+            }
+        },
+        Data::Enum(_) => {
+            return Err(Error::new_spanned(
+                &input,
+                "Deriving `Hex` is not supported in enums; use it on a newtype over a byte \
+                 container",
+            ))
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                &input,
+                "Deriving `Hex` is not supported in unions",
+            ))
+        }
+    };
+    inner_newtype(&input, field)
+}
+
+fn inner_newtype(input: &DeriveInput, field: &Field) -> Result<TokenStream2> {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let ident_name = &input.ident;
+    let amplify_crate = get_amplify_crate(input);
+    let attr = HexAttr::with(input)?;
+
+    let ty = &field.ty;
+    let member = match field.ident {
+        Some(ref ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index::from(0)),
+    };
+    let bytes = quote_spanned! { ty.span() => &self.#member[..] };
+    let (lower, upper, iter) = if attr.reversed {
+        (
+            quote! { format_hex_reverse },
+            quote! { format_hex_upper_reverse },
+            quote! { iter.rev() },
+        )
+    } else {
+        (
+            quote! { format_hex },
+            quote! { format_hex_upper },
+            quote! { iter },
+        )
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::core::fmt::LowerHex for #ident_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                if f.alternate() {
+                    f.write_str("0x")?;
+                }
+                #amplify_crate::hex::#lower(#bytes, f)
+            }
+        }
+
+        impl #impl_generics ::core::fmt::UpperHex for #ident_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                if f.alternate() {
+                    f.write_str("0x")?;
+                }
+                #amplify_crate::hex::#upper(#bytes, f)
+            }
+        }
+
+        impl #impl_generics ::core::fmt::Display for #ident_name #ty_generics #where_clause {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::LowerHex::fmt(self, f)
+            }
+        }
+
+        impl #impl_generics #amplify_crate::hex::FromHex for #ident_name #ty_generics #where_clause {
+            fn from_byte_iter<I>(iter: I) -> Result<Self, #amplify_crate::hex::Error>
+            where
+                I: ::core::iter::Iterator<Item = Result<u8, #amplify_crate::hex::Error>>
+                    + ::core::iter::ExactSizeIterator
+                    + ::core::iter::DoubleEndedIterator,
+            {
+                <#ty as #amplify_crate::hex::FromHex>::from_byte_iter(#iter)
+                    .map(|inner| Self { #member: inner })
+            }
+        }
+
+        impl #impl_generics ::core::str::FromStr for #ident_name #ty_generics #where_clause {
+            type Err = #amplify_crate::hex::Error;
+
+            #[inline]
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                <Self as #amplify_crate::hex::FromHex>::from_hex(s)
+            }
+        }
+    })
+}
@@ -39,15 +39,23 @@ extern crate proc_macro;
 mod util;
 
 mod as_any;
+mod debug_hex;
+mod default;
 mod display;
 mod error;
 mod from;
+mod from_str;
 mod getters;
+mod hex;
+mod setters;
+mod try_from;
 mod wrapper;
 
 use proc_macro::TokenStream;
 use syn::DeriveInput;
 
+use crate::default::DefaultTrait;
+
 /// # Usage
 ///
 /// 1. Generate [`Display`] descriptions using other formatting trait:
@@ -60,6 +68,45 @@ use syn::DeriveInput;
 ///         Twice(u8)
 ///     }
 ///    ```
+///    While `Debug` formats the whole value, other formatting traits
+///    (`Octal`, `Binary`, `Pointer`, `LowerHex`, `UpperHex`, `LowerExp`,
+///    `UpperExp`) are forwarded to the inner field, which is either the only
+///    field or the one marked with `#[display(delegate)]`. Formatter flags like
+///    width, fill or alternate `#` form are respected:
+///    ```
+///     # #[macro_use] extern crate amplify_derive;
+///     #[derive(Display)]
+///     #[display(LowerHex)]
+///     struct Id(u32);
+///
+///     #[derive(Display)]
+///     #[display(Binary)]
+///     struct Flags {
+///         #[display(delegate)]
+///         bits: u8,
+///         name: &'static str,
+///     }
+///
+///     #[derive(Display)]
+///     #[display(UpperHex)]
+///     enum Key {
+///         Short(u16),
+///         Long(u64),
+///     }
+///
+///     assert_eq!(format!("{}", Id(0xbeef)), "beef");
+///     assert_eq!(format!("{:#}", Id(0xbeef)), "0xbeef");
+///     assert_eq!(format!("{:08}", Id(0xbeef)), "0000beef");
+///     assert_eq!(format!("{}", Flags { bits: 5, name: "x" }), "101");
+///     assert_eq!(format!("{}", Key::Long(0xAB)), "AB");
+///    ```
+///    Unknown formatting traits result in a compile-time error:
+///    ```compile_fail
+///     # #[macro_use] extern crate amplify_derive;
+///     #[derive(Display)]
+///     #[display(Hex)]
+///     struct Id(u32);
+///    ```
 /// 2. Use existing function for displaying descriptions:
 ///    ```
 ///     # #[macro_use] extern crate amplify_derive;
@@ -107,6 +154,64 @@ use syn::DeriveInput;
 ///     struct Point { x: u32, y: u32 }
 ///     assert_eq!(format!("{}", Point { x: 0, y: 1 }), "(0, 1)");
 ///    ```
+///    Placeholders may reference fields by their name or index, and may
+///    access fields of the fields with dotted notation. Escaped braces and
+///    format specs are passed through to the generated `write!` call:
+///    ```
+///     # #[macro_use] extern crate amplify_derive;
+///     pub struct Header { pub height: u32 }
+///
+///     #[derive(Display)]
+///     #[display("{{{name}}} #{header.height}: {amount:>6}")]
+///     struct Block { name: &'static str, amount: u64, header: Header }
+///
+///     #[derive(Display)]
+///     #[display("{1} at {0.height}")]
+///     struct Tagged(Header, &'static str);
+///
+///     let header = Header { height: 5 };
+///     let block = Block { name: "genesis", amount: 50, header };
+///     assert_eq!(format!("{}", block), "{genesis} #5:     50");
+///     let tagged = Tagged(Header { height: 1 }, "tip");
+///     assert_eq!(format!("{}", tagged), "tip at 1");
+///    ```
+///    Implicit positional placeholders `{}` take the fields of tuple
+///    structures one by one, and width or precision of format specs may be
+///    taken from the fields with `field$` syntax:
+///    ```
+///     # #[macro_use] extern crate amplify_derive;
+///     #[derive(Display)]
+///     #[display("{} at {:#x}")]
+///     struct Tagged(&'static str, u32);
+///
+///     #[derive(Display)]
+///     #[display("[{value:>width$.prec$}]")]
+///     struct Padded { value: f64, width: usize, prec: usize }
+///
+///     assert_eq!(format!("{}", Tagged("tip", 255)), "tip at 0xff");
+///     let padded = Padded { value: 1.5, width: 6, prec: 2 };
+///     assert_eq!(format!("{}", padded), "[  1.50]");
+///    ```
+///    Fields named with raw identifiers may be referenced both with and
+///    without the `r#` prefix:
+///    ```
+///     # #[macro_use] extern crate amplify_derive;
+///     #[derive(Display)]
+///     #[display("{r#type}: {match}")]
+///     struct Rule { r#type: &'static str, r#match: u8 }
+///
+///     assert_eq!(Rule { r#type: "exact", r#match: 1 }.to_string(), "exact: 1");
+///    ```
+///    Format strings (including `alt` ones) are validated at compile time:
+///    placeholders referencing unknown fields or field indices, as well as
+///    invalid format specs, result in an error pointing to the placeholder
+///    and listing the available fields:
+///    ```compile_fail
+///     # #[macro_use] extern crate amplify_derive;
+///     #[derive(Display)]
+///     #[display("{name} is {nmae}")]
+///     struct Person { name: String }
+///    ```
 /// 4. Support for alternative formatting with `alt` parameter:
 ///    ```
 ///     # #[macro_use] extern crate amplify_derive;
@@ -116,8 +221,41 @@ use syn::DeriveInput;
 ///     assert_eq!(format!("{}", Point { x: 0, y: 1 }), "(0, 1)");
 ///     assert_eq!(format!("{:#}", Point { x: 0, y: 1 }), "0:1");
 ///    ```
+///    Enum-level format strings are applied to each of the variants, with
+///    placeholders resolved against the variant fields. Variants may override
+///    both format strings, or just the alternative one:
+///    ```
+///     # #[macro_use] extern crate amplify_derive;
+///     # #[macro_use] extern crate amplify;
+///     #[derive(Display)]
+///     #[display("{0}", alt = "{0} ({1} bytes)")]
+///     enum Data {
+///         Script(String, usize),
+///         #[display(alt = "witness {0} of {1} bytes")]
+///         Witness(String, usize),
+///         #[display("<{1}>", alt = "<{1} hidden>")]
+///         Hidden(String, usize),
+///     }
+///
+///     assert_eq!(format!("{}", Data::Script(s!("a"), 5)), "a");
+///     assert_eq!(format!("{:#}", Data::Script(s!("a"), 5)), "a (5 bytes)");
+///     assert_eq!(format!("{}", Data::Witness(s!("w"), 3)), "w");
+///     assert_eq!(format!("{:#}", Data::Witness(s!("w"), 3)), "witness w of 3 bytes");
+///     assert_eq!(format!("{}", Data::Hidden(s!("h"), 1)), "<1>");
+///     assert_eq!(format!("{:#}", Data::Hidden(s!("h"), 1)), "<1 hidden>");
+///    ```
+///    Alternative format string without the primary one to pair with results
+///    in a compile-time error:
+///    ```compile_fail
+///     # #[macro_use] extern crate amplify_derive;
+///     #[derive(Display)]
+///     #[display(alt = "{x}:{y}")]
+///     struct Point { x: u32, y: u32 }
+///    ```
 /// 5. Use of doc comments for descrition representation. In this case doc
-///    comments may also contain formatting like in the case 3:
+///    comments may also contain formatting like in the case 3. Only the first
+///    paragraph of the doc comments is used, with its lines joined by spaces;
+///    variants without doc comments are displayed with their names:
 ///    ```
 ///     # #[macro_use] extern crate amplify_derive;
 ///     #[macro_use] extern crate amplify;
@@ -128,11 +266,9 @@ use syn::DeriveInput;
 ///     enum Variants {
 ///         /// Letter A.
 ///         /// Multiline comments are also working, but joined together
+///         /// \nYou may also use this way to add a line break
 ///         ///
-///         /// Empty line is replaced with line break
-///         /// \nYou may also use this way
-///         /// \n
-///         /// The above will still result in a single line break
+///         /// Further paragraphs are not displayed
 ///         A,
 ///         /// Letter B
 ///         B,
@@ -144,17 +280,25 @@ use syn::DeriveInput;
 ///         /// You can omit parameters and just have a normal doc comment
 ///         Number(u8),
 ///         /// ... for variants with named fields as well
-///         Named { some: String }
+///         Named { some: String },
+///         /// Amount {value} exceeds
+///         /// the limit of {limit}
+///         Excess { value: u64, limit: u64 },
+///         Undocumented,
 ///     };
 ///
 ///     assert_eq!(
 ///         format!("{}", Variants::A),
 ///         "Letter A. Multiline comments are also working, but joined \
-///         together\nEmpty line is replaced with line break\nYou may also use \
-///         this way\nThe above will still result in a single line break"
+///         together\nYou may also use this way to add a line break"
 ///     );
 ///     assert_eq!(format!("{}", Variants::C), "Letter C");
 ///     assert_eq!(format!("{}", Variants::Letter(s!("K"))), "Letter K");
+///     assert_eq!(
+///         format!("{}", Variants::Excess { value: 5, limit: 3 }),
+///         "Amount 5 exceeds the limit of 3"
+///     );
+///     assert_eq!(format!("{}", Variants::Undocumented), "Undocumented");
 ///    ```
 ///    You can also mix in this mode with other fors of display tags on a
 ///    specific options; in this case doc comments are ignored
@@ -166,6 +310,10 @@ use syn::DeriveInput;
 ///     #[display(doc_comments)]
 ///     pub struct UnitStruct;
 ///    ```
+///    Structures displayed with `doc_comments` must be documented; doc
+///    attributes which are not string literals (like
+///    `#[doc = include_str!("...")]`) are skipped, since derive macros can't
+///    read them.
 /// 7. Print the name of enum variant in lowercase/uppercase:
 ///    ```
 ///     # #[macro_use] extern crate amplify_derive;
@@ -193,6 +341,105 @@ use syn::DeriveInput;
 ///     assert_eq!(format!("{}", Event::Init), "INIT");
 ///     assert_eq!(format!("{}", Event::Load(Message::ChangeColor(0, 255, 0))), "LOAD(changecolor(0, 255, 0))");
 ///    ```
+///    Other supported case transformations are `snake_case`, `kebab_case`
+///    (producing `kebab-case` output) and `SCREAMING_SNAKE_CASE`. Acronyms are
+///    kept together as a single word. Case transformation applies to the
+///    variant name only; variant fields are appended in the same way as with
+///    `lowercase`. An explicit format string on a variant overrides the mode:
+///    ```
+///     # #[macro_use] extern crate amplify_derive;
+///     #[derive(Display)]
+///     #[display(snake_case)]
+///     enum Alert {
+///         TLSError,
+///         CloseNotify,
+///         #[display("bad-record")]
+///         BadRecordMac,
+///         Unknown(u8),
+///     }
+///
+///     #[derive(Display)]
+///     #[display(kebab_case)]
+///     enum Method {
+///         GetBlockHash,
+///     }
+///
+///     #[derive(Display)]
+///     #[display(SCREAMING_SNAKE_CASE)]
+///     enum Level {
+///         MaxHTLCValue,
+///     }
+///
+///     assert_eq!(format!("{}", Alert::TLSError), "tls_error");
+///     assert_eq!(format!("{}", Alert::CloseNotify), "close_notify");
+///     assert_eq!(format!("{}", Alert::BadRecordMac), "bad-record");
+///     assert_eq!(format!("{}", Alert::Unknown(5)), "unknown(5)");
+///     assert_eq!(format!("{}", Method::GetBlockHash), "get-block-hash");
+///     assert_eq!(format!("{}", Level::MaxHTLCValue), "MAX_HTLC_VALUE");
+///    ```
+///    Unknown case transformation modes (like `camel_case`) are rejected at
+///    compile time.
+/// 8. Generic types get trait bounds only for the fields which are actually
+///    formatted (for enums – in any of the variants); each field type
+///    depending on a type parameter is required to implement the trait used
+///    to format it:
+///    ```
+///     # #[macro_use] extern crate amplify_derive;
+///     # #[macro_use] extern crate amplify;
+///     struct Payload;
+///
+///     #[derive(Display)]
+///     #[display("{1}")]
+///     struct Tagged<T>(T, String);
+///
+///     #[derive(Display)]
+///     #[display(LowerHex)]
+///     struct Id<T>(T);
+///
+///     #[derive(Debug, Display)]
+///     #[display(Debug)]
+///     struct Opaque<T>(T);
+///
+///     #[derive(Display)]
+///     enum Either<A, B, C> {
+///         #[display("left {0}")]
+///         Left(A),
+///         #[display("right {0:?}")]
+///         Right(B),
+///         #[display("other")]
+///         Other(C),
+///     }
+///
+///     let tagged = Tagged(Payload, s!("tag"));
+///     assert_eq!(format!("{}", tagged), "tag");
+///     assert_eq!(format!("{}", Id(255u8)), "ff");
+///     assert_eq!(format!("{}", Opaque(5u8)), "Opaque(5)");
+///     type Choice = Either<u8, &'static str, Payload>;
+///     assert_eq!(format!("{}", Choice::Left(1)), "left 1");
+///     assert_eq!(format!("{}", Choice::Right("x")), "right \"x\"");
+///     assert_eq!(format!("{}", Choice::Other(Payload)), "other");
+///    ```
+///    Bounds which can't be inferred (for instance, the ones required by
+///    formatting functions) can be given explicitly with `bound` argument,
+///    which replaces the inferred bounds:
+///    ```
+///     # #[macro_use] extern crate amplify_derive;
+///     use std::fmt::Display;
+///
+///     #[derive(Display)]
+///     #[display(Pair::describe)]
+///     #[display(bound = "T: Display + Clone")]
+///     struct Pair<T>(T, T);
+///
+///     impl<T: Display + Clone> Pair<T> {
+///         fn describe(&self) -> String {
+///             let first = self.0.clone();
+///             format!("{} & {}", first, self.1)
+///         }
+///     }
+///
+///     assert_eq!(format!("{}", Pair(1, 2)), "1 & 2");
+///    ```
 /// # Example
 ///
 /// Advanced use with enums:
@@ -280,6 +527,162 @@ use syn::DeriveInput;
 ///     "127.0.0.1"
 /// );
 /// ```
+///
+/// If a structure or enum variant has multiple fields, the field used for
+/// `inner` representation must be marked with `#[display(delegate)]`; this is
+/// required for each of the multi-field enum variants independently:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// # #[macro_use] extern crate amplify;
+/// #[derive(Clone, Debug, Display)]
+/// #[display(inner)]
+/// struct Labeled {
+///     #[display(delegate)]
+///     name: String,
+///     id: u32,
+/// }
+///
+/// #[derive(Clone, Debug, Display)]
+/// #[display(inner)]
+/// enum Message {
+///     Ping,
+///     Text(String),
+///     Reply(u64, #[display(delegate)] String),
+///     #[display("#{id}")]
+///     Ack { id: u64, time: u64 },
+/// }
+///
+/// assert_eq!(Labeled { name: s!("alice"), id: 1 }.to_string(), "alice");
+/// assert_eq!(Message::Ping.to_string(), "Ping");
+/// assert_eq!(Message::Text(s!("hi")).to_string(), "hi");
+/// assert_eq!(Message::Reply(5, s!("ok")).to_string(), "ok");
+/// assert_eq!(Message::Ack { id: 5, time: 0 }.to_string(), "#5");
+/// ```
+///
+/// Missing marker on a multi-field item, as well as multiple marked fields,
+/// result in a compile-time error:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Display)]
+/// #[display(inner)]
+/// struct Pair(u8, u16);
+/// ```
+///
+/// When the inner field is a reference or a well-known smart pointer (`Box`,
+/// `Rc`, `Arc` or `Cow`), formatting is delegated directly to the pointee,
+/// which is required to implement `Display` instead of the pointer type. This
+/// allows unsized pointees, like trait objects or `T: ?Sized` type
+/// parameters. Other smart pointers can be dereferenced before formatting with
+/// `#[display(inner_deref)]`:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// use std::borrow::Cow;
+/// use std::fmt::Display;
+/// use std::rc::Rc;
+/// use std::sync::Arc;
+///
+/// #[derive(Display)]
+/// #[display(inner)]
+/// struct Boxed(Box<dyn Display>);
+///
+/// #[derive(Display)]
+/// #[display(inner)]
+/// struct Shared<T: ?Sized>(Arc<T>);
+///
+/// #[derive(Display)]
+/// #[display(inner)]
+/// struct Borrowed<'a, T: ?Sized + ToOwned>(Cow<'a, T>);
+///
+/// #[derive(Display)]
+/// #[display(inner)]
+/// enum Ref<'a, T: ?Sized> {
+///     Empty,
+///     Local(Rc<T>),
+///     Ref(&'a T),
+/// }
+///
+/// #[derive(Display)]
+/// #[display(inner_deref)]
+/// struct Manual<T>(std::mem::ManuallyDrop<T>);
+///
+/// assert_eq!(Boxed(Box::new(5u8)).to_string(), "5");
+/// assert_eq!(format!("{:>4}", Boxed(Box::new("ab"))), "  ab");
+/// assert_eq!(Shared::<str>(Arc::from("shared")).to_string(), "shared");
+/// assert_eq!(Borrowed::<str>(Cow::Borrowed("cow")).to_string(), "cow");
+/// assert_eq!(Ref::<u8>::Empty.to_string(), "Empty");
+/// assert_eq!(Ref::<str>::Local(Rc::from("rc")).to_string(), "rc");
+/// assert_eq!(Ref::<str>::Ref("ref").to_string(), "ref");
+/// assert_eq!(Manual(std::mem::ManuallyDrop::new(7)).to_string(), "7");
+/// ```
+///
+/// Integer fields can be formatted in a human-friendly way with the
+/// `amplify::num::decimal::DisplayDecimal` adaptor (which requires `amplify`
+/// crate dependency). Placeholders with `grouped` format spec, like
+/// `{0:grouped}` or `{amount:>12grouped}`, separate each three digits of the
+/// integer part with `,` or a separator given with `separator = "..."`
+/// argument. `scale = N` argument divides the value by `10^N` and renders
+/// exactly `N` fractional digits, and `unit = "..."` appends the unit name.
+/// The arguments can be given for a field, or for a type or enum variant, in
+/// which case they apply to the inner field (see `display(inner)` above);
+/// `separator` used as an argument turns grouping on for all placeholders of
+/// the field. Without a format string the arguments imply `display(inner)`.
+///
+/// Any field type which `Display` implementation produces an integer number
+/// is supported, including big integers like `amplify::num::u256`. Formatting
+/// does not use floating point arithmetics: scaling only shifts the decimal
+/// point, so the values are never rounded, and the sign of negative values
+/// precedes grouped digits:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// # extern crate amplify;
+/// use amplify::num::u256;
+///
+/// #[derive(Display)]
+/// #[display(scale = 3, unit = "sat", separator = " ")]
+/// struct MilliSats(u64);
+///
+/// #[derive(Display)]
+/// #[display("{0:grouped}")]
+/// struct Count(i64);
+///
+/// #[derive(Display)]
+/// #[display("{amount} (fee {fee:grouped})")]
+/// struct Payment {
+///     #[display(scale = 8, unit = "BTC")]
+///     amount: i64,
+///     fee: u256,
+/// }
+///
+/// #[derive(Display)]
+/// #[display(scale = 2)]
+/// enum Price {
+///     #[display("{0} USD")]
+///     Usd(u32),
+///     #[display("{1:grouped}", separator = "'")]
+///     Chf(&'static str, #[display(delegate)] u64),
+/// }
+///
+/// assert_eq!(MilliSats(1_234_567).to_string(), "1 234.567 sat");
+/// assert_eq!(MilliSats(0).to_string(), "0.000 sat");
+/// assert_eq!(MilliSats(7).to_string(), "0.007 sat");
+/// assert_eq!(MilliSats(999).to_string(), "0.999 sat");
+/// assert_eq!(
+///     MilliSats(u64::MAX).to_string(),
+///     "18 446 744 073 709 551.615 sat"
+/// );
+/// assert_eq!(Count(-1_000_000).to_string(), "-1,000,000");
+/// assert_eq!(Count(i64::MIN).to_string(), "-9,223,372,036,854,775,808");
+/// let payment = Payment {
+///     amount: -150_000,
+///     fee: u256::from(u64::MAX) * u256::from(1000u32),
+/// };
+/// assert_eq!(
+///     payment.to_string(),
+///     "-0.00150000 BTC (fee 18,446,744,073,709,551,615,000)"
+/// );
+/// assert_eq!(Price::Usd(99).to_string(), "0.99 USD");
+/// assert_eq!(Price::Chf("chf", 123_456_789).to_string(), "1'234'567.89");
+/// ```
 #[proc_macro_derive(Display, attributes(display))]
 pub fn derive_display(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
@@ -288,6 +691,67 @@ pub fn derive_display(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Derives [`std::str::FromStr`] for fieldless enums, accepting exactly the
+/// same strings which are produced by the [`Display`] derive macro for each of
+/// the enum variants. This includes per-variant `#[display("...")]` strings,
+/// `#[display(doc_comments)]` and case transformation modes like
+/// `#[display(lowercase)]`. Parse failures are reported with a [`String`]
+/// error describing the unrecognized value.
+///
+/// Additional spellings may be accepted with `#[from_str(alias = "...")]`
+/// variant-level attribute, which may be given multiple times. Case-insensitive
+/// parsing is enabled with `#[from_str(case_insensitive)]` on the enum.
+///
+/// # Errors
+///
+/// Variants with fields, enum-level format strings and `display` attributes
+/// using formatting traits or functions result in a compile-time error, since
+/// the string representation of the variants can't be known in advance.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// use std::str::FromStr;
+///
+/// #[derive(Copy, Clone, PartialEq, Eq, Debug, Display, FromStr)]
+/// #[display(kebab_case)]
+/// #[from_str(case_insensitive)]
+/// enum Network {
+///     Mainnet,
+///     #[from_str(alias = "test", alias = "testnet3")]
+///     Testnet,
+///     #[display("regtest")]
+///     RegressionTest,
+/// }
+///
+/// assert_eq!(Network::from_str("mainnet"), Ok(Network::Mainnet));
+/// assert_eq!(Network::from_str("Testnet3"), Ok(Network::Testnet));
+/// assert_eq!(Network::from_str("regtest"), Ok(Network::RegressionTest));
+/// assert_eq!(
+///     Network::from_str(&Network::Testnet.to_string()),
+///     Ok(Network::Testnet)
+/// );
+/// assert!(Network::from_str("signet").is_err());
+/// ```
+///
+/// Variants with fields can't be parsed:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Display, FromStr)]
+/// #[display(lowercase)]
+/// enum Value {
+///     Number(u8),
+/// }
+/// ```
+#[proc_macro_derive(FromStr, attributes(display, from_str))]
+pub fn derive_from_str(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    from_str::inner(derive_input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
 /// Error derive macro works to the full extend only when other derive macros
 /// are used. With `#[derive(Display)]` and `[display(doc_comments)]` it uses
 /// doc comments for generating error descriptions; with `#[derive(From)]` it
@@ -315,7 +779,309 @@ pub fn derive_display(input: TokenStream) -> TokenStream {
 ///     "Zero division with 2"
 /// );
 /// ```
-#[proc_macro_derive(Error)]
+///
+/// Error source returned by [`std::error::Error::source`] is the field marked
+/// with `#[source]` attribute; if none of the fields is marked, the field
+/// converted from with `#[from]` is used. Optional sources (fields of
+/// `Option<E>` type) are supported as well:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// use std::error::Error as _;
+/// use std::{fmt, io};
+///
+/// #[derive(Debug, Display, Error)]
+/// #[display("can't read {path}")]
+/// pub struct ReadError {
+///     path: String,
+///     #[source]
+///     io: io::Error,
+/// }
+///
+/// #[derive(Debug, Display, Error)]
+/// #[display("can't write {path}")]
+/// pub struct WriteError {
+///     path: String,
+///     #[source]
+///     io: Option<io::Error>,
+/// }
+///
+/// #[derive(Debug, Display, Error, From)]
+/// #[display(doc_comments)]
+/// pub enum OpenError {
+///     /// I/O error
+///     #[from(io::Error, io::ErrorKind)]
+///     Io(io::Error),
+/// }
+///
+/// #[derive(Debug, Display, Error, From)]
+/// #[display(doc_comments)]
+/// pub enum Error {
+///     /// I/O error
+///     Io(#[from] io::Error),
+///     /// formatting error
+///     #[from]
+///     Format(fmt::Error),
+///     /// read error
+///     Read(#[source] ReadError, u8),
+///     /// optional error
+///     Optional {
+///         details: String,
+///         #[source]
+///         cause: Option<fmt::Error>,
+///     },
+///     /// no error source
+///     Other,
+/// }
+///
+/// let err = Error::from(fmt::Error);
+/// assert_eq!(err.source().unwrap().to_string(), fmt::Error.to_string());
+/// let err = ReadError {
+///     path: "/tmp".to_string(),
+///     io: io::Error::new(io::ErrorKind::NotFound, "not found"),
+/// };
+/// assert_eq!(err.source().unwrap().to_string(), "not found");
+/// let err = Error::Read(err, 0);
+/// assert_eq!(err.source().unwrap().to_string(), "can't read /tmp");
+/// let err = Error::Optional { details: String::new(), cause: None };
+/// assert!(err.source().is_none());
+/// let err = Error::Optional { details: String::new(), cause: Some(fmt::Error) };
+/// assert!(err.source().is_some());
+/// assert!(Error::Other.source().is_none());
+/// let err = OpenError::from(io::ErrorKind::NotFound);
+/// assert!(err.source().is_some());
+/// let err = WriteError { path: "/tmp".to_string(), io: None };
+/// assert!(err.source().is_none());
+/// let err = WriteError {
+///     path: "/tmp".to_string(),
+///     io: Some(io::Error::new(io::ErrorKind::NotFound, "not found")),
+/// };
+/// assert_eq!(err.source().unwrap().to_string(), "not found");
+/// ```
+///
+/// Single-field enum variants marked with `#[error(transparent)]` delegate
+/// both [`Display`] (when used together with `#[derive(Display)]`) and
+/// [`std::error::Error::source`] to the inner error, such that the variant
+/// adds no extra layer to the error chain:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// use std::error::Error as StdError;
+/// use std::io;
+///
+/// #[derive(Debug, Display, Error)]
+/// #[display("can't read {path}")]
+/// pub struct ReadError {
+///     path: &'static str,
+///     #[source]
+///     io: io::Error,
+/// }
+///
+/// #[derive(Debug, Display, Error, From)]
+/// pub enum StorageError {
+///     #[error(transparent)]
+///     Read(#[from] ReadError),
+///     #[display("storage is full")]
+///     Full,
+/// }
+///
+/// #[derive(Debug, Display, Error, From)]
+/// pub enum AppError {
+///     #[display("storage failure")]
+///     Storage(#[from] StorageError),
+/// }
+///
+/// fn load() -> Result<(), AppError> {
+///     let io = io::Error::new(io::ErrorKind::NotFound, "not found");
+///     Err(StorageError::from(ReadError { path: "/tmp", io }))?;
+///     Ok(())
+/// }
+///
+/// let err = load().unwrap_err();
+/// let mut chain = vec![err.to_string()];
+/// let mut source = err.source();
+/// while let Some(err) = source {
+///     chain.push(err.to_string());
+///     source = err.source();
+/// }
+/// assert_eq!(chain, vec!["storage failure", "can't read /tmp", "not found"]);
+/// ```
+///
+/// Generic error types are bounded only with the traits actually required:
+/// type parameters used in the error sources must implement
+/// [`std::error::Error`], while the rest of the parameters are required to
+/// satisfy just the bounds of the `Debug` and `Display` implementations.
+/// Fields which are converted from with `#[from]`, but must not be used as
+/// error sources, can be marked with `#[error(not_source)]` (the same
+/// attribute on an enum variant disables its error source). Bounds may also be
+/// given explicitly with `#[error(bound = "...")]`, which replaces the
+/// inferred ones:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// use std::error::Error as StdError;
+/// use std::io;
+///
+/// #[derive(Debug, Display, Error, From)]
+/// pub enum Failure<T, E> {
+///     #[display("can't parse {0}")]
+///     Parse(T),
+///     #[display("data {0} failed")]
+///     Data(T, #[source] E),
+///     #[display("code {0}")]
+///     Code(#[from] #[error(not_source)] u16),
+/// }
+///
+/// // `u8` does not implement `Error`, but it is not used as an error source
+/// let err = Failure::<u8, io::Error>::Parse(5);
+/// assert_eq!(err.to_string(), "can't parse 5");
+/// assert!(err.source().is_none());
+/// let err = Failure::<u8, io::Error>::Data(7, io::Error::from(io::ErrorKind::Other));
+/// assert!(err.source().is_some());
+/// assert!(Failure::<u8, io::Error>::from(404).source().is_none());
+///
+/// #[derive(Debug, Display, Error)]
+/// #[display("{0:?}")]
+/// #[error(bound = "T: std::fmt::Debug")]
+/// pub struct Opaque<T>(T);
+///
+/// assert_eq!(Opaque(1).to_string(), "1");
+/// ```
+///
+/// Transparent variants must have exactly one field:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Debug, Display, Error)]
+/// pub enum Error {
+///     #[error(transparent)]
+///     Io(std::io::Error, u8),
+/// }
+/// ```
+///
+/// Only a single field may be the source:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Debug, Display, Error)]
+/// #[display("double error")]
+/// pub struct Double {
+///     #[source]
+///     first: std::fmt::Error,
+///     #[source]
+///     second: std::fmt::Error,
+/// }
+/// ```
+///
+/// Enum variants may be given numeric codes and categories with
+/// `#[error(code = ..., category = "...")]` attributes, for which the derive
+/// macro generates inherent `fn code(&self) -> u16` and
+/// `fn category(&self) -> &'static str` methods, together with
+/// `fn from_code(code: u16) -> Option<Self>` constructor. The constructor
+/// produces only unit variants: variants with data can't be constructed from a
+/// code and their codes result in `None`.
+///
+/// The code type may be changed to `u8` or `u32` with enum-level
+/// `#[error(code_type = "...")]` attribute. Enum-level `category = "..."`
+/// provides category for the variants without their own one (otherwise such
+/// variants have empty category). With enum-level `#[error(code_offset = ...)]`
+/// unit variants without explicit codes are numbered automatically: the first
+/// variant gets `code_offset`, and each of the following ones – the code of
+/// the previous variant incremented by one. Variants with data always require
+/// explicit codes.
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Copy, Clone, PartialEq, Eq, Debug, Display, Error)]
+/// #[display(doc_comments)]
+/// #[error(code_offset = 0x0100, category = "general")]
+/// enum Failure {
+///     /// unknown failure
+///     Unknown,
+///     /// timeout
+///     Timeout,
+///     /// no route to the node
+///     #[error(code = 0x0201, category = "routing")]
+///     NoRoute,
+///     /// channel is closed
+///     ChannelClosed,
+///     /// insufficient funds: {0} sats available
+///     #[error(code = 0x0301, category = "funds")]
+///     Insufficient(u64),
+///     /// fee {fee} exceeds the limit
+///     #[error(code = 0x0302, category = "funds")]
+///     FeeTooHigh { fee: u64 },
+/// }
+///
+/// assert_eq!(Failure::Unknown.code(), 0x0100);
+/// assert_eq!(Failure::Timeout.code(), 0x0101);
+/// assert_eq!(Failure::NoRoute.code(), 0x0201);
+/// assert_eq!(Failure::ChannelClosed.code(), 0x0202);
+/// assert_eq!(Failure::Insufficient(5).code(), 0x0301);
+/// assert_eq!(Failure::FeeTooHigh { fee: 1 }.code(), 0x0302);
+///
+/// assert_eq!(Failure::Timeout.category(), "general");
+/// assert_eq!(Failure::NoRoute.category(), "routing");
+/// assert_eq!(Failure::ChannelClosed.category(), "general");
+/// assert_eq!(Failure::Insufficient(5).category(), "funds");
+///
+/// assert_eq!(Failure::from_code(0x0100), Some(Failure::Unknown));
+/// assert_eq!(Failure::from_code(0x0101), Some(Failure::Timeout));
+/// assert_eq!(Failure::from_code(0x0201), Some(Failure::NoRoute));
+/// assert_eq!(Failure::from_code(0x0202), Some(Failure::ChannelClosed));
+/// assert_eq!(Failure::from_code(0x0301), None);
+/// assert_eq!(Failure::from_code(0x0302), None);
+/// assert_eq!(Failure::from_code(0x0102), None);
+/// for err in [Failure::Unknown, Failure::NoRoute, Failure::ChannelClosed] {
+///     assert_eq!(Failure::from_code(err.code()), Some(err));
+/// }
+///
+/// #[derive(Debug, Display, Error)]
+/// #[display(Debug)]
+/// #[error(code_type = "u8")]
+/// enum Short {
+///     #[error(code = 1)]
+///     First,
+///     #[error(code = 255)]
+///     Last,
+/// }
+///
+/// let code: u8 = Short::Last.code();
+/// assert_eq!(code, 255);
+/// assert!(matches!(Short::from_code(1), Some(Short::First)));
+/// ```
+///
+/// Codes must be unique; duplicated codes result in a compile-time error
+/// naming both variants. The same applies to the codes which do not fit the
+/// code type, and to the variants with data missing explicit codes:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Debug, Display, Error)]
+/// #[display(Debug)]
+/// #[error(code_offset = 1)]
+/// enum Failure {
+///     First,
+///     #[error(code = 1)]
+///     Second,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Debug, Display, Error)]
+/// #[display(Debug)]
+/// #[error(code_type = "u8")]
+/// enum Failure {
+///     #[error(code = 256)]
+///     Overflow,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Debug, Display, Error)]
+/// #[display(Debug)]
+/// #[error(code_offset = 1)]
+/// enum Failure {
+///     Unit,
+///     Data(u8),
+/// }
+/// ```
+#[proc_macro_derive(Error, attributes(source, error))]
 pub fn derive_error(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
     error::inner(derive_input)
@@ -372,12 +1138,206 @@ pub fn derive_error(input: TokenStream) -> TokenStream {
 /// pub struct Wrapper(u32, i16);
 /// ```
 ///
-/// If you use rust nightly and `#![feature(never_type)]` for [`!`], you can
-/// even do the following:
-/// ```ignore
-/// #![feature(never_type)]
+/// In multi-field structures a single field may be marked with `#[from]`;
+/// the rest of the fields are initialized with their [`Default`]
+/// implementation, or with an explicit expression given via
+/// `#[from(default = ...)]`:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// # #[derive(Debug, PartialEq)]
+/// pub struct Payload(Vec<u8>);
 ///
-/// #[macro_use]
+/// #[derive(From)]
+/// pub struct Request {
+///     id: u64,
+///     #[from]
+///     payload: Payload,
+///     #[from(default = 3)]
+///     retries: u8,
+/// }
+///
+/// let request = Request::from(Payload(vec![0xde, 0xad]));
+/// assert_eq!(request.id, 0);
+/// assert_eq!(request.payload, Payload(vec![0xde, 0xad]));
+/// assert_eq!(request.retries, 3);
+/// ```
+///
+/// Fields without default value must implement [`Default`]:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// pub struct Payload(Vec<u8>);
+/// pub struct Session;
+///
+/// #[derive(From)]
+/// pub struct Request {
+///     session: Session,
+///     #[from]
+///     payload: Payload,
+/// }
+/// ```
+///
+/// Multiple source types may be listed in a single attribute; each of them
+/// gets its own `From` implementation, converting into the field type with
+/// [`Into`]:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// use std::io;
+///
+/// pub struct IoFailure(io::ErrorKind);
+///
+/// impl From<io::Error> for IoFailure {
+///     fn from(err: io::Error) -> Self { IoFailure(err.kind()) }
+/// }
+///
+/// impl From<io::ErrorKind> for IoFailure {
+///     fn from(kind: io::ErrorKind) -> Self { IoFailure(kind) }
+/// }
+///
+/// #[derive(From)]
+/// pub enum Error {
+///     #[from(io::Error, io::ErrorKind)]
+///     Io(IoFailure),
+///
+///     #[from]
+///     Format(::std::fmt::Error),
+/// }
+///
+/// let err = Error::from(io::ErrorKind::NotFound);
+/// assert!(matches!(err, Error::Io(IoFailure(io::ErrorKind::NotFound))));
+/// let err = Error::from(io::Error::from(io::ErrorKind::Other));
+/// assert!(matches!(err, Error::Io(IoFailure(io::ErrorKind::Other))));
+/// ```
+///
+/// With `ref` flag conversions from references to the source types are also
+/// derived, cloning the referenced value; the source types must implement
+/// [`Clone`]:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Clone, PartialEq, Eq, Debug)]
+/// pub struct Key([u8; 4]);
+///
+/// #[derive(From, PartialEq, Eq, Debug)]
+/// pub struct Wrapper(#[from(ref)] Key);
+///
+/// #[derive(From, PartialEq, Eq, Debug)]
+/// pub enum Id {
+///     #[from(ref)]
+///     Name(String),
+///     #[from(ref, u8)]
+///     Index(u16),
+/// }
+///
+/// let key = Key([1, 2, 3, 4]);
+/// assert_eq!(Wrapper::from(&key), Wrapper(key.clone()));
+/// assert_eq!(Id::from(&String::from("alice")), Id::Name(String::from("alice")));
+/// assert_eq!(Id::from(&7u8), Id::Index(7));
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// pub struct Key([u8; 4]);
+///
+/// #[derive(From)]
+/// pub struct Wrapper(#[from(ref)] Key); // `Key` is not `Clone`
+/// ```
+///
+/// Enums may request conversions into all variants having a single field
+/// with enum-level `#[from(all_single_field)]`. Variants with explicit
+/// `#[from]` attributes are left as is; variants which must not be converted
+/// into are marked with `#[from(skip)]`. Unit and multi-field variants are
+/// skipped:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// use std::fmt::Debug;
+/// use std::{fmt, io};
+///
+/// #[derive(From, Debug)]
+/// #[from(all_single_field)]
+/// pub enum Error<T: Debug> {
+///     Io(io::Error),
+///     Format { source: fmt::Error },
+///     #[from(u8)]
+///     Code(u16),
+///     #[from(skip)]
+///     Message(String),
+///     Parse(std::num::ParseIntError, String),
+///     Unknown,
+///     Custom(Vec<T>),
+/// }
+///
+/// fn read() -> Result<(), Error<char>> {
+///     Err(io::Error::from(io::ErrorKind::NotFound))?
+/// }
+///
+/// assert!(matches!(read(), Err(Error::Io(_))));
+/// assert!(matches!(Error::<char>::from(fmt::Error), Error::Format { .. }));
+/// assert!(matches!(Error::<char>::from(5u8), Error::Code(5)));
+/// assert!(matches!(Error::from(vec!['a']), Error::Custom(_)));
+///
+/// #[derive(From, PartialEq, Debug)]
+/// #[from(all_single_field)]
+/// pub enum Value<T> {
+///     None,
+///     Custom(T),
+///     Pair(T, T),
+/// }
+///
+/// assert_eq!(Value::from(5u8), Value::Custom(5u8));
+/// ```
+///
+/// Variants wrapping the same type result in a compile-time error, unless
+/// all but one of them are marked with `#[from(skip)]`:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(From)]
+/// #[from(all_single_field)]
+/// pub enum Error {
+///     Read(std::io::Error),
+///     Write(std::io::Error),
+/// }
+/// ```
+///
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(From)]
+/// #[from(all_single_field)]
+/// pub enum Error {
+///     Read(std::io::Error),
+///     #[from(skip)]
+///     Write(std::io::Error),
+/// }
+/// ```
+///
+/// Conversion from the same type can't be derived more than once, including
+/// the cases of different enum variants listing the same type:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(From)]
+/// pub enum Error {
+///     #[from(u8, u16)]
+///     Small(u32),
+///     #[from(u16)]
+///     Medium(u32),
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(From)]
+/// pub struct Pair {
+///     #[from]
+///     first: u8,
+///     #[from]
+///     second: u8,
+/// }
+/// ```
+///
+/// If you use rust nightly and `#![feature(never_type)]` for [`!`], you can
+/// even do the following:
+/// ```ignore
+/// #![feature(never_type)]
+///
+/// #[macro_use]
 /// extern crate amplify_derive;
 ///
 /// #[derive(From)]
@@ -398,6 +1358,487 @@ pub fn derive_from(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Implements [`TryFrom`] trait for C-like enums, matching on the enum
+/// discriminants, and for newtypes with a validating constructor. Each
+/// `#[try_from(...)]` attribute produces a separate implementation for the
+/// source type given as the first argument.
+///
+/// # Attribute arguments
+///
+/// - `Error = Type`: required error type of the conversion;
+/// - `error_expr = expr`: for enums, an expression constructing the error for
+///   the unknown values, which may use the converted `value`; by default
+///   the error is constructed with `From` conversion from the source type;
+/// - `with = path`: for newtypes, a validating function taking the source
+///   value and returning `Result<Self, Error>`; by default an inherent
+///   `Self::try_from_inner` function is called.
+///
+/// For newtypes the source type may be omitted, in which case the type of the
+/// wrapped field is used.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// use std::convert::TryFrom;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// pub struct UnknownValue(u8);
+///
+/// impl From<u8> for UnknownValue {
+///     fn from(value: u8) -> Self { UnknownValue(value) }
+/// }
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Debug, TryFrom)]
+/// #[try_from(u8, Error = UnknownValue)]
+/// #[try_from(u16, Error = UnknownValue, error_expr = UnknownValue(value as u8))]
+/// #[repr(u8)]
+/// pub enum Network {
+///     Mainnet = 1,
+///     Testnet = 3,
+///     Signet,
+/// }
+///
+/// assert_eq!(Network::try_from(3u8), Ok(Network::Testnet));
+/// assert_eq!(Network::try_from(4u8), Ok(Network::Signet));
+/// assert_eq!(Network::try_from(2u8), Err(UnknownValue(2)));
+/// assert_eq!(Network::try_from(9u16), Err(UnknownValue(9)));
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Debug, TryFrom)]
+/// #[try_from(Error = UnknownValue)]
+/// #[try_from(u16, Error = UnknownValue, with = Percent::from_wide)]
+/// pub struct Percent(u8);
+///
+/// impl Percent {
+///     fn try_from_inner(value: u8) -> Result<Self, UnknownValue> {
+///         if value > 100 {
+///             return Err(UnknownValue(value));
+///         }
+///         Ok(Percent(value))
+///     }
+///
+///     fn from_wide(value: u16) -> Result<Self, UnknownValue> {
+///         Self::try_from_inner(value as u8)
+///     }
+/// }
+///
+/// assert_eq!(Percent::try_from(42u8), Ok(Percent(42)));
+/// assert_eq!(Percent::try_from(142u8), Err(UnknownValue(142)));
+/// assert_eq!(Percent::try_from(7u16), Ok(Percent(7)));
+/// ```
+///
+/// Error type is required:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(TryFrom)]
+/// #[try_from(u8)]
+/// pub enum Network {
+///     Mainnet = 1,
+///     Testnet = 3,
+/// }
+/// ```
+///
+/// Overlapping discriminants are reported:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// # pub struct UnknownValue;
+/// # impl From<u8> for UnknownValue { fn from(_: u8) -> Self { UnknownValue } }
+/// #[derive(TryFrom)]
+/// #[try_from(u8, Error = UnknownValue)]
+/// pub enum Network {
+///     Mainnet = 1,
+///     Testnet = 0,
+///     Signet,
+/// }
+/// ```
+#[proc_macro_derive(TryFrom, attributes(try_from))]
+pub fn derive_try_from(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    try_from::inner(derive_input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derives [`Default`] for enums and structures.
+///
+/// Unlike the `core` derive, it works for enums with any variant being the
+/// default one, including variants carrying data: all fields of the default
+/// variant are constructed with `Default::default()`. The default variant is
+/// marked with `#[default]` attribute, or, alternatively, named by the
+/// type-level `#[default(Variant)]` attribute. Enums must have exactly one
+/// default variant; none or several of them result in a compile-time error.
+///
+/// For structures the derived implementation constructs each of the fields
+/// with `Default::default()`. The value of individual fields (including
+/// fields of the default enum variant) may be overridden with
+/// `#[default(expr)]` attribute, where the expression may be any valid Rust
+/// expression of the field type. Literal values may also be given in
+/// `#[default = literal]` form.
+///
+/// As with the `core` derive, all type parameters are required to implement
+/// `Default`. The derive is named `EnumDefault` so that it doesn't shadow the
+/// `core` one when imported.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(EnumDefault, PartialEq, Debug)]
+/// enum Network {
+///     Mainnet,
+///     Testnet,
+///     #[default]
+///     Regtest(u16),
+/// }
+///
+/// #[derive(EnumDefault, PartialEq, Debug)]
+/// #[default(Local)]
+/// enum Peer<A> {
+///     Remote { addr: A, port: u16 },
+///     Local,
+/// }
+///
+/// #[derive(EnumDefault, PartialEq, Debug)]
+/// enum Channel {
+///     Closed,
+///     #[default]
+///     Opening {
+///         #[default = 144]
+///         confirmations: u32,
+///         funding: Option<u64>,
+///     },
+/// }
+///
+/// #[derive(EnumDefault, PartialEq, Debug)]
+/// struct Config {
+///     network: Network,
+///     #[default = 9735]
+///     port: u16,
+///     #[default(vec!["localhost".to_owned()])]
+///     hosts: Vec<String>,
+///     verbose: bool,
+/// }
+///
+/// assert_eq!(Network::default(), Network::Regtest(0));
+/// assert_eq!(Peer::<String>::default(), Peer::Local);
+/// assert_eq!(
+///     Channel::default(),
+///     Channel::Opening {
+///         confirmations: 144,
+///         funding: None
+///     }
+/// );
+/// assert_eq!(
+///     Config::default(),
+///     Config {
+///         network: Network::Regtest(0),
+///         port: 9735,
+///         hosts: vec!["localhost".to_owned()],
+///         verbose: false,
+///     }
+/// );
+/// ```
+///
+/// Enum without default variant:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(EnumDefault)]
+/// enum Network {
+///     Mainnet,
+///     Testnet,
+/// }
+/// ```
+///
+/// Enum with multiple default variants:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(EnumDefault)]
+/// #[default(Mainnet)]
+/// enum Network {
+///     Mainnet,
+///     #[default]
+///     Testnet,
+/// }
+/// ```
+#[proc_macro_derive(EnumDefault, attributes(default))]
+pub fn derive_enum_default(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    default::inner(derive_input, DefaultTrait::Default)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derives [`amplify::DumbDefault`] for enums and structures.
+///
+/// The derived implementation constructs each of the fields with
+/// `DumbDefault::dumb_default()`. Fields of types implementing only the
+/// ordinary `Default` must be marked with `#[dumb_default(Default)]`
+/// attribute; otherwise the compiler reports the field type not implementing
+/// `DumbDefault`. The value of individual fields may be overridden with
+/// `#[dumb_default(expr)]` attribute, or `#[dumb_default = literal]` for
+/// literal values.
+///
+/// Enums must have a single variant marked with `#[dumb_default]` or named by
+/// the type-level `#[dumb_default(Variant)]` attribute, in the same way as
+/// for the [`Default`] derive.
+///
+/// Unlike the [`Default`] derive, type parameters get `DumbDefault` bounds
+/// only when they are used by the field types constructed with
+/// `DumbDefault::dumb_default()`, and `Default` bounds for the fields marked
+/// with `#[dumb_default(Default)]`.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// # extern crate amplify;
+/// use amplify::DumbDefault;
+/// use std::marker::PhantomData;
+///
+/// #[derive(PartialEq, Debug)]
+/// struct PubKey([u8; 33]);
+///
+/// impl DumbDefault for PubKey {
+///     fn dumb_default() -> Self {
+///         PubKey([2u8; 33])
+///     }
+/// }
+///
+/// #[derive(DumbDefault, PartialEq, Debug)]
+/// struct Node<K, M> {
+///     key: K,
+///     #[dumb_default(Default)]
+///     alias: String,
+///     #[dumb_default = 9735]
+///     port: u16,
+///     #[dumb_default(vec![1, 2])]
+///     features: Vec<u8>,
+///     #[dumb_default(PhantomData)]
+///     _phantom: PhantomData<M>,
+/// }
+///
+/// #[derive(DumbDefault, PartialEq, Debug)]
+/// enum Peer<K> {
+///     Disconnected,
+///     #[dumb_default]
+///     Connected(K, #[dumb_default(Default)] u16),
+/// }
+///
+/// // `M` is not required to implement neither `DumbDefault` nor `Default`
+/// struct Marker;
+///
+/// let node = Node::<PubKey, Marker>::dumb_default();
+/// assert_eq!(node.key, PubKey([2u8; 33]));
+/// assert_eq!(node.alias, "");
+/// assert_eq!(node.port, 9735);
+/// assert_eq!(node.features, vec![1, 2]);
+///
+/// assert_eq!(
+///     Peer::<PubKey>::dumb_default(),
+///     Peer::Connected(PubKey([2u8; 33]), 0)
+/// );
+/// ```
+///
+/// Field types must implement `DumbDefault` unless marked otherwise:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// # extern crate amplify;
+/// struct Port(u16);
+///
+/// #[derive(DumbDefault)]
+/// struct Node {
+///     port: Port,
+/// }
+/// ```
+#[proc_macro_derive(DumbDefault, attributes(dumb_default, amplify_crate))]
+pub fn derive_dumb_default(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    default::inner(derive_input, DefaultTrait::DumbDefault)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derives hex encoding and decoding for newtypes over byte containers, like
+/// `[u8; 32]` or `Vec<u8>`.
+///
+/// The derive macro implements the following traits using the bytes of the
+/// inner type:
+/// - [`core::fmt::LowerHex`] and [`core::fmt::UpperHex`], supporting width
+///   and precision formatting options; the alternate form (`{:#x}`) adds `0x`
+///   prefix;
+/// - [`core::fmt::Display`], forwarding to `LowerHex`;
+/// - `amplify::hex::FromHex`, using `FromHex` implementation of the inner type,
+///   which for byte arrays validates the length of the data;
+/// - [`core::str::FromStr`], decoding hex string with `amplify::hex::Error`
+///   error type.
+///
+/// `amplify::hex::ToHex` is implemented automatically through `LowerHex`.
+///
+/// Types which are displayed in reverse byte order (like bitcoin transaction
+/// ids) should be marked with `#[hex(reversed)]` attribute; in this case both
+/// formatting and parsing reverse the order of the bytes.
+///
+/// The derive macro works only for structures with a single field; the inner
+/// type must support `&inner[..]` slicing to `[u8]` and implement
+/// `amplify::hex::FromHex`. Requires `hex` feature of `amplify` crate.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// # extern crate amplify;
+/// use amplify::hex::{self, FromHex, ToHex};
+///
+/// #[derive(Hex, Clone, PartialEq, Eq, Debug)]
+/// struct Script(Vec<u8>);
+///
+/// #[derive(Hex, Clone, Copy, PartialEq, Eq, Debug)]
+/// #[hex(reversed)]
+/// struct Txid {
+///     hash: [u8; 4],
+/// }
+///
+/// let script = Script(vec![0x76, 0xa9, 0x14]);
+/// assert_eq!(script.to_hex(), "76a914");
+/// assert_eq!(format!("{}", script), "76a914");
+/// assert_eq!(format!("{:#x}", script), "0x76a914");
+/// assert_eq!(format!("{:X}", script), "76A914");
+/// assert_eq!(format!("{:.3}", script), "76a");
+/// assert_eq!(Script::from_hex("76a914"), Ok(script.clone()));
+/// assert_eq!("76a914".parse(), Ok(script));
+///
+/// let txid = Txid {
+///     hash: [0x01, 0x02, 0x03, 0xff],
+/// };
+/// assert_eq!(txid.to_string(), "ff030201");
+/// assert_eq!(format!("{:#X}", txid), "0xFF030201");
+/// assert_eq!("ff030201".parse(), Ok(txid));
+/// assert_eq!(
+///     Txid::from_hex("ff0302"),
+///     Err(hex::Error::InvalidLength(8, 6))
+/// );
+/// assert_eq!(
+///     "ff03020z".parse::<Txid>(),
+///     Err(hex::Error::InvalidChar('z', 7))
+/// );
+/// ```
+///
+/// Enums and structures with multiple fields are not supported:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// # extern crate amplify;
+/// #[derive(Hex)]
+/// enum Data {
+///     Short([u8; 4]),
+///     Long([u8; 32]),
+/// }
+/// ```
+#[proc_macro_derive(Hex, attributes(hex, amplify_crate))]
+pub fn derive_hex(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    hex::inner(derive_input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derives [`core::fmt::Debug`] in the same way as the standard `Debug`
+/// derive macro, but formats byte containers as lowercase hex strings instead
+/// of lists of numbers.
+///
+/// The following field types are recognized as byte containers: `Vec<u8>`,
+/// `[u8; N]`, `Box<[u8]>` and `amplify` byte arrays (`Slice20`, `Slice32`,
+/// `Slice64`). Other fields may be formatted as hex by marking them with
+/// `#[debug(hex)]`; the type of such fields must implement `AsRef<[u8]>`.
+/// Field attribute arguments:
+/// - `max = <bytes>`: prints only the first given number of bytes followed by
+///   `...`;
+/// - `skip`: prints `..` instead of the field value.
+///
+/// Works for structures, tuple structures and enums. Requires `hex` feature of
+/// `amplify` crate.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// # extern crate amplify;
+/// #[derive(DebugHex)]
+/// struct Tx {
+///     version: u32,
+///     script: Vec<u8>,
+///     #[debug(hex, max = 2)]
+///     witness: Box<[u8]>,
+///     #[debug(skip)]
+///     secret: [u8; 4],
+/// }
+///
+/// #[derive(DebugHex)]
+/// struct Id([u8; 4], #[debug(hex)] &'static str);
+///
+/// #[derive(DebugHex)]
+/// enum Message {
+///     Ping,
+///     Data(u16, Vec<u8>),
+///     Signed {
+///         #[debug(hex, max = 1)]
+///         sig: [u8; 3],
+///         nonce: u64,
+///     },
+/// }
+///
+/// let tx = Tx {
+///     version: 2,
+///     script: vec![0x76, 0xa9, 0x14],
+///     witness: Box::new([0xde, 0xad, 0xbe, 0xef]),
+///     secret: [0xff; 4],
+/// };
+/// assert_eq!(
+///     format!("{:?}", tx),
+///     "Tx { version: 2, script: 76a914, witness: dead..., secret: .. }"
+/// );
+/// assert_eq!(
+///     format!("{:#?}", tx),
+///     "Tx {\n    version: 2,\n    script: 76a914,\n    witness: dead...,\n    \
+///      secret: ..,\n}"
+/// );
+///
+/// assert_eq!(format!("{:?}", Id([0, 1, 2, 0xab], "AB")), "Id(000102ab, 4142)");
+///
+/// assert_eq!(format!("{:?}", Message::Ping), "Ping");
+/// assert_eq!(
+///     format!("{:?}", Message::Data(7, vec![0x0f, 0x10])),
+///     "Data(7, 0f10)"
+/// );
+/// assert_eq!(
+///     format!(
+///         "{:?}",
+///         Message::Signed {
+///             sig: [0x30, 0x44, 0x02],
+///             nonce: 5
+///         }
+///     ),
+///     "Signed { sig: 30..., nonce: 5 }"
+/// );
+/// ```
+///
+/// Skipped fields can't be formatted as hex:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// # extern crate amplify;
+/// #[derive(DebugHex)]
+/// struct Data {
+///     #[debug(hex, skip)]
+///     value: Vec<u8>,
+/// }
+/// ```
+#[proc_macro_derive(DebugHex, attributes(debug, amplify_crate))]
+pub fn derive_debug_hex(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    debug_hex::inner(derive_input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
 /// Trait `amplify::AsAny` allows simple conversion of any type into a
 /// generic "thick" pointer `&dyn Any` (see [`::core::any::Any`]), that can be
 /// later converted back to the original type with a graceful failing for all
@@ -439,7 +1880,53 @@ pub fn derive_from(input: TokenStream) -> TokenStream {
 /// let p = point_ptr.downcast_ref::<Point>().unwrap();
 /// assert_eq!(p.x, 1)
 /// ```
-#[proc_macro_derive(AsAny)]
+///
+/// # Enums and generic types
+///
+/// The derive macro works with enums in the same way as with structures. For
+/// generic types each type parameter gets a `'static` bound in the generated
+/// implementation, since [`::core::any::Any`] is implemented only for
+/// `'static` types:
+///
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// extern crate amplify;
+/// use amplify::AsAny;
+///
+/// #[derive(AsAny, PartialEq, Debug)]
+/// enum Shape {
+///     Empty,
+///     Square(u32),
+/// }
+///
+/// #[derive(AsAny, PartialEq, Debug)]
+/// struct Named<T> {
+///     pub name: &'static str,
+///     pub value: T,
+/// }
+///
+/// let shapes: Vec<&dyn AsAny> = vec![&Shape::Empty, &Shape::Square(5)];
+/// assert_eq!(shapes[0].as_any().downcast_ref(), Some(&Shape::Empty));
+/// assert_eq!(shapes[1].as_any().downcast_ref(), Some(&Shape::Square(5)));
+/// assert_eq!(shapes[1].as_any().downcast_ref::<Named<u8>>(), None);
+///
+/// let named = Named { name: "shape", value: Shape::Square(2) };
+/// let ptr = named.as_any();
+/// assert_eq!(ptr.downcast_ref::<Named<Shape>>().unwrap().value, Shape::Square(2));
+/// assert_eq!(ptr.downcast_ref::<Named<u32>>(), None);
+/// ```
+///
+/// Types with lifetime parameters can't be `'static` and the derive macro
+/// fails on them:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// extern crate amplify;
+///
+/// #[derive(AsAny)]
+/// struct Borrowed<'a>(&'a str);
+/// ```
+#[proc_macro_derive(AsAny, attributes(amplify_crate))]
 pub fn derive_as_any(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
     as_any::inner(derive_input)
@@ -466,7 +1953,9 @@ pub fn derive_as_any(input: TokenStream) -> TokenStream {
 ///
 /// Attribute takes a list of arguments in form of verbatim literals:
 /// - `as_copy`: derives methods returning copy of the field value. Will error
-///   at compile time on types which does not implement `Copy`
+///   at compile time on types which does not implement `Copy`, pointing to the
+///   field type
+/// - `copy`: same as `as_copy`
 /// - `as_clone`: derives methods returning cloned value; will conflict with
 ///   `as_copy`. Errors at compile time on types which does not implement
 ///   `Clone`.
@@ -475,12 +1964,25 @@ pub fn derive_as_any(input: TokenStream) -> TokenStream {
 ///   suffixed with `_ref`; otherwise the base name is used (see below)
 /// - `as_mut`: derives method returning mutable reference. Method name is
 ///   suffixed with `_mut`
+/// - `mut`: same as `as_mut`. The mutable accessor is derived in addition to
+///   the other methods, and it is not affected by `as_copy`/`copy`
 /// - `all`: equivalent to `as_clone, as_ref, as_mut`
 ///
 /// **Can be used**: at type and field level
 ///
 /// **Defaults to**: `as_ref`
 ///
+/// ### `#[getter(deref)]`
+/// Makes borrowing methods return dereferenced forms of well-known types:
+/// `&str` for `String`, `&[T]` for `Vec<T>`, `&Path` for `PathBuf` and
+/// `Option<&T>` for `Option<T>` (with `Option<String>` returned as
+/// `Option<&str>` etc). Types are recognized by their name, which may be also
+/// given as a full path like `std::string::String`.
+///
+/// **Can be used**: at type level, where it applies to the fields of the
+/// supported types only, and at field level, where it errors on unsupported
+/// field types.
+///
 /// ### `#[getter(skip)]`
 /// Skips derivation of a all gettter methods for this field
 ///
@@ -500,57 +2002,340 @@ pub fn derive_as_any(input: TokenStream) -> TokenStream {
 ///
 /// **Can be used**: at field level
 ///
+/// ### `#[getter(rename = "...")]`
+/// Defines custom name for the getter method. Unlike `base_name`, the name is
+/// not prefixed with the type-level `prefix`; method-specific suffixes are
+/// still applied. Can't be combined with `base_name`.
+///
+/// **Can be used**: at field level
+///
+/// ### `#[getter(parts)]`, `#[getter(parts = ...)]` and `#[getter(parts_struct)]`
+/// Additionally derives `into_parts(self)` method, decomposing the structure
+/// into its fields, and `from_parts(parts)` method performing the inverse
+/// operation. The fields follow the order of their declaration; fields marked
+/// with `#[getter(skip)]` are excluded from the parts and are initialized with
+/// `Default::default()` by `from_parts`.
+///
+/// The parts are represented as a tuple, unless the number of fields exceeds
+/// the value of `parts` argument (12, if not specified) or `parts_struct`
+/// argument is given. In these cases the macro generates `<Name>Parts`
+/// structure with the same fields, generic parameters and visibility as the
+/// original structure (fields of tuple structs are named `_0`, `_1` etc).
+/// Methods inherit the visibility of the structure.
+///
+/// Unlike other getters, `into_parts` and `from_parts` can be derived for the
+/// tuple structs; getter methods are derived only for their fields which have
+/// `base_name` or `rename` argument.
+///
+/// **Can be used**: at type level
+///
+/// ## Raw identifiers
+///
+/// Fields named with raw identifiers, like `r#type`, use the name without the
+/// `r#` prefix as the base name of their getters. If the resulting method name
+/// is a keyword, the method is derived with raw identifier name, i.e.
+/// `fn r#type(&self)`, while prefixed and suffixed names are ordinary
+/// identifiers, like `type_mut` or `get_type`.
+///
+/// ## Documentation
+///
+/// Documentation comments of the field are copied to all getter methods
+/// derived for it.
+///
 /// # Errors
 ///
 /// Enums and units are not supported; attempt to derive `Getters` on them will
 /// result in a compile-time error.
 ///
 /// Deriving getters on unit structs and structs with unnamed fields (tupe
-/// structs) is not supported (since it's meaningless), and results in a error.
+/// structs) is not supported (since it's meaningless), and results in a error;
+/// the only exception are tuple structs deriving `into_parts` and `from_parts`.
+/// Generating `<Name>Parts` structure fails if some generic parameter is used
+/// only by the skipped fields.
 ///
 /// Additionally to these two cases, macro errors on argument inconsistencies,
-/// as described in the argument-specific sections.
+/// as described in the argument-specific sections, and on getter methods with
+/// the same name derived for different fields.
+///
+/// # Examples
+///
+/// Basic use:
+///
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Getters, Default)]
+/// struct One {
+///     vec: Vec<u8>,
+///     defaults: String,
+///     #[getter(as_copy)]
+///     pub flag: bool,
+///     #[getter(as_copy)]
+///     pub(self) field: u8,
+/// }
+///
+/// let mut one = One::default();
+/// assert_eq!(one.vec(), &Vec::<u8>::default());
+/// assert_eq!(one.defaults(), "");
+/// assert_eq!(one.flag(), false);
+/// assert_eq!(one.field(), 0);
+/// ```
+///
+/// Important, that field-level arguments to override struct-level arguments:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Getters, Default)]
+/// #[getter(as_copy)]
+/// struct Other {
+///     #[getter(as_ref)]
+///     vec: Vec<u8>,
+///     #[getter(as_clone)]
+///     defaults: String,
+///     pub flag: bool,
+///     pub(self) field: u8,
+/// }
+///
+/// let mut other = Other::default();
+/// assert_eq!(other.vec(), &Vec::<u8>::default());
+/// assert_eq!(other.defaults(), String::from(""));
+/// ```
+///
+/// Skipping and renaming getters:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Getters, Default)]
+/// #[getter(prefix = "get_")]
+/// struct Config {
+///     /// Name of the configuration
+///     name: String,
+///     #[getter(skip)]
+///     secret: Vec<u8>,
+///     #[getter(as_copy, rename = "is_enabled")]
+///     enabled: bool,
+/// }
+///
+/// impl Config {
+///     pub fn secret(&self) -> usize {
+///         self.secret.len()
+///     }
+/// }
+///
+/// let config = Config::default();
+/// assert_eq!(config.get_name(), "");
+/// assert_eq!(config.is_enabled(), false);
+/// assert_eq!(config.secret(), 0);
+/// ```
+///
+/// Getter names must be unique:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Getters, Default)]
+/// struct Config {
+///     #[getter(rename = "name")]
+///     title: String,
+///     name: String,
+/// }
+/// ```
+///
+/// /// Fields may be returned by value with type-level `copy` argument; fields
+/// which are not `Copy` have to opt out by specifying other methods:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Getters, Default)]
+/// #[getter(copy)]
+/// struct Stats {
+///     len: usize,
+///     #[getter(rename = "is_empty")]
+///     empty: bool,
+///     average: Option<u32>,
+///     #[getter(as_ref)]
+///     name: String,
+///     #[getter(skip)]
+///     cache: Vec<u8>,
+/// }
+///
+/// let stats = Stats::default();
+/// let len: usize = stats.len();
+/// assert_eq!(len, 0);
+/// assert_eq!(stats.is_empty(), false);
+/// assert_eq!(stats.average(), None);
+/// assert_eq!(stats.name(), "");
+/// ```
+///
+/// Returning non-`Copy` types by value is an error:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Getters, Default)]
+/// struct Stats {
+///     #[getter(copy)]
+///     name: String,
+/// }
+/// ```
+///
+/// Deref-style getters:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// use std::path::{Path, PathBuf};
+///
+/// #[derive(Getters, Default)]
+/// #[getter(deref)]
+/// struct Record {
+///     name: std::string::String,
+///     data: Vec<u8>,
+///     path: PathBuf,
+///     comment: Option<String>,
+///     index: Option<u32>,
+///     count: usize,
+/// }
+///
+/// let record = Record::default();
+/// let name: &str = record.name();
+/// let data: &[u8] = record.data();
+/// let path: &Path = record.path();
+/// let comment: Option<&str> = record.comment();
+/// let index: Option<&u32> = record.index();
+/// let count: &usize = record.count();
+/// ```
+///
+/// Field-level `deref` requires the type to be supported:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Getters, Default)]
+/// struct Record {
+///     #[getter(deref)]
+///     count: usize,
+/// }
+/// ```
+///
+/// Mutable accessors; all methods are placed into a single `impl` block with
+/// the same generic parameters as the structure:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Getters)]
+/// #[getter(mut)]
+/// struct Fixture<'a, T>
+/// where
+///     T: Clone,
+/// {
+///     name: &'a str,
+///     items: Vec<T>,
+///     #[getter(copy, mut, rename = "is_ready")]
+///     ready: bool,
+/// }
+///
+/// let mut fixture = Fixture {
+///     name: "test",
+///     items: vec![1u8],
+///     ready: false,
+/// };
+/// *fixture.name_mut() = "other";
+/// fixture.items_mut().push(2);
+/// *fixture.is_ready_mut() = true;
+/// let name: &&str = fixture.name();
+/// assert_eq!(*name, "other");
+/// assert_eq!(fixture.items(), &vec![1, 2]);
+/// assert_eq!(fixture.is_ready(), true);
+/// ```
+///
+/// Getters for fields named with raw identifiers:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Getters, Default)]
+/// #[getter(as_copy, as_mut)]
+/// struct Token {
+///     r#type: u8,
+///     #[getter(rename = "kind")]
+///     r#ref: u16,
+/// }
+///
+/// let mut token = Token::default();
+/// *token.type_mut() = 1;
+/// assert_eq!(token.r#type(), 1);
+/// assert_eq!(token.kind(), 0);
+/// ```
+///
+/// Decomposing structure into parts and composing it back; skipped fields
+/// must implement `Default`:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Getters, Clone, PartialEq, Eq, Debug)]
+/// #[getter(parts)]
+/// pub struct Header {
+///     version: u16,
+///     name: String,
+///     #[getter(skip)]
+///     cache: Option<Vec<u8>>,
+/// }
+///
+/// let header = Header {
+///     version: 1,
+///     name: String::from("header"),
+///     cache: None,
+/// };
+/// let (version, name): (u16, String) = header.clone().into_parts();
+/// assert_eq!((version, name.as_str()), (1, "header"));
+/// assert_eq!(Header::from_parts(header.clone().into_parts()), header);
+///
+/// let cached = Header {
+///     cache: Some(vec![1, 2, 3]),
+///     ..header.clone()
+/// };
+/// assert_eq!(Header::from_parts(cached.into_parts()), header);
 ///
-/// # Examples
+/// #[derive(Getters, Clone, PartialEq, Eq, Debug)]
+/// #[getter(parts)]
+/// struct Pair<T>(T, #[getter(as_copy, base_name = "flag")] bool);
 ///
-/// Basic use:
+/// let pair = Pair(vec![1u8], true);
+/// assert!(pair.flag());
+/// assert_eq!(pair.clone().into_parts(), (vec![1u8], true));
+/// assert_eq!(Pair::from_parts(pair.clone().into_parts()), pair);
+/// ```
 ///
+/// Parts given as a generated structure:
 /// ```
 /// # #[macro_use] extern crate amplify_derive;
-/// #[derive(Getters, Default)]
-/// struct One {
-///     vec: Vec<u8>,
-///     defaults: String,
-///     #[getter(as_copy)]
-///     pub flag: bool,
-///     #[getter(as_copy)]
-///     pub(self) field: u8,
+/// #[derive(Getters, Clone, PartialEq, Eq, Debug)]
+/// #[getter(parts_struct)]
+/// struct Named<'a, T: Clone>
+/// where
+///     T: Default,
+/// {
+///     name: &'a str,
+///     value: T,
+///     #[getter(skip)]
+///     len: usize,
 /// }
 ///
-/// let mut one = One::default();
-/// assert_eq!(one.vec(), &Vec::<u8>::default());
-/// assert_eq!(one.defaults(), "");
-/// assert_eq!(one.flag(), false);
-/// assert_eq!(one.field(), 0);
-/// ```
+/// let named = Named {
+///     name: "first",
+///     value: 5u8,
+///     len: 0,
+/// };
+/// let NamedParts { name, value } = named.clone().into_parts();
+/// assert_eq!((name, value), ("first", 5));
+/// assert_eq!(Named::from_parts(NamedParts { name, value }), named);
 ///
-/// Important, that field-level arguments to override struct-level arguments:
+/// #[derive(Getters, Clone, PartialEq, Eq, Debug)]
+/// #[getter(parts = 2)]
+/// struct Triple(u8, u16, u32);
+///
+/// let parts: TripleParts = Triple(1, 2, 3).into_parts();
+/// assert_eq!((parts._0, parts._1, parts._2), (1, 2, 3));
+/// assert_eq!(Triple::from_parts(parts), Triple(1, 2, 3));
 /// ```
+///
+/// Skipped fields must implement `Default`:
+/// ```compile_fail
 /// # #[macro_use] extern crate amplify_derive;
-/// #[derive(Getters, Default)]
-/// #[getter(as_copy)]
-/// struct Other {
-///     #[getter(as_ref)]
-///     vec: Vec<u8>,
-///     #[getter(as_clone)]
-///     defaults: String,
-///     pub flag: bool,
-///     pub(self) field: u8,
-/// }
+/// struct NoDefault;
 ///
-/// let mut other = Other::default();
-/// assert_eq!(other.vec(), &Vec::<u8>::default());
-/// assert_eq!(other.defaults(), String::from(""));
+/// #[derive(Getters)]
+/// #[getter(parts)]
+/// struct Data {
+///     value: u8,
+///     #[getter(skip)]
+///     other: NoDefault,
+/// }
 /// ```
 ///
 /// Advanced use: please pay attention that `as_mut` on a struct level is not
@@ -622,7 +2407,10 @@ pub fn derive_as_any(input: TokenStream) -> TokenStream {
 ///     #[doc = "Method returning copy of [`One::flag`] field.\n"]
 ///     #[inline]
 ///     pub fn get_flag(&self) -> bool {
-///         self.flag
+///         fn copy<T: ::core::marker::Copy>(value: &T) -> T {
+///             *value
+///         }
+///         copy(&self.flag)
 ///     }
 ///
 ///     #[doc = "Method returning mutable borrow of [`One::flag`] field.\n"]
@@ -640,33 +2428,194 @@ pub fn derive_getters(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Derives setter methods for structures: for each of the fields, a consuming
+/// builder-style `with_<field>` method returning the modified value and a
+/// mutating `set_<field>` method.
+///
+/// Unless specified otherwise, setters take `impl Into<T>` argument, where `T`
+/// is the field type.
+///
+/// # Attribute `#[setter(...)]`
+///
+/// ### `#[setter(skip)]`
+/// Skips derivation of both setter methods for this field
+///
+/// **Can be used**: at field level
+///
+/// ### `#[setter(rename = "...")]`
+/// Defines custom base name for the setter methods, which are named
+/// `with_<name>` and `set_<name>`.
+///
+/// **Defaults to**: field name
+///
+/// **Can be used**: at field level
+///
+/// ### `#[setter(no_into)]`
+/// Makes setters take the value of the exact field type instead of
+/// `impl Into<T>`, which helps with type inference for literals and
+/// closures.
+///
+/// **Can be used**: at type and field level
+///
+/// ### `#[setter(strip_option)]`
+/// Makes setters for `Option<T>` fields take `T` (or `impl Into<T>`) value and
+/// wrap it into `Some`.
+///
+/// **Can be used**: at field level, and at type level, in which case it must
+/// be applicable to all fields which are not skipped
+///
+/// ## Raw identifiers
+///
+/// Fields named with raw identifiers, like `r#type`, use the name without the
+/// `r#` prefix in the setter names (`with_type` and `set_type`).
+///
+/// ## Documentation
+///
+/// Documentation comments of the field are copied to both setter methods.
+///
+/// # Errors
+///
+/// Enums, unions, unit structs and structs with unnamed fields are not
+/// supported. Macro also errors on `strip_option` used with non-`Option`
+/// fields and on setter methods with the same name derived for different
+/// fields or for a getter derived with [`Getters`] (the latter is detected
+/// only if the structure has `#[getter]` attributes).
+///
+/// # Examples
+///
+/// Setters for a generic structure:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Setters, Clone, PartialEq, Eq, Debug, Default)]
+/// struct Config<T>
+/// where
+///     T: Clone,
+/// {
+///     /// Name of the configuration
+///     name: String,
+///     items: Vec<T>,
+///     #[setter(strip_option)]
+///     comment: Option<String>,
+///     #[setter(no_into, rename = "limit")]
+///     max_items: u64,
+///     #[setter(skip)]
+///     cache: Vec<u8>,
+/// }
+///
+/// let config = Config::<u8>::default()
+///     .with_name("test")
+///     .with_items(vec![1, 2])
+///     .with_comment("some comment")
+///     .with_limit(10);
+/// assert_eq!(config.name, "test");
+/// assert_eq!(config.items, vec![1, 2]);
+/// assert_eq!(config.comment.as_deref(), Some("some comment"));
+/// assert_eq!(config.max_items, 10);
+///
+/// let mut other = config.clone();
+/// other.set_name(String::from("other"));
+/// other.set_comment("none");
+/// other.set_limit(5);
+/// assert_eq!(other.name, "other");
+/// assert_eq!(other.comment.as_deref(), Some("none"));
+/// assert_eq!(other.max_items, 5);
+/// ```
+///
+/// Setter names must be unique:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Setters)]
+/// struct Config {
+///     #[setter(rename = "name")]
+///     title: String,
+///     name: String,
+/// }
+/// ```
+///
+/// and must not collide with the getter names:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Getters, Setters)]
+/// #[getter(prefix = "with_")]
+/// struct Config {
+///     name: String,
+/// }
+/// ```
+///
+/// `strip_option` requires `Option` field:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Setters)]
+/// struct Config {
+///     #[setter(strip_option)]
+///     name: String,
+/// }
+/// ```
+#[proc_macro_derive(Setters, attributes(setter))]
+pub fn derive_setters(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    setters::derive(derive_input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
 /// Creates rust new type wrapping existing type. Can be used in sturctures
 /// containing multiple named or unnamed fields; in this case the field you'd
-/// like to wrap should be marked with `#[wrap]` attribute; otherwise the first
-/// field is assumed to be the wrapped one.
+/// like to wrap should be marked with `#[wrap]` attribute; otherwise the only
+/// field which is not a `PhantomData` is assumed to be the wrapped one.
 ///
-/// NB: You have to use `derive(From)` in order foe Wrapper to work properly.
-/// Also, in case of multiple fields, each non-wrapped field type must implement
-/// `Default` trait.
+/// NB: In case of multiple fields, each non-wrapped field type must implement
+/// `Default` trait, which is used to construct them in `Wrapper::from_inner`.
 ///
 /// Supports automatic implementation of the following traits:
 /// * `amplify::Wrapper`
 /// * [`AsRef`]
-/// * [`AsMut`]
 /// * [`std::borrow::Borrow`]
-/// * [`std::borrow::BorrowMut`]
 /// * [`std::ops::Deref`]
-/// * [`std::ops::DerefMut`]
+///
+/// Mutable access to the wrapped data is opt-in: `#[wrapper(AsMut, BorrowMut,
+/// DerefMut)]` implement the corresponding traits, while `#[wrapper_mut]`
+/// attribute is a shorthand for all three of them. Each of these, as well as
+/// `IndexMut` and `*Assign` operations, also implements `amplify::WrapperMut`
+/// providing `as_inner_mut` method. Types which must keep invariants over
+/// their data (like hashes) should not use these derives.
+///
+/// Wrappers declared with `#[repr(transparent)]`, for which all fields other
+/// than the wrapped one are `PhantomData`, also implement
+/// `amplify::WrapperTransparent`, allowing to convert a reference to the
+/// inner data into a reference to the wrapper without copying with
+/// `WrapperTransparent::from_inner_ref`, as well as slices and vectors of the
+/// inner data into slices and vectors of wrappers (and back) with
+/// `wrap_slice`, `wrap_vec`, `peel_slice`, `peel_vec` and their `_mut`
+/// counterparts.
 ///
 /// You can implement additonal derives, it they are implemented for the wrapped
 /// type, using `#[wrapper()]` proc macro:
+/// * [`AsMut`]
+/// * [`std::borrow::BorrowMut`]
+/// * [`std::ops::DerefMut`]
+/// * [`std::str::FromStr`]; `FromStr(Error)` form uses custom `Error` type
+///   which must implement `From` for the inner type parsing error
+/// * `amplify::hex::FromHex`
+/// * `amplify::RawArray`, for the wrappers around byte arrays and other types
+///   implementing `RawArray`
+/// * [`std::fmt::Display`]
+/// * [`std::fmt::Debug`]
 /// * [`std::fmt::LowerHex`]
 /// * [`std::fmt::UpperHex`]
 /// * [`std::fmt::LowerExp`]
 /// * [`std::fmt::UpperExp`]
 /// * [`std::fmt::Octal`]
+/// * [`std::fmt::Binary`]
+/// * [`std::hash::Hash`]
+/// * [`PartialOrd`]
+/// * [`Ord`]
+/// * `serde::Serialize` and `serde::Deserialize`, if `serde` feature of the
+///   crate is enabled (which is done by `serde` feature of `amplify`)
 /// * [`std::ops::Index`]
 /// * [`std::ops::IndexMut`]
+/// * `RangeOps`, implementing [`std::ops::Index`] for all range types over
+///   `usize` (and [`std::ops::IndexMut`], if `IndexMut` is also given)
 /// * [`std::ops::Neg`]
 /// * [`std::ops::Not`]
 /// * [`std::ops::Add`]
@@ -690,9 +2639,51 @@ pub fn derive_getters(input: TokenStream) -> TokenStream {
 /// * [`std::ops::BitXor`]
 /// * [`std::ops::BitXorAssign`]
 ///
-/// Other traits, such as [`PartialEq`], [`Eq`], [`PartialOrd`], [`Ord`],
-/// [`Hash`] can be implemented using standard `#[derive]` attribute in the
-/// same manner as [`Default`], [`Debug`] and [`From`]
+/// Arithmetic and bitwise operations unwrap both operands, apply the operation
+/// to the inner values and wrap the result back; `*Assign` operations modify
+/// the inner value in place. [`std::ops::Shl`], [`std::ops::Shr`] and their
+/// `*Assign` counterparts take the same right-hand side as the inner type
+/// shift (i.e. a primitive integer), not the wrapper. Operations which are not
+/// supported by the wrapped type are reported as trait bound errors pointing
+/// to the name of the operation in the `#[wrapper()]` attribute.
+///
+/// Formatting traits are forwarded to the inner type together with the
+/// formatter, so width, fill, precision and alternate flags keep working.
+/// `#[wrapper(Display)]` can't be combined with `#[derive(Display)]` and its
+/// `#[display(...)]` attribute.
+///
+/// [`std::hash::Hash`], [`PartialOrd`] and [`Ord`] are forwarded to the inner
+/// value, such that the wrapper hashes and compares exactly as the inner type.
+/// Serde implementations are transparent: the inner value is serialized
+/// directly, and deserialized value is wrapped with `Wrapper::from_inner`, so
+/// serde attributes of the inner type are respected. These traits can't be
+/// simultaneously implemented with a separate `#[derive(...)]` attribute,
+/// unless it is listed in the same attribute as `Wrapper` (in which case the
+/// conflict is reported by the compiler). Serde traits also can't be combined
+/// with `#[serde(...)]` attributes on the wrapper.
+///
+/// Wrappers keeping invariants over the inner data may be given
+/// `#[wrapper(validate(function, Error))]` attribute, where `function` has
+/// `fn(&Inner) -> Result<(), Error>` signature and `Error` implements
+/// [`std::fmt::Display`]. In this case the derive macro implements inherent
+/// `try_from_inner` constructor returning the validation error, while
+/// `Wrapper::from_inner` and generated [`From`] implementation for the inner
+/// type panic if the validation fails. With additional `no_from` argument
+/// (`#[wrapper(validate(function, Error), no_from)]`) the [`From`]
+/// implementation is replaced with [`TryFrom`] using the validation error
+/// (they can't coexist, since [`TryFrom`] is implemented for all [`From`]
+/// conversions); `Wrapper::from_inner` is still required by the trait and
+/// panics. In both cases the wrapped field must not be marked with `#[from]`.
+/// `FromStr` must use `FromStr(Error)` form, where the error type is
+/// convertible from the validation error; `FromHex` fails with
+/// `hex::Error::InvalidValue` and serde deserialization fails with a custom
+/// error carrying the validation error message. Validated wrappers don't
+/// implement `amplify::WrapperTransparent` even if declared with
+/// `#[repr(transparent)]`, since it allows to bypass the validation.
+///
+/// Other traits, such as [`PartialEq`] and [`Eq`] can be implemented using
+/// standard `#[derive]` attribute in the same manner as [`Default`], [`Debug`]
+/// and [`From`]
 ///
 /// # Example
 ///
@@ -713,6 +2704,158 @@ pub fn derive_getters(input: TokenStream) -> TokenStream {
 /// struct Int64(i64);
 /// ```
 ///
+/// Parsing and formatting forwarded to the inner type:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// use std::num::ParseIntError;
+/// use std::str::FromStr;
+/// use amplify::hex::FromHex;
+///
+/// #[derive(Clone, PartialEq, Eq, Debug)]
+/// struct AmountError(ParseIntError);
+///
+/// impl From<ParseIntError> for AmountError {
+///     fn from(err: ParseIntError) -> Self {
+///         AmountError(err)
+///     }
+/// }
+///
+/// #[derive(Wrapper, Clone, Copy, PartialEq, Eq, From, Debug)]
+/// #[wrapper(FromStr(AmountError), Display, LowerHex, UpperHex, Binary)]
+/// struct Amount(u32);
+///
+/// #[derive(Wrapper, Clone, Copy, PartialEq, Eq, From, Debug)]
+/// #[wrapper(FromStr)]
+/// struct Height(u16);
+///
+/// #[derive(Wrapper, Clone, PartialEq, Eq, From, Debug)]
+/// #[wrapper(FromHex)]
+/// struct Script(Vec<u8>);
+///
+/// let amount = Amount::from_str("255").unwrap();
+/// assert_eq!(format!("{:>5}", amount), "  255");
+/// assert_eq!(format!("{:#x}", amount), "0xff");
+/// assert_eq!(format!("{:04X}", amount), "00FF");
+/// assert_eq!(format!("{:#b}", amount), "0b11111111");
+/// assert!(Amount::from_str("-1").is_err());
+///
+/// let err: ParseIntError = Height::from_str("65536").unwrap_err();
+/// assert_eq!(err.to_string(), "number too large to fit in target type");
+///
+/// assert_eq!(Script::from_hex("00ff").unwrap(), Script(vec![0x00, 0xff]));
+/// ```
+///
+/// Wrappers around byte arrays converting to and from raw arrays:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// use amplify::{RawArray, Slice32};
+///
+/// #[derive(Wrapper, Clone, Copy, PartialEq, Eq, From, Debug)]
+/// #[wrapper(RawArray)]
+/// struct Hash(Slice32);
+///
+/// #[derive(Wrapper, Clone, Copy, PartialEq, Eq, From, Debug)]
+/// #[wrapper(RawArray)]
+/// struct Id([u8; 20]);
+///
+/// let hash = Hash::from_raw_array([0xA5; 32]);
+/// assert_eq!(hash.to_raw_array(), [0xA5; 32]);
+/// assert_eq!(Hash::from_raw_slice(&[0xA5; 32]), Some(hash));
+/// assert_eq!(Id::LEN, 20);
+/// assert_eq!(Id::from_raw_slice(&[0xA5; 32]), None);
+/// ```
+///
+/// Display can't be provided both by the wrapper and the `Display` derive:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Wrapper, Clone, From, Display)]
+/// #[wrapper(Display)]
+/// #[display(inner)]
+/// struct Amount(u32);
+/// ```
+///
+/// Hashing and ordering forwarded to the inner type:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// # #[macro_use] extern crate amplify;
+/// use std::collections::hash_map::DefaultHasher;
+/// use std::collections::BTreeMap;
+/// use std::hash::{Hash, Hasher};
+///
+/// #[derive(Wrapper, Clone, PartialEq, Eq, Default, From, Debug)]
+/// #[wrapper(Hash, PartialOrd, Ord)]
+/// struct Name(String);
+///
+/// #[derive(Wrapper, Clone, PartialEq, Eq, Default, From, Debug)]
+/// #[wrapper(Hash, PartialOrd, Ord)]
+/// struct Registry(BTreeMap<Name, u8>);
+///
+/// fn hash(value: impl Hash) -> u64 {
+///     let mut hasher = DefaultHasher::new();
+///     value.hash(&mut hasher);
+///     hasher.finish()
+/// }
+///
+/// let name = Name::from(s!("alice"));
+/// assert_eq!(hash(&name), hash("alice".to_string()));
+/// assert!(name < Name::from(s!("bob")));
+///
+/// let registry = Registry(bmap! { name.clone() => 1 });
+/// assert_eq!(hash(&registry), hash(bmap! { name => 1u8 }));
+/// ```
+///
+/// With `serde` feature of the crate serialization is forwarded to the inner
+/// type, without wrapping it into a newtype struct:
+#[cfg_attr(feature = "serde", doc = "```")]
+#[cfg_attr(not(feature = "serde"), doc = "```ignore")]
+/// # #[macro_use] extern crate amplify_derive;
+/// # extern crate serde_crate;
+/// use serde_crate::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+/// #[serde(crate = "serde_crate", rename_all = "camelCase")]
+/// struct Settings {
+///     max_peers: u16,
+/// }
+///
+/// #[derive(Wrapper, Clone, PartialEq, Eq, From, Debug)]
+/// #[wrapper(Serialize, Deserialize)]
+/// struct Config(Settings);
+///
+/// let config = Config(Settings { max_peers: 8 });
+/// let json = serde_json::to_string(&config).unwrap();
+/// assert_eq!(json, r#"{"maxPeers":8}"#);
+/// assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+/// ```
+///
+/// Traits forwarded by the wrapper can't be derived independently:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Wrapper, Clone, PartialEq, Eq, From)]
+/// #[wrapper(Hash)]
+/// #[derive(Hash)]
+/// struct Name(String);
+/// ```
+///
+/// /// Arithmetics on a newtype:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// use amplify::WrapperMut;
+///
+/// #[derive(Wrapper, Clone, Copy, PartialEq, Eq, Default, From, Debug)]
+/// #[wrapper(Add, Sub, Shl, Shr, AddAssign, ShlAssign)]
+/// struct Sats(u64);
+///
+/// let mut sats = Sats(1000);
+/// sats += Sats(24);
+/// assert_eq!(*sats.as_inner_mut(), 1024);
+/// assert_eq!(sats - Sats(24) + Sats(1), Sats(1001));
+///
+/// assert_eq!(sats >> 10u32, Sats(1));
+/// sats <<= 2u8;
+/// assert_eq!(*sats.as_inner_mut(), 4096);
+/// ```
+///
 /// More complex wrapper with multiple unnamed fields:
 /// ```
 /// # #[macro_use] extern crate amplify_derive;
@@ -736,16 +2879,337 @@ pub fn derive_getters(input: TokenStream) -> TokenStream {
 /// assert_eq!(w.into_inner(), HashMap::<usize, Vec<u8>>::default());
 /// ```
 ///
-/// Wrappers for indexable types
+/// Generic parameters, including lifetimes and const generics, are passed to
+/// all generated implementations:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// use std::fmt::Debug;
+/// use amplify::Wrapper;
+///
+/// #[derive(Wrapper, Clone, Copy, PartialEq, Eq, From, Debug)]
+/// #[wrapper(Index, IndexMut, RangeOps)]
+/// struct Hash<const LEN: usize>([u8; LEN]);
+///
+/// #[derive(Wrapper, Clone, PartialEq, Eq, From, Debug)]
+/// #[wrapper(Index, RangeOps)]
+/// struct Refs<'a, T, const LEN: usize>([&'a T; LEN])
+/// where
+///     T: Debug;
+///
+/// let mut short = Hash::<4>::from_inner([1, 2, 3, 4]);
+/// short[0] = 0;
+/// assert_eq!(&short[..2], &[0, 2]);
+/// assert_eq!(short.into_inner(), [0, 2, 3, 4]);
+///
+/// let long = Hash::<32>::from([0xFF; 32]);
+/// assert_eq!(long.as_inner().len(), 32);
+/// assert_eq!(long[31], 0xFF);
+///
+/// let value = 5u8;
+/// let refs = Refs::<u8, 1>::from_inner([&value]);
+/// assert_eq!(*refs[0], 5);
+/// ```
+///
+/// Default values of generic parameters are kept for the wrapper type:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// use amplify::Wrapper;
+///
+/// #[derive(Wrapper, Clone, Copy, From)]
+/// struct Hash<const LEN: usize = 32>([u8; LEN]);
+///
+/// let hash: Hash = Hash::from_inner([0u8; 16]);
+/// ```
+///
+/// Typed markers with the wrapped field detected automatically:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// use std::marker::PhantomData;
+/// use amplify::Wrapper;
+///
+/// #[derive(Wrapper, Clone, Debug)]
+/// struct Handle<T> {
+///     raw: u32,
+///     _phantom: PhantomData<T>,
+/// }
+///
+/// #[derive(Wrapper, Clone, Debug)]
+/// struct Tagged<T>(PhantomData<T>, String, #[wrap] u16);
+///
+/// let handle = Handle::<String>::from_inner(5);
+/// assert_eq!(*handle, 5);
+/// assert_eq!(handle.into_inner(), 5);
+///
+/// let tagged = Tagged::<u8>::from_inner(1);
+/// assert_eq!(tagged.1, "");
+/// assert_eq!(tagged.as_inner(), &1);
+/// ```
+///
+/// Only a single field may be wrapped:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Wrapper, Clone)]
+/// struct Pair {
+///     #[wrap]
+///     first: u8,
+///     #[wrap]
+///     second: u8,
+/// }
+/// ```
+///
+/// Non-wrapped fields must implement `Default`:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Clone)]
+/// struct NoDefault;
+///
+/// #[derive(Wrapper, Clone)]
+/// struct Wrapped(#[wrap] u8, NoDefault);
+/// ```
+///
+/// Mutable access to the wrapped data:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// use amplify::{Wrapper, WrapperMut};
+///
+/// #[derive(Wrapper, Clone, Default, From, Debug)]
+/// #[wrapper_mut]
+/// struct Bytes(Vec<u8>);
+///
+/// #[derive(Wrapper, Clone, Default, From, Debug)]
+/// #[wrapper(DerefMut)]
+/// struct List<T>(Vec<T>)
+/// where
+///     T: Clone;
+///
+/// #[derive(Wrapper, Clone, Copy, Default, From, Debug)]
+/// #[wrapper(AsMut)]
+/// struct Name<'a>(&'a str);
+///
+/// let mut bytes = Bytes::default();
+/// bytes.push(1);
+/// bytes.as_mut().push(2);
+/// WrapperMut::as_inner_mut(&mut bytes).push(3);
+/// assert_eq!(bytes.into_inner(), vec![1, 2, 3]);
+///
+/// let mut list = List::<&str>::default();
+/// list.push("amplify");
+/// assert_eq!(list.len(), 1);
+///
+/// let mut name = Name("a");
+/// *name.as_mut() = "b";
+/// assert_eq!(name.into_inner(), "b");
+/// ```
+///
+/// Zero-copy conversion of references for transparent wrappers:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// use amplify::{Wrapper, WrapperTransparent};
+///
+/// #[derive(Wrapper, Copy, Clone, PartialEq, Eq, Debug, From)]
+/// #[repr(transparent)]
+/// struct Sats(u64);
+///
+/// let balances = vec![100u64, 200, 300];
+/// let total: u64 = balances
+///     .iter()
+///     .map(Sats::from_inner_ref)
+///     .map(|sats: &Sats| sats.into_inner())
+///     .sum();
+/// assert_eq!(total, 600);
+/// ```
+///
+/// Wrappers without `#[repr(transparent)]` do not support it:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// use amplify::{Wrapper, WrapperTransparent};
+///
+/// #[derive(Wrapper, Copy, Clone, PartialEq, Eq, Debug, From)]
+/// struct Sats(u64);
+///
+/// let sats = Sats::from_inner_ref(&100);
+/// ```
+///
+/// Neither do transparent wrappers with other non-`PhantomData` fields, since
+/// the wrapped field may be zero-sized:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// use amplify::{Wrapper, WrapperTransparent};
+///
+/// #[derive(Wrapper, Copy, Clone, PartialEq, Eq, Debug)]
+/// #[repr(transparent)]
+/// struct Bad {
+///     #[wrap]
+///     marker: (),
+///     value: u64,
+/// }
+///
+/// let bad = Bad::from_inner_ref(&());
+/// ```
+///
+/// Wrappers without mutable access derives can't be modified:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Wrapper, Clone, Default, From, Debug)]
+/// struct Hash([u8; 32]);
+///
+/// let mut hash = Hash::default();
+/// hash[0] = 1;
+/// ```
+///
+/// Wrappers for indexable types, which take the index output types from the
+/// inner type:
 /// ```
 /// # #[macro_use] extern crate amplify_derive;
+/// # #[macro_use] extern crate amplify;
 /// use amplify::Wrapper;
 ///
 /// #[derive(Wrapper, From)]
 /// #[wrapper(Index, IndexRange, IndexFrom, IndexTo, IndexInclusive, IndexFull)]
 /// struct VecNewtype(Vec<u8>);
+///
+/// #[derive(Wrapper, From)]
+/// #[wrapper(Index, IndexMut, RangeOps)]
+/// struct Bytes(Vec<u8>);
+///
+/// #[derive(Wrapper, From)]
+/// #[wrapper(RangeOps)]
+/// struct Name(String);
+///
+/// #[derive(Wrapper, From)]
+/// #[wrapper(Index, IndexMut, RangeOps)]
+/// struct Hash([u8; 32]);
+///
+/// let mut bytes = Bytes(vec![1, 2, 3, 4]);
+/// bytes[0] = 0;
+/// bytes[2..].copy_from_slice(&[5, 6]);
+/// assert_eq!(&bytes[..], &[0, 2, 5, 6]);
+/// assert_eq!(&bytes[..=1], &[0, 2]);
+///
+/// let name = Name(s!("amplify"));
+/// let part: &str = &name[1..4];
+/// assert_eq!(part, "mpl");
+/// assert_eq!(&name[..], "amplify");
+///
+/// let mut hash = Hash([0u8; 32]);
+/// hash[31] = 1;
+/// hash[..2].copy_from_slice(&[0xFF, 0xFF]);
+/// assert_eq!(&hash[30..], &[0, 1]);
+/// assert_eq!(&hash[..3], &[0xFF, 0xFF, 0]);
+/// ```
+///
+/// Validated wrappers:
+/// ```
+/// # #[macro_use] extern crate amplify_derive;
+/// use std::convert::TryFrom;
+/// use std::fmt::{self, Display, Formatter};
+/// use std::num::ParseIntError;
+/// use std::str::FromStr;
+///
+/// #[derive(Clone, PartialEq, Eq, Debug)]
+/// enum AmountError {
+///     Zero,
+///     Parse(ParseIntError),
+/// }
+///
+/// impl Display for AmountError {
+///     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+///         match self {
+///             AmountError::Zero => f.write_str("amount must not be zero"),
+///             AmountError::Parse(err) => Display::fmt(err, f),
+///         }
+///     }
+/// }
+///
+/// impl From<ParseIntError> for AmountError {
+///     fn from(err: ParseIntError) -> Self {
+///         AmountError::Parse(err)
+///     }
+/// }
+///
+/// fn non_zero(value: &u64) -> Result<(), AmountError> {
+///     if *value == 0 {
+///         return Err(AmountError::Zero);
+///     }
+///     Ok(())
+/// }
+///
+/// #[derive(Wrapper, Clone, Copy, PartialEq, Eq, Debug)]
+/// #[wrapper(validate(non_zero, AmountError), FromStr(AmountError))]
+/// struct Amount(u64);
+///
+/// #[derive(Wrapper, Clone, Copy, PartialEq, Eq, Debug)]
+/// #[wrapper(validate(non_zero, AmountError), no_from)]
+/// struct Index(u64);
+///
+/// assert_eq!(Amount::try_from_inner(5), Ok(Amount(5)));
+/// assert_eq!(Amount::try_from_inner(0), Err(AmountError::Zero));
+/// assert_eq!(Amount::from(5), Amount(5));
+/// assert_eq!(Amount::from_str("7"), Ok(Amount(7)));
+/// assert_eq!(Amount::from_str("0"), Err(AmountError::Zero));
+/// assert!(matches!(Amount::from_str("-1"), Err(AmountError::Parse(_))));
+///
+/// assert_eq!(Index::try_from(1), Ok(Index(1)));
+/// assert_eq!(Index::try_from(0), Err(AmountError::Zero));
+/// ```
+///
+/// Construction of validated wrapper from invalid data with
+/// `Wrapper::from_inner` or [`From`] panics:
+/// ```should_panic
+/// # #[macro_use] extern crate amplify_derive;
+/// # #[derive(Debug)]
+/// # struct Zero;
+/// # impl std::fmt::Display for Zero {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+/// #         f.write_str("amount must not be zero")
+/// #     }
+/// # }
+/// # fn non_zero(value: &u64) -> Result<(), Zero> {
+/// #     if *value == 0 { Err(Zero) } else { Ok(()) }
+/// # }
+/// #[derive(Wrapper, Clone, Copy, PartialEq, Eq, Debug)]
+/// #[wrapper(validate(non_zero, Zero))]
+/// struct Amount(u64);
+///
+/// // panics with "invalid `Amount` value: amount must not be zero"
+/// let _ = Amount::from(0);
+/// ```
+///
+/// Validated wrappers can't be constructed from a reference to invalid data,
+/// even if they are transparent:
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify_derive;
+/// # #[derive(Debug)]
+/// # struct Zero;
+/// # impl std::fmt::Display for Zero {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+/// #         f.write_str("amount must not be zero")
+/// #     }
+/// # }
+/// # fn non_zero(value: &u64) -> Result<(), Zero> {
+/// #     if *value == 0 { Err(Zero) } else { Ok(()) }
+/// # }
+/// use amplify::WrapperTransparent;
+///
+/// #[derive(Wrapper, Clone, Copy, PartialEq, Eq, Debug)]
+/// #[wrapper(validate(non_zero, Zero))]
+/// #[repr(transparent)]
+/// struct Amount(u64);
+///
+/// let _ = Amount::from_inner_ref(&0);
+/// ```
+///
+/// Out of bounds access panics in the same way as for the inner type:
+/// ```should_panic
+/// # #[macro_use] extern crate amplify_derive;
+/// #[derive(Wrapper, From)]
+/// #[wrapper(Index, RangeOps)]
+/// struct Bytes(Vec<u8>);
+///
+/// let bytes = Bytes(vec![1, 2, 3]);
+/// let _ = &bytes[2..4];
 /// ```
-#[proc_macro_derive(Wrapper, attributes(wrap, wrapper, amplify_crate))]
+#[proc_macro_derive(Wrapper, attributes(wrap, wrapper, wrapper_mut, amplify_crate))]
 pub fn derive_wrapper(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
     wrapper::inner(derive_input)
@@ -0,0 +1,275 @@
+// Rust language amplification derive library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::iter::FromIterator;
+use proc_macro2::{TokenStream as TokenStream2, Span, Ident};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Error, Fields, Result, LitStr, DataStruct, Field};
+
+use amplify_syn::{ParametrizedAttr, AttrReq, ArgValueReq, ValueClass, ExtractAttr};
+
+use crate::getters;
+use crate::util::ident_or_raw;
+
+const NAME: &str = "setter";
+
+pub(crate) fn derive(input: DeriveInput) -> Result<TokenStream2> {
+    let mut global_param = SetterDerive::extract(&input, true)?;
+    let _ = SetterDerive::try_from(&mut global_param, true)?;
+
+    match input.data {
+        Data::Struct(ref data) => derive_struct_impl(&input, data, global_param),
+        Data::Enum(_) => Err(Error::new_spanned(
+            &input,
+            "Deriving setters is not supported in enums",
+        )),
+        Data::Union(_) => Err(Error::new_spanned(
+            &input,
+            "Deriving setters is not supported in unions",
+        )),
+    }
+}
+
+#[derive(Clone)]
+struct SetterDerive {
+    pub skip: bool,
+    pub no_into: bool,
+    pub strip_option: bool,
+    pub rename: Option<LitStr>,
+}
+
+impl SetterDerive {
+    fn attr_req(global: bool) -> AttrReq {
+        let mut map = HashMap::from_iter(vec![
+            ("no_into", ArgValueReq::Prohibited),
+            ("strip_option", ArgValueReq::Prohibited),
+        ]);
+
+        if !global {
+            map.insert("skip", ArgValueReq::Prohibited);
+            map.insert("rename", ArgValueReq::Optional(ValueClass::str()));
+        }
+
+        AttrReq::with(map)
+    }
+
+    /// Extracts checked `#[setter]` attribute from a structure or a field,
+    /// returning an empty attribute if it is absent
+    fn extract(item: &impl ExtractAttr, global: bool) -> Result<ParametrizedAttr> {
+        Ok(item
+            .parametrized_attr(NAME, &SetterDerive::attr_req(global))?
+            .unwrap_or_else(|| ParametrizedAttr::new(NAME)))
+    }
+
+    fn try_from(attr: &mut ParametrizedAttr, global: bool) -> Result<SetterDerive> {
+        attr.check(SetterDerive::attr_req(global))?;
+
+        Ok(SetterDerive {
+            skip: attr.args.contains_key("skip"),
+            no_into: attr.args.contains_key("no_into"),
+            strip_option: attr.args.contains_key("strip_option"),
+            rename: attr
+                .args
+                .get("rename")
+                .map(|a| a.clone().try_into())
+                .transpose()?,
+        })
+    }
+
+    fn setter_fn_ident(&self, prefix: &str, field_name: &Ident, span: Span) -> Result<Ident> {
+        let base = self
+            .rename
+            .as_ref()
+            .map(LitStr::value)
+            .unwrap_or_else(|| field_name.unraw().to_string());
+        let s = format!("{}{}", prefix, base);
+        ident_or_raw(&s, span)
+            .ok_or_else(|| Error::new(span, format!("`{}` is not a valid setter name", s)))
+    }
+}
+
+fn derive_struct_impl(
+    input: &DeriveInput,
+    data: &DataStruct,
+    global_param: ParametrizedAttr,
+) -> Result<TokenStream2> {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let struct_name = &input.ident;
+
+    let fields = match data.fields {
+        Fields::Named(ref fields) => &fields.named,
+        Fields::Unnamed(_) => {
+            return Err(Error::new(
+                Span::call_site(),
+                "Deriving setters is not supported for tuple-bases structs",
+            ))
+        }
+        Fields::Unit => {
+            return Err(Error::new(
+                Span::call_site(),
+                "Deriving setters is meaningless for unit structs",
+            ))
+        }
+    };
+
+    // Getter names are known only if the structure is equipped with
+    // `#[getter]` attributes, which is possible only when `Getters` are
+    // derived
+    let has_getters = input
+        .attrs
+        .iter()
+        .chain(fields.iter().flat_map(|field| &field.attrs))
+        .any(|attr| attr.path.is_ident("getter"));
+    let getters = if has_getters {
+        getters::method_names(input, data)?
+    } else {
+        vec![]
+    };
+
+    let mut methods = Vec::<(Ident, Span, TokenStream2)>::with_capacity(fields.len() * 2);
+    for field in fields {
+        for (name, span, method) in derive_field_methods(field, struct_name, &global_param)? {
+            let first = getters
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, first)| ("getter", *first))
+                .or_else(|| {
+                    methods
+                        .iter()
+                        .find(|(n, ..)| *n == name)
+                        .map(|(_, first, _)| ("setter", *first))
+                });
+            if let Some((kind, first)) = first {
+                let mut err =
+                    Error::new(span, format!("setter method `{}` is already defined", name));
+                err.combine(Error::new(
+                    first,
+                    format!("{} method `{}` is first defined here", kind, name),
+                ));
+                return Err(err);
+            }
+            methods.push((name, span, method));
+        }
+    }
+
+    let methods = methods.into_iter().map(|(_, _, method)| method);
+    Ok(quote! {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            #( #methods )*
+        }
+    })
+}
+
+fn derive_field_methods(
+    field: &Field,
+    struct_name: &Ident,
+    global_param: &ParametrizedAttr,
+) -> Result<Vec<(Ident, Span, TokenStream2)>> {
+    let mut local_param = SetterDerive::extract(field, false)?;
+    let _ = SetterDerive::try_from(&mut local_param, false)?;
+    let mut params = global_param.clone().merged(local_param)?;
+    let setter = SetterDerive::try_from(&mut params, false)?;
+
+    if setter.skip {
+        return Ok(Vec::new());
+    }
+
+    let field_name = field
+        .ident
+        .as_ref()
+        .expect("named fields are checked before");
+    let ty = &field.ty;
+
+    let value_ty = match (
+        setter.strip_option,
+        getters::is_known_type(ty, "Option", &["option"]),
+    ) {
+        (true, Some(Some(inner))) => inner,
+        (true, _) => {
+            return Err(Error::new(
+                ty.span(),
+                "`#[setter(strip_option)]` is applicable only to `Option<T>` fields",
+            ))
+        }
+        (false, _) => ty.clone(),
+    };
+    let (arg_ty, value) = if setter.no_into {
+        (quote! { #value_ty }, quote! { value })
+    } else {
+        (
+            quote! { impl ::core::convert::Into<#value_ty> },
+            quote! { value.into() },
+        )
+    };
+    let value = if setter.strip_option {
+        quote! { ::core::option::Option::Some(#value) }
+    } else {
+        value
+    };
+
+    let doc = field
+        .attrs
+        .iter()
+        .filter(|a| a.path.is_ident("doc"))
+        .collect::<Vec<_>>();
+    let field_ref = format!("[`{}::{}`]", struct_name, field_name);
+    let with_doc = format!(
+        "Method consuming the value and returning it with {} field replaced.\n",
+        field_ref
+    );
+    let set_doc = format!("Method replacing {} field value.\n", field_ref);
+
+    // Name collisions are reported at the setter attribute, if any
+    let span = field
+        .attrs
+        .iter()
+        .find(|a| a.path.is_ident(NAME))
+        .map(|a| a.tokens.span())
+        .unwrap_or_else(|| field.span());
+
+    let with_name = setter.setter_fn_ident("with_", field_name, field.span())?;
+    let set_name = setter.setter_fn_ident("set_", field_name, field.span())?;
+
+    Ok(vec![
+        (
+            with_name.clone(),
+            span,
+            quote_spanned! { field.span() =>
+                #[doc = #with_doc]
+                #( #doc )*
+                #[inline]
+                pub fn #with_name(mut self, value: #arg_ty) -> Self {
+                    self.#field_name = #value;
+                    self
+                }
+            },
+        ),
+        (
+            set_name.clone(),
+            span,
+            quote_spanned! { field.span() =>
+                #[doc = #set_doc]
+                #( #doc )*
+                #[inline]
+                pub fn #set_name(&mut self, value: #arg_ty) {
+                    self.#field_name = #value;
+                }
+            },
+        ),
+    ])
+}
@@ -0,0 +1,272 @@
+// Rust language amplification derive library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::ext::IdentExt;
+use syn::parse::ParseStream;
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Data, DataEnum, DataStruct, DeriveInput, Error, Expr, ExprLit, ExprUnary, Fields,
+    Ident, Lit, Path, Result, Type, UnOp,
+};
+
+const NAME: &str = "try_from";
+const EXAMPLE: &str = r#"#[try_from(u8, Error = ParseError)]"#;
+
+/// Parsed `#[try_from(...)]` attribute, each of which produces a separate
+/// `TryFrom` implementation
+struct TryFromAttr {
+    pub span: Span,
+    pub source: Option<Type>,
+    pub error: Option<Type>,
+    pub error_expr: Option<Expr>,
+    pub with: Option<Path>,
+}
+
+impl TryFromAttr {
+    fn with(attr: &Attribute) -> Result<Self> {
+        let mut res = TryFromAttr {
+            span: attr.tokens.span(),
+            source: None,
+            error: None,
+            error_expr: None,
+            with: None,
+        };
+        attr.parse_args_with(|input: ParseStream| {
+            while !input.is_empty() {
+                if input.peek(Ident) && input.peek2(Token![=]) {
+                    let name = input.parse::<Ident>()?;
+                    input.parse::<Token![=]>()?;
+                    let duplicate = match name.to_string().as_str() {
+                        "Error" => res.error.replace(input.parse()?).is_some(),
+                        "error_expr" => res.error_expr.replace(input.parse()?).is_some(),
+                        "with" => res.with.replace(input.parse()?).is_some(),
+                        _ => {
+                            return Err(attr_err!(
+                                name,
+                                "unknown argument; supported arguments are `Error`, \
+                                 `error_expr` and `with`"
+                            ))
+                        }
+                    };
+                    if duplicate {
+                        return Err(attr_err!(name, "argument can be given only once"));
+                    }
+                } else if res.source.is_none() {
+                    res.source = Some(input.parse()?);
+                } else {
+                    return Err(attr_err!(
+                        input.span(),
+                        "only a single source type can be given per attribute"
+                    ));
+                }
+                if input.is_empty() {
+                    break;
+                }
+                input.parse::<Token![,]>()?;
+            }
+            Ok(())
+        })?;
+        Ok(res)
+    }
+
+    fn error_type(&self) -> Result<&Type> {
+        self.error.as_ref().ok_or_else(|| {
+            attr_err!(
+                self.span,
+                "error type must be given with `Error = Type` argument"
+            )
+        })
+    }
+}
+
+fn attrs(input: &DeriveInput) -> Result<Vec<TryFromAttr>> {
+    let attrs = input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident(NAME))
+        .map(TryFromAttr::with)
+        .collect::<Result<Vec<_>>>()?;
+    if attrs.is_empty() {
+        return Err(Error::new(
+            input.span(),
+            format!(
+                "Deriving `TryFrom`: required attribute `{}` is missing.\n{}",
+                NAME, EXAMPLE
+            ),
+        ));
+    }
+    Ok(attrs)
+}
+
+pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
+    match input.data {
+        Data::Struct(ref data) => inner_struct(&input, data),
+        Data::Enum(ref data) => inner_enum(&input, data),
+        Data::Union(_) => Err(Error::new_spanned(
+            &input,
+            "Deriving `TryFrom` is not supported in unions",
+        )),
+    }
+}
+
+fn inner_struct(input: &DeriveInput, data: &DataStruct) -> Result<TokenStream2> {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let ident_name = &input.ident;
+
+    let field = match (data.fields.len(), data.fields.iter().next()) {
+        (1, Some(field)) => field,
+        _ => {
+            return Err(Error::new_spanned(
+                input,
+                "Deriving `TryFrom` is supported only for newtypes (structures with a single \
+                 field) and C-like enums",
+            ))
+        }
+    };
+
+    let mut impls = TokenStream2::new();
+    for attr in attrs(input)? {
+        if let Some(ref expr) = attr.error_expr {
+            return Err(attr_err!(
+                expr,
+                "`error_expr` is applicable only to enums; newtypes report errors from the \
+                 validating function"
+            ));
+        }
+        let source = attr.source.as_ref().unwrap_or(&field.ty);
+        let error = attr.error_type()?;
+        let validate = match attr.with {
+            Some(ref path) => quote_spanned! { path.span() => #path(value) },
+            None => quote_spanned! { attr.span => Self::try_from_inner(value) },
+        };
+        impls.extend(quote! {
+            impl #impl_generics ::core::convert::TryFrom<#source> for #ident_name #ty_generics #where_clause {
+                type Error = #error;
+
+                fn try_from(value: #source) -> Result<Self, Self::Error> {
+                    #validate
+                }
+            }
+        });
+    }
+    Ok(impls)
+}
+
+/// Returns value of the discriminant if it is given by an integer literal
+fn literal_value(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse().ok(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => literal_value(expr).map(|value| -value),
+        _ => None,
+    }
+}
+
+fn inner_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream2> {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let ident_name = &input.ident;
+
+    // Discriminant constants: variant name, constant name and the expression
+    // for the constant value, which for variants without explicit
+    // discriminants is computed from the previous one
+    let mut discriminants = Vec::<(&Ident, Ident, TokenStream2)>::new();
+    let mut known = Vec::<(i128, &Ident)>::new();
+    let mut last = None::<i128>;
+    for v in &data.variants {
+        match v.fields {
+            Fields::Unit => {}
+            _ => {
+                return Err(Error::new_spanned(
+                    v,
+                    "Deriving `TryFrom` is possible only for C-like enums with fieldless variants",
+                ))
+            }
+        }
+        let name = Ident::new(&format!("__{}", v.ident.unraw()), v.ident.span());
+        let (value, expr) = match (&v.discriminant, discriminants.last()) {
+            (Some((_, expr)), _) => (literal_value(expr), quote! { #expr }),
+            (None, Some((_, prev, _))) => (last.map(|last| last + 1), quote! { #prev + 1 }),
+            (None, None) => (Some(0), quote! { 0 }),
+        };
+        if let Some(value) = value {
+            if let Some((_, first)) = known.iter().find(|(known, _)| *known == value) {
+                let msg = format!(
+                    "discriminant value `{}` of `{}` overlaps with variant `{}`",
+                    value, v.ident, first
+                );
+                let mut err = attr_err!(v, msg);
+                err.combine(Error::new(
+                    first.span(),
+                    format!("discriminant value `{}` is first used here", value),
+                ));
+                return Err(err);
+            }
+            known.push((value, &v.ident));
+        }
+        last = value;
+        discriminants.push((&v.ident, name, expr));
+    }
+
+    let mut impls = TokenStream2::new();
+    for attr in attrs(input)? {
+        if let Some(ref path) = attr.with {
+            return Err(attr_err!(
+                path,
+                "`with` is applicable only to newtypes; enums are converted by their \
+                 discriminants"
+            ));
+        }
+        let source = attr.source.as_ref().ok_or_else(|| {
+            attr_err!(
+                attr.span,
+                "source type must be given as the first argument for enums"
+            )
+        })?;
+        let error = attr.error_type()?;
+        let err = match attr.error_expr {
+            Some(ref expr) => quote! { #expr },
+            None => quote_spanned! { error.span() =>
+                <#error as ::core::convert::From<#source>>::from(value)
+            },
+        };
+        let consts = discriminants.iter().map(|(_, name, expr)| {
+            quote! { const #name: #source = #expr; }
+        });
+        let arms = discriminants.iter().map(|(variant, name, _)| {
+            quote! { #name => Ok(Self::#variant), }
+        });
+        impls.extend(quote! {
+            impl #impl_generics ::core::convert::TryFrom<#source> for #ident_name #ty_generics #where_clause {
+                type Error = #error;
+
+                #[allow(non_upper_case_globals)]
+                fn try_from(value: #source) -> Result<Self, Self::Error> {
+                    #( #consts )*
+                    match value {
+                        #( #arms )*
+                        _ => Err(#err),
+                    }
+                }
+            }
+        });
+    }
+    Ok(impls)
+}
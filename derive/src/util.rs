@@ -15,7 +15,12 @@
 
 #![allow(dead_code)]
 
-use syn::{DeriveInput, Lit, Result, Meta, Ident, Attribute, NestedMeta, Path, MetaNameValue};
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::ToTokens;
+use syn::{
+    DeriveInput, Lit, Result, Meta, Ident, Attribute, NestedMeta, Path, MetaNameValue, Generics,
+    Type, WhereClause, WherePredicate,
+};
 use syn::punctuated::IntoIter;
 use syn::spanned::Spanned;
 
@@ -172,3 +177,67 @@ pub fn nested_one_named_value(
     })
     .transpose()
 }
+
+/// Detects whether a type references any of the generic type parameters
+pub fn type_uses_generics(ty: &Type, generics: &Generics) -> bool {
+    !type_generic_params(ty, generics).is_empty()
+}
+
+/// Returns generic type parameters referenced by a type
+pub fn type_generic_params<'a>(ty: &Type, generics: &'a Generics) -> Vec<&'a Ident> {
+    generics
+        .type_params()
+        .map(|param| &param.ident)
+        .filter(|param| type_uses_ident(ty, param))
+        .collect()
+}
+
+/// Detects whether a type mentions the identifier (which may be a name of a
+/// generic parameter, including lifetimes, given without the apostrophe)
+pub fn type_uses_ident(ty: &Type, ident: &Ident) -> bool {
+    fn scan(stream: TokenStream2, param: &Ident) -> bool {
+        stream.into_iter().any(|tt| match tt {
+            TokenTree::Ident(ident) => &ident == param,
+            TokenTree::Group(group) => scan(group.stream(), param),
+            _ => false,
+        })
+    }
+
+    scan(ty.to_token_stream(), ident)
+}
+
+/// Constructs where clause for a generated trait implementation, extending
+/// the where clause of the original type with additional predicates.
+/// Duplicated predicates are omitted.
+pub fn where_clause_with(
+    generics: &Generics,
+    predicates: impl IntoIterator<Item = WherePredicate>,
+) -> Option<WhereClause> {
+    let mut where_clause = generics.where_clause.clone();
+    for predicate in predicates {
+        let clause = where_clause.get_or_insert_with(|| WhereClause {
+            where_token: Default::default(),
+            predicates: Default::default(),
+        });
+        let repr = predicate.to_token_stream().to_string();
+        if !clause
+            .predicates
+            .iter()
+            .any(|p| p.to_token_stream().to_string() == repr)
+        {
+            clause.predicates.push(predicate);
+        }
+    }
+    where_clause
+}
+
+/// Constructs identifier with the given name and span, using raw identifier
+/// form (like `r#type`) for names which are keywords. Returns `None` if the
+/// name can't be an identifier at all.
+pub fn ident_or_raw(name: &str, span: Span) -> Option<Ident> {
+    let mut ident = syn::parse_str::<Ident>(name)
+        .or_else(|_| syn::parse_str::<Ident>(&format!("r#{}", name)))
+        .ok()?;
+    ident.set_span(span);
+    Some(ident)
+}
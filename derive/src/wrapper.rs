@@ -13,28 +13,48 @@
 // along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use syn::{
-    DeriveInput, Result, Data, Error, Fields, Index, Meta, MetaList, Path, NestedMeta,
-    spanned::Spanned,
+    Attribute, DeriveInput, Result, Data, Error, Fields, Ident, Index, Meta, MetaList, Path,
+    NestedMeta, Type, punctuated::Punctuated, spanned::Spanned,
 };
 
-use crate::util::get_amplify_crate;
+use crate::util::{get_amplify_crate, where_clause_with};
 
 const NAME: &str = "wrapper";
 const EXAMPLE: &str = r#"#[wrapper(LowerHex, Add)]"#;
 
+/// Validation function with its error type given in
+/// `#[wrapper(validate(function, Error))]` attribute
+struct Validate {
+    func: Path,
+    err: Path,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 enum WrapperDerives {
     FromStr,
     Display,
     Debug,
     Octal,
+    Binary,
+    FromHex,
     LowerHex,
     UpperHex,
     LowerExp,
     UpperExp,
+    Hash,
+    PartialOrd,
+    Ord,
+    #[cfg(feature = "serde")]
+    Serialize,
+    #[cfg(feature = "serde")]
+    Deserialize,
     BorrowSlice,
+    RawArray,
+    AsMut,
+    BorrowMut,
+    DerefMut,
     Index,
     IndexMut,
     IndexRange,
@@ -42,6 +62,10 @@ enum WrapperDerives {
     IndexFrom,
     IndexTo,
     IndexInclusive,
+    RangeOps,
+    /// Mutable indexing by ranges; not parsed from the attribute but implied
+    /// when both `IndexMut` and `RangeOps` are present
+    RangeOpsMut,
     Neg,
     Not,
     Add,
@@ -81,11 +105,33 @@ impl WrapperDerives {
                     "Display" => Some(WrapperDerives::Display),
                     "Debug" => Some(WrapperDerives::Debug),
                     "Octal" => Some(WrapperDerives::Octal),
+                    "Binary" => Some(WrapperDerives::Binary),
+                    "FromHex" => Some(WrapperDerives::FromHex),
                     "LowerHex" => Some(WrapperDerives::LowerHex),
                     "UpperHex" => Some(WrapperDerives::UpperHex),
                     "LowerExp" => Some(WrapperDerives::LowerExp),
                     "UpperExp" => Some(WrapperDerives::UpperExp),
+                    "Hash" => Some(WrapperDerives::Hash),
+                    "PartialOrd" => Some(WrapperDerives::PartialOrd),
+                    "Ord" => Some(WrapperDerives::Ord),
+                    #[cfg(feature = "serde")]
+                    "Serialize" => Some(WrapperDerives::Serialize),
+                    #[cfg(feature = "serde")]
+                    "Deserialize" => Some(WrapperDerives::Deserialize),
+                    #[cfg(not(feature = "serde"))]
+                    "Serialize" | "Deserialize" => {
+                        return Err(attr_err!(
+                            path.span(),
+                            NAME,
+                            "serde traits require `serde` feature of `amplify_derive` crate",
+                            EXAMPLE
+                        ))
+                    }
                     "BorrowSlice" => Some(WrapperDerives::BorrowSlice),
+                    "RawArray" => Some(WrapperDerives::RawArray),
+                    "AsMut" => Some(WrapperDerives::AsMut),
+                    "BorrowMut" => Some(WrapperDerives::BorrowMut),
+                    "DerefMut" => Some(WrapperDerives::DerefMut),
                     "Index" => Some(WrapperDerives::Index),
                     "IndexMut" => Some(WrapperDerives::IndexMut),
                     "IndexRange" => Some(WrapperDerives::IndexRange),
@@ -93,6 +139,7 @@ impl WrapperDerives {
                     "IndexFrom" => Some(WrapperDerives::IndexFrom),
                     "IndexTo" => Some(WrapperDerives::IndexTo),
                     "IndexInclusive" => Some(WrapperDerives::IndexInclusive),
+                    "RangeOps" => Some(WrapperDerives::RangeOps),
                     "Add" => Some(WrapperDerives::Add),
                     "Neg" => Some(WrapperDerives::Neg),
                     "Not" => Some(WrapperDerives::Not),
@@ -121,28 +168,146 @@ impl WrapperDerives {
         )
     }
 
-    pub fn into_token_stream2(self, input: &DeriveInput) -> TokenStream2 {
-        let impl_generics_params = input.generics.params.clone();
+    /// Whether the derive requires mutable access to the inner data via
+    /// `WrapperMut`
+    pub fn is_mut(self) -> bool {
+        #[allow(clippy::match_like_matches_macro)]
+        // Ancient rust versions do not known about `matches!` macro
+        match self {
+            WrapperDerives::AsMut
+            | WrapperDerives::BorrowMut
+            | WrapperDerives::DerefMut
+            | WrapperDerives::IndexMut
+            | WrapperDerives::RangeOpsMut
+            | WrapperDerives::AddAssign
+            | WrapperDerives::SubAssign
+            | WrapperDerives::MulAssign
+            | WrapperDerives::DivAssign
+            | WrapperDerives::RemAssign
+            | WrapperDerives::ShlAssign
+            | WrapperDerives::ShrAssign
+            | WrapperDerives::BitAndAssign
+            | WrapperDerives::BitOrAssign
+            | WrapperDerives::BitXorAssign => true,
+            _ => false,
+        }
+    }
+
+    /// Name of the trait which may also be implemented with a separate derive
+    /// macro, conflicting with the implementation provided by the wrapper
+    pub fn derivable_trait(self) -> Option<&'static str> {
+        match self {
+            WrapperDerives::Hash => Some("Hash"),
+            WrapperDerives::PartialOrd => Some("PartialOrd"),
+            WrapperDerives::Ord => Some("Ord"),
+            #[cfg(feature = "serde")]
+            WrapperDerives::Serialize => Some("Serialize"),
+            #[cfg(feature = "serde")]
+            WrapperDerives::Deserialize => Some("Deserialize"),
+            _ => None,
+        }
+    }
+
+    pub fn into_token_stream2(
+        self,
+        input: &DeriveInput,
+        span: Span,
+        from_str_err: Option<&Path>,
+        validate: Option<&Validate>,
+    ) -> TokenStream2 {
         let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
         let ident_name = &input.ident;
         let amplify_crate = get_amplify_crate(input);
 
-        match self {
-            WrapperDerives::FromStr => quote! {
-                impl #impl_generics ::core::str::FromStr for #ident_name #ty_generics #where_clause
+        // Shifts take the count type of the inner value (e.g. `u32`) as the
+        // right-hand side, not the wrapper itself
+        let mut rhs_generics = input.generics.clone();
+        rhs_generics.params.push(parse_quote! { __Rhs });
+        let (rhs_impl_generics, _, _) = rhs_generics.split_for_impl();
+        let inner_where = |bound: TokenStream2| {
+            let predicate = quote_spanned! { span =>
+                <Self as #amplify_crate::Wrapper>::Inner: #bound
+            };
+            where_clause_with(&input.generics, Some(parse_quote! { #predicate }))
+        };
+
+        // Index output types are taken from the inner type own `Index`
+        // implementations, so `String` wrappers produce `str` for ranges etc.
+        let index = |idx: TokenStream2| {
+            let where_clause = inner_where(quote! { ::core::ops::Index<#idx> });
+            quote_spanned! { span =>
+                impl #impl_generics ::core::ops::Index<#idx> for #ident_name #ty_generics #where_clause
                 {
-                    type Err = <<Self as #amplify_crate::Wrapper>::Inner as ::core::str::FromStr>::Err;
+                    type Output = <<Self as #amplify_crate::Wrapper>::Inner as ::core::ops::Index<#idx>>::Output;
 
                     #[inline]
-                    fn from_str(s: &str) -> Result<Self, Self::Err> {
-                        use ::core::str::FromStr;
+                    fn index(&self, index: #idx) -> &Self::Output {
                         use #amplify_crate::Wrapper;
+                        ::core::ops::Index::index(Wrapper::as_inner(self), index)
+                    }
+                }
+            }
+        };
+        let index_mut = |idx: TokenStream2| {
+            let where_clause = inner_where(quote! { ::core::ops::IndexMut<#idx> });
+            quote_spanned! { span =>
+                impl #impl_generics ::core::ops::IndexMut<#idx> for #ident_name #ty_generics #where_clause
+                {
+                    #[inline]
+                    fn index_mut(&mut self, index: #idx) -> &mut Self::Output {
+                        use #amplify_crate::WrapperMut;
+                        ::core::ops::IndexMut::index_mut(WrapperMut::as_inner_mut(self), index)
+                    }
+                }
+            }
+        };
+        let ranges = [
+            quote! { ::core::ops::Range<usize> },
+            quote! { ::core::ops::RangeFrom<usize> },
+            quote! { ::core::ops::RangeTo<usize> },
+            quote! { ::core::ops::RangeFull },
+            quote! { ::core::ops::RangeInclusive<usize> },
+            quote! { ::core::ops::RangeToInclusive<usize> },
+        ];
+
+        match self {
+            WrapperDerives::FromStr => {
+                let (err, map_err) = match from_str_err {
+                    Some(err) => (
+                        quote! { #err },
+                        quote_spanned! { err.span() => .map_err(<#err as ::core::convert::From<_>>::from) },
+                    ),
+                    None => (
+                        quote! { <<Self as #amplify_crate::Wrapper>::Inner as ::core::str::FromStr>::Err },
+                        quote! {},
+                    ),
+                };
+                let construct = match validate {
+                    Some(_) => quote! {
+                        Self::try_from_inner(
+                            <Self as #amplify_crate::Wrapper>::Inner::from_str(s) #map_err ?,
+                        ) #map_err
+                    },
+                    None => quote! {
                         Ok(Wrapper::from_inner(
-                            <Self as #amplify_crate::Wrapper>::Inner::from_str(s)?,
+                            <Self as #amplify_crate::Wrapper>::Inner::from_str(s) #map_err ?,
                         ))
+                    },
+                };
+                quote! {
+                    impl #impl_generics ::core::str::FromStr for #ident_name #ty_generics #where_clause
+                    {
+                        type Err = #err;
+
+                        #[inline]
+                        fn from_str(s: &str) -> Result<Self, Self::Err> {
+                            use ::core::str::FromStr;
+                            use #amplify_crate::Wrapper;
+                            #construct
+                        }
                     }
                 }
-            },
+            }
             WrapperDerives::Display => quote! {
                 impl #impl_generics ::core::fmt::Display for #ident_name #ty_generics #where_clause
                 {
@@ -173,6 +338,43 @@ impl WrapperDerives {
                     }
                 }
             },
+            WrapperDerives::Binary => quote! {
+                impl #impl_generics ::core::fmt::Binary for #ident_name #ty_generics #where_clause
+                {
+                    #[inline]
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        use #amplify_crate::Wrapper;
+                        ::core::fmt::Binary::fmt(Wrapper::as_inner(self), f)
+                    }
+                }
+            },
+            WrapperDerives::FromHex => {
+                let construct = match validate {
+                    Some(_) => quote! {
+                        .and_then(|inner| {
+                            Self::try_from_inner(inner)
+                                .map_err(|_| #amplify_crate::hex::Error::InvalidValue)
+                        })
+                    },
+                    None => quote! { .map(Wrapper::from_inner) },
+                };
+                quote! {
+                impl #impl_generics #amplify_crate::hex::FromHex for #ident_name #ty_generics #where_clause
+                {
+                    #[inline]
+                    fn from_byte_iter<I>(iter: I) -> Result<Self, #amplify_crate::hex::Error>
+                    where
+                        I: Iterator<Item = Result<u8, #amplify_crate::hex::Error>>
+                            + ExactSizeIterator
+                            + DoubleEndedIterator,
+                    {
+                        use #amplify_crate::Wrapper;
+                        <<Self as Wrapper>::Inner as #amplify_crate::hex::FromHex>::from_byte_iter(iter)
+                            #construct
+                    }
+                }
+                }
+            }
             WrapperDerives::LowerHex => quote! {
                 impl #impl_generics ::core::fmt::LowerHex for #ident_name #ty_generics #where_clause
                 {
@@ -213,121 +415,160 @@ impl WrapperDerives {
                     }
                 }
             },
-            WrapperDerives::BorrowSlice => quote! {
-                impl #impl_generics ::core::borrow::Borrow<[u8]> for #ident_name #ty_generics #where_clause
-                {
-                    #[inline]
-                    fn borrow(&self) -> &[u8] {
-                        use #amplify_crate::Wrapper;
-                        ::core::borrow::Borrow::<[u8]>::borrow(Wrapper::as_inner(self))
-                    }
-                }
-            },
-            WrapperDerives::Index => {
-                let where_clause = match where_clause {
-                    None => quote! { where },
-                    Some(_) => quote! { #where_clause },
-                };
-                quote! {
-                    impl <#impl_generics_params> ::core::ops::Index<usize> for #ident_name #ty_generics #where_clause
+            WrapperDerives::Hash => {
+                let where_clause = inner_where(quote! { ::core::hash::Hash });
+                quote_spanned! { span =>
+                    impl #impl_generics ::core::hash::Hash for #ident_name #ty_generics #where_clause
                     {
-                        type Output = <<Self as #amplify_crate::Wrapper>::Inner as ::core::ops::Index<usize>>::Output;
-
                         #[inline]
-                        fn index(&self, index: usize) -> &Self::Output {
+                        fn hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
                             use #amplify_crate::Wrapper;
-                            Wrapper::as_inner(self).index(index)
+                            ::core::hash::Hash::hash(Wrapper::as_inner(self), state)
                         }
                     }
                 }
             }
-            WrapperDerives::IndexMut => {
-                let where_clause = match where_clause {
-                    None => quote! { where },
-                    Some(_) => quote! { #where_clause },
-                };
-                quote! {
-                    impl <#impl_generics_params> ::core::ops::IndexMut<usize> for #ident_name #ty_generics #where_clause
+            WrapperDerives::PartialOrd => {
+                let where_clause = inner_where(quote! { ::core::cmp::PartialOrd });
+                quote_spanned! { span =>
+                    impl #impl_generics ::core::cmp::PartialOrd for #ident_name #ty_generics #where_clause
                     {
                         #[inline]
-                        fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+                        fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
                             use #amplify_crate::Wrapper;
-                            Wrapper::as_inner_mut(self).index_mut(index)
+                            ::core::cmp::PartialOrd::partial_cmp(Wrapper::as_inner(self), Wrapper::as_inner(other))
                         }
                     }
                 }
             }
-            WrapperDerives::IndexRange => {
-                quote! {
-                    impl <#impl_generics_params> ::core::ops::Index<::core::ops::Range<usize>> for #ident_name #ty_generics #where_clause
+            WrapperDerives::Ord => {
+                let where_clause = inner_where(quote! { ::core::cmp::Ord });
+                quote_spanned! { span =>
+                    impl #impl_generics ::core::cmp::Ord for #ident_name #ty_generics #where_clause
                     {
-                        type Output = <<Self as #amplify_crate::Wrapper>::Inner as ::core::ops::Index<::core::ops::Range<usize>>>::Output;
-
                         #[inline]
-                        fn index(&self, index: ::core::ops::Range<usize>) -> &Self::Output {
+                        fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
                             use #amplify_crate::Wrapper;
-                            Wrapper::as_inner(self).index(index)
+                            ::core::cmp::Ord::cmp(Wrapper::as_inner(self), Wrapper::as_inner(other))
                         }
                     }
                 }
             }
-            WrapperDerives::IndexFrom => {
-                quote! {
-                    impl <#impl_generics_params> ::core::ops::Index<::core::ops::RangeFrom<usize>> for #ident_name #ty_generics #where_clause
+            #[cfg(feature = "serde")]
+            WrapperDerives::Serialize => {
+                let serde = quote! { #amplify_crate::_export::serde };
+                let where_clause = inner_where(quote! { #serde::Serialize });
+                quote_spanned! { span =>
+                    impl #impl_generics #serde::Serialize for #ident_name #ty_generics #where_clause
                     {
-                        type Output = <<Self as #amplify_crate::Wrapper>::Inner as ::core::ops::Index<::core::ops::RangeFrom<usize>>>::Output;
-
                         #[inline]
-                        fn index(&self, index: ::core::ops::RangeFrom<usize>) -> &Self::Output {
+                        fn serialize<__S>(&self, serializer: __S) -> Result<__S::Ok, __S::Error>
+                        where
+                            __S: #serde::Serializer,
+                        {
                             use #amplify_crate::Wrapper;
-                            Wrapper::as_inner(self).index(index)
+                            #serde::Serialize::serialize(Wrapper::as_inner(self), serializer)
                         }
                     }
                 }
             }
-            WrapperDerives::IndexTo => {
-                quote! {
-                    impl <#impl_generics_params> ::core::ops::Index<::core::ops::RangeTo<usize>> for #ident_name #ty_generics #where_clause
+            // Deserialization is universally quantified over the lifetime of
+            // the deserialized data
+            #[cfg(feature = "serde")]
+            WrapperDerives::Deserialize => {
+                let serde = quote! { #amplify_crate::_export::serde };
+                let mut de_generics = input.generics.clone();
+                de_generics.params.insert(0, parse_quote! { '__de });
+                let (de_impl_generics, ..) = de_generics.split_for_impl();
+                let where_clause = inner_where(quote! { #serde::Deserialize<'__de> });
+                let construct = match validate {
+                    Some(_) => quote! {
+                        .and_then(|inner| {
+                            Self::try_from_inner(inner).map_err(#serde::de::Error::custom)
+                        })
+                    },
+                    None => quote! { .map(Wrapper::from_inner) },
+                };
+                quote_spanned! { span =>
+                    impl #de_impl_generics #serde::Deserialize<'__de> for #ident_name #ty_generics #where_clause
                     {
-                        type Output = <<Self as #amplify_crate::Wrapper>::Inner as ::core::ops::Index<::core::ops::RangeTo<usize>>>::Output;
-
                         #[inline]
-                        fn index(&self, index: ::core::ops::RangeTo<usize>) -> &Self::Output {
+                        fn deserialize<__D>(deserializer: __D) -> Result<Self, __D::Error>
+                        where
+                            __D: #serde::Deserializer<'__de>,
+                        {
                             use #amplify_crate::Wrapper;
-                            Wrapper::as_inner(self).index(index)
+                            <<Self as Wrapper>::Inner as #serde::Deserialize<'__de>>::deserialize(deserializer)
+                                #construct
                         }
                     }
                 }
             }
-            WrapperDerives::IndexInclusive => {
-                quote! {
-                    impl <#impl_generics_params> ::core::ops::Index<::core::ops::RangeInclusive<usize>> for #ident_name #ty_generics #where_clause
+            WrapperDerives::BorrowSlice => quote! {
+                impl #impl_generics ::core::borrow::Borrow<[u8]> for #ident_name #ty_generics #where_clause
+                {
+                    #[inline]
+                    fn borrow(&self) -> &[u8] {
+                        use #amplify_crate::Wrapper;
+                        ::core::borrow::Borrow::<[u8]>::borrow(Wrapper::as_inner(self))
+                    }
+                }
+            },
+            WrapperDerives::RawArray => {
+                let where_clause = inner_where(quote! { #amplify_crate::RawArray });
+                quote_spanned! { span =>
+                    impl #impl_generics #amplify_crate::RawArray for #ident_name #ty_generics #where_clause
                     {
-                        type Output = <<Self as #amplify_crate::Wrapper>::Inner as ::core::ops::Index<::core::ops::RangeInclusive<usize>>>::Output;
+                        type Array = <<Self as #amplify_crate::Wrapper>::Inner as #amplify_crate::RawArray>::Array;
 
                         #[inline]
-                        fn index(&self, index: ::core::ops::RangeInclusive<usize>) -> &Self::Output {
+                        fn from_raw_array(val: Self::Array) -> Self {
                             use #amplify_crate::Wrapper;
-                            Wrapper::as_inner(self).index(index)
+                            Wrapper::from_inner(#amplify_crate::RawArray::from_raw_array(val))
                         }
-                    }
-                }
-            }
-            WrapperDerives::IndexFull => {
-                quote! {
-                    impl <#impl_generics_params> ::core::ops::Index<::core::ops::RangeFull> for #ident_name #ty_generics #where_clause
-                    {
-                        type Output = <<Self as #amplify_crate::Wrapper>::Inner as ::core::ops::Index<::core::ops::RangeFull>>::Output;
 
                         #[inline]
-                        fn index(&self, index: ::core::ops::RangeFull) -> &Self::Output {
+                        fn to_raw_array(&self) -> Self::Array {
                             use #amplify_crate::Wrapper;
-                            Wrapper::as_inner(self).index(index)
+                            #amplify_crate::RawArray::to_raw_array(Wrapper::as_inner(self))
                         }
                     }
                 }
             }
-            WrapperDerives::Neg => quote! {
+            WrapperDerives::AsMut => quote_spanned! { span =>
+                impl #impl_generics ::core::convert::AsMut<<#ident_name #ty_generics as #amplify_crate::Wrapper>::Inner> for #ident_name #ty_generics #where_clause {
+                    #[inline]
+                    fn as_mut(&mut self) -> &mut <Self as #amplify_crate::Wrapper>::Inner {
+                        #amplify_crate::WrapperMut::as_inner_mut(self)
+                    }
+                }
+            },
+            WrapperDerives::BorrowMut => quote_spanned! { span =>
+                impl #impl_generics ::core::borrow::BorrowMut<<#ident_name #ty_generics as #amplify_crate::Wrapper>::Inner> for #ident_name #ty_generics #where_clause {
+                    #[inline]
+                    fn borrow_mut(&mut self) -> &mut <Self as #amplify_crate::Wrapper>::Inner {
+                        #amplify_crate::WrapperMut::as_inner_mut(self)
+                    }
+                }
+            },
+            WrapperDerives::DerefMut => quote_spanned! { span =>
+                impl #impl_generics ::core::ops::DerefMut for #ident_name #ty_generics #where_clause {
+                    #[inline]
+                    fn deref_mut(&mut self) -> &mut Self::Target {
+                        #amplify_crate::WrapperMut::as_inner_mut(self)
+                    }
+                }
+            },
+            WrapperDerives::Index => index(quote! { usize }),
+            WrapperDerives::IndexMut => index_mut(quote! { usize }),
+            WrapperDerives::IndexRange => index(quote! { ::core::ops::Range<usize> }),
+            WrapperDerives::IndexFrom => index(quote! { ::core::ops::RangeFrom<usize> }),
+            WrapperDerives::IndexTo => index(quote! { ::core::ops::RangeTo<usize> }),
+            WrapperDerives::IndexInclusive => index(quote! { ::core::ops::RangeInclusive<usize> }),
+            WrapperDerives::IndexFull => index(quote! { ::core::ops::RangeFull }),
+            WrapperDerives::RangeOps => ranges.iter().cloned().map(index).collect(),
+            WrapperDerives::RangeOpsMut => ranges.iter().cloned().map(index_mut).collect(),
+            WrapperDerives::Neg => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::Neg for #ident_name #ty_generics #where_clause
                 {
                     type Output = Self;
@@ -339,7 +580,7 @@ impl WrapperDerives {
                     }
                 }
             },
-            WrapperDerives::Not => quote! {
+            WrapperDerives::Not => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::Not for #ident_name #ty_generics #where_clause
                 {
                     type Output = Self;
@@ -351,7 +592,7 @@ impl WrapperDerives {
                     }
                 }
             },
-            WrapperDerives::Add => quote! {
+            WrapperDerives::Add => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::Add for #ident_name #ty_generics #where_clause
                 {
                     type Output = Self;
@@ -363,7 +604,7 @@ impl WrapperDerives {
                     }
                 }
             },
-            WrapperDerives::Sub => quote! {
+            WrapperDerives::Sub => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::Sub for #ident_name #ty_generics #where_clause
                 {
                     type Output = Self;
@@ -375,7 +616,7 @@ impl WrapperDerives {
                     }
                 }
             },
-            WrapperDerives::Mul => quote! {
+            WrapperDerives::Mul => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::Mul for #ident_name #ty_generics #where_clause
                 {
                     type Output = Self;
@@ -387,7 +628,7 @@ impl WrapperDerives {
                     }
                 }
             },
-            WrapperDerives::Div => quote! {
+            WrapperDerives::Div => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::Div for #ident_name #ty_generics #where_clause
                 {
                     type Output = Self;
@@ -399,7 +640,7 @@ impl WrapperDerives {
                     }
                 }
             },
-            WrapperDerives::Rem => quote! {
+            WrapperDerives::Rem => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::Rem for #ident_name #ty_generics #where_clause
                 {
                     type Output = Self;
@@ -411,31 +652,41 @@ impl WrapperDerives {
                     }
                 }
             },
-            WrapperDerives::Shl => quote! {
-                impl #impl_generics ::core::ops::Shl for #ident_name #ty_generics #where_clause
-                {
-                    type Output = Self;
+            WrapperDerives::Shl => {
+                let rhs_where = inner_where(
+                    quote! { ::core::ops::Shl<__Rhs, Output = <Self as #amplify_crate::Wrapper>::Inner> },
+                );
+                quote_spanned! { span =>
+                    impl #rhs_impl_generics ::core::ops::Shl<__Rhs> for #ident_name #ty_generics #rhs_where
+                    {
+                        type Output = Self;
 
-                    #[inline]
-                    fn shl(self, rhs: Self) -> Self {
-                        use #amplify_crate::Wrapper;
-                        Wrapper::from_inner(::core::ops::Shl::shl(Wrapper::into_inner(self), rhs.into_inner()))
+                        #[inline]
+                        fn shl(self, rhs: __Rhs) -> Self {
+                            use #amplify_crate::Wrapper;
+                            Wrapper::from_inner(::core::ops::Shl::shl(Wrapper::into_inner(self), rhs))
+                        }
                     }
                 }
-            },
-            WrapperDerives::Shr => quote! {
-                impl #impl_generics ::core::ops::Shr for #ident_name #ty_generics #where_clause
-                {
-                    type Output = Self;
+            }
+            WrapperDerives::Shr => {
+                let rhs_where = inner_where(
+                    quote! { ::core::ops::Shr<__Rhs, Output = <Self as #amplify_crate::Wrapper>::Inner> },
+                );
+                quote_spanned! { span =>
+                    impl #rhs_impl_generics ::core::ops::Shr<__Rhs> for #ident_name #ty_generics #rhs_where
+                    {
+                        type Output = Self;
 
-                    #[inline]
-                    fn shr(self, rhs: Self) -> Self {
-                        use #amplify_crate::Wrapper;
-                        Wrapper::from_inner(::core::ops::Shr::shr(Wrapper::into_inner(self), rhs.into_inner()))
+                        #[inline]
+                        fn shr(self, rhs: __Rhs) -> Self {
+                            use #amplify_crate::Wrapper;
+                            Wrapper::from_inner(::core::ops::Shr::shr(Wrapper::into_inner(self), rhs))
+                        }
                     }
                 }
-            },
-            WrapperDerives::BitAnd => quote! {
+            }
+            WrapperDerives::BitAnd => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::BitAnd for #ident_name #ty_generics #where_clause
                 {
                     type Output = Self;
@@ -447,7 +698,7 @@ impl WrapperDerives {
                     }
                 }
             },
-            WrapperDerives::BitOr => quote! {
+            WrapperDerives::BitOr => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::BitOr for #ident_name #ty_generics #where_clause
                 {
                     type Output = Self;
@@ -459,7 +710,7 @@ impl WrapperDerives {
                     }
                 }
             },
-            WrapperDerives::BitXor => quote! {
+            WrapperDerives::BitXor => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::BitXor for #ident_name #ty_generics #where_clause
                 {
                     type Output = Self;
@@ -471,103 +722,109 @@ impl WrapperDerives {
                     }
                 }
             },
-            WrapperDerives::AddAssign => quote! {
+            WrapperDerives::AddAssign => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::AddAssign for #ident_name #ty_generics #where_clause
                 {
                     #[inline]
                     fn add_assign(&mut self, rhs: Self) {
-                        use #amplify_crate::Wrapper;
-                        ::core::ops::AddAssign::add_assign(Wrapper::as_inner_mut(self), rhs.into_inner())
+                        use #amplify_crate::{Wrapper, WrapperMut};
+                        ::core::ops::AddAssign::add_assign(WrapperMut::as_inner_mut(self), rhs.into_inner())
                     }
                 }
             },
-            WrapperDerives::SubAssign => quote! {
+            WrapperDerives::SubAssign => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::SubAssign for #ident_name #ty_generics #where_clause
                 {
                     #[inline]
                     fn sub_assign(&mut self, rhs: Self) {
-                        use #amplify_crate::Wrapper;
-                        ::core::ops::SubAssign::sub_assign(Wrapper::as_inner_mut(self), rhs.into_inner())
+                        use #amplify_crate::{Wrapper, WrapperMut};
+                        ::core::ops::SubAssign::sub_assign(WrapperMut::as_inner_mut(self), rhs.into_inner())
                     }
                 }
             },
-            WrapperDerives::MulAssign => quote! {
+            WrapperDerives::MulAssign => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::MulAssign for #ident_name #ty_generics #where_clause
                 {
                     #[inline]
                     fn mul_assign(&mut self, rhs: Self) {
-                        use #amplify_crate::Wrapper;
-                        ::core::ops::MulAssign::mul_assign(Wrapper::as_inner_mut(self), rhs.into_inner())
+                        use #amplify_crate::{Wrapper, WrapperMut};
+                        ::core::ops::MulAssign::mul_assign(WrapperMut::as_inner_mut(self), rhs.into_inner())
                     }
                 }
             },
-            WrapperDerives::DivAssign => quote! {
+            WrapperDerives::DivAssign => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::DivAssign for #ident_name #ty_generics #where_clause
                 {
                     #[inline]
                     fn div_assign(&mut self, rhs: Self) {
-                        use #amplify_crate::Wrapper;
-                        ::core::ops::DivAssign::div_assign(Wrapper::as_inner_mut(self), rhs.into_inner())
+                        use #amplify_crate::{Wrapper, WrapperMut};
+                        ::core::ops::DivAssign::div_assign(WrapperMut::as_inner_mut(self), rhs.into_inner())
                     }
                 }
             },
-            WrapperDerives::RemAssign => quote! {4
+            WrapperDerives::RemAssign => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::RemAssign for #ident_name #ty_generics #where_clause
                 {
                     #[inline]
                     fn rem_assign(&mut self, rhs: Self) {
-                        use #amplify_crate::Wrapper;
-                        ::core::ops::RemAssign::rem_assign(Wrapper::as_inner_mut(self), rhs.into_inner())
+                        use #amplify_crate::{Wrapper, WrapperMut};
+                        ::core::ops::RemAssign::rem_assign(WrapperMut::as_inner_mut(self), rhs.into_inner())
                     }
                 }
             },
-            WrapperDerives::ShlAssign => quote! {
-                impl #impl_generics ::core::ops::ShlAssign for #ident_name #ty_generics #where_clause
-                {
-                    #[inline]
-                    fn shl_assign(&mut self, rhs: Self) {
-                        use #amplify_crate::Wrapper;
-                        ::core::ops::ShlAssign::shl_assign(Wrapper::as_inner_mut(self), rhs.into_inner())
+            WrapperDerives::ShlAssign => {
+                let rhs_where = inner_where(quote! { ::core::ops::ShlAssign<__Rhs> });
+                quote_spanned! { span =>
+                    impl #rhs_impl_generics ::core::ops::ShlAssign<__Rhs> for #ident_name #ty_generics #rhs_where
+                    {
+                        #[inline]
+                        fn shl_assign(&mut self, rhs: __Rhs) {
+                            use #amplify_crate::WrapperMut;
+                            ::core::ops::ShlAssign::shl_assign(WrapperMut::as_inner_mut(self), rhs)
+                        }
                     }
                 }
-            },
-            WrapperDerives::ShrAssign => quote! {
-                impl #impl_generics ::core::ops::ShrAssign for #ident_name #ty_generics #where_clause
-                {
-                    #[inline]
-                    fn shr_assign(&mut self, rhs: Self) {
-                        use #amplify_crate::Wrapper;
-                        ::core::ops::ShrAssign::shr_assign(Wrapper::as_inner_mut(self), rhs.into_inner())
+            }
+            WrapperDerives::ShrAssign => {
+                let rhs_where = inner_where(quote! { ::core::ops::ShrAssign<__Rhs> });
+                quote_spanned! { span =>
+                    impl #rhs_impl_generics ::core::ops::ShrAssign<__Rhs> for #ident_name #ty_generics #rhs_where
+                    {
+                        #[inline]
+                        fn shr_assign(&mut self, rhs: __Rhs) {
+                            use #amplify_crate::WrapperMut;
+                            ::core::ops::ShrAssign::shr_assign(WrapperMut::as_inner_mut(self), rhs)
+                        }
                     }
                 }
-            },
-            WrapperDerives::BitAndAssign => quote! {
+            }
+            WrapperDerives::BitAndAssign => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::BitAndAssign for #ident_name #ty_generics #where_clause
                 {
                     #[inline]
                     fn bitand_assign(&mut self, rhs: Self) {
-                        use #amplify_crate::Wrapper;
-                        ::core::ops::BitAndAssign::bitand_assign(Wrapper::as_inner_mut(self), rhs.into_inner())
+                        use #amplify_crate::{Wrapper, WrapperMut};
+                        ::core::ops::BitAndAssign::bitand_assign(WrapperMut::as_inner_mut(self), rhs.into_inner())
                     }
                 }
             },
-            WrapperDerives::BitOrAssign => quote! {
+            WrapperDerives::BitOrAssign => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::BitOrAssign for #ident_name #ty_generics #where_clause
                 {
                     #[inline]
                     fn bitor_assign(&mut self, rhs: Self) {
-                        use #amplify_crate::Wrapper;
-                        ::core::ops::BitOrAssign::bitor_assign(Wrapper::as_inner_mut(self), rhs.into_inner())
+                        use #amplify_crate::{Wrapper, WrapperMut};
+                        ::core::ops::BitOrAssign::bitor_assign(WrapperMut::as_inner_mut(self), rhs.into_inner())
                     }
                 }
             },
-            WrapperDerives::BitXorAssign => quote! {
+            WrapperDerives::BitXorAssign => quote_spanned! { span =>
                 impl #impl_generics ::core::ops::BitXorAssign for #ident_name #ty_generics #where_clause
                 {
                     #[inline]
                     fn bitxor_assign(&mut self, rhs: Self) {
-                        use #amplify_crate::Wrapper;
-                        ::core::ops::BitXorAssign::bitxor_assign(Wrapper::as_inner_mut(self), rhs.into_inner())
+                        use #amplify_crate::{Wrapper, WrapperMut};
+                        ::core::ops::BitXorAssign::bitxor_assign(WrapperMut::as_inner_mut(self), rhs.into_inner())
                     }
                 }
             },
@@ -575,6 +832,31 @@ impl WrapperDerives {
     }
 }
 
+/// Detects `PhantomData` marker fields, which are skipped when the wrapped
+/// field is not explicitly marked with `#[wrap]`
+fn is_phantom(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "PhantomData")
+            .unwrap_or_default(),
+        _ => false,
+    }
+}
+
+/// Returns paths of the traits derived with `#[derive(...)]` attributes. Only
+/// the attributes following the one invoking the current derive macro are
+/// visible to it.
+fn derived_traits(attrs: &[Attribute]) -> Result<Vec<Path>> {
+    let mut traits = vec![];
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("derive")) {
+        traits.extend(attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?);
+    }
+    Ok(traits)
+}
+
 pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
     let ident_name = &input.ident;
@@ -598,6 +880,9 @@ pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
     };
 
     let mut wrappers = vec![];
+    let mut from_str_err = None;
+    let mut validate = None;
+    let mut no_from = None;
     const WRAPPER_DERIVE_ERR: &str = "Wrapper attributes must be in a form of type list";
     for attr in input
         .attrs
@@ -611,10 +896,58 @@ pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
             Meta::List(MetaList { nested, .. }) => {
                 for meta in nested {
                     match meta {
+                        NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("no_from") => {
+                            no_from = Some(path.span())
+                        }
                         NestedMeta::Meta(Meta::Path(path)) => {
-                            wrappers.push(WrapperDerives::from_path(&path)?.ok_or_else(|| {
-                                attr_err!(path, "Unrecognized wrapper parameter")
-                            })?);
+                            let derive = WrapperDerives::from_path(&path)?
+                                .ok_or_else(|| attr_err!(path, "Unrecognized wrapper parameter"))?;
+                            wrappers.push((derive, path.span()));
+                        }
+                        // `FromStr(Error)` uses a custom error type converted from
+                        // the error of the inner type
+                        NestedMeta::Meta(Meta::List(ref list)) if list.path.is_ident("FromStr") => {
+                            match (list.nested.len(), list.nested.first()) {
+                                (1, Some(NestedMeta::Meta(Meta::Path(err)))) => {
+                                    from_str_err = Some(err.clone())
+                                }
+                                _ => {
+                                    return Err(attr_err!(
+                                        list,
+                                        "`FromStr` takes a single error type argument"
+                                    ))
+                                }
+                            }
+                            wrappers.push((WrapperDerives::FromStr, list.path.span()));
+                        }
+                        // `validate(function, Error)` checks the inner value on
+                        // construction of the wrapper
+                        NestedMeta::Meta(Meta::List(ref list))
+                            if list.path.is_ident("validate") =>
+                        {
+                            let mut args = list.nested.iter();
+                            match (args.next(), args.next(), args.next()) {
+                                (
+                                    Some(NestedMeta::Meta(Meta::Path(func))),
+                                    Some(NestedMeta::Meta(Meta::Path(err))),
+                                    None,
+                                ) if validate.is_none() => {
+                                    validate = Some(Validate {
+                                        func: func.clone(),
+                                        err: err.clone(),
+                                    })
+                                }
+                                _ if validate.is_some() => {
+                                    return Err(attr_err!(list, "`validate` is given twice"))
+                                }
+                                _ => {
+                                    return Err(attr_err!(
+                                        list,
+                                        "`validate` takes a validation function path and its \
+                                         error type: `validate(function, Error)`"
+                                    ))
+                                }
+                            }
                         }
                         _ => return Err(attr_err!(meta, WRAPPER_DERIVE_ERR)),
                     }
@@ -624,73 +957,283 @@ pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
         }
     }
 
-    let field;
-    let mut from;
-    match data.fields {
-        Fields::Named(ref fields) => {
-            let mut source = None;
-            from = fields.named[0].ty.clone();
-            for field in &fields.named {
-                for attr in &field.attrs {
-                    if attr.path.is_ident("wrap") {
-                        if source.is_some() {
-                            return Err(Error::new_spanned(
-                                attr,
-                                "Only a single field may be wrapped",
-                            ));
-                        }
-                        source = Some(field.ident.clone().expect("we know it's named"));
-                        from = field.ty.clone();
-                    }
-                }
+    if let (Some(span), None) = (no_from, &validate) {
+        return Err(attr_err!(
+            span,
+            "`no_from` can be used only together with `validate(function, Error)`"
+        ));
+    }
+    if let (Some(_), None) = (&validate, &from_str_err) {
+        if let Some((_, span)) = wrappers.iter().find(|(w, _)| *w == WrapperDerives::FromStr) {
+            return Err(attr_err!(
+                span,
+                "validated wrappers must use `FromStr(Error)` form with an error type \
+                 convertible from both the inner type parsing error and the validation error"
+            ));
+        }
+    }
+
+    let derived = derived_traits(&input.attrs)?;
+    for (derive, span) in &wrappers {
+        let name = match derive.derivable_trait() {
+            Some(name) => name,
+            None => continue,
+        };
+        if let Some(path) = derived.iter().find(|path| {
+            path.segments
+                .last()
+                .map(|segment| segment.ident == name)
+                .unwrap_or_default()
+        }) {
+            let msg = format!(
+                "`#[wrapper({0})]` conflicts with `#[derive({0})]`; use either of them",
+                name
+            );
+            let mut err = attr_err!(span, msg);
+            err.combine(Error::new_spanned(
+                path,
+                format!("`{}` is also derived here", name),
+            ));
+            return Err(err);
+        }
+        if name != "Serialize" && name != "Deserialize" {
+            continue;
+        }
+        if let Some(serde) = input.attrs.iter().find(|attr| attr.path.is_ident("serde")) {
+            let msg = format!(
+                "`#[wrapper({})]` delegates to the inner type and conflicts with \
+                 `#[serde(...)]` attribute; serde attributes must be given to the inner type",
+                name
+            );
+            let mut err = attr_err!(span, msg);
+            err.combine(Error::new_spanned(
+                serde,
+                "`#[serde(...)]` attribute is here",
+            ));
+            return Err(err);
+        }
+    }
+    if let Some((_, span)) = wrappers.iter().find(|(w, _)| *w == WrapperDerives::Display) {
+        if let Some(display) = input
+            .attrs
+            .iter()
+            .find(|attr| attr.path.is_ident("display"))
+        {
+            let mut err = attr_err!(
+                span,
+                "`#[wrapper(Display)]` conflicts with `#[display(...)]` attribute; \
+                 use either `#[derive(Display)]` or `#[wrapper(Display)]`"
+            );
+            err.combine(Error::new_spanned(
+                display,
+                "`#[display(...)]` attribute for `#[derive(Display)]` is here",
+            ));
+            return Err(err);
+        }
+    }
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("wrapper_mut"))
+    {
+        if !attr.tokens.is_empty() {
+            return Err(attr_err!(
+                attr,
+                "wrapper_mut",
+                "attribute takes no arguments",
+                "#[wrapper_mut]"
+            ));
+        }
+        let span = attr.path.span();
+        for derive in &[
+            WrapperDerives::AsMut,
+            WrapperDerives::BorrowMut,
+            WrapperDerives::DerefMut,
+        ] {
+            if !wrappers.iter().any(|(w, _)| w == derive) {
+                wrappers.push((*derive, span));
             }
-            if source.is_none() && fields.named.len() > 1 {
-                return Err(Error::new_spanned(
-                    fields,
-                    "When the structure has multiple fields you must point out \
-                     the one you will wrap by using `#[wrap]` attribute",
+        }
+    }
+
+    let range_ops = wrappers
+        .iter()
+        .find(|(w, _)| *w == WrapperDerives::RangeOps)
+        .map(|(_, span)| *span);
+    if let Some(span) = range_ops {
+        if wrappers.iter().any(|(w, _)| *w == WrapperDerives::IndexMut) {
+            wrappers.push((WrapperDerives::RangeOpsMut, span));
+        }
+    }
+
+    if let Fields::Unit = data.fields {
+        return Err(Error::new_spanned(
+            &input,
+            "Deriving wrapper is meaningless for unit structs",
+        ));
+    }
+
+    let mut wrapped = None;
+    for (index, field) in data.fields.iter().enumerate() {
+        for attr in field.attrs.iter().filter(|attr| attr.path.is_ident("wrap")) {
+            if let Some((_, first)) = wrapped {
+                let mut err = Error::new_spanned(attr, "Only a single field may be wrapped");
+                err.combine(Error::new_spanned(
+                    first,
+                    "first `#[wrap]` attribute is here",
                 ));
+                return Err(err);
             }
-            let source = source
-                .unwrap_or_else(|| fields.named[0].ident.clone().expect("we know it's named"));
-            field = quote! { #source };
+            wrapped = Some((index, attr));
         }
-        Fields::Unnamed(ref fields) => {
-            let mut source = None;
-            from = fields.unnamed[0].ty.clone();
-            for (index, field) in fields.unnamed.iter().enumerate() {
-                for attr in &field.attrs {
-                    if attr.path.is_ident("wrap") {
-                        if source.is_some() {
-                            return Err(Error::new_spanned(
-                                attr,
-                                "Only a single field may be wrapped",
-                            ));
+    }
+    let wrapped = match wrapped {
+        Some((index, _)) => index,
+        None => {
+            // Auto-detect the only field which is not a `PhantomData` marker
+            let mut candidates = data
+                .fields
+                .iter()
+                .enumerate()
+                .filter(|(_, field)| data.fields.len() == 1 || !is_phantom(&field.ty));
+            match (candidates.next(), candidates.next()) {
+                (Some((index, _)), None) => index,
+                _ => {
+                    return Err(Error::new_spanned(
+                        &data.fields,
+                        "When the structure has multiple fields you must point out \
+                         the one you will wrap by using `#[wrap]` attribute",
+                    ))
+                }
+            }
+        }
+    };
+
+    let from = &data.fields.iter().nth(wrapped).expect("index is known").ty;
+    let members = data.fields.iter().enumerate().map(|(index, field)| {
+        field
+            .ident
+            .as_ref()
+            .map(|ident| quote! { #ident })
+            .unwrap_or_else(|| {
+                let index = Index::from(index);
+                quote! { #index }
+            })
+    });
+    let field = members.clone().nth(wrapped).expect("index is known");
+    // Non-wrapped fields are constructed with their `Default`, which errors
+    // at the field type if it is not implemented
+    let inits = data
+        .fields
+        .iter()
+        .zip(members)
+        .enumerate()
+        .map(|(index, (f, member))| {
+            if index == wrapped {
+                quote! { #member: inner }
+            } else {
+                let ty = &f.ty;
+                quote_spanned! { ty.span() => #member: <#ty as ::core::default::Default>::default() }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Validated wrappers are constructed only through `try_from_inner`, while
+    // `from_inner` panics on invalid data
+    let (from_inner, validated) = match validate {
+        Some(Validate { ref func, ref err }) => {
+            let vis = &input.vis;
+            let conversion = if no_from.is_some() {
+                quote! {
+                    impl #impl_generics ::core::convert::TryFrom<#from> for #ident_name #ty_generics #where_clause {
+                        type Error = #err;
+
+                        #[inline]
+                        fn try_from(inner: #from) -> Result<Self, Self::Error> {
+                            Self::try_from_inner(inner)
                         }
-                        let i = Index::from(index);
-                        source = Some(quote! { #i });
-                        from = field.ty.clone();
                     }
                 }
+            } else {
+                quote! {
+                    impl #impl_generics ::core::convert::From<#from> for #ident_name #ty_generics #where_clause {
+                        #[inline]
+                        fn from(inner: #from) -> Self {
+                            use #amplify_crate::Wrapper;
+                            Wrapper::from_inner(inner)
+                        }
+                    }
+                }
+            };
+            let ident_str = ident_name.to_string();
+            (
+                quote! {
+                    match Self::try_from_inner(inner) {
+                        Ok(wrapper) => wrapper,
+                        Err(err) => panic!("invalid `{}` value: {}", #ident_str, err),
+                    }
+                },
+                quote! {
+                    impl #impl_generics #ident_name #ty_generics #where_clause {
+                        /// Constructs the wrapper from the inner data, if they pass the
+                        /// validation
+                        #vis fn try_from_inner(inner: #from) -> Result<Self, #err> {
+                            #func(&inner)?;
+                            Ok(Self { #( #inits ),* })
+                        }
+                    }
+
+                    #conversion
+                },
+            )
+        }
+        None => (quote! { Self { #( #inits ),* } }, TokenStream2::new()),
+    };
+
+    let wrapper_mut = if wrappers.iter().any(|(w, _)| w.is_mut()) {
+        quote! {
+            impl #impl_generics #amplify_crate::WrapperMut for #ident_name #ty_generics #where_clause {
+                #[inline]
+                fn as_inner_mut(&mut self) -> &mut <Self as #amplify_crate::Wrapper>::Inner {
+                    &mut self.#field
+                }
             }
-            if source.is_none() && fields.unnamed.len() > 1 {
-                return Err(Error::new_spanned(
-                    fields,
-                    "When the structure has multiple fields you must point out \
-                     the one you will wrap by using `#[wrap]` attribute",
-                ));
-            }
-            field = source.unwrap_or(quote! { 0 });
         }
-        Fields::Unit => {
-            return Err(Error::new_spanned(
-                &input,
-                "Deriving wrapper is meaningless for unit structs",
-            ))
+    } else {
+        TokenStream2::new()
+    };
+
+    // `#[repr(transparent)]` guarantees that the wrapper has the same layout
+    // as its only non-zero-sized field. Since we can't compute type sizes
+    // here, we require all fields except the wrapped one to be `PhantomData`,
+    // so that the wrapped field is known to be that non-zero-sized field.
+    // Validated wrappers are never transparent, since `from_inner_ref` would
+    // bypass the validation.
+    let is_transparent = validate.is_none()
+        && input.attrs.iter().any(|attr| {
+            attr.path.is_ident("repr")
+                && attr
+                    .parse_args::<Ident>()
+                    .map(|repr| repr == "transparent")
+                    .unwrap_or(false)
+        })
+        && data
+            .fields
+            .iter()
+            .enumerate()
+            .all(|(index, field)| index == wrapped || is_phantom(&field.ty));
+    let wrapper_transparent = if is_transparent {
+        quote! {
+            unsafe impl #impl_generics #amplify_crate::WrapperTransparent for #ident_name #ty_generics #where_clause {}
         }
+    } else {
+        TokenStream2::new()
     };
 
-    let wrapper_derive = wrappers.iter().map(|w| w.into_token_stream2(&input));
+    let wrapper_derive = wrappers.iter().map(|(w, span)| {
+        w.into_token_stream2(&input, *span, from_str_err.as_ref(), validate.as_ref())
+    });
 
     Ok(quote! {
         impl #impl_generics #amplify_crate::Wrapper for #ident_name #ty_generics #where_clause {
@@ -698,7 +1241,7 @@ pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
 
             #[inline]
             fn from_inner(inner: Self::Inner) -> Self {
-                Self::from(inner)
+                #from_inner
             }
 
             #[inline]
@@ -706,11 +1249,6 @@ pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
                 &self.#field
             }
 
-            #[inline]
-            fn as_inner_mut(&mut self) -> &mut Self::Inner {
-                &mut self.#field
-            }
-
             #[inline]
             fn into_inner(self) -> Self::Inner {
                 self.#field
@@ -725,7 +1263,7 @@ pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
             }
         }
 
-        impl #impl_generics ::core::convert::AsRef<<#ident_name #impl_generics as #amplify_crate::Wrapper>::Inner> for #ident_name #ty_generics #where_clause {
+        impl #impl_generics ::core::convert::AsRef<<#ident_name #ty_generics as #amplify_crate::Wrapper>::Inner> for #ident_name #ty_generics #where_clause {
             #[inline]
             fn as_ref(&self) -> &<Self as #amplify_crate::Wrapper>::Inner {
                 use #amplify_crate::Wrapper;
@@ -733,15 +1271,7 @@ pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
             }
         }
 
-        impl #impl_generics ::core::convert::AsMut<<#ident_name #impl_generics as #amplify_crate::Wrapper>::Inner> for #ident_name #ty_generics #where_clause {
-            #[inline]
-            fn as_mut(&mut self) -> &mut <Self as #amplify_crate::Wrapper>::Inner {
-                use #amplify_crate::Wrapper;
-                Wrapper::as_inner_mut(self)
-            }
-        }
-
-        impl #impl_generics ::core::borrow::Borrow<<#ident_name #impl_generics as #amplify_crate::Wrapper>::Inner> for #ident_name #ty_generics #where_clause {
+        impl #impl_generics ::core::borrow::Borrow<<#ident_name #ty_generics as #amplify_crate::Wrapper>::Inner> for #ident_name #ty_generics #where_clause {
             #[inline]
             fn borrow(&self) -> &<Self as #amplify_crate::Wrapper>::Inner {
                 use #amplify_crate::Wrapper;
@@ -749,14 +1279,6 @@ pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
             }
         }
 
-        impl #impl_generics ::core::borrow::BorrowMut<<#ident_name #impl_generics as #amplify_crate::Wrapper>::Inner> for #ident_name #ty_generics #where_clause {
-            #[inline]
-            fn borrow_mut(&mut self) -> &mut <Self as #amplify_crate::Wrapper>::Inner {
-                use #amplify_crate::Wrapper;
-                Wrapper::as_inner_mut(self)
-            }
-        }
-
         impl #impl_generics ::core::ops::Deref for #ident_name #ty_generics #where_clause {
             type Target = <Self as #amplify_crate::Wrapper>::Inner;
             #[inline]
@@ -766,13 +1288,11 @@ pub(crate) fn inner(input: DeriveInput) -> Result<TokenStream2> {
             }
         }
 
-        impl #impl_generics ::core::ops::DerefMut for #ident_name #ty_generics #where_clause {
-            #[inline]
-            fn deref_mut(&mut self) -> &mut Self::Target {
-                use #amplify_crate::Wrapper;
-                Wrapper::as_inner_mut(self)
-            }
-        }
+        #validated
+
+        #wrapper_mut
+
+        #wrapper_transparent
 
         #( #wrapper_derive )*
     })
@@ -0,0 +1,156 @@
+// Rust language amplification derive library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Fields and variants named with raw identifiers (like `r#type`) passed
+//! through all derive macros
+
+#![allow(non_camel_case_types, dead_code)]
+
+#[macro_use]
+extern crate amplify_derive;
+
+use std::convert::TryFrom;
+use std::error::Error as _;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use amplify::{AsAny, DumbDefault, Wrapper};
+
+#[derive(
+    Getters, Setters, Display, DebugHex, EnumDefault, DumbDefault, AsAny, Clone, PartialEq, Eq,
+)]
+#[display("{r#type}/{ref}/{r#match:grouped}")]
+#[getter(all)]
+struct Keywords {
+    #[default(7)]
+    r#type: u8,
+    #[getter(as_copy)]
+    r#ref: u16,
+    #[display(separator = ",")]
+    r#match: u64,
+    #[debug(hex)]
+    bytes: Vec<u8>,
+}
+
+#[derive(Display, Error, Clone, Copy, PartialEq, Eq, Debug)]
+#[display(inner)]
+struct KeywordError {
+    r#fn: u8,
+}
+
+#[derive(Wrapper, From, Clone, Copy, PartialEq, Eq, Debug, Display)]
+#[display("{r#in:#x}")]
+#[wrapper(LowerHex)]
+struct Wrapped {
+    r#in: u32,
+}
+
+#[derive(Display, Debug)]
+#[display(lowercase)]
+enum Variants {
+    Item {
+        r#type: u8,
+        r#as: u8,
+    },
+    #[display("{type}->{as:02}")]
+    Other {
+        r#type: u8,
+        r#as: u8,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct UnknownValue(u8);
+
+impl From<u8> for UnknownValue {
+    fn from(value: u8) -> Self {
+        UnknownValue(value)
+    }
+}
+
+#[derive(TryFrom, FromStr, Display, Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+#[try_from(u8, Error = UnknownValue)]
+#[display(lowercase)]
+enum Raw {
+    r#type = 1,
+    r#match = 2,
+}
+
+#[test]
+fn getters_setters() {
+    let mut value = Keywords {
+        r#type: 1,
+        r#ref: 2,
+        r#match: 1_000,
+        bytes: vec![0xAB],
+    };
+    assert_eq!(value.r#type(), 1);
+    assert_eq!(value.r#ref(), 2);
+    assert_eq!(*value.match_ref(), 1_000);
+    *value.type_mut() = 3;
+    value.set_ref(4u16);
+    let value = value.with_match(1_000_000u64);
+    assert_eq!(
+        (value.r#type, value.r#ref, value.r#match),
+        (3, 4, 1_000_000)
+    );
+}
+
+#[test]
+fn display_debug() {
+    let value = Keywords {
+        r#type: 1,
+        r#ref: 2,
+        r#match: 1_234_567,
+        bytes: vec![0xAB, 0xCD],
+    };
+    assert_eq!(value.to_string(), "1/2/1,234,567");
+    assert_eq!(
+        format!("{:?}", value),
+        "Keywords { type: 1, ref: 2, match: 1234567, bytes: abcd }"
+    );
+
+    assert_eq!(
+        Variants::Item { r#type: 1, r#as: 2 }.to_string(),
+        "item { type: 1, as: 2 }"
+    );
+    assert_eq!(Variants::Other { r#type: 1, r#as: 2 }.to_string(), "1->02");
+    assert_eq!(Wrapped { r#in: 255 }.to_string(), "0xff");
+}
+
+#[test]
+fn defaults() {
+    let value = Keywords::default();
+    assert_eq!((value.r#type, value.r#ref), (7, 0));
+    assert_eq!(Keywords::dumb_default().r#type(), 0);
+    assert!(value.as_any().downcast_ref::<Keywords>().is_some());
+}
+
+#[test]
+fn conversions() {
+    let err = KeywordError { r#fn: 5 };
+    assert_eq!(err.to_string(), "5");
+    assert!(err.source().is_none());
+
+    let wrapped = Wrapped::from(0xAB);
+    assert_eq!(wrapped.into_inner(), 0xAB);
+    assert_eq!(format!("{:x}", wrapped), "ab");
+
+    assert_eq!(Raw::try_from(2), Ok(Raw::r#match));
+    assert_eq!(Raw::try_from(3), Err(UnknownValue(3)));
+    assert_eq!(Raw::from_str("type"), Ok(Raw::r#type));
+    assert_eq!(Raw::r#match.to_string(), "match");
+}
@@ -0,0 +1,35 @@
+// Rust language amplification derive library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+// Stable compilers can't create sub-spans of literals, so the expected errors
+// point to the whole format string; spans of the placeholders are checked by
+// unit tests of the display module
+#[test]
+fn display() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/display/*.rs");
+}
+
+#[test]
+fn error() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/error/*.rs");
+}
+
+#[test]
+fn wrapper() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/wrapper/*.rs");
+}
@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate amplify_derive;
+
+#[derive(Display)]
+#[display("{x}", alt = "{x}:{z}")]
+struct Point {
+    x: u32,
+    y: u32,
+}
+
+fn main() {}
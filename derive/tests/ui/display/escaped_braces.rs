@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate amplify_derive;
+
+#[derive(Display)]
+#[display("{{value}} is {vlaue}")]
+struct Value {
+    value: u32,
+}
+
+fn main() {}
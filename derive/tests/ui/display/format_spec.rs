@@ -0,0 +1,8 @@
+#[macro_use]
+extern crate amplify_derive;
+
+#[derive(Display)]
+#[display("{0:>8z}")]
+struct Id(u32);
+
+fn main() {}
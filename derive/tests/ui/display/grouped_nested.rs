@@ -0,0 +1,14 @@
+#[macro_use]
+extern crate amplify_derive;
+
+struct Header {
+    height: u32,
+}
+
+#[derive(Display)]
+#[display("{header.height:grouped}")]
+struct Block {
+    header: Header,
+}
+
+fn main() {}
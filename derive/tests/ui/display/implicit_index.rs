@@ -0,0 +1,8 @@
+#[macro_use]
+extern crate amplify_derive;
+
+#[derive(Display)]
+#[display("{} and {} or {}")]
+struct Pair(u8, u8);
+
+fn main() {}
@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate amplify_derive;
+
+#[derive(Display)]
+#[display("{0}")]
+struct Named {
+    name: String,
+}
+
+fn main() {}
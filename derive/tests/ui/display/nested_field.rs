@@ -0,0 +1,14 @@
+#[macro_use]
+extern crate amplify_derive;
+
+struct Header {
+    height: u32,
+}
+
+#[derive(Display)]
+#[display("{header.0x}")]
+struct Block {
+    header: Header,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[macro_use]
+extern crate amplify_derive;
+
+#[derive(Display)]
+#[display("{0:.*}")]
+struct Float(f64, usize);
+
+fn main() {}
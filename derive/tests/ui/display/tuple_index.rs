@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate amplify_derive;
+
+#[derive(Display)]
+enum Pair {
+    #[display("{0} and {2}")]
+    Bytes(u8, u8),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate amplify_derive;
+
+#[derive(Display)]
+#[display("{value} of {totl}")]
+struct Progress {
+    value: u32,
+    total: u32,
+}
+
+fn main() {}
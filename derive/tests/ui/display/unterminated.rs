@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate amplify_derive;
+
+#[derive(Display)]
+#[display("value {x")]
+struct Point {
+    x: u32,
+}
+
+fn main() {}
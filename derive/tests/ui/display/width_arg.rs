@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate amplify_derive;
+
+#[derive(Display)]
+#[display("{value:>width$}")]
+struct Padded {
+    value: u32,
+    widht: usize,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#[macro_use]
+extern crate amplify_derive;
+
+#[derive(Debug, Display, Error)]
+#[display(Debug)]
+#[error(code_offset = 1)]
+enum Failure {
+    Unknown,
+    Insufficient(u64),
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+#[macro_use]
+extern crate amplify_derive;
+
+#[derive(Debug, Display, Error)]
+#[display(Debug)]
+#[error(code_offset = 0x0100)]
+enum Failure {
+    Unknown,
+    Timeout,
+    #[error(code = 0x0101)]
+    Expired,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[macro_use]
+extern crate amplify_derive;
+
+#[derive(Wrapper, Clone, Copy, Debug)]
+#[wrapper(Display, no_from)]
+struct Amount(u64);
+
+fn main() {}
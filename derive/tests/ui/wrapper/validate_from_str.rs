@@ -0,0 +1,24 @@
+#[macro_use]
+extern crate amplify_derive;
+
+#[derive(Debug)]
+struct Zero;
+
+impl std::fmt::Display for Zero {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("amount must not be zero")
+    }
+}
+
+fn non_zero(value: &u64) -> Result<(), Zero> {
+    if *value == 0 {
+        return Err(Zero);
+    }
+    Ok(())
+}
+
+#[derive(Wrapper, Clone, Copy, Debug)]
+#[wrapper(validate(non_zero, Zero), FromStr)]
+struct Amount(u64);
+
+fn main() {}
@@ -15,15 +15,72 @@
 // along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
-use crate::error::ParseLengthError;
+use crate::error::{ParseIntError, ParseLengthError, TryFromIntError};
 use crate::divrem::DivRem;
 
+/// Sampler of big integers uniformly distributed within a range, used by
+/// `rand::Rng::gen_range` and `rand::distributions::Uniform`.
+///
+/// Values are sampled with Lemire's widening multiplication method, rejecting
+/// the samples which would bias the distribution. Signed integers are sampled
+/// as unsigned offsets (of type `U`) from the lower bound.
+#[cfg(feature = "rand")]
+#[derive(Copy, Clone, Debug)]
+pub struct UniformBigint<T, U = T> {
+    low: T,
+    /// Size of the range; zero stands for the full range of the type
+    range: U,
+    /// Largest accepted low half of the product of a random value and the
+    /// range size
+    zone: U,
+}
+
+macro_rules! impl_try_into_primitive {
+    ($name:ident; $($ty:ident),+) => {
+        $(
+            impl ::core::convert::TryFrom<$name> for $ty {
+                type Error = TryFromIntError;
+
+                fn try_from(value: $name) -> Result<$ty, Self::Error> {
+                    if !value.fits_u128() {
+                        return Err(TryFromIntError);
+                    }
+                    <$ty as ::core::convert::TryFrom<u128>>::try_from(value.low_u128())
+                        .map_err(|_| TryFromIntError)
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_try_from_signed {
+    ($name:ident; $($ty:ident),+) => {
+        $(
+            impl ::core::convert::TryFrom<$ty> for $name {
+                type Error = TryFromIntError;
+
+                fn try_from(value: $ty) -> Result<$name, Self::Error> {
+                    if value < 0 {
+                        return Err(TryFromIntError);
+                    }
+                    Ok($name::from(value as u128))
+                }
+            }
+        )+
+    };
+}
+
 macro_rules! construct_bigint {
     ($name:ident, $n_words:expr) => {
         /// Large integer type
         ///
         /// The type is composed of little-endian ordered 64-bit words, which represents
         /// its inner representation.
+        ///
+        /// With `serde` feature the type is serialized as a decimal string by
+        /// human-readable serializers; deserialization also accepts
+        /// `0x`-prefixed hexadecimal strings and integers fitting into `u64`.
+        /// Binary serializers use a fixed-size array of little-endian bytes.
         #[allow(non_camel_case_types)]
         #[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
         pub struct $name([u64; $n_words]);
@@ -45,19 +102,19 @@ macro_rules! construct_bigint {
 
             #[inline]
             /// Returns the underlying array of words constituting large integer
-            pub fn as_inner(&self) -> &[u64; $n_words] {
+            pub const fn as_inner(&self) -> &[u64; $n_words] {
                 &self.0
             }
 
             #[inline]
             /// Returns the underlying array of words constituting large integer
-            pub fn into_inner(self) -> [u64; $n_words] {
+            pub const fn into_inner(self) -> [u64; $n_words] {
                 self.0
             }
 
             #[inline]
             /// Constructs integer type from the underlying array of words.
-            pub fn from_inner(array: [u64; $n_words]) -> Self {
+            pub const fn from_inner(array: [u64; $n_words]) -> Self {
                 Self(array)
             }
         }
@@ -88,27 +145,428 @@ macro_rules! construct_bigint {
             /// Length of the inner representation in 64-bit words
             pub const INNER_LEN: u8 = $n_words;
 
-            /// Returns whether specific bit number is set to `1` or not
+            /// Constructs integer from `u64` value
+            #[inline]
+            pub const fn from_u64(value: u64) -> $name {
+                let mut ret = [0u64; $n_words];
+                ret[0] = value;
+                $name(ret)
+            }
+
+            /// Checks equality of two integers in `const` context
+            pub const fn const_eq(self, other: $name) -> bool {
+                let mut i = 0;
+                while i < $n_words {
+                    if self.0[i] != other.0[i] {
+                        return false;
+                    }
+                    i += 1;
+                }
+                true
+            }
+
+            /// Checks whether `self` is less than `other` in `const` context
+            pub const fn const_lt(self, other: $name) -> bool {
+                let mut i = $n_words;
+                while i > 0 {
+                    i -= 1;
+                    if self.0[i] != other.0[i] {
+                        return self.0[i] < other.0[i];
+                    }
+                }
+                false
+            }
+
+            /// Calculates `self + other` in `const` context, returning the
+            /// wrapped value together with a boolean indicating whether an
+            /// arithmetic overflow has occurred
+            pub const fn const_overflowing_add(self, other: $name) -> ($name, bool) {
+                let mut ret = [0u64; $n_words];
+                let mut carry = false;
+                let mut i = 0;
+                while i < $n_words {
+                    let (res, flag1) = self.0[i].overflowing_add(other.0[i]);
+                    let (res, flag2) = res.overflowing_add(carry as u64);
+                    ret[i] = res;
+                    carry = flag1 || flag2;
+                    i += 1;
+                }
+                ($name(ret), carry)
+            }
+
+            /// Calculates `self - other` in `const` context, returning the
+            /// wrapped value together with a boolean indicating whether an
+            /// arithmetic overflow has occurred
+            pub const fn const_overflowing_sub(self, other: $name) -> ($name, bool) {
+                let mut ret = [0u64; $n_words];
+                let mut borrow = false;
+                let mut i = 0;
+                while i < $n_words {
+                    let (res, flag1) = self.0[i].overflowing_sub(other.0[i]);
+                    let (res, flag2) = res.overflowing_sub(borrow as u64);
+                    ret[i] = res;
+                    borrow = flag1 || flag2;
+                    i += 1;
+                }
+                ($name(ret), borrow)
+            }
+
+            /// Wrapping (modular) addition in `const` context
+            #[inline]
+            pub const fn const_wrapping_add(self, other: $name) -> $name {
+                self.const_overflowing_add(other).0
+            }
+
+            /// Wrapping (modular) subtraction in `const` context
+            #[inline]
+            pub const fn const_wrapping_sub(self, other: $name) -> $name {
+                self.const_overflowing_sub(other).0
+            }
+
+            /// Bitwise AND in `const` context
+            pub const fn const_and(self, other: $name) -> $name {
+                let mut ret = [0u64; $n_words];
+                let mut i = 0;
+                while i < $n_words {
+                    ret[i] = self.0[i] & other.0[i];
+                    i += 1;
+                }
+                $name(ret)
+            }
+
+            /// Bitwise OR in `const` context
+            pub const fn const_or(self, other: $name) -> $name {
+                let mut ret = [0u64; $n_words];
+                let mut i = 0;
+                while i < $n_words {
+                    ret[i] = self.0[i] | other.0[i];
+                    i += 1;
+                }
+                $name(ret)
+            }
+
+            /// Bitwise XOR in `const` context
+            pub const fn const_xor(self, other: $name) -> $name {
+                let mut ret = [0u64; $n_words];
+                let mut i = 0;
+                while i < $n_words {
+                    ret[i] = self.0[i] ^ other.0[i];
+                    i += 1;
+                }
+                $name(ret)
+            }
+
+            /// Bitwise negation in `const` context
+            pub const fn const_not(self) -> $name {
+                let mut ret = [0u64; $n_words];
+                let mut i = 0;
+                while i < $n_words {
+                    ret[i] = !self.0[i];
+                    i += 1;
+                }
+                $name(ret)
+            }
+
+            /// Returns whether specific bit number is set to `1` or not.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is not less than `Self::BITS`.
             #[inline]
             pub fn bit(&self, index: usize) -> bool {
                 let &$name(ref arr) = self;
                 arr[index / 64] & (1 << (index % 64)) != 0
             }
 
+            /// Sets specific bit number to `1` (if `value` is `true`) or `0`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is not less than `Self::BITS`.
+            #[inline]
+            pub fn set_bit(&mut self, index: usize, value: bool) {
+                let &mut $name(ref mut arr) = self;
+                if value {
+                    arr[index / 64] |= 1 << (index % 64);
+                } else {
+                    arr[index / 64] &= !(1 << (index % 64));
+                }
+            }
+
+            /// Returns mask with `width` least significant bits set
+            #[inline]
+            fn bit_mask(width: u32) -> $name {
+                !($name::MAX << width as usize)
+            }
+
+            /// Checks that the bit range fits the integer dimension
+            #[inline]
+            fn check_bit_range(range: &::core::ops::Range<u32>) {
+                assert!(
+                    range.start <= range.end && range.end <= Self::BITS,
+                    "bit range {}..{} is invalid for {}-bit integer",
+                    range.start,
+                    range.end,
+                    Self::BITS
+                );
+            }
+
+            /// Returns bit field occupying the `range` of bit positions,
+            /// right-aligned: the bit at `range.start` becomes the least
+            /// significant bit of the result.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the range start exceeds its end, or the range end
+            /// exceeds `Self::BITS`.
+            pub fn bits(&self, range: ::core::ops::Range<u32>) -> $name {
+                Self::check_bit_range(&range);
+                (*self >> range.start as usize) & Self::bit_mask(range.end - range.start)
+            }
+
+            /// Writes `value` into the bit field occupying the `range` of bit
+            /// positions, keeping the rest of the bits intact.
+            ///
+            /// The value must fit the width of the field. This is checked with
+            /// debug assertions; in release builds the bits of the value
+            /// exceeding the field width are discarded, similarly to the
+            /// overflowing arithmetic operations.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the range start exceeds its end, or the range end
+            /// exceeds `Self::BITS`.
+            pub fn set_bits(&mut self, range: ::core::ops::Range<u32>, value: $name) {
+                Self::check_bit_range(&range);
+                let width = range.end - range.start;
+                let mask = Self::bit_mask(width);
+                debug_assert!(
+                    value & mask == value,
+                    "value does not fit {}-bit field at bits {}..{}",
+                    width,
+                    range.start,
+                    range.end
+                );
+                let shift = range.start as usize;
+                *self = (*self & !(mask << shift)) | ((value & mask) << shift);
+            }
+
+            /// Returns bit field of `width` bits starting at bit `offset` as
+            /// `u64` value; see [`Self::bits`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if `width` exceeds 64 or the field does not fit into
+            /// `Self::BITS`.
+            pub fn extract_u64(&self, offset: u32, width: u32) -> u64 {
+                assert!(width <= 64, "bit field of {} bits does not fit u64", width);
+                Self::check_bit_range(&(offset..offset.saturating_add(width)));
+                if width == 0 {
+                    return 0;
+                }
+                let &$name(ref arr) = self;
+                let word = (offset / 64) as usize;
+                let shift = offset % 64;
+                let mut field = arr[word] >> shift;
+                if shift > 0 && word + 1 < $n_words {
+                    field |= arr[word + 1] << (64 - shift);
+                }
+                if width < 64 {
+                    field &= (1 << width) - 1;
+                }
+                field
+            }
+
+            /// Returns the number of leading zeros in the binary
+            /// representation of `self`
+            pub fn leading_zeros(&self) -> u32 {
+                Self::BITS - self.bits_required() as u32
+            }
+
+            /// Returns the number of trailing zeros in the binary
+            /// representation of `self`
+            pub fn trailing_zeros(&self) -> u32 {
+                let &$name(ref arr) = self;
+                match arr.iter().position(|word| *word != 0) {
+                    Some(i) => i as u32 * 64 + arr[i].trailing_zeros(),
+                    None => Self::BITS,
+                }
+            }
+
+            /// Returns the number of ones in the binary representation of
+            /// `self`
+            pub fn count_ones(&self) -> u32 {
+                self.0.iter().map(|word| word.count_ones()).sum()
+            }
+
+            /// Returns the number of zeros in the binary representation of
+            /// `self`
+            pub fn count_zeros(&self) -> u32 {
+                Self::BITS - self.count_ones()
+            }
+
+            /// Shifts the bits to the left by a specified amount, `n`, wrapping
+            /// the truncated bits to the end of the resulting integer.
+            pub fn rotate_left(self, n: u32) -> $name {
+                let n = (n % Self::BITS) as usize;
+                if n == 0 {
+                    return self;
+                }
+                (self << n) | (self >> (Self::BITS as usize - n))
+            }
+
+            /// Shifts the bits to the right by a specified amount, `n`,
+            /// wrapping the truncated bits to the beginning of the resulting
+            /// integer.
+            pub fn rotate_right(self, n: u32) -> $name {
+                let n = (n % Self::BITS) as usize;
+                if n == 0 {
+                    return self;
+                }
+                (self >> n) | (self << (Self::BITS as usize - n))
+            }
+
+            /// Returns `true` if and only if `self == 2^k` for some `k`
+            pub fn is_power_of_two(&self) -> bool {
+                self.count_ones() == 1
+            }
+
+            /// Returns the base 2 logarithm of the number, rounded down.
+            ///
+            /// # Panics
+            ///
+            /// This function will panic if `self` is zero.
+            pub fn ilog2(&self) -> u32 {
+                self.checked_ilog2()
+                    .expect("argument of integer logarithm must be positive")
+            }
+
+            /// Returns the base 2 logarithm of the number, rounded down, or
+            /// `None` if the number is zero
+            pub fn checked_ilog2(&self) -> Option<u32> {
+                match self.bits_required() {
+                    0 => None,
+                    bits => Some(bits as u32 - 1),
+                }
+            }
+
+            /// Returns the smallest power of two greater than or equal to
+            /// `self`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the next power of two is greater than `Self::MAX`.
+            pub fn next_power_of_two(self) -> $name {
+                self.checked_next_power_of_two()
+                    .expect("attempt to compute next power of two with overflow")
+            }
+
+            /// Returns the smallest power of two greater than or equal to
+            /// `self`. If the next power of two is greater than `Self::MAX`,
+            /// `None` is returned, otherwise the power of two is wrapped in
+            /// `Some`.
+            pub fn checked_next_power_of_two(self) -> Option<$name> {
+                if self <= $name::ONE {
+                    return Some($name::ONE);
+                }
+                // Number of bits required to store `self - 1`
+                let bits = (self - 1u8).bits_required();
+                if bits >= Self::BITS as usize {
+                    None
+                } else {
+                    Some($name::ONE << bits)
+                }
+            }
+
             /// Returns lower 32 bits of the number as `u32`
             #[inline]
-            pub fn low_u32(&self) -> u32 {
+            pub const fn low_u32(&self) -> u32 {
                 let &$name(ref arr) = self;
                 (arr[0] & ::core::u32::MAX as u64) as u32
             }
 
-            /// Returns lower 64 bits of the number as `u32`
+            /// Returns lower 64 bits of the number as `u64`
             #[inline]
-            pub fn low_u64(&self) -> u64 {
+            pub const fn low_u64(&self) -> u64 {
                 let &$name(ref arr) = self;
                 arr[0] as u64
             }
 
+            /// Returns lower 128 bits of the number as `u128`
+            #[inline]
+            pub const fn low_u128(&self) -> u128 {
+                let &$name(ref arr) = self;
+                (arr[1] as u128) << 64 | arr[0] as u128
+            }
+
+            /// Returns whether the number fits into `u64` without truncation
+            #[inline]
+            pub fn fits_u64(&self) -> bool {
+                let &$name(ref arr) = self;
+                arr[1..].iter().all(|word| *word == 0)
+            }
+
+            /// Returns whether the number fits into `u128` without truncation
+            #[inline]
+            pub fn fits_u128(&self) -> bool {
+                let &$name(ref arr) = self;
+                arr[2..].iter().all(|word| *word == 0)
+            }
+
+            /// Converts a float into the integer, truncating the fractional
+            /// part (rounding towards zero).
+            ///
+            /// Returns `None` for NaN, infinite and negative inputs and for
+            /// values not fitting into the type. Since `f64` has 53 bits of
+            /// precision, integers above 2^53 produced by the conversion are
+            /// exactly the (rounded) values the float holds, not the ones it
+            /// may have been computed from.
+            pub fn from_f64_lossy(value: f64) -> Option<$name> {
+                if value.is_nan() || value.is_infinite() || value < 0.0 {
+                    return None;
+                }
+                let bits = value.to_bits();
+                let exp = ((bits >> 52) & 0x7FF) as i32;
+                if exp == 0 {
+                    // Zero or subnormal number, which is less than one
+                    return Some($name::ZERO);
+                }
+                let mantissa = bits & 0x000F_FFFF_FFFF_FFFF | 0x0010_0000_0000_0000;
+                // Value is `mantissa * 2^shift`
+                let shift = exp - 1075;
+                if shift <= -53 {
+                    Some($name::ZERO)
+                } else if shift < 0 {
+                    Some($name::from(mantissa >> -shift))
+                } else if shift + 53 > Self::BITS as i32 {
+                    None
+                } else {
+                    Some($name::from(mantissa) << shift as usize)
+                }
+            }
+
+            /// Converts the integer into the nearest float, rounding ties to
+            /// even like `u128 as f64` does.
+            ///
+            /// The conversion loses precision for values above 2^53, which
+            /// can't be represented by `f64` exactly. Values exceeding
+            /// `f64::MAX` (possible only for `u1024`) are converted into
+            /// infinity.
+            pub fn to_f64_lossy(self) -> f64 {
+                if self.fits_u128() {
+                    return self.low_u128() as f64;
+                }
+                // Take 64 most significant bits, setting the least significant
+                // one if any of the discarded bits is set, so that `u64 as f64`
+                // does the correct rounding
+                let shift = self.bits_required() - 64;
+                let mut top = (self >> shift).low_u64();
+                if (self.trailing_zeros() as usize) < shift {
+                    top |= 1;
+                }
+                top as f64 * f64::from_bits((1023 + shift as u64) << 52)
+            }
+
             /// Return the least number of bits needed to represent the number
             #[inline]
             pub fn bits_required(&self) -> usize {
@@ -124,8 +582,14 @@ macro_rules! construct_bigint {
 
             /// Creates the integer value from a byte array using big-endian
             /// encoding
-            pub fn from_be_bytes(bytes: [u8; $n_words * 8]) -> $name {
-                Self::_from_be_slice(&bytes)
+            pub const fn from_be_bytes(bytes: [u8; $n_words * 8]) -> $name {
+                let mut ret = [0u64; $n_words];
+                let mut i = 0;
+                while i < $n_words {
+                    ret[i] = u64::from_be_bytes(Self::_word_bytes(&bytes, $n_words - 1 - i));
+                    i += 1;
+                }
+                $name(ret)
             }
 
             /// Creates the integer value from a byte slice using big-endian
@@ -143,8 +607,29 @@ macro_rules! construct_bigint {
 
             /// Creates the integer value from a byte array using little-endian
             /// encoding
-            pub fn from_le_bytes(bytes: [u8; $n_words * 8]) -> $name {
-                Self::_from_le_slice(&bytes)
+            pub const fn from_le_bytes(bytes: [u8; $n_words * 8]) -> $name {
+                let mut ret = [0u64; $n_words];
+                let mut i = 0;
+                while i < $n_words {
+                    ret[i] = u64::from_le_bytes(Self::_word_bytes(&bytes, i));
+                    i += 1;
+                }
+                $name(ret)
+            }
+
+            /// Returns `word`-th group of eight bytes from the byte array
+            const fn _word_bytes(bytes: &[u8; $n_words * 8], word: usize) -> [u8; 8] {
+                let start = word * 8;
+                [
+                    bytes[start],
+                    bytes[start + 1],
+                    bytes[start + 2],
+                    bytes[start + 3],
+                    bytes[start + 4],
+                    bytes[start + 5],
+                    bytes[start + 6],
+                    bytes[start + 7],
+                ]
             }
 
             /// Creates the integer value from a byte slice using little-endian
@@ -188,62 +673,78 @@ macro_rules! construct_bigint {
             }
 
             /// Convert the integer into a byte array using big-endian encoding
-            pub fn to_be_bytes(self) -> [u8; $n_words * 8] {
+            pub const fn to_be_bytes(self) -> [u8; $n_words * 8] {
                 let mut res = [0; $n_words * 8];
-                for i in 0..$n_words {
-                    let start = i * 8;
-                    res[start..start + 8]
-                        .copy_from_slice(&self.0[$n_words - (i + 1)].to_be_bytes());
+                let mut i = 0;
+                while i < $n_words * 8 {
+                    res[i] = self.0[$n_words - 1 - i / 8].to_be_bytes()[i % 8];
+                    i += 1;
                 }
                 res
             }
 
             /// Convert a integer into a byte array using little-endian encoding
-            pub fn to_le_bytes(self) -> [u8; $n_words * 8] {
+            pub const fn to_le_bytes(self) -> [u8; $n_words * 8] {
                 let mut res = [0; $n_words * 8];
-                for i in 0..$n_words {
-                    let start = i * 8;
-                    res[start..start + 8].copy_from_slice(&self.0[i].to_le_bytes());
+                let mut i = 0;
+                while i < $n_words * 8 {
+                    res[i] = self.0[i / 8].to_le_bytes()[i % 8];
+                    i += 1;
                 }
                 res
             }
+
+            /// Creates the integer value from a byte array using the native
+            /// byte order of the target platform
+            pub fn from_ne_bytes(bytes: [u8; $n_words * 8]) -> $name {
+                if cfg!(target_endian = "big") {
+                    Self::from_be_bytes(bytes)
+                } else {
+                    Self::from_le_bytes(bytes)
+                }
+            }
+
+            /// Convert the integer into a byte array using the native byte
+            /// order of the target platform
+            pub fn to_ne_bytes(self) -> [u8; $n_words * 8] {
+                if cfg!(target_endian = "big") {
+                    self.to_be_bytes()
+                } else {
+                    self.to_le_bytes()
+                }
+            }
         }
 
         impl DivRem for $name {
             // divmod like operation, returns (quotient, remainder)
             #[inline]
             fn div_rem(self, other: Self) -> (Self, Self) {
-                let mut sub_copy = self;
-                let mut shift_copy = other;
-                let mut ret = [0u64; $n_words];
-
-                let my_bits = self.bits_required();
-                let your_bits = other.bits_required();
+                let my_words = self
+                    .0
+                    .iter()
+                    .rposition(|w| *w != 0)
+                    .map(|i| i + 1)
+                    .unwrap_or(0);
+                let your_words = other
+                    .0
+                    .iter()
+                    .rposition(|w| *w != 0)
+                    .map(|i| i + 1)
+                    .unwrap_or(0);
 
                 // Check for division by 0
-                assert!(your_bits != 0);
+                assert!(your_words != 0, "attempt to divide by zero");
 
                 // Early return in case we are dividing by a larger number than us
-                if my_bits < your_bits {
-                    return ($name(ret), sub_copy);
+                if self < other {
+                    return ($name::ZERO, self);
                 }
 
-                // Bitwise long division
-                let mut shift = my_bits - your_bits;
-                shift_copy <<= shift;
-                loop {
-                    if sub_copy >= shift_copy {
-                        ret[shift / 64] |= 1 << (shift % 64);
-                        sub_copy -= shift_copy;
-                    }
-                    shift_copy >>= 1;
-                    if shift == 0 {
-                        break;
-                    }
-                    shift -= 1;
+                if your_words == 1 {
+                    let (quot, rem) = self.div_rem_word(my_words, other.0[0]);
+                    return (quot, $name::from(rem));
                 }
-
-                ($name(ret), sub_copy)
+                self.div_rem_knuth(my_words, other, your_words)
             }
             // same operation as in div_rem, not panicking when
             #[inline]
@@ -282,9 +783,7 @@ macro_rules! construct_bigint {
 
         impl From<u64> for $name {
             fn from(init: u64) -> $name {
-                let mut ret = [0; $n_words];
-                ret[0] = init;
-                $name(ret)
+                $name::from_u64(init)
             }
         }
 
@@ -298,6 +797,9 @@ macro_rules! construct_bigint {
             }
         }
 
+        impl_try_into_primitive!($name; u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+        impl_try_from_signed!($name; i8, i16, i32, i64, i128);
+
         impl<'a> ::core::convert::TryFrom<&'a [u64]> for $name {
             type Error = $crate::error::ParseLengthError;
             fn try_from(data: &'a [u64]) -> Result<$name, Self::Error> {
@@ -421,18 +923,7 @@ macro_rules! construct_bigint {
             where
                 T: Into<$name>,
             {
-                let $name(ref me) = self;
-                let $name(ref you) = other.into();
-                let mut ret = [0u64; $n_words];
-                let mut carry = 0u64;
-                for i in 0..$n_words {
-                    let (res, flag) = me[i].overflowing_add(carry);
-                    carry = flag as u64;
-                    let (res, flag) = res.overflowing_add(you[i]);
-                    carry += flag as u64;
-                    ret[i] = res;
-                }
-                (Self(ret), carry > 0)
+                self.const_overflowing_add(other.into())
             }
 
             /// Wrapping (modular) addition. Computes `self + rhs`, wrapping around at
@@ -466,7 +957,7 @@ macro_rules! construct_bigint {
             {
                 let (res, flag) = self.overflowing_sub(other);
                 if flag {
-                    Self::MAX
+                    Self::MIN
                 } else {
                     res
                 }
@@ -481,11 +972,7 @@ macro_rules! construct_bigint {
             where
                 T: Into<$name>,
             {
-                let other = other.into();
-                (
-                    self.wrapping_add(!other).wrapping_add($name::ONE),
-                    self < other,
-                )
+                self.const_overflowing_sub(other.into())
             }
 
             /// Wrapping (modular) subtraction. Computes `self - rhs`, wrapping around
@@ -573,41 +1060,428 @@ macro_rules! construct_bigint {
             {
                 self.overflowing_mul(other).0
             }
-        }
-
-        impl<T> ::core::ops::Add<T> for $name
-        where
-            T: Into<$name>,
-        {
-            type Output = $name;
 
-            fn add(self, other: T) -> $name {
-                let (res, flag) = self.overflowing_add(other);
-                assert!(!flag, "attempt to add with overflow");
-                res
+            /// Calculates the complete product `self * rhs` without the
+            /// possibility to overflow.
+            ///
+            /// Returns a tuple of the low-order (wrapping) bits and the
+            /// high-order (overflow) bits of the result.
+            pub fn widening_mul<T>(self, other: T) -> ($name, $name)
+            where
+                T: Into<$name>,
+            {
+                let $name(ref me) = self;
+                let $name(ref you) = other.into();
+                let mut ret = [0u64; $n_words * 2];
+                for i in 0..$n_words {
+                    let mut carry = 0u64;
+                    for j in 0..$n_words {
+                        let res = me[i] as u128 * you[j] as u128
+                            + ret[i + j] as u128
+                            + carry as u128;
+                        ret[i + j] = res as u64;
+                        carry = (res >> 64) as u64;
+                    }
+                    ret[i + $n_words] = carry;
+                }
+                let mut low = [0u64; $n_words];
+                let mut high = [0u64; $n_words];
+                low.copy_from_slice(&ret[..$n_words]);
+                high.copy_from_slice(&ret[$n_words..]);
+                ($name(low), $name(high))
             }
-        }
-        impl<T> ::core::ops::AddAssign<T> for $name
-        where
-            T: Into<$name>,
-        {
-            #[inline]
-            fn add_assign(&mut self, rhs: T) {
-                self.0 = (*self + rhs).0
+
+            /// Divides by a single-word divisor, using only `my_words` least
+            /// significant words of `self`
+            fn div_rem_word(self, my_words: usize, divisor: u64) -> ($name, u64) {
+                let mut quot = [0u64; $n_words];
+                let mut rem = 0u128;
+                for i in (0..my_words).rev() {
+                    let cur = rem << 64 | self.0[i] as u128;
+                    quot[i] = (cur / divisor as u128) as u64;
+                    rem = cur % divisor as u128;
+                }
+                ($name(quot), rem as u64)
             }
-        }
 
-        impl<T> ::core::ops::Sub<T> for $name
-        where
-            T: Into<$name>,
-        {
-            type Output = $name;
+            /// Long division of multi-word numbers (Knuth, TAOCP vol. 2,
+            /// 4.3.1, algorithm D). Requires `self >= other` and `other` to
+            /// have at least two significant words.
+            fn div_rem_knuth(
+                self,
+                my_words: usize,
+                other: $name,
+                your_words: usize,
+            ) -> ($name, $name) {
+                let (m, n) = (my_words, your_words);
+
+                // D1: normalize, so the top word of the divisor has its high
+                // bit set
+                let shift = other.0[n - 1].leading_zeros();
+                let mut u = [0u64; $n_words + 1];
+                let mut v = [0u64; $n_words];
+                for i in 0..m {
+                    u[i] = self.0[i] << shift;
+                    if shift > 0 && i > 0 {
+                        u[i] |= self.0[i - 1] >> (64 - shift);
+                    }
+                }
+                if shift > 0 {
+                    u[m] = self.0[m - 1] >> (64 - shift);
+                }
+                for i in 0..n {
+                    v[i] = other.0[i] << shift;
+                    if shift > 0 && i > 0 {
+                        v[i] |= other.0[i - 1] >> (64 - shift);
+                    }
+                }
 
-            #[inline]
-            fn sub(self, other: T) -> $name {
-                let (res, flag) = self.overflowing_sub(other);
-                assert!(!flag, "attempt to subtract with overflow");
-                res
+                let base = 1u128 << 64;
+                let mut quot = [0u64; $n_words];
+                for j in (0..=(m - n)).rev() {
+                    // D3: estimate quotient word, correcting it so it exceeds
+                    // the real value by at most one
+                    let num = (u[j + n] as u128) << 64 | u[j + n - 1] as u128;
+                    let mut qhat = num / v[n - 1] as u128;
+                    let mut rhat = num % v[n - 1] as u128;
+                    while qhat >= base
+                        || qhat * v[n - 2] as u128 > (rhat << 64 | u[j + n - 2] as u128)
+                    {
+                        qhat -= 1;
+                        rhat += v[n - 1] as u128;
+                        if rhat >= base {
+                            break;
+                        }
+                    }
+
+                    // D4: multiply and subtract
+                    let mut carry = 0u128;
+                    let mut borrow = false;
+                    for i in 0..n {
+                        let prod = qhat * v[i] as u128 + carry;
+                        carry = prod >> 64;
+                        let (res, b1) = u[i + j].overflowing_sub(prod as u64);
+                        let (res, b2) = res.overflowing_sub(borrow as u64);
+                        u[i + j] = res;
+                        borrow = b1 || b2;
+                    }
+                    let (res, b1) = u[j + n].overflowing_sub(carry as u64);
+                    let (res, b2) = res.overflowing_sub(borrow as u64);
+                    u[j + n] = res;
+
+                    // D5-D6: add back if the estimate was one too large
+                    quot[j] = qhat as u64;
+                    if b1 || b2 {
+                        quot[j] -= 1;
+                        let mut carry = false;
+                        for i in 0..n {
+                            let (res, c1) = u[i + j].overflowing_add(v[i]);
+                            let (res, c2) = res.overflowing_add(carry as u64);
+                            u[i + j] = res;
+                            carry = c1 || c2;
+                        }
+                        u[j + n] = u[j + n].wrapping_add(carry as u64);
+                    }
+                }
+
+                // D8: unnormalize the remainder
+                let mut rem = [0u64; $n_words];
+                for i in 0..n {
+                    rem[i] = u[i] >> shift;
+                    if shift > 0 {
+                        rem[i] |= u[i + 1] << (64 - shift);
+                    }
+                }
+                ($name(quot), $name(rem))
+            }
+
+            /// Checked integer division. Computes `self / rhs`, returning `None`
+            /// if `rhs == 0`.
+            pub fn checked_div<T>(self, other: T) -> Option<$name>
+            where
+                T: Into<$name>,
+            {
+                self.div_rem_checked(other.into()).map(|(q, _)| q)
+            }
+
+            /// Calculates `self / rhs`
+            ///
+            /// Returns a tuple of the divisor along with a boolean indicating
+            /// whether an arithmetic overflow would occur. Since unsigned
+            /// division never overflows, the boolean is always `false`.
+            ///
+            /// # Panics
+            ///
+            /// This function will panic if `rhs` is 0.
+            pub fn overflowing_div<T>(self, other: T) -> ($name, bool)
+            where
+                T: Into<$name>,
+            {
+                (self / other, false)
+            }
+
+            /// Wrapping division. Computes `self / rhs`. Wrapped division on
+            /// unsigned types is just normal division; this function exists so
+            /// that all operations are accounted for in the wrapping operations.
+            ///
+            /// # Panics
+            ///
+            /// This function will panic if `rhs` is 0.
+            pub fn wrapping_div<T>(self, other: T) -> $name
+            where
+                T: Into<$name>,
+            {
+                self / other
+            }
+
+            /// Checked integer remainder. Computes `self % rhs`, returning
+            /// `None` if `rhs == 0`.
+            pub fn checked_rem<T>(self, other: T) -> Option<$name>
+            where
+                T: Into<$name>,
+            {
+                self.div_rem_checked(other.into()).map(|(_, r)| r)
+            }
+
+            /// Calculates `self % rhs`
+            ///
+            /// Returns a tuple of the remainder along with a boolean indicating
+            /// whether an arithmetic overflow would occur. Since unsigned
+            /// division never overflows, the boolean is always `false`.
+            ///
+            /// # Panics
+            ///
+            /// This function will panic if `rhs` is 0.
+            pub fn overflowing_rem<T>(self, other: T) -> ($name, bool)
+            where
+                T: Into<$name>,
+            {
+                (self % other, false)
+            }
+
+            /// Wrapping (modular) remainder. Computes `self % rhs`. Wrapped
+            /// remainder calculation on unsigned types is just the regular
+            /// remainder calculation; this function exists so that all
+            /// operations are accounted for in the wrapping operations.
+            ///
+            /// # Panics
+            ///
+            /// This function will panic if `rhs` is 0.
+            pub fn wrapping_rem<T>(self, other: T) -> $name
+            where
+                T: Into<$name>,
+            {
+                self % other
+            }
+
+            /// Checked shift left. Computes `self << rhs`, returning `None` if
+            /// `rhs` is larger than or equal to the number of bits in `self`.
+            pub fn checked_shl(self, rhs: u32) -> Option<$name> {
+                match self.overflowing_shl(rhs) {
+                    (_, true) => None,
+                    (res, false) => Some(res),
+                }
+            }
+
+            /// Shifts `self` left by `rhs` bits.
+            ///
+            /// Returns a tuple of the shifted version of `self` along with a
+            /// boolean indicating whether the shift value was larger than or
+            /// equal to the number of bits. If the shift value is too large,
+            /// then value is masked (`rhs % Self::BITS`) and this value is then
+            /// used to perform the shift.
+            pub fn overflowing_shl(self, rhs: u32) -> ($name, bool) {
+                (self << (rhs % Self::BITS) as usize, rhs >= Self::BITS)
+            }
+
+            /// Panic-free bitwise shift-left; yields `self << (rhs % Self::BITS)`,
+            /// where the shift amount is masked to the number of bits in the
+            /// type.
+            ///
+            /// Note that this is *not* the same as a rotate-left; the RHS of a
+            /// wrapping shift-left is restricted to the range of the type,
+            /// rather than the bits shifted out of the LHS being returned to
+            /// the other end. Shifting by the `Shl` operator instead yields
+            /// zero for shift amounts exceeding the number of bits.
+            pub fn wrapping_shl(self, rhs: u32) -> $name {
+                self.overflowing_shl(rhs).0
+            }
+
+            /// Checked shift right. Computes `self >> rhs`, returning `None` if
+            /// `rhs` is larger than or equal to the number of bits in `self`.
+            pub fn checked_shr(self, rhs: u32) -> Option<$name> {
+                match self.overflowing_shr(rhs) {
+                    (_, true) => None,
+                    (res, false) => Some(res),
+                }
+            }
+
+            /// Shifts `self` right by `rhs` bits.
+            ///
+            /// Returns a tuple of the shifted version of `self` along with a
+            /// boolean indicating whether the shift value was larger than or
+            /// equal to the number of bits. If the shift value is too large,
+            /// then value is masked (`rhs % Self::BITS`) and this value is then
+            /// used to perform the shift.
+            pub fn overflowing_shr(self, rhs: u32) -> ($name, bool) {
+                (self >> (rhs % Self::BITS) as usize, rhs >= Self::BITS)
+            }
+
+            /// Panic-free bitwise shift-right; yields `self >> (rhs % Self::BITS)`,
+            /// where the shift amount is masked to the number of bits in the
+            /// type.
+            ///
+            /// Note that this is *not* the same as a rotate-right; the RHS of a
+            /// wrapping shift-right is restricted to the range of the type,
+            /// rather than the bits shifted out of the LHS being returned to
+            /// the other end. Shifting by the `Shr` operator instead yields
+            /// zero for shift amounts exceeding the number of bits.
+            pub fn wrapping_shr(self, rhs: u32) -> $name {
+                self.overflowing_shr(rhs).0
+            }
+
+            /// Raises self to the power of `exp`, using exponentiation by
+            /// squaring.
+            ///
+            /// # Panics
+            ///
+            /// This function will panic if the result overflows.
+            pub fn pow(self, exp: u32) -> $name {
+                let (res, flag) = self.overflowing_pow(exp);
+                assert!(!flag, "attempt to multiply with overflow");
+                res
+            }
+
+            /// Checked exponentiation. Computes `self.pow(exp)`, returning
+            /// `None` if overflow occurred.
+            pub fn checked_pow(self, exp: u32) -> Option<$name> {
+                match self.overflowing_pow(exp) {
+                    (_, true) => None,
+                    (res, false) => Some(res),
+                }
+            }
+
+            /// Raises self to the power of `exp`, using exponentiation by
+            /// squaring.
+            ///
+            /// Returns a tuple of the exponentiation along with a bool
+            /// indicating whether an overflow happened.
+            pub fn overflowing_pow(self, mut exp: u32) -> ($name, bool) {
+                if exp == 0 {
+                    return ($name::ONE, false);
+                }
+                let mut base = self;
+                let mut acc = $name::ONE;
+                let mut overflow = false;
+                while exp > 1 {
+                    if exp & 1 == 1 {
+                        let (res, flag) = acc.overflowing_mul(base);
+                        acc = res;
+                        overflow |= flag;
+                    }
+                    exp /= 2;
+                    let (res, flag) = base.overflowing_mul(base);
+                    base = res;
+                    overflow |= flag;
+                }
+                let (res, flag) = acc.overflowing_mul(base);
+                (res, overflow | flag)
+            }
+
+            /// Wrapping (modular) exponentiation. Computes `self.pow(exp)`,
+            /// wrapping around at the boundary of the type.
+            pub fn wrapping_pow(self, exp: u32) -> $name {
+                self.overflowing_pow(exp).0
+            }
+
+            /// Returns the square root of the number, rounded down, computed
+            /// with Newton's method
+            pub fn isqrt(self) -> $name {
+                if self < $name::from(2u8) {
+                    return self;
+                }
+                // Initial estimate is a power of two greater than the root
+                let mut x = $name::ONE << (self.bits_required() / 2 + 1);
+                loop {
+                    let y = (x + self / x) >> 1;
+                    if y >= x {
+                        return x;
+                    }
+                    x = y;
+                }
+            }
+
+            /// Calculates the smallest value greater than or equal to `self`
+            /// that is a multiple of `rhs`. Returns `None` if `rhs` is zero or
+            /// the operation would result in overflow.
+            pub fn checked_next_multiple_of<T>(self, other: T) -> Option<$name>
+            where
+                T: Into<$name>,
+            {
+                let other = other.into();
+                match self.checked_rem(other)? {
+                    rem if rem == $name::ZERO => Some(self),
+                    rem => self.checked_add(other - rem),
+                }
+            }
+
+            /// Computes the absolute difference between `self` and `other`
+            pub fn abs_diff<T>(self, other: T) -> $name
+            where
+                T: Into<$name>,
+            {
+                let other = other.into();
+                if self > other {
+                    self - other
+                } else {
+                    other - self
+                }
+            }
+
+            /// Calculates the middle point of `self` and `rhs`, rounded down,
+            /// without an intermediate overflow
+            pub fn midpoint<T>(self, other: T) -> $name
+            where
+                T: Into<$name>,
+            {
+                let other = other.into();
+                (self & other) + ((self ^ other) >> 1)
+            }
+        }
+
+        impl<T> ::core::ops::Add<T> for $name
+        where
+            T: Into<$name>,
+        {
+            type Output = $name;
+
+            fn add(self, other: T) -> $name {
+                let (res, flag) = self.overflowing_add(other);
+                assert!(!flag, "attempt to add with overflow");
+                res
+            }
+        }
+        impl<T> ::core::ops::AddAssign<T> for $name
+        where
+            T: Into<$name>,
+        {
+            #[inline]
+            fn add_assign(&mut self, rhs: T) {
+                self.0 = (*self + rhs).0
+            }
+        }
+
+        impl<T> ::core::ops::Sub<T> for $name
+        where
+            T: Into<$name>,
+        {
+            type Output = $name;
+
+            #[inline]
+            fn sub(self, other: T) -> $name {
+                let (res, flag) = self.overflowing_sub(other);
+                assert!(!flag, "attempt to subtract with overflow");
+                res
             }
         }
         impl<T> ::core::ops::SubAssign<T> for $name
@@ -669,7 +1543,12 @@ macro_rules! construct_bigint {
             type Output = $name;
 
             fn rem(self, other: T) -> $name {
-                self.div_rem(other.into()).1
+                let other = other.into();
+                assert!(
+                    other != $name::ZERO,
+                    "attempt to calculate the remainder with a divisor of zero"
+                );
+                self.div_rem(other).1
             }
         }
         impl<T> ::core::ops::RemAssign<T> for $name
@@ -690,13 +1569,7 @@ macro_rules! construct_bigint {
 
             #[inline]
             fn bitand(self, other: T) -> $name {
-                let $name(ref arr1) = self;
-                let $name(ref arr2) = other.into();
-                let mut ret = [0u64; $n_words];
-                for i in 0..$n_words {
-                    ret[i] = arr1[i] & arr2[i];
-                }
-                $name(ret)
+                self.const_and(other.into())
             }
         }
         impl<T> ::core::ops::BitAndAssign<T> for $name
@@ -717,13 +1590,7 @@ macro_rules! construct_bigint {
 
             #[inline]
             fn bitxor(self, other: T) -> $name {
-                let $name(ref arr1) = self;
-                let $name(ref arr2) = other.into();
-                let mut ret = [0u64; $n_words];
-                for i in 0..$n_words {
-                    ret[i] = arr1[i] ^ arr2[i];
-                }
-                $name(ret)
+                self.const_xor(other.into())
             }
         }
         impl<T> ::core::ops::BitXorAssign<T> for $name
@@ -744,13 +1611,7 @@ macro_rules! construct_bigint {
 
             #[inline]
             fn bitor(self, other: T) -> $name {
-                let $name(ref arr1) = self;
-                let $name(ref arr2) = other.into();
-                let mut ret = [0u64; $n_words];
-                for i in 0..$n_words {
-                    ret[i] = arr1[i] | arr2[i];
-                }
-                $name(ret)
+                self.const_or(other.into())
             }
         }
         impl<T> ::core::ops::BitOrAssign<T> for $name
@@ -763,6 +1624,9 @@ macro_rules! construct_bigint {
             }
         }
 
+        /// Logical shift left; shifting by `Self::BITS` or more bits yields
+        /// zero. See `wrapping_shl` for the primitive-like masking of the
+        /// shift amount.
         impl ::core::ops::Shl<usize> for $name {
             type Output = $name;
 
@@ -791,6 +1655,9 @@ macro_rules! construct_bigint {
             }
         }
 
+        /// Logical shift right; shifting by `Self::BITS` or more bits yields
+        /// zero. See `wrapping_shr` for the primitive-like masking of the
+        /// shift amount.
         impl ::core::ops::Shr<usize> for $name {
             type Output = $name;
 
@@ -822,12 +1689,7 @@ macro_rules! construct_bigint {
 
             #[inline]
             fn not(self) -> $name {
-                let $name(ref arr) = self;
-                let mut ret = [0u64; $n_words];
-                for i in 0..$n_words {
-                    ret[i] = !arr[i];
-                }
-                $name(ret)
+                self.const_not()
             }
         }
 
@@ -842,9 +1704,84 @@ macro_rules! construct_bigint {
             }
         }
 
+        /// Formats the value as a decimal number, honoring formatter flags in
+        /// the same way as primitive integers do
         impl ::core::fmt::Display for $name {
+            #[inline]
             fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-                ::core::fmt::Debug::fmt(self, f)
+                self.fmt_decimal(true, f)
+            }
+        }
+
+        impl $name {
+            /// Writes decimal digits of the value, padded according to the
+            /// formatter flags; used by both unsigned and signed integers
+            fn fmt_decimal(
+                &self,
+                is_nonnegative: bool,
+                f: &mut ::core::fmt::Formatter,
+            ) -> ::core::fmt::Result {
+                // Largest power of 10 fitting into a single word
+                const CHUNK: u64 = 10_000_000_000_000_000_000;
+
+                // Each of the words takes no more than 20 decimal digits
+                let mut buf = [0u8; $n_words * 20];
+                let mut pos = buf.len();
+                let mut val = *self;
+                loop {
+                    let words = val
+                        .0
+                        .iter()
+                        .rposition(|w| *w != 0)
+                        .map(|i| i + 1)
+                        .unwrap_or(0);
+                    let (quot, mut rem) = val.div_rem_word(words, CHUNK);
+                    val = quot;
+                    let last = val == $name::ZERO;
+                    for _ in 0..19 {
+                        pos -= 1;
+                        buf[pos] = b'0' + (rem % 10) as u8;
+                        rem /= 10;
+                        if last && rem == 0 {
+                            break;
+                        }
+                    }
+                    if last {
+                        break;
+                    }
+                }
+                let digits =
+                    ::core::str::from_utf8(&buf[pos..]).expect("decimal digits are always ASCII");
+                f.pad_integral(is_nonnegative, "", digits)
+            }
+        }
+
+        /// Parses decimal number or hexadecimal number prefixed with `0x` or
+        /// `0X`. Signs, whitespace and digit separators (underscores) are not
+        /// allowed.
+        impl ::core::str::FromStr for $name {
+            type Err = ParseIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let (digits, radix, offset) = if s.starts_with("0x") || s.starts_with("0X") {
+                    (&s[2..], 16, 2)
+                } else {
+                    (s, 10, 0)
+                };
+                if digits.is_empty() {
+                    return Err(ParseIntError::Empty);
+                }
+                let mut val = $name::ZERO;
+                for (pos, ch) in digits.char_indices() {
+                    let digit = ch
+                        .to_digit(radix)
+                        .ok_or(ParseIntError::InvalidDigit(ch, pos + offset))?;
+                    val = val
+                        .checked_mul(radix)
+                        .and_then(|val| val.checked_add(digit))
+                        .ok_or(ParseIntError::Overflow)?;
+                }
+                Ok(val)
             }
         }
 
@@ -924,15 +1861,18 @@ macro_rules! construct_bigint {
         impl ::core::fmt::Octal for $name {
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> Result<(), ::core::fmt::Error> {
                 use alloc::string::String;
-                use alloc::format;
 
+                // Word boundaries do not match octal digit boundaries, so the
+                // digits are taken from each three bits of the whole number
                 let mut octal = String::new();
-                for chunk in self.0.iter().rev().skip_while(|x| **x == 0) {
-                    if octal.is_empty() {
-                        octal.push_str(&format!("{:o}", chunk));
-                    } else {
-                        octal.push_str(&format!("{:0>22o}", chunk));
-                    }
+                let bits = self.bits_required();
+                let digits = bits / 3 + (bits % 3 != 0) as usize;
+                for no in (0..digits).rev() {
+                    let digit = (0..3)
+                        .map(|i| 3 * no + i)
+                        .filter(|index| *index < Self::BITS as usize && self.bit(*index))
+                        .fold(0u8, |digit, index| digit | 1 << (index % 3));
+                    octal.push((b'0' + digit) as char);
                 }
                 if octal.is_empty() {
                     octal.push_str("0");
@@ -1000,12 +1940,16 @@ macro_rules! construct_bigint {
             where
                 S: $crate::serde::Serializer,
             {
-                use $crate::hex::ToHex;
-                let bytes = self.to_be_bytes();
+                use $crate::serde::ser::SerializeTuple;
                 if serializer.is_human_readable() {
-                    serializer.serialize_str(&bytes.to_hex())
+                    serializer.collect_str(self)
                 } else {
-                    serializer.serialize_bytes(&bytes)
+                    let bytes = self.to_le_bytes();
+                    let mut tuple = serializer.serialize_tuple(bytes.len())?;
+                    for byte in bytes.iter() {
+                        tuple.serialize_element(byte)?;
+                    }
+                    tuple.end()
                 }
             }
         }
@@ -1016,585 +1960,3029 @@ macro_rules! construct_bigint {
                 deserializer: D,
             ) -> Result<Self, D::Error> {
                 use ::std::fmt;
-                use $crate::hex::FromHex;
+                use ::core::str::FromStr;
                 use $crate::serde::de;
-                struct Visitor;
+                struct Visitor {
+                    human_readable: bool,
+                }
                 impl<'de> de::Visitor<'de> for Visitor {
                     type Value = $name;
 
                     fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                        write!(
-                            f,
-                            "{} bytes or a hex string with {} characters",
-                            $n_words * 8,
-                            $n_words * 8 * 2
-                        )
+                        if self.human_readable {
+                            write!(
+                                f,
+                                "{} as a decimal or 0x-prefixed hexadecimal string, or an \
+                                 unsigned integer",
+                                stringify!($name)
+                            )
+                        } else {
+                            write!(
+                                f,
+                                "{} as {} little-endian bytes",
+                                stringify!($name),
+                                $n_words * 8
+                            )
+                        }
                     }
 
                     fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
                     where
                         E: de::Error,
                     {
-                        let bytes = Vec::from_hex(s)
-                            .map_err(|_| de::Error::invalid_value(de::Unexpected::Str(s), &self))?;
-                        $name::from_be_slice(&bytes)
-                            .map_err(|_| de::Error::invalid_length(bytes.len() * 2, &self))
+                        $name::from_str(s).map_err(|err| {
+                            E::custom(format_args!(
+                                "invalid {} string \"{}\": {}",
+                                stringify!($name),
+                                s,
+                                err
+                            ))
+                        })
+                    }
+
+                    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+                    where
+                        E: de::Error,
+                    {
+                        Ok($name::from(value))
                     }
 
                     fn visit_bytes<E>(self, bytes: &[u8]) -> Result<Self::Value, E>
                     where
                         E: de::Error,
                     {
-                        $name::from_be_slice(bytes)
-                            .map_err(|_| de::Error::invalid_length(bytes.len(), &self))
+                        $name::from_le_slice(bytes)
+                            .map_err(|_| E::invalid_length(bytes.len(), &self))
+                    }
+
+                    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: de::SeqAccess<'de>,
+                    {
+                        let mut bytes = [0u8; $n_words * 8];
+                        for (index, byte) in bytes.iter_mut().enumerate() {
+                            *byte = seq
+                                .next_element()?
+                                .ok_or_else(|| de::Error::invalid_length(index, &self))?;
+                        }
+                        Ok($name::from_le_bytes(bytes))
                     }
                 }
 
                 if deserializer.is_human_readable() {
-                    deserializer.deserialize_str(Visitor)
+                    deserializer.deserialize_any(Visitor {
+                        human_readable: true,
+                    })
                 } else {
-                    deserializer.deserialize_bytes(Visitor)
+                    deserializer.deserialize_tuple(
+                        $n_words * 8,
+                        Visitor {
+                            human_readable: false,
+                        },
+                    )
                 }
             }
         }
+
+        #[cfg(feature = "rand")]
+        impl ::rand::distributions::Distribution<$name> for ::rand::distributions::Standard {
+            fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> $name {
+                let mut words = [0u64; $n_words];
+                for word in words.iter_mut() {
+                    *word = rng.gen();
+                }
+                $name(words)
+            }
+        }
+
+        #[cfg(feature = "rand")]
+        impl ::rand::distributions::uniform::SampleUniform for $name {
+            type Sampler = UniformBigint<$name>;
+        }
+
+        #[cfg(feature = "rand")]
+        impl UniformBigint<$name> {
+            /// Computes the largest accepted low half of the product of a
+            /// random value and the range size
+            fn zone(range: $name) -> $name {
+                if range == $name::ZERO {
+                    return $name::MAX;
+                }
+                // Number of values to reject is `2^BITS % range`
+                $name::MAX - ($name::MAX - range + 1u8) % range
+            }
+
+            /// Samples a value below `range`, or any value if `range` is zero
+            fn sample_below<R: ::rand::Rng + ?Sized>(
+                range: $name,
+                zone: $name,
+                rng: &mut R,
+            ) -> $name {
+                if range == $name::ZERO {
+                    return rng.gen();
+                }
+                loop {
+                    let (low, high) = rng.gen::<$name>().widening_mul(range);
+                    if low <= zone {
+                        return high;
+                    }
+                }
+            }
+        }
+
+        #[cfg(feature = "rand")]
+        impl ::rand::distributions::uniform::UniformSampler for UniformBigint<$name> {
+            type X = $name;
+
+            fn new<B1, B2>(low: B1, high: B2) -> Self
+            where
+                B1: ::rand::distributions::uniform::SampleBorrow<$name> + Sized,
+                B2: ::rand::distributions::uniform::SampleBorrow<$name> + Sized,
+            {
+                let (low, high) = (*low.borrow(), *high.borrow());
+                assert!(low < high, "Uniform::new called with `low >= high`");
+                Self::new_inclusive(low, high - 1u8)
+            }
+
+            fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+            where
+                B1: ::rand::distributions::uniform::SampleBorrow<$name> + Sized,
+                B2: ::rand::distributions::uniform::SampleBorrow<$name> + Sized,
+            {
+                let (low, high) = (*low.borrow(), *high.borrow());
+                assert!(low <= high, "Uniform::new_inclusive called with `low > high`");
+                let range = (high - low).wrapping_add(1u8);
+                UniformBigint {
+                    low,
+                    range,
+                    zone: Self::zone(range),
+                }
+            }
+
+            fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> $name {
+                self.low + Self::sample_below(self.range, self.zone, rng)
+            }
+        }
     };
 }
 
 construct_bigint!(u256, 4);
 construct_bigint!(u512, 8);
 construct_bigint!(u1024, 16);
-#[cfg(test)]
-mod tests {
-    #![allow(unused)]
 
-    use super::*;
-
-    construct_bigint!(Uint128, 2);
+macro_rules! impl_bigint_conversions {
+    ($small:ident, $big:ident) => {
+        impl From<$small> for $big {
+            fn from(value: $small) -> $big {
+                let mut big = $big::ZERO;
+                big.0[..value.0.len()].copy_from_slice(&value.0);
+                big
+            }
+        }
 
-    #[test]
-    fn u256_bits_test() {
-        assert_eq!(u256::from(255u64).bits_required(), 8);
-        assert_eq!(u256::from(256u64).bits_required(), 9);
-        assert_eq!(u256::from(300u64).bits_required(), 9);
-        assert_eq!(u256::from(60000u64).bits_required(), 16);
-        assert_eq!(u256::from(70000u64).bits_required(), 17);
+        impl ::core::convert::TryFrom<$big> for $small {
+            type Error = TryFromIntError;
 
-        // Try to read the following lines out loud quickly
-        let mut shl = u256::from(70000u64);
-        shl = shl << 100;
-        assert_eq!(shl.bits_required(), 117);
-        shl = shl << 100;
-        assert_eq!(shl.bits_required(), 217);
-        shl = shl << 100;
-        assert_eq!(shl.bits_required(), 0);
+            fn try_from(value: $big) -> Result<$small, Self::Error> {
+                let mut small = $small::ZERO;
+                let (low, high) = value.0.split_at(small.0.len());
+                if high.iter().any(|word| *word != 0) {
+                    return Err(TryFromIntError);
+                }
+                small.0.copy_from_slice(low);
+                Ok(small)
+            }
+        }
+    };
+}
 
-        // Bit set check
-        assert!(!u256::from(10u64).bit(0));
-        assert!(u256::from(10u64).bit(1));
-        assert!(!u256::from(10u64).bit(2));
-        assert!(u256::from(10u64).bit(3));
-        assert!(!u256::from(10u64).bit(4));
-    }
+impl_bigint_conversions!(u256, u512);
+impl_bigint_conversions!(u256, u1024);
+impl_bigint_conversions!(u512, u1024);
+
+macro_rules! impl_bigint_halves {
+    ($half:ident, $name:ident) => {
+        impl $name {
+            /// Composes integer from its high and low halves
+            pub fn from_halves(hi: $half, lo: $half) -> $name {
+                let mut words = [0u64; $name::BITS as usize / 64];
+                let (low, high) = words.split_at_mut(lo.0.len());
+                low.copy_from_slice(&lo.0);
+                high.copy_from_slice(&hi.0);
+                $name(words)
+            }
+
+            /// Splits integer into its high and low halves, returned in this
+            /// order
+            pub fn split(self) -> ($half, $half) {
+                let mut hi = $half::ZERO;
+                let mut lo = $half::ZERO;
+                let (low, high) = self.0.split_at(lo.0.len());
+                lo.0.copy_from_slice(low);
+                hi.0.copy_from_slice(high);
+                (hi, lo)
+            }
+        }
+    };
+}
+
+impl_bigint_halves!(u256, u512);
+impl_bigint_halves!(u512, u1024);
+
+impl u256 {
+    /// Composes integer from its high and low halves
+    pub const fn from_halves(hi: u128, lo: u128) -> u256 {
+        u256([lo as u64, (lo >> 64) as u64, hi as u64, (hi >> 64) as u64])
+    }
+
+    /// Splits integer into its high and low halves, returned in this order
+    pub const fn split(self) -> (u128, u128) {
+        let u256(ref arr) = self;
+        (
+            arr[2] as u128 | (arr[3] as u128) << 64,
+            arr[0] as u128 | (arr[1] as u128) << 64,
+        )
+    }
+}
+
+macro_rules! construct_signed_bigint {
+    ($name:ident, $uname:ident, $n_words:expr) => {
+        /// Large signed integer type
+        ///
+        /// The type uses two's complement representation, stored as the
+        /// unsigned integer of the same bit dimension.
+        #[allow(non_camel_case_types)]
+        #[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
+        pub struct $name($uname);
+
+        impl $name {
+            /// Zero value
+            pub const ZERO: $name = $name($uname::ZERO);
+
+            /// Value for `1`
+            pub const ONE: $name = $name($uname::ONE);
+
+            /// Value for `-1`
+            pub const MINUS_ONE: $name = $name($uname::MAX);
+
+            /// Minimum value
+            pub const MIN: $name = $name($uname({
+                let mut min = [0u64; $n_words];
+                min[$n_words - 1] = 1 << 63;
+                min
+            }));
+
+            /// Maximum value
+            pub const MAX: $name = $name($uname({
+                let mut max = [::core::u64::MAX; $n_words];
+                max[$n_words - 1] = ::core::u64::MAX >> 1;
+                max
+            }));
+
+            /// Bit dimension
+            pub const BITS: u32 = $n_words * 64;
+
+            /// Constructs integer from its two's complement representation
+            /// stored as an unsigned integer
+            #[inline]
+            pub const fn from_bits(bits: $uname) -> $name {
+                $name(bits)
+            }
+
+            /// Returns two's complement representation of the integer as an
+            /// unsigned integer
+            #[inline]
+            pub const fn to_bits(self) -> $uname {
+                self.0
+            }
+
+            /// Constructs integer from `i64` value
+            #[inline]
+            pub const fn from_i64(value: i64) -> $name {
+                let fill = if value < 0 { ::core::u64::MAX } else { 0 };
+                let mut ret = [fill; $n_words];
+                ret[0] = value as u64;
+                $name($uname::from_inner(ret))
+            }
+
+            /// Returns `true` if `self` is negative and `false` if the number
+            /// is zero or positive
+            #[inline]
+            pub const fn is_negative(self) -> bool {
+                (self.0).0[$n_words - 1] >> 63 == 1
+            }
+
+            /// Returns `true` if `self` is positive and `false` if the number
+            /// is zero or negative
+            #[inline]
+            pub fn is_positive(self) -> bool {
+                !self.is_negative() && self != $name::ZERO
+            }
+
+            /// Returns a number representing sign of `self`: `0` if the number
+            /// is zero, `1` if the number is positive and `-1` if the number
+            /// is negative
+            pub fn signum(self) -> $name {
+                if self.is_negative() {
+                    $name::MINUS_ONE
+                } else if self == $name::ZERO {
+                    $name::ZERO
+                } else {
+                    $name::ONE
+                }
+            }
+
+            /// Computes the absolute value of `self` without any wrapping or
+            /// panicking
+            pub fn unsigned_abs(self) -> $uname {
+                if self.is_negative() {
+                    (!self.0).wrapping_add(1u8)
+                } else {
+                    self.0
+                }
+            }
+
+            /// Computes the absolute value of `self`.
+            ///
+            /// # Panics
+            ///
+            /// The absolute value of `Self::MIN` cannot be represented and
+            /// results in a panic.
+            pub fn abs(self) -> $name {
+                let (res, flag) = self.overflowing_abs();
+                assert!(!flag, "attempt to negate with overflow");
+                res
+            }
+
+            /// Checked absolute value. Computes `self.abs()`, returning `None`
+            /// if `self == MIN`.
+            pub fn checked_abs(self) -> Option<$name> {
+                match self.overflowing_abs() {
+                    (_, true) => None,
+                    (res, false) => Some(res),
+                }
+            }
+
+            /// Saturating absolute value. Computes `self.abs()`, returning
+            /// `MAX` if `self == MIN` instead of overflowing.
+            pub fn saturating_abs(self) -> $name {
+                self.checked_abs().unwrap_or($name::MAX)
+            }
+
+            /// Computes the absolute value of `self`.
+            ///
+            /// Returns a tuple of the absolute version of `self` along with a
+            /// boolean indicating whether an overflow happened. If `self` is
+            /// the minimum value, then the minimum value will be returned again
+            /// and `true` will be returned for an overflow happening.
+            pub fn overflowing_abs(self) -> ($name, bool) {
+                if self.is_negative() {
+                    self.overflowing_neg()
+                } else {
+                    (self, false)
+                }
+            }
+
+            /// Wrapping (modular) absolute value. Computes `self.abs()`,
+            /// wrapping around at the boundary of the type.
+            pub fn wrapping_abs(self) -> $name {
+                self.overflowing_abs().0
+            }
+
+            /// Checked negation. Computes `-self`, returning `None` if
+            /// `self == MIN`.
+            pub fn checked_neg(self) -> Option<$name> {
+                match self.overflowing_neg() {
+                    (_, true) => None,
+                    (res, false) => Some(res),
+                }
+            }
+
+            /// Saturating integer negation. Computes `-self`, returning `MAX`
+            /// if `self == MIN` instead of overflowing.
+            pub fn saturating_neg(self) -> $name {
+                self.checked_neg().unwrap_or($name::MAX)
+            }
+
+            /// Negates `self`, overflowing if this is equal to the minimum
+            /// value.
+            ///
+            /// Returns a tuple of the negated version of `self` along with a
+            /// boolean indicating whether an overflow happened. If `self` is
+            /// the minimum value, then the minimum value will be returned again
+            /// and `true` will be returned for an overflow happening.
+            pub fn overflowing_neg(self) -> ($name, bool) {
+                ($name((!self.0).wrapping_add(1u8)), self == $name::MIN)
+            }
+
+            /// Wrapping (modular) negation. Computes `-self`, wrapping around
+            /// at the boundary of the type.
+            pub fn wrapping_neg(self) -> $name {
+                self.overflowing_neg().0
+            }
+
+            /// Checked integer addition. Computes `self + rhs`, returning
+            /// `None` if overflow occurred.
+            pub fn checked_add<T>(self, other: T) -> Option<$name>
+            where
+                T: Into<$name>,
+            {
+                match self.overflowing_add(other) {
+                    (_, true) => None,
+                    (res, false) => Some(res),
+                }
+            }
+
+            /// Saturating integer addition. Computes `self + rhs`, saturating
+            /// at the numeric bounds instead of overflowing.
+            pub fn saturating_add<T>(self, other: T) -> $name
+            where
+                T: Into<$name>,
+            {
+                match self.overflowing_add(other) {
+                    (_, true) if self.is_negative() => $name::MIN,
+                    (_, true) => $name::MAX,
+                    (res, false) => res,
+                }
+            }
+
+            /// Calculates `self + rhs`
+            ///
+            /// Returns a tuple of the addition along with a boolean indicating
+            /// whether an arithmetic overflow would occur. If an overflow would
+            /// have occurred then the wrapped value is returned.
+            pub fn overflowing_add<T>(self, other: T) -> ($name, bool)
+            where
+                T: Into<$name>,
+            {
+                let other = other.into();
+                let res = $name(self.0.wrapping_add(other.0));
+                let overflow = self.is_negative() == other.is_negative()
+                    && res.is_negative() != self.is_negative();
+                (res, overflow)
+            }
+
+            /// Wrapping (modular) addition. Computes `self + rhs`, wrapping
+            /// around at the boundary of the type.
+            pub fn wrapping_add<T>(self, other: T) -> $name
+            where
+                T: Into<$name>,
+            {
+                $name(self.0.wrapping_add(other.into().0))
+            }
+
+            /// Checked integer subtraction. Computes `self - rhs`, returning
+            /// `None` if overflow occurred.
+            pub fn checked_sub<T>(self, other: T) -> Option<$name>
+            where
+                T: Into<$name>,
+            {
+                match self.overflowing_sub(other) {
+                    (_, true) => None,
+                    (res, false) => Some(res),
+                }
+            }
+
+            /// Saturating integer subtraction. Computes `self - rhs`,
+            /// saturating at the numeric bounds instead of overflowing.
+            pub fn saturating_sub<T>(self, other: T) -> $name
+            where
+                T: Into<$name>,
+            {
+                match self.overflowing_sub(other) {
+                    (_, true) if self.is_negative() => $name::MIN,
+                    (_, true) => $name::MAX,
+                    (res, false) => res,
+                }
+            }
+
+            /// Calculates `self - rhs`
+            ///
+            /// Returns a tuple of the subtraction along with a boolean
+            /// indicating whether an arithmetic overflow would occur. If an
+            /// overflow would have occurred then the wrapped value is returned.
+            pub fn overflowing_sub<T>(self, other: T) -> ($name, bool)
+            where
+                T: Into<$name>,
+            {
+                let other = other.into();
+                let res = $name(self.0.wrapping_sub(other.0));
+                let overflow = self.is_negative() != other.is_negative()
+                    && res.is_negative() != self.is_negative();
+                (res, overflow)
+            }
+
+            /// Wrapping (modular) subtraction. Computes `self - rhs`, wrapping
+            /// around at the boundary of the type.
+            pub fn wrapping_sub<T>(self, other: T) -> $name
+            where
+                T: Into<$name>,
+            {
+                $name(self.0.wrapping_sub(other.into().0))
+            }
+
+            /// Checked integer multiplication. Computes `self * rhs`, returning
+            /// `None` if overflow occurred.
+            pub fn checked_mul<T>(self, other: T) -> Option<$name>
+            where
+                T: Into<$name>,
+            {
+                match self.overflowing_mul(other) {
+                    (_, true) => None,
+                    (res, false) => Some(res),
+                }
+            }
+
+            /// Saturating integer multiplication. Computes `self * rhs`,
+            /// saturating at the numeric bounds instead of overflowing.
+            pub fn saturating_mul<T>(self, other: T) -> $name
+            where
+                T: Into<$name>,
+            {
+                let other = other.into();
+                match self.overflowing_mul(other) {
+                    (_, true) if self.is_negative() != other.is_negative() => $name::MIN,
+                    (_, true) => $name::MAX,
+                    (res, false) => res,
+                }
+            }
+
+            /// Calculates `self * rhs`
+            ///
+            /// Returns a tuple of the multiplication along with a boolean
+            /// indicating whether an arithmetic overflow would occur. If an
+            /// overflow would have occurred then the wrapped value is returned.
+            pub fn overflowing_mul<T>(self, other: T) -> ($name, bool)
+            where
+                T: Into<$name>,
+            {
+                let other = other.into();
+                let (magnitude, overflow) =
+                    self.unsigned_abs().overflowing_mul(other.unsigned_abs());
+                let limit = if self.is_negative() != other.is_negative() {
+                    $name::MIN.0
+                } else {
+                    $name::MAX.0
+                };
+                (
+                    $name(self.0.wrapping_mul(other.0)),
+                    overflow || magnitude > limit,
+                )
+            }
+
+            /// Wrapping (modular) multiplication. Computes `self * rhs`,
+            /// wrapping around at the boundary of the type.
+            pub fn wrapping_mul<T>(self, other: T) -> $name
+            where
+                T: Into<$name>,
+            {
+                $name(self.0.wrapping_mul(other.into().0))
+            }
+
+            /// Truncating division with remainder, wrapping around for
+            /// `MIN / -1`. Panics if `other` is zero.
+            fn wrapping_div_rem(self, other: $name) -> ($name, $name) {
+                let (quot, rem) = self.unsigned_abs().div_rem(other.unsigned_abs());
+                let quot = if self.is_negative() != other.is_negative() {
+                    $name(quot).wrapping_neg()
+                } else {
+                    $name(quot)
+                };
+                let rem = if self.is_negative() {
+                    $name(rem).wrapping_neg()
+                } else {
+                    $name(rem)
+                };
+                (quot, rem)
+            }
+
+            /// Checked integer division. Computes `self / rhs`, returning
+            /// `None` if `rhs == 0` or the division results in overflow.
+            pub fn checked_div<T>(self, other: T) -> Option<$name>
+            where
+                T: Into<$name>,
+            {
+                let other = other.into();
+                if other == $name::ZERO {
+                    return None;
+                }
+                match self.overflowing_div(other) {
+                    (_, true) => None,
+                    (res, false) => Some(res),
+                }
+            }
+
+            /// Calculates the quotient when `self` is divided by `rhs`.
+            ///
+            /// Returns a tuple of the divisor along with a boolean indicating
+            /// whether an arithmetic overflow would occur. If an overflow would
+            /// occur (`MIN / -1`) then `self` is returned.
+            ///
+            /// # Panics
+            ///
+            /// This function will panic if `rhs` is 0.
+            pub fn overflowing_div<T>(self, other: T) -> ($name, bool)
+            where
+                T: Into<$name>,
+            {
+                let other = other.into();
+                assert!(other != $name::ZERO, "attempt to divide by zero");
+                (
+                    self.wrapping_div_rem(other).0,
+                    self == $name::MIN && other == $name::MINUS_ONE,
+                )
+            }
+
+            /// Wrapping (modular) division. Computes `self / rhs`, wrapping
+            /// around at the boundary of the type. The only case where such
+            /// wrapping can occur is `MIN / -1`, which results in `MIN`.
+            ///
+            /// # Panics
+            ///
+            /// This function will panic if `rhs` is 0.
+            pub fn wrapping_div<T>(self, other: T) -> $name
+            where
+                T: Into<$name>,
+            {
+                self.overflowing_div(other).0
+            }
+
+            /// Checked integer remainder. Computes `self % rhs`, returning
+            /// `None` if `rhs == 0` or the division results in overflow.
+            pub fn checked_rem<T>(self, other: T) -> Option<$name>
+            where
+                T: Into<$name>,
+            {
+                let other = other.into();
+                if other == $name::ZERO {
+                    return None;
+                }
+                match self.overflowing_rem(other) {
+                    (_, true) => None,
+                    (res, false) => Some(res),
+                }
+            }
+
+            /// Calculates the remainder when `self` is divided by `rhs`.
+            ///
+            /// Returns a tuple of the remainder after dividing along with a
+            /// boolean indicating whether an arithmetic overflow would occur.
+            /// If an overflow would occur (`MIN % -1`) then 0 is returned.
+            ///
+            /// # Panics
+            ///
+            /// This function will panic if `rhs` is 0.
+            pub fn overflowing_rem<T>(self, other: T) -> ($name, bool)
+            where
+                T: Into<$name>,
+            {
+                let other = other.into();
+                assert!(
+                    other != $name::ZERO,
+                    "attempt to calculate the remainder with a divisor of zero"
+                );
+                (
+                    self.wrapping_div_rem(other).1,
+                    self == $name::MIN && other == $name::MINUS_ONE,
+                )
+            }
+
+            /// Wrapping (modular) remainder. Computes `self % rhs`, wrapping
+            /// around at the boundary of the type. The only case where such
+            /// wrapping can occur is `MIN % -1`, which results in 0.
+            ///
+            /// # Panics
+            ///
+            /// This function will panic if `rhs` is 0.
+            pub fn wrapping_rem<T>(self, other: T) -> $name
+            where
+                T: Into<$name>,
+            {
+                self.overflowing_rem(other).0
+            }
+
+            /// Checked shift left. Computes `self << rhs`, returning `None` if
+            /// `rhs` is larger than or equal to the number of bits in `self`.
+            pub fn checked_shl(self, rhs: u32) -> Option<$name> {
+                self.0.checked_shl(rhs).map($name)
+            }
+
+            /// Shifts `self` left by `rhs` bits.
+            ///
+            /// Returns a tuple of the shifted version of `self` along with a
+            /// boolean indicating whether the shift value was larger than or
+            /// equal to the number of bits. If the shift value is too large,
+            /// then value is masked (`rhs % Self::BITS`) and this value is then
+            /// used to perform the shift.
+            pub fn overflowing_shl(self, rhs: u32) -> ($name, bool) {
+                let (res, flag) = self.0.overflowing_shl(rhs);
+                ($name(res), flag)
+            }
+
+            /// Panic-free bitwise shift-left; yields `self << (rhs % Self::BITS)`,
+            /// where the shift amount is masked to the number of bits in the
+            /// type.
+            pub fn wrapping_shl(self, rhs: u32) -> $name {
+                self.overflowing_shl(rhs).0
+            }
+
+            /// Checked arithmetic shift right. Computes `self >> rhs`,
+            /// returning `None` if `rhs` is larger than or equal to the number
+            /// of bits in `self`.
+            pub fn checked_shr(self, rhs: u32) -> Option<$name> {
+                match self.overflowing_shr(rhs) {
+                    (_, true) => None,
+                    (res, false) => Some(res),
+                }
+            }
+
+            /// Shifts `self` right by `rhs` bits, preserving the sign.
+            ///
+            /// Returns a tuple of the shifted version of `self` along with a
+            /// boolean indicating whether the shift value was larger than or
+            /// equal to the number of bits. If the shift value is too large,
+            /// then value is masked (`rhs % Self::BITS`) and this value is then
+            /// used to perform the shift.
+            pub fn overflowing_shr(self, rhs: u32) -> ($name, bool) {
+                (self >> (rhs % Self::BITS) as usize, rhs >= Self::BITS)
+            }
+
+            /// Panic-free arithmetic shift-right; yields
+            /// `self >> (rhs % Self::BITS)`, where the shift amount is masked
+            /// to the number of bits in the type.
+            pub fn wrapping_shr(self, rhs: u32) -> $name {
+                self.overflowing_shr(rhs).0
+            }
+        }
+
+        impl From<i128> for $name {
+            fn from(init: i128) -> $name {
+                let mut ret = if init < 0 {
+                    [::core::u64::MAX; $n_words]
+                } else {
+                    [0u64; $n_words]
+                };
+                ret[0] = init as u64;
+                ret[1] = (init >> 64) as u64;
+                $name($uname(ret))
+            }
+        }
+
+        impl From<i64> for $name {
+            #[inline]
+            fn from(init: i64) -> $name {
+                $name::from_i64(init)
+            }
+        }
+
+        impl From<i32> for $name {
+            #[inline]
+            fn from(init: i32) -> $name {
+                $name::from(init as i128)
+            }
+        }
+
+        impl From<i16> for $name {
+            #[inline]
+            fn from(init: i16) -> $name {
+                $name::from(init as i128)
+            }
+        }
+
+        impl From<i8> for $name {
+            #[inline]
+            fn from(init: i8) -> $name {
+                $name::from(init as i128)
+            }
+        }
+
+        impl From<u64> for $name {
+            #[inline]
+            fn from(init: u64) -> $name {
+                $name($uname::from(init))
+            }
+        }
+
+        impl From<u32> for $name {
+            #[inline]
+            fn from(init: u32) -> $name {
+                $name($uname::from(init))
+            }
+        }
+
+        impl From<u16> for $name {
+            #[inline]
+            fn from(init: u16) -> $name {
+                $name($uname::from(init))
+            }
+        }
+
+        impl From<u8> for $name {
+            #[inline]
+            fn from(init: u8) -> $name {
+                $name($uname::from(init))
+            }
+        }
+
+        impl ::core::convert::TryFrom<$uname> for $name {
+            type Error = TryFromIntError;
+
+            fn try_from(value: $uname) -> Result<$name, Self::Error> {
+                if $name(value).is_negative() {
+                    Err(TryFromIntError)
+                } else {
+                    Ok($name(value))
+                }
+            }
+        }
+
+        impl ::core::convert::TryFrom<$name> for $uname {
+            type Error = TryFromIntError;
+
+            fn try_from(value: $name) -> Result<$uname, Self::Error> {
+                if value.is_negative() {
+                    Err(TryFromIntError)
+                } else {
+                    Ok(value.0)
+                }
+            }
+        }
+
+        impl PartialOrd for $name {
+            #[inline]
+            fn partial_cmp(&self, other: &$name) -> Option<::core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name {
+            #[inline]
+            fn cmp(&self, other: &$name) -> ::core::cmp::Ordering {
+                // Flipping the sign bit maps two's complement values onto
+                // unsigned ones preserving the order
+                (self.0 ^ $name::MIN.0).cmp(&(other.0 ^ $name::MIN.0))
+            }
+        }
+
+        impl<T> ::core::ops::Add<T> for $name
+        where
+            T: Into<$name>,
+        {
+            type Output = $name;
+
+            fn add(self, other: T) -> $name {
+                let (res, flag) = self.overflowing_add(other);
+                assert!(!flag, "attempt to add with overflow");
+                res
+            }
+        }
+        impl<T> ::core::ops::AddAssign<T> for $name
+        where
+            T: Into<$name>,
+        {
+            #[inline]
+            fn add_assign(&mut self, rhs: T) {
+                *self = *self + rhs
+            }
+        }
+
+        impl<T> ::core::ops::Sub<T> for $name
+        where
+            T: Into<$name>,
+        {
+            type Output = $name;
+
+            fn sub(self, other: T) -> $name {
+                let (res, flag) = self.overflowing_sub(other);
+                assert!(!flag, "attempt to subtract with overflow");
+                res
+            }
+        }
+        impl<T> ::core::ops::SubAssign<T> for $name
+        where
+            T: Into<$name>,
+        {
+            #[inline]
+            fn sub_assign(&mut self, rhs: T) {
+                *self = *self - rhs
+            }
+        }
+
+        impl<T> ::core::ops::Mul<T> for $name
+        where
+            T: Into<$name>,
+        {
+            type Output = $name;
+
+            fn mul(self, other: T) -> $name {
+                let (res, flag) = self.overflowing_mul(other);
+                assert!(!flag, "attempt to multiply with overflow");
+                res
+            }
+        }
+        impl<T> ::core::ops::MulAssign<T> for $name
+        where
+            T: Into<$name>,
+        {
+            #[inline]
+            fn mul_assign(&mut self, rhs: T) {
+                *self = *self * rhs
+            }
+        }
+
+        impl<T> ::core::ops::Div<T> for $name
+        where
+            T: Into<$name>,
+        {
+            type Output = $name;
+
+            fn div(self, other: T) -> $name {
+                let (res, flag) = self.overflowing_div(other);
+                assert!(!flag, "attempt to divide with overflow");
+                res
+            }
+        }
+        impl<T> ::core::ops::DivAssign<T> for $name
+        where
+            T: Into<$name>,
+        {
+            #[inline]
+            fn div_assign(&mut self, rhs: T) {
+                *self = *self / rhs
+            }
+        }
+
+        impl<T> ::core::ops::Rem<T> for $name
+        where
+            T: Into<$name>,
+        {
+            type Output = $name;
+
+            fn rem(self, other: T) -> $name {
+                let (res, flag) = self.overflowing_rem(other);
+                assert!(!flag, "attempt to calculate the remainder with overflow");
+                res
+            }
+        }
+        impl<T> ::core::ops::RemAssign<T> for $name
+        where
+            T: Into<$name>,
+        {
+            #[inline]
+            fn rem_assign(&mut self, rhs: T) {
+                *self = *self % rhs
+            }
+        }
+
+        impl ::core::ops::Neg for $name {
+            type Output = $name;
+
+            fn neg(self) -> $name {
+                let (res, flag) = self.overflowing_neg();
+                assert!(!flag, "attempt to negate with overflow");
+                res
+            }
+        }
+
+        impl ::core::ops::Shl<usize> for $name {
+            type Output = $name;
+
+            #[inline]
+            fn shl(self, shift: usize) -> $name {
+                $name(self.0 << shift)
+            }
+        }
+        impl ::core::ops::ShlAssign<usize> for $name {
+            #[inline]
+            fn shl_assign(&mut self, rhs: usize) {
+                *self = *self << rhs
+            }
+        }
+
+        /// Arithmetic shift right, filling the high bits with the sign bit
+        impl ::core::ops::Shr<usize> for $name {
+            type Output = $name;
+
+            #[inline]
+            fn shr(self, shift: usize) -> $name {
+                if self.is_negative() {
+                    $name(!(!self.0 >> shift))
+                } else {
+                    $name(self.0 >> shift)
+                }
+            }
+        }
+        impl ::core::ops::ShrAssign<usize> for $name {
+            #[inline]
+            fn shr_assign(&mut self, rhs: usize) {
+                *self = *self >> rhs
+            }
+        }
+
+        impl ::core::ops::Not for $name {
+            type Output = $name;
+
+            #[inline]
+            fn not(self) -> $name {
+                $name(!self.0)
+            }
+        }
+
+        impl ::core::fmt::Debug for $name {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(self, f)
+            }
+        }
+
+        /// Formats the value as a decimal number, honoring formatter flags in
+        /// the same way as primitive integers do
+        impl ::core::fmt::Display for $name {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                self.unsigned_abs().fmt_decimal(!self.is_negative(), f)
+            }
+        }
+
+        /// Formats two's complement representation of the value, like
+        /// primitive integers do
+        #[cfg(feature = "alloc")]
+        impl ::core::fmt::LowerHex for $name {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::LowerHex::fmt(&self.0, f)
+            }
+        }
+
+        /// Formats two's complement representation of the value, like
+        /// primitive integers do
+        #[cfg(feature = "alloc")]
+        impl ::core::fmt::UpperHex for $name {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::UpperHex::fmt(&self.0, f)
+            }
+        }
+
+        /// Formats two's complement representation of the value, like
+        /// primitive integers do
+        #[cfg(feature = "alloc")]
+        impl ::core::fmt::Octal for $name {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Octal::fmt(&self.0, f)
+            }
+        }
+
+        /// Formats two's complement representation of the value, like
+        /// primitive integers do
+        #[cfg(feature = "alloc")]
+        impl ::core::fmt::Binary for $name {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Binary::fmt(&self.0, f)
+            }
+        }
+
+        /// Parses decimal or `0x`-prefixed hexadecimal number with an
+        /// optional leading minus. Plus sign, whitespace and digit separators
+        /// (underscores) are not allowed.
+        impl ::core::str::FromStr for $name {
+            type Err = ParseIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let negative = s.starts_with('-');
+                let digits = if negative { &s[1..] } else { s };
+                let magnitude = digits.parse::<$uname>().map_err(|err| match err {
+                    ParseIntError::InvalidDigit(ch, pos) if negative => {
+                        ParseIntError::InvalidDigit(ch, pos + 1)
+                    }
+                    err => err,
+                })?;
+                if negative {
+                    if magnitude > $name::MIN.0 {
+                        return Err(ParseIntError::Overflow);
+                    }
+                    Ok($name(magnitude).wrapping_neg())
+                } else {
+                    if magnitude > $name::MAX.0 {
+                        return Err(ParseIntError::Overflow);
+                    }
+                    Ok($name(magnitude))
+                }
+            }
+        }
+
+        #[cfg(feature = "rand")]
+        impl ::rand::distributions::Distribution<$name> for ::rand::distributions::Standard {
+            fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> $name {
+                $name(rng.gen())
+            }
+        }
+
+        #[cfg(feature = "rand")]
+        impl ::rand::distributions::uniform::SampleUniform for $name {
+            type Sampler = UniformBigint<$name, $uname>;
+        }
+
+        #[cfg(feature = "rand")]
+        impl ::rand::distributions::uniform::UniformSampler for UniformBigint<$name, $uname> {
+            type X = $name;
+
+            fn new<B1, B2>(low: B1, high: B2) -> Self
+            where
+                B1: ::rand::distributions::uniform::SampleBorrow<$name> + Sized,
+                B2: ::rand::distributions::uniform::SampleBorrow<$name> + Sized,
+            {
+                let (low, high) = (*low.borrow(), *high.borrow());
+                assert!(low < high, "Uniform::new called with `low >= high`");
+                Self::new_inclusive(low, high - $name::ONE)
+            }
+
+            fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+            where
+                B1: ::rand::distributions::uniform::SampleBorrow<$name> + Sized,
+                B2: ::rand::distributions::uniform::SampleBorrow<$name> + Sized,
+            {
+                let (low, high) = (*low.borrow(), *high.borrow());
+                assert!(
+                    low <= high,
+                    "Uniform::new_inclusive called with `low > high`"
+                );
+                let range = high.0.wrapping_sub(low.0).wrapping_add(1u8);
+                UniformBigint {
+                    low,
+                    range,
+                    zone: UniformBigint::<$uname>::zone(range),
+                }
+            }
+
+            fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> $name {
+                let offset = UniformBigint::<$uname>::sample_below(self.range, self.zone, rng);
+                $name(self.low.0.wrapping_add(offset))
+            }
+        }
+    };
+}
+construct_signed_bigint!(i256, u256, 4);
+construct_signed_bigint!(i512, u512, 8);
+construct_signed_bigint!(i1024, u1024, 16);
+#[cfg(test)]
+mod tests {
+    #![allow(unused)]
+
+    use super::*;
+
+    construct_bigint!(Uint128, 2);
+    construct_signed_bigint!(Int128, Uint128, 2);
+
+    const MAX_TARGET: u256 = u256::from_be_bytes({
+        let mut bytes = [0u8; 32];
+        bytes[2] = 0xFF;
+        bytes[3] = 0xFF;
+        bytes
+    });
+    const _: () = assert!(MAX_TARGET.const_eq(u256::from_inner([0, 0, 0, 0xFFFF << 32])));
+    const _: () = assert!(MAX_TARGET.to_be_bytes()[2] == 0xFF);
+    const _: () = assert!(MAX_TARGET.to_le_bytes()[29] == 0xFF);
+    const _: () = assert!(u256::from_le_bytes(MAX_TARGET.to_le_bytes()).const_eq(MAX_TARGET));
+    const _: () = assert!(u256::from_u64(7).low_u64() == 7);
+    const _: () = assert!(u256::from_u64(7).const_lt(MAX_TARGET));
+    const _: () = assert!(!MAX_TARGET.const_lt(MAX_TARGET));
+    const _: () = assert!(u256::from_u64(::core::u64::MAX)
+        .const_wrapping_add(u256::ONE)
+        .const_eq(u256::from_inner([0, 1, 0, 0])));
+    const _: () = assert!(u256::MAX.const_overflowing_add(u256::ONE).1);
+    const _: () = assert!(u256::MAX.const_wrapping_add(u256::ONE).const_eq(u256::ZERO));
+    const _: () = assert!(u256::ZERO.const_overflowing_sub(u256::ONE).1);
+    const _: () = assert!(u256::ZERO.const_wrapping_sub(u256::ONE).const_eq(u256::MAX));
+    const _: () = assert!(u256::from_u64(0xFF00)
+        .const_and(u256::from_u64(0x0FF0))
+        .const_eq(u256::from_u64(0x0F00)));
+    const _: () = assert!(u256::from_u64(0xFF00)
+        .const_or(u256::from_u64(0x0FF0))
+        .const_eq(u256::from_u64(0xFFF0)));
+    const _: () = assert!(u256::from_u64(0xFF00)
+        .const_xor(u256::from_u64(0x0FF0))
+        .const_eq(u256::from_u64(0xF0F0)));
+    const _: () = assert!(u256::ZERO.const_not().const_eq(u256::MAX));
+    const _: () = assert!(i256::from_i64(-1).to_bits().const_eq(u256::MAX));
+    const _: () = assert!(i256::from_i64(-1).is_negative());
+    const _: () = assert!(i256::from_bits(u256::ONE).to_bits().const_eq(u256::ONE));
+
+    #[test]
+    fn const_arithmetic_matches_ops() {
+        let a = u256::from_inner([::core::u64::MAX, 0, 0xDEAD, 1]);
+        let b = u256::from_inner([1, ::core::u64::MAX, 0xBEEF, 0]);
+        assert_eq!(a.const_overflowing_add(b), a.overflowing_add(b));
+        assert_eq!(a.const_overflowing_sub(b), (a - b, false));
+        assert!(b.const_overflowing_sub(a).1);
+        assert_eq!(b.const_wrapping_sub(a).wrapping_add(a), b);
+        assert_eq!(a.const_eq(b), a == b);
+        assert_eq!(a.const_lt(b), a < b);
+        assert_eq!(b.const_lt(a), b < a);
+        assert_eq!(i256::from_i64(-0xDEAD), i256::from(-0xDEADi128));
+        assert_eq!(i256::from_i64(0xDEAD), i256::from(0xDEADi128));
+    }
+
+    #[test]
+    fn u256_bits_test() {
+        assert_eq!(u256::from(255u64).bits_required(), 8);
+        assert_eq!(u256::from(256u64).bits_required(), 9);
+        assert_eq!(u256::from(300u64).bits_required(), 9);
+        assert_eq!(u256::from(60000u64).bits_required(), 16);
+        assert_eq!(u256::from(70000u64).bits_required(), 17);
+
+        // Try to read the following lines out loud quickly
+        let mut shl = u256::from(70000u64);
+        shl = shl << 100;
+        assert_eq!(shl.bits_required(), 117);
+        shl = shl << 100;
+        assert_eq!(shl.bits_required(), 217);
+        shl = shl << 100;
+        assert_eq!(shl.bits_required(), 0);
+
+        // Bit set check
+        assert!(!u256::from(10u64).bit(0));
+        assert!(u256::from(10u64).bit(1));
+        assert!(!u256::from(10u64).bit(2));
+        assert!(u256::from(10u64).bit(3));
+        assert!(!u256::from(10u64).bit(4));
+    }
+
+    #[test]
+    fn u256_display_test() {
+        assert_eq!(format!("{}", u256::from(0xDEADBEEFu64)), "3735928559");
+        assert_eq!(
+            format!("{}", u256::from(::core::u64::MAX)),
+            "18446744073709551615"
+        );
+        assert_eq!(format!("{}", u256::ZERO), "0");
+        assert_eq!(
+            format!("{}", u256::from(10_000_000_000_000_000_000u64)),
+            "10000000000000000000"
+        );
+        assert_eq!(format!("{:5}", u256::from(42u8)), "   42");
+        assert_eq!(format!("{:<5}", u256::from(42u8)), "42   ");
+        assert_eq!(format!("{:05}", u256::from(42u8)), "00042");
+        assert_eq!(format!("{:+}", u256::from(42u8)), "+42");
+
+        let max_val = u256([
+            0xFFFFFFFFFFFFFFFF,
+            0xFFFFFFFFFFFFFFFF,
+            0xFFFFFFFFFFFFFFFF,
+            0xFFFFFFFFFFFFFFFF,
+        ]);
+        assert_eq!(
+            format!("{}", max_val),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
+        assert_eq!(
+            format!("{:?}", max_val),
+            "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn fmt_hex() {
+        let one = u256::ONE;
+        let mut u_256 = u256([
+            0x0000000000000000,
+            0xAAAAAAAABBBBBBBB,
+            0x0000000111122222,
+            0x0000000000000000,
+        ]);
+
+        // UpperHex
+        assert_eq!(
+            format!("{:X}", u_256),
+            "111122222AAAAAAAABBBBBBBB0000000000000000"
+        );
+        assert_eq!(
+            format!("{:#X}", u_256),
+            "0x111122222AAAAAAAABBBBBBBB0000000000000000"
+        );
+        assert_eq!(format!("{:X}", u256::ZERO), "0");
+        assert_eq!(format!("{:05X}", one), "00001");
+        assert_eq!(format!("{:#05X}", one), "0x001");
+        assert_eq!(format!("{:5X}", one), "1    ");
+        assert_eq!(format!("{:#5X}", one), "0x1  ");
+        assert_eq!(format!("{:w^#7X}", one), "ww0x1ww");
+
+        // LowerHex
+        assert_eq!(
+            format!("{:x}", u_256),
+            "111122222aaaaaaaabbbbbbbb0000000000000000"
+        );
+        assert_eq!(
+            format!("{:#x}", u_256),
+            "0x111122222aaaaaaaabbbbbbbb0000000000000000"
+        );
+        assert_eq!(format!("{:x}", u256::ZERO), "0");
+        assert_eq!(format!("{:05x}", one), "00001");
+        assert_eq!(format!("{:#05x}", one), "0x001");
+        assert_eq!(format!("{:5x}", one), "1    ");
+        assert_eq!(format!("{:#5x}", one), "0x1  ");
+        assert_eq!(format!("{:w^#7x}", one), "ww0x1ww");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn fmt_octal() {
+        let one = u256::ONE;
+        let mut u_256 = u256([
+            0o0000000000000000000000,
+            0o0011222222222222222222,
+            0o0000000001111111111111,
+            0o0000000000000000000000,
+        ]);
+
+        assert_eq!(
+            format!("{:o}", u_256),
+            "4444444444444022444444444444444444000000000000000000000"
+        );
+        assert_eq!(
+            format!("{:#o}", u_256),
+            "0o4444444444444022444444444444444444000000000000000000000"
+        );
+        assert_eq!(format!("{:o}", u256::ZERO), "0");
+        assert_eq!(format!("{:05o}", one), "00001");
+        assert_eq!(format!("{:#05o}", one), "0o001");
+        assert_eq!(format!("{:5o}", one), "1    ");
+        assert_eq!(format!("{:#5o}", one), "0o1  ");
+        assert_eq!(format!("{:w^#7o}", one), "ww0o1ww");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn fmt_binary() {
+        let one = u256::ONE;
+        let mut u_256 = u256([
+            0b0000000000000000000000000000000000000000000000000000000000000000,
+            0b0001111000011110001111000011110001111000011110001111000011110000,
+            0b0000000000000000000000000000001111111111111111111111111111111111,
+            0b0000000000000000000000000000000000000000000000000000000000000000,
+        ]);
+
+        assert_eq!(
+            format!("{:b}", u_256),
+            "111111111111111111111111111111111100011110000111100011110000111100011110000111100011110000111100000000000000000000000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(
+            format!("{:#b}", u_256),
+            "0b111111111111111111111111111111111100011110000111100011110000111100011110000111100011110000111100000000000000000000000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(format!("{:b}", u256::ZERO), "0");
+        assert_eq!(format!("{:05b}", one), "00001");
+        assert_eq!(format!("{:#05b}", one), "0b001");
+        assert_eq!(format!("{:5b}", one), "1    ");
+        assert_eq!(format!("{:#5b}", one), "0b1  ");
+        assert_eq!(format!("{:w^#7b}", one), "ww0b1ww");
+    }
+
+    #[test]
+    fn u256_comp_test() {
+        let small = u256([10u64, 0, 0, 0]);
+        let big = u256([0x8C8C3EE70C644118u64, 0x0209E7378231E632, 0, 0]);
+        let bigger = u256([0x9C8C3EE70C644118u64, 0x0209E7378231E632, 0, 0]);
+        let biggest = u256([0x5C8C3EE70C644118u64, 0x0209E7378231E632, 0, 1]);
+
+        assert!(small < big);
+        assert!(big < bigger);
+        assert!(bigger < biggest);
+        assert!(bigger <= biggest);
+        assert!(biggest <= biggest);
+        assert!(bigger >= big);
+        assert!(bigger >= small);
+        assert!(small <= small);
+    }
+
+    #[test]
+    fn uint_from_be_bytes() {
+        assert_eq!(
+            Uint128::from_be_bytes([
+                0x1b, 0xad, 0xca, 0xfe, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xaf, 0xba, 0xbe, 0x2b, 0xed,
+                0xfe, 0xed
+            ]),
+            Uint128([0xdeafbabe2bedfeed, 0x1badcafedeadbeef])
+        );
+
+        assert_eq!(
+            u256::from_be_bytes([
+                0x1b, 0xad, 0xca, 0xfe, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xaf, 0xba, 0xbe, 0x2b, 0xed,
+                0xfe, 0xed, 0xba, 0xad, 0xf0, 0x0d, 0xde, 0xfa, 0xce, 0xda, 0x11, 0xfe, 0xd2, 0xba,
+                0xd1, 0xc0, 0xff, 0xe0
+            ]),
+            u256([
+                0x11fed2bad1c0ffe0,
+                0xbaadf00ddefaceda,
+                0xdeafbabe2bedfeed,
+                0x1badcafedeadbeef
+            ])
+        );
+    }
+
+    #[test]
+    fn uint_from_le_bytes() {
+        let mut be = [
+            0x1b, 0xad, 0xca, 0xfe, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xaf, 0xba, 0xbe, 0x2b, 0xed,
+            0xfe, 0xed,
+        ];
+        be.reverse();
+        assert_eq!(
+            Uint128::from_le_bytes(be),
+            Uint128([0xdeafbabe2bedfeed, 0x1badcafedeadbeef])
+        );
+
+        let mut be = [
+            0x1b, 0xad, 0xca, 0xfe, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xaf, 0xba, 0xbe, 0x2b, 0xed,
+            0xfe, 0xed, 0xba, 0xad, 0xf0, 0x0d, 0xde, 0xfa, 0xce, 0xda, 0x11, 0xfe, 0xd2, 0xba,
+            0xd1, 0xc0, 0xff, 0xe0,
+        ];
+        be.reverse();
+        assert_eq!(
+            u256::from_le_bytes(be),
+            u256([
+                0x11fed2bad1c0ffe0,
+                0xbaadf00ddefaceda,
+                0xdeafbabe2bedfeed,
+                0x1badcafedeadbeef
+            ])
+        );
+    }
+
+    #[test]
+    fn uint_to_be_bytes() {
+        assert_eq!(
+            Uint128([0xdeafbabe2bedfeed, 0x1badcafedeadbeef]).to_be_bytes(),
+            [
+                0x1b, 0xad, 0xca, 0xfe, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xaf, 0xba, 0xbe, 0x2b, 0xed,
+                0xfe, 0xed
+            ]
+        );
+
+        assert_eq!(
+            u256([
+                0x11fed2bad1c0ffe0,
+                0xbaadf00ddefaceda,
+                0xdeafbabe2bedfeed,
+                0x1badcafedeadbeef
+            ])
+            .to_be_bytes(),
+            [
+                0x1b, 0xad, 0xca, 0xfe, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xaf, 0xba, 0xbe, 0x2b, 0xed,
+                0xfe, 0xed, 0xba, 0xad, 0xf0, 0x0d, 0xde, 0xfa, 0xce, 0xda, 0x11, 0xfe, 0xd2, 0xba,
+                0xd1, 0xc0, 0xff, 0xe0
+            ]
+        );
+    }
 
     #[test]
-    fn u256_display_test() {
+    fn uint_to_le_bytes() {
         assert_eq!(
-            format!("{}", u256::from(0xDEADBEEFu64)),
-            "0x00000000000000000000000000000000000000000000000000000000deadbeef"
+            Uint128([0xdeafbabe2bedfeed, 0x1badcafedeadbeef]).to_le_bytes(),
+            [
+                0xed, 0xfe, 0xed, 0x2b, 0xbe, 0xba, 0xaf, 0xde, 0xef, 0xbe, 0xad, 0xde, 0xfe, 0xca,
+                0xad, 0x1b
+            ]
         );
+
         assert_eq!(
-            format!("{}", u256::from(::core::u64::MAX)),
-            "0x000000000000000000000000000000000000000000000000ffffffffffffffff"
+            u256([
+                0x11fed2bad1c0ffe0,
+                0xbaadf00ddefaceda,
+                0xdeafbabe2bedfeed,
+                0x1badcafedeadbeef
+            ])
+            .to_le_bytes(),
+            [
+                0xe0, 0xff, 0xc0, 0xd1, 0xba, 0xd2, 0xfe, 0x11, 0xda, 0xce, 0xfa, 0xde, 0x0d, 0xf0,
+                0xad, 0xba, 0xed, 0xfe, 0xed, 0x2b, 0xbe, 0xba, 0xaf, 0xde, 0xef, 0xbe, 0xad, 0xde,
+                0xfe, 0xca, 0xad, 0x1b,
+            ]
         );
+    }
 
-        let max_val = u256([
-            0xFFFFFFFFFFFFFFFF,
-            0xFFFFFFFFFFFFFFFF,
-            0xFFFFFFFFFFFFFFFF,
-            0xFFFFFFFFFFFFFFFF,
-        ]);
+    #[test]
+    fn u256_div_rem_checked() {
+        let zero = u256::ZERO;
+        let number_one = u256::from(0xDEADBEEFu64);
+        let number_two = u256::from(::core::u64::MAX);
+        let one_div_rem_two = (
+            u256::from(::core::u64::MAX / 0xDEADBEEFu64),
+            u256::from(::core::u64::MAX % 0xDEADBEEFu64),
+        );
+        let max = u256::MAX;
+
+        // Division by zero gets not panic and gets None
+        assert_eq!(u256::div_rem_checked(max, zero), None);
+        assert_eq!(u256::div_rem_checked(number_two, zero), None);
+        assert_eq!(u256::div_rem_checked(number_one, zero), None);
+
+        // Division of zero gets Zero
+        assert_eq!(u256::div_rem_checked(zero, max), Some((zero, zero)));
+        assert_eq!(u256::div_rem_checked(zero, number_two), Some((zero, zero)));
+        assert_eq!(u256::div_rem_checked(zero, number_one), Some((zero, zero)));
+
+        // Division by another than zero not gets None
+        assert_ne!(u256::div_rem_checked(max, number_one), None);
+        assert_ne!(u256::div_rem_checked(number_two, number_one), None);
+
+        // In u256 division gets the same as in u64
+        assert_eq!(
+            u256::div_rem_checked(number_two, number_one),
+            Some(one_div_rem_two)
+        );
+    }
+
+    #[test]
+    fn u256_div_rem() {
+        let zero = u256::ZERO;
+        let number_one = u256::from(0xDEADBEEFu64);
+        let number_two = u256::from(::core::u64::MAX);
+        let max = u256::MAX;
+
+        let result1 = std::panic::catch_unwind(|| u256::div_rem(max, zero));
+        assert!(result1.is_err());
+        let result2 = std::panic::catch_unwind(|| u256::div_rem(number_one, zero));
+        assert!(result2.is_err());
+        let result3 = std::panic::catch_unwind(|| u256::div_rem(number_two, zero));
+        assert!(result3.is_err());
+    }
+
+    #[test]
+    fn bigint_min_max() {
+        assert_eq!(u256::MIN.as_inner(), &[0u64; 4]);
+        assert_eq!(u512::MIN.as_inner(), &[0u64; 8]);
+        assert_eq!(u1024::MIN.as_inner(), &[0u64; 16]);
+        assert_eq!(u256::MAX.as_inner(), &[::core::u64::MAX; 4]);
+        assert_eq!(u512::MAX.as_inner(), &[::core::u64::MAX; 8]);
+        assert_eq!(u1024::MAX.as_inner(), &[::core::u64::MAX; 16]);
+        assert_eq!(u256::BITS, 4 * 64);
+        assert_eq!(u512::BITS, 8 * 64);
+        assert_eq!(u1024::BITS, 16 * 64);
+    }
+
+    #[test]
+    fn u256_arithmetic_test() {
+        let init = u256::from(0xDEADBEEFDEADBEEFu64);
+        let copy = init;
+
+        let add = init + copy;
+        assert_eq!(add, u256([0xBD5B7DDFBD5B7DDEu64, 1, 0, 0]));
+        // Bitshifts
+        let shl = add << 88;
+        assert_eq!(shl, u256([0u64, 0xDFBD5B7DDE000000, 0x1BD5B7D, 0]));
+        let shr = shl >> 40;
+        assert_eq!(shr, u256([0x7DDE000000000000u64, 0x0001BD5B7DDFBD5B, 0, 0]));
+        // Increment
+        let mut incr = shr;
+        incr += 1u32;
+        assert_eq!(
+            incr,
+            u256([0x7DDE000000000001u64, 0x0001BD5B7DDFBD5B, 0, 0])
+        );
+        // Subtraction
+        let sub = incr - init;
+        assert_eq!(sub, u256([0x9F30411021524112u64, 0x0001BD5B7DDFBD5A, 0, 0]));
+        // Multiplication
+        let mult = sub * 300u32;
+        assert_eq!(
+            mult,
+            u256([0x8C8C3EE70C644118u64, 0x0209E7378231E632, 0, 0])
+        );
+        // Division
+        assert_eq!(u256::from(105u64) / u256::from(5u64), u256::from(21u64));
+        let div = mult / u256::from(300u64);
+        assert_eq!(div, u256([0x9F30411021524112u64, 0x0001BD5B7DDFBD5A, 0, 0]));
+
+        assert_eq!(u256::from(105u64) % u256::from(5u64), u256::from(0u64));
+        assert_eq!(
+            u256::from(35498456u64) % u256::from(3435u64),
+            u256::from(1166u64)
+        );
+        let rem_src = mult * u256::from(39842u64) + u256::from(9054u64);
+        assert_eq!(rem_src % u256::from(39842u64), u256::from(9054u64));
+        // TODO: bit inversion
+    }
+
+    #[test]
+    fn mul_u32_test() {
+        let u64_val = u256::from(0xDEADBEEFDEADBEEFu64);
+
+        let u96_res = u64_val * 0xFFFFFFFFu32;
+        let u128_res = u96_res * 0xFFFFFFFFu32;
+        let u160_res = u128_res * 0xFFFFFFFFu32;
+        let u192_res = u160_res * 0xFFFFFFFFu32;
+        let u224_res = u192_res * 0xFFFFFFFFu32;
+        let u256_res = u224_res * 0xFFFFFFFFu32;
+
+        assert_eq!(u96_res, u256([0xffffffff21524111u64, 0xDEADBEEE, 0, 0]));
+        assert_eq!(
+            u128_res,
+            u256([0x21524111DEADBEEFu64, 0xDEADBEEE21524110, 0, 0])
+        );
+        assert_eq!(
+            u160_res,
+            u256([0xBD5B7DDD21524111u64, 0x42A4822200000001, 0xDEADBEED, 0])
+        );
+        assert_eq!(
+            u192_res,
+            u256([
+                0x63F6C333DEADBEEFu64,
+                0xBD5B7DDFBD5B7DDB,
+                0xDEADBEEC63F6C334,
+                0
+            ])
+        );
+        assert_eq!(
+            u224_res,
+            u256([
+                0x7AB6FBBB21524111u64,
+                0xFFFFFFFBA69B4558,
+                0x854904485964BAAA,
+                0xDEADBEEB
+            ])
+        );
+        assert_eq!(
+            u256_res,
+            u256([
+                0xA69B4555DEADBEEFu64,
+                0xA69B455CD41BB662,
+                0xD41BB662A69B4550,
+                0xDEADBEEAA69B455C
+            ])
+        );
+    }
+
+    #[test]
+    fn multiplication_test() {
+        let u64_val = u256::from(0xDEADBEEFDEADBEEFu64);
+
+        let u128_res = u64_val * u64_val;
+
+        assert_eq!(
+            u128_res,
+            u256([0x048D1354216DA321u64, 0xC1B1CD13A4D13D46, 0, 0])
+        );
+
+        let u256_res = u128_res * u128_res;
+
+        assert_eq!(
+            u256_res,
+            u256([
+                0xF4E166AAD40D0A41u64,
+                0xF5CF7F3618C2C886u64,
+                0x4AFCFF6F0375C608u64,
+                0x928D92B4D7F5DF33u64
+            ])
+        );
+    }
+
+    #[test]
+    fn u256_extreme_bitshift_test() {
+        // Shifting a u64 by 64 bits gives an undefined value, so make sure that
+        // we're doing the Right Thing here
+        let init = u256::from(0xDEADBEEFDEADBEEFu64);
+
+        assert_eq!(init << 64, u256([0, 0xDEADBEEFDEADBEEF, 0, 0]));
+        let add = (init << 64) + init;
+        assert_eq!(add, u256([0xDEADBEEFDEADBEEF, 0xDEADBEEFDEADBEEF, 0, 0]));
+        assert_eq!(
+            add >> 0,
+            u256([0xDEADBEEFDEADBEEF, 0xDEADBEEFDEADBEEF, 0, 0])
+        );
+        assert_eq!(
+            add << 0,
+            u256([0xDEADBEEFDEADBEEF, 0xDEADBEEFDEADBEEF, 0, 0])
+        );
+        assert_eq!(add >> 64, u256([0xDEADBEEFDEADBEEF, 0, 0, 0]));
+        assert_eq!(
+            add << 64,
+            u256([0, 0xDEADBEEFDEADBEEF, 0xDEADBEEFDEADBEEF, 0])
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn u256_serde_test() {
+        let check = |uint: u256, dec: &str| {
+            let json = format!("\"{}\"", dec);
+            assert_eq!(::serde_json::to_string(&uint).unwrap(), json);
+            assert_eq!(::serde_json::from_str::<u256>(&json).unwrap(), uint);
+            let hex = format!("\"0x{:x}\"", uint);
+            assert_eq!(::serde_json::from_str::<u256>(&hex).unwrap(), uint);
+
+            let bin_encoded = ::bincode::serialize(&uint).unwrap();
+            assert_eq!(bin_encoded, uint.to_le_bytes());
+            let bin_decoded: u256 = ::bincode::deserialize(&bin_encoded).unwrap();
+            assert_eq!(bin_decoded, uint);
+        };
+
+        check(u256::from(0u64), "0");
+        check(u256::from(0xDEADBEEFu64), "3735928559");
+        check(
+            u256([0xaa11, 0xbb22, 0xcc33, 0xdd44]),
+            "355560150699243145204502716818967114652133439121311583982103057",
+        );
+        check(
+            u256::MAX,
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+        );
+
+        assert_eq!(
+            ::serde_json::from_str::<u256>("12345").unwrap(),
+            u256::from(12345u64)
+        );
         assert_eq!(
-            format!("{}", max_val),
-            "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+            ::serde_json::from_str::<u256>("18446744073709551615").unwrap(),
+            u256::from(::core::u64::MAX)
         );
+        assert!(::serde_json::from_str::<u256>("-1").is_err());
+        assert!(::serde_json::from_str::<u256>("1.5").is_err());
+
+        let err = |json: &str| {
+            ::serde_json::from_str::<u256>(json)
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(err("\"12a\"").starts_with("invalid u256 string \"12a\": Invalid digit 'a'"));
+        assert!(err("\"0xg\"").starts_with("invalid u256 string \"0xg\": Invalid digit 'g'"));
+        assert!(err("\"\"").starts_with("invalid u256 string \"\": Cannot parse integer"));
+        assert!(err(
+            "\"115792089237316195423570985008687907853269984665640564039457584007913129639936\""
+        )
+        .contains("Number too large"));
+        assert!(err(&format!("\"0x1{}\"", "0".repeat(64))).contains("Number too large"));
+
+        assert!(::bincode::deserialize::<u256>(&[0u8; 31]).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn bigint_serde_roundtrip() {
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            seed
+        };
+
+        for _ in 0..32 {
+            let mut words = [0u64; 16];
+            for word in &mut words {
+                *word = next();
+            }
+            let val = u1024(words);
+            let json = ::serde_json::to_string(&val).unwrap();
+            assert_eq!(json, format!("\"{}\"", val));
+            assert_eq!(::serde_json::from_str::<u1024>(&json).unwrap(), val);
+            let bin = ::bincode::serialize(&val).unwrap();
+            assert_eq!(bin.len(), 128);
+            assert_eq!(::bincode::deserialize::<u1024>(&bin).unwrap(), val);
+
+            let val = u512::from_le_slice(&val.to_le_bytes()[..64]).unwrap();
+            let json = ::serde_json::to_string(&val).unwrap();
+            assert_eq!(::serde_json::from_str::<u512>(&json).unwrap(), val);
+            let bin = ::bincode::serialize(&val).unwrap();
+            assert_eq!(bin.len(), 64);
+            assert_eq!(::bincode::deserialize::<u512>(&bin).unwrap(), val);
+        }
+    }
+
+    fn to_u128(val: Uint128) -> u128 {
+        (val.0[1] as u128) << 64 | val.0[0] as u128
+    }
+
+    #[test]
+    fn uint128_matches_u128() {
+        // Simple LCG providing reproducible pseudo-random values; values are
+        // shifted by a random amount to cover all magnitudes
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            let mut val = 0u128;
+            for _ in 0..3 {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                val = val << 64 | seed as u128;
+            }
+            val >> (seed >> 57)
+        };
+        let edge = [
+            0u128,
+            1,
+            2,
+            u64::max_value() as u128,
+            1 << 64,
+            u128::max_value() - 1,
+            u128::max_value(),
+        ];
+
+        let mut values = edge.to_vec();
+        values.extend((0..64).map(|_| next()));
+        for a in values.iter().copied() {
+            for b in values.iter().copied() {
+                let x = Uint128::from(a);
+                let y = Uint128::from(b);
+
+                assert_eq!(x.checked_add(y).map(to_u128), a.checked_add(b));
+                assert_eq!(x.checked_sub(y).map(to_u128), a.checked_sub(b));
+                assert_eq!(x.checked_mul(y).map(to_u128), a.checked_mul(b));
+                assert_eq!(x.checked_div(y).map(to_u128), a.checked_div(b));
+                assert_eq!(x.checked_rem(y).map(to_u128), a.checked_rem(b));
+
+                assert_eq!(to_u128(x.saturating_add(y)), a.saturating_add(b));
+                assert_eq!(to_u128(x.saturating_sub(y)), a.saturating_sub(b));
+                assert_eq!(to_u128(x.saturating_mul(y)), a.saturating_mul(b));
+
+                let (res, flag) = x.overflowing_add(y);
+                assert_eq!((to_u128(res), flag), a.overflowing_add(b));
+                let (res, flag) = x.overflowing_sub(y);
+                assert_eq!((to_u128(res), flag), a.overflowing_sub(b));
+                let (res, flag) = x.overflowing_mul(y);
+                assert_eq!((to_u128(res), flag), a.overflowing_mul(b));
+
+                assert_eq!(to_u128(x.wrapping_add(y)), a.wrapping_add(b));
+                assert_eq!(to_u128(x.wrapping_sub(y)), a.wrapping_sub(b));
+                assert_eq!(to_u128(x.wrapping_mul(y)), a.wrapping_mul(b));
+
+                if b != 0 {
+                    let (res, flag) = x.overflowing_div(y);
+                    assert_eq!((to_u128(res), flag), a.overflowing_div(b));
+                    let (res, flag) = x.overflowing_rem(y);
+                    assert_eq!((to_u128(res), flag), a.overflowing_rem(b));
+                    assert_eq!(to_u128(x.wrapping_div(y)), a.wrapping_div(b));
+                    assert_eq!(to_u128(x.wrapping_rem(y)), a.wrapping_rem(b));
+                }
+            }
+
+            let x = Uint128::from(a);
+            for shift in (0..300).chain(Some(u32::max_value())) {
+                assert_eq!(x.checked_shl(shift).map(to_u128), a.checked_shl(shift));
+                assert_eq!(x.checked_shr(shift).map(to_u128), a.checked_shr(shift));
+                let (res, flag) = x.overflowing_shl(shift);
+                assert_eq!((to_u128(res), flag), a.overflowing_shl(shift));
+                let (res, flag) = x.overflowing_shr(shift);
+                assert_eq!((to_u128(res), flag), a.overflowing_shr(shift));
+                assert_eq!(to_u128(x.wrapping_shl(shift)), a.wrapping_shl(shift));
+                assert_eq!(to_u128(x.wrapping_shr(shift)), a.wrapping_shr(shift));
+            }
+        }
+    }
+
+    #[test]
+    fn bigint_arithmetic_edge_cases() {
+        let top = u256::ONE << 255;
+        let two = u256::from(2u8);
+
+        assert_eq!(u256::BITS, 256);
+        assert_eq!(u512::BITS, 512);
+        assert_eq!(u1024::BITS, 1024);
+        assert_eq!(u256::MIN, u256::ZERO);
+        assert_eq!(u256::MAX, !u256::ZERO);
+
+        assert_eq!(u256::MAX.checked_add(1u8), None);
+        assert_eq!(u256::MAX.checked_add(0u8), Some(u256::MAX));
+        assert_eq!(u256::MAX.overflowing_add(1u8), (u256::ZERO, true));
+        assert_eq!(u256::MAX.wrapping_add(2u8), u256::ONE);
+        assert_eq!(u256::MAX.saturating_add(1u8), u256::MAX);
+        assert_eq!((u256::MAX - 1u8).saturating_add(1u8), u256::MAX);
+
+        assert_eq!(u256::ZERO.checked_sub(1u8), None);
+        assert_eq!(u256::ZERO.overflowing_sub(1u8), (u256::MAX, true));
+        assert_eq!(u256::ZERO.wrapping_sub(1u8), u256::MAX);
+        assert_eq!(u256::ZERO.saturating_sub(1u8), u256::MIN);
+        assert_eq!(u512::ONE.saturating_sub(u512::MAX), u512::MIN);
+
+        assert_eq!(top.checked_mul(2u8), None);
+        assert_eq!(top.overflowing_mul(2u8), (u256::ZERO, true));
+        assert_eq!(top.overflowing_mul(3u8), (top, true));
+        assert_eq!(top.saturating_mul(2u8), u256::MAX);
+        assert_eq!((top >> 1).checked_mul(2u8), Some(top));
+        assert_eq!(u256::MAX.wrapping_mul(u256::MAX), u256::ONE);
+        assert_eq!(u1024::MAX.saturating_mul(u1024::MAX), u1024::MAX);
+
+        assert_eq!(u256::MAX.checked_div(u256::ZERO), None);
+        assert_eq!(u256::MAX.checked_rem(u256::ZERO), None);
+        assert_eq!(u256::MAX.checked_div(top), Some(u256::ONE));
+        assert_eq!(u256::MAX.checked_rem(top), Some(top - 1u8));
+        assert_eq!(u256::MAX.overflowing_div(two), (top - 1u8, false));
+        assert_eq!(u256::MAX.wrapping_rem(two), u256::ONE);
+
+        assert_eq!(u256::ONE.checked_shl(255), Some(top));
+        assert_eq!(u256::ONE.checked_shl(256), None);
+        assert_eq!(u256::ONE.overflowing_shl(256), (u256::ONE, true));
+        assert_eq!(u256::ONE.overflowing_shl(257), (two, true));
+        assert_eq!(u256::ONE.wrapping_shl(256 + 255), top);
+        assert_eq!(u256::ONE.wrapping_shl(u32::max_value()), top);
+        assert_eq!(top.checked_shl(1), Some(u256::ZERO));
+        assert_eq!(top.checked_shr(255), Some(u256::ONE));
+        assert_eq!(top.checked_shr(256), None);
+        assert_eq!(top.overflowing_shr(256), (top, true));
+        assert_eq!(top.wrapping_shr(256 + 255), u256::ONE);
+        assert_eq!(u512::ONE.checked_shl(511), Some(u512::ONE << 511));
+        assert_eq!(u512::ONE.checked_shl(512), None);
+        assert_eq!(u1024::ONE.wrapping_shl(1024), u1024::ONE);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn bigint_wrapping_div_zero() {
+        u256::ONE.wrapping_div(u256::ZERO);
+    }
+
+    macro_rules! check_div_rem {
+        ($name:ident, $a:expr, $b:expr) => {{
+            use num_bigint::BigUint;
+            let (a, b): ($name, $name) = ($a, $b);
+            let big_a = BigUint::from_bytes_le(&a.to_le_bytes());
+            let big_b = BigUint::from_bytes_le(&b.to_le_bytes());
+            let (quot, rem) = a.div_rem(b);
+            assert_eq!(
+                BigUint::from_bytes_le(&quot.to_le_bytes()),
+                &big_a / &big_b,
+                "{:?} / {:?}",
+                a,
+                b
+            );
+            assert_eq!(
+                BigUint::from_bytes_le(&rem.to_le_bytes()),
+                &big_a % &big_b,
+                "{:?} % {:?}",
+                a,
+                b
+            );
+            assert_eq!(a / b, quot);
+            assert_eq!(a % b, rem);
+            assert_eq!(a.checked_div(b), Some(quot));
+            assert_eq!(a.checked_rem(b), Some(rem));
+        }};
+    }
+
+    macro_rules! test_div_rem_random {
+        ($name:ident, $n_words:expr) => {{
+            // Simple LCG providing reproducible pseudo-random values
+            let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+            let mut next = move || {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                seed
+            };
+            let mut random = move || {
+                let mut words = [0u64; $n_words];
+                let len = (next() % $n_words as u64 + 1) as usize;
+                for word in &mut words[..len] {
+                    // Favor patterns with long runs of ones and zeros
+                    *word = match next() % 4 {
+                        0 => u64::max_value(),
+                        1 => next() | 1 << 63,
+                        2 => next() >> (next() % 64),
+                        _ => next(),
+                    };
+                }
+                $name(words)
+            };
+            for _ in 0..2000 {
+                let a = random();
+                let b = random();
+                if b != $name::ZERO {
+                    check_div_rem!($name, a, b);
+                }
+                if a != $name::ZERO {
+                    check_div_rem!($name, b, a);
+                }
+            }
+        }};
+    }
+
+    #[test]
+    fn bigint_div_rem_random() {
+        test_div_rem_random!(u256, 4);
+        test_div_rem_random!(u512, 8);
+        test_div_rem_random!(u1024, 16);
+    }
+
+    #[test]
+    fn bigint_div_rem_knuth_cases() {
+        const MAX: u64 = 0xFFFF_FFFF_FFFF_FFFF;
+        const HIGH: u64 = 0x8000_0000_0000_0000;
+
+        // 64-bit word equivalents of the algorithm D corner cases from
+        // Hacker's Delight, triggering quotient estimate correction and
+        // add-back steps
+        let cases = [
+            ([3, 0, HIGH, 0], [1, 0, 0x2000_0000_0000_0000, 0]),
+            ([0, 0, HIGH, HIGH - 1], [1, 0, HIGH, 0]),
+            ([0, 0, HIGH, HIGH - 1], [MAX, 0, HIGH, 0]),
+            ([0, MAX - 1, 0, HIGH], [MAX, HIGH, 0, 0]),
+            ([0, 0, 0, HIGH - 1], [MAX, HIGH, 0, 0]),
+            ([0, 0, 0, HIGH - 1], [MAX, MAX, HIGH, 0]),
+            ([MAX, MAX, MAX, MAX], [MAX, MAX, 0, 0]),
+            ([MAX, MAX, MAX, MAX], [1, 0, 1, 0]),
+            ([0, 0, 0, 1], [MAX, MAX, MAX, 0]),
+            ([MAX, MAX, MAX, MAX], [MAX, MAX, MAX, MAX]),
+            ([MAX, MAX, MAX, MAX - 1], [MAX, MAX, MAX, MAX]),
+            ([1, 0, 0, 0], [0, 1, 0, 0]),
+            ([0, 0, 1, 0], [1, 1, 0, 0]),
+            ([0, 0, 1, 0], [MAX, 1, 0, 0]),
+        ];
+        for (a, b) in cases.iter() {
+            check_div_rem!(u256, u256(*a), u256(*b));
+        }
+
+        // Single-word divisor path
+        check_div_rem!(u1024, u1024::MAX, u1024::from(3u8));
+        check_div_rem!(u1024, u1024::MAX, u1024::from(MAX));
+        check_div_rem!(u1024, u1024::ONE << 1000, u1024::from(HIGH | 1));
+        check_div_rem!(u256, u256::from(7u8), u256::from(8u8));
+        assert_eq!(u1024::MAX / u1024::MAX, u1024::ONE);
+        assert_eq!(u1024::MAX % u1024::MAX, u1024::ZERO);
+        assert_eq!(u512::ZERO / u512::MAX, u512::ZERO);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to calculate the remainder with a divisor of zero")]
+    fn bigint_rem_zero() {
+        let _ = u512::MAX % u512::ZERO;
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn bigint_div_zero() {
+        let _ = u1024::MAX / u1024::ZERO;
+    }
+
+    macro_rules! test_byte_order {
+        ($name:ident, $n_words:expr) => {{
+            // Simple LCG providing reproducible pseudo-random values
+            let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+            for _ in 0..200 {
+                let mut words = [0u64; $n_words];
+                for word in &mut words {
+                    seed = seed
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    *word = seed;
+                }
+                let val = $name(words);
+                let be = val.to_be_bytes();
+                let le = val.to_le_bytes();
+                let mut rev = le;
+                rev.reverse();
+                assert_eq!(be, rev);
+                assert_eq!($name::from_be_bytes(be), val);
+                assert_eq!($name::from_le_bytes(le), val);
+                assert_eq!($name::from_ne_bytes(val.to_ne_bytes()), val);
+                assert_eq!($name::from_be_slice(&be), Ok(val));
+                assert_eq!($name::from_le_slice(&le), Ok(val));
+                assert_eq!(
+                    $name::from_be_slice(&be[1..]),
+                    Err(ParseLengthError {
+                        actual: $n_words * 8 - 1,
+                        expected: $n_words * 8
+                    })
+                );
+                assert_eq!(
+                    $name::from_le_slice(&[0u8; $n_words * 8 + 1]),
+                    Err(ParseLengthError {
+                        actual: $n_words * 8 + 1,
+                        expected: $n_words * 8
+                    })
+                );
+            }
+        }};
+    }
+
+    #[test]
+    fn bigint_byte_order() {
+        test_byte_order!(u256, 4);
+        test_byte_order!(u512, 8);
+        test_byte_order!(u1024, 16);
+
+        // 2^255 + 2^64 * 0x0102 + 0x0a0b0c0d
+        let val = (u256::ONE << 255) + (u256::from(0x0102u16) << 64) + 0x0a0b0c0du32;
+        let mut be = [0u8; 32];
+        be[0] = 0x80;
+        be[22] = 0x01;
+        be[23] = 0x02;
+        be[28..].copy_from_slice(&[0x0a, 0x0b, 0x0c, 0x0d]);
+        let mut le = be;
+        le.reverse();
+        assert_eq!(val.to_be_bytes(), be);
+        assert_eq!(val.to_le_bytes(), le);
+        assert_eq!(u256::from_be_bytes(be), val);
+        assert_eq!(u256::from_le_bytes(le), val);
+        #[cfg(target_endian = "little")]
+        assert_eq!(val.to_ne_bytes(), le);
+        #[cfg(target_endian = "big")]
+        assert_eq!(val.to_ne_bytes(), be);
+    }
+
+    macro_rules! test_str_random {
+        ($name:ident, $n_words:expr) => {{
+            use num_bigint::BigUint;
+            // Simple LCG providing reproducible pseudo-random values
+            let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+            for _ in 0..300 {
+                let mut words = [0u64; $n_words];
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                let len = (seed % $n_words as u64 + 1) as usize;
+                for word in &mut words[..len] {
+                    seed = seed
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    *word = seed >> (seed % 64);
+                }
+                let val = $name(words);
+                let big = BigUint::from_bytes_le(&val.to_le_bytes());
+                let dec = val.to_string();
+                assert_eq!(dec, big.to_string());
+                assert_eq!(dec.parse::<$name>(), Ok(val));
+                assert_eq!(format!("{:x}", val), format!("{:x}", big));
+                assert_eq!(format!("{:X}", val), format!("{:X}", big));
+                assert_eq!(format!("{:o}", val), format!("{:o}", big));
+                assert_eq!(format!("{:b}", val), format!("{:b}", big));
+                assert_eq!(format!("{:#x}", val).parse::<$name>(), Ok(val));
+                assert_eq!(format!("0X{:X}", val).parse::<$name>(), Ok(val));
+            }
+
+            let max = $name::MAX.to_string();
+            assert_eq!(
+                max,
+                ((BigUint::from(1u8) << ($n_words * 64usize)) - 1u8).to_string()
+            );
+            assert_eq!(max.parse::<$name>(), Ok($name::MAX));
+            let over = (BigUint::from(1u8) << ($n_words * 64usize)).to_string();
+            assert_eq!(over.parse::<$name>(), Err(ParseIntError::Overflow));
+            let over = format!("0x1{}", "0".repeat($n_words * 16));
+            assert_eq!(over.parse::<$name>(), Err(ParseIntError::Overflow));
+            let padded = format!("0x{}{:x}", "0".repeat(100), $name::MAX);
+            assert_eq!(padded.parse::<$name>(), Ok($name::MAX));
+        }};
     }
 
     #[cfg(feature = "alloc")]
     #[test]
-    fn fmt_hex() {
-        let one = u256::ONE;
-        let mut u_256 = u256([
-            0x0000000000000000,
-            0xAAAAAAAABBBBBBBB,
-            0x0000000111122222,
-            0x0000000000000000,
-        ]);
+    fn bigint_str_random() {
+        test_str_random!(u256, 4);
+        test_str_random!(u512, 8);
+        test_str_random!(u1024, 16);
+    }
 
-        // UpperHex
+    #[test]
+    fn bigint_from_str() {
+        assert_eq!("0".parse(), Ok(u256::ZERO));
+        assert_eq!("0x0".parse(), Ok(u256::ZERO));
+        assert_eq!("000123".parse(), Ok(u256::from(123u8)));
+        assert_eq!("0xdeadBEEF".parse(), Ok(u256::from(0xDEADBEEFu32)));
+        assert_eq!("0Xff".parse(), Ok(u256::from(0xFFu8)));
+        assert_eq!("".parse::<u256>(), Err(ParseIntError::Empty));
+        assert_eq!("0x".parse::<u256>(), Err(ParseIntError::Empty));
         assert_eq!(
-            format!("{:X}", u_256),
-            "111122222AAAAAAAABBBBBBBB0000000000000000"
+            "+1".parse::<u256>(),
+            Err(ParseIntError::InvalidDigit('+', 0))
         );
         assert_eq!(
-            format!("{:#X}", u_256),
-            "0x111122222AAAAAAAABBBBBBBB0000000000000000"
+            "-1".parse::<u256>(),
+            Err(ParseIntError::InvalidDigit('-', 0))
         );
-        assert_eq!(format!("{:X}", u256::ZERO), "0");
-        assert_eq!(format!("{:05X}", one), "00001");
-        assert_eq!(format!("{:#05X}", one), "0x001");
-        assert_eq!(format!("{:5X}", one), "1    ");
-        assert_eq!(format!("{:#5X}", one), "0x1  ");
-        assert_eq!(format!("{:w^#7X}", one), "ww0x1ww");
-
-        // LowerHex
         assert_eq!(
-            format!("{:x}", u_256),
-            "111122222aaaaaaaabbbbbbbb0000000000000000"
+            "1_000".parse::<u256>(),
+            Err(ParseIntError::InvalidDigit('_', 1))
         );
         assert_eq!(
-            format!("{:#x}", u_256),
-            "0x111122222aaaaaaaabbbbbbbb0000000000000000"
+            " 1".parse::<u256>(),
+            Err(ParseIntError::InvalidDigit(' ', 0))
         );
-        assert_eq!(format!("{:x}", u256::ZERO), "0");
-        assert_eq!(format!("{:05x}", one), "00001");
-        assert_eq!(format!("{:#05x}", one), "0x001");
-        assert_eq!(format!("{:5x}", one), "1    ");
-        assert_eq!(format!("{:#5x}", one), "0x1  ");
-        assert_eq!(format!("{:w^#7x}", one), "ww0x1ww");
-    }
-
-    #[cfg(feature = "alloc")]
-    #[test]
-    fn fmt_octal() {
-        let one = u256::ONE;
-        let mut u_256 = u256([
-            0o0000000000000000000000,
-            0o0011222222222222222222,
-            0o0000000001111111111111,
-            0o0000000000000000000000,
-        ]);
-
         assert_eq!(
-            format!("{:o}", u_256),
-            "111111111111100112222222222222222220000000000000000000000"
+            "ff".parse::<u256>(),
+            Err(ParseIntError::InvalidDigit('f', 0))
         );
         assert_eq!(
-            format!("{:#o}", u_256),
-            "0o111111111111100112222222222222222220000000000000000000000"
+            "0x12g".parse::<u256>(),
+            Err(ParseIntError::InvalidDigit('g', 4))
         );
-        assert_eq!(format!("{:o}", u256::ZERO), "0");
-        assert_eq!(format!("{:05o}", one), "00001");
-        assert_eq!(format!("{:#05o}", one), "0o001");
-        assert_eq!(format!("{:5o}", one), "1    ");
-        assert_eq!(format!("{:#5o}", one), "0o1  ");
-        assert_eq!(format!("{:w^#7o}", one), "ww0o1ww");
-    }
-
-    #[cfg(feature = "alloc")]
-    #[test]
-    fn fmt_binary() {
-        let one = u256::ONE;
-        let mut u_256 = u256([
-            0b0000000000000000000000000000000000000000000000000000000000000000,
-            0b0001111000011110001111000011110001111000011110001111000011110000,
-            0b0000000000000000000000000000001111111111111111111111111111111111,
-            0b0000000000000000000000000000000000000000000000000000000000000000,
-        ]);
-
         assert_eq!(
-            format!("{:b}", u_256),
-            "111111111111111111111111111111111100011110000111100011110000111100011110000111100011110000111100000000000000000000000000000000000000000000000000000000000000000000"
+            "0x0x1".parse::<u256>(),
+            Err(ParseIntError::InvalidDigit('x', 3))
         );
         assert_eq!(
-            format!("{:#b}", u_256),
-            "0b111111111111111111111111111111111100011110000111100011110000111100011110000111100011110000111100000000000000000000000000000000000000000000000000000000000000000000"
+            "12«".parse::<u256>(),
+            Err(ParseIntError::InvalidDigit('«', 2))
+        );
+        assert_eq!(
+            ParseIntError::InvalidDigit('_', 1).to_string(),
+            "Invalid digit '_' at position 1"
         );
-        assert_eq!(format!("{:b}", u256::ZERO), "0");
-        assert_eq!(format!("{:05b}", one), "00001");
-        assert_eq!(format!("{:#05b}", one), "0b001");
-        assert_eq!(format!("{:5b}", one), "1    ");
-        assert_eq!(format!("{:#5b}", one), "0b1  ");
-        assert_eq!(format!("{:w^#7b}", one), "ww0b1ww");
+    }
+
+    fn to_i128(val: Int128) -> i128 {
+        to_u128(val.to_bits()) as i128
     }
 
     #[test]
-    fn u256_comp_test() {
-        let small = u256([10u64, 0, 0, 0]);
-        let big = u256([0x8C8C3EE70C644118u64, 0x0209E7378231E632, 0, 0]);
-        let bigger = u256([0x9C8C3EE70C644118u64, 0x0209E7378231E632, 0, 0]);
-        let biggest = u256([0x5C8C3EE70C644118u64, 0x0209E7378231E632, 0, 1]);
+    fn int128_matches_i128() {
+        // Simple LCG providing reproducible pseudo-random values; values are
+        // shifted by a random amount to cover all magnitudes
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            let mut val = 0u128;
+            for _ in 0..3 {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                val = val << 64 | seed as u128;
+            }
+            (val as i128) >> (seed >> 57)
+        };
+        let edge = [
+            0i128,
+            1,
+            -1,
+            2,
+            -2,
+            i64::max_value() as i128,
+            i64::min_value() as i128,
+            i128::max_value(),
+            i128::max_value() - 1,
+            i128::min_value(),
+            i128::min_value() + 1,
+        ];
 
-        assert!(small < big);
-        assert!(big < bigger);
-        assert!(bigger < biggest);
-        assert!(bigger <= biggest);
-        assert!(biggest <= biggest);
-        assert!(bigger >= big);
-        assert!(bigger >= small);
-        assert!(small <= small);
+        let mut values = edge.to_vec();
+        values.extend((0..48).map(|_| next()));
+        for a in values.iter().copied() {
+            let x = Int128::from(a);
+            assert_eq!(to_i128(x), a);
+            assert_eq!(x.is_negative(), a.is_negative());
+            assert_eq!(x.is_positive(), a.is_positive());
+            assert_eq!(to_i128(x.signum()), a.signum());
+            assert_eq!(to_u128(x.unsigned_abs()), a.wrapping_abs() as u128);
+            assert_eq!(x.checked_abs().map(to_i128), a.checked_abs());
+            assert_eq!(to_i128(x.saturating_abs()), a.saturating_abs());
+            assert_eq!(to_i128(x.wrapping_abs()), a.wrapping_abs());
+            let (res, flag) = x.overflowing_abs();
+            assert_eq!((to_i128(res), flag), a.overflowing_abs());
+            assert_eq!(x.checked_neg().map(to_i128), a.checked_neg());
+            assert_eq!(to_i128(x.saturating_neg()), a.saturating_neg());
+            assert_eq!(to_i128(x.wrapping_neg()), a.wrapping_neg());
+            let (res, flag) = x.overflowing_neg();
+            assert_eq!((to_i128(res), flag), a.overflowing_neg());
+            assert_eq!(to_i128(!x), !a);
+            assert_eq!(x.to_string(), a.to_string());
+            assert_eq!(format!("{:?}", x), format!("{:?}", a));
+            assert_eq!(format!("{:+08}", x), format!("{:+08}", a));
+            assert_eq!(format!("{:>45}", x), format!("{:>45}", a));
+            assert_eq!(a.to_string().parse::<Int128>(), Ok(x));
+
+            for b in values.iter().copied() {
+                let y = Int128::from(b);
+
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+
+                assert_eq!(x.checked_add(y).map(to_i128), a.checked_add(b));
+                assert_eq!(x.checked_sub(y).map(to_i128), a.checked_sub(b));
+                assert_eq!(x.checked_mul(y).map(to_i128), a.checked_mul(b));
+                assert_eq!(x.checked_div(y).map(to_i128), a.checked_div(b));
+                assert_eq!(x.checked_rem(y).map(to_i128), a.checked_rem(b));
+
+                assert_eq!(to_i128(x.saturating_add(y)), a.saturating_add(b));
+                assert_eq!(to_i128(x.saturating_sub(y)), a.saturating_sub(b));
+                assert_eq!(to_i128(x.saturating_mul(y)), a.saturating_mul(b));
+
+                let (res, flag) = x.overflowing_add(y);
+                assert_eq!((to_i128(res), flag), a.overflowing_add(b));
+                let (res, flag) = x.overflowing_sub(y);
+                assert_eq!((to_i128(res), flag), a.overflowing_sub(b));
+                let (res, flag) = x.overflowing_mul(y);
+                assert_eq!((to_i128(res), flag), a.overflowing_mul(b));
+
+                assert_eq!(to_i128(x.wrapping_add(y)), a.wrapping_add(b));
+                assert_eq!(to_i128(x.wrapping_sub(y)), a.wrapping_sub(b));
+                assert_eq!(to_i128(x.wrapping_mul(y)), a.wrapping_mul(b));
+
+                if b != 0 {
+                    let (res, flag) = x.overflowing_div(y);
+                    assert_eq!((to_i128(res), flag), a.overflowing_div(b));
+                    let (res, flag) = x.overflowing_rem(y);
+                    assert_eq!((to_i128(res), flag), a.overflowing_rem(b));
+                    assert_eq!(to_i128(x.wrapping_div(y)), a.wrapping_div(b));
+                    assert_eq!(to_i128(x.wrapping_rem(y)), a.wrapping_rem(b));
+                }
+            }
+
+            for shift in (0..300).chain(Some(u32::max_value())) {
+                assert_eq!(x.checked_shl(shift).map(to_i128), a.checked_shl(shift));
+                assert_eq!(x.checked_shr(shift).map(to_i128), a.checked_shr(shift));
+                let (res, flag) = x.overflowing_shl(shift);
+                assert_eq!((to_i128(res), flag), a.overflowing_shl(shift));
+                let (res, flag) = x.overflowing_shr(shift);
+                assert_eq!((to_i128(res), flag), a.overflowing_shr(shift));
+                assert_eq!(to_i128(x.wrapping_shl(shift)), a.wrapping_shl(shift));
+                assert_eq!(to_i128(x.wrapping_shr(shift)), a.wrapping_shr(shift));
+            }
+        }
     }
 
     #[test]
-    fn uint_from_be_bytes() {
+    fn signed_bigint_edge_cases() {
+        use core::convert::TryFrom;
+
+        assert_eq!(i256::BITS, 256);
+        assert_eq!(i256::MIN.to_bits(), u256::ONE << 255);
+        assert_eq!(i256::MAX.to_bits(), (u256::ONE << 255) - 1u8);
+        assert_eq!(i256::MINUS_ONE.to_bits(), u256::MAX);
+        assert_eq!(i256::from(-1i8), i256::MINUS_ONE);
+        assert_eq!(i1024::from(-5i64) + 7u8, i1024::from(2u8));
+        assert_eq!(-i512::from(42u8), i512::from(-42i32));
+
+        assert!(i256::MIN < i256::MINUS_ONE);
+        assert!(i256::MINUS_ONE < i256::ZERO);
+        assert!(i256::ZERO < i256::MAX);
+
+        assert_eq!(i256::MIN.checked_abs(), None);
+        assert_eq!(i256::MIN.overflowing_abs(), (i256::MIN, true));
+        assert_eq!(i256::MIN.unsigned_abs(), u256::ONE << 255);
+        assert_eq!(i256::MIN.overflowing_div(-1), (i256::MIN, true));
+        assert_eq!(i256::MIN.overflowing_rem(-1), (i256::ZERO, true));
+        assert_eq!(i256::MIN.checked_div(-1), None);
+        assert_eq!(i256::MIN.checked_div(0), None);
+        assert_eq!(i256::MIN.saturating_mul(-1), i256::MAX);
+        assert_eq!(i256::MAX.saturating_add(1), i256::MAX);
+        assert_eq!(i256::MIN.saturating_sub(1), i256::MIN);
+        assert_eq!(i256::MIN >> 255, i256::MINUS_ONE);
+        assert_eq!(i256::MIN >> 300, i256::MINUS_ONE);
+        assert_eq!(i256::MAX >> 254, i256::ONE);
+        assert_eq!(i256::MIN.wrapping_shr(256 + 254), i256::from(-2));
+        assert_eq!(i256::ONE.checked_shl(255), Some(i256::MIN));
+
+        assert_eq!(u256::try_from(i256::MAX), Ok(i256::MAX.to_bits()));
+        assert_eq!(u256::try_from(i256::MINUS_ONE), Err(TryFromIntError));
+        assert_eq!(i256::try_from(u256::MAX >> 1), Ok(i256::MAX));
+        assert_eq!(i256::try_from(u256::MAX), Err(TryFromIntError));
+        assert_eq!(i256::from_bits(u256::MAX), i256::MINUS_ONE);
+    }
+
+    #[test]
+    fn signed_bigint_str() {
+        let min = "-57896044618658097711785492504343953926634992332820282019728792003956564819968";
+        let max = "57896044618658097711785492504343953926634992332820282019728792003956564819967";
+        assert_eq!(i256::MIN.to_string(), min);
+        assert_eq!(i256::MAX.to_string(), max);
+        assert_eq!(min.parse(), Ok(i256::MIN));
+        assert_eq!(max.parse(), Ok(i256::MAX));
         assert_eq!(
-            Uint128::from_be_bytes([
-                0x1b, 0xad, 0xca, 0xfe, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xaf, 0xba, 0xbe, 0x2b, 0xed,
-                0xfe, 0xed
-            ]),
-            Uint128([0xdeafbabe2bedfeed, 0x1badcafedeadbeef])
+            "-57896044618658097711785492504343953926634992332820282019728792003956564819969"
+                .parse::<i256>(),
+            Err(ParseIntError::Overflow)
         );
-
         assert_eq!(
-            u256::from_be_bytes([
-                0x1b, 0xad, 0xca, 0xfe, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xaf, 0xba, 0xbe, 0x2b, 0xed,
-                0xfe, 0xed, 0xba, 0xad, 0xf0, 0x0d, 0xde, 0xfa, 0xce, 0xda, 0x11, 0xfe, 0xd2, 0xba,
-                0xd1, 0xc0, 0xff, 0xe0
-            ]),
-            u256([
-                0x11fed2bad1c0ffe0,
-                0xbaadf00ddefaceda,
-                0xdeafbabe2bedfeed,
-                0x1badcafedeadbeef
-            ])
+            "57896044618658097711785492504343953926634992332820282019728792003956564819968"
+                .parse::<i256>(),
+            Err(ParseIntError::Overflow)
         );
-    }
-
-    #[test]
-    fn uint_from_le_bytes() {
-        let mut be = [
-            0x1b, 0xad, 0xca, 0xfe, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xaf, 0xba, 0xbe, 0x2b, 0xed,
-            0xfe, 0xed,
-        ];
-        be.reverse();
+        assert_eq!("-0x10".parse(), Ok(i256::from(-16)));
+        assert_eq!("-0".parse(), Ok(i256::ZERO));
+        assert_eq!("-".parse::<i256>(), Err(ParseIntError::Empty));
         assert_eq!(
-            Uint128::from_le_bytes(be),
-            Uint128([0xdeafbabe2bedfeed, 0x1badcafedeadbeef])
+            "--1".parse::<i256>(),
+            Err(ParseIntError::InvalidDigit('-', 1))
         );
-
-        let mut be = [
-            0x1b, 0xad, 0xca, 0xfe, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xaf, 0xba, 0xbe, 0x2b, 0xed,
-            0xfe, 0xed, 0xba, 0xad, 0xf0, 0x0d, 0xde, 0xfa, 0xce, 0xda, 0x11, 0xfe, 0xd2, 0xba,
-            0xd1, 0xc0, 0xff, 0xe0,
-        ];
-        be.reverse();
         assert_eq!(
-            u256::from_le_bytes(be),
-            u256([
-                0x11fed2bad1c0ffe0,
-                0xbaadf00ddefaceda,
-                0xdeafbabe2bedfeed,
-                0x1badcafedeadbeef
-            ])
+            "+1".parse::<i256>(),
+            Err(ParseIntError::InvalidDigit('+', 0))
+        );
+        assert_eq!(
+            "-12a".parse::<i256>(),
+            Err(ParseIntError::InvalidDigit('a', 3))
         );
+        assert_eq!(format!("{:06}", i256::from(-42)), "-00042");
+        assert_eq!(format!("{:?}", i1024::from(-42)), "-42");
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
-    fn uint_to_be_bytes() {
+    fn signed_bigint_fmt_radix() {
+        assert_eq!(format!("{:x}", i256::MINUS_ONE), "f".repeat(64));
         assert_eq!(
-            Uint128([0xdeafbabe2bedfeed, 0x1badcafedeadbeef]).to_be_bytes(),
-            [
-                0x1b, 0xad, 0xca, 0xfe, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xaf, 0xba, 0xbe, 0x2b, 0xed,
-                0xfe, 0xed
-            ]
+            format!("{:#X}", i256::from(-2)),
+            format!("0x{}E", "F".repeat(63))
         );
+        assert_eq!(format!("{:o}", i256::from(8)), "10");
+        assert_eq!(format!("{:b}", i256::from(5)), "101");
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to negate with overflow")]
+    fn signed_bigint_min_abs() {
+        i256::MIN.abs();
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to negate with overflow")]
+    fn signed_bigint_min_neg() {
+        let _ = -i512::MIN;
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide with overflow")]
+    fn signed_bigint_min_div() {
+        let _ = i256::MIN / -1;
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to calculate the remainder with overflow")]
+    #[allow(clippy::modulo_one)]
+    fn signed_bigint_min_rem() {
+        let _ = i1024::MIN % -1;
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn signed_bigint_div_zero() {
+        let _ = i256::ONE / 0;
+    }
+
+    #[test]
+    fn bigint_bit_ops() {
+        use num_bigint::BigUint;
+
+        fn to_big(val: u256) -> BigUint {
+            BigUint::from_bytes_le(&val.to_le_bytes())
+        }
+
+        // Simple LCG providing reproducible pseudo-random values
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut values = vec![
+            u256::ZERO,
+            u256::ONE,
+            u256::MAX,
+            u256::ONE << 63,
+            u256::ONE << 64,
+            u256::ONE << 255,
+            u256::MAX >> 1,
+            u256::from(::core::u64::MAX),
+        ];
+        for _ in 0..64 {
+            let mut words = [0u64; 4];
+            for word in &mut words {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                *word = seed.checked_shr((seed % 128) as u32).unwrap_or(0);
+            }
+            values.push(u256(words));
+        }
+
+        let mask = to_big(u256::MAX);
+        for val in values {
+            let big = to_big(val);
+            let bits = (0..256).map(|i| val.bit(i)).collect::<Vec<_>>();
+
+            let ones = bits.iter().filter(|bit| **bit).count() as u32;
+            assert_eq!(val.count_ones(), ones);
+            assert_eq!(val.count_zeros(), 256 - ones);
+            let leading = bits.iter().rev().take_while(|bit| !**bit).count() as u32;
+            assert_eq!(val.leading_zeros(), leading);
+            let trailing = bits.iter().take_while(|bit| !**bit).count() as u32;
+            assert_eq!(val.trailing_zeros(), trailing);
+            assert_eq!(val.is_power_of_two(), ones == 1);
+            assert_eq!(val.checked_ilog2(), (big.bits() as u32).checked_sub(1));
+
+            let mut copy = u256::ZERO;
+            for (index, bit) in bits.iter().enumerate() {
+                copy.set_bit(index, *bit);
+            }
+            assert_eq!(copy, val);
+            let mut copy = u256::MAX;
+            for (index, bit) in bits.iter().enumerate() {
+                copy.set_bit(index, *bit);
+            }
+            assert_eq!(copy, val);
+
+            for shift in &[0u32, 1, 63, 64, 65, 127, 128, 129, 191, 192, 255, 256, 300] {
+                let shift = *shift;
+                assert_eq!(
+                    to_big(val << shift as usize),
+                    (&big << shift as usize) & &mask,
+                    "{:?} << {}",
+                    val,
+                    shift
+                );
+                assert_eq!(
+                    to_big(val >> shift as usize),
+                    &big >> shift as usize,
+                    "{:?} >> {}",
+                    val,
+                    shift
+                );
+
+                let n = shift as usize % 256;
+                let rotated = (0..256).fold(u256::ZERO, |mut acc, i| {
+                    acc.set_bit((i + n) % 256, bits[i]);
+                    acc
+                });
+                assert_eq!(val.rotate_left(shift), rotated);
+                assert_eq!(rotated.rotate_right(shift), val);
+            }
 
-        assert_eq!(
-            u256([
-                0x11fed2bad1c0ffe0,
-                0xbaadf00ddefaceda,
-                0xdeafbabe2bedfeed,
-                0x1badcafedeadbeef
-            ])
-            .to_be_bytes(),
-            [
-                0x1b, 0xad, 0xca, 0xfe, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xaf, 0xba, 0xbe, 0x2b, 0xed,
-                0xfe, 0xed, 0xba, 0xad, 0xf0, 0x0d, 0xde, 0xfa, 0xce, 0xda, 0x11, 0xfe, 0xd2, 0xba,
-                0xd1, 0xc0, 0xff, 0xe0
-            ]
-        );
+            match val.checked_next_power_of_two() {
+                Some(pow) => {
+                    assert!(pow.is_power_of_two());
+                    assert!(pow >= val);
+                    assert!(pow == u256::ONE || (pow >> 1) < val);
+                }
+                None => assert!(val > u256::ONE << 255),
+            }
+        }
+
+        assert_eq!(u256::ZERO.next_power_of_two(), u256::ONE);
+        assert_eq!(u256::from(3u8).next_power_of_two(), u256::from(4u8));
+        assert_eq!((u256::ONE << 255).next_power_of_two(), u256::ONE << 255);
+        assert_eq!(((u256::ONE << 255) + 1u8).checked_next_power_of_two(), None);
+        assert_eq!((u256::ONE << 200).ilog2(), 200);
+        assert_eq!(u1024::MAX.ilog2(), 1023);
+        assert_eq!(u256::ZERO.checked_ilog2(), None);
+        assert_eq!(u512::ONE.rotate_right(1), u512::ONE << 511);
+        assert_eq!(u512::ZERO.leading_zeros(), 512);
+        assert_eq!(u512::ZERO.trailing_zeros(), 512);
     }
 
     #[test]
-    fn uint_to_le_bytes() {
-        assert_eq!(
-            Uint128([0xdeafbabe2bedfeed, 0x1badcafedeadbeef]).to_le_bytes(),
-            [
-                0xed, 0xfe, 0xed, 0x2b, 0xbe, 0xba, 0xaf, 0xde, 0xef, 0xbe, 0xad, 0xde, 0xfe, 0xca,
-                0xad, 0x1b
-            ]
-        );
+    #[should_panic(expected = "argument of integer logarithm must be positive")]
+    fn bigint_ilog2_zero() {
+        u256::ZERO.ilog2();
+    }
 
+    #[test]
+    fn bigint_width_conversions() {
+        use core::convert::TryFrom;
+
+        let max_u128 = u256::from(::core::u128::MAX);
+        let above_u128 = max_u128 + 1u8;
+        assert!(max_u128.fits_u128());
+        assert!(!above_u128.fits_u128());
+        assert!(!max_u128.fits_u64());
+        assert_eq!(u128::try_from(max_u128), Ok(::core::u128::MAX));
+        assert_eq!(u128::try_from(above_u128), Err(TryFromIntError));
+        assert_eq!(above_u128.low_u128(), 0);
+        assert_eq!(max_u128.low_u128(), ::core::u128::MAX);
+        assert_eq!(max_u128.low_u64(), ::core::u64::MAX);
+
+        let max_u64 = u512::from(::core::u64::MAX);
+        assert!(max_u64.fits_u64());
+        assert!(!(max_u64 + 1u8).fits_u64());
+        assert_eq!(u64::try_from(max_u64), Ok(::core::u64::MAX));
+        assert_eq!(u64::try_from(max_u64 + 1u8), Err(TryFromIntError));
         assert_eq!(
-            u256([
-                0x11fed2bad1c0ffe0,
-                0xbaadf00ddefaceda,
-                0xdeafbabe2bedfeed,
-                0x1badcafedeadbeef
-            ])
-            .to_le_bytes(),
-            [
-                0xe0, 0xff, 0xc0, 0xd1, 0xba, 0xd2, 0xfe, 0x11, 0xda, 0xce, 0xfa, 0xde, 0x0d, 0xf0,
-                0xad, 0xba, 0xed, 0xfe, 0xed, 0x2b, 0xbe, 0xba, 0xaf, 0xde, 0xef, 0xbe, 0xad, 0xde,
-                0xfe, 0xca, 0xad, 0x1b,
-            ]
+            u32::try_from(u1024::from(::core::u32::MAX)),
+            Ok(::core::u32::MAX)
         );
+        assert_eq!(u32::try_from(u1024::from(1u64 << 32)), Err(TryFromIntError));
+        assert_eq!(u16::try_from(u256::from(0xFFFFu32)), Ok(0xFFFF));
+        assert_eq!(u16::try_from(u256::from(0x10000u32)), Err(TryFromIntError));
+        assert_eq!(u8::try_from(u256::from(0xFFu8)), Ok(0xFF));
+        assert_eq!(u8::try_from(u256::from(0x100u16)), Err(TryFromIntError));
+        assert_eq!(u8::try_from(u256::ONE << 128), Err(TryFromIntError));
+
+        assert_eq!(u512::from(u256::MAX), (u512::ONE << 256) - 1u8);
+        assert_eq!(u1024::from(u256::MAX), (u1024::ONE << 256) - 1u8);
+        assert_eq!(u1024::from(u512::MAX), (u1024::ONE << 512) - 1u8);
+        assert_eq!(u256::try_from(u512::from(u256::MAX)), Ok(u256::MAX));
+        assert_eq!(u256::try_from(u512::ONE << 256), Err(TryFromIntError));
+        assert_eq!(u256::try_from(u1024::from(u256::MAX)), Ok(u256::MAX));
+        assert_eq!(u256::try_from(u1024::ONE << 256), Err(TryFromIntError));
+        assert_eq!(u256::try_from(u1024::ONE << 1023), Err(TryFromIntError));
+        assert_eq!(u512::try_from(u1024::from(u512::MAX)), Ok(u512::MAX));
+        assert_eq!(u512::try_from(u1024::ONE << 512), Err(TryFromIntError));
+        assert_eq!(u512::try_from(u1024::ZERO), Ok(u512::ZERO));
     }
 
     #[test]
-    fn u256_div_rem_checked() {
-        let zero = u256::ZERO;
-        let number_one = u256::from(0xDEADBEEFu64);
-        let number_two = u256::from(::core::u64::MAX);
-        let one_div_rem_two = (
-            u256::from(::core::u64::MAX / 0xDEADBEEFu64),
-            u256::from(::core::u64::MAX % 0xDEADBEEFu64),
-        );
-        let max = u256::MAX;
-
-        // Division by zero gets not panic and gets None
-        assert_eq!(u256::div_rem_checked(max, zero), None);
-        assert_eq!(u256::div_rem_checked(number_two, zero), None);
-        assert_eq!(u256::div_rem_checked(number_one, zero), None);
+    fn bigint_pow_matches_u128() {
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            seed
+        };
 
-        // Division of zero gets Zero
-        assert_eq!(u256::div_rem_checked(zero, max), Some((zero, zero)));
-        assert_eq!(u256::div_rem_checked(zero, number_two), Some((zero, zero)));
-        assert_eq!(u256::div_rem_checked(zero, number_one), Some((zero, zero)));
+        for _ in 0..2000 {
+            let shift = next() % 128;
+            let a = ((next() as u128) << 64 | next() as u128) >> shift;
+            let b = ((next() as u128) << 64 | next() as u128) >> (next() % 128);
+            let x = Uint128::from(a);
+            let y = Uint128::from(b);
+            let exp = (next() % 140) as u32;
+
+            let (res, flag) = x.overflowing_pow(exp);
+            assert_eq!(
+                (to_u128(res), flag),
+                a.overflowing_pow(exp),
+                "{}^{}",
+                a,
+                exp
+            );
+            assert_eq!(x.checked_pow(exp).map(to_u128), a.checked_pow(exp));
+            assert_eq!(to_u128(x.wrapping_pow(exp)), a.wrapping_pow(exp));
+            if let Some(pow) = a.checked_pow(exp) {
+                assert_eq!(to_u128(x.pow(exp)), pow);
+            }
 
-        // Division by another than zero not gets None
-        assert_ne!(u256::div_rem_checked(max, number_one), None);
-        assert_ne!(u256::div_rem_checked(number_two, number_one), None);
+            assert_eq!(to_u128(x.abs_diff(y)), a.max(b) - a.min(b));
+            assert_eq!(to_u128(x.midpoint(y)), a / 2 + b / 2 + (a & b & 1));
+
+            let next_multiple = match a.checked_rem(b) {
+                None => None,
+                Some(0) => Some(a),
+                Some(rem) => a.checked_add(b - rem),
+            };
+            assert_eq!(x.checked_next_multiple_of(y).map(to_u128), next_multiple);
+
+            let root = to_u128(x.isqrt());
+            assert!(root * root <= a);
+            assert!(root
+                .checked_add(1)
+                .and_then(|r| r.checked_mul(r))
+                .map(|sq| sq > a)
+                .unwrap_or(true));
+        }
 
-        // In u256 division gets the same as in u64
+        assert_eq!(u256::from(3u8).pow(161), u256::from(3u8).wrapping_pow(161));
+        assert_eq!(u256::from(3u8).checked_pow(162), None);
+        assert_eq!(u256::from(2u8).overflowing_pow(256), (u256::ZERO, true));
+        assert_eq!(u256::ZERO.pow(0), u256::ONE);
+        assert_eq!(u256::MAX.checked_next_multiple_of(2u8), None);
         assert_eq!(
-            u256::div_rem_checked(number_two, number_one),
-            Some(one_div_rem_two)
+            u256::MAX.checked_next_multiple_of(u256::MAX),
+            Some(u256::MAX)
         );
+        assert_eq!(u256::MAX.midpoint(u256::MAX), u256::MAX);
+        assert_eq!(u256::MAX.midpoint(u256::MAX - 1u8), u256::MAX - 1u8);
+        assert_eq!(u256::ZERO.abs_diff(u256::MAX), u256::MAX);
     }
 
     #[test]
-    fn u256_div_rem() {
-        let zero = u256::ZERO;
-        let number_one = u256::from(0xDEADBEEFu64);
-        let number_two = u256::from(::core::u64::MAX);
-        let max = u256::MAX;
-
-        let result1 = std::panic::catch_unwind(|| u256::div_rem(max, zero));
-        assert!(result1.is_err());
-        let result2 = std::panic::catch_unwind(|| u256::div_rem(number_one, zero));
-        assert!(result2.is_err());
-        let result3 = std::panic::catch_unwind(|| u256::div_rem(number_two, zero));
-        assert!(result3.is_err());
+    #[should_panic(expected = "attempt to multiply with overflow")]
+    fn bigint_pow_overflow() {
+        u256::from(2u8).pow(256);
     }
 
     #[test]
-    fn bigint_min_max() {
-        assert_eq!(u256::MIN.as_inner(), &[0u64; 4]);
-        assert_eq!(u512::MIN.as_inner(), &[0u64; 8]);
-        assert_eq!(u1024::MIN.as_inner(), &[0u64; 16]);
-        assert_eq!(u256::MAX.as_inner(), &[::core::u64::MAX; 4]);
-        assert_eq!(u512::MAX.as_inner(), &[::core::u64::MAX; 8]);
-        assert_eq!(u1024::MAX.as_inner(), &[::core::u64::MAX; 16]);
-        assert_eq!(u256::BITS, 4 * 64);
-        assert_eq!(u512::BITS, 8 * 64);
-        assert_eq!(u1024::BITS, 16 * 64);
+    fn bigint_isqrt() {
+        use num_bigint::BigUint;
+
+        for n in 0u64..1000 {
+            let root = (n as f64).sqrt() as u64;
+            assert_eq!(u256::from(n).isqrt(), u256::from(root));
+        }
+        for root in &[
+            u256::from(2u8),
+            u256::from(::core::u64::MAX),
+            u256::from(::core::u128::MAX),
+            (u256::ONE << 127) + 12345u64,
+        ] {
+            let square = *root * *root;
+            assert_eq!(square.isqrt(), *root);
+            assert_eq!((square - 1u8).isqrt(), *root - 1u8);
+            assert_eq!((square + 1u8).isqrt(), *root);
+        }
+        assert_eq!(u256::MAX.isqrt(), u256::from(::core::u128::MAX));
+        assert_eq!(u512::MAX.isqrt(), u512::from(u256::MAX));
+        assert_eq!(u1024::MAX.isqrt(), u1024::from(u512::MAX));
+
+        let val = u1024::MAX / 7u8;
+        let big = BigUint::from_bytes_le(&val.to_le_bytes());
+        let root = val.isqrt().to_le_bytes();
+        let len = root.iter().rposition(|byte| *byte != 0).unwrap() + 1;
+        assert_eq!(root[..len], big.sqrt().to_bytes_le()[..]);
     }
 
     #[test]
-    fn u256_arithmetic_test() {
-        let init = u256::from(0xDEADBEEFDEADBEEFu64);
-        let copy = init;
+    fn bigint_signed_primitive_conversions() {
+        use core::convert::TryFrom;
 
-        let add = init + copy;
-        assert_eq!(add, u256([0xBD5B7DDFBD5B7DDEu64, 1, 0, 0]));
-        // Bitshifts
-        let shl = add << 88;
-        assert_eq!(shl, u256([0u64, 0xDFBD5B7DDE000000, 0x1BD5B7D, 0]));
-        let shr = shl >> 40;
-        assert_eq!(shr, u256([0x7DDE000000000000u64, 0x0001BD5B7DDFBD5B, 0, 0]));
-        // Increment
-        let mut incr = shr;
-        incr += 1u32;
-        assert_eq!(
-            incr,
-            u256([0x7DDE000000000001u64, 0x0001BD5B7DDFBD5B, 0, 0])
-        );
-        // Subtraction
-        let sub = incr - init;
-        assert_eq!(sub, u256([0x9F30411021524112u64, 0x0001BD5B7DDFBD5A, 0, 0]));
-        // Multiplication
-        let mult = sub * 300u32;
         assert_eq!(
-            mult,
-            u256([0x8C8C3EE70C644118u64, 0x0209E7378231E632, 0, 0])
+            u256::try_from(::core::i128::MAX),
+            Ok(u256::from(::core::i128::MAX as u128))
         );
-        // Division
-        assert_eq!(u256::from(105u64) / u256::from(5u64), u256::from(21u64));
-        let div = mult / u256::from(300u64);
-        assert_eq!(div, u256([0x9F30411021524112u64, 0x0001BD5B7DDFBD5A, 0, 0]));
-
-        assert_eq!(u256::from(105u64) % u256::from(5u64), u256::from(0u64));
+        assert_eq!(u256::try_from(0i128), Ok(u256::ZERO));
+        assert_eq!(u256::try_from(-1i128), Err(TryFromIntError));
+        assert_eq!(u512::try_from(::core::i64::MIN), Err(TryFromIntError));
+        assert_eq!(u1024::try_from(42i8), Ok(u1024::from(42u8)));
+
+        let max_i128 = u256::from(::core::i128::MAX as u128);
+        assert_eq!(i128::try_from(max_i128), Ok(::core::i128::MAX));
+        assert_eq!(i128::try_from(max_i128 + 1u8), Err(TryFromIntError));
         assert_eq!(
-            u256::from(35498456u64) % u256::from(3435u64),
-            u256::from(1166u64)
+            i64::try_from(u512::from(::core::i64::MAX as u64)),
+            Ok(::core::i64::MAX)
         );
-        let rem_src = mult * u256::from(39842u64) + u256::from(9054u64);
-        assert_eq!(rem_src % u256::from(39842u64), u256::from(9054u64));
-        // TODO: bit inversion
+        assert_eq!(i64::try_from(u512::from(1u64 << 63)), Err(TryFromIntError));
+        assert_eq!(i8::try_from(u1024::from(127u8)), Ok(127));
+        assert_eq!(i8::try_from(u1024::from(128u8)), Err(TryFromIntError));
     }
 
     #[test]
-    fn mul_u32_test() {
-        let u64_val = u256::from(0xDEADBEEFDEADBEEFu64);
-
-        let u96_res = u64_val * 0xFFFFFFFFu32;
-        let u128_res = u96_res * 0xFFFFFFFFu32;
-        let u160_res = u128_res * 0xFFFFFFFFu32;
-        let u192_res = u160_res * 0xFFFFFFFFu32;
-        let u224_res = u192_res * 0xFFFFFFFFu32;
-        let u256_res = u224_res * 0xFFFFFFFFu32;
-
-        assert_eq!(u96_res, u256([0xffffffff21524111u64, 0xDEADBEEE, 0, 0]));
+    fn bigint_float_conversions() {
+        let two53 = u256::ONE << 53;
+        assert_eq!(two53.to_f64_lossy(), 9007199254740992.0);
+        assert_eq!((two53 + 1u8).to_f64_lossy(), 9007199254740992.0);
+        assert_eq!((two53 + 2u8).to_f64_lossy(), 9007199254740994.0);
+        assert_eq!((two53 + 3u8).to_f64_lossy(), 9007199254740996.0);
+        assert_eq!(u256::from_f64_lossy(9007199254740992.0), Some(two53));
+        assert_eq!(u256::from_f64_lossy(9007199254740993.0), Some(two53));
+        assert_eq!(u256::from_f64_lossy(9007199254740994.0), Some(two53 + 2u8));
+        assert_eq!((two53 - 1u8).to_f64_lossy(), 9007199254740991.0);
+
+        // Rounding of values exceeding `u128`, including ties to even and
+        // values slightly above the tie
+        let tie = (two53 + 1u8) << 200;
+        assert_eq!(tie.to_f64_lossy(), (two53 << 200).to_f64_lossy());
         assert_eq!(
-            u128_res,
-            u256([0x21524111DEADBEEFu64, 0xDEADBEEE21524110, 0, 0])
+            (tie + 1u8).to_f64_lossy(),
+            ((two53 + 2u8) << 200).to_f64_lossy()
         );
         assert_eq!(
-            u160_res,
-            u256([0xBD5B7DDD21524111u64, 0x42A4822200000001, 0xDEADBEED, 0])
+            ((two53 + 3u8) << 200).to_f64_lossy(),
+            ((two53 + 4u8) << 200).to_f64_lossy()
         );
+        assert_eq!(u256::MAX.to_f64_lossy(), 2f64.powi(256));
+        assert_eq!(u512::ONE.rotate_right(1).to_f64_lossy(), 2f64.powi(511));
+        assert_eq!(u1024::MAX.to_f64_lossy(), ::core::f64::INFINITY);
+        assert_eq!((u1024::MAX >> 1).to_f64_lossy(), 2f64.powi(1023));
+        assert_eq!((u1024::MAX >> 2).to_f64_lossy(), 2f64.powi(1022));
+
+        assert_eq!(u256::from_f64_lossy(::core::f64::NAN), None);
+        assert_eq!(u256::from_f64_lossy(::core::f64::INFINITY), None);
+        assert_eq!(u256::from_f64_lossy(-1.0), None);
+        assert_eq!(u256::from_f64_lossy(-0.0), Some(u256::ZERO));
+        assert_eq!(u256::from_f64_lossy(0.999), Some(u256::ZERO));
         assert_eq!(
-            u192_res,
-            u256([
-                0x63F6C333DEADBEEFu64,
-                0xBD5B7DDFBD5B7DDB,
-                0xDEADBEEC63F6C334,
-                0
-            ])
+            u256::from_f64_lossy(::core::f64::MIN_POSITIVE),
+            Some(u256::ZERO)
         );
+        assert_eq!(u256::from_f64_lossy(12345.678), Some(u256::from(12345u32)));
+        assert_eq!(u256::from_f64_lossy(2f64.powi(255)), Some(u256::ONE << 255));
+        assert_eq!(u256::from_f64_lossy(2f64.powi(256)), None);
         assert_eq!(
-            u224_res,
-            u256([
-                0x7AB6FBBB21524111u64,
-                0xFFFFFFFBA69B4558,
-                0x854904485964BAAA,
-                0xDEADBEEB
-            ])
+            u1024::from_f64_lossy(::core::f64::MAX).map(|v| v.bits_required()),
+            Some(1024)
         );
+
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            seed
+        };
+        for _ in 0..2000 {
+            let val = ((next() as u128) << 64 | next() as u128) >> (next() % 128);
+            assert_eq!(u256::from(val).to_f64_lossy(), val as f64);
+            let float = val as f64;
+            assert_eq!(
+                u256::from_f64_lossy(float).map(|v| v.to_f64_lossy()),
+                Some(float)
+            );
+
+            // Integers with at most 53 significant bits round-trip exactly
+            let exact = u512::from(next() >> 11) << (next() % 459) as usize;
+            assert_eq!(u512::from_f64_lossy(exact.to_f64_lossy()), Some(exact));
+        }
+    }
+
+    #[test]
+    fn bigint_widening_mul() {
+        use num_bigint::BigUint;
+
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            seed
+        };
+        for _ in 0..500 {
+            let mut a = [0u64; 4];
+            let mut b = [0u64; 4];
+            for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+                *x = next() >> (next() % 64);
+                *y = next();
+            }
+            let (a, b) = (u256(a), u256(b));
+            let (low, high) = a.widening_mul(b);
+            assert_eq!(low, a.wrapping_mul(b));
+            let mut bytes = low.to_le_bytes().to_vec();
+            bytes.extend_from_slice(&high.to_le_bytes());
+            let product =
+                BigUint::from_bytes_le(&a.to_le_bytes()) * BigUint::from_bytes_le(&b.to_le_bytes());
+            assert_eq!(BigUint::from_bytes_le(&bytes), product);
+        }
         assert_eq!(
-            u256_res,
-            u256([
-                0xA69B4555DEADBEEFu64,
-                0xA69B455CD41BB662,
-                0xD41BB662A69B4550,
-                0xDEADBEEAA69B455C
-            ])
+            u256::MAX.widening_mul(u256::MAX),
+            (u256::ONE, u256::MAX - 1u8)
         );
+        assert_eq!(u512::MAX.widening_mul(u512::ZERO), (u512::ZERO, u512::ZERO));
     }
 
     #[test]
-    fn multiplication_test() {
-        let u64_val = u256::from(0xDEADBEEFDEADBEEFu64);
-
-        let u128_res = u64_val * u64_val;
-
+    fn bit_fields() {
+        let value = u256::from_inner([
+            0x0123_4567_89AB_CDEF,
+            0xFEDC_BA98_7654_3210,
+            0x0F0F_0F0F_0F0F_0F0F,
+            0x8000_0000_0000_0001,
+        ]);
+        assert_eq!(value.bits(0..0), u256::ZERO);
+        assert_eq!(value.bits(256..256), u256::ZERO);
+        assert_eq!(value.bits(0..256), value);
+        assert_eq!(value.bits(40..64), u256::from(0x0001_2345u32));
+        assert_eq!(value.extract_u64(40, 24), 0x0001_2345);
+        // Crossing word boundary
+        assert_eq!(value.bits(56..72), u256::from(0x1001u32));
+        assert_eq!(value.extract_u64(56, 16), 0x1001);
+        assert_eq!(value.extract_u64(32, 64), 0x7654_3210_0123_4567);
+        assert_eq!(value.bits(32..96), u256::from(0x7654_3210_0123_4567u64));
         assert_eq!(
-            u128_res,
-            u256([0x048D1354216DA321u64, 0xC1B1CD13A4D13D46, 0, 0])
+            value.bits(60..200),
+            u256::from_inner([0xEDCB_A987_6543_2100, 0xF0F0_F0F0_F0F0_F0FF, 0x10, 0])
         );
+        // Touching the top bit
+        assert_eq!(value.bits(255..256), u256::ONE);
+        assert_eq!(value.extract_u64(255, 1), 1);
+        assert_eq!(value.extract_u64(192, 64), 0x8000_0000_0000_0001);
+        assert_eq!(value.extract_u64(250, 6), 0x20);
+        assert_eq!(value.extract_u64(256, 0), 0);
+        assert_eq!(value.bits(128..256), u256::from(value.split().0));
+        assert_eq!(u512::MAX.bits(511..512), u512::ONE);
+        assert_eq!(u1024::MAX.bits(1..1024), u1024::MAX >> 1);
+
+        let mut field = u256::ZERO;
+        field.set_bits(40..64, u256::from(0xABCDEFu32));
+        assert_eq!(field, u256::from(0xABCDEFu64 << 40));
+        field.set_bits(56..72, u256::from(0x1234u32));
+        assert_eq!(field, u256::from_inner([0x34CD_EF00_0000_0000, 0x12, 0, 0]));
+        field.set_bits(255..256, u256::ONE);
+        assert_eq!(field.bits(255..256), u256::ONE);
+        assert!(field.bit(255));
+        field.set_bits(200..256, u256::ZERO);
+        assert_eq!(field, u256::from_inner([0x34CD_EF00_0000_0000, 0x12, 0, 0]));
+        field.set_bits(0..256, u256::MAX);
+        assert_eq!(field, u256::MAX);
+        field.set_bits(10..10, u256::ZERO);
+        assert_eq!(field, u256::MAX);
+        let mut field = u1024::ZERO;
+        field.set_bits(1000..1024, u1024::from(0xFFFFFFu32));
+        assert_eq!(field.leading_zeros(), 0);
+        assert_eq!(field.trailing_zeros(), 1000);
+    }
 
-        let u256_res = u128_res * u128_res;
+    #[test]
+    fn bit_fields_roundtrip() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(0x5eed_b175);
+        for _ in 0..1000 {
+            let base = u512::from_inner(rng.gen());
+            let start = rng.gen_range(0..=512u32);
+            let end = rng.gen_range(start..=512u32);
+            let width = end - start;
+            let value = u512::from_inner(rng.gen()) & !(u512::MAX << width as usize);
+
+            let mut field = base;
+            field.set_bits(start..end, value);
+            assert_eq!(field.bits(start..end), value);
+            assert_eq!(field.bits(0..start), base.bits(0..start));
+            assert_eq!(field.bits(end..512), base.bits(end..512));
+            if width <= 64 {
+                assert_eq!(field.extract_u64(start, width), value.low_u64());
+            }
+            let offset = rng.gen_range(0..=448u32);
+            assert_eq!(
+                base.extract_u64(offset, 64),
+                base.bits(offset..offset + 64).low_u64()
+            );
+        }
+    }
 
-        assert_eq!(
-            u256_res,
-            u256([
-                0xF4E166AAD40D0A41u64,
-                0xF5CF7F3618C2C886u64,
-                0x4AFCFF6F0375C608u64,
-                0x928D92B4D7F5DF33u64
-            ])
-        );
+    #[test]
+    #[should_panic(expected = "bit range 200..257 is invalid for 256-bit integer")]
+    fn bit_fields_out_of_range() {
+        u256::MAX.bits(200..257);
     }
 
     #[test]
-    fn u256_extreme_bitshift_test() {
-        // Shifting a u64 by 64 bits gives an undefined value, so make sure that
-        // we're doing the Right Thing here
-        let init = u256::from(0xDEADBEEFDEADBEEFu64);
+    #[should_panic(expected = "bit field of 65 bits does not fit u64")]
+    fn bit_fields_wide_u64() {
+        u256::MAX.extract_u64(0, 65);
+    }
 
-        assert_eq!(init << 64, u256([0, 0xDEADBEEFDEADBEEF, 0, 0]));
-        let add = (init << 64) + init;
-        assert_eq!(add, u256([0xDEADBEEFDEADBEEF, 0xDEADBEEFDEADBEEF, 0, 0]));
-        assert_eq!(
-            add >> 0,
-            u256([0xDEADBEEFDEADBEEF, 0xDEADBEEFDEADBEEF, 0, 0])
-        );
-        assert_eq!(
-            add << 0,
-            u256([0xDEADBEEFDEADBEEF, 0xDEADBEEFDEADBEEF, 0, 0])
-        );
-        assert_eq!(add >> 64, u256([0xDEADBEEFDEADBEEF, 0, 0, 0]));
-        assert_eq!(
-            add << 64,
-            u256([0, 0xDEADBEEFDEADBEEF, 0xDEADBEEFDEADBEEF, 0])
-        );
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "value does not fit 8-bit field at bits 8..16")]
+    fn bit_fields_overflow() {
+        let mut field = u256::ZERO;
+        field.set_bits(8..16, u256::from(0x100u32));
     }
 
-    #[cfg(feature = "serde")]
     #[test]
-    fn u256_serde_test() {
-        let check = |uint, hex| {
-            let json = format!("\"{}\"", hex);
-            assert_eq!(::serde_json::to_string(&uint).unwrap(), json);
-            assert_eq!(::serde_json::from_str::<u256>(&json).unwrap(), uint);
+    fn halves() {
+        let value = u512::from_inner([1, 2, 3, 4, 5, 6, 7, 8]);
+        let (hi, lo) = value.split();
+        assert_eq!(hi, u256::from_inner([5, 6, 7, 8]));
+        assert_eq!(lo, u256::from_inner([1, 2, 3, 4]));
+        assert_eq!(u512::from_halves(hi, lo), value);
+        assert_eq!(u512::from_halves(u256::ZERO, lo), u512::from(lo));
+        assert_eq!(u512::from_halves(hi, u256::ZERO), u512::from(hi) << 256);
+
+        let value = u1024::MAX - 1u8;
+        let (hi, lo) = value.split();
+        assert_eq!((hi, lo), (u512::MAX, u512::MAX - 1u8));
+        assert_eq!(u1024::from_halves(hi, lo), value);
+
+        let value = u256::from_inner([1, 2, 3, 4]);
+        assert_eq!(value.split(), (3 | 4 << 64, 1 | 2 << 64));
+        assert_eq!(u256::from_halves(3 | 4 << 64, 1 | 2 << 64), value);
+        assert_eq!(u256::from_halves(0, u128::MAX), u256::from(u128::MAX));
+        assert_eq!(u256::MAX.split(), (u128::MAX, u128::MAX));
+    }
 
-            let bin_encoded = ::bincode::serialize(&uint).unwrap();
-            let bin_decoded: u256 = ::bincode::deserialize(&bin_encoded).unwrap();
-            assert_eq!(bin_decoded, uint);
-        };
+    #[cfg(feature = "rand")]
+    #[test]
+    fn bigint_rand() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(0x2545_f491_4f6c_dd1d);
+        let mut other = StdRng::seed_from_u64(0x2545_f491_4f6c_dd1d);
+        for _ in 0..100 {
+            assert_eq!(rng.gen::<u1024>(), other.gen::<u1024>());
+            let low = rng.gen::<u256>() >> 1;
+            assert_eq!(low, other.gen::<u256>() >> 1);
+            assert_eq!(
+                rng.gen_range(low..u256::MAX),
+                other.gen_range(low..u256::MAX)
+            );
+            assert_eq!(
+                rng.gen_range(i512::MIN..=i512::MAX),
+                other.gen_range(i512::MIN..=i512::MAX)
+            );
+        }
 
-        check(
-            u256::from(0u64),
-            "0000000000000000000000000000000000000000000000000000000000000000",
-        );
-        check(
-            u256::from(0xDEADBEEFu64),
-            "00000000000000000000000000000000000000000000000000000000deadbeef",
-        );
-        check(
-            u256([0xaa11, 0xbb22, 0xcc33, 0xdd44]),
-            "000000000000dd44000000000000cc33000000000000bb22000000000000aa11",
-        );
-        check(
-            u256([
-                u64::max_value(),
-                u64::max_value(),
-                u64::max_value(),
-                u64::max_value(),
-            ]),
-            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
-        );
-        check(
-            u256([
-                0xA69B4555DEADBEEF,
-                0xA69B455CD41BB662,
-                0xD41BB662A69B4550,
-                0xDEADBEEAA69B455C,
-            ]),
-            "deadbeeaa69b455cd41bb662a69b4550a69b455cd41bb662a69b4555deadbeef",
-        );
+        // Chi-squared test over the 4 least significant bits with 15 degrees
+        // of freedom; the critical value for p = 0.001 is 37.7
+        let mut buckets = [0u32; 16];
+        let samples = 16000;
+        for _ in 0..samples {
+            buckets[(rng.gen::<u256>().low_u32() & 0xF) as usize] += 1;
+        }
+        let expected = samples as f64 / 16.0;
+        let chi2 = buckets
+            .iter()
+            .map(|count| (*count as f64 - expected).powi(2) / expected)
+            .sum::<f64>();
+        assert!(chi2 < 37.7, "chi-squared is {}", chi2);
+
+        // Range not dividing `2^256`, where modulo reduction would be biased
+        let mut buckets = [0u32; 6];
+        for _ in 0..samples {
+            let val = rng.gen_range(u256::from(10u8)..u256::from(16u8));
+            buckets[(val.low_u32() - 10) as usize] += 1;
+        }
+        let expected = samples as f64 / 6.0;
+        let chi2 = buckets
+            .iter()
+            .map(|count| (*count as f64 - expected).powi(2) / expected)
+            .sum::<f64>();
+        assert!(chi2 < 20.5, "chi-squared is {}", chi2);
+
+        let low = u512::MAX / 3u8;
+        let high = low * 2u8;
+        for _ in 0..1000 {
+            let val = rng.gen_range(low..high);
+            assert!(val >= low && val < high);
+            let val = rng.gen_range(i256::from(-3)..=i256::from(2));
+            assert!(val >= i256::from(-3) && val <= i256::from(2));
+            let val = rng.gen_range(i1024::MIN..i1024::MIN + 2);
+            assert!(val == i1024::MIN || val == i1024::MIN + 1);
+        }
+        assert_eq!(rng.gen_range(u256::MAX..=u256::MAX), u256::MAX);
+        assert_eq!(rng.gen_range(i256::MIN..=i256::MIN), i256::MIN);
+    }
 
-        assert!(::serde_json::from_str::<u256>(
-            "\"fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffg\""
-        )
-        .is_err()); // invalid char
-        assert!(::serde_json::from_str::<u256>(
-            "\"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\""
-        )
-        .is_err()); // invalid length
-        assert!(::serde_json::from_str::<u256>(
-            "\"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\""
-        )
-        .is_err()); // invalid length
+    #[cfg(feature = "rand")]
+    #[test]
+    #[should_panic(expected = "Uniform::new called with `low >= high`")]
+    fn bigint_rand_empty_range() {
+        rand::distributions::Uniform::new(u256::ONE, u256::ONE);
     }
 }
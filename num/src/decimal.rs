@@ -0,0 +1,347 @@
+// Rust language amplification library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! # Human-readable decimal formatting of integers
+//!
+//! [`DisplayDecimal`] adaptor formats integers with thousands separators and
+//! fixed decimal scaling, like `1 234.567 sat` for an amount of
+//! millisatoshis. The adaptor works with any type which `Display`
+//! implementation produces decimal digits optionally preceded by `-`, which
+//! includes primitive integers and big integers like [`crate::u256`].
+//!
+//! Scaling only shifts the decimal point in the digits produced by the
+//! `Display` implementation, so the result is always exact: no floating point
+//! arithmetics is involved and values are never rounded.
+//!
+//! ```
+//! use amplify_num::decimal::DisplayDecimal;
+//!
+//! let display = DisplayDecimal::new(&1_234_567u64).grouped(" ").scaled(3);
+//! assert_eq!(display.with_unit("sat").to_string(), "1 234.567 sat");
+//! assert_eq!(DisplayDecimal::new(&7).scaled(3).to_string(), "0.007");
+//! assert_eq!(DisplayDecimal::new(&-1_000_000i32).grouped(",").to_string(), "-1,000,000");
+//! ```
+//!
+//! The adaptor is used by the `Display` derive macro from `amplify_derive` for
+//! fields formatted with `{field:grouped}` placeholders or marked with
+//! `#[display(scale = N, unit = "...")]` attributes.
+
+use core::fmt::{self, Display, Formatter, Write};
+
+/// Adaptor formatting an integer with group separators between each three
+/// digits of the integer part, shifting the decimal point by a fixed number of
+/// digits (scale) and appending unit name; see [module docs](self).
+///
+/// The formatter honors width, fill and alignment (with the default right
+/// alignment), `+` flag and zero flag (`{:08}`), which pads with zeros after
+/// the sign. Precision and alternate flag are ignored.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DisplayDecimal<'a, T>
+where
+    T: ?Sized,
+{
+    value: &'a T,
+    /// Separator between groups of digits; empty for no grouping
+    sep: &'a str,
+    /// Number of fractional digits
+    scale: usize,
+    unit: &'a str,
+}
+
+impl<'a, T> DisplayDecimal<'a, T>
+where
+    T: Display + ?Sized,
+{
+    /// Constructs adaptor formatting `value` as is
+    #[inline]
+    pub fn new(value: &'a T) -> Self {
+        DisplayDecimal {
+            value,
+            sep: "",
+            scale: 0,
+            unit: "",
+        }
+    }
+
+    /// Inserts `sep` separator between each three digits of the integer part
+    /// of the number. Empty `sep` removes grouping.
+    #[inline]
+    pub fn grouped(mut self, sep: &'a str) -> Self {
+        self.sep = sep;
+        self
+    }
+
+    /// Divides value by `10^scale`, rendering exactly `scale` fractional
+    /// digits. Zero `scale` removes fractional part.
+    #[inline]
+    pub fn scaled(mut self, scale: usize) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Appends unit name to the number, separated by a space. Empty `unit`
+    /// removes the unit.
+    #[inline]
+    pub fn with_unit(mut self, unit: &'a str) -> Self {
+        self.unit = unit;
+        self
+    }
+}
+
+/// Counts digits produced by the `Display` implementation
+#[derive(Default)]
+struct Measure {
+    negative: bool,
+    digits: usize,
+}
+
+impl Write for Measure {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '-' if self.digits == 0 && !self.negative => self.negative = true,
+                '0'..='9' => self.digits += 1,
+                _ => return Err(fmt::Error),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes digits produced by the `Display` implementation into the formatter,
+/// inserting separators and decimal point
+struct Emit<'a, 'f, 'b> {
+    f: &'f mut Formatter<'b>,
+    sep: &'a str,
+    /// Number of digits in the integer part
+    int_len: usize,
+    /// Number of zeros following the decimal point before the first digit
+    zeros: usize,
+    /// Number of digits written so far
+    pos: usize,
+    /// Number of integer part digits left before the next separator
+    group: usize,
+}
+
+impl<'a, 'f, 'b> Emit<'a, 'f, 'b> {
+    fn write_fraction_start(&mut self) -> fmt::Result {
+        self.f.write_char('.')?;
+        for _ in 0..self.zeros {
+            self.f.write_char('0')?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, 'f, 'b> Write for Emit<'a, 'f, 'b> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars().filter(|c| *c != '-') {
+            if self.pos == 0 && self.int_len == 0 {
+                self.f.write_char('0')?;
+                self.write_fraction_start()?;
+            } else if self.pos > 0 && self.pos == self.int_len {
+                self.write_fraction_start()?;
+            } else if self.pos < self.int_len && self.group == 0 {
+                self.f.write_str(self.sep)?;
+                self.group = 3;
+            }
+            self.f.write_char(c)?;
+            self.group = self.group.saturating_sub(1);
+            self.pos += 1;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T> Display for DisplayDecimal<'a, T>
+where
+    T: Display + ?Sized,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut measure = Measure::default();
+        write!(measure, "{}", self.value)?;
+
+        let int_len = measure.digits.saturating_sub(self.scale);
+        let zeros = self.scale.saturating_sub(measure.digits);
+        let seps = if int_len > 0 { (int_len - 1) / 3 } else { 0 };
+        let sign = match (measure.negative, f.sign_plus()) {
+            (true, _) => "-",
+            (false, true) => "+",
+            (false, false) => "",
+        };
+        let fraction = if self.scale > 0 { self.scale + 1 } else { 0 };
+        let unit = if self.unit.is_empty() {
+            0
+        } else {
+            self.unit.chars().count() + 1
+        };
+        let len = sign.len() + int_len.max(1) + seps * self.sep.chars().count() + fraction + unit;
+        let padding = f.width().unwrap_or(0).saturating_sub(len);
+
+        let (fill, pre, post) = if f.sign_aware_zero_pad() {
+            ('0', 0, 0)
+        } else {
+            match f.align() {
+                None | Some(fmt::Alignment::Right) => (f.fill(), padding, 0),
+                Some(fmt::Alignment::Left) => (f.fill(), 0, padding),
+                Some(fmt::Alignment::Center) => (f.fill(), padding / 2, padding - padding / 2),
+            }
+        };
+
+        for _ in 0..pre {
+            f.write_char(fill)?;
+        }
+        f.write_str(sign)?;
+        if f.sign_aware_zero_pad() {
+            for _ in 0..padding {
+                f.write_char('0')?;
+            }
+        }
+        let mut emit = Emit {
+            f,
+            sep: self.sep,
+            int_len,
+            zeros,
+            pos: 0,
+            group: (int_len + 2) % 3 + 1,
+        };
+        write!(emit, "{}", self.value)?;
+        if !self.unit.is_empty() {
+            f.write_char(' ')?;
+            f.write_str(self.unit)?;
+        }
+        for _ in 0..post {
+            f.write_char(fill)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{i256, u256};
+
+    fn scaled<T: Display>(value: T, scale: usize) -> String {
+        DisplayDecimal::new(&value).scaled(scale).to_string()
+    }
+
+    fn grouped<T: Display>(value: T) -> String {
+        DisplayDecimal::new(&value).grouped(",").to_string()
+    }
+
+    #[test]
+    fn plain() {
+        assert_eq!(DisplayDecimal::new(&0u8).to_string(), "0");
+        assert_eq!(DisplayDecimal::new(&-15i8).to_string(), "-15");
+        assert_eq!(
+            DisplayDecimal::new(&u64::MAX).to_string(),
+            u64::MAX.to_string()
+        );
+        assert_eq!(
+            DisplayDecimal::new(&1u8).with_unit("sat").to_string(),
+            "1 sat"
+        );
+    }
+
+    #[test]
+    fn grouping() {
+        assert_eq!(grouped(0u64), "0");
+        assert_eq!(grouped(1u64), "1");
+        assert_eq!(grouped(999u64), "999");
+        assert_eq!(grouped(1_000u64), "1,000");
+        assert_eq!(grouped(100_000u64), "100,000");
+        assert_eq!(grouped(1_000_000u64), "1,000,000");
+        assert_eq!(grouped(u64::MAX), "18,446,744,073,709,551,615");
+        assert_eq!(grouped(-1i64), "-1");
+        assert_eq!(grouped(-999i64), "-999");
+        assert_eq!(grouped(-1_000i64), "-1,000");
+        assert_eq!(grouped(i64::MIN), "-9,223,372,036,854,775,808");
+        assert_eq!(
+            DisplayDecimal::new(&1_234_567u32)
+                .grouped("\u{202F}")
+                .to_string(),
+            "1\u{202F}234\u{202F}567"
+        );
+        assert_eq!(
+            grouped(u256::from(u128::MAX) + u256::from(1u8)),
+            "340,282,366,920,938,463,463,374,607,431,768,211,456"
+        );
+        assert_eq!(grouped(-i256::from(1_000_000i64)), "-1,000,000");
+    }
+
+    #[test]
+    fn scaling() {
+        assert_eq!(scaled(0u64, 3), "0.000");
+        assert_eq!(scaled(1u64, 3), "0.001");
+        assert_eq!(scaled(7u64, 3), "0.007");
+        assert_eq!(scaled(999u64, 3), "0.999");
+        assert_eq!(scaled(1_000u64, 3), "1.000");
+        assert_eq!(scaled(1_234_567u64, 3), "1234.567");
+        assert_eq!(scaled(u64::MAX, 3), "18446744073709551.615");
+        assert_eq!(scaled(u64::MAX, 20), "0.18446744073709551615");
+        assert_eq!(scaled(u64::MAX, 22), "0.0018446744073709551615");
+        assert_eq!(scaled(12u64, 0), "12");
+        assert_eq!(scaled(-1i64, 3), "-0.001");
+        assert_eq!(scaled(-1_000i64, 3), "-1.000");
+        assert_eq!(scaled(u256::from(123_456u64), 4), "12.3456");
+    }
+
+    #[test]
+    fn grouped_scaling() {
+        let fmt = |value: i64| {
+            DisplayDecimal::new(&value)
+                .grouped(" ")
+                .scaled(3)
+                .with_unit("sat")
+                .to_string()
+        };
+        assert_eq!(fmt(0), "0.000 sat");
+        assert_eq!(fmt(1), "0.001 sat");
+        assert_eq!(fmt(999), "0.999 sat");
+        assert_eq!(fmt(1_234_567), "1 234.567 sat");
+        assert_eq!(fmt(-1_234_567), "-1 234.567 sat");
+        assert_eq!(fmt(-999_999_999), "-999 999.999 sat");
+        assert_eq!(fmt(i64::MAX), "9 223 372 036 854 775.807 sat");
+        assert_eq!(
+            DisplayDecimal::new(&u64::MAX)
+                .grouped(",")
+                .scaled(3)
+                .to_string(),
+            "18,446,744,073,709,551.615"
+        );
+    }
+
+    #[test]
+    fn padding() {
+        let value = DisplayDecimal::new(&-1_234i32).grouped(",").scaled(1);
+        assert_eq!(format!("{}", value), "-123.4");
+        assert_eq!(format!("{:8}", value), "  -123.4");
+        assert_eq!(format!("{:<8}|", value), "-123.4  |");
+        assert_eq!(format!("{:*^10}", value), "**-123.4**");
+        assert_eq!(format!("{:08}", value), "-00123.4");
+        assert_eq!(format!("{:3}", value), "-123.4");
+        let value = DisplayDecimal::new(&1_234u32).grouped(",");
+        assert_eq!(format!("{:+}", value), "+1,234");
+        assert_eq!(format!("{:+8}", value), "  +1,234");
+        assert_eq!(format!("{:>10}", value.with_unit("sat")), " 1,234 sat");
+    }
+
+    #[test]
+    fn non_numeric() {
+        let value = DisplayDecimal::new("12a");
+        assert!(fmt::write(&mut String::new(), format_args!("{}", value)).is_err());
+    }
+}
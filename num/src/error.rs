@@ -57,3 +57,81 @@ impl core::fmt::Display for ParseLengthError {
 }
 #[cfg(feature = "std")]
 impl std::error::Error for ParseLengthError {}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+/// Error parsing big integer from a string
+pub enum ParseIntError {
+    /// Empty string, or a string consisting only of `0x` prefix
+    Empty,
+    /// Invalid digit, with the byte position of the character in the string
+    InvalidDigit(char, usize),
+    /// Value exceeds the maximum value of the integer type
+    Overflow,
+}
+
+impl core::fmt::Display for ParseIntError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            ParseIntError::Empty => f.write_str("Cannot parse integer from empty string"),
+            ParseIntError::InvalidDigit(ch, pos) => write!(
+                f,
+                "Invalid digit '{}' at position {}",
+                ch.escape_debug(),
+                pos
+            ),
+            ParseIntError::Overflow => f.write_str("Number too large to fit in the integer type"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseIntError {}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+/// Error converting between integer types, when the value does not fit the
+/// target type
+pub struct TryFromIntError;
+
+impl core::fmt::Display for TryFromIntError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("Out of range integral type conversion attempted")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryFromIntError {}
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+/// Error parsing integer with a scale suffix, like `64k`
+pub enum ParseScaledError {
+    /// The number part of the string is empty or contains invalid digit
+    Number(ParseIntError),
+    /// Unknown scale suffix, with the byte position of the suffix in the string
+    UnknownSuffix(usize),
+    /// Scaled value exceeds the maximum value of the integer type
+    Overflow,
+}
+
+impl core::fmt::Display for ParseScaledError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            ParseScaledError::Number(err) => write!(f, "Invalid number part: {}", err),
+            ParseScaledError::UnknownSuffix(pos) => {
+                write!(f, "Unknown scale suffix at position {}", pos)
+            }
+            ParseScaledError::Overflow => {
+                f.write_str("Scaled number too large to fit in the integer type")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseScaledError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseScaledError::Number(err) => Some(err),
+            ParseScaledError::UnknownSuffix(_) | ParseScaledError::Overflow => None,
+        }
+    }
+}
@@ -16,30 +16,68 @@
 
 //! # Hex encoding and decoding
 
+use core::convert::TryFrom;
 use core::{fmt, str};
 #[cfg(feature = "alloc")]
 use alloc::{string::String, vec::Vec, format};
 
+use crate::error::OverflowError;
+use crate::{u1, u2, u3, u4, u5, u6, u7, u24};
+
 /// Hex decoding error
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
 pub enum Error {
-    /// non-hexadecimal character
-    InvalidChar(u8),
-    /// purported hex string had odd length
+    /// non-hexadecimal character (character, byte offset of the character in
+    /// the string)
+    InvalidChar(char, usize),
+    /// purported hex string had odd length (actual length)
     OddLengthString(usize),
     /// tried to parse fixed-length hash from a string with the wrong type
     /// (expected, got)
     InvalidLength(usize, usize),
+    /// hex string was required to start with `0x` prefix
+    MissingPrefix,
+    /// hex string consists of `0x` prefix only, without any hex digits
+    EmptyPrefixed,
+    /// hex string contains `0x` prefix at a position where it is not allowed
+    /// (position of the prefix)
+    MisplacedPrefix(usize),
+    /// hex data were decoded, but the value is rejected by the type validation
+    InvalidValue,
+    /// hex data represent a number exceeding the maximal value of the integer
+    /// type
+    Overflow(OverflowError),
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Error::InvalidChar(ch) => write!(f, "invalid hex character {}", ch),
+            Error::InvalidChar(ch, pos) => write!(
+                f,
+                "invalid hex character '{}' at position {}",
+                ch.escape_debug(),
+                pos
+            ),
             Error::OddLengthString(ell) => write!(f, "odd hex string length {}", ell),
             Error::InvalidLength(ell, ell2) => {
                 write!(f, "bad hex string length {} (expected {})", ell2, ell)
             }
+            Error::MissingPrefix => f.write_str("hex string is missing `0x` prefix"),
+            Error::EmptyPrefixed => f.write_str("hex string has `0x` prefix but no hex digits"),
+            Error::InvalidValue => f.write_str("hex data represent an invalid value"),
+            Error::Overflow(err) => write!(
+                f,
+                "hex number {:#x} exceeds maximal value {:#x}",
+                err.value, err.max
+            ),
+            Error::MisplacedPrefix(pos) => {
+                write!(
+                    f,
+                    "unexpected `0x` prefix at position {} of hex string",
+                    pos
+                )
+            }
         }
     }
 }
@@ -52,19 +90,81 @@ impl std::error::Error for Error {}
 pub trait ToHex {
     /// Hex representation of the object
     fn to_hex(&self) -> String;
+
+    /// Hex representation of the object using uppercase letters
+    fn to_hex_upper(&self) -> String {
+        self.to_hex().to_ascii_uppercase()
+    }
 }
 
-/// Trait for objects that can be deserialized from hex strings
+/// Trait for objects that can be deserialized from hex strings.
+///
+/// Hex digits are accepted in any case, including mixed-case strings. The
+/// `0x` (or `0X`) prefix is handled according to the method used:
+/// - [`FromHex::from_hex`] does not accept the prefix;
+/// - [`FromHex::from_hex_prefixed`] requires the prefix;
+/// - [`FromHex::from_hex_any`] accepts strings both with and without the
+///   prefix.
+///
+/// A string consisting of the prefix only, as well as a prefix found anywhere
+/// besides the start of the string, are always an error.
 pub trait FromHex: Sized {
     /// Produce an object from a byte iterator
     fn from_byte_iter<I>(iter: I) -> Result<Self, Error>
     where
         I: Iterator<Item = Result<u8, Error>> + ExactSizeIterator + DoubleEndedIterator;
 
-    /// Produce an object from a hex string
+    /// Produce an object from a hex string without `0x` prefix
     fn from_hex(s: &str) -> Result<Self, Error> {
+        check_misplaced_prefix(s, 0)?;
         Self::from_byte_iter(HexIterator::new(s)?)
     }
+
+    /// Produce an object from a hex string which must start with `0x` (or
+    /// `0X`) prefix
+    fn from_hex_prefixed(s: &str) -> Result<Self, Error> {
+        match strip_prefix(s)? {
+            Some(hex) => Self::from_hex(hex),
+            None => Err(Error::MissingPrefix),
+        }
+    }
+
+    /// Produce an object from a hex string which may start with optional
+    /// `0x` (or `0X`) prefix
+    fn from_hex_any(s: &str) -> Result<Self, Error> {
+        match strip_prefix(s)? {
+            Some(hex) => Self::from_hex(hex),
+            None => Self::from_hex(s),
+        }
+    }
+}
+
+/// Strips `0x` or `0X` prefix from the string, returning `None` if there was
+/// no prefix, and checks that the remaining string has hex digits and does
+/// not contain more prefixes
+fn strip_prefix(s: &str) -> Result<Option<&str>, Error> {
+    match s.get(..2) {
+        Some("0x") | Some("0X") if s.len() == 2 => Err(Error::EmptyPrefixed),
+        Some("0x") | Some("0X") => {
+            let hex = &s[2..];
+            check_misplaced_prefix(hex, 2)?;
+            Ok(Some(hex))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Checks that the string does not contain `0x` prefix; `offset` is the
+/// position of the string within the original input, used for error reporting
+fn check_misplaced_prefix(s: &str, offset: usize) -> Result<(), Error> {
+    match s
+        .as_bytes()
+        .windows(2)
+        .position(|pair| pair[0] == b'0' && (pair[1] == b'x' || pair[1] == b'X'))
+    {
+        Some(pos) => Err(Error::MisplacedPrefix(offset + pos)),
+        None => Ok(()),
+    }
 }
 
 #[cfg(any(test, feature = "std", feature = "alloc"))]
@@ -77,9 +177,12 @@ impl<T: fmt::LowerHex> ToHex for T {
 
 /// Iterator over a hex-encoded string slice which decodes hex and yields bytes.
 pub struct HexIterator<'a> {
-    /// The `Bytes` iterator whose next two bytes will be decoded to yield
-    /// the next byte.
-    iter: str::Bytes<'a>,
+    /// The hex string which pairs of bytes will be decoded to yield bytes
+    s: &'a str,
+    /// Offset of the next pair of bytes from the front of the string
+    front: usize,
+    /// Offset following the next pair of bytes from the back of the string
+    back: usize,
 }
 
 impl<'a> HexIterator<'a> {
@@ -89,39 +192,67 @@ impl<'a> HexIterator<'a> {
         if s.len() % 2 != 0 {
             Err(Error::OddLengthString(s.len()))
         } else {
-            Ok(HexIterator { iter: s.bytes() })
+            Ok(HexIterator {
+                s,
+                front: 0,
+                back: s.len(),
+            })
         }
     }
-}
 
-fn chars_to_hex(hi: u8, lo: u8) -> Result<u8, Error> {
-    let hih = (hi as char).to_digit(16).ok_or(Error::InvalidChar(hi))?;
-    let loh = (lo as char).to_digit(16).ok_or(Error::InvalidChar(lo))?;
+    /// Decodes pair of hex digits starting at byte offset `pos`
+    fn decode(&self, pos: usize) -> Result<u8, Error> {
+        let hi = self.digit(pos)?;
+        let lo = self.digit(pos + 1)?;
+        Ok((hi << 4) + lo)
+    }
 
-    let ret = (hih << 4) + loh;
-    Ok(ret as u8)
+    /// Decodes a single hex digit at byte offset `pos`, reporting the whole
+    /// (possibly multi-byte) character containing the byte on error
+    fn digit(&self, pos: usize) -> Result<u8, Error> {
+        let byte = self.s.as_bytes()[pos];
+        match (byte as char).to_digit(16) {
+            Some(digit) if byte.is_ascii() => Ok(digit as u8),
+            _ => {
+                let start = (0..=pos)
+                    .rev()
+                    .find(|i| self.s.is_char_boundary(*i))
+                    .unwrap_or(0);
+                let ch = self.s[start..]
+                    .chars()
+                    .next()
+                    .expect("string is non-empty at char boundary");
+                Err(Error::InvalidChar(ch, start))
+            }
+        }
+    }
 }
 
 impl<'a> Iterator for HexIterator<'a> {
     type Item = Result<u8, Error>;
 
     fn next(&mut self) -> Option<Result<u8, Error>> {
-        let hi = self.iter.next()?;
-        let lo = self.iter.next().unwrap();
-        Some(chars_to_hex(hi, lo))
+        if self.front >= self.back {
+            return None;
+        }
+        let pos = self.front;
+        self.front += 2;
+        Some(self.decode(pos))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let (min, max) = self.iter.size_hint();
-        (min / 2, max.map(|x| x / 2))
+        let len = (self.back - self.front) / 2;
+        (len, Some(len))
     }
 }
 
 impl<'a> DoubleEndedIterator for HexIterator<'a> {
     fn next_back(&mut self) -> Option<Result<u8, Error>> {
-        let lo = self.iter.next_back()?;
-        let hi = self.iter.next_back().unwrap();
-        Some(chars_to_hex(hi, lo))
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 2;
+        Some(self.decode(self.back))
     }
 }
 
@@ -130,37 +261,234 @@ impl<'a> ExactSizeIterator for HexIterator<'a> {}
 /// Output hex into an object implementing `fmt::Write`, which is usually more
 /// efficient than going through a `String` using `ToHex`.
 pub fn format_hex(data: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
-    let prec = f.precision().unwrap_or(2 * data.len());
-    let width = f.width().unwrap_or(2 * data.len());
-    for _ in (2 * data.len())..width {
-        f.write_str("0")?;
-    }
-    for ch in data.iter().take(prec / 2) {
-        write!(f, "{:02x}", *ch)?;
-    }
-    if prec < 2 * data.len() && prec % 2 == 1 {
-        write!(f, "{:x}", data[prec / 2] / 16)?;
-    }
-    Ok(())
+    write_hex(data.iter(), data.len(), false, f)
 }
 
 /// Output hex in reverse order; used for Sha256dHash whose standard hex
 /// encoding has the bytes reversed.
 pub fn format_hex_reverse(data: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
-    let prec = f.precision().unwrap_or(2 * data.len());
-    let width = f.width().unwrap_or(2 * data.len());
-    for _ in (2 * data.len())..width {
+    write_hex(data.iter().rev(), data.len(), false, f)
+}
+
+/// Output hex using uppercase letters; see [`format_hex`].
+pub fn format_hex_upper(data: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
+    write_hex(data.iter(), data.len(), true, f)
+}
+
+/// Output hex using uppercase letters in reverse order; see
+/// [`format_hex_reverse`].
+pub fn format_hex_upper_reverse(data: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
+    write_hex(data.iter().rev(), data.len(), true, f)
+}
+
+fn write_hex<'a>(
+    mut data: impl Iterator<Item = &'a u8>,
+    len: usize,
+    upper: bool,
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    let prec = f.precision().unwrap_or(2 * len);
+    let width = f.width().unwrap_or(2 * len);
+    for _ in (2 * len)..width {
         f.write_str("0")?;
     }
-    for ch in data.iter().rev().take(prec / 2) {
-        write!(f, "{:02x}", *ch)?;
+    for ch in data.by_ref().take(prec / 2) {
+        if upper {
+            write!(f, "{:02X}", *ch)?;
+        } else {
+            write!(f, "{:02x}", *ch)?;
+        }
     }
-    if prec < 2 * data.len() && prec % 2 == 1 {
-        write!(f, "{:x}", data[data.len() - 1 - prec / 2] / 16)?;
+    if prec < 2 * len && prec % 2 == 1 {
+        if let Some(ch) = data.next() {
+            if upper {
+                write!(f, "{:X}", *ch / 16)?;
+            } else {
+                write!(f, "{:x}", *ch / 16)?;
+            }
+        }
     }
     Ok(())
 }
 
+/// Extension trait for byte containers providing hex formatting without
+/// intermediate allocations; see [`DisplayHex`].
+pub trait AsDisplayHex {
+    /// Returns adaptor formatting the bytes as a hex string
+    fn as_display_hex(&self) -> DisplayHex<'_>;
+}
+
+impl<T> AsDisplayHex for T
+where
+    T: AsRef<[u8]> + ?Sized,
+{
+    #[inline]
+    fn as_display_hex(&self) -> DisplayHex<'_> {
+        DisplayHex::new(self.as_ref())
+    }
+}
+
+/// Adaptor formatting a byte slice as hex, writing digits directly into the
+/// formatter, which makes it suitable for logging in hot code paths where
+/// [`ToHex::to_hex`] allocation is undesirable.
+///
+/// `Display`, `Debug` and `LowerHex` produce lowercase digits, `UpperHex` -
+/// uppercase. All of them honor formatter width, fill and alignment (with the
+/// default left alignment); alternate flag (`{:#}`) adds `0x` prefix and zero
+/// flag (`{:08x}`) pads with zeros after the prefix. Precision is ignored.
+///
+/// ```
+/// # use amplify_num::hex::AsDisplayHex;
+/// let data = [0xde, 0xad, 0xbe, 0xef, 0x01];
+/// assert_eq!(format!("{}", data.as_display_hex()), "deadbeef01");
+/// assert_eq!(format!("{:#X}", data.as_display_hex()), "0xDEADBEEF01");
+/// assert_eq!(format!("{:>12}", data.as_display_hex()), "  deadbeef01");
+/// assert_eq!(
+///     format!("{}", data.as_display_hex().chunked(2, " ")),
+///     "dead beef 01"
+/// );
+/// assert_eq!(format!("{}", data.as_display_hex().truncated(2)), "dead...");
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DisplayHex<'a> {
+    data: &'a [u8],
+    /// Number of bytes between separators; zero for no separators
+    chunk: usize,
+    sep: &'a str,
+    /// Whether the data were truncated and must be followed by an ellipsis
+    truncated: bool,
+}
+
+impl<'a> DisplayHex<'a> {
+    /// Constructs adaptor formatting `data` as a contiguous hex string
+    #[inline]
+    pub fn new(data: &'a [u8]) -> Self {
+        DisplayHex {
+            data,
+            chunk: 0,
+            sep: "",
+            truncated: false,
+        }
+    }
+
+    /// Inserts `sep` separator after each `n` bytes of data. Zero `n` removes
+    /// separators.
+    #[inline]
+    pub fn chunked(mut self, n: usize, sep: &'a str) -> Self {
+        self.chunk = n;
+        self.sep = sep;
+        self
+    }
+
+    /// Limits output to the first `max` bytes of data, followed by `...`
+    /// ellipsis if some of the data were omitted
+    #[inline]
+    pub fn truncated(mut self, max: usize) -> Self {
+        if max < self.data.len() {
+            self.data = &self.data[..max];
+            self.truncated = true;
+        }
+        self
+    }
+
+    fn fmt_hex(&self, upper: bool, f: &mut fmt::Formatter) -> fmt::Result {
+        let alphabet = if upper {
+            b"0123456789ABCDEF"
+        } else {
+            b"0123456789abcdef"
+        };
+        let prefix = if f.alternate() { "0x" } else { "" };
+        let seps = if self.chunk == 0 || self.data.is_empty() {
+            0
+        } else {
+            (self.data.len() - 1) / self.chunk
+        };
+        let ellipsis = if self.truncated { "..." } else { "" };
+        let len =
+            prefix.len() + 2 * self.data.len() + seps * self.sep.chars().count() + ellipsis.len();
+        let padding = f.width().unwrap_or(0).saturating_sub(len);
+
+        let (fill, pre, post) = if f.sign_aware_zero_pad() {
+            ('0', 0, 0)
+        } else {
+            match f.align() {
+                None | Some(fmt::Alignment::Left) => (f.fill(), 0, padding),
+                Some(fmt::Alignment::Right) => (f.fill(), padding, 0),
+                Some(fmt::Alignment::Center) => (f.fill(), padding / 2, padding - padding / 2),
+            }
+        };
+
+        for _ in 0..pre {
+            fmt::Write::write_char(f, fill)?;
+        }
+        f.write_str(prefix)?;
+        if f.sign_aware_zero_pad() {
+            for _ in 0..padding {
+                f.write_str("0")?;
+            }
+        }
+
+        let mut buf = [0u8; 128];
+        let mut pos = 0;
+        let mut in_chunk = 0;
+        for byte in self.data {
+            if self.chunk > 0 && in_chunk == self.chunk {
+                flush_hex(&buf[..pos], f)?;
+                pos = 0;
+                in_chunk = 0;
+                f.write_str(self.sep)?;
+            }
+            if pos == buf.len() {
+                flush_hex(&buf, f)?;
+                pos = 0;
+            }
+            buf[pos] = alphabet[(byte >> 4) as usize];
+            buf[pos + 1] = alphabet[(byte & 0x0F) as usize];
+            pos += 2;
+            in_chunk += 1;
+        }
+        flush_hex(&buf[..pos], f)?;
+        f.write_str(ellipsis)?;
+
+        for _ in 0..post {
+            fmt::Write::write_char(f, fill)?;
+        }
+        Ok(())
+    }
+}
+
+fn flush_hex(buf: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str(str::from_utf8(buf).expect("hex digits are always ASCII"))
+}
+
+impl<'a> fmt::LowerHex for DisplayHex<'a> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_hex(false, f)
+    }
+}
+
+impl<'a> fmt::UpperHex for DisplayHex<'a> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_hex(true, f)
+    }
+}
+
+impl<'a> fmt::Display for DisplayHex<'a> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_hex(false, f)
+    }
+}
+
+impl<'a> fmt::Debug for DisplayHex<'a> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_hex(false, f)
+    }
+}
+
 #[cfg(any(test, feature = "std", feature = "alloc"))]
 impl ToHex for [u8] {
     fn to_hex(&self) -> String {
@@ -183,9 +511,21 @@ impl FromHex for Vec<u8> {
     }
 }
 
+/// Implements [`FromHex`] for a fixed-size byte array, decoding directly into
+/// the array without intermediate allocations. Strings which do not have
+/// exactly two hex digits per each array byte result in
+/// [`Error::InvalidLength`] reporting expected and actual number of digits.
 macro_rules! impl_fromhex_array {
     ($len:expr) => {
         impl FromHex for [u8; $len] {
+            fn from_hex(s: &str) -> Result<Self, Error> {
+                check_misplaced_prefix(s, 0)?;
+                if s.len() != 2 * $len {
+                    return Err(Error::InvalidLength(2 * $len, s.len()));
+                }
+                Self::from_byte_iter(HexIterator::new(s)?)
+            }
+
             fn from_byte_iter<I>(iter: I) -> Result<Self, Error>
             where
                 I: Iterator<Item = Result<u8, Error>> + ExactSizeIterator + DoubleEndedIterator,
@@ -204,6 +544,7 @@ macro_rules! impl_fromhex_array {
     };
 }
 
+impl_fromhex_array!(0);
 impl_fromhex_array!(2);
 impl_fromhex_array!(4);
 impl_fromhex_array!(6);
@@ -224,6 +565,311 @@ impl_fromhex_array!(256);
 impl_fromhex_array!(384);
 impl_fromhex_array!(512);
 
+/// Implements [`FromHex`] for a small integer type, which is decoded from at
+/// most `$bytes` bytes in big-endian order. Unlike byte arrays, the hex string
+/// may have an odd number of digits and is not required to be zero-padded, so
+/// the output of [`ToHex::to_hex`] (which omits leading zeros, as `LowerHex`
+/// does) is parsed back. Values above the maximum of the type result in
+/// [`Error::Overflow`].
+macro_rules! impl_fromhex_smallint {
+    ($ty:ident, $inner:ident, $bytes:expr) => {
+        impl FromHex for $ty {
+            fn from_hex(s: &str) -> Result<Self, Error> {
+                check_misplaced_prefix(s, 0)?;
+                if s.is_empty() || s.len() > 2 * $bytes {
+                    return Err(Error::InvalidLength(2 * $bytes, s.len()));
+                }
+                let iter = HexIterator {
+                    s,
+                    front: 0,
+                    back: s.len(),
+                };
+                let mut value = 0u32;
+                for pos in 0..s.len() {
+                    value = (value << 4) | iter.digit(pos)? as u32;
+                }
+                $ty::try_from(value as $inner).map_err(Error::Overflow)
+            }
+
+            fn from_byte_iter<I>(iter: I) -> Result<Self, Error>
+            where
+                I: Iterator<Item = Result<u8, Error>> + ExactSizeIterator + DoubleEndedIterator,
+            {
+                if iter.len() == 0 || iter.len() > $bytes {
+                    return Err(Error::InvalidLength(2 * $bytes, 2 * iter.len()));
+                }
+                let mut value = 0u32;
+                for byte in iter {
+                    value = (value << 8) | byte? as u32;
+                }
+                $ty::try_from(value as $inner).map_err(Error::Overflow)
+            }
+        }
+    };
+}
+
+impl_fromhex_smallint!(u1, u8, 1);
+impl_fromhex_smallint!(u2, u8, 1);
+impl_fromhex_smallint!(u3, u8, 1);
+impl_fromhex_smallint!(u4, u8, 1);
+impl_fromhex_smallint!(u5, u8, 1);
+impl_fromhex_smallint!(u6, u8, 1);
+impl_fromhex_smallint!(u7, u8, 1);
+impl_fromhex_smallint!(u24, u32, 3);
+
+/// Lowercase hex digits used by the streaming encoder
+#[cfg(feature = "std")]
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Error decoding hex data read by [`HexReader`], which is returned wrapped
+/// into [`std::io::Error`] of [`std::io::ErrorKind::InvalidData`] kind
+#[cfg(feature = "std")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StreamError {
+    /// Offset of the offending byte in the underlying stream
+    pub offset: usize,
+    /// Hex decoding error
+    pub error: Error,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.error {
+            // Position is already reported by the error itself
+            Error::InvalidChar(..) => fmt::Display::fmt(&self.error, f),
+            _ => write!(f, "{} at byte offset {}", self.error, self.offset),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StreamError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<StreamError> for std::io::Error {
+    fn from(err: StreamError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err)
+    }
+}
+
+/// Writer encoding all bytes written to it as lowercase hex and forwarding
+/// the resulting ASCII characters to the underlying writer.
+///
+/// The writer does not allocate: the data are encoded in chunks through an
+/// internal stack buffer. If the underlying writer accepts only part of the
+/// encoded chunk, the rest is kept in the buffer and written first during the
+/// next [`std::io::Write::write`] or [`std::io::Write::flush`] call; thus the
+/// writer must be flushed (or converted with [`HexWriter::into_inner`]) to
+/// guarantee that all data reached the underlying writer.
+#[cfg(feature = "std")]
+pub struct HexWriter<W: std::io::Write> {
+    inner: W,
+    buf: [u8; 128],
+    pos: usize,
+    len: usize,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> HexWriter<W> {
+    /// Constructs hex writer over the underlying writer
+    pub fn new(inner: W) -> Self {
+        HexWriter {
+            inner,
+            buf: [0u8; 128],
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns reference to the underlying writer
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Writes all pending encoded data and returns the underlying writer
+    pub fn into_inner(mut self) -> std::io::Result<W> {
+        self.drain()?;
+        Ok(self.inner)
+    }
+
+    /// Writes all pending encoded data from the internal buffer to the
+    /// underlying writer
+    fn drain(&mut self) -> std::io::Result<()> {
+        use std::io::ErrorKind;
+        while self.pos < self.len {
+            match self.inner.write(&self.buf[self.pos..self.len]) {
+                Ok(0) => {
+                    return Err(std::io::Error::new(
+                        ErrorKind::WriteZero,
+                        "failed to write hex-encoded data",
+                    ))
+                }
+                Ok(written) => self.pos += written,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> std::io::Write for HexWriter<W> {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        self.drain()?;
+        let count = data.len().min(self.buf.len() / 2);
+        for (i, byte) in data[..count].iter().enumerate() {
+            self.buf[i * 2] = HEX_DIGITS[(byte >> 4) as usize];
+            self.buf[i * 2 + 1] = HEX_DIGITS[(byte & 0x0F) as usize];
+        }
+        self.pos = 0;
+        self.len = count * 2;
+        // The data are already accepted, so a failure of the underlying
+        // writer is reported by the next `write` or `flush` call, which will
+        // retry writing the rest of the buffer
+        let _ = self.drain();
+        Ok(count)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.drain()?;
+        self.inner.flush()
+    }
+}
+
+/// Reader decoding hex data from the underlying reader and yielding raw
+/// bytes.
+///
+/// The reader does not allocate and may be optionally configured to skip
+/// ASCII whitespace characters (including newlines) in the hex input. Invalid
+/// characters and an odd number of hex digits at the end of the stream are
+/// reported as [`std::io::Error`] of [`std::io::ErrorKind::InvalidData`]
+/// kind, wrapping [`StreamError`] with the offset of the offending byte.
+/// Since the input is not required to be valid UTF-8, invalid non-ASCII bytes
+/// are reported as [`char::REPLACEMENT_CHARACTER`].
+#[cfg(feature = "std")]
+pub struct HexReader<R: std::io::Read> {
+    inner: R,
+    skip_whitespace: bool,
+    /// Number of bytes read from the underlying reader
+    offset: usize,
+    /// Number of bytes decoded so far
+    decoded: usize,
+    /// High nibble of the next byte, together with its offset
+    nibble: Option<(u8, usize)>,
+    /// Decoding error which is reported on the next read, since the previous
+    /// read had some data decoded before the error
+    failure: Option<StreamError>,
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> HexReader<R> {
+    /// Constructs hex reader over the underlying reader; if `skip_whitespace`
+    /// is set ASCII whitespaces and newlines in the input are ignored,
+    /// otherwise they are reported as invalid characters
+    pub fn new(inner: R, skip_whitespace: bool) -> Self {
+        HexReader {
+            inner,
+            skip_whitespace,
+            offset: 0,
+            decoded: 0,
+            nibble: None,
+            failure: None,
+        }
+    }
+
+    /// Returns reference to the underlying reader
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the underlying reader
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> std::io::Read for HexReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if let Some(failure) = self.failure {
+            return Err(failure.into());
+        }
+
+        let mut chunk = [0u8; 128];
+        let mut decoded = 0usize;
+        while decoded < buf.len() {
+            // Maximum number of hex digits which fits the rest of the output
+            let max = (buf.len() - decoded) * 2 - self.nibble.is_some() as usize;
+            let max = max.min(chunk.len());
+            let read = match self.inner.read(&mut chunk[..max]) {
+                Ok(0) => match self.nibble {
+                    Some((_, offset)) if decoded == 0 => {
+                        let failure = StreamError {
+                            offset,
+                            error: Error::OddLengthString(self.decoded * 2 + 1),
+                        };
+                        self.failure = Some(failure);
+                        return Err(failure.into());
+                    }
+                    _ => break,
+                },
+                Ok(read) => read,
+                Err(_) if decoded > 0 => break,
+                Err(err) => return Err(err),
+            };
+
+            for (pos, ch) in chunk[..read].iter().enumerate() {
+                let offset = self.offset + pos;
+                if self.skip_whitespace && ch.is_ascii_whitespace() {
+                    continue;
+                }
+                let nibble = match (*ch as char).to_digit(16) {
+                    Some(nibble) => nibble as u8,
+                    None => {
+                        let failure = StreamError {
+                            offset,
+                            error: Error::InvalidChar(
+                                if ch.is_ascii() {
+                                    char::from(*ch)
+                                } else {
+                                    char::REPLACEMENT_CHARACTER
+                                },
+                                offset,
+                            ),
+                        };
+                        self.offset = offset + 1;
+                        self.failure = Some(failure);
+                        if decoded > 0 {
+                            return Ok(decoded);
+                        }
+                        return Err(failure.into());
+                    }
+                };
+                match self.nibble.take() {
+                    None => self.nibble = Some((nibble, offset)),
+                    Some((high, _)) => {
+                        buf[decoded] = (high << 4) | nibble;
+                        decoded += 1;
+                        self.decoded += 1;
+                    }
+                }
+            }
+            self.offset += read;
+
+            if decoded > 0 {
+                break;
+            }
+        }
+        Ok(decoded)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -299,6 +945,142 @@ mod tests {
         assert_eq!(format!("{:26x}", bytes), "0000000a090807060504030201");
     }
 
+    #[test]
+    fn hex_upper() {
+        struct HexBytes(Vec<u8>);
+        impl fmt::UpperHex for HexBytes {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                if f.alternate() {
+                    format_hex_upper_reverse(&self.0, f)
+                } else {
+                    format_hex_upper(&self.0, f)
+                }
+            }
+        }
+
+        let bytes = HexBytes(vec![0xab, 0xcd, 0xef, 0x0a]);
+
+        assert_eq!(format!("{:X}", bytes), "ABCDEF0A");
+        assert_eq!(format!("{:#X}", bytes), "0AEFCDAB");
+        assert_eq!(format!("{:.3X}", bytes), "ABC");
+        assert_eq!(format!("{:#.3X}", bytes), "0AE");
+        assert_eq!(format!("{:10X}", bytes), "00ABCDEF0A");
+    }
+
+    #[test]
+    fn hex_upper_case() {
+        let data = vec![0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+        assert_eq!(data.to_hex_upper(), "0123456789ABCDEF");
+        assert_eq!(data[..].to_hex_upper(), "0123456789ABCDEF");
+        assert_eq!(Vec::<u8>::new().to_hex_upper(), "");
+        assert_eq!(Vec::<u8>::from_hex("0123456789AbCdeF"), Ok(data.clone()));
+        assert_eq!(Vec::<u8>::from_hex(&data.to_hex_upper()), Ok(data));
+    }
+
+    #[test]
+    fn hex_prefix() {
+        let data = vec![0xab, 0xcd, 0xef];
+
+        assert_eq!(Vec::<u8>::from_hex("abcdef"), Ok(data.clone()));
+        assert_eq!(
+            Vec::<u8>::from_hex("0xabcdef"),
+            Err(Error::MisplacedPrefix(0))
+        );
+
+        assert_eq!(Vec::<u8>::from_hex_prefixed("0xabcdef"), Ok(data.clone()));
+        assert_eq!(Vec::<u8>::from_hex_prefixed("0XABcdEF"), Ok(data.clone()));
+        assert_eq!(
+            Vec::<u8>::from_hex_prefixed("abcdef"),
+            Err(Error::MissingPrefix)
+        );
+        assert_eq!(Vec::<u8>::from_hex_prefixed(""), Err(Error::MissingPrefix));
+
+        assert_eq!(Vec::<u8>::from_hex_any("0xabcdef"), Ok(data.clone()));
+        assert_eq!(Vec::<u8>::from_hex_any("0XabCDef"), Ok(data.clone()));
+        assert_eq!(Vec::<u8>::from_hex_any("AbcDef"), Ok(data));
+        assert_eq!(Vec::<u8>::from_hex_any(""), Ok(vec![]));
+        assert_eq!(<[u8; 2]>::from_hex_any("0x0a0b"), Ok([0x0a, 0x0b]));
+
+        assert_eq!(
+            Vec::<u8>::from_hex_prefixed("0x"),
+            Err(Error::EmptyPrefixed)
+        );
+        assert_eq!(Vec::<u8>::from_hex_any("0X"), Err(Error::EmptyPrefixed));
+
+        assert_eq!(
+            Vec::<u8>::from_hex("ab0xcd"),
+            Err(Error::MisplacedPrefix(2))
+        );
+        assert_eq!(
+            Vec::<u8>::from_hex_any("ab0Xcd"),
+            Err(Error::MisplacedPrefix(2))
+        );
+        assert_eq!(
+            Vec::<u8>::from_hex_any("0xab0xcd"),
+            Err(Error::MisplacedPrefix(4))
+        );
+        assert_eq!(
+            Vec::<u8>::from_hex_prefixed("0x0x"),
+            Err(Error::MisplacedPrefix(2))
+        );
+        assert_eq!(
+            Vec::<u8>::from_hex_any("0xa"),
+            Err(Error::OddLengthString(1))
+        );
+
+        assert_eq!(
+            Error::MissingPrefix.to_string(),
+            "hex string is missing `0x` prefix"
+        );
+        assert_eq!(
+            Error::EmptyPrefixed.to_string(),
+            "hex string has `0x` prefix but no hex digits"
+        );
+        assert_eq!(
+            Error::MisplacedPrefix(2).to_string(),
+            "unexpected `0x` prefix at position 2 of hex string"
+        );
+    }
+
+    #[test]
+    fn hex_array() {
+        assert_eq!(<[u8; 0]>::from_hex(""), Ok([]));
+        assert_eq!(<[u8; 0]>::from_hex("00"), Err(Error::InvalidLength(0, 2)));
+        assert_eq!(<[u8; 0]>::from_hex_any("0x"), Err(Error::EmptyPrefixed));
+        assert_eq!([0u8; 0].to_hex(), "");
+
+        let hex = "a5".repeat(512);
+        let array = <[u8; 512]>::from_hex(&hex).unwrap();
+        assert_eq!(&array[..], &[0xa5; 512][..]);
+        assert_eq!(array.to_hex(), hex);
+        assert_eq!(<[u8; 512]>::from_hex_any(&format!("0x{}", hex)), Ok(array));
+        assert_eq!(
+            <[u8; 512]>::from_hex(&hex[1..]),
+            Err(Error::InvalidLength(1024, 1023))
+        );
+        assert_eq!(
+            <[u8; 512]>::from_hex(&format!("{}00", hex)),
+            Err(Error::InvalidLength(1024, 1026))
+        );
+
+        let array = <[u8; 32]>::from_hex(&"0f".repeat(32)).unwrap();
+        assert_eq!(array, [0x0f; 32]);
+        assert_eq!(<[u8; 32]>::from_hex(&array.to_hex()), Ok(array));
+        assert_eq!(<[u8; 32]>::from_hex(&array.to_hex_upper()), Ok(array));
+        assert_eq!(
+            <[u8; 32]>::from_hex_prefixed(&format!("0x{}", &"0f".repeat(31))),
+            Err(Error::InvalidLength(64, 62))
+        );
+        assert_eq!(
+            <[u8; 32]>::from_hex(&"0f".repeat(33)),
+            Err(Error::InvalidLength(64, 66))
+        );
+        assert_eq!(
+            <[u8; 32]>::from_hex(&format!("zz{}", &"0f".repeat(31))),
+            Err(Error::InvalidChar('z', 0))
+        );
+    }
+
     #[test]
     fn hex_error() {
         let oddlen = "0123456789abcdef0";
@@ -307,10 +1089,330 @@ mod tests {
         let badchar3 = "«23456789abcdef";
 
         assert_eq!(Vec::<u8>::from_hex(oddlen), Err(Error::OddLengthString(17)));
-        assert_eq!(<[u8; 4]>::from_hex(oddlen), Err(Error::OddLengthString(17)));
-        assert_eq!(<[u8; 8]>::from_hex(oddlen), Err(Error::OddLengthString(17)));
-        assert_eq!(Vec::<u8>::from_hex(badchar1), Err(Error::InvalidChar(b'Z')));
-        assert_eq!(Vec::<u8>::from_hex(badchar2), Err(Error::InvalidChar(b'Y')));
-        assert_eq!(Vec::<u8>::from_hex(badchar3), Err(Error::InvalidChar(194)));
+        assert_eq!(
+            <[u8; 4]>::from_hex(oddlen),
+            Err(Error::InvalidLength(8, 17))
+        );
+        assert_eq!(
+            <[u8; 8]>::from_hex(oddlen),
+            Err(Error::InvalidLength(16, 17))
+        );
+        assert_eq!(
+            Vec::<u8>::from_hex(badchar1),
+            Err(Error::InvalidChar('Z', 0))
+        );
+        assert_eq!(
+            Vec::<u8>::from_hex(badchar2),
+            Err(Error::InvalidChar('Y', 3))
+        );
+        assert_eq!(
+            Vec::<u8>::from_hex(badchar3),
+            Err(Error::InvalidChar('«', 0))
+        );
+        assert_eq!(Vec::<u8>::from_hex("ab«"), Err(Error::InvalidChar('«', 2)));
+        assert_eq!(<[u8; 2]>::from_hex("a«b"), Err(Error::InvalidChar('«', 1)));
+        assert_eq!(
+            <[u8; 4]>::from_hex("0123456g"),
+            Err(Error::InvalidChar('g', 7))
+        );
+        assert_eq!(
+            <[u8; 4]>::from_hex_any("0x0123456g"),
+            Err(Error::InvalidChar('g', 7))
+        );
+        assert_eq!(
+            HexIterator::new("0123z567")
+                .unwrap()
+                .rev()
+                .collect::<Result<Vec<_>, _>>(),
+            Err(Error::InvalidChar('z', 4))
+        );
+        assert_eq!(
+            Error::InvalidChar('g', 17).to_string(),
+            "invalid hex character 'g' at position 17"
+        );
+        assert_eq!(
+            Error::InvalidChar('\n', 2).to_string(),
+            "invalid hex character '\\n' at position 2"
+        );
+    }
+
+    #[test]
+    fn display_hex_random() {
+        // Simple LCG providing reproducible pseudo-random buffers
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let mut data = Vec::new();
+        for len in 0..300 {
+            data.clear();
+            for _ in 0..len {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                data.push((seed >> 56) as u8);
+            }
+            let hex = data.to_hex();
+            assert_eq!(data.as_display_hex().to_string(), hex);
+            assert_eq!(format!("{:?}", data.as_display_hex()), hex);
+            assert_eq!(format!("{:x}", data.as_display_hex()), hex);
+            assert_eq!(format!("{:X}", data.as_display_hex()), data.to_hex_upper());
+            assert_eq!(
+                format!("{:#x}", data.as_display_hex()),
+                format!("0x{}", hex)
+            );
+            assert_eq!(data.as_display_hex().chunked(0, ":").to_string(), hex);
+            let chunked = data
+                .chunks(3)
+                .map(|chunk| chunk.to_hex())
+                .collect::<Vec<_>>()
+                .join(" - ");
+            assert_eq!(data.as_display_hex().chunked(3, " - ").to_string(), chunked);
+        }
+    }
+
+    #[test]
+    fn display_hex_padding() {
+        let data = [0xab_u8, 0xcd];
+        let hex = data.as_display_hex();
+        assert_eq!(format!("{:8}", hex), "abcd    ");
+        assert_eq!(format!("{:<8}", hex), "abcd    ");
+        assert_eq!(format!("{:>8}", hex), "    abcd");
+        assert_eq!(format!("{:^9}", hex), "  abcd   ");
+        assert_eq!(format!("{:*^#10X}", hex), "**0xABCD**");
+        assert_eq!(format!("{:08x}", hex), "0000abcd");
+        assert_eq!(format!("{:#08x}", hex), "0x00abcd");
+        assert_eq!(format!("{:2}", hex), "abcd");
+        assert_eq!(format!("{:>7}", hex.chunked(1, "·")), "  ab·cd");
+        assert_eq!(format!("{:>4}", [0u8; 0].as_display_hex()), "    ");
+        assert_eq!(format!("{:#}", "ab".as_display_hex()), "0x6162");
+    }
+
+    #[test]
+    fn display_hex_truncated() {
+        let data = [0xde_u8, 0xad, 0xbe, 0xef];
+        let hex = data.as_display_hex();
+        assert_eq!(hex.truncated(2).to_string(), "dead...");
+        assert_eq!(hex.truncated(4).to_string(), "deadbeef");
+        assert_eq!(hex.truncated(16).to_string(), "deadbeef");
+        assert_eq!(hex.truncated(0).to_string(), "...");
+        assert_eq!(format!("{:#X}", hex.truncated(1)), "0xDE...");
+        assert_eq!(format!("{:>9}", hex.truncated(2)), "  dead...");
+        assert_eq!(hex.truncated(3).chunked(2, " ").to_string(), "dead be...");
+        assert_eq!([0u8; 0].as_display_hex().truncated(0).to_string(), "");
+    }
+
+    #[cfg(feature = "std")]
+    mod stream {
+        use super::super::*;
+        use std::io::{self, Cursor, Read, Write};
+
+        /// Adaptor passing at most a single byte per each read or write call
+        struct ByteByByte<T>(T);
+
+        impl<T: Read> Read for ByteByByte<T> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let len = buf.len().min(1);
+                self.0.read(&mut buf[..len])
+            }
+        }
+
+        impl<T: Write> Write for ByteByByte<T> {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                let len = buf.len().min(1);
+                self.0.write(&buf[..len])
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                self.0.flush()
+            }
+        }
+
+        fn sample() -> Vec<u8> {
+            (0..1000u32).map(|i| (i * 7 % 256) as u8).collect()
+        }
+
+        fn stream_error(err: io::Error) -> StreamError {
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            *err.into_inner()
+                .expect("hex stream error")
+                .downcast::<StreamError>()
+                .expect("hex stream error")
+        }
+
+        #[test]
+        fn hex_writer() {
+            let data = sample();
+            let mut writer = HexWriter::new(ByteByByte(Cursor::new(Vec::new())));
+            writer.write_all(&data).unwrap();
+            writer.flush().unwrap();
+            let hex = writer.into_inner().unwrap().0.into_inner();
+            assert_eq!(String::from_utf8(hex).unwrap(), data.to_hex());
+
+            let mut writer = HexWriter::new(Vec::new());
+            for byte in &data {
+                writer.write_all(&[*byte]).unwrap();
+            }
+            assert_eq!(writer.into_inner().unwrap(), data.to_hex().into_bytes());
+
+            let mut writer = HexWriter::new(Vec::new());
+            assert_eq!(writer.write(&[]).unwrap(), 0);
+            assert!(writer.into_inner().unwrap().is_empty());
+        }
+
+        #[test]
+        fn hex_writer_zero() {
+            let mut buf = [0u8; 3];
+            let mut writer = HexWriter::new(&mut buf[..]);
+            assert_eq!(writer.write(&[0xab, 0xcd]).unwrap(), 2);
+            assert_eq!(writer.flush().unwrap_err().kind(), io::ErrorKind::WriteZero);
+        }
+
+        #[test]
+        fn hex_reader() {
+            let data = sample();
+            let hex = data.to_hex();
+
+            let mut reader = HexReader::new(ByteByByte(Cursor::new(hex.as_bytes())), false);
+            let mut decoded = vec![];
+            reader.read_to_end(&mut decoded).unwrap();
+            assert_eq!(decoded, data);
+
+            let mut reader = HexReader::new(Cursor::new(hex.to_uppercase()), false);
+            let mut decoded = vec![];
+            let mut byte = [0u8; 1];
+            while reader.read(&mut byte).unwrap() > 0 {
+                decoded.push(byte[0]);
+            }
+            assert_eq!(decoded, data);
+
+            let mut reader = HexReader::new(Cursor::new(b""), false);
+            assert_eq!(reader.read(&mut [0u8; 4]).unwrap(), 0);
+        }
+
+        #[test]
+        fn hex_reader_whitespace() {
+            let data = sample();
+            let hex = data
+                .to_hex()
+                .as_bytes()
+                .chunks(7)
+                .map(|chunk| std::str::from_utf8(chunk).unwrap())
+                .collect::<Vec<_>>()
+                .join(" \n\t");
+
+            let mut reader = HexReader::new(ByteByByte(Cursor::new(hex.as_bytes())), true);
+            let mut decoded = vec![];
+            reader.read_to_end(&mut decoded).unwrap();
+            assert_eq!(decoded, data);
+
+            let mut reader = HexReader::new(Cursor::new(hex.as_bytes()), false);
+            let err = reader.read_to_end(&mut vec![]).unwrap_err();
+            assert_eq!(
+                stream_error(err),
+                StreamError {
+                    offset: 7,
+                    error: Error::InvalidChar(' ', 7)
+                }
+            );
+        }
+
+        #[test]
+        fn hex_reader_errors() {
+            let mut reader = HexReader::new(ByteByByte(Cursor::new(b"0123\n456")), true);
+            let mut decoded = vec![];
+            let err = reader.read_to_end(&mut decoded).unwrap_err();
+            assert_eq!(decoded, vec![0x01, 0x23, 0x45]);
+            let err = stream_error(err);
+            assert_eq!(
+                err,
+                StreamError {
+                    offset: 7,
+                    error: Error::OddLengthString(7)
+                }
+            );
+            assert_eq!(err.to_string(), "odd hex string length 7 at byte offset 7");
+
+            let mut reader = HexReader::new(Cursor::new(b"0123zz45"), false);
+            let mut buf = [0u8; 8];
+            assert_eq!(reader.read(&mut buf).unwrap(), 2);
+            assert_eq!(&buf[..2], &[0x01, 0x23]);
+            let err = stream_error(reader.read(&mut buf).unwrap_err());
+            assert_eq!(
+                err,
+                StreamError {
+                    offset: 4,
+                    error: Error::InvalidChar('z', 4)
+                }
+            );
+            assert_eq!(err.to_string(), "invalid hex character 'z' at position 4");
+            assert!(reader.read(&mut buf).is_err());
+        }
+    }
+
+    #[test]
+    fn smallint_hex() {
+        assert_eq!(u24::MAX.to_hex(), "ffffff");
+        assert_eq!(u24::MAX.to_hex_upper(), "FFFFFF");
+        assert_eq!(u24::ZERO.to_hex(), "0");
+        assert_eq!(format!("{:06x}", u24::with(0xabc)), "000abc");
+        assert_eq!(format!("{:#X}", u24::with(0xabc)), "0xABC");
+        assert_eq!(u24::from_hex("ffffff"), Ok(u24::MAX));
+        assert_eq!(u24::from_hex("000abc"), Ok(u24::with(0xabc)));
+        assert_eq!(u24::from_hex("AbC"), Ok(u24::with(0xabc)));
+        assert_eq!(u24::from_hex("0"), Ok(u24::ZERO));
+        assert_eq!(u24::from_hex_prefixed("0x12345"), Ok(u24::with(0x12345)));
+        assert_eq!(u24::from_hex(""), Err(Error::InvalidLength(6, 0)));
+        assert_eq!(u24::from_hex("1000000"), Err(Error::InvalidLength(6, 7)));
+        assert_eq!(u24::from_hex("12g"), Err(Error::InvalidChar('g', 2)));
+        assert_eq!(u24::from_hex("1ä"), Err(Error::InvalidChar('ä', 1)));
+        assert_eq!(u24::from_hex("10x1"), Err(Error::MisplacedPrefix(1)));
+        assert_eq!(
+            u24::from_byte_iter(HexIterator::new("abcdef").unwrap()),
+            Ok(u24::from_be_bytes([0xab, 0xcd, 0xef]))
+        );
+        assert_eq!(
+            u24::from_byte_iter(HexIterator::new("00abcdef").unwrap()),
+            Err(Error::InvalidLength(6, 8))
+        );
+
+        assert_eq!(u5::MAX.to_hex(), "1f");
+        assert_eq!(u5::with(7).to_hex(), "7");
+        assert_eq!(u5::from_hex("1f"), Ok(u5::MAX));
+        assert_eq!(u5::from_hex("1F"), Ok(u5::MAX));
+        assert_eq!(u5::from_hex("07"), Ok(u5::with(7)));
+        assert_eq!(u5::from_hex("7"), Ok(u5::with(7)));
+        assert_eq!(u5::from_hex("007"), Err(Error::InvalidLength(2, 3)));
+        assert_eq!(
+            u5::from_hex("20"),
+            Err(Error::Overflow(OverflowError { max: 31, value: 32 }))
+        );
+        assert_eq!(
+            u5::from_hex("ff").unwrap_err().to_string(),
+            "hex number 0xff exceeds maximal value 0x1f"
+        );
+        assert_eq!(u1::from_hex("1"), Ok(u1::ONE));
+        assert_eq!(
+            u1::from_hex("2"),
+            Err(Error::Overflow(OverflowError { max: 1, value: 2 }))
+        );
+        for value in 0..32u8 {
+            let value = u5::with(value);
+            assert_eq!(u5::from_hex(&value.to_hex()), Ok(value));
+            assert_eq!(u5::from_hex(&format!("{:02x}", value)), Ok(value));
+            assert_eq!(u5::from_hex(&value.to_hex_upper()), Ok(value));
+        }
+    }
+
+    #[test]
+    fn u24_hex_random() {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..10_000 {
+            let value = u24::from_masked(rng.gen());
+            assert_eq!(u24::from_hex(&value.to_hex()), Ok(value));
+            assert_eq!(u24::from_hex(&format!("{:06X}", value)), Ok(value));
+            assert_eq!(u24::from_hex_any(&format!("{:#x}", value)), Ok(value));
+            let bytes = value.to_be_bytes().to_vec();
+            assert_eq!(u24::from_hex(&bytes.to_hex()), Ok(value));
+        }
     }
 }
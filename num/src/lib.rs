@@ -25,6 +25,24 @@
 //! * 24-bit signed integer.
 //!
 //! The functions here are designed to be fast.
+//!
+//! Integer constructors, byte conversions and `const_*` arithmetic and
+//! bitwise operations of big integers, as well as constructors of small
+//! integers, are `const fn` and can be used to define constants. This
+//! requires rust compiler version 1.57 or newer.
+//!
+//! With `serde` feature (implies `std` and `hex`) big unsigned integers
+//! implement `Serialize` and `Deserialize`, using decimal strings for
+//! human-readable formats and little-endian byte arrays for binary ones.
+//!
+//! Integers can be parsed from and formatted into strings with decimal,
+//! binary or custom scale suffixes, like `64k` or `2MiB`, using functions
+//! from [`scaled`] module. Human-readable formatting of integers with digit
+//! grouping and fixed decimal scaling is provided by [`decimal`] module.
+//!
+//! With `rand` feature all the integer types can be generated with
+//! `rand::Rng::gen` and sampled uniformly within a range with
+//! `rand::Rng::gen_range`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #[cfg(feature = "alloc")]
@@ -36,14 +54,20 @@ extern crate core;
 extern crate serde_crate as serde;
 
 mod bigint;
+pub mod decimal;
 mod divrem;
 pub mod error;
 #[cfg(feature = "hex")]
 pub mod hex;
+pub mod scaled;
 mod smallint;
 
-pub use bigint::{u256, u512, u1024};
+pub use bigint::{u256, u512, u1024, i256, i512, i1024};
 pub use smallint::{u1, u2, u3, u4, u5, u6, u7, u24};
+#[cfg(feature = "rand")]
+pub use bigint::UniformBigint;
+#[cfg(feature = "rand")]
+pub use smallint::UniformSmallint;
 
 // TODO: Create arbitrary precision types
 // TODO: Move from using `u64` to `u128` for big int types
@@ -0,0 +1,355 @@
+// Rust language amplification library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! # Integers with scale suffixes
+//!
+//! Parsing and formatting of unsigned integers with decimal (`k`, `M`, `G`,
+//! `T`), binary (`Ki`, `Mi`, `Gi`, `Ti`) or custom scale suffixes, like `64k`
+//! or `2MiB`. Digits may be separated with underscores, and the suffix may be
+//! separated from the number with whitespaces. Any integer type convertible
+//! from [`u1024`] is supported, including [`crate::u256`].
+//!
+//! Custom suffixes are provided as a table of [`Scale`]s; if several entries
+//! match the same suffix, the first one is used.
+//!
+//! ```
+//! use amplify_num::error::ParseScaledError;
+//! use amplify_num::scaled::{self, parse_scaled, parse_scaled_with, Scale, BYTE_SCALES};
+//!
+//! assert_eq!(parse_scaled::<u64>("64k"), Ok(64_000));
+//! assert_eq!(parse_scaled::<u32>("1_024 Ki"), Ok(1 << 20));
+//! assert_eq!(parse_scaled::<u16>("64Ki"), Err(ParseScaledError::Overflow));
+//! assert_eq!(parse_scaled_with::<u64>("2MiB", BYTE_SCALES), Ok(2 << 20));
+//!
+//! const SATS: &[Scale] = &[Scale::new("sat", 1), Scale::new("btc", 100_000_000)];
+//! assert_eq!(parse_scaled_with::<u64>("21btc", SATS), Ok(2_100_000_000));
+//! assert_eq!(scaled::format_scaled_with(2_100_000_000u64, SATS), "21btc");
+//! assert_eq!(scaled::format_scaled(3_145_728u64), "3Mi");
+//! ```
+
+use core::convert::TryFrom;
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String};
+
+use crate::error::{ParseIntError, ParseScaledError};
+use crate::u1024;
+
+/// Scale suffix with its multiplier
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Scale {
+    /// Suffix following the number
+    pub suffix: &'static str,
+    /// Multiplier applied to the number
+    pub multiplier: u128,
+    /// Whether the suffix must match exactly, or may be given in any case
+    pub case_sensitive: bool,
+}
+
+impl Scale {
+    /// Constructs case-sensitive scale suffix
+    pub const fn new(suffix: &'static str, multiplier: u128) -> Scale {
+        Scale {
+            suffix,
+            multiplier,
+            case_sensitive: true,
+        }
+    }
+
+    /// Makes the suffix case-insensitive
+    pub const fn case_insensitive(self) -> Scale {
+        Scale {
+            case_sensitive: false,
+            ..self
+        }
+    }
+
+    /// Checks whether the given suffix matches the scale
+    pub fn matches(&self, suffix: &str) -> bool {
+        if self.case_sensitive {
+            self.suffix == suffix
+        } else {
+            self.suffix.eq_ignore_ascii_case(suffix)
+        }
+    }
+}
+
+/// Decimal and binary scale suffixes. `M` is case-sensitive, since `m`
+/// commonly stands for "milli"; other suffixes are case-insensitive.
+pub const SCALES: &[Scale] = &[
+    Scale::new("k", 1_000).case_insensitive(),
+    Scale::new("M", 1_000_000),
+    Scale::new("G", 1_000_000_000).case_insensitive(),
+    Scale::new("T", 1_000_000_000_000).case_insensitive(),
+    Scale::new("Ki", 1 << 10).case_insensitive(),
+    Scale::new("Mi", 1 << 20).case_insensitive(),
+    Scale::new("Gi", 1 << 30).case_insensitive(),
+    Scale::new("Ti", 1 << 40).case_insensitive(),
+];
+
+/// Scale suffixes for the data sizes in bytes, like `64kB` or `2MiB`. The
+/// suffixes are case-sensitive, since lowercase `b` stands for bits.
+pub const BYTE_SCALES: &[Scale] = &[
+    Scale::new("B", 1),
+    Scale::new("kB", 1_000),
+    Scale::new("MB", 1_000_000),
+    Scale::new("GB", 1_000_000_000),
+    Scale::new("TB", 1_000_000_000_000),
+    Scale::new("KiB", 1 << 10),
+    Scale::new("MiB", 1 << 20),
+    Scale::new("GiB", 1 << 30),
+    Scale::new("TiB", 1 << 40),
+];
+
+/// Parses integer with optional decimal or binary scale suffix from
+/// [`SCALES`], like `64k` or `16Mi`.
+#[inline]
+pub fn parse_scaled<T>(s: &str) -> Result<T, ParseScaledError>
+where
+    T: TryFrom<u1024>,
+{
+    parse_scaled_with(s, SCALES)
+}
+
+/// Parses integer with optional scale suffix from the provided table.
+///
+/// # Errors
+///
+/// - [`ParseScaledError::Number`] if the number part is absent or starts
+///   with an underscore;
+/// - [`ParseScaledError::UnknownSuffix`] if the suffix is not present in
+///   `scales`;
+/// - [`ParseScaledError::Overflow`] if the scaled value does not fit `T`.
+pub fn parse_scaled_with<T>(s: &str, scales: &[Scale]) -> Result<T, ParseScaledError>
+where
+    T: TryFrom<u1024>,
+{
+    let end = s
+        .find(|ch: char| !ch.is_ascii_digit() && ch != '_')
+        .unwrap_or(s.len());
+    let (number, rest) = s.split_at(end);
+    match number.chars().next() {
+        Some(ch) if ch.is_ascii_digit() => {}
+        _ => {
+            let err = match s.chars().next() {
+                None => ParseIntError::Empty,
+                Some(ch) => ParseIntError::InvalidDigit(ch, 0),
+            };
+            return Err(ParseScaledError::Number(err));
+        }
+    }
+
+    let mut value = u1024::ZERO;
+    for digit in number.bytes().filter(|byte| *byte != b'_') {
+        value = value
+            .checked_mul(10u8)
+            .and_then(|value| value.checked_add(digit - b'0'))
+            .ok_or(ParseScaledError::Overflow)?;
+    }
+
+    let suffix = rest.trim_start();
+    if !rest.is_empty() {
+        let pos = s.len() - suffix.len();
+        let scale = scales
+            .iter()
+            .find(|scale| scale.matches(suffix))
+            .filter(|_| !suffix.is_empty())
+            .ok_or(ParseScaledError::UnknownSuffix(pos))?;
+        value = value
+            .checked_mul(scale.multiplier)
+            .ok_or(ParseScaledError::Overflow)?;
+    }
+
+    T::try_from(value).map_err(|_| ParseScaledError::Overflow)
+}
+
+/// Formats integer using the shortest exact representation with a scale
+/// suffix from [`SCALES`], such that [`parse_scaled`] returns the same value.
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[inline]
+pub fn format_scaled<T>(value: T) -> String
+where
+    T: Into<u1024>,
+{
+    format_scaled_with(value, SCALES)
+}
+
+/// Formats integer using the shortest exact representation with a scale
+/// suffix from the provided table, such that [`parse_scaled_with`] with the
+/// same table returns the same value. If there is no shorter representation,
+/// the number is formatted without a suffix.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub fn format_scaled_with<T>(value: T, scales: &[Scale]) -> String
+where
+    T: Into<u1024>,
+{
+    let value = value.into();
+    let mut shortest = format!("{}", value);
+    if value == u1024::ZERO {
+        return shortest;
+    }
+    for scale in scales {
+        // Scales, which are shadowed by a previous table entry, can't be parsed
+        // back
+        let first = scales.iter().find(|other| other.matches(scale.suffix));
+        if scale.multiplier == 0 || first != Some(scale) {
+            continue;
+        }
+        let multiplier = u1024::from(scale.multiplier);
+        if value % multiplier != u1024::ZERO {
+            continue;
+        }
+        let scaled = format!("{}{}", value / multiplier, scale.suffix);
+        if scaled.len() < shortest.len() {
+            shortest = scaled;
+        }
+    }
+    shortest
+}
+
+#[cfg(test)]
+mod test {
+    use rand::Rng;
+
+    use super::*;
+    use crate::u256;
+
+    #[test]
+    fn parse() {
+        assert_eq!(parse_scaled::<u8>("0"), Ok(0));
+        assert_eq!(parse_scaled::<u64>("64k"), Ok(64_000));
+        assert_eq!(parse_scaled::<u64>("64K"), Ok(64_000));
+        assert_eq!(parse_scaled::<u64>("2M"), Ok(2_000_000));
+        assert_eq!(parse_scaled::<u64>("3g"), Ok(3_000_000_000));
+        assert_eq!(parse_scaled::<u64>("4T"), Ok(4_000_000_000_000));
+        assert_eq!(parse_scaled::<u64>("2ki"), Ok(2048));
+        assert_eq!(parse_scaled::<u64>("2MI"), Ok(2 << 20));
+        assert_eq!(parse_scaled::<u64>("1Gi"), Ok(1 << 30));
+        assert_eq!(parse_scaled::<u64>("1 Ti"), Ok(1 << 40));
+        assert_eq!(parse_scaled::<u64>("1_000_000"), Ok(1_000_000));
+        assert_eq!(parse_scaled::<u64>("1__0_k"), Ok(10_000));
+        assert_eq!(
+            parse_scaled::<u256>("1_000_000T"),
+            Ok(u256::from(1_000_000_000_000_000_000u128))
+        );
+        assert_eq!(parse_scaled::<u8>("255"), Ok(255));
+        assert_eq!(parse_scaled_with::<u64>("1kB", BYTE_SCALES), Ok(1000));
+        assert_eq!(parse_scaled_with::<u64>("8B", BYTE_SCALES), Ok(8));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            parse_scaled::<u64>(""),
+            Err(ParseScaledError::Number(ParseIntError::Empty))
+        );
+        assert_eq!(
+            parse_scaled::<u64>("k"),
+            Err(ParseScaledError::Number(ParseIntError::InvalidDigit(
+                'k', 0
+            )))
+        );
+        assert_eq!(
+            parse_scaled::<u64>("_1"),
+            Err(ParseScaledError::Number(ParseIntError::InvalidDigit(
+                '_', 0
+            )))
+        );
+        assert_eq!(
+            parse_scaled::<u64>("-1"),
+            Err(ParseScaledError::Number(ParseIntError::InvalidDigit(
+                '-', 0
+            )))
+        );
+        assert_eq!(
+            parse_scaled::<u64>("2m"),
+            Err(ParseScaledError::UnknownSuffix(1))
+        );
+        assert_eq!(
+            parse_scaled::<u64>("2  x"),
+            Err(ParseScaledError::UnknownSuffix(3))
+        );
+        assert_eq!(
+            parse_scaled::<u64>("2 "),
+            Err(ParseScaledError::UnknownSuffix(2))
+        );
+        assert_eq!(
+            parse_scaled::<u64>("1.5k"),
+            Err(ParseScaledError::UnknownSuffix(1))
+        );
+        assert_eq!(
+            parse_scaled_with::<u64>("2mib", BYTE_SCALES),
+            Err(ParseScaledError::UnknownSuffix(1))
+        );
+        assert_eq!(parse_scaled::<u8>("256"), Err(ParseScaledError::Overflow));
+        assert_eq!(parse_scaled::<u8>("1k"), Err(ParseScaledError::Overflow));
+        assert_eq!(
+            parse_scaled::<u64>("18_446_744_073_709_552k"),
+            Err(ParseScaledError::Overflow)
+        );
+        assert_eq!(
+            parse_scaled::<u256>(&format!("{}0", u256::MAX)),
+            Err(ParseScaledError::Overflow)
+        );
+        assert_eq!(
+            parse_scaled::<u1024>(&format!("{}Ki", u1024::MAX)),
+            Err(ParseScaledError::Overflow)
+        );
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(format_scaled(0u8), "0");
+        assert_eq!(format_scaled(999u16), "999");
+        assert_eq!(format_scaled(1000u16), "1k");
+        assert_eq!(format_scaled(1024u16), "1Ki");
+        assert_eq!(format_scaled(1536u16), "1536");
+        assert_eq!(format_scaled(2_000_000u32), "2M");
+        assert_eq!(format_scaled(64_000u32), "64k");
+        assert_eq!(format_scaled(1u64 << 40), "1Ti");
+        assert_eq!(format_scaled(1_000_000_000_000_000u64), "1000T");
+        assert_eq!(format_scaled_with(1u64 << 20, BYTE_SCALES), "1MiB");
+        assert_eq!(format_scaled_with(3u8, BYTE_SCALES), "3");
+
+        const SHADOWED: &[Scale] = &[
+            Scale::new("x", 1_000).case_insensitive(),
+            Scale::new("X", 1_000_000),
+        ];
+        assert_eq!(format_scaled_with(1_000_000u32, SHADOWED), "1000x");
+    }
+
+    #[test]
+    fn round_trip() {
+        const SATS: &[Scale] = &[Scale::new("sat", 1), Scale::new("btc", 100_000_000)];
+        let mut rng = rand::thread_rng();
+        for _ in 0..1000 {
+            let value = rng.gen::<u64>();
+            assert_eq!(parse_scaled::<u64>(&format_scaled(value)), Ok(value));
+
+            let multiplier = SCALES[rng.gen_range(0..SCALES.len())].multiplier as u64;
+            let value = rng.gen_range(0..=u64::MAX / multiplier) * multiplier;
+            assert_eq!(parse_scaled::<u64>(&format_scaled(value)), Ok(value));
+
+            let value = rng.gen::<u32>() as u64 * 100_000_000;
+            let formatted = format_scaled_with(value, SATS);
+            assert_eq!(parse_scaled_with::<u64>(&formatted, SATS), Ok(value));
+
+            let value = u256::from_le_bytes(rng.gen::<[u8; 32]>());
+            assert_eq!(parse_scaled::<u256>(&format_scaled(value)), Ok(value));
+
+            let value = u256::from(rng.gen::<u128>()) * u256::from(1u64 << 30);
+            assert_eq!(parse_scaled::<u256>(&format_scaled(value)), Ok(value));
+        }
+    }
+}
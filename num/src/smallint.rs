@@ -23,6 +23,15 @@ use crate::error::OverflowError;
 
 use crate::divrem::DivRem;
 
+/// Sampler of small integers uniformly distributed within a range, used by
+/// `rand::Rng::gen_range` and `rand::distributions::Uniform`
+#[cfg(feature = "rand")]
+#[derive(Copy, Clone, Debug)]
+pub struct UniformSmallint<T> {
+    inner: ::rand::distributions::uniform::UniformInt<u32>,
+    _phantom: ::core::marker::PhantomData<T>,
+}
+
 macro_rules! construct_smallint {
     ($ty:ident, $inner:ident, $as:ident, $bits:literal, $max:expr, $doc:meta) => {
         #[$doc]
@@ -53,16 +62,32 @@ macro_rules! construct_smallint {
 
             /// Creates a new value from a provided `value.
             ///
-            /// Panics if the value exceeds `Self::MAX`
-            pub fn with(value: $inner) -> Self {
+            /// Panics if the value exceeds `Self::MAX`; in `const` context the
+            /// panic is reported as a compilation error
+            pub const fn with(value: $inner) -> Self {
                 assert!(value < $max, "provided value exceeds Self::MAX");
                 Self(value)
             }
 
             /// Returns inner `u8` representation, which is always less or equal to `Self::MAX`
-            pub fn $as(self) -> $inner {
+            pub const fn $as(self) -> $inner {
                 self.0 as $inner
             }
+
+            /// Creates a new value from the lowest `Self::BITS` bits of the
+            /// provided value, discarding the higher bits
+            #[inline]
+            pub const fn from_masked(value: $inner) -> Self {
+                Self(value & Self::MAX.0)
+            }
+        }
+
+        impl ::core::ops::Not for $ty {
+            type Output = Self;
+            #[inline]
+            fn not(self) -> Self::Output {
+                Self::from_masked(!self.0)
+            }
         }
 
         impl ::core::convert::TryFrom<$inner> for $ty {
@@ -183,7 +208,7 @@ macro_rules! construct_smallint {
             pub fn overflowing_add<T>(self, rhs: T) -> (Self, bool) where T: Into<$inner> {
                 let mut ret = self.0.overflowing_add(rhs.into());
                 if ret.0 > Self::MAX.0 {
-                    ret.0 %= Self::MAX.0;
+                    ret.0 %= $max;
                     ret.1 = true;
                 }
                 (Self(ret.0), ret.1)
@@ -191,7 +216,7 @@ macro_rules! construct_smallint {
             /// Wrapping (modular) addition. Computes `self + rhs`, wrapping around at
             /// the boundary of the type.
             pub fn wrapping_add<T>(self, rhs: T) -> Self where T: Into<$inner> {
-                Self(self.0.wrapping_add(rhs.into()) % Self::MAX.0)
+                Self(self.0.wrapping_add(rhs.into()) % $max)
             }
 
             /// Checked integer subtraction. Computes `self - rhs`, returning `None` if
@@ -217,7 +242,7 @@ macro_rules! construct_smallint {
             pub fn overflowing_sub<T>(self, rhs: T) -> (Self, bool) where T: Into<$inner> {
                 let mut ret = self.0.overflowing_sub(rhs.into());
                 if ret.0 > Self::MAX.0 {
-                    ret.0 %= Self::MAX.0;
+                    ret.0 %= $max;
                     ret.1 = true;
                 }
                 (Self(ret.0), ret.1)
@@ -225,7 +250,7 @@ macro_rules! construct_smallint {
             /// Wrapping (modular) subtraction. Computes `self - rhs`, wrapping around at
             /// the boundary of the type.
             pub fn wrapping_sub<T>(self, rhs: T) -> Self where T: Into<$inner> {
-                Self(self.0.wrapping_sub(rhs.into()) % Self::MAX.0)
+                Self(self.0.wrapping_sub(rhs.into()) % $max)
             }
 
             /// Checked integer multiplication. Computes `self * rhs`, returning `None` if
@@ -251,7 +276,7 @@ macro_rules! construct_smallint {
             pub fn overflowing_mul<T>(self, rhs: T) -> (Self, bool) where T: Into<$inner> {
                 let mut ret = self.0.overflowing_mul(rhs.into());
                 if ret.0 > Self::MAX.0 {
-                    ret.0 %= Self::MAX.0;
+                    ret.0 %= $max;
                     ret.1 = true;
                 }
                 (Self(ret.0), ret.1)
@@ -259,9 +284,130 @@ macro_rules! construct_smallint {
             /// Wrapping (modular) multiplication. Computes `self * rhs`, wrapping around at
             /// the boundary of the type.
             pub fn wrapping_mul<T>(self, rhs: T) -> Self where T: Into<$inner> {
-                Self(self.0.wrapping_mul(rhs.into()) % Self::MAX.0)
+                Self(self.0.wrapping_mul(rhs.into()) % $max)
+            }
+
+            /// Checked integer division. Computes `self / rhs`, returning `None`
+            /// if `rhs == 0`.
+            pub fn checked_div<T>(self, rhs: T) -> Option<Self> where T: Into<$inner> {
+                self.0.checked_div(rhs.into()).map(Self)
+            }
+            /// Wrapping division. Computes `self / rhs`, which never overflows
+            /// for unsigned types.
+            ///
+            /// Panics if `rhs == 0`.
+            pub fn wrapping_div<T>(self, rhs: T) -> Self where T: Into<$inner> {
+                Self(self.0 / rhs.into())
+            }
+
+            /// Checked integer remainder. Computes `self % rhs`, returning `None`
+            /// if `rhs == 0`.
+            pub fn checked_rem<T>(self, rhs: T) -> Option<Self> where T: Into<$inner> {
+                self.0.checked_rem(rhs.into()).map(Self)
+            }
+            /// Wrapping remainder. Computes `self % rhs`, which never overflows
+            /// for unsigned types.
+            ///
+            /// Panics if `rhs == 0`.
+            pub fn wrapping_rem<T>(self, rhs: T) -> Self where T: Into<$inner> {
+                Self(self.0 % rhs.into())
+            }
+        }
+
+        #[cfg(feature = "rand")]
+        impl ::rand::distributions::Distribution<$ty> for ::rand::distributions::Standard {
+            fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                // The type dimension is a power of two, so the remainder is
+                // uniformly distributed
+                $ty(rng.gen::<$inner>() % $max)
+            }
+        }
+
+        #[cfg(feature = "rand")]
+        impl ::rand::distributions::uniform::SampleUniform for $ty {
+            type Sampler = UniformSmallint<$ty>;
+        }
+
+        #[cfg(feature = "rand")]
+        impl ::rand::distributions::uniform::UniformSampler for UniformSmallint<$ty> {
+            type X = $ty;
+
+            fn new<B1, B2>(low: B1, high: B2) -> Self
+            where
+                B1: ::rand::distributions::uniform::SampleBorrow<$ty> + Sized,
+                B2: ::rand::distributions::uniform::SampleBorrow<$ty> + Sized,
+            {
+                UniformSmallint {
+                    inner: ::rand::distributions::uniform::UniformInt::<u32>::new(
+                        low.borrow().0 as u32,
+                        high.borrow().0 as u32,
+                    ),
+                    _phantom: ::core::marker::PhantomData,
+                }
+            }
+
+            fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+            where
+                B1: ::rand::distributions::uniform::SampleBorrow<$ty> + Sized,
+                B2: ::rand::distributions::uniform::SampleBorrow<$ty> + Sized,
+            {
+                UniformSmallint {
+                    inner: ::rand::distributions::uniform::UniformInt::<u32>::new_inclusive(
+                        low.borrow().0 as u32,
+                        high.borrow().0 as u32,
+                    ),
+                    _phantom: ::core::marker::PhantomData,
+                }
+            }
+
+            fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                $ty(self.inner.sample(rng) as $inner)
+            }
+        }
+    };
+}
+macro_rules! impl_try_from {
+    ($ty:ty, $max:expr, $($from:ty),+) => {
+        $(
+        impl ::core::convert::TryFrom<$from> for $ty {
+            type Error = OverflowError;
+            #[inline]
+            fn try_from(value: $from) -> Result<Self, Self::Error> {
+                if value >= $max as $from {
+                    Err(OverflowError {
+                        max: $max as usize - 1,
+                        value: usize::try_from(value).unwrap_or(!0),
+                    })
+                } else {
+                    Ok(Self(value as _))
+                }
+            }
+        }
+        )+
+    };
+}
+macro_rules! impl_from {
+    ($ty:ty, $($from:ty),+) => {
+        $(
+        impl From<$from> for $ty {
+            #[inline]
+            fn from(value: $from) -> Self {
+                Self(value.into())
+            }
+        }
+        )+
+    };
+}
+macro_rules! impl_into {
+    ($ty:ty, $($into:ty),+) => {
+        $(
+        impl From<$ty> for $into {
+            #[inline]
+            fn from(value: $ty) -> Self {
+                value.0.into()
             }
         }
+        )+
     };
 }
 macro_rules! impl_op {
@@ -270,11 +416,8 @@ macro_rules! impl_op {
             type Output = $ty;
             #[inline]
             fn $fn(self, rhs: T) -> Self::Output {
-                Self::try_from((self.0).$fn(rhs.into())).expect(stringify!(
-                    "attempt to ",
-                    $fn,
-                    " with overflow"
-                ))
+                Self::try_from((self.0).$fn(rhs.into()))
+                    .expect(concat!("attempt to ", stringify!($fn), " with overflow"))
             }
         }
         impl<T> $op<T> for &$ty where T: Into<$inner> {
@@ -301,7 +444,7 @@ construct_smallint!(
     1,
     2,
     doc =
-        "1-bit unsigned integer in the range `0..1`. It can be used instead of `bool` when 1-bit \
+        "1-bit unsigned integer in the range `0..2`. It can be used instead of `bool` when 1-bit \
     numeric (and not boolean) arithmetic is required"
 );
 construct_smallint!(
@@ -361,37 +504,50 @@ construct_smallint!(
     doc = "24-bit unsigned integer in the range `0..16_777_216`"
 );
 
+impl_try_from!(u1, 2u8, u16, u32, u64, usize);
+impl_try_from!(u2, 4u8, u16, u32, u64, usize);
+impl_try_from!(u3, 8u8, u16, u32, u64, usize);
+impl_try_from!(u4, 16u8, u16, u32, u64, usize);
+impl_try_from!(u5, 32u8, u16, u32, u64, usize);
+impl_try_from!(u6, 64u8, u16, u32, u64, usize);
+impl_try_from!(u7, 128u8, u16, u32, u64, usize);
+impl_try_from!(u24, 1u32 << 24, u64, usize);
+impl_from!(u24, u8, u16);
+
+impl_into!(u1, u16, u32, u64, u128, usize);
+impl_into!(u2, u16, u32, u64, u128, usize);
+impl_into!(u3, u16, u32, u64, u128, usize);
+impl_into!(u4, u16, u32, u64, u128, usize);
+impl_into!(u5, u16, u32, u64, u128, usize);
+impl_into!(u6, u16, u32, u64, u128, usize);
+impl_into!(u7, u16, u32, u64, u128, usize);
+impl_into!(u24, u64, u128);
+
 impl u24 {
     /// Create a native endian integer value from its representation as a byte
     /// array in little endian.
-    pub fn from_le_bytes(bytes: [u8; 3]) -> u24 {
-        let mut inner = [0u8; 4];
-        inner[..3].copy_from_slice(&bytes);
-        Self(u32::from_le_bytes(inner))
+    pub const fn from_le_bytes(bytes: [u8; 3]) -> u24 {
+        Self(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]))
     }
 
     /// Return the memory representation of this integer as a byte array in
     /// little-endian byte order.
-    pub fn to_le_bytes(self) -> [u8; 3] {
-        let mut inner = [0u8; 3];
-        inner.copy_from_slice(&self.0.to_le_bytes()[..3]);
-        inner
+    pub const fn to_le_bytes(self) -> [u8; 3] {
+        let bytes = self.0.to_le_bytes();
+        [bytes[0], bytes[1], bytes[2]]
     }
 
     /// Create a native endian integer value from its representation as a byte
     /// array in big endian.
-    pub fn from_be_bytes(bytes: [u8; 3]) -> u24 {
-        let mut inner = [0u8; 4];
-        inner[1..].copy_from_slice(&bytes);
-        Self(u32::from_be_bytes(inner))
+    pub const fn from_be_bytes(bytes: [u8; 3]) -> u24 {
+        Self(u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]))
     }
 
     /// Return the memory representation of this integer as a byte array in
     /// big-endian byte order.
-    pub fn to_be_bytes(self) -> [u8; 3] {
-        let mut inner = [0u8; 3];
-        inner.copy_from_slice(&self.0.to_be_bytes()[1..]);
-        inner
+    pub const fn to_be_bytes(self) -> [u8; 3] {
+        let bytes = self.0.to_be_bytes();
+        [bytes[1], bytes[2], bytes[3]]
     }
 }
 
@@ -399,6 +555,21 @@ impl u24 {
 mod test {
     use super::*;
 
+    const U5: u5 = u5::with(31);
+    const U24: u24 = u24::with(0xABCDEF);
+    const _: () = assert!(U5.as_u8() == 31);
+    const _: () = assert!(u5::from_masked(0xFF).as_u8() == U5.as_u8());
+    const _: () = assert!(U24.to_be_bytes()[0] == 0xAB);
+    const _: () = assert!(U24.to_le_bytes()[0] == 0xEF);
+    const _: () = assert!(u24::from_le_bytes([0xEF, 0xCD, 0xAB]).as_u32() == U24.as_u32());
+    const _: () = assert!(u24::from_be_bytes([0xAB, 0xCD, 0xEF]).as_u32() == U24.as_u32());
+
+    #[test]
+    #[should_panic(expected = "provided value exceeds Self::MAX")]
+    fn with_overflow() {
+        u5::with(32);
+    }
+
     #[test]
     fn ubit_test() {
         let mut u_2 = u2::try_from(u2::MAX.as_u8()).unwrap();
@@ -488,43 +659,43 @@ mod test {
     #[test]
     #[should_panic(expected = "OverflowError { max: 3, value: 4 }")]
     fn u2_overflow_test() {
-        u2::try_from(4).unwrap();
+        u2::try_from(4u8).unwrap();
     }
 
     #[test]
     #[should_panic(expected = "OverflowError { max: 7, value: 8 }")]
     fn u3_overflow_test() {
-        u3::try_from(8).unwrap();
+        u3::try_from(8u8).unwrap();
     }
 
     #[test]
     #[should_panic(expected = "OverflowError { max: 15, value: 16 }")]
     fn u4_overflow_test() {
-        u4::try_from(16).unwrap();
+        u4::try_from(16u8).unwrap();
     }
 
     #[test]
     #[should_panic(expected = "OverflowError { max: 31, value: 32 }")]
     fn u5_overflow_test() {
-        u5::try_from(32).unwrap();
+        u5::try_from(32u8).unwrap();
     }
 
     #[test]
     #[should_panic(expected = "OverflowError { max: 63, value: 64 }")]
     fn u6_overflow_test() {
-        u6::try_from(64).unwrap();
+        u6::try_from(64u8).unwrap();
     }
 
     #[test]
     #[should_panic(expected = "OverflowError { max: 127, value: 128 }")]
     fn u7_overflow_test() {
-        u7::try_from(128).unwrap();
+        u7::try_from(128u8).unwrap();
     }
 
     #[test]
     #[should_panic(expected = "OverflowError { max: 16777215, value: 16777216 }")]
     fn u24_overflow_test() {
-        u24::try_from(1 << 24).unwrap();
+        u24::try_from(1u32 << 24).unwrap();
     }
 
     #[test]
@@ -543,7 +714,7 @@ mod test {
     #[test]
     fn smallint_div_rem_checked() {
         let u_2 = u2::MAX;
-        let u_2_2 = u2::try_from(2).unwrap();
+        let u_2_2 = u2::try_from(2u8).unwrap();
         let u_2_half = (
             u2::try_from(u2::MAX / 2).unwrap(),
             u2::try_from(u2::MAX % 2).unwrap(),
@@ -636,4 +807,254 @@ mod test {
         assert_eq!(format!("{:#b}", u_7), "0b1111111");
         assert_eq!(format!("{:#b}", u_24), "0b111111111111111111111111");
     }
+
+    #[test]
+    fn u5_exhaustive() {
+        for a in 0..32u8 {
+            let x = u5::with(a);
+            assert_eq!(x.to_string(), a.to_string());
+            assert_eq!(a.to_string().parse(), Ok(x));
+            assert_eq!(u8::from(x), a);
+            assert_eq!(u32::from(x), a as u32);
+            assert_eq!(u5::try_from(a as u64), Ok(x));
+            assert_eq!(u5::try_from(a as usize), Ok(x));
+            for b in 0..32u8 {
+                let y = u5::with(b);
+                let sum = a + b;
+                let diff = a.wrapping_sub(b);
+                let prod = a as u16 * b as u16;
+
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+                assert_eq!(x.checked_add(b), u5::try_from(sum).ok());
+                assert_eq!(x.checked_sub(b), a.checked_sub(b).map(u5::with));
+                assert_eq!(x.checked_mul(b), u5::try_from(prod).ok());
+                assert_eq!(x.checked_mul(b).is_some(), prod < 32);
+                assert_eq!(x.checked_div(b), a.checked_div(b).map(u5::with));
+                assert_eq!(x.checked_rem(b), a.checked_rem(b).map(u5::with));
+                assert_eq!(x.wrapping_add(b), u5::with(sum % 32));
+                assert_eq!(x.wrapping_sub(b), u5::with(diff % 32));
+                assert_eq!(x.wrapping_mul(b), u5::with((prod % 32) as u8));
+                assert_eq!(x.overflowing_add(b), (u5::with(sum % 32), sum >= 32));
+                assert_eq!(x.overflowing_sub(b), (u5::with(diff % 32), a < b));
+                assert_eq!(
+                    x.overflowing_mul(b),
+                    (u5::with((prod % 32) as u8), prod >= 32)
+                );
+                assert_eq!(x.saturating_add(b), u5::with(sum.min(31)));
+                assert_eq!(x.saturating_sub(b), u5::with(a.saturating_sub(b)));
+                assert_eq!(x.saturating_mul(b), u5::with(prod.min(31) as u8));
+                assert_eq!(x | y, u5::with(a | b));
+                assert_eq!(x & y, u5::with(a & b));
+                assert_eq!(x ^ y, u5::with(a ^ b));
+                if b != 0 {
+                    assert_eq!(x.wrapping_div(b), u5::with(a / b));
+                    assert_eq!(x.wrapping_rem(b), u5::with(a % b));
+                }
+            }
+        }
+        assert_eq!(
+            u5::try_from(32u32),
+            Err(OverflowError { max: 31, value: 32 })
+        );
+        assert_eq!(
+            u5::try_from(0x1_0000_0000u64),
+            Err(OverflowError {
+                max: 31,
+                value: usize::try_from(0x1_0000_0000u64).unwrap_or(!0)
+            })
+        );
+        assert!("32".parse::<u5>().is_err());
+    }
+
+    macro_rules! test_u8_based {
+        ($ty:ident) => {
+            let max = $ty::MAX.as_u8();
+            let modulo = max as u16 + 1;
+            assert_eq!(modulo, 1 << $ty::BITS);
+            assert_eq!($ty::MIN.as_u8(), 0);
+            for value in 0..=255u8 {
+                assert_eq!($ty::from_masked(value).as_u8(), value & max);
+                match $ty::try_from(value) {
+                    Ok(x) => {
+                        assert!(value <= max);
+                        assert_eq!(x.as_u8(), value);
+                    }
+                    Err(err) => {
+                        assert!(value > max);
+                        assert_eq!(
+                            err,
+                            OverflowError {
+                                max: max as usize,
+                                value: value as usize
+                            }
+                        );
+                    }
+                }
+                assert_eq!($ty::try_from(value as u16).ok(), $ty::try_from(value).ok());
+                assert_eq!($ty::try_from(value as u32).ok(), $ty::try_from(value).ok());
+                assert_eq!($ty::try_from(value as u64).ok(), $ty::try_from(value).ok());
+                assert_eq!(
+                    value.to_string().parse::<$ty>().ok(),
+                    $ty::try_from(value).ok()
+                );
+            }
+            for a in 0..=max {
+                let x = $ty::with(a);
+                assert_eq!(x.to_string(), a.to_string());
+                assert_eq!(u16::from(x), a as u16);
+                assert_eq!(u64::from(x), a as u64);
+                assert_eq!((!x).as_u8(), !a & max);
+                // Right-hand operands cover the whole `u8` domain, so the
+                // results must stay within the type bounds
+                for b in 0..=255u8 {
+                    let sum = a as u16 + b as u16;
+                    let diff = (a as u16 + 256 * modulo - b as u16) % modulo;
+                    let prod = a as u16 * b as u16;
+
+                    assert_eq!(
+                        x.checked_add(b).map($ty::as_u8),
+                        Some(sum).filter(|v| *v <= max as u16).map(|v| v as u8)
+                    );
+                    assert_eq!(x.wrapping_add(b).as_u8() as u16, sum % modulo);
+                    assert_eq!(
+                        x.overflowing_add(b),
+                        ($ty::with((sum % modulo) as u8), sum > max as u16)
+                    );
+                    assert_eq!(x.saturating_add(b).as_u8() as u16, sum.min(max as u16));
+                    assert_eq!(x.checked_sub(b).map($ty::as_u8), a.checked_sub(b));
+                    assert_eq!(x.wrapping_sub(b).as_u8() as u16, diff);
+                    assert_eq!(x.overflowing_sub(b), ($ty::with(diff as u8), b > a));
+                    assert_eq!(x.saturating_sub(b).as_u8(), a.saturating_sub(b));
+                    assert_eq!(
+                        x.checked_mul(b).map($ty::as_u8),
+                        Some(prod).filter(|v| *v <= max as u16).map(|v| v as u8)
+                    );
+                    assert_eq!(x.wrapping_mul(b).as_u8() as u16, prod % modulo);
+                    assert_eq!(
+                        x.overflowing_mul(b),
+                        ($ty::with((prod % modulo) as u8), prod > max as u16)
+                    );
+                    assert_eq!(x.saturating_mul(b).as_u8() as u16, prod.min(max as u16));
+                    assert_eq!(x.checked_div(b).map($ty::as_u8), a.checked_div(b));
+                    assert_eq!(x.checked_rem(b).map($ty::as_u8), a.checked_rem(b));
+                    if b <= max {
+                        let y = $ty::with(b);
+                        assert_eq!(x.cmp(&y), a.cmp(&b));
+                        assert_eq!((x & y).as_u8(), a & b);
+                        assert_eq!((x | y).as_u8(), a | b);
+                        assert_eq!((x ^ y).as_u8(), a ^ b);
+                    }
+                }
+            }
+        };
+    }
+
+    #[test]
+    fn u8_based_exhaustive() {
+        test_u8_based!(u1);
+        test_u8_based!(u2);
+        test_u8_based!(u3);
+        test_u8_based!(u4);
+        test_u8_based!(u5);
+        test_u8_based!(u6);
+        test_u8_based!(u7);
+    }
+
+    #[test]
+    fn u24_boundaries() {
+        let max = 0x00FF_FFFFu32;
+        assert_eq!(u24::MAX.as_u32(), max);
+        assert_eq!(u24::BITS, 24);
+        assert_eq!(u24::try_from(max as u64), Ok(u24::MAX));
+        assert_eq!(u24::try_from(max as usize), Ok(u24::MAX));
+        assert_eq!(
+            u24::try_from(max as u64 + 1),
+            Err(OverflowError {
+                max: max as usize,
+                value: max as usize + 1
+            })
+        );
+        assert_eq!(u24::from(0xFFFFu16).as_u32(), 0xFFFF);
+        assert_eq!(u24::from(0xFFu8).as_u32(), 0xFF);
+        assert_eq!(u64::from(u24::MAX), max as u64);
+
+        assert_eq!(u24::MAX.checked_add(1u32), None);
+        assert_eq!(u24::MAX.wrapping_add(1u32), u24::ZERO);
+        assert_eq!(u24::MAX.wrapping_add(2u32), u24::ONE);
+        assert_eq!(u24::MAX.overflowing_add(1u32), (u24::ZERO, true));
+        assert_eq!(u24::MAX.saturating_add(1u32), u24::MAX);
+        assert_eq!(u24::ZERO.checked_sub(1u32), None);
+        assert_eq!(u24::ZERO.wrapping_sub(1u32), u24::MAX);
+        assert_eq!(u24::ZERO.overflowing_sub(1u32), (u24::MAX, true));
+        assert_eq!(u24::ZERO.saturating_sub(1u32), u24::ZERO);
+        assert_eq!(u24::MAX.checked_mul(2u32), None);
+        assert_eq!(u24::MAX.wrapping_mul(2u32), u24::with(max - 1));
+        assert_eq!(u24::MAX.wrapping_mul(max), u24::ONE);
+        assert_eq!(u24::MAX.overflowing_mul(max), (u24::ONE, true));
+        assert_eq!(u24::MAX.saturating_mul(2u32), u24::MAX);
+        assert_eq!(u24::MAX.checked_div(0u32), None);
+        assert_eq!(u24::MAX.checked_rem(2u32), Some(u24::ONE));
+
+        assert_eq!(u24::MAX.to_string(), "16777215");
+        assert_eq!("16777215".parse(), Ok(u24::MAX));
+        assert!("16777216".parse::<u24>().is_err());
+        assert_eq!(u24::MAX.to_le_bytes(), [0xFF; 3]);
+        assert_eq!(u24::from_le_bytes([0xFF; 3]), u24::MAX);
+        assert_eq!(u24::from_be_bytes([0x01, 0x02, 0x03]).as_u32(), 0x010203);
+        assert_eq!(u24::with(0x010203).to_be_bytes(), [0x01, 0x02, 0x03]);
+        assert!(u24::MAX > u24::with(max - 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to add with overflow")]
+    fn u24_add_overflow() {
+        let _ = u24::MAX + 1u32;
+    }
+
+    #[test]
+    #[should_panic(expected = "with overflow")]
+    fn u5_sub_overflow() {
+        let _ = u5::ZERO - 1;
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn smallint_rand() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(0x2545_f491_4f6c_dd1d);
+        let mut other = StdRng::seed_from_u64(0x2545_f491_4f6c_dd1d);
+        for _ in 0..100 {
+            assert_eq!(rng.gen::<u24>(), other.gen::<u24>());
+            assert_eq!(rng.gen::<u5>(), other.gen::<u5>());
+        }
+
+        // Chi-squared test with 31 degrees of freedom; the critical value for
+        // p = 0.001 is 61.1
+        let mut buckets = [0u32; 32];
+        let samples = 32000;
+        for _ in 0..samples {
+            buckets[rng.gen::<u5>().as_u8() as usize] += 1;
+        }
+        let expected = samples as f64 / 32.0;
+        let chi2 = buckets
+            .iter()
+            .map(|count| (*count as f64 - expected).powi(2) / expected)
+            .sum::<f64>();
+        assert!(chi2 < 61.1, "chi-squared is {}", chi2);
+
+        let mut seen = [false; 32];
+        for _ in 0..1000 {
+            let val = rng.gen::<u24>();
+            assert!(val <= u24::MAX);
+            let val = rng.gen_range(u24::with(1000)..u24::MAX);
+            assert!(val >= u24::with(1000) && val < u24::MAX);
+            let val = rng.gen_range(u5::with(3)..=u5::MAX);
+            assert!(val >= u5::with(3));
+            seen[val.as_u8() as usize] = true;
+        }
+        assert!(seen[3..].iter().all(|seen| *seen));
+        assert!(!seen[..3].iter().any(|seen| *seen));
+    }
 }
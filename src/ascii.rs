@@ -0,0 +1,620 @@
+// Rust language amplification library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! ASCII strings and characters: types guaranteeing that the wrapped string
+//! data contains only ASCII characters, validated on construction.
+//!
+//! ```
+//! use std::convert::TryFrom;
+//! use amplify::ascii::{AsciiError, AsciiString};
+//! use amplify::ascii;
+//!
+//! let mut id = AsciiString::try_from("proto").unwrap();
+//! id.push_str(ascii!("col/1"));
+//! assert_eq!(id, "protocol/1");
+//! assert_eq!(id.try_push('ü'), Err(AsciiError { pos: 0, byte: 0xC3 }));
+//!
+//! let err = AsciiString::try_from("naïve").unwrap_err();
+//! assert_eq!(err, AsciiError { pos: 2, byte: 0xC3 });
+//! assert_eq!(AsciiString::from_ascii_lossy("naïve"), "na?ve");
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::ToOwned;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+use core::borrow::Borrow;
+use core::convert::TryFrom;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::ops::Deref;
+use core::str::FromStr;
+#[cfg(all(feature = "std", not(feature = "alloc")))]
+use std::borrow::ToOwned;
+
+/// Character used by [`AsciiString::from_ascii_lossy`] in place of non-ASCII
+/// characters
+pub const REPLACEMENT_CHAR: AsciiChar = AsciiChar(b'?');
+
+/// Checks whether the string consists of ASCII characters only. Unlike
+/// [`str::is_ascii`], can be used in constant context, which is utilized by
+/// [`ascii!`](crate::ascii!) macro.
+#[inline]
+pub const fn is_ascii(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        if bytes[pos] > 0x7F {
+            return false;
+        }
+        pos += 1;
+    }
+    true
+}
+
+/// Constructs [`&'static AsciiStr`](AsciiStr) from a string literal, failing
+/// compilation if the literal contains non-ASCII characters.
+///
+/// ```
+/// use amplify::ascii;
+/// use amplify::ascii::AsciiStr;
+///
+/// let hello: &'static AsciiStr = ascii!("hello");
+/// assert_eq!(hello, "hello");
+/// ```
+///
+/// ```compile_fail
+/// let hello = amplify::ascii!("héllo");
+/// ```
+#[macro_export]
+macro_rules! ascii {
+    ($s:literal) => {{
+        #[allow(dead_code)]
+        const LITERAL_MUST_BE_ASCII: () = [()][!$crate::ascii::is_ascii($s) as usize];
+        $crate::ascii::AsciiStr::from_str($s).expect("literal is checked to be ASCII")
+    }};
+}
+
+/// Error indicating non-ASCII data
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct AsciiError {
+    /// Position of the first non-ASCII byte
+    pub pos: usize,
+    /// Value of the first non-ASCII byte
+    pub byte: u8,
+}
+
+impl Display for AsciiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "non-ASCII byte {:#04x} at position {}",
+            self.byte, self.pos
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AsciiError {}
+
+/// Checks that the data consists of ASCII bytes only, reporting the first
+/// non-ASCII byte otherwise
+fn check_ascii(bytes: &[u8]) -> Result<(), AsciiError> {
+    match bytes.iter().position(|byte| !byte.is_ascii()) {
+        None => Ok(()),
+        Some(pos) => Err(AsciiError {
+            pos,
+            byte: bytes[pos],
+        }),
+    }
+}
+
+/// Single ASCII character
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AsciiChar(u8);
+
+impl AsciiChar {
+    /// Constructs ASCII character from a byte, failing if the byte is not
+    /// an ASCII character code
+    #[inline]
+    pub fn from_byte(byte: u8) -> Result<Self, AsciiError> {
+        check_ascii(&[byte]).map(|_| AsciiChar(byte))
+    }
+
+    /// Returns character code
+    #[inline]
+    pub fn as_byte(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the character as [`char`]
+    #[inline]
+    pub fn as_char(self) -> char {
+        self.0 as char
+    }
+}
+
+impl TryFrom<u8> for AsciiChar {
+    type Error = AsciiError;
+
+    #[inline]
+    fn try_from(byte: u8) -> Result<Self, Self::Error> {
+        AsciiChar::from_byte(byte)
+    }
+}
+
+impl TryFrom<char> for AsciiChar {
+    type Error = AsciiError;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        let mut buf = [0u8; 4];
+        let bytes = ch.encode_utf8(&mut buf).as_bytes();
+        check_ascii(bytes).map(|_| AsciiChar(bytes[0]))
+    }
+}
+
+impl From<AsciiChar> for u8 {
+    #[inline]
+    fn from(ch: AsciiChar) -> Self {
+        ch.as_byte()
+    }
+}
+
+impl From<AsciiChar> for char {
+    #[inline]
+    fn from(ch: AsciiChar) -> Self {
+        ch.as_char()
+    }
+}
+
+impl Display for AsciiChar {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.as_char(), f)
+    }
+}
+
+impl Debug for AsciiChar {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.as_char(), f)
+    }
+}
+
+/// Borrowed ASCII string slice, the same way as [`str`] is a borrowed form of
+/// [`String`].
+///
+/// Ordering and hashing are the same as for the underlying string.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct AsciiStr(str);
+
+impl AsciiStr {
+    /// Constructs ASCII string slice from a string slice, failing if it
+    /// contains non-ASCII characters
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<&AsciiStr, AsciiError> {
+        check_ascii(s.as_bytes())?;
+        Ok(AsciiStr::from_str_unchecked(s))
+    }
+
+    /// Constructs ASCII string slice from a byte slice, failing if it
+    /// contains non-ASCII bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<&AsciiStr, AsciiError> {
+        check_ascii(bytes)?;
+        let s = core::str::from_utf8(bytes).expect("ASCII data are always valid UTF-8");
+        Ok(AsciiStr::from_str_unchecked(s))
+    }
+
+    /// Converts string slice into ASCII string slice, which must be checked
+    /// to contain ASCII characters only by the caller
+    #[allow(unsafe_code)]
+    fn from_str_unchecked(s: &str) -> &AsciiStr {
+        // Safety: `AsciiStr` is a `#[repr(transparent)]` wrapper around `str`
+        unsafe { &*(s as *const str as *const AsciiStr) }
+    }
+
+    /// Returns the underlying string slice
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns ASCII character codes
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    /// Returns iterator over ASCII characters
+    pub fn chars(&self) -> impl Iterator<Item = AsciiChar> + '_ {
+        self.0.bytes().map(AsciiChar)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for &'a AsciiStr {
+    type Error = AsciiError;
+
+    #[inline]
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        AsciiStr::from_str(s)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for &'a AsciiStr {
+    type Error = AsciiError;
+
+    #[inline]
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        AsciiStr::from_bytes(bytes)
+    }
+}
+
+impl Deref for AsciiStr {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<str> for AsciiStr {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for AsciiStr {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl ToOwned for AsciiStr {
+    type Owned = AsciiString;
+
+    #[inline]
+    fn to_owned(&self) -> Self::Owned {
+        AsciiString(self.0.to_owned())
+    }
+}
+
+impl PartialEq<str> for AsciiStr {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        &self.0 == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for AsciiStr {
+    #[inline]
+    fn eq(&self, other: &&'a str) -> bool {
+        &self.0 == *other
+    }
+}
+
+impl Display for AsciiStr {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl Debug for AsciiStr {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+/// Owned string containing ASCII characters only.
+///
+/// Ordering and hashing are the same as for the underlying [`String`]; the
+/// string can be converted back into [`String`] or [`Vec<u8>`] without
+/// copying the data.
+///
+/// With `serde` feature the type is serialized as a string, checking that the
+/// string contains only ASCII characters on deserialization.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct AsciiString(String);
+
+impl AsciiString {
+    /// Constructs empty ASCII string
+    #[inline]
+    pub fn new() -> Self {
+        AsciiString::default()
+    }
+
+    /// Constructs ASCII string from a string, replacing each of non-ASCII
+    /// characters with [`REPLACEMENT_CHAR`]
+    pub fn from_ascii_lossy(s: &str) -> Self {
+        AsciiString(
+            s.chars()
+                .map(|ch| {
+                    if ch.is_ascii() {
+                        ch
+                    } else {
+                        REPLACEMENT_CHAR.as_char()
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns ASCII string slice with the whole string
+    #[inline]
+    pub fn as_ascii_str(&self) -> &AsciiStr {
+        AsciiStr::from_str_unchecked(&self.0)
+    }
+
+    /// Appends ASCII character to the end of the string
+    #[inline]
+    pub fn push(&mut self, ch: AsciiChar) {
+        self.0.push(ch.as_char())
+    }
+
+    /// Appends a character to the end of the string, failing if it is not an
+    /// ASCII character
+    pub fn try_push(&mut self, ch: char) -> Result<(), AsciiError> {
+        self.push(AsciiChar::try_from(ch)?);
+        Ok(())
+    }
+
+    /// Appends ASCII string slice to the end of the string
+    #[inline]
+    pub fn push_str(&mut self, s: &AsciiStr) {
+        self.0.push_str(s.as_str())
+    }
+
+    /// Removes the last character from the string and returns it, or `None`
+    /// if the string is empty
+    #[inline]
+    pub fn pop(&mut self) -> Option<AsciiChar> {
+        self.0.pop().map(|ch| AsciiChar(ch as u8))
+    }
+
+    /// Converts into the underlying [`String`]
+    #[inline]
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// Converts into the vector of ASCII character codes
+    #[inline]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0.into_bytes()
+    }
+}
+
+impl TryFrom<String> for AsciiString {
+    type Error = AsciiError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        check_ascii(s.as_bytes())?;
+        Ok(AsciiString(s))
+    }
+}
+
+impl<'a> TryFrom<&'a str> for AsciiString {
+    type Error = AsciiError;
+
+    #[inline]
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        AsciiStr::from_str(s).map(AsciiStr::to_owned)
+    }
+}
+
+impl TryFrom<Vec<u8>> for AsciiString {
+    type Error = AsciiError;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        check_ascii(&bytes)?;
+        let s = String::from_utf8(bytes).expect("ASCII data are always valid UTF-8");
+        Ok(AsciiString(s))
+    }
+}
+
+impl FromStr for AsciiString {
+    type Err = AsciiError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AsciiString::try_from(s)
+    }
+}
+
+impl<'a> From<&'a AsciiStr> for AsciiString {
+    #[inline]
+    fn from(s: &'a AsciiStr) -> Self {
+        s.to_owned()
+    }
+}
+
+impl From<AsciiString> for String {
+    #[inline]
+    fn from(s: AsciiString) -> Self {
+        s.into_string()
+    }
+}
+
+impl From<AsciiString> for Vec<u8> {
+    #[inline]
+    fn from(s: AsciiString) -> Self {
+        s.into_bytes()
+    }
+}
+
+impl Deref for AsciiString {
+    type Target = AsciiStr;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_ascii_str()
+    }
+}
+
+impl Borrow<AsciiStr> for AsciiString {
+    #[inline]
+    fn borrow(&self) -> &AsciiStr {
+        self.as_ascii_str()
+    }
+}
+
+impl AsRef<AsciiStr> for AsciiString {
+    #[inline]
+    fn as_ref(&self) -> &AsciiStr {
+        self.as_ascii_str()
+    }
+}
+
+impl AsRef<str> for AsciiString {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for AsciiString {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl PartialEq<str> for AsciiString {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for AsciiString {
+    #[inline]
+    fn eq(&self, other: &&'a str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl Display for AsciiString {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl Debug for AsciiString {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for AsciiString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AsciiString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        AsciiString::try_from(s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn construction() {
+        assert_eq!(
+            AsciiString::try_from(s!("abc")).unwrap().into_string(),
+            "abc"
+        );
+        assert_eq!(
+            AsciiString::try_from(vec![b'a', 0x80]),
+            Err(AsciiError { pos: 1, byte: 0x80 })
+        );
+        assert_eq!(
+            AsciiString::try_from(vec![b'a', b'b'])
+                .unwrap()
+                .into_bytes(),
+            b"ab"
+        );
+        assert_eq!(
+            "x\u{7f}".parse::<AsciiString>().unwrap().as_bytes(),
+            b"x\x7f"
+        );
+        assert_eq!(
+            "ж".parse::<AsciiString>(),
+            Err(AsciiError { pos: 0, byte: 0xD0 })
+        );
+        assert_eq!(
+            AsciiError { pos: 3, byte: 0xD0 }.to_string(),
+            "non-ASCII byte 0xd0 at position 3"
+        );
+        assert_eq!(AsciiStr::from_bytes(b"ab\xff").unwrap_err().pos, 2);
+        assert_eq!(AsciiString::from_ascii_lossy("€1"), "?1");
+    }
+
+    #[test]
+    fn chars() {
+        assert_eq!(AsciiChar::try_from(b'a').unwrap().as_char(), 'a');
+        assert!(AsciiChar::try_from(0x80u8).is_err());
+        assert!(AsciiChar::try_from('é').is_err());
+
+        let mut s = AsciiString::new();
+        s.push(AsciiChar::try_from('h').unwrap());
+        s.try_push('i').unwrap();
+        assert!(s.try_push('í').is_err());
+        assert_eq!(s, "hi");
+        assert_eq!(s.chars().map(char::from).collect::<String>(), "hi");
+        assert_eq!(s.pop().map(AsciiChar::as_char), Some('i'));
+        assert_eq!(s.len(), 1);
+    }
+
+    #[test]
+    fn ordering() {
+        let a = AsciiString::try_from("abc").unwrap();
+        let b = AsciiString::try_from("abd").unwrap();
+        assert_eq!(a.cmp(&b), "abc".cmp("abd"));
+        assert_eq!(a.as_ascii_str(), ascii!("abc"));
+        assert_eq!(&*ascii!("abc").to_owned(), a.as_ascii_str());
+        assert_eq!(format!("{} {:?}", a, a), "abc \"abc\"");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let s = AsciiString::try_from("abc").unwrap();
+        assert_eq!(serde_json::to_string(&s).unwrap(), "\"abc\"");
+        assert_eq!(serde_json::from_str::<AsciiString>("\"abc\"").unwrap(), s);
+        let err = serde_json::from_str::<AsciiString>("\"ab\u{e9}\"").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("non-ASCII byte 0xc3 at position 2"));
+    }
+}
@@ -13,9 +13,19 @@
 // along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::net::TcpStream;
+#[cfg(feature = "std")]
+use std::sync::mpsc::{self, Receiver, RecvError, SendError, Sender};
+
 /// Trait for splittable streams and other types, which can be separated into
 /// some two types ([`Bipolar::Left`], [`Bipolar::Right`]), like a reader and
 /// writer streams.
+///
+/// Structures consisting of exactly two fields may implement the trait with
+/// [`bipolar!`] macro.
 pub trait Bipolar {
     /// First separable type (like reader)
     type Left;
@@ -27,4 +37,249 @@ pub trait Bipolar {
 
     /// Split the type into two
     fn split(self) -> (Self::Left, Self::Right);
+
+    /// Splits the type, transforms its left half with `f` and joins the halves
+    /// back
+    fn map_left(self, f: impl FnOnce(Self::Left) -> Self::Left) -> Self
+    where
+        Self: Sized,
+    {
+        let (left, right) = self.split();
+        Self::join(f(left), right)
+    }
+
+    /// Splits the type, transforms its right half with `f` and joins the
+    /// halves back
+    fn map_right(self, f: impl FnOnce(Self::Right) -> Self::Right) -> Self
+    where
+        Self: Sized,
+    {
+        let (left, right) = self.split();
+        Self::join(left, f(right))
+    }
+}
+
+impl<L, R> Bipolar for (L, R) {
+    type Left = L;
+    type Right = R;
+
+    #[inline]
+    fn join(left: Self::Left, right: Self::Right) -> Self {
+        (left, right)
+    }
+
+    #[inline]
+    fn split(self) -> (Self::Left, Self::Right) {
+        self
+    }
+}
+
+/// Splits TCP stream into two handles to the same socket, which may be used
+/// for reading and writing from different threads. The socket stays open
+/// while any of the handles is alive.
+///
+/// # Errors
+///
+/// If the socket handle can't be duplicated with [`TcpStream::try_clone`].
+#[cfg(feature = "std")]
+pub fn split_tcp_stream(stream: TcpStream) -> io::Result<(TcpStream, TcpStream)> {
+    let right = stream.try_clone()?;
+    Ok((stream, right))
+}
+
+/// Joined sending and receiving halves of [`std::sync::mpsc`] channel.
+///
+/// When created with [`Channel::new`] the channel is a loopback: the messages
+/// sent are received by the same channel. Splitting the channel with
+/// [`Bipolar::split`] allows to pass its halves to different threads.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct Channel<T> {
+    sender: Sender<T>,
+    receiver: Receiver<T>,
+}
+
+#[cfg(feature = "std")]
+impl<T> Default for Channel<T> {
+    fn default() -> Self {
+        Channel::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Channel<T> {
+    /// Constructs new channel with [`mpsc::channel`]
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Channel { sender, receiver }
+    }
+
+    /// Returns reference to the sending half of the channel
+    #[inline]
+    pub fn sender(&self) -> &Sender<T> {
+        &self.sender
+    }
+
+    /// Returns reference to the receiving half of the channel
+    #[inline]
+    pub fn receiver(&self) -> &Receiver<T> {
+        &self.receiver
+    }
+
+    /// Sends a value using the sending half of the channel
+    #[inline]
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        self.sender.send(value)
+    }
+
+    /// Waits for a value on the receiving half of the channel
+    #[inline]
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.receiver.recv()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Bipolar for Channel<T> {
+    type Left = Sender<T>;
+    type Right = Receiver<T>;
+
+    #[inline]
+    fn join(sender: Self::Left, receiver: Self::Right) -> Self {
+        Channel { sender, receiver }
+    }
+
+    #[inline]
+    fn split(self) -> (Self::Left, Self::Right) {
+        (self.sender, self.receiver)
+    }
+}
+
+/// Implements [`Bipolar`] for a structure with exactly two fields, the first
+/// of which becomes [`Bipolar::Left`] and the second [`Bipolar::Right`].
+///
+/// Generic structures are not supported.
+///
+/// # Example
+///
+/// ```
+/// #[macro_use]
+/// extern crate amplify;
+/// use amplify::Bipolar;
+///
+/// struct Duplex {
+///     reader: Vec<u8>,
+///     writer: String,
+/// }
+/// bipolar!(Duplex { reader: Vec<u8>, writer: String });
+///
+/// struct Pair(u8, char);
+/// bipolar!(Pair(u8, char));
+///
+/// # fn main() {
+/// let duplex = Duplex::join(vec![1, 2], s!("text"));
+/// let (reader, writer) = duplex.map_right(|s| s.to_uppercase()).split();
+/// assert_eq!(reader, vec![1, 2]);
+/// assert_eq!(writer, "TEXT");
+///
+/// assert_eq!(Pair(1, 'a').split(), (1, 'a'));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! bipolar {
+    ($ty:ident { $left:ident : $left_ty:ty, $right:ident : $right_ty:ty $(,)? }) => {
+        impl $crate::Bipolar for $ty {
+            type Left = $left_ty;
+            type Right = $right_ty;
+
+            #[inline]
+            fn join(left: Self::Left, right: Self::Right) -> Self {
+                $ty {
+                    $left: left,
+                    $right: right,
+                }
+            }
+
+            #[inline]
+            fn split(self) -> (Self::Left, Self::Right) {
+                (self.$left, self.$right)
+            }
+        }
+    };
+    ($ty:ident ( $left_ty:ty, $right_ty:ty $(,)? )) => {
+        impl $crate::Bipolar for $ty {
+            type Left = $left_ty;
+            type Right = $right_ty;
+
+            #[inline]
+            fn join(left: Self::Left, right: Self::Right) -> Self {
+                $ty(left, right)
+            }
+
+            #[inline]
+            fn split(self) -> (Self::Left, Self::Right) {
+                (self.0, self.1)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tuple() {
+        let pair = (1u8, "one");
+        assert_eq!(pair.split(), (1, "one"));
+        assert_eq!(<(u8, &str)>::join(2, "two"), (2, "two"));
+        assert_eq!(pair.map_left(|n| n + 1), (2, "one"));
+        assert_eq!(pair.map_right(|_| "other"), (1, "other"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn channel() {
+        let channel = Channel::new();
+        channel.send(1u32).unwrap();
+        assert_eq!(channel.recv(), Ok(1));
+
+        let (sender, receiver) = channel.split();
+        let thread = std::thread::spawn(move || {
+            for no in 0..3 {
+                sender.send(no).unwrap();
+            }
+            sender
+        });
+        let sender = thread.join().unwrap();
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+
+        let channel = Channel::join(sender, receiver);
+        channel.sender().send(5).unwrap();
+        assert_eq!(channel.receiver().recv(), Ok(5));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn tcp_stream() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+
+        let (mut reader, mut writer) = split_tcp_stream(server).unwrap();
+        client.write_all(b"ping").unwrap();
+        let mut buf = [0u8; 4];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"ping");
+
+        writer.write_all(b"pong").unwrap();
+        client.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"pong");
+
+        drop(writer);
+        assert_eq!(reader.peer_addr().unwrap(), client.local_addr().unwrap());
+    }
 }
@@ -0,0 +1,670 @@
+// Rust language amplification library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Confined collections: wrappers over standard collection types guaranteeing
+//! that the number of the collection elements always stays within the bounds
+//! given by the type.
+//!
+//! The minimal and maximal number of elements are const generic parameters
+//! of [`Confined`], like in `Confined<Vec<T>, 1, 32>`; the module provides
+//! constants for the most common maximums ([`U8`], [`U16`], [`U32`]) and type
+//! aliases for the most common collections.
+//!
+//! ```
+//! use amplify::confinement::{self, Confined, NonEmptyVec, TinyString};
+//!
+//! let vec = Confined::<Vec<u8>, 1, 32>::try_from(vec![1, 2, 3]).unwrap();
+//! assert_eq!(vec.len(), 3);
+//!
+//! let mut vec = NonEmptyVec::try_from(vec![1u8]).unwrap();
+//! vec.push(2).unwrap();
+//! assert_eq!(vec.remove(0), Ok(1));
+//! assert_eq!(vec.remove(0), Err(confinement::Error::Undersize { len: 0, min_len: 1 }));
+//! assert_eq!(vec.as_slice(), &[2]);
+//!
+//! let err = TinyString::try_from("a".repeat(256)).unwrap_err();
+//! assert_eq!(err, confinement::Error::Oversize { len: 256, max_len: 255 });
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Debug, Display, Formatter};
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+use core::iter::FromIterator;
+use core::ops::Deref;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+#[cfg(all(feature = "std", not(feature = "alloc")))]
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+/// Maximal number of elements in collections with `u8` length
+pub const U8: usize = u8::MAX as usize;
+/// Maximal number of elements in collections with `u16` length
+pub const U16: usize = u16::MAX as usize;
+/// Maximal number of elements in collections with `u32` length
+pub const U32: usize = u32::MAX as usize;
+
+/// Collection types which may be confined
+pub trait Collection {
+    /// Number of elements in the collection; for strings this is the length
+    /// in bytes
+    fn len(&self) -> usize;
+
+    /// Detects whether the collection has no elements
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+macro_rules! impl_collection {
+    ($( impl<$($gen:ident),*> for $ty:ty; )+) => {
+        $(
+            impl<$($gen),*> Collection for $ty {
+                #[inline]
+                fn len(&self) -> usize {
+                    <$ty>::len(self)
+                }
+            }
+        )+
+    };
+}
+
+impl_collection! {
+    impl<> for String;
+    impl<T> for Vec<T>;
+    impl<T> for VecDeque<T>;
+    impl<T> for BTreeSet<T>;
+    impl<K, V> for BTreeMap<K, V>;
+}
+
+#[cfg(feature = "std")]
+impl_collection! {
+    impl<T, S> for HashSet<T, S>;
+    impl<K, V, S> for HashMap<K, V, S>;
+}
+
+/// Error constructing or modifying a [`Confined`] collection, which would
+/// violate its bounds
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Error {
+    /// The collection would have less elements than the minimum allowed
+    Undersize {
+        /// Number of elements the collection would have
+        len: usize,
+        /// Minimal number of elements
+        min_len: usize,
+    },
+
+    /// The collection would have more elements than the maximum allowed
+    Oversize {
+        /// Number of elements the collection would have
+        len: usize,
+        /// Maximal number of elements
+        max_len: usize,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Undersize { len, min_len } => write!(
+                f,
+                "collection must have at least {} elements, while it has {}",
+                min_len, len
+            ),
+            Error::Oversize { len, max_len } => write!(
+                f,
+                "collection must have at most {} elements, while it has {}",
+                max_len, len
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Collection `C`, which number of elements is confined to be at least `MIN`
+/// and at most `MAX` (both inclusive).
+///
+/// The collection is constructed with [`Confined::try_from`] or
+/// [`Confined::try_from_iter`] and can be modified only with the methods
+/// checking the bounds, while it provides read access to the underlying
+/// collection via [`Deref`].
+///
+/// With `serde` feature the collection is serialized as the underlying
+/// collection, checking the bounds on deserialization.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Confined<C, const MIN: usize, const MAX: usize>(C);
+
+/// Vector with up to 255 (`u8::MAX`) elements
+pub type TinyVec<T> = Confined<Vec<T>, 0, U8>;
+/// Vector with up to 65535 (`u16::MAX`) elements
+pub type SmallVec<T> = Confined<Vec<T>, 0, U16>;
+/// Vector with at least one element
+pub type NonEmptyVec<T> = Confined<Vec<T>, 1, { usize::MAX }>;
+/// String with up to 255 (`u8::MAX`) bytes
+pub type TinyString = Confined<String, 0, U8>;
+/// String with up to 65535 (`u16::MAX`) bytes
+pub type SmallString = Confined<String, 0, U16>;
+/// Non-empty string
+pub type NonEmptyString = Confined<String, 1, { usize::MAX }>;
+/// Ordered set with up to 255 (`u8::MAX`) elements
+pub type TinyOrdSet<T> = Confined<BTreeSet<T>, 0, U8>;
+/// Ordered set with up to 65535 (`u16::MAX`) elements
+pub type SmallOrdSet<T> = Confined<BTreeSet<T>, 0, U16>;
+/// Ordered map with up to 255 (`u8::MAX`) elements
+pub type TinyOrdMap<K, V> = Confined<BTreeMap<K, V>, 0, U8>;
+/// Ordered map with up to 65535 (`u16::MAX`) elements
+pub type SmallOrdMap<K, V> = Confined<BTreeMap<K, V>, 0, U16>;
+#[cfg(feature = "std")]
+/// Hash set with up to 255 (`u8::MAX`) elements
+pub type TinyHashSet<T> = Confined<HashSet<T>, 0, U8>;
+#[cfg(feature = "std")]
+/// Hash set with up to 65535 (`u16::MAX`) elements
+pub type SmallHashSet<T> = Confined<HashSet<T>, 0, U16>;
+#[cfg(feature = "std")]
+/// Hash map with up to 255 (`u8::MAX`) elements
+pub type TinyHashMap<K, V> = Confined<HashMap<K, V>, 0, U8>;
+#[cfg(feature = "std")]
+/// Hash map with up to 65535 (`u16::MAX`) elements
+pub type SmallHashMap<K, V> = Confined<HashMap<K, V>, 0, U16>;
+
+impl<C, const MIN: usize, const MAX: usize> Confined<C, MIN, MAX>
+where
+    C: Collection,
+{
+    /// Constructs confined collection from the underlying collection, failing
+    /// if the number of its elements does not fit the bounds
+    pub fn try_from(col: C) -> Result<Self, Error> {
+        let len = col.len();
+        if len < MIN {
+            return Err(Error::Undersize { len, min_len: MIN });
+        }
+        if len > MAX {
+            return Err(Error::Oversize { len, max_len: MAX });
+        }
+        Ok(Confined(col))
+    }
+
+    /// Constructs confined collection from an iterator, failing if the number
+    /// of the collected elements does not fit the bounds
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, Error>
+    where
+        I: IntoIterator,
+        C: FromIterator<I::Item>,
+    {
+        Self::try_from(iter.into_iter().collect::<C>())
+    }
+
+    /// Returns reference to the underlying collection
+    #[inline]
+    pub fn as_inner(&self) -> &C {
+        &self.0
+    }
+
+    /// Releases the underlying collection
+    #[inline]
+    pub fn into_inner(self) -> C {
+        self.0
+    }
+
+    /// Detects whether the collection has the maximal allowed number of
+    /// elements, such that no elements can be added to it
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.0.len() >= MAX
+    }
+
+    /// Checks whether the collection may grow up to `len` elements
+    fn check_grow(&self, len: usize) -> Result<(), Error> {
+        if len > MAX {
+            return Err(Error::Oversize { len, max_len: MAX });
+        }
+        Ok(())
+    }
+
+    /// Checks whether the collection may lose a single element
+    fn check_shrink(&self) -> Result<(), Error> {
+        let len = self.0.len().saturating_sub(1);
+        if len < MIN {
+            return Err(Error::Undersize { len, min_len: MIN });
+        }
+        Ok(())
+    }
+}
+
+impl<C, const MIN: usize, const MAX: usize> Deref for Confined<C, MIN, MAX> {
+    type Target = C;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<C, const MIN: usize, const MAX: usize> AsRef<C> for Confined<C, MIN, MAX> {
+    #[inline]
+    fn as_ref(&self) -> &C {
+        &self.0
+    }
+}
+
+impl<C, const MIN: usize, const MAX: usize> Debug for Confined<C, MIN, MAX>
+where
+    C: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<C, const MIN: usize, const MAX: usize> Display for Confined<C, MIN, MAX>
+where
+    C: Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<C, const MIN: usize, const MAX: usize> IntoIterator for Confined<C, MIN, MAX>
+where
+    C: IntoIterator,
+{
+    type Item = C::Item;
+    type IntoIter = C::IntoIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, C, const MIN: usize, const MAX: usize> IntoIterator for &'a Confined<C, MIN, MAX>
+where
+    &'a C: IntoIterator,
+{
+    type Item = <&'a C as IntoIterator>::Item;
+    type IntoIter = <&'a C as IntoIterator>::IntoIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        (&self.0).into_iter()
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> Confined<Vec<T>, MIN, MAX> {
+    /// Appends an element to the back of the vector, failing if the vector
+    /// is full
+    pub fn push(&mut self, elem: T) -> Result<(), Error> {
+        self.check_grow(self.0.len() + 1)?;
+        self.0.push(elem);
+        Ok(())
+    }
+
+    /// Inserts an element at position `index`, failing if the vector is full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, elem: T) -> Result<(), Error> {
+        self.check_grow(self.0.len() + 1)?;
+        self.0.insert(index, elem);
+        Ok(())
+    }
+
+    /// Appends all elements of the iterator to the vector, failing without
+    /// modifying the vector if the resulting number of elements exceeds the
+    /// maximum
+    pub fn extend<I>(&mut self, iter: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let elems = iter.into_iter().collect::<Vec<_>>();
+        self.check_grow(self.0.len() + elems.len())?;
+        self.0.extend(elems);
+        Ok(())
+    }
+
+    /// Removes and returns the element at position `index`, failing if the
+    /// vector has the minimal allowed number of elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Result<T, Error> {
+        self.check_shrink()?;
+        Ok(self.0.remove(index))
+    }
+
+    /// Removes the last element from the vector and returns it (or `None` if
+    /// the vector is empty), failing if the vector has the minimal allowed
+    /// number of elements
+    pub fn pop(&mut self) -> Result<Option<T>, Error> {
+        if self.0.is_empty() {
+            return Ok(None);
+        }
+        self.check_shrink()?;
+        Ok(self.0.pop())
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> Confined<String, MIN, MAX> {
+    /// Appends a character to the end of the string, failing if the string
+    /// length in bytes would exceed the maximum
+    pub fn push(&mut self, ch: char) -> Result<(), Error> {
+        self.check_grow(self.0.len() + ch.len_utf8())?;
+        self.0.push(ch);
+        Ok(())
+    }
+
+    /// Appends a string slice to the end of the string, failing if the string
+    /// length in bytes would exceed the maximum
+    pub fn push_str(&mut self, s: &str) -> Result<(), Error> {
+        self.check_grow(self.0.len() + s.len())?;
+        self.0.push_str(s);
+        Ok(())
+    }
+
+    /// Removes the last character from the string and returns it (or `None`
+    /// if the string is empty), failing if the string length in bytes would
+    /// become less than the minimum
+    pub fn pop(&mut self) -> Result<Option<char>, Error> {
+        let ch = match self.0.chars().next_back() {
+            None => return Ok(None),
+            Some(ch) => ch,
+        };
+        let len = self.0.len() - ch.len_utf8();
+        if len < MIN {
+            return Err(Error::Undersize { len, min_len: MIN });
+        }
+        Ok(self.0.pop())
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> Confined<BTreeSet<T>, MIN, MAX>
+where
+    T: Ord,
+{
+    /// Adds an element to the set, returning whether it was not present in
+    /// the set. Fails if the element is not present and the set is full.
+    pub fn insert(&mut self, elem: T) -> Result<bool, Error> {
+        if !self.0.contains(&elem) {
+            self.check_grow(self.0.len() + 1)?;
+        }
+        Ok(self.0.insert(elem))
+    }
+
+    /// Removes an element from the set, returning whether it was present in
+    /// the set. Fails if the element is present and the set has the minimal
+    /// allowed number of elements.
+    pub fn remove(&mut self, elem: &T) -> Result<bool, Error> {
+        if self.0.contains(elem) {
+            self.check_shrink()?;
+        }
+        Ok(self.0.remove(elem))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S, const MIN: usize, const MAX: usize> Confined<HashSet<T, S>, MIN, MAX>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Adds an element to the set, returning whether it was not present in
+    /// the set. Fails if the element is not present and the set is full.
+    pub fn insert(&mut self, elem: T) -> Result<bool, Error> {
+        if !self.0.contains(&elem) {
+            self.check_grow(self.0.len() + 1)?;
+        }
+        Ok(self.0.insert(elem))
+    }
+
+    /// Removes an element from the set, returning whether it was present in
+    /// the set. Fails if the element is present and the set has the minimal
+    /// allowed number of elements.
+    pub fn remove(&mut self, elem: &T) -> Result<bool, Error> {
+        if self.0.contains(elem) {
+            self.check_shrink()?;
+        }
+        Ok(self.0.remove(elem))
+    }
+}
+
+impl<K, V, const MIN: usize, const MAX: usize> Confined<BTreeMap<K, V>, MIN, MAX>
+where
+    K: Ord,
+{
+    /// Inserts a key-value pair into the map, returning the previous value
+    /// for the key. Fails if the key is not present and the map is full.
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, Error> {
+        if !self.0.contains_key(&key) {
+            self.check_grow(self.0.len() + 1)?;
+        }
+        Ok(self.0.insert(key, value))
+    }
+
+    /// Removes a key from the map, returning its value. Fails if the key is
+    /// present and the map has the minimal allowed number of elements.
+    pub fn remove(&mut self, key: &K) -> Result<Option<V>, Error> {
+        if self.0.contains_key(key) {
+            self.check_shrink()?;
+        }
+        Ok(self.0.remove(key))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S, const MIN: usize, const MAX: usize> Confined<HashMap<K, V, S>, MIN, MAX>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Inserts a key-value pair into the map, returning the previous value
+    /// for the key. Fails if the key is not present and the map is full.
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, Error> {
+        if !self.0.contains_key(&key) {
+            self.check_grow(self.0.len() + 1)?;
+        }
+        Ok(self.0.insert(key, value))
+    }
+
+    /// Removes a key from the map, returning its value. Fails if the key is
+    /// present and the map has the minimal allowed number of elements.
+    pub fn remove(&mut self, key: &K) -> Result<Option<V>, Error> {
+        if self.0.contains_key(key) {
+            self.check_shrink()?;
+        }
+        Ok(self.0.remove(key))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C, const MIN: usize, const MAX: usize> serde::Serialize for Confined<C, MIN, MAX>
+where
+    C: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C, const MIN: usize, const MAX: usize> serde::Deserialize<'de> for Confined<C, MIN, MAX>
+where
+    C: serde::Deserialize<'de> + Collection,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let col = C::deserialize(deserializer)?;
+        Confined::try_from(col).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn construction() {
+        assert_eq!(
+            Confined::<Vec<u8>, 1, 2>::try_from(vec![]),
+            Err(Error::Undersize { len: 0, min_len: 1 })
+        );
+        assert_eq!(
+            Confined::<Vec<u8>, 1, 2>::try_from(vec![1, 2, 3]),
+            Err(Error::Oversize { len: 3, max_len: 2 })
+        );
+        let vec = Confined::<Vec<u8>, 1, 2>::try_from_iter(1..3).unwrap();
+        assert_eq!(vec.as_slice(), &[1, 2]);
+        assert_eq!(vec.into_inner(), vec![1, 2]);
+
+        assert!(TinyVec::<u8>::try_from(vec![0; 255]).is_ok());
+        assert!(TinyVec::<u8>::try_from(vec![0; 256]).is_err());
+        assert!(SmallVec::<u8>::try_from(vec![0; 256]).is_ok());
+        assert!(NonEmptyString::try_from(String::new()).is_err());
+        assert_eq!(
+            Error::Oversize { len: 3, max_len: 2 }.to_string(),
+            "collection must have at most 2 elements, while it has 3"
+        );
+    }
+
+    #[test]
+    fn vec() {
+        let mut vec = Confined::<Vec<u8>, 1, 2>::try_from(vec![1]).unwrap();
+        vec.push(2).unwrap();
+        assert!(vec.is_full());
+        assert_eq!(vec.push(3), Err(Error::Oversize { len: 3, max_len: 2 }));
+        assert_eq!(
+            vec.insert(0, 3),
+            Err(Error::Oversize { len: 3, max_len: 2 })
+        );
+        assert_eq!(vec.pop(), Ok(Some(2)));
+        assert_eq!(vec.pop(), Err(Error::Undersize { len: 0, min_len: 1 }));
+        assert_eq!(vec.remove(0), Err(Error::Undersize { len: 0, min_len: 1 }));
+        assert_eq!(
+            vec.extend(vec![2, 3]),
+            Err(Error::Oversize { len: 3, max_len: 2 })
+        );
+        assert_eq!(vec.len(), 1);
+        vec.extend(Some(2)).unwrap();
+        assert_eq!(vec.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!((&vec).into_iter().count(), 2);
+        assert_eq!(format!("{:?}", vec), "[1, 2]");
+    }
+
+    #[test]
+    fn string() {
+        let mut s = TinyString::try_from("a".repeat(253)).unwrap();
+        assert_eq!(s.push('ä'), Ok(()));
+        assert_eq!(
+            s.push('b'),
+            Err(Error::Oversize {
+                len: 256,
+                max_len: 255
+            })
+        );
+        assert_eq!(
+            s.push_str("bc"),
+            Err(Error::Oversize {
+                len: 257,
+                max_len: 255
+            })
+        );
+        assert_eq!(s.len(), 255);
+
+        let mut s = NonEmptyString::try_from(s!("ab")).unwrap();
+        assert_eq!(s.pop(), Ok(Some('b')));
+        assert_eq!(s.pop(), Err(Error::Undersize { len: 0, min_len: 1 }));
+        assert_eq!(s.to_string(), "a");
+    }
+
+    #[test]
+    fn sets_and_maps() {
+        let mut set = Confined::<BTreeSet<u8>, 1, 2>::try_from(bset! {1, 2}).unwrap();
+        assert_eq!(set.insert(2), Ok(false));
+        assert_eq!(set.insert(3), Err(Error::Oversize { len: 3, max_len: 2 }));
+        assert_eq!(set.remove(&3), Ok(false));
+        assert_eq!(set.remove(&2), Ok(true));
+        assert_eq!(set.remove(&1), Err(Error::Undersize { len: 0, min_len: 1 }));
+
+        let mut set = Confined::<HashSet<u8>, 1, 2>::try_from(set! {1, 2}).unwrap();
+        assert_eq!(set.insert(3), Err(Error::Oversize { len: 3, max_len: 2 }));
+        assert_eq!(set.remove(&2), Ok(true));
+        assert_eq!(set.remove(&1), Err(Error::Undersize { len: 0, min_len: 1 }));
+
+        let mut map = Confined::<BTreeMap<u8, u8>, 1, 2>::try_from(bmap! {1 => 1}).unwrap();
+        assert_eq!(map.insert(2, 2), Ok(None));
+        assert_eq!(map.insert(2, 3), Ok(Some(2)));
+        assert_eq!(
+            map.insert(3, 3),
+            Err(Error::Oversize { len: 3, max_len: 2 })
+        );
+        assert_eq!(map.remove(&1), Ok(Some(1)));
+        assert_eq!(map.remove(&2), Err(Error::Undersize { len: 0, min_len: 1 }));
+
+        let mut map = Confined::<HashMap<u8, u8>, 1, 2>::try_from(map! {1 => 1, 2 => 2}).unwrap();
+        assert_eq!(
+            map.insert(3, 3),
+            Err(Error::Oversize { len: 3, max_len: 2 })
+        );
+        assert_eq!(map.remove(&1), Ok(Some(1)));
+        assert_eq!(map.remove(&2), Err(Error::Undersize { len: 0, min_len: 1 }));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let vec = Confined::<Vec<u8>, 1, 2>::try_from(vec![1, 2]).unwrap();
+        assert_eq!(serde_json::to_string(&vec).unwrap(), "[1,2]");
+        assert_eq!(
+            serde_json::from_str::<Confined<Vec<u8>, 1, 2>>("[1,2]").unwrap(),
+            vec
+        );
+        let err = serde_json::from_str::<Confined<Vec<u8>, 1, 2>>("[1,2,3]").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "collection must have at most 2 elements, while it has 3"
+        );
+        assert!(serde_json::from_str::<Confined<Vec<u8>, 1, 2>>("[]").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "derive")]
+    fn wrapper() {
+        use crate::Wrapper;
+
+        #[derive(Wrapper, Clone, PartialEq, Eq, Debug, From)]
+        #[amplify_crate(crate)]
+        struct Names(TinyVec<String>);
+
+        let names = Names::from_inner(TinyVec::try_from(vec![s!("Alice")]).unwrap());
+        assert_eq!(names.as_inner().len(), 1);
+        assert_eq!(names.as_inner()[0], "Alice");
+    }
+}
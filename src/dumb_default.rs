@@ -14,8 +14,47 @@
 // along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
+#[cfg(feature = "alloc")]
+use alloc::collections::{BTreeMap, BTreeSet, LinkedList, VecDeque};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{boxed::Box, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+#[cfg(all(feature = "std", not(feature = "alloc")))]
+use std::collections::{BTreeMap, BTreeSet, LinkedList, VecDeque};
+
 /// Used as an alternative to default for test and prototyping purposes, when a
 /// type can't have a default value, but you need to generate some dumb data.
+///
+/// The crate implements the trait for the types which are commonly used as
+/// fields of structures deriving `DumbDefault`. For numbers, strings,
+/// collections, `Option` (which is `None`), `FlagVec` and the integer types
+/// from [`crate::num`] the implementation returns the same meaningful value
+/// as `Default`. Slice types ([`crate::Slice32`] and others) get zero-filled
+/// dumb placeholders, and `Box` and arrays are filled with the dumb values of
+/// their items.
+///
+/// ```
+/// # use std::collections::BTreeMap;
+/// use amplify::num::u256;
+/// use amplify::{DumbDefault, Slice32};
+///
+/// #[derive(DumbDefault, PartialEq, Debug)]
+/// struct Record {
+///     id: Slice32,
+///     value: u256,
+///     name: String,
+///     tags: Vec<String>,
+///     index: BTreeMap<u8, u64>,
+///     parent: Option<Slice32>,
+///     keys: [Slice32; 2],
+/// }
+///
+/// let record = Record::dumb_default();
+/// assert_eq!(record.value, u256::ZERO);
+/// assert_eq!(record.parent, None);
+/// assert_eq!(record.keys, [Slice32::default(); 2]);
+/// ```
 pub trait DumbDefault
 where
     Self: Sized,
@@ -23,3 +62,169 @@ where
     /// Returns an object initialized with dumb data
     fn dumb_default() -> Self;
 }
+
+/// Implements `DumbDefault` returning `Default::default()`
+macro_rules! impl_as_default {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl DumbDefault for $ty {
+                #[inline]
+                fn dumb_default() -> Self {
+                    Default::default()
+                }
+            }
+        )+
+    };
+}
+
+impl_as_default!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64
+);
+
+impl_as_default!(
+    crate::num::u1,
+    crate::num::u2,
+    crate::num::u3,
+    crate::num::u4,
+    crate::num::u5,
+    crate::num::u6,
+    crate::num::u7,
+    crate::num::u24,
+    crate::num::u256,
+    crate::num::u512,
+    crate::num::u1024,
+    crate::num::i256,
+    crate::num::i512,
+    crate::num::i1024,
+);
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl_as_default!(String);
+
+impl<T> DumbDefault for Option<T> {
+    #[inline]
+    fn dumb_default() -> Self {
+        None
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<T> DumbDefault for Box<T>
+where
+    T: DumbDefault,
+{
+    #[inline]
+    fn dumb_default() -> Self {
+        Box::new(T::dumb_default())
+    }
+}
+
+/// Implements `DumbDefault` for collections as empty collections
+#[cfg(any(feature = "std", feature = "alloc"))]
+macro_rules! impl_collection {
+    ($($ty:ident < $($param:ident),+ >),+ $(,)?) => {
+        $(
+            impl<$($param),+> DumbDefault for $ty<$($param),+> {
+                #[inline]
+                fn dumb_default() -> Self {
+                    $ty::new()
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl_collection!(
+    Vec<T>,
+    VecDeque<T>,
+    LinkedList<T>,
+    BTreeSet<T>,
+    BTreeMap<K, V>
+);
+
+#[cfg(feature = "std")]
+impl<K, V, S> DumbDefault for HashMap<K, V, S>
+where
+    S: Default,
+{
+    #[inline]
+    fn dumb_default() -> Self {
+        HashMap::default()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> DumbDefault for HashSet<T, S>
+where
+    S: Default,
+{
+    #[inline]
+    fn dumb_default() -> Self {
+        HashSet::default()
+    }
+}
+
+/// Implements `DumbDefault` for arrays of all sizes up to the number of the
+/// provided identifiers, following the same approach as `Default` in `core`
+macro_rules! impl_array {
+    ($n:expr, $t:ident $($ts:ident)*) => {
+        impl<T> DumbDefault for [T; $n]
+        where
+            T: DumbDefault,
+        {
+            #[inline]
+            fn dumb_default() -> Self {
+                [$t::dumb_default(), $($ts::dumb_default()),*]
+            }
+        }
+        impl_array!($n - 1, $($ts)*);
+    };
+    ($n:expr,) => {
+        impl<T> DumbDefault for [T; $n] {
+            #[inline]
+            fn dumb_default() -> Self {
+                []
+            }
+        }
+    };
+}
+
+impl_array!(32, T T T T T T T T T T T T T T T T T T T T T T T T T T T T T T T T);
+
+#[cfg(test)]
+mod test {
+    use super::DumbDefault;
+    use crate::num::{u24, u256};
+    use crate::{Slice32, Wrapper};
+
+    #[test]
+    fn dumb_defaults() {
+        assert_eq!(dumb!(u256), u256::ZERO);
+        assert_eq!(dumb!(u24), u24::ZERO);
+        assert_eq!(dumb!(Slice32), Slice32::from_inner([0u8; 32]));
+        assert_eq!(dumb!(Option<Slice32>), None);
+        assert_eq!(dumb!([u8; 0]), [0u8; 0]);
+        assert_eq!(dumb!([Slice32; 32]), [Slice32::default(); 32]);
+        assert_eq!(dumb!(Box<u256>), Box::new(u256::ZERO));
+        assert!(Vec::<Slice32>::dumb_default().is_empty());
+        assert!(dumb!(std::collections::HashMap<u8, Slice32>).is_empty());
+        assert!(dumb!(crate::flags::FlagVec).is_empty());
+    }
+}
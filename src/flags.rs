@@ -15,12 +15,15 @@
 
 //! Types for building flag-based APIs and functionality
 
-use std::cmp::{max, Ordering};
-use std::convert::{TryFrom, TryInto};
-use std::fmt::{self, Binary, Debug, Display, Formatter, LowerHex, Octal, UpperHex};
-use std::hash::{Hash, Hasher};
-use std::ops::{BitAnd, BitOr, BitXor};
-use std::str::FromStr;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::ToString, vec, vec::Vec};
+use core::cmp::{max, Ordering};
+use core::convert::TryFrom;
+use core::fmt::{self, Binary, Debug, Display, Formatter, LowerHex, Octal, UpperHex};
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+use core::ops::{BitAnd, BitOr, BitXor, Sub};
+use core::str::FromStr;
 
 /// A single feature flag, represented by it's number inside feature vector
 pub type FlagNo = u16;
@@ -33,53 +36,41 @@ pub struct FlagRef<'a> {
 }
 
 /// Structure holding a given set of features
+///
+/// With `serde` feature the vector is serialized as a string of `#` (set) and
+/// `_` (unset) flag markers for human-readable formats and as raw bytes for
+/// binary formats. In both cases trailing unset flags are trimmed, so equal
+/// vectors always have the same serialization.
 #[derive(Clone)]
 pub struct FlagVec(Vec<u8>);
 
-impl BitOr for FlagVec {
-    type Output = Self;
-    fn bitor(self, mut rhs: Self) -> Self::Output {
-        let mut lhs = self.shrunk();
-        rhs.shrink();
-        let size = max(lhs.capacity(), rhs.capacity());
-        lhs.enlarge(size);
-        rhs.enlarge(size);
-        for i in 0..rhs.0.len() {
-            rhs.0[i] |= lhs.0[i];
+/// Implements binary operation over flag vectors both for values and
+/// references, treating flags beyond the capacity of the shorter operand as
+/// unset
+macro_rules! impl_flag_op {
+    ($op:ident, $fn:ident, | $lhs:ident, $rhs:ident | $expr:expr) => {
+        impl $op for FlagVec {
+            type Output = Self;
+            #[inline]
+            fn $fn(self, rhs: Self) -> Self::Output {
+                FlagVec::combine(&self, &rhs, |$lhs, $rhs| $expr)
+            }
         }
-        rhs
-    }
-}
 
-impl BitAnd for FlagVec {
-    type Output = Self;
-    fn bitand(self, mut rhs: Self) -> Self::Output {
-        let mut lhs = self.shrunk();
-        rhs.shrink();
-        let size = max(lhs.capacity(), rhs.capacity());
-        lhs.enlarge(size);
-        rhs.enlarge(size);
-        for i in 0..rhs.0.len() {
-            rhs.0[i] &= lhs.0[i];
+        impl<'a> $op<&'a FlagVec> for &'a FlagVec {
+            type Output = FlagVec;
+            #[inline]
+            fn $fn(self, rhs: Self) -> Self::Output {
+                FlagVec::combine(self, rhs, |$lhs, $rhs| $expr)
+            }
         }
-        rhs
-    }
+    };
 }
 
-impl BitXor for FlagVec {
-    type Output = Self;
-    fn bitxor(self, mut rhs: Self) -> Self::Output {
-        let mut lhs = self.shrunk();
-        rhs.shrink();
-        let size = max(lhs.capacity(), rhs.capacity());
-        lhs.enlarge(size);
-        rhs.enlarge(size);
-        for i in 0..rhs.0.len() {
-            rhs.0[i] ^= lhs.0[i];
-        }
-        rhs
-    }
-}
+impl_flag_op!(BitOr, bitor, |lhs, rhs| lhs | rhs);
+impl_flag_op!(BitAnd, bitand, |lhs, rhs| lhs & rhs);
+impl_flag_op!(BitXor, bitxor, |lhs, rhs| lhs ^ rhs);
+impl_flag_op!(Sub, sub, |lhs, rhs| lhs & !rhs);
 
 impl Default for FlagVec {
     fn default() -> Self {
@@ -87,9 +78,16 @@ impl Default for FlagVec {
     }
 }
 
+/// Empty flag vector, same as [`FlagVec::default`]
+impl crate::DumbDefault for FlagVec {
+    fn dumb_default() -> Self {
+        FlagVec::new()
+    }
+}
+
 impl PartialEq for FlagVec {
     fn eq(&self, other: &Self) -> bool {
-        self.shrunk().0 == other.shrunk().0
+        self.significant() == other.significant()
     }
 }
 
@@ -103,54 +101,58 @@ impl PartialOrd for FlagVec {
 
 impl Ord for FlagVec {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.shrunk().0.cmp(&other.shrunk().0)
+        self.significant().cmp(other.significant())
     }
 }
 
 impl Hash for FlagVec {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.shrunk().0.hash(state)
+        self.significant().hash(state)
     }
 }
 
 impl Debug for FlagVec {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let mut shrunk = self.clone();
-        if f.alternate() {
-            f.write_str("flags:")?;
-            shrunk.shrink();
-        }
-        for b in 0..shrunk.capacity() {
-            write!(f, "{}", if self.is_set(b) { '1' } else { '0' })?;
-        }
-        Ok(())
+        f.debug_struct("FlagVec")
+            .field("capacity", &self.capacity())
+            .field("flags", &self.to_string())
+            .finish()
     }
 }
 
+/// Formats feature vector using mnemonic notation, where each of the flags up
+/// to the last set one is represented by `#` (set) or `_` (unset) character,
+/// like `#__#`. Alternate form (`{:#}`) lists numbers of the set flags
+/// separated by commas, like `0, 3, 17`. Both forms are parsed back with
+/// [`FlagVec::from_str`].
 impl Display for FlagVec {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let mut shrunk = self.clone();
-        let (p, n) = if f.alternate() {
-            shrunk.shrink();
-            ('#', '_')
-        } else {
-            ('+', '-')
-        };
-        for b in 0..shrunk.capacity() {
-            write!(f, "{}", if shrunk.is_set(b) { p } else { n })?;
+        if f.alternate() {
+            for (index, flag_no) in self.iter().enumerate() {
+                if index > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}", flag_no)?;
+            }
+        } else if let Some(max) = self.max_flag() {
+            for flag_no in 0..=max {
+                f.write_str(if self.is_set(flag_no) { "#" } else { "_" })?;
+            }
         }
         Ok(())
     }
 }
 
+/// Formats bytes of the inner buffer as hex, omitting trailing bytes without
+/// set flags, so equal vectors always have the same hex representation. Hex
+/// is parsed back with [`FromHex`](crate::hex::FromHex). Alternate form
+/// (`{:#x}`) adds `0x` prefix.
 impl LowerHex for FlagVec {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let mut shrunk = self.clone();
         if f.alternate() {
             f.write_str("0x")?;
-            shrunk.shrink();
         }
-        for b in shrunk.0 {
+        for b in self.significant() {
             write!(f, "{:02x}", b)?;
         }
         Ok(())
@@ -159,12 +161,10 @@ impl LowerHex for FlagVec {
 
 impl UpperHex for FlagVec {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let mut shrunk = self.clone();
         if f.alternate() {
             f.write_str("0x")?;
-            shrunk.shrink();
         }
-        for b in shrunk.0 {
+        for b in self.significant() {
             write!(f, "{:02X}", b)?;
         }
         Ok(())
@@ -199,17 +199,60 @@ impl Octal for FlagVec {
     }
 }
 
-/// Error parsing feature vector string representation: only `1`, `0`, `+`, `-`,
-/// `*`, `!`, `#`, `_` and whitespace characters are allowed
+/// Error parsing feature vector string or byte representation
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
-pub struct ParseError;
+pub enum ParseError {
+    /// Character `ch` at (zero-based) character position `pos` is not
+    /// allowed. Mnemonic notation accepts only `#`, `+`, `*` for set flags
+    /// and `_`, `-`, `!` for unset flags; list notation accepts only decimal
+    /// flag numbers separated by commas. Both notations ignore whitespaces.
+    InvalidChar {
+        /// Offending character
+        ch: char,
+        /// Position of the character within the string
+        pos: usize,
+    },
+
+    /// Flag number starting at character position `pos` exceeds maximum
+    /// flag number representable by [`FlagNo`]
+    FlagOverflow {
+        /// Position of the flag number within the string
+        pos: usize,
+    },
+
+    /// Byte `byte` at position `pos` of a byte-per-flag representation is
+    /// neither `0` nor `1`
+    InvalidByte {
+        /// Offending byte value
+        byte: u8,
+        /// Position of the byte
+        pos: usize,
+    },
+}
 
 impl Display for ParseError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        f.write_str(
-            "Error parsing feature vector string representation: only `1`, `0`, `+`, `-`, `*`, \
-            `!`, `#`, `_` and whitespace characters are allowed",
-        )
+        match self {
+            ParseError::InvalidChar { ch, pos } => write!(
+                f,
+                "invalid character `{}` at position {} of feature vector string \
+                 representation; use either `#__#` or `0, 3` notation",
+                ch.escape_default(),
+                pos
+            ),
+            ParseError::FlagOverflow { pos } => write!(
+                f,
+                "feature flag at position {} of feature vector string representation exceeds \
+                 maximum flag number 65535",
+                pos
+            ),
+            ParseError::InvalidByte { byte, pos } => write!(
+                f,
+                "invalid byte value {} at position {} of feature vector representation; only \
+                 0 and 1 are allowed",
+                byte, pos
+            ),
+        }
     }
 }
 
@@ -219,17 +262,40 @@ impl std::error::Error for ParseError {}
 impl FromStr for FlagVec {
     type Err = ParseError;
 
+    /// Parses feature vector from either mnemonic (`#__#`) or list (`0, 3`)
+    /// notation, as produced by [`Display`] implementation. List notation is
+    /// detected by the first non-whitespace character being a decimal digit;
+    /// the notations can't be mixed.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut vec = Vec::with_capacity(s.len());
-        for c in s.chars() {
-            vec.push(match c {
-                '1' | '+' | '*' | '#' => 1,
-                '0' | '-' | '!' | '_' => 0,
-                ' ' | '\n' | '\t' | '\r' => continue,
-                _ => return Err(ParseError),
-            })
+        if s.trim_start().starts_with(|ch: char| ch.is_ascii_digit()) {
+            FlagVec::parse_list(s)
+        } else {
+            FlagVec::parse_mnemonic(s)
         }
-        vec.try_into()
+    }
+}
+
+impl FromIterator<FlagNo> for FlagVec {
+    /// Constructs flag vector with all flags from the iterator set. Flag
+    /// numbers may go in any order and may repeat.
+    fn from_iter<T: IntoIterator<Item = FlagNo>>(iter: T) -> Self {
+        let mut vec = FlagVec::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl Extend<FlagNo> for FlagVec {
+    fn extend<T: IntoIterator<Item = FlagNo>>(&mut self, iter: T) {
+        for flag_no in iter {
+            self.set(flag_no);
+        }
+    }
+}
+
+impl From<&[FlagNo]> for FlagVec {
+    fn from(flags: &[FlagNo]) -> Self {
+        flags.iter().copied().collect()
     }
 }
 
@@ -250,20 +316,185 @@ impl TryFrom<&[u8]> for FlagVec {
             match v {
                 1 => vec.set(i as FlagNo),
                 0 => false,
-                _ => return Err(ParseError),
+                byte => {
+                    return Err(ParseError::InvalidByte {
+                        byte: *byte,
+                        pos: i,
+                    })
+                }
             };
         }
         Ok(vec)
     }
 }
 
+/// Parses hex representation of the inner buffer bytes. Trailing bytes without
+/// set flags are removed, so the parsed vector has the same representation as
+/// the one produced by [`ToHex::to_hex`](crate::hex::ToHex::to_hex).
+#[cfg(feature = "hex")]
+impl crate::hex::FromHex for FlagVec {
+    fn from_byte_iter<I>(iter: I) -> Result<Self, crate::hex::Error>
+    where
+        I: Iterator<Item = Result<u8, crate::hex::Error>> + ExactSizeIterator + DoubleEndedIterator,
+    {
+        let mut vec = FlagVec(iter.collect::<Result<_, _>>()?);
+        vec.shrink();
+        Ok(vec)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for FlagVec {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_string())
+        } else {
+            serializer.serialize_bytes(self.significant())
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FlagVec {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de;
+
+        struct Visitor;
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = FlagVec;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.write_str("feature vector bytes or a string in either `#__#` or `0, 3` notation")
+            }
+
+            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                FlagVec::from_str(s).map_err(de::Error::custom)
+            }
+
+            fn visit_bytes<E>(self, bytes: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let mut vec = FlagVec::from_inner(bytes.to_vec());
+                vec.shrink();
+                Ok(vec)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(Visitor)
+        } else {
+            deserializer.deserialize_bytes(Visitor)
+        }
+    }
+}
+
 impl FlagVec {
-    fn bits_to_bytes(bits: FlagNo) -> usize {
+    fn bits_to_bytes(bits: usize) -> usize {
         if bits == 0 {
             0
         } else {
-            ((bits - 1) / 8 + 1) as usize
+            (bits - 1) / 8 + 1
+        }
+    }
+
+    /// Parses mnemonic notation, keeping the capacity for all the flags
+    /// mentioned in the string, including trailing unset ones
+    fn parse_mnemonic(s: &str) -> Result<FlagVec, ParseError> {
+        let mut vec = FlagVec::new();
+        let mut count = 0usize;
+        for (pos, ch) in s.chars().enumerate() {
+            let set = match ch {
+                '#' | '+' | '*' => true,
+                '_' | '-' | '!' => false,
+                ch if ch.is_whitespace() => continue,
+                ch => return Err(ParseError::InvalidChar { ch, pos }),
+            };
+            let flag_no = FlagNo::try_from(count).map_err(|_| ParseError::FlagOverflow { pos })?;
+            if set {
+                vec.set(flag_no);
+            }
+            count += 1;
         }
+        vec.enlarge(count);
+        Ok(vec)
+    }
+
+    /// Parses list notation: comma-separated decimal numbers of set flags
+    fn parse_list(s: &str) -> Result<FlagVec, ParseError> {
+        let mut vec = FlagVec::new();
+        // Flag number being parsed, together with its starting position
+        let mut number = None::<(usize, FlagNo)>;
+        // Whether the current flag number was terminated by a whitespace
+        let mut terminated = false;
+        // Position of the last comma which is not yet followed by a number
+        let mut comma = None::<usize>;
+        for (pos, ch) in s.chars().enumerate() {
+            match ch {
+                '0'..='9' if !terminated => {
+                    let digit = ch as FlagNo - '0' as FlagNo;
+                    let (start, no) = number.unwrap_or((pos, 0));
+                    let no = no
+                        .checked_mul(10)
+                        .and_then(|no| no.checked_add(digit))
+                        .ok_or(ParseError::FlagOverflow { pos: start })?;
+                    number = Some((start, no));
+                    comma = None;
+                }
+                ',' if number.is_some() => {
+                    if let Some((_, no)) = number.take() {
+                        vec.set(no);
+                    }
+                    terminated = false;
+                    comma = Some(pos);
+                }
+                ch if ch.is_whitespace() => terminated = number.is_some(),
+                ch => return Err(ParseError::InvalidChar { ch, pos }),
+            }
+        }
+        match (number, comma) {
+            (Some((_, no)), _) => {
+                vec.set(no);
+            }
+            (None, Some(pos)) => return Err(ParseError::InvalidChar { ch: ',', pos }),
+            (None, None) => {}
+        }
+        Ok(vec)
+    }
+
+    /// Returns bytes of the inner buffer up to the last byte having any flag
+    /// set, i.e. the buffer without trailing unset capacity
+    #[inline]
+    fn significant(&self) -> &[u8] {
+        let len = self
+            .0
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .map(|pos| pos + 1)
+            .unwrap_or(0);
+        &self.0[..len]
+    }
+
+    /// Combines two flag vectors byte by byte into a new vector with the
+    /// capacity equal to the largest of the operands capacities; bytes
+    /// missing in the shorter operand are treated as zero
+    fn combine(lhs: &FlagVec, rhs: &FlagVec, op: impl Fn(u8, u8) -> u8) -> FlagVec {
+        let len = max(lhs.0.len(), rhs.0.len());
+        let byte = |vec: &FlagVec, pos: usize| vec.0.get(pos).copied().unwrap_or(0);
+        FlagVec(
+            (0..len)
+                .map(|pos| op(byte(lhs, pos), byte(rhs, pos)))
+                .collect(),
+        )
     }
 
     /// Constructs a features vector of zero feature flag set
@@ -276,20 +507,43 @@ impl FlagVec {
         if upto == 0 {
             FlagVec::default()
         } else {
-            FlagVec(vec![0u8; Self::bits_to_bytes(upto)])
+            FlagVec(vec![0u8; Self::bits_to_bytes(upto as usize)])
         }
     }
 
     /// Detects whether structure contains any flags set
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.shrunk().0 == &[] as &[u8]
+        self.0.iter().all(|byte| *byte == 0)
     }
 
     /// Counts number of flags set
     #[inline]
     pub fn count_flags(&self) -> u16 {
-        self.iter().count() as u16
+        self.count_ones() as u16
+    }
+
+    /// Counts number of flags set (population count)
+    #[inline]
+    pub fn count_ones(&self) -> u32 {
+        self.0.iter().map(|byte| byte.count_ones()).sum()
+    }
+
+    /// Counts number of flags which are not set within the current capacity
+    /// of the internal buffer
+    #[inline]
+    pub fn count_zeros(&self) -> u32 {
+        self.0.iter().map(|byte| byte.count_zeros()).sum()
+    }
+
+    /// Returns number of the highest flag which is set, or [`Option::None`]
+    /// if no flags are set
+    #[inline]
+    pub fn max_flag(&self) -> Option<FlagNo> {
+        self.0
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .map(|pos| (pos * 8 + 7 - self.0[pos].leading_zeros() as usize) as FlagNo)
     }
 
     /// Returns byte slice representation of the inner data (slice of bytes,
@@ -304,6 +558,32 @@ impl FlagVec {
         Self(slice)
     }
 
+    /// Constructs flag vector from a bit mask, where bit `i` of the mask
+    /// corresponds to the flag number `i`
+    pub fn from_mask(mask: u64) -> Self {
+        let mut vec = FlagVec(mask.to_le_bytes().to_vec());
+        vec.shrink();
+        vec
+    }
+
+    /// Returns bit mask with bit `i` set for each of the set flags number `i`,
+    /// or [`Option::None`] if any of the flags above 63 is set
+    pub fn try_to_mask(&self) -> Option<u64> {
+        let bytes = self.significant();
+        if bytes.len() > 8 {
+            return None;
+        }
+        let mut mask = [0u8; 8];
+        mask[..bytes.len()].copy_from_slice(bytes);
+        Some(u64::from_le_bytes(mask))
+    }
+
+    /// Returns numbers of all set flags in ascending order
+    #[inline]
+    pub fn to_indices(&self) -> Vec<FlagNo> {
+        self.iter().collect()
+    }
+
     /// Returns a shrunk copy of the self
     pub fn shrunk(&self) -> Self {
         let mut shrinked = self.clone();
@@ -311,35 +591,62 @@ impl FlagVec {
         shrinked
     }
 
-    /// Creates an iterator for the current feature flags which have "set" state
+    /// Creates an iterator for the current feature flags which have "set"
+    /// state, yielding flag numbers in ascending order
     #[inline]
-    pub fn iter(&self) -> AllSet {
+    pub fn iter(&self) -> AllSet<'_> {
         AllSet::new(self)
     }
 
     /// Creates iterator over known set of the features
     #[inline]
-    pub fn known_iter(&self, mut known: FlagVec) -> FilteredIter {
-        known.enlarge(self.capacity());
+    pub fn known_iter(&self, mut known: FlagVec) -> FilteredIter<'_> {
+        known.enlarge(self.bit_len());
         FilteredIter::new(self, known)
     }
 
     /// Creates iterator over unknown set of the features, i.e. features that
     /// **do not** match flags set in `known` parameter
     #[inline]
-    pub fn unknown_iter(&self, mut known: FlagVec) -> FilteredIter {
-        known.enlarge(self.capacity());
+    pub fn unknown_iter(&self, mut known: FlagVec) -> FilteredIter<'_> {
+        known.enlarge(self.bit_len());
         for byte in 0..self.0.len() {
             known.0[byte as usize] = !known.0[byte as usize];
         }
         FilteredIter::new(self, known)
     }
 
+    /// Detects whether any of the flags set in `other` is also set in `self`
+    #[inline]
+    pub fn intersects(&self, other: &FlagVec) -> bool {
+        self.0.iter().zip(&other.0).any(|(lhs, rhs)| lhs & rhs != 0)
+    }
+
+    /// Detects whether all of the flags set in `other` are also set in `self`
+    /// (i.e. `self` is a superset of `other`)
+    #[inline]
+    pub fn contains(&self, other: &FlagVec) -> bool {
+        other
+            .0
+            .iter()
+            .enumerate()
+            .all(|(pos, rhs)| rhs & !self.0.get(pos).copied().unwrap_or(0) == 0)
+    }
+
     /// Returns how many features current structure can hold without
     /// re-allocation of the internal buffer
     #[inline]
-    pub(self) fn capacity(&self) -> FlagNo {
-        (self.0.len() * 8) as FlagNo
+    fn bit_len(&self) -> usize {
+        self.0.len() * 8
+    }
+
+    /// Returns the highest flag number which can be set without re-allocation
+    /// of the internal buffer, or [`Option::None`] if the buffer is empty
+    #[inline]
+    pub fn capacity(&self) -> Option<FlagNo> {
+        self.bit_len()
+            .checked_sub(1)
+            .map(|max| FlagNo::try_from(max).unwrap_or(!0))
     }
 
     /// Changes the size of the internal buffer holding flags until it will be
@@ -347,38 +654,61 @@ impl FlagVec {
     /// required and `false` if no resizing was needed and the internal buffer
     /// already had sufficient capacity
     #[inline]
-    fn enlarge(&mut self, upto: FlagNo) -> bool {
-        if upto <= self.capacity() {
+    fn enlarge(&mut self, upto: usize) -> bool {
+        let len = Self::bits_to_bytes(upto);
+        if len <= self.0.len() {
             // We have nothing to do
             return false;
         }
-
-        let old = self.0.clone();
-        self.0 = vec![0u8; Self::bits_to_bytes(upto)];
-        self.0[..old.len()].copy_from_slice(&old);
+        self.0.resize(len, 0);
         true
     }
 
+    /// Ensures that the internal buffer is able to hold at least `upto` flags
+    /// (i.e. flags with numbers below `upto`) without re-allocation. Does not
+    /// change any of the flags. Returns `true` if the buffer had to be
+    /// enlarged and `false` if it already had sufficient capacity
+    #[inline]
+    pub fn reserve(&mut self, upto: FlagNo) -> bool {
+        self.enlarge(upto as usize)
+    }
+
+    /// Resizes the internal buffer to fit exactly `upto` flags (rounded up to
+    /// the whole bytes), enlarging or reducing it as needed. All flags with
+    /// numbers equal or above `upto` get unset.
+    pub fn resize(&mut self, upto: FlagNo) {
+        self.0.resize(Self::bits_to_bytes(upto as usize), 0);
+        let tail = upto % 8;
+        if let Some(last) = self.0.last_mut() {
+            if tail > 0 {
+                *last &= (1u8 << tail) - 1;
+            }
+        }
+    }
+
+    /// Unsets all flags with numbers equal or above `upto` and
+    /// [shrinks](FlagVec::shrink) the internal buffer. Truncating to zero
+    /// results in a value equal to [`FlagVec::default`].
+    pub fn truncate(&mut self, upto: FlagNo) {
+        if (upto as usize) < self.bit_len() {
+            self.resize(upto);
+        }
+        self.shrink();
+    }
+
     /// Reduces the size of the internal buffer to the smallest capacity
     /// required to keep all currently set feature flags. Returns `true` if
     /// resize operation was required, or `false` otherwise, when the internal
-    /// buffer already was of the smallest possible size
+    /// buffer already was of the smallest possible size.
+    ///
+    /// If no flags are set the buffer becomes empty, which is the same
+    /// representation as used by [`FlagVec::default`].
     #[inline]
     pub fn shrink(&mut self) -> bool {
-        let capacity = self.capacity();
-        if capacity == 0 {
-            return false;
-        }
-        let mut top = 1;
-        while top < capacity && !self.is_set(capacity - top) {
-            top += 1;
-        }
-        let top = capacity - top;
-        let used = Self::bits_to_bytes(top);
+        let used = self.significant().len();
         if used < self.0.len() {
-            let old = self.0.clone();
-            self.0 = vec![0u8; used as usize];
-            self.0.copy_from_slice(&old[..used]);
+            self.0.truncate(used);
+            self.0.shrink_to_fit();
             return true;
         }
         false
@@ -389,7 +719,7 @@ impl FlagVec {
     /// [`Option::None`].
     #[inline]
     fn byte_at(&self, flag_no: FlagNo) -> Option<&u8> {
-        if flag_no >= self.capacity() {
+        if flag_no as usize >= self.bit_len() {
             return None;
         }
         Some(&self.0[flag_no as usize / 8])
@@ -400,7 +730,7 @@ impl FlagVec {
     /// value exceeds current maximum flag capacity.
     #[inline]
     fn mut_byte_at(&mut self, flag_no: FlagNo) -> &mut u8 {
-        self.enlarge(flag_no);
+        self.enlarge(flag_no as usize + 1);
         &mut self.0[flag_no as usize / 8]
     }
 
@@ -451,8 +781,15 @@ pub struct AllSet<'a> {
     /// Reference to features object we iterate
     features: &'a FlagVec,
 
-    /// Offset of the last feature flag
-    offset: FlagNo,
+    /// Offset of the last feature flag; wider than [`FlagNo`] to fit the
+    /// offset following the flag `FlagNo::MAX`
+    offset: u32,
+
+    /// Offset following the last flag which is not yet iterated from the end
+    end: u32,
+
+    /// Number of set flags which are not yet iterated
+    remaining: usize,
 }
 
 impl<'a> AllSet<'a> {
@@ -462,6 +799,8 @@ impl<'a> AllSet<'a> {
         Self {
             features,
             offset: 0,
+            end: features.max_flag().map(|flag| flag as u32 + 1).unwrap_or(0),
+            remaining: features.count_ones() as usize,
         }
     }
 }
@@ -471,16 +810,40 @@ impl Iterator for AllSet<'_> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        while self.offset < self.features.capacity() {
+        while self.offset < self.end {
+            let flag_no = self.offset as FlagNo;
             self.offset += 1;
-            if self.features.is_set(self.offset - 1) {
-                return Some(self.offset - 1);
+            if self.features.is_set(flag_no) {
+                self.remaining -= 1;
+                return Some(flag_no);
             }
         }
         None
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
 }
 
+impl DoubleEndedIterator for AllSet<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.end > self.offset {
+            self.end -= 1;
+            let flag_no = self.end as FlagNo;
+            if self.features.is_set(flag_no) {
+                self.remaining -= 1;
+                return Some(flag_no);
+            }
+        }
+        None
+    }
+}
+
+impl ExactSizeIterator for AllSet<'_> {}
+
 /// Iterator over a filtered set feature flags
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub struct FilteredIter<'a> {
@@ -491,7 +854,7 @@ pub struct FilteredIter<'a> {
     filter: FlagVec,
 
     /// Offset of the last feature flag
-    offset: FlagNo,
+    offset: u32,
 }
 
 impl<'a> FilteredIter<'a> {
@@ -512,10 +875,11 @@ impl Iterator for FilteredIter<'_> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        while self.offset < self.features.capacity() {
+        while (self.offset as usize) < self.features.bit_len() {
+            let flag_no = self.offset as FlagNo;
             self.offset += 1;
-            if self.features.is_set(self.offset - 1) && self.filter.is_set(self.offset - 1) {
-                return Some(self.offset - 1);
+            if self.features.is_set(flag_no) && self.filter.is_set(flag_no) {
+                return Some(flag_no);
             }
         }
         None
@@ -536,58 +900,135 @@ mod test {
         assert_eq!(f1, f2);
         assert_eq!(f1, f3);
         assert_eq!(f2, f3);
-        assert_eq!(f1.capacity(), 0);
+        assert_eq!(f1.capacity(), None);
         assert_eq!(f1.iter().collect::<Vec<_>>(), empty_vec);
-        assert_eq!(f2.capacity(), 0);
+        assert_eq!(f2.capacity(), None);
         assert_eq!(f2.iter().collect::<Vec<_>>(), empty_vec);
-        assert_eq!(f3.capacity(), 0);
+        assert_eq!(f3.capacity(), None);
         assert_eq!(f3.iter().collect::<Vec<_>>(), empty_vec);
 
         let f4 = FlagVec::with_capacity(10);
         assert_eq!(f1, f4);
-        assert_eq!(f4.capacity(), 16);
+        assert_eq!(f4.capacity(), Some(15));
         assert_eq!(f4.iter().collect::<Vec<_>>(), empty_vec);
     }
 
     #[test]
     fn test_flag_capacity() {
         let mut f1 = FlagVec::with_capacity(10);
-        assert_eq!(f1.capacity(), 16);
+        assert_eq!(f1.capacity(), Some(15));
         let mut f2 = f1.clone();
-        assert_eq!(f2.capacity(), 16);
+        assert_eq!(f2.capacity(), Some(15));
         f2 = f1.shrunk();
         f1.shrink();
-        assert_eq!(f1.capacity(), 0);
+        assert_eq!(f1.capacity(), None);
         assert_eq!(f1, f2);
         f1.enlarge(20);
-        assert_eq!(f1.capacity(), 24);
+        assert_eq!(f1.capacity(), Some(23));
+    }
+
+    #[test]
+    fn test_reserve_resize() {
+        let mut f1 = FlagVec::new();
+        assert_eq!(f1.reserve(0), false);
+        assert_eq!(f1.capacity(), None);
+        assert_eq!(f1.reserve(9), true);
+        assert_eq!(f1.capacity(), Some(15));
+        assert_eq!(f1.reserve(16), false);
+        assert!(f1.is_empty());
+
+        f1.set(3);
+        f1.set(13);
+        f1.resize(40);
+        assert_eq!(f1.capacity(), Some(39));
+        assert_eq!(f1.iter().collect::<Vec<_>>(), vec![3, 13]);
+        f1.resize(12);
+        assert_eq!(f1.capacity(), Some(15));
+        assert_eq!(f1.iter().collect::<Vec<_>>(), vec![3]);
+        f1.resize(0);
+        assert_eq!(f1.capacity(), None);
+        assert_eq!(f1, FlagVec::default());
+    }
+
+    #[test]
+    fn test_truncate() {
+        let mut f1 = FlagVec::with_capacity(64);
+        f1.set(0);
+        f1.set(9);
+        f1.set(17);
+        f1.truncate(100);
+        assert_eq!(f1.capacity(), Some(23));
+        f1.truncate(17);
+        assert_eq!(f1.capacity(), Some(15));
+        assert_eq!(f1.iter().collect::<Vec<_>>(), vec![0, 9]);
+        f1.truncate(9);
+        assert_eq!(f1.capacity(), Some(7));
+        assert_eq!(f1.iter().collect::<Vec<_>>(), vec![0]);
+        f1.truncate(0);
+        assert_eq!(f1, FlagVec::default());
+        assert_eq!(f1.as_inner(), FlagVec::default().as_inner());
+    }
+
+    #[test]
+    fn test_shrink_invariants() {
+        // Simple linear congruential generator, so the test is deterministic
+        let mut seed = 0x2545_f491_u32;
+        let mut next = move || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            seed >> 8
+        };
+        for _ in 0..256 {
+            let mut f1 = FlagVec::with_capacity((next() % 128) as FlagNo);
+            for _ in 0..(next() % 16) {
+                let flag_no = (next() % 128) as FlagNo;
+                if next() % 2 == 0 {
+                    f1.set(flag_no);
+                } else {
+                    f1.unset(flag_no);
+                }
+            }
+            let flags = f1.iter().collect::<Vec<_>>();
+            let mut f2 = f1.clone();
+            f2.shrink();
+            assert_eq!(f1, f2);
+            assert_eq!(f2.iter().collect::<Vec<_>>(), flags);
+            assert_eq!(
+                f2.capacity().map(|max| max / 8),
+                f1.max_flag().map(|max| max / 8)
+            );
+            assert_eq!(f2.shrink(), false);
+            assert_eq!(f2.as_inner(), f1.shrunk().as_inner());
+            f2.reserve(300);
+            assert_eq!(f1, f2);
+            assert_eq!(f2.iter().collect::<Vec<_>>(), flags);
+        }
     }
 
     #[test]
     fn test_flag_ops() {
         let mut f1 = FlagVec::with_capacity(10);
         assert_eq!(f1.is_set(33), false);
-        assert_eq!(f1.capacity(), 16);
+        assert_eq!(f1.capacity(), Some(15));
         assert_eq!(f1.set(2), false);
         assert_eq!(f1.is_set(2), true);
         assert_eq!(f1.unset(2), true);
         assert_eq!(f1.is_set(2), false);
         assert_eq!(f1.set(7), false);
         f1.shrink();
-        assert_eq!(f1.capacity(), 8);
+        assert_eq!(f1.capacity(), Some(7));
         assert_eq!(f1.set(22), false);
         assert_eq!(f1.is_set(22), true);
-        assert_eq!(f1.capacity(), 24);
+        assert_eq!(f1.capacity(), Some(23));
         assert_eq!(f1.invert(22), ());
         assert_eq!(f1.is_set(22), false);
-        assert_eq!(f1.capacity(), 24);
+        assert_eq!(f1.capacity(), Some(23));
         f1.shrink();
-        assert_eq!(f1.capacity(), 8);
+        assert_eq!(f1.capacity(), Some(7));
     }
 
     #[test]
     fn test_fmt() {
-        let mut f1 = FlagVec::from_str("-0-\t#__1 \n--\r+* +!").unwrap();
+        let mut f1 = FlagVec::from_str("-_-\t#__* \n--\r+* +!").unwrap();
         assert_eq!(f1.is_set(0), false);
         assert_eq!(f1.is_set(1), false);
         assert_eq!(f1.is_set(2), false);
@@ -601,19 +1042,26 @@ mod test {
         assert_eq!(f1.is_set(10), true);
         assert_eq!(f1.is_set(11), true);
         assert_eq!(f1.is_set(12), false);
-        assert_eq!(f1.capacity(), 16);
-        assert_eq!(format!("{}", f1), "---+--+--+++----");
-        assert_eq!(format!("{:#}", f1), "___#__#__###____");
-        assert_eq!(format!("{:?}", f1), "0001001001110000");
+        assert_eq!(f1.capacity(), Some(15));
+        assert_eq!(format!("{}", f1), "___#__#__###");
+        assert_eq!(format!("{:#}", f1), "3, 6, 9, 10, 11");
+        assert_eq!(
+            format!("{:?}", f1),
+            r#"FlagVec { capacity: Some(15), flags: "___#__#__###" }"#
+        );
         assert_eq!(format!("{:x}", f1), "480e");
         assert_eq!(format!("{:X}", f1), "480E");
         assert_eq!(format!("{:b}", f1), "0100100000001110");
         assert_eq!(format!("{:o}", f1), "110016");
         f1.enlarge(22);
-        assert_eq!(format!("{}", f1), "---+--+--+++------------");
-        assert_eq!(format!("{:#}", f1), "___#__#__###____");
-        assert_eq!(format!("{:?}", f1), "000100100111000000000000");
-        assert_eq!(format!("{:#?}", f1), "flags:0001001001110000");
+        assert_eq!(format!("{}", f1), "___#__#__###");
+        assert_eq!(format!("{:#}", f1), "3, 6, 9, 10, 11");
+        assert_eq!(
+            format!("{:?}", f1),
+            r#"FlagVec { capacity: Some(23), flags: "___#__#__###" }"#
+        );
+        assert_eq!(format!("{:x}", f1), "480e");
+        assert_eq!(format!("{:X}", f1), "480E");
         assert_eq!(format!("{:#x}", f1), "0x480e");
         assert_eq!(format!("{:#X}", f1), "0x480E");
         assert_eq!(format!("{:#b}", f1), "0b0100100000001110");
@@ -667,4 +1115,379 @@ mod test {
             FlagVec::from_str("-++--++---+-+--+").unwrap()
         );
     }
+
+    #[test]
+    fn test_binary_sub() {
+        let f1 = FlagVec::from_str("---+--+--+++-").unwrap();
+        let f2 = FlagVec::from_str("-+++-+---+-++--+").unwrap();
+        assert_eq!(&f1 - &f2, FlagVec::from_str("------+---+").unwrap());
+        assert_eq!(f2 - f1, FlagVec::from_str("-++--+------+--+").unwrap());
+    }
+
+    #[test]
+    fn test_binary_different_len() {
+        let mut short = FlagVec::new();
+        short.set(1);
+        let mut long = FlagVec::with_capacity(64);
+        long.set(1);
+        long.set(40);
+
+        for res in &[
+            &short | &long,
+            &long | &short,
+            &short & &long,
+            &short ^ &long,
+            &short - &long,
+            &long - &short,
+        ] {
+            assert_eq!(res.capacity(), Some(63));
+        }
+        assert_eq!(&short | &long, &long | &short);
+        assert_eq!(&short | &long, long);
+        assert_eq!(&short & &long, short);
+        assert_eq!(&long & &short, short);
+        assert_eq!((&short ^ &long).iter().collect::<Vec<_>>(), vec![40]);
+        assert_eq!(&long - &short, &short ^ &long);
+        assert!((&short - &long).is_empty());
+        assert_eq!(short.clone() | long.clone(), long.clone() | short.clone());
+    }
+
+    #[test]
+    fn test_predicates() {
+        let mut f1 = FlagVec::new();
+        f1.set(0);
+        f1.set(17);
+        let mut f2 = FlagVec::with_capacity(128);
+        f2.set(17);
+        let mut f3 = FlagVec::new();
+        f3.set(8);
+
+        assert!(f1.intersects(&f2));
+        assert!(f2.intersects(&f1));
+        assert!(!f1.intersects(&f3));
+        assert!(!f1.intersects(&FlagVec::new()));
+
+        assert!(f1.contains(&f2));
+        assert!(!f2.contains(&f1));
+        assert!(!f1.contains(&f3));
+        assert!(f1.contains(&f1));
+        assert!(f1.contains(&FlagVec::with_capacity(256)));
+        assert!(FlagVec::new().contains(&FlagVec::with_capacity(16)));
+    }
+
+    #[test]
+    fn test_eq_trailing_capacity() {
+        let mut f1 = FlagVec::new();
+        f1.set(0);
+        let mut f2 = FlagVec::with_capacity(100);
+        f2.set(0);
+        assert_ne!(f1, FlagVec::new());
+        assert_eq!(f1, f2);
+        assert_eq!(f1.cmp(&f2), Ordering::Equal);
+
+        let mut f3 = FlagVec::with_capacity(16);
+        f3.set(8);
+        assert_eq!(f3.shrunk().capacity(), Some(15));
+        assert_ne!(f3, FlagVec::new());
+        f3.unset(8);
+        assert!(f3.shrink());
+        assert_eq!(f3.capacity(), None);
+        assert_eq!(f3, FlagVec::new());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let mut f1 = FlagVec::new();
+        f1.set(0);
+        f1.set(3);
+        f1.set(17);
+        assert_eq!(FlagVec::from_str("#__#_____________#").unwrap(), f1);
+        assert_eq!(FlagVec::from_str("0,3,17").unwrap(), f1);
+        assert_eq!(FlagVec::from_str(" 17 ,\t0, 3 ").unwrap(), f1);
+        assert_eq!(FlagVec::from_str("0, 0, 3, 17").unwrap(), f1);
+        assert_eq!(FlagVec::from_str("").unwrap(), FlagVec::new());
+        assert_eq!(FlagVec::from_str(" \n").unwrap(), FlagVec::new());
+        assert_eq!(FlagVec::from_str("65535").unwrap().max_flag(), Some(65535));
+        assert_eq!(FlagVec::from_str("___").unwrap().capacity(), Some(7));
+
+        assert_eq!(
+            FlagVec::from_str("#_0"),
+            Err(ParseError::InvalidChar { ch: '0', pos: 2 })
+        );
+        assert_eq!(
+            FlagVec::from_str("0,3#"),
+            Err(ParseError::InvalidChar { ch: '#', pos: 3 })
+        );
+        assert_eq!(
+            FlagVec::from_str("0,,3"),
+            Err(ParseError::InvalidChar { ch: ',', pos: 2 })
+        );
+        assert_eq!(
+            FlagVec::from_str("0, 3,"),
+            Err(ParseError::InvalidChar { ch: ',', pos: 4 })
+        );
+        assert_eq!(
+            FlagVec::from_str("0 3"),
+            Err(ParseError::InvalidChar { ch: '3', pos: 2 })
+        );
+        assert_eq!(
+            FlagVec::from_str("1, 65536"),
+            Err(ParseError::FlagOverflow { pos: 3 })
+        );
+        assert_eq!(
+            FlagVec::from_str("__x"),
+            Err(ParseError::InvalidChar { ch: 'x', pos: 2 })
+        );
+        assert_eq!(
+            FlagVec::from_str("0;1").unwrap_err().to_string(),
+            "invalid character `;` at position 1 of feature vector string representation; \
+             use either `#__#` or `0, 3` notation"
+        );
+        assert_eq!(
+            FlagVec::try_from(&[0u8, 1, 2][..]),
+            Err(ParseError::InvalidByte { byte: 2, pos: 2 })
+        );
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        let mut f1 = FlagVec::with_capacity(64);
+        for flag_no in &[0, 3, 8, 17, 40] {
+            f1.set(*flag_no);
+        }
+        for vec in &[FlagVec::new(), FlagVec::with_capacity(32), f1] {
+            assert_eq!(&FlagVec::from_str(&format!("{}", vec)).unwrap(), vec);
+            assert_eq!(&FlagVec::from_str(&format!("{:#}", vec)).unwrap(), vec);
+        }
+        assert_eq!(format!("{:#}", FlagVec::new()), "");
+        assert_eq!(format!("{}", FlagVec::with_capacity(32)), "");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let check = |flags: FlagVec, json: &str| {
+            let json = format!("\"{}\"", json);
+            assert_eq!(serde_json::to_string(&flags).unwrap(), json);
+            assert_eq!(serde_json::from_str::<FlagVec>(&json).unwrap(), flags);
+
+            let bin_encoded = bincode::serialize(&flags).unwrap();
+            assert_eq!(bin_encoded, bincode::serialize(&flags.shrunk()).unwrap());
+            let bin_decoded: FlagVec = bincode::deserialize(&bin_encoded).unwrap();
+            assert_eq!(bin_decoded, flags);
+            assert_eq!(bin_decoded.as_inner(), flags.shrunk().as_inner());
+        };
+
+        check(FlagVec::new(), "");
+        check(FlagVec::with_capacity(64), "");
+        check(FlagVec::from_str("#").unwrap(), "#");
+        check(
+            FlagVec::from_str("-+-+---+ -------- +").unwrap(),
+            "_#_#___#________#",
+        );
+        let mut large = FlagVec::with_capacity(1024);
+        large.set(9);
+        check(large, "_________#");
+
+        assert_eq!(
+            serde_json::from_str::<FlagVec>("\"0, 2,4\"").unwrap(),
+            FlagVec::from_str("#_#_#").unwrap()
+        );
+        assert_eq!(
+            bincode::deserialize::<FlagVec>(&[3, 0, 0, 0, 0, 0, 0, 0, 0x10, 0, 0])
+                .unwrap()
+                .as_inner(),
+            &[0x10]
+        );
+        let err = serde_json::from_str::<FlagVec>("\"#_x\"").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("invalid character `x` at position 2 of feature vector string"));
+    }
+
+    #[test]
+    fn test_flag_count() {
+        let mut f = FlagVec::new();
+        assert!(f.is_empty());
+        assert_eq!(f.count_ones(), 0);
+        assert_eq!(f.count_zeros(), 0);
+        assert_eq!(f.max_flag(), None);
+
+        f.set(3);
+        f.set(9);
+        f.unset(9);
+        assert!(!f.is_empty());
+        assert_eq!(f.count_ones(), 1);
+        assert_eq!(f.count_flags(), 1);
+        assert_eq!(f.count_zeros(), 15);
+        assert_eq!(f.max_flag(), Some(3));
+
+        f.unset(3);
+        assert!(f.is_empty());
+        assert_eq!(f.count_zeros(), 16);
+        assert_eq!(f.max_flag(), None);
+    }
+
+    #[test]
+    fn test_flag_max() {
+        let mut f = FlagVec::new();
+        f.set(1);
+        f.set(FlagNo::MAX);
+        assert_eq!(f.max_flag(), Some(FlagNo::MAX));
+        assert_eq!(f.count_ones(), 2);
+        assert_eq!(f.count_zeros(), 65534);
+        assert_eq!(f.iter().len(), 2);
+        assert_eq!(f.iter().collect::<Vec<_>>(), vec![1, FlagNo::MAX]);
+        assert_eq!(f.iter().rev().collect::<Vec<_>>(), vec![FlagNo::MAX, 1]);
+        assert_eq!(f.to_indices(), vec![1, FlagNo::MAX]);
+        assert_eq!(
+            f.known_iter(f.clone()).collect::<Vec<_>>(),
+            vec![1, FlagNo::MAX]
+        );
+        assert_eq!(format!("{:#}", f), "1, 65535");
+        assert_eq!(format!("{}", f).len(), 65536);
+
+        let empty = FlagVec::with_capacity(FlagNo::MAX);
+        assert_eq!(empty.count_ones(), 0);
+        assert_eq!(empty.count_zeros(), 65536);
+        assert_eq!(empty.iter().next(), None);
+    }
+
+    #[test]
+    fn test_flag_iter_byte_boundary() {
+        let mut f = FlagVec::new();
+        f.set(0);
+        f.set(7);
+        f.set(8);
+        f.set(15);
+        assert_eq!(f.capacity(), Some(15));
+        assert_eq!(f.max_flag(), Some(15));
+
+        let iter = f.iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0, 7, 8, 15]);
+        assert_eq!(f.iter().rev().collect::<Vec<_>>(), vec![15, 8, 7, 0]);
+
+        let mut iter = f.iter();
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next_back(), Some(15));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back(), Some(8));
+        assert_eq!(iter.next(), Some(7));
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn test_flag_iter_large_capacity() {
+        let mut f = FlagVec::with_capacity(4096);
+        f.set(1);
+        f.set(10);
+        assert_eq!(f.capacity(), Some(4095));
+        assert_eq!(f.count_ones(), 2);
+        assert_eq!(f.count_zeros(), 4094);
+        assert_eq!(f.max_flag(), Some(10));
+
+        let mut iter = f.iter();
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back(), Some(10));
+        assert_eq!(iter.next_back(), Some(1));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(f.iter().collect::<Vec<_>>(), vec![1, 10]);
+    }
+
+    #[test]
+    fn test_flag_indices() {
+        let f = [17u16, 0, 5, 17, 0].iter().copied().collect::<FlagVec>();
+        assert_eq!(f.to_indices(), vec![0, 5, 17]);
+        assert_eq!(FlagVec::from(&[5u16, 17, 0][..]), f);
+        assert_eq!(f.to_indices().into_iter().collect::<FlagVec>(), f);
+        assert_eq!(f, flags![0, 5, 17]);
+        assert_eq!(f, flags![17, 5, 0, 5,]);
+
+        let mut g = flags![0];
+        g.extend(vec![17, 5]);
+        assert_eq!(g, f);
+
+        let empty = Vec::<FlagNo>::new().into_iter().collect::<FlagVec>();
+        assert_eq!(empty, FlagVec::new());
+        assert_eq!(FlagVec::from(&[][..]), FlagVec::new());
+        assert_eq!(flags![], FlagVec::new());
+        assert!(empty.to_indices().is_empty());
+    }
+
+    #[test]
+    fn test_flag_mask() {
+        let f = flags![0, 5, 17, 63];
+        let mask = 1u64 | 1 << 5 | 1 << 17 | 1 << 63;
+        assert_eq!(FlagVec::from_mask(mask), f);
+        assert_eq!(f.try_to_mask(), Some(mask));
+        assert_eq!(FlagVec::from_mask(mask).to_indices(), vec![0, 5, 17, 63]);
+        assert_eq!(FlagVec::from_mask(mask).as_inner().len(), 8);
+        assert_eq!(FlagVec::from_mask(0x21).as_inner(), &[0x21]);
+
+        assert_eq!(FlagVec::from_mask(0), FlagVec::new());
+        assert!(FlagVec::from_mask(0).as_inner().is_empty());
+        assert_eq!(FlagVec::new().try_to_mask(), Some(0));
+        assert_eq!(FlagVec::with_capacity(1024).try_to_mask(), Some(0));
+
+        let mut large = f.clone();
+        large.set(64);
+        assert_eq!(large.try_to_mask(), None);
+        large.unset(64);
+        assert_eq!(large.try_to_mask(), Some(mask));
+    }
+
+    #[test]
+    #[cfg(feature = "hex")]
+    fn test_hex() {
+        use crate::hex::{Error, FromHex, ToHex};
+
+        assert_eq!(FlagVec::new().to_hex(), "");
+        assert_eq!(FlagVec::from_hex(""), Ok(FlagVec::new()));
+        assert_eq!(FlagVec::from_hex("0000"), Ok(FlagVec::new()));
+        assert!(FlagVec::from_hex("0000").unwrap().as_inner().is_empty());
+
+        let vec = FlagVec::from_str("___#__#__###").unwrap();
+        assert_eq!(vec.to_hex(), "480e");
+        assert_eq!(vec.to_hex_upper(), "480E");
+        assert_eq!(FlagVec::from_hex("480e"), Ok(vec.clone()));
+        assert_eq!(FlagVec::from_hex_prefixed("0x480E"), Ok(vec.clone()));
+        let padded = FlagVec::from_hex("480e0000").unwrap();
+        assert_eq!(padded.as_inner(), &[0x48, 0x0e]);
+        assert_eq!(padded, vec);
+
+        let mut enlarged = vec.clone();
+        enlarged.enlarge(64);
+        assert_eq!(enlarged.as_inner().len(), 8);
+        assert_eq!(enlarged.to_hex(), "480e");
+        assert_eq!(format!("{:#X}", enlarged), "0x480E");
+
+        assert_eq!(FlagVec::from_hex("480"), Err(Error::OddLengthString(3)));
+        assert_eq!(FlagVec::from_hex("48x0"), Err(Error::InvalidChar('x', 2)));
+    }
+
+    #[test]
+    #[cfg(feature = "hex")]
+    fn test_hex_all_masks() {
+        use crate::hex::{FromHex, ToHex};
+
+        for mask in 0..=0xFFFFu64 {
+            let vec = FlagVec::from_mask(mask);
+            for padding in 0..3 {
+                let mut padded = vec.clone();
+                padded.0.extend(vec![0; padding]);
+                let hex = padded.to_hex();
+                assert_eq!(hex, vec.to_hex());
+                assert_eq!(hex.len() % 2, 0);
+                let parsed = FlagVec::from_hex(&hex).unwrap();
+                assert_eq!(parsed, padded);
+                assert_eq!(parsed.as_inner(), vec.as_inner());
+                let mut hex = hex;
+                hex.push_str(&"00".repeat(padding));
+                assert_eq!(FlagVec::from_hex(&hex).unwrap().as_inner(), vec.as_inner());
+            }
+        }
+    }
 }
@@ -15,11 +15,25 @@
 // If not, see <https://opensource.org/licenses/MIT>.
 
 use ::std::io;
-use ::std::fmt::{Debug, Display, Formatter, self};
+use ::std::fmt::{Display, Formatter, self};
 
 use crate::Wrapper;
 
-/// Copyable & cloneable I/O error type represented by the error kind function.
+/// Cloneable & comparable I/O error type preserving the error kind, the
+/// original error message and the OS error code of [`io::Error`].
+///
+/// The error kind is kept as [`io::ErrorKind`] value, so any kind – including
+/// the ones added in the future versions of the standard library – is
+/// preserved without being mapped onto [`io::ErrorKind::Other`].
+///
+/// Equality, ordering and hashing take into account all of the error data;
+/// use [`IoError::same_kind`] to compare errors only by their kind. An error
+/// is also comparable with [`io::ErrorKind`] directly, in which case only the
+/// kind is compared.
+///
+/// The original [`io::Error`] (and the custom error it may wrap) can't be
+/// cloned, so only its message is kept and [`std::error::Error::source`]
+/// returns [`Option::None`].
 ///
 /// Available only when both `std` and `derive` features are present.
 ///
@@ -27,39 +41,231 @@ use crate::Wrapper;
 /// ```
 /// use amplify::{IoError, Error, Display, From};
 ///
-/// #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, From, Debug, Display, Error)]
+/// #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, From, Debug, Display, Error)]
 /// enum Error {
 ///     #[from(::std::io::Error)]
 ///     #[display(inner)]
 ///     Io(IoError),
 /// }
+///
+/// let err = Error::from(::std::io::Error::from_raw_os_error(2));
+/// let Error::Io(io) = err;
+/// assert_eq!(io.raw_os_error(), Some(2));
+/// assert_eq!(io.kind(), ::std::io::ErrorKind::NotFound);
 /// ```
-#[derive(Wrapper, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, From, Error)]
+#[derive(Wrapper, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Error)]
 #[amplify_crate(crate)]
-pub struct IoError(io::ErrorKind);
+pub struct IoError {
+    #[wrap]
+    kind: io::ErrorKind,
+    message: Option<String>,
+    raw_os_error: Option<i32>,
+}
+
+impl IoError {
+    /// Returns the kind of the error.
+    #[inline]
+    pub fn kind(&self) -> io::ErrorKind {
+        self.kind
+    }
+
+    /// Returns the message of a custom error, if the original [`io::Error`]
+    /// had one.
+    #[inline]
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// Returns the OS error code, if the original [`io::Error`] was
+    /// constructed from it.
+    #[inline]
+    pub fn raw_os_error(&self) -> Option<i32> {
+        self.raw_os_error
+    }
+
+    /// Detects whether both errors have the same kind, ignoring their messages
+    /// and OS error codes.
+    #[inline]
+    pub fn same_kind(&self, other: &IoError) -> bool {
+        self.kind == other.kind
+    }
+
+    /// Detects whether the error kind is one of the provided `kinds`. Useful
+    /// for the retry logic:
+    /// ```
+    /// # use std::io::ErrorKind;
+    /// # use amplify::IoError;
+    /// let err = IoError::from(ErrorKind::Interrupted);
+    /// assert!(err.matches_kind(&[ErrorKind::WouldBlock, ErrorKind::Interrupted]));
+    /// ```
+    #[inline]
+    pub fn matches_kind(&self, kinds: &[io::ErrorKind]) -> bool {
+        kinds.contains(&self.kind)
+    }
+}
 
 impl Display for IoError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let err = io::Error::from(*self.as_inner());
-        Display::fmt(&err, f)
+        match (self.raw_os_error, &self.message) {
+            (Some(code), _) => Display::fmt(&io::Error::from_raw_os_error(code), f),
+            (None, Some(msg)) => f.write_str(msg),
+            (None, None) => Display::fmt(&io::Error::from(self.kind), f),
+        }
     }
 }
 
-impl Debug for IoError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let err = io::Error::from(*self.as_inner());
-        Debug::fmt(&err, f)
+impl From<io::ErrorKind> for IoError {
+    #[inline]
+    fn from(kind: io::ErrorKind) -> Self {
+        IoError::from_inner(kind)
+    }
+}
+
+impl PartialEq<io::ErrorKind> for IoError {
+    #[inline]
+    fn eq(&self, kind: &io::ErrorKind) -> bool {
+        self.kind == *kind
+    }
+}
+
+impl PartialEq<IoError> for io::ErrorKind {
+    #[inline]
+    fn eq(&self, err: &IoError) -> bool {
+        *self == err.kind
     }
 }
 
 impl From<io::Error> for IoError {
     fn from(err: io::Error) -> Self {
-        IoError::from_inner(err.kind())
+        IoError {
+            kind: err.kind(),
+            message: err.get_ref().map(|inner| inner.to_string()),
+            raw_os_error: err.raw_os_error(),
+        }
     }
 }
 
 impl From<IoError> for io::Error {
     fn from(err: IoError) -> Self {
-        io::Error::from(err.into_inner())
+        match (err.raw_os_error, err.message) {
+            (Some(code), _) => io::Error::from_raw_os_error(code),
+            (None, Some(msg)) => io::Error::new(err.kind, msg),
+            (None, None) => io::Error::from(err.kind),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn os_error_roundtrip() {
+        let orig = io::Error::from_raw_os_error(2);
+        let err = IoError::from(io::Error::from_raw_os_error(2));
+        assert_eq!(err.kind(), orig.kind());
+        assert_eq!(err.raw_os_error(), Some(2));
+        assert_eq!(err.message(), None);
+        assert_eq!(err.to_string(), orig.to_string());
+        assert!(format!("{:?}", err).contains("raw_os_error: Some(2)"));
+
+        let back = io::Error::from(err.clone());
+        assert_eq!(back.raw_os_error(), Some(2));
+        assert_eq!(back.kind(), orig.kind());
+        assert_eq!(back.to_string(), orig.to_string());
+        assert_eq!(IoError::from(back), err);
+    }
+
+    #[test]
+    fn custom_error_roundtrip() {
+        let err = IoError::from(io::Error::new(io::ErrorKind::InvalidData, "bad magic"));
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.message(), Some("bad magic"));
+        assert_eq!(err.raw_os_error(), None);
+        assert_eq!(err.to_string(), "bad magic");
+
+        let back = io::Error::from(err.clone());
+        assert_eq!(back.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(back.to_string(), "bad magic");
+        assert_eq!(IoError::from(back), err);
+    }
+
+    #[test]
+    fn kind_roundtrip() {
+        let err = IoError::from(io::ErrorKind::UnexpectedEof);
+        assert_eq!(err.message(), None);
+        assert_eq!(err.raw_os_error(), None);
+        assert_eq!(
+            err.to_string(),
+            io::Error::from(io::ErrorKind::UnexpectedEof).to_string()
+        );
+        assert_eq!(io::Error::from(err).kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn equality() {
+        let a = IoError::from(io::Error::new(io::ErrorKind::InvalidInput, "a"));
+        let b = IoError::from(io::Error::new(io::ErrorKind::InvalidInput, "b"));
+        assert_ne!(a, b);
+        assert!(a.same_kind(&b));
+        assert!(!a.same_kind(&IoError::from(io::ErrorKind::NotFound)));
+    }
+
+    #[test]
+    fn kind_conversions() {
+        let kinds = [
+            io::ErrorKind::NotFound,
+            io::ErrorKind::PermissionDenied,
+            io::ErrorKind::ConnectionRefused,
+            io::ErrorKind::ConnectionReset,
+            io::ErrorKind::ConnectionAborted,
+            io::ErrorKind::NotConnected,
+            io::ErrorKind::AddrInUse,
+            io::ErrorKind::AddrNotAvailable,
+            io::ErrorKind::BrokenPipe,
+            io::ErrorKind::AlreadyExists,
+            io::ErrorKind::WouldBlock,
+            io::ErrorKind::InvalidInput,
+            io::ErrorKind::InvalidData,
+            io::ErrorKind::TimedOut,
+            io::ErrorKind::WriteZero,
+            io::ErrorKind::Interrupted,
+            io::ErrorKind::Unsupported,
+            io::ErrorKind::UnexpectedEof,
+            io::ErrorKind::OutOfMemory,
+            io::ErrorKind::Other,
+        ];
+        for kind in kinds {
+            let err = IoError::from(kind);
+            assert_eq!(err.kind(), kind);
+            assert_eq!(err, kind);
+            assert_eq!(kind, err);
+            assert_eq!(io::ErrorKind::from(err.clone()), kind);
+            assert_eq!(IoError::from(io::Error::from(kind)), err);
+            assert!(err.matches_kind(&[kind]));
+        }
+
+        let err = IoError::from(io::Error::new(io::ErrorKind::InvalidInput, "custom"));
+        assert_eq!(err, io::ErrorKind::InvalidInput);
+        assert_ne!(err, io::ErrorKind::NotFound);
+        assert_ne!(err, IoError::from(io::ErrorKind::InvalidInput));
+        assert_eq!(io::ErrorKind::from(err), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn matches_kind() {
+        let retry = [io::ErrorKind::WouldBlock, io::ErrorKind::Interrupted];
+        assert!(IoError::from(io::ErrorKind::WouldBlock).matches_kind(&retry));
+        assert!(IoError::from(io::ErrorKind::Interrupted).matches_kind(&retry));
+        assert!(!IoError::from(io::ErrorKind::NotFound).matches_kind(&retry));
+        assert!(!IoError::from(io::ErrorKind::Interrupted).matches_kind(&[]));
+    }
+
+    #[test]
+    fn error_source() {
+        use std::error::Error;
+
+        let err = IoError::from(io::Error::new(io::ErrorKind::InvalidData, "bad magic"));
+        assert!(err.source().is_none());
     }
 }
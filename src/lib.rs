@@ -17,7 +17,7 @@
 //! Amplifying Rust language capabilities: multiple generic trait
 //! implementations, type wrappers, derive macros.
 //!
-//! Minimum supported rust compiler version (MSRV): 1.46 (stable channel)
+//! Minimum supported rust compiler version (MSRV): 1.57 (stable channel)
 
 #![recursion_limit = "256"]
 #![deny(
@@ -39,12 +39,26 @@ extern crate core;
 #[macro_use]
 extern crate amplify_derive;
 #[cfg(feature = "derive")]
-pub use amplify_derive::{Wrapper, Display, AsAny, From, Getters, Error};
+pub use amplify_derive::{
+    Wrapper, Display, AsAny, EnumDefault, DumbDefault, From, FromStr, TryFrom, Getters, Setters,
+    Error, Hex, DebugHex,
+};
 
 #[cfg(feature = "serde")]
-#[macro_use]
 extern crate serde_crate as serde;
 
+/// Big-sized and bit-sized integer types. With `rand` feature they can be
+/// generated randomly:
+#[cfg_attr(feature = "rand", doc = "```")]
+#[cfg_attr(not(feature = "rand"), doc = "```ignore")]
+/// # extern crate rand_crate as rand;
+/// use amplify::num::{u24, u256};
+/// use rand::Rng;
+///
+/// let mut rng = rand::thread_rng();
+/// let _: u256 = rng.gen();
+/// assert!(rng.gen_range(u24::with(1)..u24::with(10)) < u24::with(10));
+/// ```
 pub extern crate amplify_num as num;
 #[cfg(any(test, feature = "hex"))]
 pub use num::hex;
@@ -57,32 +71,70 @@ pub extern crate amplify_syn as proc_attr;
 #[cfg(feature = "proc_attr")]
 pub use proc_attr::ident;
 
+/// Re-exports used by the code generated with the public macros
+#[doc(hidden)]
+pub mod _export {
+    #[cfg(feature = "alloc")]
+    pub use alloc::collections::{BTreeMap, BTreeSet, LinkedList, VecDeque};
+    #[cfg(feature = "alloc")]
+    pub use alloc::{string::String, vec::Vec};
+    #[cfg(all(feature = "std", not(feature = "alloc")))]
+    pub use std::collections::{BTreeMap, BTreeSet, LinkedList, VecDeque};
+    #[cfg(all(feature = "std", not(feature = "alloc")))]
+    pub use std::{string::String, vec::Vec};
+    #[cfg(feature = "rand")]
+    pub use rand_crate as rand;
+    #[cfg(feature = "serde")]
+    pub use serde_crate as serde;
+}
+
 #[macro_use]
 mod macros;
 #[macro_use]
 mod wrapper;
 
 mod as_any;
+#[macro_use]
 mod bipolar;
 mod dumb_default;
 #[cfg(all(feature = "std", feature = "derive"))]
 mod io_error;
 #[cfg(feature = "c_raw")]
 mod raw;
-mod slice32;
+mod raw_array;
+#[macro_use]
+mod slices;
+#[cfg(feature = "serde")]
+pub mod serde_helpers;
 pub mod strategy;
+#[cfg(feature = "std")]
+pub mod time;
 #[cfg(feature = "serde")]
 mod to_serde_string;
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[macro_use]
+pub mod ascii;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod confinement;
+#[cfg(any(feature = "std", feature = "alloc"))]
 pub mod flags;
 
 pub use crate::as_any::AsAny;
 pub use crate::bipolar::Bipolar;
+#[cfg(feature = "std")]
+pub use crate::bipolar::{split_tcp_stream, Channel};
 pub use crate::strategy::Holder;
-pub use crate::wrapper::Wrapper;
-pub use crate::slice32::Slice32;
+pub use crate::wrapper::{Wrapper, WrapperMut, WrapperTransparent};
+pub use crate::raw_array::{ByteArray, RawArray};
+pub use crate::slices::{Slice20, Slice32, Slice64};
 pub use crate::dumb_default::DumbDefault;
+#[cfg(feature = "std")]
+pub use crate::time::{DurationExt, Timestamp};
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use crate::ascii::{AsciiChar, AsciiStr, AsciiString};
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use crate::confinement::Confined;
 #[cfg(feature = "serde")]
 pub use crate::to_serde_string::{ToYamlString, ToJsonString, ToTomlString};
 #[cfg(all(feature = "std", feature = "derive"))]
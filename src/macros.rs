@@ -34,7 +34,7 @@
 #[macro_export]
 macro_rules! s {
     ( $str:literal ) => {
-        String::from($str)
+        $crate::_export::String::from($str)
     };
 }
 
@@ -77,12 +77,26 @@ macro_rules! default {
     };
 }
 
-/// Shorthand for `DumbDefault::dumb_default()`
+/// Shorthand for `DumbDefault::dumb_default()`; the type may be given
+/// explicitly when it can't be inferred:
+/// ```
+/// #[macro_use]
+/// extern crate amplify;
+///
+/// # fn main() {
+/// let vec: Vec<u8> = dumb!();
+/// assert!(vec.is_empty());
+/// assert_eq!(dumb!(amplify::Slice32), amplify::Slice32::default());
+/// # }
+/// ```
 #[macro_export]
 macro_rules! dumb {
     () => {
         $crate::DumbDefault::dumb_default()
     };
+    ($ty:ty) => {
+        <$ty as $crate::DumbDefault>::dumb_default()
+    };
 }
 
 /// Macro for creating [`std::collections::HashMap`] in the same manner as
@@ -92,10 +106,28 @@ macro_rules! dumb {
 /// extern crate amplify;
 ///
 /// # fn main() {
+/// let key = "other_key";
 /// let map = map! {
 ///     s!("key") => 5,
-///     s!("other_key") => 10
+///     key.to_owned() => 2 * 5,
 /// };
+/// assert_eq!(map["other_key"], 10);
+/// assert!(map.capacity() >= 2);
+///
+/// let empty: std::collections::HashMap<u8, u8> = map! {};
+/// assert!(empty.is_empty());
+/// # }
+/// ```
+///
+/// Both keys and values may be arbitrary expressions; the map is
+/// pre-allocated with the capacity for the number of entries. Each entry must
+/// be given as `key => value`:
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate amplify;
+///
+/// # fn main() {
+/// let map = map! { 1, 2 };
 /// # }
 /// ```
 #[macro_export]
@@ -106,9 +138,11 @@ macro_rules! map {
         }
     };
 
-    { $($key:expr => $value:expr),+ } => {
+    { $($key:expr => $value:expr),+ $(,)? } => {
         {
-            let mut m = ::std::collections::HashMap::new();
+            let mut m = ::std::collections::HashMap::with_capacity(
+                <[&str]>::len(&[$(stringify!($key)),+])
+            );
             $(
                 m.insert($key, $value);
             )+
@@ -124,10 +158,17 @@ macro_rules! map {
 /// extern crate amplify;
 ///
 /// # fn main() {
-/// let map = set![5, 6, 7];
+/// let set = set![5, 6, 3 + 4,];
+/// assert!(set.contains(&7));
+/// assert!(set.capacity() >= 3);
+///
+/// let empty: std::collections::HashSet<u8> = set![];
+/// assert!(empty.is_empty());
 /// # }
 /// ```
 ///
+/// The set is pre-allocated with the capacity for the number of values.
+///
 /// NB: you can't use repeated values with [`std::collections::HashSet`], unlike
 /// to [`Vec`]'s:
 /// ```
@@ -146,9 +187,11 @@ macro_rules! set {
         }
     };
 
-    { $($value:expr),+ } => {
+    { $($value:expr),+ $(,)? } => {
         {
-            let mut m = ::std::collections::HashSet::new();
+            let mut m = ::std::collections::HashSet::with_capacity(
+                <[&str]>::len(&[$(stringify!($value)),+])
+            );
             $(
                 m.insert($value);
             )+
@@ -166,21 +209,35 @@ macro_rules! set {
 /// # fn main() {
 /// let map = bmap! {
 ///     s!("key") => 5,
-///     s!("other_key") => 10
+///     format!("{}_key", "other") => 2 * 5,
 /// };
+/// assert_eq!(map.values().collect::<Vec<_>>(), vec![&5, &10]);
+///
+/// let empty: std::collections::BTreeMap<u8, u8> = bmap! {};
+/// assert!(empty.is_empty());
+/// # }
+/// ```
+///
+/// Each entry must be given as `key => value`:
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate amplify;
+///
+/// # fn main() {
+/// let map = bmap! { 1 => 2, 3 };
 /// # }
 /// ```
 #[macro_export]
 macro_rules! bmap {
     { } =>  {
         {
-            ::std::collections::BTreeMap::new()
+            $crate::_export::BTreeMap::new()
         }
     };
 
-    { $($key:expr => $value:expr),+ } => {
+    { $($key:expr => $value:expr),+ $(,)? } => {
         {
-            let mut m = ::std::collections::BTreeMap::new();
+            let mut m = $crate::_export::BTreeMap::new();
             $(
                 m.insert($key, $value);
             )+
@@ -196,12 +253,16 @@ macro_rules! bmap {
 /// extern crate amplify;
 ///
 /// # fn main() {
-/// let map = bset![5, 6, 7];
+/// let set = bset![7, 6, 2 + 3,];
+/// assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![5, 6, 7]);
+///
+/// let empty: std::collections::BTreeSet<u8> = bset![];
+/// assert!(empty.is_empty());
 /// # }
 /// ```
 ///
-/// NB: you can't use repeated values with [`std::collections::HashSet`], unlike
-/// to [`Vec`]'s:
+/// NB: you can't use repeated values with [`std::collections::BTreeSet`],
+/// unlike to [`Vec`]'s:
 /// ```
 /// #[macro_use]
 /// extern crate amplify;
@@ -214,13 +275,13 @@ macro_rules! bmap {
 macro_rules! bset {
     { } =>  {
         {
-            ::std::collections::BTreeSet::new()
+            $crate::_export::BTreeSet::new()
         }
     };
 
-    { $($value:expr),+ } => {
+    { $($value:expr),+ $(,)? } => {
         {
-            let mut m = ::std::collections::BTreeSet::new();
+            let mut m = $crate::_export::BTreeSet::new();
             $(
                 m.insert($value);
             )+
@@ -236,24 +297,78 @@ macro_rules! bset {
 /// extern crate amplify;
 ///
 /// # fn main() {
-/// let list = list! {
-///     s!("item one") =>
-///     s!("item two") =>
-///     s!("item three")
-/// };
+/// let list = list![
+///     s!("item one"),
+///     s!("item two"),
+///     format!("item {}", "three"),
+/// ];
+/// assert_eq!(list.back().unwrap(), "item three");
+///
+/// let empty: std::collections::LinkedList<u8> = list![];
+/// assert!(empty.is_empty());
+/// # }
+/// ```
+///
+/// For backward compatibility, items may be also separated with `=>`:
+/// ```
+/// #[macro_use]
+/// extern crate amplify;
+///
+/// # fn main() {
+/// assert_eq!(list! { 1 => 2 => 3 }, list![1, 2, 3]);
 /// # }
 /// ```
 #[macro_export]
 macro_rules! list {
     { } =>  {
         {
-            ::std::collections::LinkedList::new()
+            $crate::_export::LinkedList::new()
+        }
+    };
+
+    { $($value:expr),+ $(,)? } => {
+        {
+            let mut m = $crate::_export::LinkedList::new();
+            $(
+                m.push_back($value);
+            )+
+            m
         }
     };
 
     { $($value:expr)=>+ } => {
+        $crate::list![$($value),+]
+    }
+}
+
+/// Macro for creating [`std::collections::VecDeque`] in the same manner as
+/// `vec!` is used for [`Vec`]:
+/// ```
+/// #[macro_use]
+/// extern crate amplify;
+///
+/// # fn main() {
+/// let mut deque = deque![1, 2, 1 + 2,];
+/// assert_eq!(deque.pop_front(), Some(1));
+/// assert_eq!(deque.pop_back(), Some(3));
+///
+/// let empty: std::collections::VecDeque<u8> = deque![];
+/// assert!(empty.is_empty());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! deque {
+    { } =>  {
         {
-            let mut m = ::std::collections::LinkedList::new();
+            $crate::_export::VecDeque::new()
+        }
+    };
+
+    { $($value:expr),+ $(,)? } => {
+        {
+            let mut m = $crate::_export::VecDeque::with_capacity(
+                <[&str]>::len(&[$(stringify!($value)),+])
+            );
             $(
                 m.push_back($value);
             )+
@@ -261,3 +376,36 @@ macro_rules! list {
         }
     }
 }
+
+/// Macro for creating [`crate::flags::FlagVec`] from the list of the set
+/// flag numbers:
+/// ```
+/// #[macro_use]
+/// extern crate amplify;
+///
+/// # fn main() {
+/// let flags = flags![0, 5, 17];
+/// assert_eq!(flags.to_indices(), vec![0, 5, 17]);
+/// assert_eq!(flags, amplify::flags::FlagVec::from_mask(1 | 1 << 5 | 1 << 17));
+///
+/// assert!(flags![].is_empty());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! flags {
+    { } =>  {
+        {
+            $crate::flags::FlagVec::new()
+        }
+    };
+
+    { $($flag:expr),+ $(,)? } => {
+        {
+            let mut m = $crate::flags::FlagVec::new();
+            $(
+                m.set($flag);
+            )+
+            m
+        }
+    }
+}
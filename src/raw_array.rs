@@ -0,0 +1,201 @@
+// Rust language amplification library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use crate::num::{i1024, i256, i512, u1024, u256, u512};
+
+/// Fixed-size byte arrays `[u8; LEN]` which may back [`RawArray`] types.
+///
+/// Implemented for arrays of length from 0 to 32, 64 and 128.
+pub trait ByteArray: Copy + AsRef<[u8]> + AsMut<[u8]> {
+    /// Array length
+    const LEN: usize;
+
+    /// Constructs array filled with zeros
+    fn zeroed() -> Self;
+}
+
+/// Types which are convertible to and from a fixed-size byte array
+/// `[u8; LEN]`.
+///
+/// The trait allows generic code to use a single bound for all "newtype over
+/// a byte array" types, like [`Slice32`](crate::Slice32), hashes and big
+/// integers.
+///
+/// # Example
+///
+/// ```
+/// use amplify::{RawArray, Slice32};
+///
+/// fn first_byte<T: RawArray>(val: &T) -> Option<u8> {
+///     val.to_raw_array().as_ref().first().copied()
+/// }
+///
+/// let slice = Slice32::from_raw_array([0xAB; 32]);
+/// assert_eq!(first_byte(&slice), Some(0xAB));
+/// assert_eq!(Slice32::LEN, 32);
+/// assert_eq!(Slice32::from_raw_slice(&[0xAB; 32][..]), Some(slice));
+/// assert_eq!(Slice32::from_raw_slice(&[0xAB; 31][..]), None);
+/// ```
+pub trait RawArray: Sized {
+    /// Byte array type `[u8; LEN]` backing the type
+    type Array: ByteArray;
+
+    /// Length of the backing byte array
+    const LEN: usize = <Self::Array as ByteArray>::LEN;
+
+    /// Constructs value from the byte array
+    fn from_raw_array(val: Self::Array) -> Self;
+
+    /// Returns copy of the backing byte array
+    fn to_raw_array(&self) -> Self::Array;
+
+    /// Constructs value from a byte slice, returning `None` if the slice
+    /// length does not match [`RawArray::LEN`]
+    fn from_raw_slice(slice: &[u8]) -> Option<Self> {
+        if slice.len() != Self::LEN {
+            return None;
+        }
+        let mut array = Self::Array::zeroed();
+        array.as_mut().copy_from_slice(slice);
+        Some(Self::from_raw_array(array))
+    }
+}
+
+macro_rules! impl_byte_array {
+    ($($len:expr),+ $(,)?) => {
+        $(
+            impl ByteArray for [u8; $len] {
+                const LEN: usize = $len;
+
+                #[inline]
+                fn zeroed() -> Self {
+                    [0u8; $len]
+                }
+            }
+
+            impl RawArray for [u8; $len] {
+                type Array = Self;
+
+                #[inline]
+                fn from_raw_array(val: Self::Array) -> Self {
+                    val
+                }
+
+                #[inline]
+                fn to_raw_array(&self) -> Self::Array {
+                    *self
+                }
+            }
+        )+
+    };
+}
+
+impl_byte_array!(
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+    26, 27, 28, 29, 30, 31, 32, 64, 128,
+);
+
+macro_rules! impl_bigint_raw_array {
+    ($ty:ty, $len:expr) => {
+        /// Uses little-endian byte order
+        impl RawArray for $ty {
+            type Array = [u8; $len];
+
+            #[inline]
+            fn from_raw_array(val: Self::Array) -> Self {
+                <$ty>::from_le_bytes(val)
+            }
+
+            #[inline]
+            fn to_raw_array(&self) -> Self::Array {
+                self.to_le_bytes()
+            }
+        }
+    };
+    ($ty:ty, $uty:ty, $len:expr) => {
+        /// Uses little-endian byte order of the two's complement representation
+        impl RawArray for $ty {
+            type Array = [u8; $len];
+
+            #[inline]
+            fn from_raw_array(val: Self::Array) -> Self {
+                <$ty>::from_bits(<$uty>::from_le_bytes(val))
+            }
+
+            #[inline]
+            fn to_raw_array(&self) -> Self::Array {
+                self.to_bits().to_le_bytes()
+            }
+        }
+    };
+}
+
+impl_bigint_raw_array!(u256, 32);
+impl_bigint_raw_array!(u512, 64);
+impl_bigint_raw_array!(u1024, 128);
+impl_bigint_raw_array!(i256, u256, 32);
+impl_bigint_raw_array!(i512, u512, 64);
+impl_bigint_raw_array!(i1024, u1024, 128);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Slice20, Slice32, Slice64};
+
+    wrapper! {
+        #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+        struct Id([u8; 20]);
+        impl RawArray;
+    }
+
+    fn roundtrip<T: RawArray + PartialEq + core::fmt::Debug>(val: T) {
+        let array = val.to_raw_array();
+        assert_eq!(array.as_ref().len(), T::LEN);
+        assert_eq!(T::from_raw_array(array), val);
+        assert_eq!(T::from_raw_slice(array.as_ref()), Some(val));
+    }
+
+    #[test]
+    fn raw_array_roundtrip() {
+        roundtrip([0xA5u8; 0]);
+        roundtrip([0xA5u8; 17]);
+        roundtrip([0xA5u8; 128]);
+        roundtrip(Slice20::from([0xA5; 20]));
+        roundtrip(Slice32::from([0xA5; 32]));
+        roundtrip(Slice64::from([0xA5; 64]));
+        roundtrip(Id([0xA5; 20]));
+        roundtrip(u256::from(0xDEAD_BEEFu64));
+        roundtrip(u512::from(0xDEAD_BEEFu64));
+        roundtrip(u1024::from(0xDEAD_BEEFu64));
+        roundtrip(i256::from(-0xDEAD_BEEFi64));
+        roundtrip(i512::from(-0xDEAD_BEEFi64));
+        roundtrip(i1024::from(-0xDEAD_BEEFi64));
+    }
+
+    #[test]
+    fn raw_array_byte_order() {
+        let mut bytes = [0u8; 32];
+        bytes[0] = 1;
+        assert_eq!(u256::from_raw_array(bytes), u256::from(1u64));
+        assert_eq!(u256::from(1u64).to_raw_array(), bytes);
+    }
+
+    #[test]
+    fn raw_slice_length() {
+        assert_eq!(Slice32::from_raw_slice(&[0u8; 33][..]), None);
+        assert_eq!(u256::from_raw_slice(&[0u8; 31][..]), None);
+        assert_eq!(<[u8; 4]>::from_raw_slice(&[1, 2, 3, 4]), Some([1, 2, 3, 4]));
+    }
+}
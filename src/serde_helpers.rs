@@ -0,0 +1,517 @@
+// Rust language amplification library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Helper modules for serializing fields with `#[serde(with = "...")]`
+//! attribute:
+//! - [`as_inner`] serializes [`Wrapper`] types as their inner value, instead
+//!   of a single-field structure;
+//! - [`as_hex`] serializes byte containers as hex strings in human-readable
+//!   formats and as raw bytes in binary formats (requires `hex` feature);
+//! - [`length_prefixed`] serializes byte containers as byte strings in all
+//!   formats, which binary formats (like bincode) prefix with the length;
+//!   this is an alternative to the fixed-size serialization of byte arrays
+//!   like [`Slice32`](crate::Slice32) (requires `hex` feature);
+//! - [`as_display_fromstr`] serializes types as strings, using their
+//!   [`Display`] and [`FromStr`] implementations.
+//!
+//! Each of the modules has a companion `opt_*` module for the optional
+//! fields.
+//!
+//! ```
+//! use amplify::{serde_helpers, From, Slice32, Wrapper};
+//! use serde_crate::{Deserialize, Serialize};
+//!
+//! #[derive(Wrapper, Copy, Clone, PartialEq, Eq, Debug, From)]
+//! pub struct Height(u32);
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+//! #[serde(crate = "serde_crate")]
+//! struct Block {
+//!     #[serde(with = "serde_helpers::as_inner")]
+//!     height: Height,
+//!     #[serde(with = "serde_helpers::opt_as_inner")]
+//!     parent: Option<Height>,
+//!     #[serde(with = "serde_helpers::as_display_fromstr")]
+//!     id: Slice32,
+//! }
+//!
+//! let block = Block {
+//!     height: Height::from(1),
+//!     parent: None,
+//!     id: Slice32::from([0xFF; 32]),
+//! };
+//! let json = serde_json::to_string(&block).unwrap();
+//! assert_eq!(json, format!(r#"{{"height":1,"parent":null,"id":"{}"}}"#, "ff".repeat(32)));
+//! assert_eq!(serde_json::from_str::<Block>(&json).unwrap(), block);
+//! ```
+//!
+//! [`Wrapper`]: crate::Wrapper
+//! [`Display`]: core::fmt::Display
+//! [`FromStr`]: core::str::FromStr
+
+/// Implements `opt_*` module serializing optional values with the given
+/// helper module
+macro_rules! opt_helper {
+    (
+        $(#[$attr:meta])* $opt:ident, $helper:ident,
+        ser: { $($ser:tt)+ },
+        de: { $($de:tt)+ } $(,)?
+    ) => {
+        $(#[$attr])*
+        pub mod $opt {
+            #[allow(unused_imports)]
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            struct Ser<'a, T>(&'a T);
+
+            impl<'a, T> Serialize for Ser<'a, T>
+            where
+                $($ser)+
+            {
+                #[inline]
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    super::$helper::serialize(self.0, serializer)
+                }
+            }
+
+            struct De<T>(T);
+
+            impl<'de, T> Deserialize<'de> for De<T>
+            where
+                $($de)+
+            {
+                #[inline]
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    super::$helper::deserialize(deserializer).map(De)
+                }
+            }
+
+            /// Serializes optional value
+            pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+                $($ser)+
+            {
+                match value {
+                    Some(value) => serializer.serialize_some(&Ser(value)),
+                    None => serializer.serialize_none(),
+                }
+            }
+
+            /// Deserializes optional value
+            pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+            where
+                D: Deserializer<'de>,
+                $($de)+
+            {
+                Option::<De<T>>::deserialize(deserializer).map(|value| value.map(|de| de.0))
+            }
+        }
+    };
+}
+
+/// Serializes [`Wrapper`](crate::Wrapper) types as their inner value
+pub mod as_inner {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::Wrapper;
+
+    /// Serializes wrapper as its inner value
+    #[inline]
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Wrapper,
+        T::Inner: Serialize,
+        S: Serializer,
+    {
+        value.as_inner().serialize(serializer)
+    }
+
+    /// Deserializes wrapper from its inner value
+    #[inline]
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Wrapper,
+        T::Inner: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        T::Inner::deserialize(deserializer).map(T::from_inner)
+    }
+}
+
+opt_helper!(
+    /// Serializes optional [`Wrapper`](crate::Wrapper) types as their inner
+    /// value
+    opt_as_inner, as_inner,
+    ser: { T: crate::Wrapper, T::Inner: Serialize },
+    de: { T: crate::Wrapper, T::Inner: Deserialize<'de> },
+);
+
+/// Serializes byte containers as hex strings in human-readable formats and as
+/// raw bytes in binary formats
+#[cfg(feature = "hex")]
+pub mod as_hex {
+    use core::fmt::{self, Formatter};
+    use core::marker::PhantomData;
+
+    use serde::{de, Deserializer, Serializer};
+
+    use crate::hex::{self, FromHex, ToHex};
+
+    /// Serializes byte container as a hex string or raw bytes
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&value.as_ref().to_hex())
+        } else {
+            serializer.serialize_bytes(value.as_ref())
+        }
+    }
+
+    /// Deserializes byte container from a hex string or raw bytes
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromHex,
+        D: Deserializer<'de>,
+    {
+        struct Visitor<T>(PhantomData<T>);
+
+        impl<'de, T> de::Visitor<'de> for Visitor<T>
+        where
+            T: FromHex,
+        {
+            type Value = T;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.write_str("bytes or a hex string")
+            }
+
+            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                T::from_hex(s).map_err(E::custom)
+            }
+
+            fn visit_bytes<E>(self, bytes: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                T::from_byte_iter(bytes.iter().map(|byte| Ok::<_, hex::Error>(*byte)))
+                    .map_err(E::custom)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(Visitor(PhantomData))
+        } else {
+            deserializer.deserialize_bytes(Visitor(PhantomData))
+        }
+    }
+}
+
+#[cfg(feature = "hex")]
+opt_helper!(
+    /// Serializes optional byte containers as hex strings in human-readable
+    /// formats and as raw bytes in binary formats
+    opt_as_hex, as_hex,
+    ser: { T: AsRef<[u8]> },
+    de: { T: crate::hex::FromHex },
+);
+
+/// Serializes byte containers as byte strings, which binary formats (like
+/// bincode) prefix with the data length, while human-readable formats (like
+/// JSON) represent as arrays of numbers. Deserialization accepts both byte
+/// strings and sequences of bytes.
+#[cfg(feature = "hex")]
+pub mod length_prefixed {
+    use core::fmt::{self, Formatter};
+    use core::marker::PhantomData;
+
+    use serde::{de, Deserializer, Serializer};
+
+    use crate::hex::{self, FromHex};
+
+    /// Serializes byte container as a byte string
+    #[inline]
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        serializer.serialize_bytes(value.as_ref())
+    }
+
+    /// Deserializes byte container from a byte string or a sequence of bytes
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromHex,
+        D: Deserializer<'de>,
+    {
+        struct Visitor<T>(PhantomData<T>);
+
+        impl<'de, T> de::Visitor<'de> for Visitor<T>
+        where
+            T: FromHex,
+        {
+            type Value = T;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.write_str("bytes")
+            }
+
+            fn visit_bytes<E>(self, bytes: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                T::from_byte_iter(bytes.iter().map(|byte| Ok::<_, hex::Error>(*byte)))
+                    .map_err(E::custom)
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(byte) = seq.next_element::<u8>()? {
+                    bytes.push(byte);
+                }
+                self.visit_bytes(&bytes)
+            }
+        }
+
+        deserializer.deserialize_bytes(Visitor(PhantomData))
+    }
+}
+
+#[cfg(feature = "hex")]
+opt_helper!(
+    /// Serializes optional byte containers as byte strings prefixed with the
+    /// data length in binary formats
+    opt_length_prefixed, length_prefixed,
+    ser: { T: AsRef<[u8]> },
+    de: { T: crate::hex::FromHex },
+);
+
+/// Serializes types as strings using their [`Display`](core::fmt::Display)
+/// and [`FromStr`](core::str::FromStr) implementations
+pub mod as_display_fromstr {
+    use core::fmt::Display;
+    use core::str::FromStr;
+
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    /// Serializes value as a string
+    #[inline]
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Display,
+        S: Serializer,
+    {
+        serializer.collect_str(value)
+    }
+
+    /// Deserializes value from a string
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+opt_helper!(
+    /// Serializes optional values as strings using their
+    /// [`Display`](core::fmt::Display) and [`FromStr`](core::str::FromStr)
+    /// implementations
+    opt_as_display_fromstr, as_display_fromstr,
+    ser: { T: core::fmt::Display },
+    de: { T: core::str::FromStr, T::Err: core::fmt::Display },
+);
+
+#[cfg(all(test, feature = "derive", feature = "hex"))]
+mod test {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::hex::{self, FromHex};
+    use crate::Slice32;
+
+    #[derive(Wrapper, Copy, Clone, PartialEq, Eq, Debug, Display)]
+    #[display(inner)]
+    #[wrapper(FromStr)]
+    #[amplify_crate(crate)]
+    struct Hash(Slice32);
+
+    impl AsRef<[u8]> for Hash {
+        fn as_ref(&self) -> &[u8] {
+            &self.0[..]
+        }
+    }
+
+    impl FromHex for Hash {
+        fn from_byte_iter<I>(iter: I) -> Result<Self, hex::Error>
+        where
+            I: Iterator<Item = Result<u8, hex::Error>> + ExactSizeIterator + DoubleEndedIterator,
+        {
+            Slice32::from_byte_iter(iter).map(Hash)
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+    #[serde(crate = "serde_crate")]
+    struct Record {
+        #[serde(with = "as_inner")]
+        inner: Hash,
+        #[serde(with = "opt_as_inner")]
+        opt_inner: Option<Hash>,
+        #[serde(with = "as_hex")]
+        hex: Hash,
+        #[serde(with = "opt_as_hex")]
+        opt_hex: Option<Hash>,
+        #[serde(with = "as_display_fromstr")]
+        string: Hash,
+        #[serde(with = "opt_as_display_fromstr")]
+        opt_string: Option<Hash>,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+    #[serde(crate = "serde_crate")]
+    struct Packet {
+        fixed: Slice32,
+        #[serde(with = "length_prefixed")]
+        prefixed: Slice32,
+        #[serde(with = "opt_length_prefixed")]
+        opt_prefixed: Option<Hash>,
+    }
+
+    fn record(opt: bool) -> Record {
+        let hash = Hash(Slice32::from([0xAB; 32]));
+        let some = if opt { Some(hash) } else { None };
+        Record {
+            inner: hash,
+            opt_inner: some,
+            hex: hash,
+            opt_hex: some,
+            string: hash,
+            opt_string: some,
+        }
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let hex = "ab".repeat(32);
+        for opt in [true, false].iter().copied() {
+            let record = record(opt);
+            let json = serde_json::to_string(&record).unwrap();
+            let field = if opt {
+                format!("\"{}\"", hex)
+            } else {
+                s!("null")
+            };
+            assert_eq!(
+                json,
+                format!(
+                    "{{\"inner\":\"{0}\",\"opt_inner\":{1},\"hex\":\"{0}\",\"opt_hex\":{1},\
+                     \"string\":\"{0}\",\"opt_string\":{1}}}",
+                    hex, field
+                )
+            );
+            assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record);
+        }
+    }
+
+    #[test]
+    fn bincode_roundtrip() {
+        for opt in [true, false].iter().copied() {
+            let record = record(opt);
+            let data = bincode::serialize(&record).unwrap();
+            assert_eq!(bincode::deserialize::<Record>(&data).unwrap(), record);
+        }
+    }
+
+    #[test]
+    fn length_prefixed_bincode() {
+        use bincode::Options;
+
+        let options = bincode::DefaultOptions::new().with_fixint_encoding();
+        let slice = Slice32::from([0x5A; 32]);
+        let hash = Hash(Slice32::from([0xAB; 32]));
+        for opt in [Some(hash), None].iter().copied() {
+            let packet = Packet {
+                fixed: slice,
+                prefixed: slice,
+                opt_prefixed: opt,
+            };
+            let data = options.serialize(&packet).unwrap();
+            let opt_len = if opt.is_some() { 1 + 8 + 32 } else { 1 };
+            assert_eq!(data.len(), 32 + 8 + 32 + opt_len);
+            assert_eq!(&data[..32], &slice[..]);
+            assert_eq!(&data[32..40], &32u64.to_le_bytes());
+            assert_eq!(&data[40..72], &slice[..]);
+            assert_eq!(options.deserialize::<Packet>(&data).unwrap(), packet);
+        }
+
+        let data = options.serialize(&[0u8; 31][..]).unwrap();
+        let mut de = bincode::Deserializer::from_slice(&data, options);
+        assert!(length_prefixed::deserialize::<Slice32, _>(&mut de).is_err());
+    }
+
+    #[test]
+    fn length_prefixed_json() {
+        let slice = Slice32::from([0x5A; 32]);
+        let packet = Packet {
+            fixed: slice,
+            prefixed: slice,
+            opt_prefixed: Some(Hash(slice)),
+        };
+        let json = serde_json::to_string(&packet).unwrap();
+        let array = format!("[{}]", vec!["90"; 32].join(","));
+        assert_eq!(
+            json,
+            format!(
+                "{{\"fixed\":\"{}\",\"prefixed\":{1},\"opt_prefixed\":{1}}}",
+                "5a".repeat(32),
+                array
+            )
+        );
+        assert_eq!(serde_json::from_str::<Packet>(&json).unwrap(), packet);
+
+        let mut de = serde_json::Deserializer::from_str("[1,2,3]");
+        assert!(length_prefixed::deserialize::<Slice32, _>(&mut de).is_err());
+    }
+
+    #[test]
+    fn invalid_hex() {
+        let json = "\"abcd\"";
+        let mut de = serde_json::Deserializer::from_str(json);
+        assert!(as_hex::deserialize::<Hash, _>(&mut de).is_err());
+        let mut de = serde_json::Deserializer::from_str("\"xyz\"");
+        assert!(as_display_fromstr::deserialize::<Hash, _>(&mut de).is_err());
+    }
+}
@@ -0,0 +1,989 @@
+// Rust language amplification library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2020-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Fixed-size byte array wrappers
+
+/// Declares a new type wrapping fixed-size byte array and implementing the
+/// same set of traits and methods as [`Slice32`](crate::Slice32), including
+/// constructors, indexing, hex formatting and parsing and (with `serde`
+/// feature) serialization.
+///
+/// The type also implements [`RawArray`](crate::RawArray), so the array length
+/// must be one of the lengths supported by [`ByteArray`](crate::ByteArray).
+///
+/// Types keep data in the byte order they were constructed with and display
+/// them in the same order.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate amplify;
+/// use amplify::Wrapper;
+///
+/// slice_type!(
+///     /// 128-bit byte array
+///     pub struct Slice16([u8; 16])
+/// );
+///
+/// # fn main() {
+/// let slice16 = Slice16::from_array([0xA5; 16]);
+/// assert_eq!(Slice16::from_slice([0xA5; 16]), Some(slice16));
+/// assert_eq!(Slice16::from_slice([0xA5; 15]), None);
+/// assert_eq!(slice16.as_inner(), &[0xA5; 16]);
+/// assert_eq!(&slice16[..2], &[0xA5, 0xA5]);
+/// # #[cfg(feature = "hex")]
+/// assert_eq!(slice16.to_string(), "a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! slice_type {
+    ($(#[$attr:meta])* $vis:vis struct $name:ident([u8; $len:literal])) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy)]
+        $vis struct $name([u8; $len]);
+
+        $crate::__slice_core!($name, $len);
+        $crate::__slice_bitops!($name, $len);
+        $crate::__slice_alloc!($name, $len);
+        $crate::__slice_rand!($name, $len);
+        $crate::__slice_hex!($name, $len);
+        $crate::__slice_serde!($name, $len);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __slice_core {
+    ($name:ident, $len:literal) => {
+        impl $name {
+            /// Constructs byte array wrapper from the provided byte array
+            #[inline]
+            pub const fn from_array(array: [u8; $len]) -> Self {
+                $name(array)
+            }
+
+            /// Returns copy of the wrapped byte array
+            #[inline]
+            pub const fn to_array(self) -> [u8; $len] {
+                self.0
+            }
+
+            /// Constructs byte array wrapper from a provided slice. If the
+            /// slice length does not match the size of the type, returns
+            /// `None`
+            pub fn from_slice(slice: impl AsRef<[u8]>) -> Option<$name> {
+                if slice.as_ref().len() != $len {
+                    return None;
+                }
+                let mut inner = [0u8; $len];
+                inner.copy_from_slice(slice.as_ref());
+                Some(Self(inner))
+            }
+
+            /// Constructs byte array wrapper by copying data from a provided
+            /// slice.
+            ///
+            /// # Panics
+            ///
+            /// If the slice length does not match the size of the type
+            pub fn copy_from_slice(slice: impl AsRef<[u8]>) -> $name {
+                let slice = slice.as_ref();
+                Self::from_slice(slice).unwrap_or_else(|| {
+                    panic!(
+                        "{} requires slice of {} bytes, while {} bytes were provided",
+                        stringify!($name),
+                        $len,
+                        slice.len()
+                    )
+                })
+            }
+        }
+
+        impl ::core::default::Default for $name {
+            #[inline]
+            fn default() -> Self {
+                $name([0u8; $len])
+            }
+        }
+
+        /// Dumb placeholder filled with zero bytes
+        impl $crate::DumbDefault for $name {
+            #[inline]
+            fn dumb_default() -> Self {
+                $name([0u8; $len])
+            }
+        }
+
+        impl ::core::cmp::PartialEq for $name {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.0[..] == other.0[..]
+            }
+        }
+
+        impl ::core::cmp::Eq for $name {}
+
+        impl ::core::cmp::PartialOrd for $name {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
+                Some(::core::cmp::Ord::cmp(self, other))
+            }
+        }
+
+        impl ::core::cmp::Ord for $name {
+            #[inline]
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                ::core::cmp::Ord::cmp(&self.0[..], &other.0[..])
+            }
+        }
+
+        impl ::core::hash::Hash for $name {
+            #[inline]
+            fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                ::core::hash::Hash::hash(&self.0[..], state)
+            }
+        }
+
+        impl ::core::ops::Index<usize> for $name {
+            type Output = u8;
+            #[inline]
+            fn index(&self, index: usize) -> &Self::Output {
+                &self.0[index]
+            }
+        }
+
+        impl ::core::ops::Index<u8> for $name {
+            type Output = u8;
+            #[inline]
+            fn index(&self, index: u8) -> &Self::Output {
+                &self.0[index as usize]
+            }
+        }
+
+        $crate::__slice_index!(
+            $name;
+            ::core::ops::Range<usize>,
+            ::core::ops::RangeFrom<usize>,
+            ::core::ops::RangeTo<usize>,
+            ::core::ops::RangeInclusive<usize>,
+            ::core::ops::RangeToInclusive<usize>,
+            ::core::ops::RangeFull
+        );
+
+        impl ::core::ops::IndexMut<usize> for $name {
+            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+                &mut self.0[index]
+            }
+        }
+
+        impl ::core::ops::IndexMut<u8> for $name {
+            fn index_mut(&mut self, index: u8) -> &mut Self::Output {
+                &mut self.0[index as usize]
+            }
+        }
+
+        impl ::core::convert::AsRef<[u8]> for $name {
+            #[inline]
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl ::core::convert::AsMut<[u8]> for $name {
+            #[inline]
+            fn as_mut(&mut self) -> &mut [u8] {
+                &mut self.0
+            }
+        }
+
+        impl ::core::borrow::Borrow<[u8]> for $name {
+            #[inline]
+            fn borrow(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl ::core::borrow::BorrowMut<[u8]> for $name {
+            #[inline]
+            fn borrow_mut(&mut self) -> &mut [u8] {
+                &mut self.0
+            }
+        }
+
+        impl<'a> ::core::iter::IntoIterator for &'a $name {
+            type Item = &'a u8;
+            type IntoIter = ::core::slice::Iter<'a, u8>;
+
+            #[inline]
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.iter()
+            }
+        }
+
+        impl ::core::convert::From<&[u8; $len]> for $name {
+            #[inline]
+            fn from(inner: &[u8; $len]) -> Self {
+                Self(*inner)
+            }
+        }
+
+        impl ::core::convert::From<[u8; $len]> for $name {
+            #[inline]
+            fn from(inner: [u8; $len]) -> Self {
+                Self(inner)
+            }
+        }
+
+        impl $crate::Wrapper for $name {
+            type Inner = [u8; $len];
+
+            #[inline]
+            fn from_inner(inner: Self::Inner) -> Self {
+                Self(inner)
+            }
+
+            #[inline]
+            fn as_inner(&self) -> &Self::Inner {
+                &self.0
+            }
+
+            #[inline]
+            fn into_inner(self) -> Self::Inner {
+                self.0
+            }
+        }
+
+        impl $crate::WrapperMut for $name {
+            #[inline]
+            fn as_inner_mut(&mut self) -> &mut Self::Inner {
+                &mut self.0
+            }
+        }
+
+        impl $crate::RawArray for $name {
+            type Array = [u8; $len];
+
+            #[inline]
+            fn from_raw_array(val: Self::Array) -> Self {
+                Self(val)
+            }
+
+            #[inline]
+            fn to_raw_array(&self) -> Self::Array {
+                self.0
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __slice_bitops {
+    ($name:ident, $len:literal) => {
+        impl $name {
+            /// Compares two values in a best-effort constant time: all bytes
+            /// are always compared, without short-circuiting on the first
+            /// difference, and the result is read through a volatile read to
+            /// prevent compiler from optimizing the comparison. Use it for
+            /// comparing secret data instead of `==` operator.
+            #[allow(unsafe_code)]
+            pub fn ct_eq(&self, other: &Self) -> bool {
+                let mut diff = 0u8;
+                for (a, b) in self.0.iter().zip(other.0.iter()) {
+                    diff |= a ^ b;
+                }
+                // Safety: reading a local variable through a valid reference
+                unsafe { ::core::ptr::read_volatile(&diff) == 0 }
+            }
+
+            /// Counts number of leading zero bits, treating the first byte as
+            /// the most significant one (i.e. big-endian order, as used by
+            /// XOR distance metrics)
+            pub fn leading_zeros(&self) -> u32 {
+                let mut count = 0u32;
+                for byte in &self.0 {
+                    count += byte.leading_zeros();
+                    if *byte != 0 {
+                        break;
+                    }
+                }
+                count
+            }
+        }
+
+        $crate::__slice_bitop!($name, BitAnd, bitand, BitAndAssign, bitand_assign, &);
+        $crate::__slice_bitop!($name, BitOr, bitor, BitOrAssign, bitor_assign, |);
+        $crate::__slice_bitop!($name, BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+        impl ::core::ops::Not for $name {
+            type Output = Self;
+            #[inline]
+            fn not(self) -> Self::Output {
+                !&self
+            }
+        }
+
+        impl ::core::ops::Not for &$name {
+            type Output = $name;
+            fn not(self) -> Self::Output {
+                let mut res = *self;
+                for byte in res.0.iter_mut() {
+                    *byte = !*byte;
+                }
+                res
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __slice_bitop {
+    ($name:ident, $op:ident, $fn:ident, $op_assign:ident, $fn_assign:ident, $sign:tt) => {
+        impl ::core::ops::$op_assign<&$name> for $name {
+            fn $fn_assign(&mut self, rhs: &$name) {
+                for (byte, other) in self.0.iter_mut().zip(rhs.0.iter()) {
+                    *byte = *byte $sign *other;
+                }
+            }
+        }
+
+        impl ::core::ops::$op_assign for $name {
+            #[inline]
+            fn $fn_assign(&mut self, rhs: $name) {
+                ::core::ops::$op_assign::$fn_assign(self, &rhs)
+            }
+        }
+
+        impl ::core::ops::$op for &$name {
+            type Output = $name;
+            #[inline]
+            fn $fn(self, rhs: Self) -> Self::Output {
+                let mut res = *self;
+                ::core::ops::$op_assign::$fn_assign(&mut res, rhs);
+                res
+            }
+        }
+
+        impl ::core::ops::$op for $name {
+            type Output = $name;
+            #[inline]
+            fn $fn(mut self, rhs: Self) -> Self::Output {
+                ::core::ops::$op_assign::$fn_assign(&mut self, &rhs);
+                self
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __slice_index {
+    ($name:ident; $( $range:ty ),+) => {
+        $(
+            impl ::core::ops::Index<$range> for $name {
+                type Output = [u8];
+                #[inline]
+                fn index(&self, index: $range) -> &Self::Output {
+                    &self.0[index]
+                }
+            }
+
+            impl ::core::ops::IndexMut<$range> for $name {
+                #[inline]
+                fn index_mut(&mut self, index: $range) -> &mut Self::Output {
+                    &mut self.0[index]
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __slice_alloc {
+    ($name:ident, $len:literal) => {
+        impl $name {
+            /// Returns vector representing internal slice data
+            #[allow(clippy::wrong_self_convention)]
+            pub fn to_vec(&self) -> $crate::_export::Vec<u8> {
+                self.0.to_vec()
+            }
+        }
+    };
+}
+
+#[cfg(not(any(feature = "std", feature = "alloc")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __slice_alloc {
+    ($name:ident, $len:literal) => {};
+}
+
+#[cfg(feature = "rand")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __slice_rand {
+    ($name:ident, $len:literal) => {
+        impl $name {
+            /// Generates byte array from `rand::thread_rng` random number
+            /// generator
+            pub fn random() -> Self {
+                use $crate::_export::rand::RngCore;
+                let mut entropy = [0u8; $len];
+                $crate::_export::rand::thread_rng().fill_bytes(&mut entropy);
+                $name(entropy)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "rand"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __slice_rand {
+    ($name:ident, $len:literal) => {};
+}
+
+#[cfg(all(feature = "hex", any(feature = "std", feature = "alloc")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __slice_hex {
+    ($name:ident, $len:literal) => {
+        impl ::core::fmt::Display for $name {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::LowerHex::fmt(self, f)
+            }
+        }
+
+        impl ::core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                use $crate::hex::ToHex;
+                write!(f, "{}({})", stringify!($name), self.to_hex())
+            }
+        }
+
+        impl ::core::str::FromStr for $name {
+            type Err = $crate::hex::Error;
+
+            /// Parses hex string with two hex digits per each byte in either
+            /// case, optionally prefixed with `0x` or `0X`. Strings of other length
+            /// result in `hex::Error::InvalidLength` error, which reports
+            /// expected and actual number of hex digits.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let hex = match s.get(..2) {
+                    Some("0x") | Some("0X") => &s[2..],
+                    _ => s,
+                };
+                if hex.len() != $len * 2 {
+                    return Err($crate::hex::Error::InvalidLength($len * 2, hex.len()));
+                }
+                <Self as $crate::hex::FromHex>::from_hex_any(s)
+            }
+        }
+
+        impl $crate::hex::FromHex for $name {
+            fn from_byte_iter<I>(iter: I) -> Result<Self, $crate::hex::Error>
+            where
+                I: ::core::iter::Iterator<Item = Result<u8, $crate::hex::Error>>
+                    + ::core::iter::ExactSizeIterator
+                    + ::core::iter::DoubleEndedIterator,
+            {
+                let vec = <$crate::_export::Vec<u8> as $crate::hex::FromHex>::from_byte_iter(iter)?;
+                if vec.len() != $len {
+                    return Err($crate::hex::Error::InvalidLength($len, vec.len()));
+                }
+                let mut id = [0u8; $len];
+                id.copy_from_slice(&vec);
+                Ok($name(id))
+            }
+        }
+
+        impl ::core::fmt::LowerHex for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                use $crate::hex::ToHex;
+                if f.alternate() {
+                    f.write_str("0x")?;
+                }
+                f.write_str(&self.0.to_hex())
+            }
+        }
+
+        impl ::core::fmt::UpperHex for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                use $crate::hex::ToHex;
+                if f.alternate() {
+                    f.write_str("0x")?;
+                }
+                f.write_str(&self.0.to_hex().to_ascii_uppercase())
+            }
+        }
+    };
+}
+
+#[cfg(not(all(feature = "hex", any(feature = "std", feature = "alloc"))))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __slice_hex {
+    ($name:ident, $len:literal) => {};
+}
+
+#[cfg(all(feature = "serde", feature = "hex"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __slice_serde {
+    ($name:ident, $len:literal) => {
+        impl $crate::_export::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: $crate::_export::serde::Serializer,
+            {
+                use $crate::_export::serde::ser::SerializeTuple;
+                use $crate::hex::ToHex;
+                if serializer.is_human_readable() {
+                    serializer.serialize_str(&self.0.to_hex())
+                } else {
+                    // Fixed-size tuple is serialized by binary formats (like
+                    // bincode) without length prefix
+                    let mut tuple = serializer.serialize_tuple($len)?;
+                    for byte in &self.0 {
+                        tuple.serialize_element(byte)?;
+                    }
+                    tuple.end()
+                }
+            }
+        }
+
+        impl<'de> $crate::_export::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: $crate::_export::serde::Deserializer<'de>,
+            {
+                use $crate::_export::serde::de;
+
+                struct Visitor;
+                impl<'de> de::Visitor<'de> for Visitor {
+                    type Value = $name;
+
+                    fn expecting(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        write!(
+                            f,
+                            "{} bytes or a hex string with {} characters",
+                            $len,
+                            $len * 2
+                        )
+                    }
+
+                    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+                    where
+                        E: de::Error,
+                    {
+                        <$name as ::core::str::FromStr>::from_str(s).map_err(|err| match err {
+                            $crate::hex::Error::InvalidLength(_, len) => {
+                                de::Error::invalid_length(len, &self)
+                            }
+                            _ => de::Error::invalid_value(de::Unexpected::Str(s), &self),
+                        })
+                    }
+
+                    fn visit_bytes<E>(self, bytes: &[u8]) -> Result<Self::Value, E>
+                    where
+                        E: de::Error,
+                    {
+                        $name::from_slice(bytes)
+                            .ok_or_else(|| de::Error::invalid_length(bytes.len(), &self))
+                    }
+
+                    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: de::SeqAccess<'de>,
+                    {
+                        let mut data = [0u8; $len];
+                        for (len, byte) in data.iter_mut().enumerate() {
+                            *byte = seq
+                                .next_element()?
+                                .ok_or_else(|| de::Error::invalid_length(len, &self))?;
+                        }
+                        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+                            return Err(de::Error::invalid_length($len + 1, &self));
+                        }
+                        Ok($name::from_array(data))
+                    }
+                }
+
+                // Human-readable formats may provide either a hex string or
+                // an array of bytes; binary formats - either a fixed-size
+                // tuple or a byte string (like in CBOR)
+                if deserializer.is_human_readable() {
+                    deserializer.deserialize_any(Visitor)
+                } else {
+                    deserializer.deserialize_tuple($len, Visitor)
+                }
+            }
+        }
+    };
+}
+
+#[cfg(not(all(feature = "serde", feature = "hex")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __slice_serde {
+    ($name:ident, $len:literal) => {};
+}
+
+slice_type!(
+    /// Wrapper type for all slice-based 160-bit types (like RIPEMD-160 hashes
+    /// or key identifiers) implementing many important traits, so types based
+    /// on it can simply derive their implementations.
+    ///
+    /// Type keeps data in little-endian byte order and displays them in the
+    /// same order.
+    ///
+    /// With `serde` feature the data are serialized as a lowercase hex string
+    /// for human-readable formats and as a fixed-size tuple of 20 bytes
+    /// (without length prefix) for binary formats. Use
+    /// [`serde_helpers::length_prefixed`](crate::serde_helpers::length_prefixed)
+    /// if length-prefixed byte string is required instead.
+    pub struct Slice20([u8; 20])
+);
+
+slice_type!(
+    /// Wrapper type for all slice-based 256-bit types implementing many
+    /// important traits, so types based on it can simply derive their
+    /// implementations.
+    ///
+    /// Type keeps data in little-endian byte order and displays them in the
+    /// same order (like bitcoin SHA256 single hash type).
+    ///
+    /// With `serde` feature the data are serialized as a lowercase hex string
+    /// for human-readable formats and as a fixed-size tuple of 32 bytes
+    /// (without length prefix) for binary formats. Use
+    /// [`serde_helpers::length_prefixed`](crate::serde_helpers::length_prefixed)
+    /// if length-prefixed byte string is required instead.
+    pub struct Slice32([u8; 32])
+);
+
+slice_type!(
+    /// Wrapper type for all slice-based 512-bit types (like signatures or
+    /// SHA-512 hashes) implementing many important traits, so types based on
+    /// it can simply derive their implementations.
+    ///
+    /// Type keeps data in little-endian byte order and displays them in the
+    /// same order.
+    ///
+    /// With `serde` feature the data are serialized as a lowercase hex string
+    /// for human-readable formats and as a fixed-size tuple of 64 bytes
+    /// (without length prefix) for binary formats. Use
+    /// [`serde_helpers::length_prefixed`](crate::serde_helpers::length_prefixed)
+    /// if length-prefixed byte string is required instead.
+    pub struct Slice64([u8; 64])
+);
+
+#[cfg(test)]
+mod test {
+    use super::{Slice20, Slice32, Slice64};
+    use crate::hex::Error;
+    use core::borrow::Borrow;
+    use crate::Wrapper;
+    use crate::hex::FromHex;
+    use core::str::FromStr;
+
+    const GENESIS: Slice32 = Slice32::from_array([0xAB; 32]);
+    const _: () = assert!(GENESIS.to_array()[31] == 0xAB);
+
+    #[test]
+    fn test_slice32_str() {
+        let s = "a3401bcceb26201b55978ff705fecf7d8a0a03598ebeccf2a947030b91a0ff53";
+        let slice32 = Slice32::from_hex(s).unwrap();
+        assert_eq!(Slice32::from_str(s), Ok(slice32));
+
+        assert_eq!(Slice32::from_hex(&s.to_uppercase()), Ok(slice32));
+        assert_eq!(Slice32::from_str(&s.to_uppercase()), Ok(slice32));
+        assert_eq!(Slice32::from_str(&format!("0x{}", s)), Ok(slice32));
+        assert_eq!(Slice32::from_str(&format!("0X{}", s)), Ok(slice32));
+        assert_eq!(
+            Slice32::from_hex(&s[..30]),
+            Err(Error::InvalidLength(32, 15))
+        );
+        assert_eq!(
+            Slice32::from_str(&s[..30]),
+            Err(Error::InvalidLength(64, 30))
+        );
+        assert_eq!(
+            Slice32::from_str(&s[..63]),
+            Err(Error::InvalidLength(64, 63))
+        );
+        assert_eq!(
+            Slice32::from_str(&format!("0x{}00", s)),
+            Err(Error::InvalidLength(64, 66))
+        );
+        assert_eq!(
+            Slice32::from_str(&format!("0x0x{}", &s[2..])),
+            Err(Error::MisplacedPrefix(2))
+        );
+        assert_eq!(
+            Slice32::from_str(&s[..30]).unwrap_err().to_string(),
+            "bad hex string length 30 (expected 64)"
+        );
+
+        assert_eq!(&slice32.to_string(), s);
+        assert_eq!(format!("{:x}", slice32), s);
+        assert_eq!(format!("{:X}", slice32), s.to_uppercase());
+        assert_eq!(format!("{:#x}", slice32), format!("0x{}", s));
+        assert_eq!(format!("{:#X}", slice32), format!("0x{}", s.to_uppercase()));
+        assert_eq!(format!("{:?}", slice32), format!("Slice32({})", s));
+        assert_eq!(Slice32::from_str(&format!("{:#x}", slice32)), Ok(slice32));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_slice32_serde() {
+        use bincode::Options;
+        use serde::de::value::BytesDeserializer;
+        use serde::Deserialize;
+
+        let s = "a3401bcceb26201b55978ff705fecf7d8a0a03598ebeccf2a947030b91a0ff53";
+        let slice32 = Slice32::from_hex(s).unwrap();
+
+        let json = format!("\"{}\"", s);
+        assert_eq!(serde_json::to_string(&slice32).unwrap(), json);
+        assert_eq!(serde_json::from_str::<Slice32>(&json).unwrap(), slice32);
+        assert_eq!(
+            serde_json::from_str::<Slice32>(&json.to_uppercase()).unwrap(),
+            slice32
+        );
+        assert!(serde_json::from_str::<Slice32>(&format!("\"{}\"", &s[2..]))
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid length 62"));
+        assert!(serde_json::from_str::<Slice32>(&format!("\"{}zz\"", &s[2..])).is_err());
+
+        let bin_encoded = bincode::serialize(&slice32).unwrap();
+        assert_eq!(&bin_encoded[..], &slice32[..]);
+        let bin_decoded: Slice32 = bincode::deserialize(&bin_encoded).unwrap();
+        assert_eq!(bin_decoded, slice32);
+        assert!(bincode::deserialize::<Slice32>(&slice32[..31]).is_err());
+
+        let options = bincode::DefaultOptions::new().with_fixint_encoding();
+        let bin_encoded = options.serialize(&slice32).unwrap();
+        assert_eq!(bin_encoded.len(), 32);
+        assert_eq!(
+            options.deserialize::<Slice32>(&bin_encoded).unwrap(),
+            slice32
+        );
+
+        // JSON arrays and byte strings
+        let array = serde_json::to_string(&slice32.to_array()).unwrap();
+        assert_eq!(serde_json::from_str::<Slice32>(&array).unwrap(), slice32);
+        assert!(
+            serde_json::from_str::<Slice32>(&serde_json::to_string(&[0u8; 31]).unwrap())
+                .unwrap_err()
+                .to_string()
+                .starts_with("invalid length 31")
+        );
+        assert!(
+            serde_json::from_str::<Slice32>(&format!("[0,{}]", &array[1..]))
+                .unwrap_err()
+                .to_string()
+                .starts_with("invalid length 33")
+        );
+        let bytes = BytesDeserializer::<serde::de::value::Error>::new(&slice32[..]);
+        assert_eq!(Slice32::deserialize(bytes).unwrap(), slice32);
+        let bytes = BytesDeserializer::<serde::de::value::Error>::new(&slice32[..31]);
+        assert!(Slice32::deserialize(bytes).is_err());
+    }
+
+    #[test]
+    fn test_encoding() {
+        let s = "a3401bcceb26201b55978ff705fecf7d8a0a03598ebeccf2a947030b91a0ff53";
+        let slice32 = Slice32::from_hex(s).unwrap();
+
+        let data = [
+            0xa3, 0x40, 0x1b, 0xcc, 0xeb, 0x26, 0x20, 0x1b, 0x55, 0x97, 0x8f, 0xf7, 0x05, 0xfe,
+            0xcf, 0x7d, 0x8a, 0x0a, 0x03, 0x59, 0x8e, 0xbe, 0xcc, 0xf2, 0xa9, 0x47, 0x03, 0x0b,
+            0x91, 0xa0, 0xff, 0x53,
+        ];
+
+        assert_eq!(Slice32::from_slice(data), Some(slice32));
+        assert_eq!(Slice32::from_slice(&data[..30]), None);
+        assert_eq!(Slice32::from_slice(&data[..31]), None);
+        assert_eq!(Slice32::from_slice([&data[..], &[0u8][..]].concat()), None);
+        assert_eq!(Slice32::copy_from_slice(&data[..]), slice32);
+        assert_eq!(Slice32::from_array(data), slice32);
+        assert_eq!(&slice32.to_vec(), &data);
+        assert_eq!(&slice32.as_inner()[..], &data);
+        assert_eq!(slice32.to_inner(), data);
+        assert_eq!(slice32.into_inner(), data);
+    }
+
+    #[test]
+    #[should_panic(expected = "Slice32 requires slice of 32 bytes, while 33 bytes were provided")]
+    fn test_copy_from_slice_len() {
+        Slice32::copy_from_slice([0u8; 33]);
+    }
+
+    #[test]
+    fn test_slice_access() {
+        const ZERO: Slice32 = Slice32::from_array([0u8; 32]);
+        assert_eq!(ZERO, Slice32::default());
+
+        let mut data = [0u8; 32];
+        for (index, byte) in data.iter_mut().enumerate() {
+            *byte = index as u8;
+        }
+        let mut slice32 = Slice32::from_array(data);
+
+        assert_eq!(slice32[5usize], 5);
+        assert_eq!(&slice32[..], &data[..]);
+        assert_eq!(&slice32[0..32], &data[..]);
+        assert_eq!(&slice32[3..6], &[3, 4, 5]);
+        assert_eq!(AsRef::<[u8]>::as_ref(&slice32), &data[..]);
+        assert_eq!(Borrow::<[u8]>::borrow(&slice32), &data[..]);
+        assert_eq!(
+            (&slice32).into_iter().copied().collect::<Vec<_>>(),
+            data.to_vec()
+        );
+        assert_eq!((&slice32).into_iter().len(), 32);
+        let mut sum = 0u32;
+        for byte in &slice32 {
+            sum += *byte as u32;
+        }
+        assert_eq!(sum, 496);
+
+        slice32[30..32].copy_from_slice(&[0xAA, 0xBB]);
+        AsMut::<[u8]>::as_mut(&mut slice32)[0] = 0xFF;
+        assert_eq!(slice32[0usize], 0xFF);
+        assert_eq!(&slice32[29..32], &[29, 0xAA, 0xBB]);
+        slice32[..].copy_from_slice(&data);
+        assert_eq!(slice32, Slice32::from(data));
+    }
+
+    #[test]
+    fn test_slice_sizes() {
+        let s20 = "a3401bcceb26201b55978ff705fecf7d8a0a0359";
+        let slice20 = Slice20::from_str(s20).unwrap();
+        assert_eq!(slice20.to_string(), s20);
+        assert_eq!(
+            format!("{:#X}", slice20),
+            format!("0x{}", s20.to_uppercase())
+        );
+        assert_eq!(format!("{:?}", slice20), format!("Slice20({})", s20));
+        assert_eq!(
+            Slice20::from_str(&s20[..38]),
+            Err(Error::InvalidLength(40, 38))
+        );
+        assert_eq!(Slice20::from_slice(&slice20[..]), Some(slice20));
+        assert_eq!(Slice20::from_slice(&slice20[..19]), None);
+
+        let s64 = "a3401bcceb26201b55978ff705fecf7d8a0a03598ebeccf2a947030b91a0ff53\
+                   a3401bcceb26201b55978ff705fecf7d8a0a03598ebeccf2a947030b91a0ff54";
+        let slice64 = Slice64::from_str(s64).unwrap();
+        assert_eq!(slice64.to_string(), s64);
+        assert_eq!(Slice64::from_str(&format!("0x{}", s64)), Ok(slice64));
+        assert_eq!(
+            Slice64::from_str(&s64[..64]),
+            Err(Error::InvalidLength(128, 64))
+        );
+        assert_eq!(Slice64::default(), Slice64::from_array([0u8; 64]));
+        assert!(slice64 > Slice64::default());
+        assert!(slice64 < Slice64::from_array([0xFF; 64]));
+        assert_eq!(slice64.into_inner()[63], 0x54);
+        assert_eq!(slice64.to_vec().len(), 64);
+        assert_eq!(
+            Slice32::copy_from_slice(&slice64[..32]),
+            Slice32::from_str(&s64[..64]).unwrap()
+        );
+        assert_eq!(&slice64[62..], &[0xff, 0x54]);
+        assert_eq!(&slice64[..=1], &[0xa3, 0x40]);
+        assert_eq!(&slice64[..2], &slice64[0..2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Slice64 requires slice of 64 bytes, while 32 bytes were provided")]
+    fn test_copy_from_slice_name() {
+        Slice64::copy_from_slice([0u8; 32]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_slice_sizes_serde() {
+        let slice64 = Slice64::from_array([0xA5; 64]);
+        let json = format!("\"{}\"", "a5".repeat(64));
+        assert_eq!(serde_json::to_string(&slice64).unwrap(), json);
+        assert_eq!(serde_json::from_str::<Slice64>(&json).unwrap(), slice64);
+        let bin_decoded: Slice64 =
+            bincode::deserialize(&bincode::serialize(&slice64).unwrap()).unwrap();
+        assert_eq!(bin_decoded, slice64);
+
+        let slice20 = Slice20::from_array([0x5A; 20]);
+        let bin_encoded = bincode::serialize(&slice20).unwrap();
+        assert_eq!(bin_encoded.len(), 20);
+        assert!(bincode::deserialize::<Slice32>(&bin_encoded).is_err());
+        assert!(serde_json::from_str::<Slice20>(&json).is_err());
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_bitops() {
+        let zero = Slice32::default();
+        let ones = Slice32::from_array([0xFF; 32]);
+        let a =
+            Slice32::from_str("00003fcceb26201b55978ff705fecf7d8a0a03598ebeccf2a947030b91a0ff53")
+                .unwrap();
+        let b =
+            Slice32::from_str("00001bcceb26201b55978ff705fecf7d8a0a03598ebeccf2a947030b91a0ff53")
+                .unwrap();
+
+        assert_eq!(a ^ a, zero);
+        assert_eq!(
+            &a ^ &b,
+            Slice32::from_str(&format!("0000{}{}", "24", "00".repeat(29))).unwrap()
+        );
+        assert_eq!(a & b, b);
+        assert_eq!(&a | &b, a);
+        assert_eq!(!zero, ones);
+        assert_eq!(!&ones, zero);
+        assert_eq!(a & !a, zero);
+        assert_eq!(a | !a, ones);
+        assert_eq!(a ^ ones, !a);
+        assert_eq!(a & ones, a);
+        assert_eq!(a | zero, a);
+
+        let mut c = a;
+        c ^= b;
+        assert_eq!(c, a ^ b);
+        c |= &b;
+        assert_eq!(c, a);
+        c &= zero;
+        assert_eq!(c, zero);
+
+        assert_eq!(zero.leading_zeros(), 256);
+        assert_eq!(ones.leading_zeros(), 0);
+        assert_eq!(a.leading_zeros(), 18);
+        assert_eq!((a ^ b).leading_zeros(), 18);
+        assert_eq!(Slice20::from_array([0u8; 20]).leading_zeros(), 160);
+        let mut last = Slice64::default();
+        last[63usize] = 1;
+        assert_eq!(last.leading_zeros(), 511);
+
+        assert!(a.ct_eq(&a));
+        assert!(!a.ct_eq(&b));
+        assert!(zero.ct_eq(&Slice32::default()));
+        assert!(!zero.ct_eq(&ones));
+        assert!(!last.ct_eq(&Slice64::default()));
+    }
+}
@@ -78,9 +78,53 @@
 //!     type Strategy = StrategyA;
 //! }
 //! ```
+//!
+//! # Formatting strategies
+//!
+//! The module also provides a ready-made set of strategies for the formatting
+//! traits ([`Display`], [`Debug`], [`LowerHex`] and [`UpperHex`]): a type
+//! selects one of [`DisplayFromInner`], [`DisplayFromHex`] or
+//! [`DisplayFromDebug`] by implementing [`FormattingStrategy`], after which
+//! [`impl_fmt_strategy!`] implements the requested formatting traits through
+//! the [`Holder`] implementations for that strategy.
+//!
+//! ```
+//! use amplify::strategy::{DisplayFromHex, DisplayFromInner, FormattingStrategy};
+//! use amplify::{impl_fmt_strategy, wrapper};
+//!
+//! wrapper! {
+//!     pub struct Amount(u64);
+//! }
+//! impl FormattingStrategy for Amount {
+//!     type Strategy = DisplayFromInner;
+//! }
+//! impl_fmt_strategy!(Amount: Display, Debug, LowerHex);
+//!
+//! wrapper! {
+//!     pub struct TxId(Vec<u8>);
+//! }
+//! impl AsRef<[u8]> for TxId {
+//!     fn as_ref(&self) -> &[u8] { &self.0 }
+//! }
+//! impl FormattingStrategy for TxId {
+//!     type Strategy = DisplayFromHex;
+//! }
+//! impl_fmt_strategy!(TxId: Display, Debug);
+//!
+//! assert_eq!(format!("{} {:?} {:x}", Amount::from(255), Amount::from(1), Amount::from(255)), "255 1 ff");
+//! assert_eq!(format!("{} {:?}", TxId::from(vec![0xca, 0xfe]), TxId::from(vec![1])), "cafe 01");
+//! ```
+//!
+//! Since the formatting traits are foreign to the crate defining the type, they
+//! can't be implemented for all types with [`FormattingStrategy`] by a
+//! blanket implementation; the macro generates the per-type implementations
+//! instead.
 
+use ::core::fmt::{self, Debug, Display, Formatter, LowerHex, UpperHex};
 use ::core::marker::PhantomData;
 
+use crate::Wrapper;
+
 /// Helper type allowing implementation of trait object for generic types
 /// multiple times. In practice this type is never used
 pub struct Holder<T, S>(T, PhantomData<S>);
@@ -103,3 +147,208 @@ impl<T, S> Holder<T, S> {
         &self.0
     }
 }
+
+/// Marker trait selecting formatting strategy for a type, which is used by
+/// [`impl_fmt_strategy!`] to implement the formatting traits.
+pub trait FormattingStrategy {
+    /// Formatting strategy: [`DisplayFromInner`], [`DisplayFromHex`] or
+    /// [`DisplayFromDebug`]
+    type Strategy;
+}
+
+/// Formatting strategy for [`Wrapper`] types delegating all formatting traits
+/// to the wrapped inner type.
+pub struct DisplayFromInner;
+
+/// Formatting strategy for byte containers (types implementing `AsRef<[u8]>`)
+/// displaying them as hex strings. Both [`Display`] and [`Debug`] use
+/// lowercase hex.
+pub struct DisplayFromHex;
+
+/// Formatting strategy using [`Debug`] representation of the type as its
+/// [`Display`] form.
+pub struct DisplayFromDebug;
+
+impl<T> Display for Holder<&T, DisplayFromInner>
+where
+    T: Wrapper,
+    T::Inner: Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.0.as_inner(), f)
+    }
+}
+
+impl<T> Debug for Holder<&T, DisplayFromInner>
+where
+    T: Wrapper,
+    T::Inner: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.0.as_inner(), f)
+    }
+}
+
+impl<T> LowerHex for Holder<&T, DisplayFromInner>
+where
+    T: Wrapper,
+    T::Inner: LowerHex,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        LowerHex::fmt(self.0.as_inner(), f)
+    }
+}
+
+impl<T> UpperHex for Holder<&T, DisplayFromInner>
+where
+    T: Wrapper,
+    T::Inner: UpperHex,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        UpperHex::fmt(self.0.as_inner(), f)
+    }
+}
+
+#[cfg(any(test, feature = "hex"))]
+impl<T> Display for Holder<&T, DisplayFromHex>
+where
+    T: AsRef<[u8]>,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        LowerHex::fmt(self, f)
+    }
+}
+
+#[cfg(any(test, feature = "hex"))]
+impl<T> Debug for Holder<&T, DisplayFromHex>
+where
+    T: AsRef<[u8]>,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        LowerHex::fmt(self, f)
+    }
+}
+
+#[cfg(any(test, feature = "hex"))]
+impl<T> LowerHex for Holder<&T, DisplayFromHex>
+where
+    T: AsRef<[u8]>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        crate::hex::format_hex(self.0.as_ref(), f)
+    }
+}
+
+#[cfg(any(test, feature = "hex"))]
+impl<T> UpperHex for Holder<&T, DisplayFromHex>
+where
+    T: AsRef<[u8]>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        crate::hex::format_hex_upper(self.0.as_ref(), f)
+    }
+}
+
+impl<T> Display for Holder<&T, DisplayFromDebug>
+where
+    T: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.0, f)
+    }
+}
+
+/// Implements formatting traits for a type through the [`Holder`]
+/// implementations for the strategy selected by the type
+/// [`FormattingStrategy`](crate::strategy::FormattingStrategy).
+///
+/// Takes the type followed by the list of `Display`, `Debug`, `LowerHex` and
+/// `UpperHex` traits to implement. See the [`strategy`](crate::strategy) module
+/// documentation for the example.
+#[macro_export]
+macro_rules! impl_fmt_strategy {
+    ($ty:ty: $( $trait:ident ),+ $(,)?) => {
+        $(
+            impl ::core::fmt::$trait for $ty {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    let holder = $crate::Holder::<
+                        &Self,
+                        <Self as $crate::strategy::FormattingStrategy>::Strategy,
+                    >::new(self);
+                    ::core::fmt::$trait::fmt(&holder, f)
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    wrapper! {
+        struct Amount(u32);
+    }
+
+    impl FormattingStrategy for Amount {
+        type Strategy = DisplayFromInner;
+    }
+    impl_fmt_strategy!(Amount: Display, Debug, LowerHex, UpperHex);
+
+    wrapper! {
+        struct Digest([u8; 4]);
+    }
+
+    impl AsRef<[u8]> for Digest {
+        fn as_ref(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    impl FormattingStrategy for Digest {
+        type Strategy = DisplayFromHex;
+    }
+    impl_fmt_strategy!(Digest: Display, Debug, LowerHex, UpperHex);
+
+    #[derive(Debug)]
+    enum Network {
+        Mainnet,
+    }
+
+    impl FormattingStrategy for Network {
+        type Strategy = DisplayFromDebug;
+    }
+    impl_fmt_strategy!(Network: Display);
+
+    #[test]
+    fn fmt_strategies() {
+        let amount = Amount::from(0xBEEF);
+        assert_eq!(format!("{}", amount), "48879");
+        assert_eq!(format!("{:?}", amount), "48879");
+        assert_eq!(format!("{:x}", amount), "beef");
+        assert_eq!(format!("{:#X}", amount), "0xBEEF");
+        assert_eq!(format!("{:>7}", amount), "  48879");
+
+        let hash = Digest::from([0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(format!("{}", hash), "deadbeef");
+        assert_eq!(format!("{:?}", hash), "deadbeef");
+        assert_eq!(format!("{:#x}", hash), "0xdeadbeef");
+        assert_eq!(format!("{:X}", hash), "DEADBEEF");
+
+        assert_eq!(format!("{}", Network::Mainnet), "Mainnet");
+    }
+}
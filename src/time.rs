@@ -0,0 +1,388 @@
+// Rust language amplification library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Time amplification: conversions between [`Duration`], integer time units
+//! used by network protocols and [`SystemTime`].
+//!
+//! Conversions never wrap silently: the ones which may overflow are either
+//! checked (returning `Option` or `Result`) or saturating, as reflected in
+//! their names.
+//!
+//! ```
+//! use std::convert::TryFrom;
+//! use std::time::{Duration, SystemTime};
+//! use amplify::time::{DurationExt, Timestamp};
+//!
+//! let duration = Duration::from_millis_u64(1500);
+//! assert_eq!(duration.as_millis_u64(), Some(1500));
+//! assert_eq!(Duration::checked_from_secs_f64(-1.0), None);
+//!
+//! let timestamp = Timestamp::from_secs(1_600_000_000);
+//! let later = timestamp.checked_add(duration).unwrap();
+//! assert_eq!(later.to_string(), "1600000001");
+//! let time = SystemTime::try_from(later).unwrap();
+//! assert_eq!(Timestamp::try_from(time), Ok(later));
+//! ```
+
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Extension trait for [`Duration`] converting it from and into integer and
+/// floating point representations with explicit overflow handling
+pub trait DurationExt: Sized {
+    /// Constructs duration from the number of microseconds. Never overflows.
+    fn from_micros_u64(micros: u64) -> Self;
+
+    /// Constructs duration from the number of milliseconds. Never overflows.
+    fn from_millis_u64(millis: u64) -> Self;
+
+    /// Constructs duration from the number of seconds given as a floating
+    /// point number, returning `None` if the number is NaN, infinite,
+    /// negative or does not fit the duration. Fractions of nanoseconds are
+    /// truncated.
+    fn checked_from_secs_f64(secs: f64) -> Option<Self>;
+
+    /// Returns the total number of whole microseconds, or `None` if the number
+    /// does not fit `u64`
+    fn as_micros_u64(&self) -> Option<u64>;
+
+    /// Returns the total number of whole milliseconds, or `None` if the number
+    /// does not fit `u64`
+    fn as_millis_u64(&self) -> Option<u64>;
+
+    /// Returns the total number of whole microseconds, saturating at
+    /// `u64::MAX`
+    fn saturating_as_micros_u64(&self) -> u64 {
+        self.as_micros_u64().unwrap_or(u64::MAX)
+    }
+
+    /// Returns the total number of whole milliseconds, saturating at
+    /// `u64::MAX`
+    fn saturating_as_millis_u64(&self) -> u64 {
+        self.as_millis_u64().unwrap_or(u64::MAX)
+    }
+}
+
+impl DurationExt for Duration {
+    #[inline]
+    fn from_micros_u64(micros: u64) -> Self {
+        Duration::from_micros(micros)
+    }
+
+    #[inline]
+    fn from_millis_u64(millis: u64) -> Self {
+        Duration::from_millis(millis)
+    }
+
+    fn checked_from_secs_f64(secs: f64) -> Option<Self> {
+        // `2^64`, which is exactly representable as `f64` (unlike `u64::MAX`)
+        const SECS_LIMIT: f64 = 18_446_744_073_709_551_616.0;
+        // Range does not contain NaN, so it is rejected as well
+        if !(0.0..SECS_LIMIT).contains(&secs) {
+            return None;
+        }
+        let whole = secs.trunc();
+        let nanos = ((secs - whole) * 1_000_000_000.0) as u32;
+        Some(Duration::new(whole as u64, nanos.min(999_999_999)))
+    }
+
+    #[inline]
+    fn as_micros_u64(&self) -> Option<u64> {
+        u64::try_from(self.as_micros()).ok()
+    }
+
+    #[inline]
+    fn as_millis_u64(&self) -> Option<u64> {
+        u64::try_from(self.as_millis()).ok()
+    }
+}
+
+wrapper! {
+    /// Unix timestamp: number of whole seconds elapsed since the Unix epoch
+    /// (1970-01-01 00:00:00 UTC).
+    ///
+    /// The timestamp is displayed and parsed as an integer number of seconds;
+    /// with `serde` feature it is serialized as an integer as well. Since the
+    /// value is unsigned and 64-bit wide, it is not subject to the year 2038
+    /// problem, but it can't represent moments preceding the epoch.
+    #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+    pub struct Timestamp(u64);
+    impl Display, FromStr;
+}
+
+impl Timestamp {
+    /// Timestamp of the Unix epoch
+    pub const EPOCH: Timestamp = Timestamp(0);
+
+    /// Constructs timestamp from the number of seconds since the Unix epoch
+    #[inline]
+    pub const fn from_secs(secs: u64) -> Self {
+        Timestamp(secs)
+    }
+
+    /// Returns the number of seconds since the Unix epoch
+    #[inline]
+    pub const fn as_secs(self) -> u64 {
+        self.0
+    }
+
+    /// Returns timestamp of the current system time, truncated to whole
+    /// seconds.
+    ///
+    /// # Panics
+    ///
+    /// If the system clock is set before the Unix epoch.
+    pub fn now() -> Self {
+        Timestamp::try_from(SystemTime::now()).expect("system clock is set before the Unix epoch")
+    }
+
+    /// Adds duration to the timestamp, returning `None` on overflow. The
+    /// sub-second part of the duration is truncated.
+    #[inline]
+    pub fn checked_add(self, duration: Duration) -> Option<Self> {
+        self.0.checked_add(duration.as_secs()).map(Timestamp)
+    }
+
+    /// Subtracts duration from the timestamp, returning `None` if the result
+    /// precedes the Unix epoch. The sub-second part of the duration is
+    /// truncated.
+    #[inline]
+    pub fn checked_sub(self, duration: Duration) -> Option<Self> {
+        self.0.checked_sub(duration.as_secs()).map(Timestamp)
+    }
+
+    /// Returns duration elapsed from the `earlier` timestamp, or `None` if the
+    /// `earlier` timestamp is later than the self
+    #[inline]
+    pub fn checked_duration_since(self, earlier: Timestamp) -> Option<Duration> {
+        self.0.checked_sub(earlier.0).map(Duration::from_secs)
+    }
+
+    /// Converts timestamp into [`SystemTime`], returning `None` if the
+    /// timestamp can't be represented on the current platform
+    #[inline]
+    pub fn to_system_time(self) -> Option<SystemTime> {
+        UNIX_EPOCH.checked_add(Duration::from_secs(self.0))
+    }
+}
+
+/// Error converting [`SystemTime`] preceding the Unix epoch into
+/// [`Timestamp`]. Contains the duration by which the time precedes the epoch.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct PreEpochError(pub Duration);
+
+impl Display for PreEpochError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "time precedes the Unix epoch by {:?}", self.0)
+    }
+}
+
+impl Error for PreEpochError {}
+
+impl TryFrom<SystemTime> for Timestamp {
+    type Error = PreEpochError;
+
+    /// Converts system time into timestamp, truncating the sub-second part
+    fn try_from(time: SystemTime) -> Result<Self, Self::Error> {
+        time.duration_since(UNIX_EPOCH)
+            .map(|elapsed| Timestamp(elapsed.as_secs()))
+            .map_err(|err| PreEpochError(err.duration()))
+    }
+}
+
+/// Error converting [`Timestamp`] into [`SystemTime`] when the timestamp can't
+/// be represented on the current platform. Contains the original timestamp.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct SystemTimeRangeError(pub Timestamp);
+
+impl Display for SystemTimeRangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "timestamp {} is out of the system time range", self.0)
+    }
+}
+
+impl Error for SystemTimeRangeError {}
+
+impl TryFrom<Timestamp> for SystemTime {
+    type Error = SystemTimeRangeError;
+
+    fn try_from(timestamp: Timestamp) -> Result<Self, Self::Error> {
+        timestamp
+            .to_system_time()
+            .ok_or(SystemTimeRangeError(timestamp))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Timestamp {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_u64(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Timestamp {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        u64::deserialize(deserializer).map(Timestamp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Wrapper;
+
+    /// Last second representable with signed 32-bit Unix time:
+    /// 2038-01-19 03:14:07 UTC
+    const Y2038: u64 = i32::MAX as u64;
+
+    #[test]
+    fn duration_units() {
+        assert_eq!(
+            Duration::from_micros_u64(1_500_000),
+            Duration::from_millis(1500)
+        );
+        assert_eq!(
+            Duration::from_millis_u64(u64::MAX).as_secs(),
+            u64::MAX / 1000
+        );
+        assert_eq!(Duration::from_millis(1500).as_millis_u64(), Some(1500));
+        assert_eq!(Duration::from_micros(1999).as_millis_u64(), Some(1));
+        assert_eq!(Duration::from_nanos(999).as_micros_u64(), Some(0));
+
+        let max_millis = Duration::from_millis(u64::MAX);
+        assert_eq!(max_millis.as_millis_u64(), Some(u64::MAX));
+        assert_eq!(max_millis.as_micros_u64(), None);
+        assert_eq!(max_millis.saturating_as_micros_u64(), u64::MAX);
+        assert_eq!(max_millis.saturating_as_millis_u64(), u64::MAX);
+
+        let overflow = max_millis + Duration::from_millis(1);
+        assert_eq!(overflow.as_millis_u64(), None);
+        assert_eq!(overflow.saturating_as_millis_u64(), u64::MAX);
+        assert_eq!(Duration::MAX.saturating_as_millis_u64(), u64::MAX);
+    }
+
+    #[test]
+    fn duration_from_f64() {
+        assert_eq!(Duration::checked_from_secs_f64(0.0), Some(Duration::ZERO));
+        assert_eq!(Duration::checked_from_secs_f64(-0.0), Some(Duration::ZERO));
+        assert_eq!(
+            Duration::checked_from_secs_f64(1.5),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            Duration::checked_from_secs_f64(2.000_000_000_9),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            Duration::checked_from_secs_f64(Y2038 as f64),
+            Some(Duration::from_secs(Y2038))
+        );
+        assert_eq!(Duration::checked_from_secs_f64(-1e-9), None);
+        assert_eq!(Duration::checked_from_secs_f64(f64::NAN), None);
+        assert_eq!(Duration::checked_from_secs_f64(f64::INFINITY), None);
+        assert_eq!(Duration::checked_from_secs_f64(f64::NEG_INFINITY), None);
+        assert_eq!(
+            Duration::checked_from_secs_f64(18_446_744_073_709_551_616.0),
+            None
+        );
+        assert_eq!(
+            Duration::checked_from_secs_f64(18_446_744_073_709_549_568.0),
+            Some(Duration::from_secs(18_446_744_073_709_549_568))
+        );
+    }
+
+    #[test]
+    fn timestamp_2038() {
+        let last = Timestamp::from_secs(Y2038);
+        let next = last.checked_add(Duration::from_millis(1999)).unwrap();
+        assert_eq!(next.as_secs(), Y2038 + 1);
+        assert_eq!(next.to_string(), "2147483648");
+        assert_eq!("2147483648".parse::<Timestamp>(), Ok(next));
+        assert_eq!(next.checked_sub(Duration::from_secs(1)), Some(last));
+        assert_eq!(
+            next.checked_duration_since(last),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(last.checked_duration_since(next), None);
+
+        let time = SystemTime::try_from(next).unwrap();
+        assert_eq!(
+            time.duration_since(UNIX_EPOCH).unwrap(),
+            Duration::from_secs(1 << 31)
+        );
+        assert_eq!(Timestamp::try_from(time), Ok(next));
+    }
+
+    #[test]
+    fn timestamp_system_time() {
+        let time = UNIX_EPOCH + Duration::new(1_600_000_000, 999_999_999);
+        let timestamp = Timestamp::try_from(time).unwrap();
+        assert_eq!(timestamp, Timestamp::from_inner(1_600_000_000));
+        assert_eq!(
+            SystemTime::try_from(timestamp),
+            Ok(UNIX_EPOCH + Duration::from_secs(1_600_000_000))
+        );
+        assert_eq!(Timestamp::try_from(UNIX_EPOCH), Ok(Timestamp::EPOCH));
+        assert!(Timestamp::now() > Timestamp::from_secs(Y2038 / 2));
+
+        let before = UNIX_EPOCH - Duration::from_millis(1500);
+        let err = Timestamp::try_from(before).unwrap_err();
+        assert_eq!(err, PreEpochError(Duration::from_millis(1500)));
+        assert_eq!(err.to_string(), "time precedes the Unix epoch by 1.5s");
+    }
+
+    #[test]
+    fn timestamp_overflow() {
+        let max = Timestamp::from_secs(u64::MAX);
+        assert_eq!(max.checked_add(Duration::from_secs(1)), None);
+        assert_eq!(max.checked_add(Duration::from_millis(999)), Some(max));
+        assert_eq!(Timestamp::EPOCH.checked_sub(Duration::from_secs(1)), None);
+        assert_eq!(
+            Timestamp::EPOCH.checked_sub(Duration::from_millis(999)),
+            Some(Timestamp::EPOCH)
+        );
+        assert_eq!(max.to_system_time(), None);
+        let err = SystemTime::try_from(max).unwrap_err();
+        assert_eq!(err, SystemTimeRangeError(max));
+        assert_eq!(
+            err.to_string(),
+            "timestamp 18446744073709551615 is out of the system time range"
+        );
+        assert!("-1".parse::<Timestamp>().is_err());
+        assert!("18446744073709551616".parse::<Timestamp>().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn timestamp_serde() {
+        let timestamp = Timestamp::from_secs(1 << 31);
+        assert_eq!(serde_json::to_string(&timestamp).unwrap(), "2147483648");
+        assert_eq!(
+            serde_json::from_str::<Timestamp>("2147483648").unwrap(),
+            timestamp
+        );
+        assert!(serde_json::from_str::<Timestamp>("-1").is_err());
+        assert!(serde_json::from_str::<Timestamp>("\"1\"").is_err());
+    }
+}
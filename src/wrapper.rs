@@ -14,6 +14,11 @@
 // along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(any(test, feature = "std", feature = "alloc"))]
+use core::mem::ManuallyDrop;
+
 /// Trait defining wrapped types ("newtypes" in rust terminology). Wrapped
 /// types are used for allowing implemeting foreign traits to foreign types:
 /// <https://doc.rust-lang.org/stable/rust-by-example/generics/new_types.html>
@@ -22,9 +27,54 @@
 /// and deconstruct newtype. It also serves as a marker trait for newtypes.
 ///
 /// The trait works well with `#[derive(Wrapper)]` from `amplify_derive` crate
+///
+/// # Combinators
+///
+/// Provided `map`, `map_ref` and `zip_map` methods (and `map_mut` from
+/// [`WrapperMut`]) allow to operate on the inner data without manual
+/// unwrapping and re-wrapping of the newtype:
+///
+/// ```
+/// # #[macro_use] extern crate amplify;
+/// use amplify::{Wrapper, WrapperMut};
+///
+/// #[derive(Wrapper, Clone, PartialEq, Eq, Debug, From)]
+/// #[wrapper_mut]
+/// struct Sats(u64);
+///
+/// let sats = Sats::from_inner(1000);
+/// assert_eq!(sats.map_ref(|value| value * 2), 2000);
+///
+/// let sats = sats.map(|value| value / 10);
+/// assert_eq!(sats, Sats(100));
+///
+/// let sats = sats.zip_map(Sats(50), |a, b| a + b);
+/// assert_eq!(sats, Sats(150));
+///
+/// let mut sats = sats;
+/// let old = sats.map_mut(|value| std::mem::replace(value, 0));
+/// assert_eq!(old, 150);
+/// assert_eq!(sats, Sats(0));
+/// ```
+///
+/// # Serde
+///
+/// With `serde` feature derived wrappers may forward serialization to the
+/// inner type, which doesn't require a direct dependency on `serde` crate:
+///
+#[cfg_attr(feature = "serde", doc = "```")]
+#[cfg_attr(not(feature = "serde"), doc = "```ignore")]
+/// # #[macro_use] extern crate amplify;
+/// #[derive(Wrapper, Copy, Clone, PartialEq, Eq, Debug, From)]
+/// #[wrapper(Serialize, Deserialize)]
+/// struct Sats(u64);
+///
+/// assert_eq!(serde_json::to_string(&Sats(1000)).unwrap(), "1000");
+/// assert_eq!(serde_json::from_str::<Sats>("1000").unwrap(), Sats(1000));
+/// ```
 pub trait Wrapper {
     /// Inner type wrapped by the current newtype
-    type Inner: Clone;
+    type Inner;
 
     /// Instantiates wrapper type with the inner data
     fn from_inner(inner: Self::Inner) -> Self;
@@ -32,13 +82,26 @@ pub trait Wrapper {
     /// Returns reference to the inner representation for the wrapper type
     fn as_inner(&self) -> &Self::Inner;
 
-    /// Returns a mutable reference to the inner representation for the wrapper
-    /// type
-    fn as_inner_mut(&mut self) -> &mut Self::Inner;
+    /// Returns a mutable reference to the inner representation for the
+    /// wrapper type.
+    ///
+    /// Kept for compatibility with the code written before [`WrapperMut`]
+    /// was introduced; calls [`WrapperMut::as_inner_mut`].
+    #[deprecated(note = "use `WrapperMut::as_inner_mut` instead")]
+    #[inline]
+    fn as_inner_mut(&mut self) -> &mut Self::Inner
+    where
+        Self: WrapperMut,
+    {
+        WrapperMut::as_inner_mut(self)
+    }
 
     /// Clones inner data of the wrapped type and return them
     #[inline]
-    fn to_inner(&self) -> Self::Inner {
+    fn to_inner(&self) -> Self::Inner
+    where
+        Self::Inner: Clone,
+    {
         self.as_inner().clone()
     }
 
@@ -53,6 +116,686 @@ pub trait Wrapper {
     {
         Self::from_inner(*self.as_inner())
     }
+
+    /// Constructs new wrapper from the inner data transformed by the provided
+    /// function
+    #[inline]
+    fn map(self, f: impl FnOnce(Self::Inner) -> Self::Inner) -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_inner(f(self.into_inner()))
+    }
+
+    /// Computes value from the reference to the inner data
+    #[inline]
+    fn map_ref<R>(&self, f: impl FnOnce(&Self::Inner) -> R) -> R {
+        f(self.as_inner())
+    }
+
+    /// Constructs new wrapper from the inner data of two wrappers of the same
+    /// type, combined by the provided function
+    #[inline]
+    fn zip_map(self, other: Self, f: impl FnOnce(Self::Inner, Self::Inner) -> Self::Inner) -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_inner(f(self.into_inner(), other.into_inner()))
+    }
+}
+
+/// Trait allowing mutable reference borrowing for the wrapped inner type.
+///
+/// The trait is separated from [`Wrapper`] so that wrappers which must keep
+/// invariants over the inner data (like hashes) may provide read-only access
+/// only. It works well with `#[derive(Wrapper)]` from `amplify_derive` crate
+/// when `#[wrapper(DerefMut, AsMut, BorrowMut)]` or `#[wrapper_mut]`
+/// attributes are used; without them the derived wrapper does not implement
+/// `WrapperMut`.
+///
+/// # Migration
+///
+/// Previously `as_inner_mut` was a method of [`Wrapper`]. Derived wrappers
+/// need `#[wrapper_mut]` attribute to keep providing it. The method is still
+/// available from [`Wrapper`] as a deprecated alias, so the existing code
+/// importing only [`Wrapper`] compiles with a deprecation warning. Since with
+/// both traits in scope `as_inner_mut` method calls are ambiguous, code using
+/// [`Wrapper`] methods as well should call `WrapperMut::as_inner_mut`
+/// explicitly:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify;
+/// #![deny(deprecated)]
+/// use amplify::Wrapper;
+///
+/// #[derive(Wrapper, Clone, PartialEq, Eq, Debug, From)]
+/// #[wrapper_mut]
+/// struct Buffer(Vec<u8>);
+///
+/// let mut buffer = Buffer::from_inner(vec![1]);
+/// buffer.as_inner_mut().push(2);
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate amplify;
+/// use amplify::{Wrapper, WrapperMut};
+///
+/// #[derive(Wrapper, Clone, PartialEq, Eq, Debug, From)]
+/// #[wrapper_mut]
+/// struct Buffer(Vec<u8>);
+///
+/// /// Lowercase ASCII string: mutable access would allow to break the invariant
+/// #[derive(Wrapper, Clone, PartialEq, Eq, Debug, From)]
+/// struct Lowercase(String);
+///
+/// impl Lowercase {
+///     pub fn with(s: &str) -> Self {
+///         Lowercase::from_inner(s.to_ascii_lowercase())
+///     }
+/// }
+///
+/// let mut buffer = Buffer::from_inner(vec![1]);
+/// WrapperMut::as_inner_mut(&mut buffer).push(2);
+/// assert_eq!(buffer.into_inner(), vec![1, 2]);
+///
+/// let name = Lowercase::with("Amplify");
+/// assert_eq!(name.as_inner(), "amplify");
+/// ```
+///
+/// Wrapper which does not implement `WrapperMut` gives no mutable access to
+/// the inner data:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate amplify;
+/// use amplify::{Wrapper, WrapperMut};
+///
+/// #[derive(Wrapper, Clone, PartialEq, Eq, Debug, From)]
+/// struct Lowercase(String);
+///
+/// let mut name = Lowercase::from_inner(s!("amplify"));
+/// name.as_inner_mut().push_str("UPPERCASE");
+/// ```
+pub trait WrapperMut: Wrapper {
+    /// Returns a mutable reference to the inner representation for the wrapper
+    /// type
+    fn as_inner_mut(&mut self) -> &mut Self::Inner;
+
+    /// Computes value from the mutable reference to the inner data, which
+    /// may be modified by the provided function
+    #[inline]
+    fn map_mut<R>(&mut self, f: impl FnOnce(&mut Self::Inner) -> R) -> R {
+        f(WrapperMut::as_inner_mut(self))
+    }
+}
+
+/// Marker trait for wrappers having exactly the same memory layout as their
+/// inner type, which allows to convert a reference to the inner data into a
+/// reference to the wrapper without copying the data.
+///
+/// `#[derive(Wrapper)]` from `amplify_derive` crate implements this trait for
+/// all wrappers declared with `#[repr(transparent)]` attribute, for which all
+/// fields other than the wrapped one are [`PhantomData`](core::marker::PhantomData).
+///
+/// Blanket implementations of [`AsRef`] and [`Borrow`](core::borrow::Borrow)
+/// for all wrappers are not possible, since coherence rules prohibit
+/// implementing foreign traits for type parameters; thus these traits are
+/// still implemented by the derive macro for each of the wrappers.
+///
+/// # Safety
+///
+/// The trait must be implemented only for structures declared with
+/// `#[repr(transparent)]` attribute, for which the field returned by
+/// [`Wrapper::as_inner`] is of type [`Wrapper::Inner`] and all other fields
+/// are zero-sized. Such structures have the same size, alignment and ABI as
+/// their inner type, so a valid reference to the inner type is also a valid
+/// reference to the wrapper type. The wrapper must not put any invariants on
+/// the inner data which are not upheld by all possible values of the inner
+/// type, since [`WrapperTransparent::from_inner_ref`] bypasses
+/// [`Wrapper::from_inner`].
+///
+/// The same layout guarantee allows casting slices and vectors of the inner
+/// type into slices and vectors of the wrappers (and back) without copying
+/// or reallocating the data: elements of both types have the same size and
+/// alignment, so a slice has the same length and memory span, and a vector
+/// allocation has the same layout for both element types.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate amplify;
+/// use amplify::{Wrapper, WrapperTransparent};
+///
+/// #[derive(Wrapper, Copy, Clone, PartialEq, Eq, Debug, From)]
+/// #[repr(transparent)]
+/// struct Sats(u64);
+///
+/// let amounts = [1000u64, 2000];
+/// let sats: &Sats = Sats::from_inner_ref(&amounts[1]);
+/// assert_eq!(sats, &Sats(2000));
+///
+/// let sats: &[Sats] = Sats::wrap_slice(&amounts);
+/// assert_eq!(sats, &[Sats(1000), Sats(2000)]);
+/// assert_eq!(Sats::peel_slice(sats), &amounts);
+///
+/// let sats: Vec<Sats> = Sats::wrap_vec(vec![1, 2, 3]);
+/// assert_eq!(Sats::peel_vec(sats), vec![1, 2, 3]);
+/// ```
+pub unsafe trait WrapperTransparent: Wrapper + Sized {
+    /// Converts reference to the inner data into a reference to the wrapper
+    /// without copying the data
+    #[inline]
+    fn from_inner_ref(inner: &Self::Inner) -> &Self {
+        // Safety: the trait guarantees that `Self` is `#[repr(transparent)]`
+        // over `Self::Inner`, so both types have the same layout and the
+        // pointer is properly aligned and valid for the same lifetime
+        unsafe { &*(inner as *const Self::Inner as *const Self) }
+    }
+
+    /// Converts slice of the inner data into a slice of wrappers without
+    /// copying the data
+    #[inline]
+    fn wrap_slice(inner: &[Self::Inner]) -> &[Self] {
+        // Safety: `Self` and `Self::Inner` have the same size and alignment,
+        // so the slice has the same length and memory span for both types;
+        // the pointer of an empty slice is non-null and properly aligned
+        unsafe { core::slice::from_raw_parts(inner.as_ptr() as *const Self, inner.len()) }
+    }
+
+    /// Converts mutable slice of the inner data into a mutable slice of
+    /// wrappers without copying the data
+    #[inline]
+    fn wrap_slice_mut(inner: &mut [Self::Inner]) -> &mut [Self] {
+        // Safety: see `wrap_slice`; the returned slice exclusively borrows the
+        // source slice for its whole lifetime
+        unsafe { core::slice::from_raw_parts_mut(inner.as_mut_ptr() as *mut Self, inner.len()) }
+    }
+
+    /// Converts slice of wrappers into a slice of the inner data without
+    /// copying the data
+    #[inline]
+    fn peel_slice(wrappers: &[Self]) -> &[Self::Inner] {
+        // Safety: see `wrap_slice`
+        unsafe {
+            core::slice::from_raw_parts(wrappers.as_ptr() as *const Self::Inner, wrappers.len())
+        }
+    }
+
+    /// Converts mutable slice of wrappers into a mutable slice of the inner
+    /// data without copying the data. Requires the wrapper to provide mutable
+    /// access to the inner data with [`WrapperMut`].
+    #[inline]
+    fn peel_slice_mut(wrappers: &mut [Self]) -> &mut [Self::Inner]
+    where
+        Self: WrapperMut,
+    {
+        // Safety: see `wrap_slice_mut`
+        unsafe {
+            core::slice::from_raw_parts_mut(
+                wrappers.as_mut_ptr() as *mut Self::Inner,
+                wrappers.len(),
+            )
+        }
+    }
+
+    /// Converts vector of the inner data into a vector of wrappers, reusing
+    /// the same allocation
+    #[cfg(any(test, feature = "std", feature = "alloc"))]
+    #[inline]
+    fn wrap_vec(inner: Vec<Self::Inner>) -> Vec<Self> {
+        let mut inner = ManuallyDrop::new(inner);
+        // Safety: `Self` and `Self::Inner` have the same size and alignment,
+        // so the allocation made for `capacity` items of `Self::Inner` has
+        // exactly the layout required for `capacity` items of `Self`, and the
+        // first `len` items are initialized. The original vector is not
+        // dropped, so the allocation is owned only by the returned vector.
+        unsafe {
+            Vec::from_raw_parts(
+                inner.as_mut_ptr() as *mut Self,
+                inner.len(),
+                inner.capacity(),
+            )
+        }
+    }
+
+    /// Converts vector of wrappers into a vector of the inner data, reusing
+    /// the same allocation
+    #[cfg(any(test, feature = "std", feature = "alloc"))]
+    #[inline]
+    fn peel_vec(wrappers: Vec<Self>) -> Vec<Self::Inner> {
+        let mut wrappers = ManuallyDrop::new(wrappers);
+        // Safety: see `wrap_vec`
+        unsafe {
+            Vec::from_raw_parts(
+                wrappers.as_mut_ptr() as *mut Self::Inner,
+                wrappers.len(),
+                wrappers.capacity(),
+            )
+        }
+    }
+}
+
+/// Declares a newtype wrapper together with [`Wrapper`] trait implementation
+/// and forwarded trait implementations, without the use of procedural macros.
+///
+/// The macro is a declarative counterpart of `#[derive(Wrapper)]` for crates
+/// avoiding proc-macro dependencies. It defines the structure (unless the
+/// `impl Wrapper for` form is used for an existing structure) and implements
+/// for it [`Wrapper`], `From` conversions from and into the inner type,
+/// `Deref`, `AsRef` and `Borrow` for the inner type.
+///
+/// Other traits are forwarded to the inner type when listed in the optional
+/// `impl` clause:
+/// - `WrapperMut`;
+/// - formatting: `Display`, `Debug`, `Octal`, `Binary`, `LowerHex`,
+///   `UpperHex`, `LowerExp`, `UpperExp`;
+/// - parsing: `FromStr`, `FromHex` (requires `hex` feature);
+/// - byte array conversions: `RawArray`;
+/// - mutable access: `AsMut`, `BorrowMut`, `DerefMut`, `BorrowSlice` (for
+///   `Borrow<[u8]>`);
+/// - indexing: `Index`, `IndexMut` (both by `usize`), `IndexRange`,
+///   `IndexFrom`, `IndexTo`, `IndexInclusive`, `IndexFull`, `RangeOps` and
+///   `RangeOpsMut` (for all range types);
+/// - arithmetics: `Neg`, `Not`, `Add`, `Sub`, `Mul`, `Div`, `Rem`, `Shl`,
+///   `Shr`, `BitAnd`, `BitOr`, `BitXor` and their `*Assign` counterparts.
+///
+/// The grammar is restricted to tuple structures with a single field, which
+/// may have generic type parameters (but not lifetimes or const generics),
+/// each with at most a single trait bound. The inner type can't be a bare
+/// type parameter, since `From` conversion into it violates coherence rules.
+///
+/// ```
+/// # #[macro_use] extern crate amplify;
+/// use amplify::Wrapper;
+///
+/// wrapper! {
+///     /// Amount in satoshis
+///     #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+///     pub struct Sats(u64);
+///     impl Add, Sub, AddAssign, Display, FromStr;
+/// }
+///
+/// let mut sats = Sats::from_inner(1000) + Sats::from(500);
+/// sats += "10".parse().unwrap();
+/// assert_eq!(*sats, 1510);
+/// assert_eq!(sats.to_string(), "1510");
+/// assert_eq!(u64::from(sats - Sats(10)), 1500);
+///
+/// wrapper! {
+///     #[derive(Clone, PartialEq, Eq, Debug)]
+///     struct List<T: Clone>(Vec<T>);
+///     impl Index, IndexFrom;
+/// }
+///
+/// let list = List::from(vec![1, 2, 3]);
+/// assert_eq!(list[1], 2);
+/// assert_eq!(&list[1..], &[2, 3]);
+/// assert_eq!(list.len(), 3);
+///
+/// #[derive(Clone, Debug)]
+/// struct Name(String);
+/// wrapper! {
+///     impl Wrapper for Name(String);
+///     impl Display;
+/// }
+/// assert_eq!(Name::from_inner(s!("Satoshi")).to_string(), "Satoshi");
+/// ```
+#[macro_export]
+macro_rules! wrapper {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident $(< $($gen:ident $(: $bound:path)?),+ >)? ($fvis:vis $inner:ty);
+        $(impl $($trait:ident),+ $(,)?;)?
+    ) => {
+        $(#[$attr])*
+        $vis struct $name $(< $($gen $(: $bound)?),+ >)? ($fvis $inner);
+
+        $crate::wrapper! {
+            impl Wrapper for $name $(< $($gen $(: $bound)?),+ >)? ($inner);
+            $(impl $($trait),+;)?
+        }
+    };
+
+    (
+        impl Wrapper for $name:ident $(< $($gen:ident $(: $bound:path)?),+ >)? ($inner:ty);
+        $(impl $($trait:ident),+ $(,)?;)?
+    ) => {
+        impl $(< $($gen $(: $bound)?),+ >)? $crate::Wrapper for $name $(< $($gen),+ >)? {
+            type Inner = $inner;
+
+            #[inline]
+            fn from_inner(inner: Self::Inner) -> Self {
+                $name(inner)
+            }
+
+            #[inline]
+            fn as_inner(&self) -> &Self::Inner {
+                &self.0
+            }
+
+            #[inline]
+            fn into_inner(self) -> Self::Inner {
+                self.0
+            }
+        }
+
+        impl $(< $($gen $(: $bound)?),+ >)? ::core::convert::From<$inner> for $name $(< $($gen),+ >)? {
+            #[inline]
+            fn from(inner: $inner) -> Self {
+                $name(inner)
+            }
+        }
+
+        impl $(< $($gen $(: $bound)?),+ >)? ::core::convert::From<$name $(< $($gen),+ >)?> for $inner {
+            #[inline]
+            fn from(wrapped: $name $(< $($gen),+ >)?) -> Self {
+                wrapped.0
+            }
+        }
+
+        impl $(< $($gen $(: $bound)?),+ >)? ::core::convert::AsRef<$inner> for $name $(< $($gen),+ >)? {
+            #[inline]
+            fn as_ref(&self) -> &$inner {
+                &self.0
+            }
+        }
+
+        impl $(< $($gen $(: $bound)?),+ >)? ::core::borrow::Borrow<$inner> for $name $(< $($gen),+ >)? {
+            #[inline]
+            fn borrow(&self) -> &$inner {
+                &self.0
+            }
+        }
+
+        impl $(< $($gen $(: $bound)?),+ >)? ::core::ops::Deref for $name $(< $($gen),+ >)? {
+            type Target = $inner;
+
+            #[inline]
+            fn deref(&self) -> &$inner {
+                &self.0
+            }
+        }
+
+        $crate::__wrapper_impl! {
+            @traits [$($($trait)+)?]
+            $name [$($($gen $(: $bound)?),+)?] [$($($gen),+)?] $inner
+        }
+    };
+}
+
+/// Implements traits forwarded by [`wrapper!`] macro; not a part of public API
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __wrapper_impl {
+    (@traits [] $($rest:tt)*) => {};
+    (@traits [$trait:ident $($traits:ident)*] $name:ident [$($ig:tt)*] [$($tg:tt)*] $inner:ty) => {
+        $crate::__wrapper_impl!(@$trait $name [$($ig)*] [$($tg)*] $inner);
+        $crate::__wrapper_impl!(@traits [$($traits)*] $name [$($ig)*] [$($tg)*] $inner);
+    };
+
+    (@WrapperMut $name:ident [$($ig:tt)*] [$($tg:tt)*] $inner:ty) => {
+        impl<$($ig)*> $crate::WrapperMut for $name<$($tg)*> {
+            #[inline]
+            fn as_inner_mut(&mut self) -> &mut $inner {
+                &mut self.0
+            }
+        }
+    };
+
+    (@Display $($rest:tt)*) => { $crate::__wrapper_impl!(@fmt Display $($rest)*); };
+    (@Debug $($rest:tt)*) => { $crate::__wrapper_impl!(@fmt Debug $($rest)*); };
+    (@Octal $($rest:tt)*) => { $crate::__wrapper_impl!(@fmt Octal $($rest)*); };
+    (@Binary $($rest:tt)*) => { $crate::__wrapper_impl!(@fmt Binary $($rest)*); };
+    (@LowerHex $($rest:tt)*) => { $crate::__wrapper_impl!(@fmt LowerHex $($rest)*); };
+    (@UpperHex $($rest:tt)*) => { $crate::__wrapper_impl!(@fmt UpperHex $($rest)*); };
+    (@LowerExp $($rest:tt)*) => { $crate::__wrapper_impl!(@fmt LowerExp $($rest)*); };
+    (@UpperExp $($rest:tt)*) => { $crate::__wrapper_impl!(@fmt UpperExp $($rest)*); };
+    (@fmt $trait:ident $name:ident [$($ig:tt)*] [$($tg:tt)*] $inner:ty) => {
+        impl<$($ig)*> ::core::fmt::$trait for $name<$($tg)*>
+        where
+            $inner: ::core::fmt::$trait,
+        {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::$trait::fmt(&self.0, f)
+            }
+        }
+    };
+
+    (@FromStr $name:ident [$($ig:tt)*] [$($tg:tt)*] $inner:ty) => {
+        impl<$($ig)*> ::core::str::FromStr for $name<$($tg)*>
+        where
+            $inner: ::core::str::FromStr,
+        {
+            type Err = <$inner as ::core::str::FromStr>::Err;
+
+            #[inline]
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                <$inner as ::core::str::FromStr>::from_str(s).map($name)
+            }
+        }
+    };
+    (@FromHex $name:ident [$($ig:tt)*] [$($tg:tt)*] $inner:ty) => {
+        impl<$($ig)*> $crate::hex::FromHex for $name<$($tg)*>
+        where
+            $inner: $crate::hex::FromHex,
+        {
+            #[inline]
+            fn from_byte_iter<I>(iter: I) -> Result<Self, $crate::hex::Error>
+            where
+                I: Iterator<Item = Result<u8, $crate::hex::Error>>
+                    + ExactSizeIterator
+                    + DoubleEndedIterator,
+            {
+                <$inner as $crate::hex::FromHex>::from_byte_iter(iter).map($name)
+            }
+        }
+    };
+    (@RawArray $name:ident [$($ig:tt)*] [$($tg:tt)*] $inner:ty) => {
+        impl<$($ig)*> $crate::RawArray for $name<$($tg)*>
+        where
+            $inner: $crate::RawArray,
+        {
+            type Array = <$inner as $crate::RawArray>::Array;
+
+            #[inline]
+            fn from_raw_array(val: Self::Array) -> Self {
+                $name(<$inner as $crate::RawArray>::from_raw_array(val))
+            }
+
+            #[inline]
+            fn to_raw_array(&self) -> Self::Array {
+                <$inner as $crate::RawArray>::to_raw_array(&self.0)
+            }
+        }
+    };
+
+    (@AsMut $name:ident [$($ig:tt)*] [$($tg:tt)*] $inner:ty) => {
+        impl<$($ig)*> ::core::convert::AsMut<$inner> for $name<$($tg)*> {
+            #[inline]
+            fn as_mut(&mut self) -> &mut $inner {
+                &mut self.0
+            }
+        }
+    };
+    (@BorrowMut $name:ident [$($ig:tt)*] [$($tg:tt)*] $inner:ty) => {
+        impl<$($ig)*> ::core::borrow::BorrowMut<$inner> for $name<$($tg)*> {
+            #[inline]
+            fn borrow_mut(&mut self) -> &mut $inner {
+                &mut self.0
+            }
+        }
+    };
+    (@DerefMut $name:ident [$($ig:tt)*] [$($tg:tt)*] $inner:ty) => {
+        impl<$($ig)*> ::core::ops::DerefMut for $name<$($tg)*> {
+            #[inline]
+            fn deref_mut(&mut self) -> &mut $inner {
+                &mut self.0
+            }
+        }
+    };
+    (@BorrowSlice $name:ident [$($ig:tt)*] [$($tg:tt)*] $inner:ty) => {
+        impl<$($ig)*> ::core::borrow::Borrow<[u8]> for $name<$($tg)*>
+        where
+            $inner: ::core::borrow::Borrow<[u8]>,
+        {
+            #[inline]
+            fn borrow(&self) -> &[u8] {
+                ::core::borrow::Borrow::<[u8]>::borrow(&self.0)
+            }
+        }
+    };
+
+    (@Index $($rest:tt)*) => {
+        $crate::__wrapper_impl!(@index [usize] $($rest)*);
+    };
+    (@IndexMut $($rest:tt)*) => {
+        $crate::__wrapper_impl!(@index_mut [usize] $($rest)*);
+    };
+    (@IndexRange $($rest:tt)*) => {
+        $crate::__wrapper_impl!(@index [::core::ops::Range<usize>] $($rest)*);
+    };
+    (@IndexFrom $($rest:tt)*) => {
+        $crate::__wrapper_impl!(@index [::core::ops::RangeFrom<usize>] $($rest)*);
+    };
+    (@IndexTo $($rest:tt)*) => {
+        $crate::__wrapper_impl!(@index [::core::ops::RangeTo<usize>] $($rest)*);
+    };
+    (@IndexInclusive $($rest:tt)*) => {
+        $crate::__wrapper_impl!(@index [::core::ops::RangeInclusive<usize>] $($rest)*);
+    };
+    (@IndexFull $($rest:tt)*) => {
+        $crate::__wrapper_impl!(@index [::core::ops::RangeFull] $($rest)*);
+    };
+    (@RangeOps $($rest:tt)*) => {
+        $crate::__wrapper_impl!(@index [::core::ops::Range<usize>] $($rest)*);
+        $crate::__wrapper_impl!(@index [::core::ops::RangeFrom<usize>] $($rest)*);
+        $crate::__wrapper_impl!(@index [::core::ops::RangeTo<usize>] $($rest)*);
+        $crate::__wrapper_impl!(@index [::core::ops::RangeFull] $($rest)*);
+        $crate::__wrapper_impl!(@index [::core::ops::RangeInclusive<usize>] $($rest)*);
+        $crate::__wrapper_impl!(@index [::core::ops::RangeToInclusive<usize>] $($rest)*);
+    };
+    (@RangeOpsMut $($rest:tt)*) => {
+        $crate::__wrapper_impl!(@index_mut [::core::ops::Range<usize>] $($rest)*);
+        $crate::__wrapper_impl!(@index_mut [::core::ops::RangeFrom<usize>] $($rest)*);
+        $crate::__wrapper_impl!(@index_mut [::core::ops::RangeTo<usize>] $($rest)*);
+        $crate::__wrapper_impl!(@index_mut [::core::ops::RangeFull] $($rest)*);
+        $crate::__wrapper_impl!(@index_mut [::core::ops::RangeInclusive<usize>] $($rest)*);
+        $crate::__wrapper_impl!(@index_mut [::core::ops::RangeToInclusive<usize>] $($rest)*);
+    };
+    (@index [$idx:ty] $name:ident [$($ig:tt)*] [$($tg:tt)*] $inner:ty) => {
+        impl<$($ig)*> ::core::ops::Index<$idx> for $name<$($tg)*>
+        where
+            $inner: ::core::ops::Index<$idx>,
+        {
+            type Output = <$inner as ::core::ops::Index<$idx>>::Output;
+
+            #[inline]
+            fn index(&self, index: $idx) -> &Self::Output {
+                ::core::ops::Index::index(&self.0, index)
+            }
+        }
+    };
+    (@index_mut [$idx:ty] $name:ident [$($ig:tt)*] [$($tg:tt)*] $inner:ty) => {
+        impl<$($ig)*> ::core::ops::IndexMut<$idx> for $name<$($tg)*>
+        where
+            $inner: ::core::ops::IndexMut<$idx>,
+        {
+            #[inline]
+            fn index_mut(&mut self, index: $idx) -> &mut Self::Output {
+                ::core::ops::IndexMut::index_mut(&mut self.0, index)
+            }
+        }
+    };
+
+    (@Neg $($rest:tt)*) => { $crate::__wrapper_impl!(@unop Neg neg $($rest)*); };
+    (@Not $($rest:tt)*) => { $crate::__wrapper_impl!(@unop Not not $($rest)*); };
+    (@unop $trait:ident $fn:ident $name:ident [$($ig:tt)*] [$($tg:tt)*] $inner:ty) => {
+        impl<$($ig)*> ::core::ops::$trait for $name<$($tg)*>
+        where
+            $inner: ::core::ops::$trait<Output = $inner>,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(self) -> Self {
+                $name(::core::ops::$trait::$fn(self.0))
+            }
+        }
+    };
+
+    (@Add $($rest:tt)*) => { $crate::__wrapper_impl!(@binop Add add $($rest)*); };
+    (@Sub $($rest:tt)*) => { $crate::__wrapper_impl!(@binop Sub sub $($rest)*); };
+    (@Mul $($rest:tt)*) => { $crate::__wrapper_impl!(@binop Mul mul $($rest)*); };
+    (@Div $($rest:tt)*) => { $crate::__wrapper_impl!(@binop Div div $($rest)*); };
+    (@Rem $($rest:tt)*) => { $crate::__wrapper_impl!(@binop Rem rem $($rest)*); };
+    (@BitAnd $($rest:tt)*) => { $crate::__wrapper_impl!(@binop BitAnd bitand $($rest)*); };
+    (@BitOr $($rest:tt)*) => { $crate::__wrapper_impl!(@binop BitOr bitor $($rest)*); };
+    (@BitXor $($rest:tt)*) => { $crate::__wrapper_impl!(@binop BitXor bitxor $($rest)*); };
+    (@binop $trait:ident $fn:ident $name:ident [$($ig:tt)*] [$($tg:tt)*] $inner:ty) => {
+        impl<$($ig)*> ::core::ops::$trait for $name<$($tg)*>
+        where
+            $inner: ::core::ops::$trait<Output = $inner>,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(self, rhs: Self) -> Self {
+                $name(::core::ops::$trait::$fn(self.0, rhs.0))
+            }
+        }
+    };
+
+    (@AddAssign $($rest:tt)*) => { $crate::__wrapper_impl!(@assignop AddAssign add_assign $($rest)*); };
+    (@SubAssign $($rest:tt)*) => { $crate::__wrapper_impl!(@assignop SubAssign sub_assign $($rest)*); };
+    (@MulAssign $($rest:tt)*) => { $crate::__wrapper_impl!(@assignop MulAssign mul_assign $($rest)*); };
+    (@DivAssign $($rest:tt)*) => { $crate::__wrapper_impl!(@assignop DivAssign div_assign $($rest)*); };
+    (@RemAssign $($rest:tt)*) => { $crate::__wrapper_impl!(@assignop RemAssign rem_assign $($rest)*); };
+    (@BitAndAssign $($rest:tt)*) => { $crate::__wrapper_impl!(@assignop BitAndAssign bitand_assign $($rest)*); };
+    (@BitOrAssign $($rest:tt)*) => { $crate::__wrapper_impl!(@assignop BitOrAssign bitor_assign $($rest)*); };
+    (@BitXorAssign $($rest:tt)*) => { $crate::__wrapper_impl!(@assignop BitXorAssign bitxor_assign $($rest)*); };
+    (@assignop $trait:ident $fn:ident $name:ident [$($ig:tt)*] [$($tg:tt)*] $inner:ty) => {
+        impl<$($ig)*> ::core::ops::$trait for $name<$($tg)*>
+        where
+            $inner: ::core::ops::$trait,
+        {
+            #[inline]
+            fn $fn(&mut self, rhs: Self) {
+                ::core::ops::$trait::$fn(&mut self.0, rhs.0)
+            }
+        }
+    };
+
+    (@Shl $($rest:tt)*) => { $crate::__wrapper_impl!(@shiftop Shl shl $($rest)*); };
+    (@Shr $($rest:tt)*) => { $crate::__wrapper_impl!(@shiftop Shr shr $($rest)*); };
+    (@shiftop $trait:ident $fn:ident $name:ident [$($ig:tt)*] [$($tg:tt)*] $inner:ty) => {
+        impl<__Rhs, $($ig)*> ::core::ops::$trait<__Rhs> for $name<$($tg)*>
+        where
+            $inner: ::core::ops::$trait<__Rhs, Output = $inner>,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(self, rhs: __Rhs) -> Self {
+                $name(::core::ops::$trait::$fn(self.0, rhs))
+            }
+        }
+    };
+
+    (@ShlAssign $($rest:tt)*) => { $crate::__wrapper_impl!(@shiftassignop ShlAssign shl_assign $($rest)*); };
+    (@ShrAssign $($rest:tt)*) => { $crate::__wrapper_impl!(@shiftassignop ShrAssign shr_assign $($rest)*); };
+    (@shiftassignop $trait:ident $fn:ident $name:ident [$($ig:tt)*] [$($tg:tt)*] $inner:ty) => {
+        impl<__Rhs, $($ig)*> ::core::ops::$trait<__Rhs> for $name<$($tg)*>
+        where
+            $inner: ::core::ops::$trait<__Rhs>,
+        {
+            #[inline]
+            fn $fn(&mut self, rhs: __Rhs) {
+                ::core::ops::$trait::$fn(&mut self.0, rhs)
+            }
+        }
+    };
 }
 
 #[cfg(test)]
@@ -73,15 +816,17 @@ mod test {
             &self.0
         }
 
-        fn as_inner_mut(&mut self) -> &mut Self::Inner {
-            &mut self.0
-        }
-
         fn into_inner(self) -> Self::Inner {
             self.0
         }
     }
 
+    impl WrapperMut for TestWrapper {
+        fn as_inner_mut(&mut self) -> &mut Self::Inner {
+            &mut self.0
+        }
+    }
+
     #[test]
     fn test_copy() {
         let item = TestWrapper::from_inner(5);
@@ -89,4 +834,283 @@ mod test {
         assert_eq!(item, copy);
         assert_eq!(copy.into_inner(), 5)
     }
+
+    #[test]
+    fn test_as_inner_mut() {
+        let mut item = TestWrapper::from_inner(5);
+        *WrapperMut::as_inner_mut(&mut item) += 1;
+        assert_eq!(item.into_inner(), 6)
+    }
+
+    mod deprecated {
+        use super::TestWrapper;
+        use crate::Wrapper;
+
+        #[test]
+        #[allow(deprecated)]
+        fn test_as_inner_mut() {
+            let mut item = TestWrapper::from_inner(5);
+            *item.as_inner_mut() += 1;
+            assert_eq!(item.into_inner(), 6)
+        }
+    }
+
+    /// Inner type which is neither `Clone` nor `Copy`, so the combinators
+    /// must move it through without cloning
+    #[derive(Eq, PartialEq, Debug)]
+    struct Unique(Vec<u8>);
+
+    #[derive(Eq, PartialEq, Debug)]
+    struct UniqueWrapper(Unique);
+
+    impl Wrapper for UniqueWrapper {
+        type Inner = Unique;
+
+        fn from_inner(inner: Self::Inner) -> Self {
+            Self(inner)
+        }
+
+        fn as_inner(&self) -> &Self::Inner {
+            &self.0
+        }
+
+        fn into_inner(self) -> Self::Inner {
+            self.0
+        }
+    }
+
+    impl WrapperMut for UniqueWrapper {
+        fn as_inner_mut(&mut self) -> &mut Self::Inner {
+            &mut self.0
+        }
+    }
+
+    #[test]
+    fn test_map() {
+        let mut data = Vec::with_capacity(4);
+        data.push(1);
+        let ptr = data.as_ptr();
+        let item = UniqueWrapper::from_inner(Unique(data)).map(|mut inner| {
+            inner.0.push(2);
+            inner
+        });
+        // the same allocation is moved through, not cloned
+        assert_eq!(item.as_inner().0.as_ptr(), ptr);
+        assert_eq!(item, UniqueWrapper(Unique(vec![1, 2])));
+        assert_eq!(TestWrapper(5).map(|inner| inner * 2), TestWrapper(10));
+    }
+
+    #[test]
+    fn test_map_ref() {
+        let item = UniqueWrapper::from_inner(Unique(vec![1, 2, 3]));
+        assert_eq!(item.map_ref(|inner| inner.0.len()), 3);
+        let inner = item.map_ref(|inner| inner as *const Unique);
+        assert_eq!(inner, item.as_inner() as *const Unique);
+    }
+
+    #[test]
+    fn test_map_mut() {
+        let mut item = UniqueWrapper::from_inner(Unique(vec![1]));
+        let len = item.map_mut(|inner| {
+            inner.0.push(2);
+            inner.0.len()
+        });
+        assert_eq!(len, 2);
+        assert_eq!(item.into_inner(), Unique(vec![1, 2]));
+    }
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[repr(transparent)]
+    struct TransparentWrapper(Unique, core::marker::PhantomData<u64>);
+
+    impl Wrapper for TransparentWrapper {
+        type Inner = Unique;
+
+        fn from_inner(inner: Self::Inner) -> Self {
+            Self(inner, core::marker::PhantomData)
+        }
+
+        fn as_inner(&self) -> &Self::Inner {
+            &self.0
+        }
+
+        fn into_inner(self) -> Self::Inner {
+            self.0
+        }
+    }
+
+    impl WrapperMut for TransparentWrapper {
+        fn as_inner_mut(&mut self) -> &mut Self::Inner {
+            &mut self.0
+        }
+    }
+
+    unsafe impl WrapperTransparent for TransparentWrapper {}
+
+    #[test]
+    fn test_from_inner_ref() {
+        let data = [Unique(vec![1, 2]), Unique(vec![3])];
+        let wrapper = TransparentWrapper::from_inner_ref(&data[1]);
+        assert_eq!(wrapper.as_inner(), &Unique(vec![3]));
+        assert_eq!(
+            wrapper.as_inner() as *const Unique,
+            &data[1] as *const Unique
+        );
+        assert_eq!(
+            TransparentWrapper::from_inner_ref(&data[0]).map_ref(|inner| inner.0.len()),
+            2
+        );
+    }
+
+    #[test]
+    fn test_wrap_slice() {
+        let empty: &[Unique] = &[];
+        assert!(TransparentWrapper::wrap_slice(empty).is_empty());
+        assert!(TransparentWrapper::peel_slice(TransparentWrapper::wrap_slice(empty)).is_empty());
+
+        let data = [Unique(vec![1]), Unique(vec![2, 3])];
+        let wrappers = TransparentWrapper::wrap_slice(&data);
+        assert_eq!(wrappers.len(), 2);
+        assert_eq!(wrappers[1].as_inner(), &Unique(vec![2, 3]));
+        assert_eq!(wrappers.as_ptr() as *const Unique, data.as_ptr());
+        let inner = TransparentWrapper::peel_slice(wrappers);
+        assert_eq!(inner, &data);
+        assert_eq!(inner.as_ptr(), data.as_ptr());
+    }
+
+    #[test]
+    fn test_wrap_slice_mut() {
+        let empty: &mut [Unique] = &mut [];
+        assert!(TransparentWrapper::wrap_slice_mut(empty).is_empty());
+
+        let mut data = [Unique(vec![1]), Unique(vec![2])];
+        {
+            let wrappers = TransparentWrapper::wrap_slice_mut(&mut data);
+            wrappers.swap(0, 1);
+            wrappers[0] = TransparentWrapper::from_inner(Unique(vec![4]));
+        }
+        assert_eq!(data, [Unique(vec![4]), Unique(vec![1])]);
+
+        let mut wrappers = [TransparentWrapper::from_inner(Unique(vec![1]))];
+        TransparentWrapper::peel_slice_mut(&mut wrappers)[0]
+            .0
+            .push(2);
+        assert_eq!(wrappers[0].as_inner(), &Unique(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_wrap_vec() {
+        let empty = TransparentWrapper::wrap_vec(Vec::new());
+        assert!(empty.is_empty());
+        assert!(TransparentWrapper::peel_vec(empty).is_empty());
+
+        let mut data = Vec::with_capacity(8);
+        data.push(Unique(vec![1]));
+        data.push(Unique(vec![2, 3]));
+        let ptr = data.as_ptr();
+
+        let mut wrappers = TransparentWrapper::wrap_vec(data);
+        assert_eq!(wrappers.len(), 2);
+        assert_eq!(wrappers.capacity(), 8);
+        assert_eq!(wrappers.as_ptr() as *const Unique, ptr);
+        assert_eq!(wrappers[1].as_inner(), &Unique(vec![2, 3]));
+
+        // spare capacity is used without reallocation
+        wrappers.push(TransparentWrapper::from_inner(Unique(vec![4])));
+        assert_eq!(wrappers.as_ptr() as *const Unique, ptr);
+
+        let inner = TransparentWrapper::peel_vec(wrappers);
+        assert_eq!(inner.capacity(), 8);
+        assert_eq!(inner.as_ptr(), ptr);
+        assert_eq!(
+            inner,
+            vec![Unique(vec![1]), Unique(vec![2, 3]), Unique(vec![4])]
+        );
+    }
+
+    #[test]
+    fn test_zip_map() {
+        let a = UniqueWrapper::from_inner(Unique(vec![1, 2]));
+        let b = UniqueWrapper::from_inner(Unique(vec![3]));
+        let ptr = a.as_inner().0.as_ptr();
+        let item = a.zip_map(b, |mut a, b| {
+            a.0.extend(b.0);
+            a
+        });
+        assert_eq!(item.as_inner().0.as_ptr(), ptr);
+        assert_eq!(item, UniqueWrapper(Unique(vec![1, 2, 3])));
+        assert_eq!(
+            TestWrapper(5).zip_map(TestWrapper(6), |a, b| a + b),
+            TestWrapper(11)
+        );
+    }
+
+    wrapper! {
+        /// Wrapper built with the declarative macro
+        #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        struct MacroAmount(i64);
+        impl WrapperMut, Display, Debug, LowerHex, FromStr, DerefMut, Neg, Not, Add, Sub, Mul,
+            Div, Rem, Shl, Shr, BitAnd, BitOr, BitXor, AddAssign, SubAssign, ShlAssign;
+    }
+
+    #[cfg(feature = "derive")]
+    #[derive(Wrapper, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+    #[amplify_crate(crate)]
+    #[wrapper(
+        Display, Debug, LowerHex, FromStr, DerefMut, Neg, Not, Add, Sub, Mul, Div, Rem
+    )]
+    #[wrapper(Shl, Shr, BitAnd, BitOr, BitXor, AddAssign, SubAssign, ShlAssign)]
+    struct DerivedAmount(i64);
+
+    macro_rules! check_amount {
+        ($ty:ident) => {
+            let amount = $ty::from_inner(12);
+            assert_eq!(*amount.as_inner(), 12);
+            assert_eq!(amount.into_inner(), 12);
+            assert_eq!(*amount, 12);
+            assert_eq!(amount.to_string(), "12");
+            assert_eq!(format!("{:?}", amount), "12");
+            assert_eq!(format!("{:#06x}", amount), "0x000c");
+            assert_eq!("-5".parse::<$ty>(), Ok($ty::from_inner(-5)));
+            assert!("x".parse::<$ty>().is_err());
+
+            let other = $ty::from_inner(5);
+            assert_eq!(-amount, $ty::from_inner(-12));
+            assert_eq!(!amount, $ty::from_inner(!12));
+            assert_eq!(amount + other, $ty::from_inner(17));
+            assert_eq!(amount - other, $ty::from_inner(7));
+            assert_eq!(amount * other, $ty::from_inner(60));
+            assert_eq!(amount / other, $ty::from_inner(2));
+            assert_eq!(amount % other, $ty::from_inner(2));
+            assert_eq!(amount << 2u8, $ty::from_inner(48));
+            assert_eq!(amount >> 2u32, $ty::from_inner(3));
+            assert_eq!(amount & other, $ty::from_inner(4));
+            assert_eq!(amount | other, $ty::from_inner(13));
+            assert_eq!(amount ^ other, $ty::from_inner(9));
+
+            let mut value = amount;
+            value += other;
+            value -= $ty::from_inner(1);
+            value <<= 1;
+            *WrapperMut::as_inner_mut(&mut value) += 1;
+            *value += 1;
+            assert_eq!(value, $ty::from_inner(34));
+            assert!(amount > other);
+            assert_eq!($ty::default(), $ty::from_inner(0));
+        };
+    }
+
+    #[test]
+    fn test_wrapper_macro() {
+        use core::borrow::Borrow;
+
+        check_amount!(MacroAmount);
+        #[cfg(feature = "derive")]
+        check_amount!(DerivedAmount);
+
+        let amount = MacroAmount::from(7);
+        assert_eq!(i64::from(amount), 7);
+        assert_eq!(amount.as_ref(), &7);
+        assert_eq!(Borrow::<i64>::borrow(&amount), &7);
+    }
 }
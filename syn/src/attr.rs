@@ -15,14 +15,23 @@
 
 use std::fmt::{Debug, Formatter, self};
 use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
 use syn::{
     Type, Path, Attribute, Meta, MetaNameValue, Lit, LitInt, LitStr, LitByteStr, LitFloat, LitChar,
-    LitBool,
+    LitBool, DeriveInput, Field, Variant,
 };
 use syn::parse_quote::ParseQuote;
-use syn::parse::Parser;
-
-use crate::{Error, ArgValue, ArgValueReq, AttrReq, MetaArg, MetaArgNameValue, MetaArgList};
+use syn::ext::IdentExt;
+use syn::parse::{ParseBuffer, Parser};
+use syn::punctuated::Punctuated;
+use quote::ToTokens;
+use proc_macro2::TokenStream;
+
+use crate::parsers::path_ident_name;
+use crate::{
+    Error, ArgValue, ArgValueReq, AttrReq, FusePolicy, PathMatch, MetaArg, MetaArgNameValue,
+    MetaArgList, ValueReq,
+};
 
 /// Internal structure representation of a proc macro attribute collected
 /// instances having some specific name (accessible via [`Attr::name()`]).
@@ -81,6 +90,10 @@ pub struct ParametrizedAttr {
 
     /// All attribute arguments that have form of `#[attr(ident = "literal")]`
     /// or `#[attr(ident = TypeName)]` mapped to their name identifiers.
+    ///
+    /// Names are stored without the raw identifier prefix, so both
+    /// `#[attr(r#type = ...)]` and `#[attr(type = ...)]` are accessible under
+    /// the `type` key.
     pub args: HashMap<String, ArgValue>,
 
     /// All attribute arguments that are paths or identifiers without any
@@ -202,6 +215,19 @@ impl Attr {
     pub fn type_value(&self) -> Result<Type, Error> {
         self.arg_value()?.type_value()
     }
+
+    /// Converts attribute of any form into [`ParametrizedAttr`], such that
+    /// both forms can be checked with the same [`AttrReq`]. Singular
+    /// attributes are converted with [`SingularAttr::into_parametrized`],
+    /// where `arg_name` is the name of the argument receiving the value of
+    /// `#[attr = value]` form.
+    #[inline]
+    pub fn normalize(self, arg_name: &str) -> ParametrizedAttr {
+        match self {
+            Attr::Singular(attr) => attr.into_parametrized(arg_name),
+            Attr::Parametrized(attr) => attr,
+        }
+    }
 }
 
 impl SingularAttr {
@@ -253,18 +279,24 @@ impl SingularAttr {
     }
 
     /// Constructs [`SingularAttr`] from a given [`syn::Attribute`] by parsing
-    /// its data. Accepts only attributes having form `#[attr(name = value)]`
-    /// and errors for other attribute types with [`Error::ArgNameMustBeIdent`]
-    /// and [`Error::SingularAttrRequired`]
+    /// its data. Accepts only attributes having form `#[attr]` or
+    /// `#[attr = value]` and errors for other attribute types with
+    /// [`Error::ArgNameMustBeIdent`] and [`Error::SingularAttrRequired`]
     pub fn from_attribute(attr: &Attribute) -> Result<Self, Error> {
         let ident = attr
             .path
             .get_ident()
             .ok_or(Error::ArgNameMustBeIdent)?
             .to_string();
+        SingularAttr::from_attribute_named(ident, attr)
+    }
+
+    /// Parses attribute data as in [`SingularAttr::from_attribute`], using
+    /// the provided name instead of the attribute path
+    pub(crate) fn from_attribute_named(ident: String, attr: &Attribute) -> Result<Self, Error> {
         match attr.parse_meta()? {
-            // `#[attr::path]` - unreachable: filtered in the code above
-            Meta::Path(_) => unreachable!(),
+            // `#[ident]`; `#[attr::path]` is filtered in the code above
+            Meta::Path(_) => Ok(SingularAttr::new(ident)),
             // `#[ident = lit]`
             Meta::NameValue(MetaNameValue { lit, .. }) => {
                 Ok(SingularAttr::with_literal(ident, lit))
@@ -357,6 +389,84 @@ impl SingularAttr {
         self.check(req)?;
         Ok(self)
     }
+
+    /// Returns attribute value converted into type `T`, or fails with
+    /// [`Error::ArgValueRequired`] if the attribute has no value. Values of a
+    /// wrong class fail with the conversion error, like
+    /// [`Error::ArgValueMustBeLiteral`].
+    pub fn value<T>(&self) -> Result<T, Error>
+    where
+        T: TryFrom<ArgValue, Error = Error>,
+    {
+        if self.value.is_none() {
+            return Err(Error::ArgValueRequired {
+                attr: self.name.clone(),
+                arg: self.name.clone(),
+            });
+        }
+        T::try_from(self.value.clone())
+    }
+
+    /// Returns attribute value converted into type `T`, or `None` if the
+    /// attribute has no value. Fails as [`SingularAttr::value`] on values of a
+    /// wrong class.
+    pub fn opt_value<T>(&self) -> Result<Option<T>, Error>
+    where
+        T: TryFrom<ArgValue, Error = Error>,
+    {
+        if self.value.is_none() {
+            return Ok(None);
+        }
+        self.value().map(Some)
+    }
+
+    /// Checks presence of the value against the requirements, assigning the
+    /// default value from [`ValueReq::Default`] to the attribute without a
+    /// value, and returns the value converted into type `T`, which checks the
+    /// value class. Returns `None` only if the value is absent and this is
+    /// allowed by the requirements.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::ArgValueRequired`] if the value is absent, while required;
+    /// - [`Error::ArgMustNotHaveValue`] if the value is present, while
+    ///   prohibited;
+    /// - conversion error, like [`Error::ArgValueMustBeLiteral`], if the value
+    ///   has a wrong class.
+    pub fn checked_value<T>(&mut self, req: &ValueReq) -> Result<Option<T>, Error>
+    where
+        T: TryFrom<ArgValue, Error = Error>,
+    {
+        match req {
+            ValueReq::Required if self.value.is_none() => {
+                return Err(Error::ArgValueRequired {
+                    attr: self.name.clone(),
+                    arg: self.name.clone(),
+                })
+            }
+            ValueReq::Prohibited if self.value.is_some() => {
+                return Err(Error::ArgMustNotHaveValue {
+                    attr: self.name.clone(),
+                    arg: self.name.clone(),
+                })
+            }
+            ValueReq::Default(value) if self.value.is_none() => self.value = value.clone(),
+            _ => {}
+        }
+        self.opt_value()
+    }
+
+    /// Converts the attribute into [`ParametrizedAttr`] with the same name.
+    /// Attribute of `#[attr = value]` form becomes `#[attr(arg_name = value)]`,
+    /// keeping the value (with its span) intact, and `#[attr]` becomes an
+    /// attribute without arguments.
+    pub fn into_parametrized(self, arg_name: &str) -> ParametrizedAttr {
+        let mut attr = ParametrizedAttr::new(self.name);
+        if self.value.is_some() {
+            attr.args.insert(arg_name.to_owned(), self.value);
+        }
+        attr
+    }
 }
 
 impl ParametrizedAttr {
@@ -386,6 +496,21 @@ impl ParametrizedAttr {
         Ok(me)
     }
 
+    /// Constructs [`ParametrizedAttr`] with the provided `name` from the
+    /// arguments of an attribute procedural macro.
+    ///
+    /// Attribute macros receive their arguments as a separate token stream
+    /// without the enclosing parentheses, i.e. `arg1, arg2 = value` for
+    /// `#[name(arg1, arg2 = value)]`. The arguments are parsed in the same way
+    /// as the arguments of [`syn::Attribute`]s (see
+    /// [`ParametrizedAttr::fuse`]), with errors reported as
+    /// [`Error::Parse`] spanning the offending tokens.
+    pub fn from_tokens(name: impl ToString, tokens: impl Into<TokenStream>) -> Result<Self, Error> {
+        let mut me = ParametrizedAttr::new(name);
+        (|input: &ParseBuffer| me.fuse_args(input)).parse2(tokens.into())?;
+        Ok(me)
+    }
+
     /// Constructs [`ParametrizedAttr`] from a given [`syn::Attribute`]
     pub fn from_attribute(attr: &Attribute) -> Result<Self, Error> {
         let name = attr
@@ -414,22 +539,22 @@ impl ParametrizedAttr {
     ///
     /// Verbatim arguments are arguments in form of `#[attr(verbatim1,
     /// verbatim2]`, i.e. path arguments containing single path segment and no
-    /// value or nested arguments.
+    /// value or nested arguments. Raw identifiers match their names without
+    /// the `r#` prefix.
     pub fn has_verbatim(&self, verbatim: &str) -> bool {
-        self.paths.iter().any(|path| path.is_ident(verbatim))
+        self.paths
+            .iter()
+            .any(|path| path_ident_name(path).as_deref() == Some(verbatim))
     }
 
     /// Returns set of verbatim attribute arguments.
     ///
     /// Verbatim arguments are arguments in form of `#[attr(verbatim1,
     /// verbatim2]`, i.e. path arguments containing single path segment and no
-    /// value or nested arguments.
+    /// value or nested arguments. Raw identifiers are returned without the
+    /// `r#` prefix.
     pub fn verbatim(&self) -> HashSet<String> {
-        self.paths
-            .iter()
-            .filter_map(Path::get_ident)
-            .map(|ident| ident.to_string())
-            .collect()
+        self.paths.iter().filter_map(path_ident_name).collect()
     }
 
     /// Merges data from the `other` into the self.
@@ -517,67 +642,87 @@ impl ParametrizedAttr {
     /// fusion takes a nested meta data.
     #[inline]
     pub fn fuse(&mut self, attr: &Attribute) -> Result<(), Error> {
-        let args = MetaArgList::parse.parse(attr.tokens.clone().into())?;
+        let args = MetaArgList::parse.parse2(attr.tokens.clone())?;
         for arg in args.list {
-            match arg {
-                // `#[ident("literal", ...)]`
-                MetaArg::Literal(Lit::Str(s)) => {
-                    let span = s.span();
-                    match self.string {
-                        None => self.string = Some(s),
-                        Some(ref mut str1) => {
-                            let mut joined = str1.value();
-                            joined.push_str(&s.value());
-                            *str1 = LitStr::new(&joined, span);
-                        }
+            self.fuse_arg(arg)?;
+        }
+        Ok(())
+    }
+
+    /// Parses comma-separated list of attribute arguments and fuses them into
+    /// the attribute parameters, reporting errors at the offending arguments
+    pub(crate) fn fuse_args(&mut self, input: &ParseBuffer) -> syn::Result<()> {
+        let args = Punctuated::<MetaArg, Token![,]>::parse_terminated(input)?;
+        for arg in args {
+            let tokens = arg.to_token_stream();
+            self.fuse_arg(arg).map_err(|err| match err {
+                Error::Parse(err) => err,
+                err => syn::Error::new_spanned(tokens, err),
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Fuses a single parsed attribute argument into the attribute parameters
+    pub(crate) fn fuse_arg(&mut self, arg: MetaArg) -> Result<(), Error> {
+        match arg {
+            // `#[ident("literal", ...)]`
+            MetaArg::Literal(Lit::Str(s)) => {
+                let span = s.span();
+                match self.string {
+                    None => self.string = Some(s),
+                    Some(ref mut str1) => {
+                        let mut joined = str1.value();
+                        joined.push_str(&s.value());
+                        *str1 = LitStr::new(&joined, span);
                     }
                 }
+            }
 
-                // `#[ident(b"literal", ...)]`
-                MetaArg::Literal(Lit::ByteStr(s)) => {
-                    let span = s.span();
-                    match self.bytes {
-                        None => self.bytes = Some(s),
-                        Some(ref mut str1) => {
-                            let mut joined = str1.value();
-                            joined.extend(&s.value());
-                            *str1 = LitByteStr::new(&joined, span);
-                        }
+            // `#[ident(b"literal", ...)]`
+            MetaArg::Literal(Lit::ByteStr(s)) => {
+                let span = s.span();
+                match self.bytes {
+                    None => self.bytes = Some(s),
+                    Some(ref mut str1) => {
+                        let mut joined = str1.value();
+                        joined.extend(&s.value());
+                        *str1 = LitByteStr::new(&joined, span);
                     }
                 }
+            }
 
-                // `#[ident(3, ...)]`
-                MetaArg::Literal(Lit::Int(lit)) => self.integers.push(lit),
+            // `#[ident(3, ...)]`
+            MetaArg::Literal(Lit::Int(lit)) => self.integers.push(lit),
 
-                // `#[ident(2.3, ...)]`
-                MetaArg::Literal(Lit::Float(lit)) => self.floats.push(lit),
+            // `#[ident(2.3, ...)]`
+            MetaArg::Literal(Lit::Float(lit)) => self.floats.push(lit),
 
-                // `#[ident('a', ...)]`
-                MetaArg::Literal(Lit::Char(lit)) => self.chars.push(lit),
+            // `#[ident('a', ...)]`
+            MetaArg::Literal(Lit::Char(lit)) => self.chars.push(lit),
 
-                // `#[ident(true, ...)]`
-                MetaArg::Literal(Lit::Bool(_)) if self.bool.is_some() => {
-                    return Err(Error::MultipleLiteralValues(self.name.clone()))
-                }
-                MetaArg::Literal(Lit::Bool(ref lit)) if self.bool.is_none() => {
-                    self.bool = Some(lit.clone())
-                }
+            // `#[ident(true, ...)]`
+            MetaArg::Literal(Lit::Bool(_)) if self.bool.is_some() => {
+                return Err(Error::MultipleLiteralValues(self.name.clone()))
+            }
+            MetaArg::Literal(Lit::Bool(ref lit)) if self.bool.is_none() => {
+                self.bool = Some(lit.clone())
+            }
 
-                // `#[ident(true, ...)]`
-                MetaArg::Literal(_) => return Err(Error::UnsupportedLiteral(self.name.clone())),
+            // `#[ident(true, ...)]`
+            MetaArg::Literal(_) => return Err(Error::UnsupportedLiteral(self.name.clone())),
 
-                // `#[ident(arg::path)]`
-                MetaArg::Path(path) => self.paths.push(path),
+            // `#[ident(arg::path)]`
+            MetaArg::Path(path) => self.paths.push(path),
 
-                // `#[ident(name = value, ...)]`
-                MetaArg::NameValue(MetaArgNameValue { name, value, .. }) => {
-                    let id = name.to_string();
-                    if self.args.insert(id.clone(), value).is_some() {
-                        return Err(Error::ArgNameMustBeUnique {
-                            attr: self.name.clone(),
-                            arg: id,
-                        });
-                    }
+            // `#[ident(name = value, ...)]`
+            MetaArg::NameValue(MetaArgNameValue { name, value, .. }) => {
+                let id = name.unraw().to_string();
+                if self.args.insert(id.clone(), value).is_some() {
+                    return Err(Error::ArgNameMustBeUnique {
+                        attr: self.name.clone(),
+                        arg: id,
+                    });
                 }
             }
         }
@@ -611,7 +756,11 @@ impl ParametrizedAttr {
     ///    [`ParametrizedAttr::args`] field.
     pub fn check(&mut self, req: AttrReq) -> Result<(), Error> {
         for (name, req) in &req.arg_req {
-            if let Some(pos) = self.paths.iter().position(|path| path.is_ident(name)) {
+            if let Some(pos) = self
+                .paths
+                .iter()
+                .position(|path| path_ident_name(path).as_ref() == Some(name))
+            {
                 self.paths.remove(pos);
                 self.args
                     .entry(name.clone())
@@ -668,81 +817,158 @@ impl ParametrizedAttr {
     }
 }
 
-// This trait should not be implemented for the types outside of this crate
-#[doc(hidden)]
+/// Extension trait for syn items having attributes, which extracts an
+/// attribute with a given name in a single call: it finds all attribute
+/// occurrences, parses them, fuses the occurrences together and checks the
+/// result against the requirements.
+///
+/// Errors are reported at the offending attribute, so a derive macro may
+/// return them right away:
+///
+/// ```
+/// # #[macro_use] extern crate syn;
+/// # use std::collections::HashMap;
+/// # use std::iter::FromIterator;
+/// use amplify_syn::{ArgValueReq, AttrReq, ExtractAttr, ValueClass};
+/// use syn::DeriveInput;
+///
+/// let input: DeriveInput = parse_quote! {
+///     #[getter(prefix = "get_")]
+///     #[getter(copy)]
+///     struct Data(u8);
+/// };
+/// let req = AttrReq::with(HashMap::from_iter(vec![
+///     ("prefix", ArgValueReq::Optional(ValueClass::str())),
+///     ("copy", ArgValueReq::Prohibited),
+/// ]));
+///
+/// let attr = input.parametrized_attr("getter", &req).unwrap().unwrap();
+/// assert!(attr.args.contains_key("prefix"));
+/// assert!(attr.args.contains_key("copy"));
+/// assert!(input.parametrized_attr("setter", &req).unwrap().is_none());
+/// ```
 pub trait ExtractAttr {
-    #[doc(hidden)]
-    fn singular_attr(
-        self,
-        name: impl ToString + AsRef<str>,
-        req: ArgValueReq,
-    ) -> Result<Option<SingularAttr>, Error>;
-
-    #[doc(hidden)]
+    /// Returns a [`ParametrizedAttr`] fused from all occurrences of the
+    /// attribute with the given `name`, which is checked against the provided
+    /// requirements. Multiple occurrences are treated according to
+    /// [`AttrReq::fuse_policy`].
+    ///
+    /// Returns `Ok(None)` if the attribute is absent.
     fn parametrized_attr(
-        self,
-        name: impl ToString + AsRef<str>,
-        req: AttrReq,
+        &self,
+        name: &str,
+        req: &AttrReq,
     ) -> Result<Option<ParametrizedAttr>, Error>;
-}
-
-impl<'a, T> ExtractAttr for T
-where
-    T: IntoIterator<Item = &'a Attribute>,
-{
-    /// Returns a [`SingularAttr`] which structure must fulfill the provided
-    /// requirements - or fails with a [`Error`] otherwise. For more information
-    /// check [`ValueReq`] requirements info.
-    fn singular_attr(
-        self,
-        name: impl ToString + AsRef<str>,
-        req: ArgValueReq,
-    ) -> Result<Option<SingularAttr>, Error> {
-        let mut attr = SingularAttr::new(name.to_string());
 
-        let filtered = self
-            .into_iter()
-            .filter(|attr| attr.path.is_ident(&name))
-            .collect::<Vec<_>>();
-
-        if filtered.is_empty() {
-            return Ok(None);
-        }
+    /// Returns a [`SingularAttr`] assembled from all occurrences of the
+    /// attribute with the given `name` (see [`SingularAttr::merge`] for the
+    /// rules).
+    ///
+    /// Returns `Ok(None)` if the attribute is absent.
+    fn singular_attr(&self, name: &str) -> Result<Option<SingularAttr>, Error> {
+        self.singular_attr_matching(name, PathMatch::Exact)
+    }
 
-        for entries in filtered {
-            attr.enrich(entries)?;
-        }
+    /// Returns a [`SingularAttr`] assembled from all occurrences of the
+    /// attribute with paths matching the given `name` according to
+    /// `path_match` policy (see [`SingularAttr::merge`] for the rules of
+    /// assembling).
+    ///
+    /// Returns `Ok(None)` if the attribute is absent.
+    fn singular_attr_matching(
+        &self,
+        name: &str,
+        path_match: PathMatch,
+    ) -> Result<Option<SingularAttr>, Error>;
+}
 
-        Some(attr.checked(req)).transpose()
+/// Adds span of the attribute to the errors which do not have it
+fn spanned(attr: &Attribute, err: Error) -> Error {
+    match err {
+        Error::Parse(_) => err,
+        err => Error::Parse(syn::Error::new_spanned(attr, err)),
     }
+}
 
-    /// Returns a [`ParametrizedAttr`] which structure must fulfill the provided
-    /// requirements - or fails with a [`Error`] otherwise. For more information
-    /// check [`AttrReq`] requirements info.
+impl ExtractAttr for [Attribute] {
     fn parametrized_attr(
-        self,
-        name: impl ToString + AsRef<str>,
-        req: AttrReq,
+        &self,
+        name: &str,
+        req: &AttrReq,
     ) -> Result<Option<ParametrizedAttr>, Error> {
-        let mut attr = ParametrizedAttr::new(name.to_string());
-
-        let filtered = self
-            .into_iter()
-            .filter(|attr| attr.path.is_ident(&name))
-            .collect::<Vec<_>>();
+        let mut filtered = self
+            .iter()
+            .filter(|attr| req.path_match.matches(&attr.path, name));
+        let first = match filtered.next() {
+            Some(attr) => attr,
+            None => return Ok(None),
+        };
 
-        if filtered.is_empty() {
-            return Ok(None);
+        let mut res = ParametrizedAttr::new(name);
+        res.fuse(first).map_err(|err| spanned(first, err))?;
+        for attr in filtered {
+            if req.fuse_policy == FusePolicy::Deny {
+                return Err(spanned(
+                    attr,
+                    Error::AttrMultipleOccurrences(name.to_owned()),
+                ));
+            }
+            res.fuse(attr).map_err(|err| spanned(attr, err))?;
         }
 
-        for entries in filtered {
-            attr.enrich(entries)?;
-        }
+        res.check(req.clone()).map_err(|err| spanned(first, err))?;
+        Ok(Some(res))
+    }
 
-        Some(attr.checked(req)).transpose()
+    fn singular_attr_matching(
+        &self,
+        name: &str,
+        path_match: PathMatch,
+    ) -> Result<Option<SingularAttr>, Error> {
+        let mut res = None::<SingularAttr>;
+        for attr in self
+            .iter()
+            .filter(|attr| path_match.matches(&attr.path, name))
+        {
+            let parsed = SingularAttr::from_attribute_named(name.to_owned(), attr)
+                .map_err(|err| spanned(attr, err))?;
+            match res {
+                None => res = Some(parsed),
+                Some(ref mut res) => res.merge(parsed).map_err(|err| spanned(attr, err))?,
+            }
+        }
+        Ok(res)
     }
 }
 
+macro_rules! impl_extract_attr {
+    ($($ty:ty),+) => {
+        $(
+            impl ExtractAttr for $ty {
+                #[inline]
+                fn parametrized_attr(
+                    &self,
+                    name: &str,
+                    req: &AttrReq,
+                ) -> Result<Option<ParametrizedAttr>, Error> {
+                    self.attrs.parametrized_attr(name, req)
+                }
+
+                #[inline]
+                fn singular_attr_matching(
+                    &self,
+                    name: &str,
+                    path_match: PathMatch,
+                ) -> Result<Option<SingularAttr>, Error> {
+                    self.attrs.singular_attr_matching(name, path_match)
+                }
+            }
+        )+
+    };
+}
+
+impl_extract_attr!(DeriveInput, Field, Variant);
+
 impl Debug for ParametrizedAttr {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.write_str("ParametrizedAttr({")?;
@@ -132,6 +132,43 @@ pub enum Error {
     /// have a single value
     ParametrizedAttrHasNoValue(String),
 
+    /// Attribute is given multiple times, while its requirements
+    /// ([`crate::AttrReq::fuse_policy`]) prohibit fusing the occurrences
+    AttrMultipleOccurrences(String),
+
+    /// Attribute assigning values to enum variants is used with a type which
+    /// is not an enum
+    EnumRequired {
+        /// Attribute name
+        attr: String,
+        /// Name of the type
+        ty: String,
+    },
+
+    /// Value assigned to enum variant does not fit enum representation
+    VariantValueOutOfRange {
+        /// Attribute name
+        attr: String,
+        /// Variant name
+        variant: String,
+        /// Value assigned to the variant
+        value: String,
+        /// Name of the integer type representing the enum
+        repr: String,
+    },
+
+    /// The same value is assigned to two enum variants
+    VariantValueNotUnique {
+        /// Attribute name
+        attr: String,
+        /// Name of the first variant with the value
+        first: String,
+        /// Name of the second variant with the value
+        second: String,
+        /// Value assigned to both variants
+        value: String,
+    },
+
     /// Lists nested within attribute arguments, like `#[attr(arg(...))]`
     /// are not supported
     #[deprecated(
@@ -155,7 +192,11 @@ impl From<syn::Error> for Error {
 
 impl From<Error> for syn::Error {
     fn from(err: Error) -> Self {
-        syn::Error::new(Span::call_site(), err.to_string())
+        match err {
+            // Parse errors keep the spans of the offending tokens
+            Error::Parse(err) => err,
+            err => syn::Error::new(Span::call_site(), err.to_string()),
+        }
     }
 }
 
@@ -231,6 +272,28 @@ impl Display for Error {
                     name = name
                 )
             }
+            Error::AttrMultipleOccurrences(name) => write!(
+                f,
+                "Attribute `{}` can be given only once",
+                name
+            ),
+            Error::EnumRequired { attr, ty } => write!(
+                f,
+                "Attribute `{}` assigns values to enum variants and can't be used with `{}`, \
+                 which is not an enum",
+                attr, ty
+            ),
+            Error::VariantValueOutOfRange { attr, variant, value, repr } => write!(
+                f,
+                "Value {} of attribute `{}` for variant `{}` does not fit into `{}` enum \
+                 representation",
+                value, attr, variant, repr
+            ),
+            Error::VariantValueNotUnique { attr, first, second, value } => write!(
+                f,
+                "Variants `{}` and `{}` have the same value {} of attribute `{}`",
+                first, second, value, attr
+            ),
             Error::NestedListsNotSupported(name) => write!(
                 f,
                 "Attribute `{name}` must be in `{name} = ...` form and a nested list",
@@ -278,10 +341,14 @@ impl std::error::Error for Error {
             | Error::ArgValueMustBeLiteral
             | Error::ArgValueMustBeType
             | Error::ParametrizedAttrHasNoValue(_)
+            | Error::AttrMultipleOccurrences(_)
             | Error::UnsupportedLiteral(_)
             | Error::AttributeUnknownArgument { .. }
             | Error::ArgNumberExceedsMax { .. }
-            | Error::ArgValueTypeMismatch { .. } => None,
+            | Error::ArgValueTypeMismatch { .. }
+            | Error::EnumRequired { .. }
+            | Error::VariantValueOutOfRange { .. }
+            | Error::VariantValueNotUnique { .. } => None,
             Error::NestedListsNotSupported(_) => None,
         }
     }
@@ -22,6 +22,9 @@
 //! `#[name = "literal"]` - optional single value
 //! `#[name = TypeName]` - path value
 //! `#[name("literal", TypeName, arg = value)]` - list of arguments
+//!
+//! With `testing` feature the crate also provides [`testing`] module with
+//! helpers for unit-testing derive macro implementations.
 
 #![deny(
     non_upper_case_globals,
@@ -33,6 +36,9 @@
     dead_code,
     warnings
 )]
+// `full` feature of syn required by the testing helpers enlarges syn types,
+// which are kept unboxed in the enums of this crate
+#![cfg_attr(feature = "testing", allow(clippy::large_enum_variant))]
 
 #[macro_use]
 extern crate quote;
@@ -45,13 +51,17 @@ mod cls;
 mod error;
 mod parsers;
 mod req;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod val;
+mod variants;
 
 pub use error::Error;
 pub use attr::{Attr, SingularAttr, ParametrizedAttr, ExtractAttr};
 pub use cls::{LiteralClass, ValueClass, TypeClass};
-pub use req::{ValueReq, ListReq, AttrReq, ArgValueReq};
+pub use req::{ValueReq, ListReq, AttrReq, ArgValueReq, FusePolicy, PathMatch};
 pub use val::ArgValue;
+pub use variants::VariantValues;
 pub use parsers::{MetaArgList, MetaArg, MetaArgNameValue};
 
 /// Convenience macro for constructing [`struct@syn::Ident`] from literals
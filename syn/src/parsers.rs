@@ -20,7 +20,7 @@ use syn::punctuated::Punctuated;
 use proc_macro2::{Ident, TokenStream};
 use quote::ToTokens;
 
-use crate::{ArgValue, Error};
+use crate::{ArgValue, Error, ParametrizedAttr};
 
 /// Drop-in replacement for [`syn::NestedMeta`], which allows to parse
 /// attributes which can have arguments made of either literal, path or
@@ -64,6 +64,12 @@ impl Parse for MetaArg {
     fn parse(input: &ParseBuffer) -> Result<Self> {
         if input.peek2(Token![=]) {
             input.parse().map(MetaArg::NameValue)
+        } else if input.peek(Lit) {
+            input.parse().map(MetaArg::Literal)
+        } else if input.peek(Ident::peek_any) && !input.peek(syn::Ident) && !input.peek2(Token![::])
+        {
+            // Keywords (like `mut`) are taken as single-segment paths
+            Ident::parse_any(input).map(|ident| MetaArg::Path(Path::from(ident)))
         } else if input.peek(Ident::peek_any)
             || input.peek(Token![::]) && input.peek3(Ident::peek_any)
         {
@@ -84,12 +90,19 @@ impl ToTokens for MetaArg {
     }
 }
 
+/// Returns name of a single-segment path, stripping the raw identifier prefix,
+/// such that `r#type` and `type` paths have the same `type` name
+pub(crate) fn path_ident_name(path: &Path) -> Option<String> {
+    path.get_ident().map(|ident| ident.unraw().to_string())
+}
+
 /// Drop-in replacement for [`syn::MetaNameValue`] used for parsing named
 /// arguments inside attributes which name is always an [`proc_macro2::Ident`]
 /// (and not [`syn::Path`]) and value can be not only a literal, but of any
 /// valid rust type.
 pub struct MetaArgNameValue {
-    /// Argument name
+    /// Argument name, which may be a keyword (like `type`) or a raw
+    /// identifier (like `r#type`)
     pub name: Ident,
     /// Token placeholder
     pub eq_token: Token![=],
@@ -99,9 +112,17 @@ pub struct MetaArgNameValue {
 
 impl Parse for MetaArgNameValue {
     fn parse(input: &ParseBuffer) -> Result<Self> {
-        let path: Path = input.parse()?;
+        // Keywords are taken as argument names, like in `#[attr(type = u8)]`
+        let name = if input.peek(Ident::peek_any) && input.peek2(Token![=]) {
+            Ident::parse_any(input)?
+        } else {
+            let path: Path = input.parse()?;
+            path.get_ident()
+                .ok_or_else(|| syn::Error::new_spanned(&path, Error::ArgNameMustBeIdent))?
+                .clone()
+        };
         Ok(MetaArgNameValue {
-            name: path.get_ident().ok_or(Error::ArgNameMustBeIdent)?.clone(),
+            name,
             eq_token: input.parse()?,
             value: input.parse()?,
         })
@@ -116,6 +137,17 @@ impl ToTokens for MetaArgNameValue {
     }
 }
 
+/// Parses arguments of an attribute procedural macro, like `arg1, arg2 =
+/// value`, into [`ParametrizedAttr`] with an empty name. Use
+/// [`ParametrizedAttr::from_tokens`] to construct a named attribute.
+impl Parse for ParametrizedAttr {
+    fn parse(input: &ParseBuffer) -> Result<Self> {
+        let mut attr = ParametrizedAttr::new("");
+        attr.fuse_args(input)?;
+        Ok(attr)
+    }
+}
+
 impl Parse for ArgValue {
     fn parse(input: &ParseBuffer) -> Result<Self> {
         if input.peek(Lit) {
@@ -135,3 +167,28 @@ impl ToTokens for ArgValue {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use syn::parse::Parser;
+    use syn::Lit;
+
+    use super::*;
+
+    #[test]
+    fn keyword_args() {
+        let args = MetaArgList::parse
+            .parse2(quote! { (mut, as_ref, true, self::path) })
+            .unwrap();
+        let args = args
+            .list
+            .iter()
+            .map(|arg| match arg {
+                MetaArg::Path(path) => path.to_token_stream().to_string(),
+                MetaArg::Literal(Lit::Bool(lit)) => format!("lit {}", lit.value),
+                _ => panic!("unexpected argument type"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(args, vec!["mut", "as_ref", "lit true", "self :: path"]);
+    }
+}
@@ -16,8 +16,10 @@
 use std::collections::{HashMap};
 use std::convert::TryInto;
 use syn::{Path, LitChar, LitInt, LitFloat};
+use syn::ext::IdentExt;
 use quote::ToTokens;
 
+use crate::parsers::path_ident_name;
 use crate::{Error, ValueClass, ArgValue};
 
 /// Structure requirements for parametrized attribute
@@ -53,6 +55,12 @@ pub struct AttrReq {
     /// Whether boolean literal is allowed as an attribute argument and, if
     /// yes, with which requirements
     pub bool_req: ValueReq,
+
+    /// How multiple occurrences of the attribute must be treated
+    pub fuse_policy: FusePolicy,
+
+    /// How attribute paths are matched against the attribute name
+    pub path_match: PathMatch,
 }
 
 impl AttrReq {
@@ -73,6 +81,66 @@ impl AttrReq {
             string_req: ValueReq::Prohibited,
             bytes_req: ValueReq::Prohibited,
             bool_req: ValueReq::Prohibited,
+            fuse_policy: FusePolicy::Fuse,
+            path_match: PathMatch::Exact,
+        }
+    }
+}
+
+/// Policy for the multiple occurrences of the same parametrized attribute,
+/// like in `#[attr(arg1)] #[attr(arg2)]`
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum FusePolicy {
+    /// Arguments of all attribute occurrences are fused into a single
+    /// attribute, as if they were given in one `#[attr(arg1, arg2)]`
+    Fuse,
+
+    /// Attribute may be given only once; other occurrences result in
+    /// [`Error::AttrMultipleOccurrences`]
+    Deny,
+}
+
+// `#[default]` enum variants are not supported by MSRV
+#[allow(clippy::derivable_impls)]
+impl Default for FusePolicy {
+    fn default() -> Self {
+        FusePolicy::Fuse
+    }
+}
+
+/// Policy for matching attribute paths against the attribute name
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum PathMatch {
+    /// Attribute path must consist of the attribute name only, like
+    /// `#[attr(...)]`
+    Exact,
+
+    /// The last segment of the attribute path must be equal to the attribute
+    /// name, such that both `#[attr(...)]` and tool-scoped `#[tool::attr(...)]`
+    /// forms match. Occurrences of both forms are treated as multiple
+    /// occurrences of the same attribute according to [`FusePolicy`]
+    Suffix,
+}
+
+// `#[default]` enum variants are not supported by MSRV
+#[allow(clippy::derivable_impls)]
+impl Default for PathMatch {
+    fn default() -> Self {
+        PathMatch::Exact
+    }
+}
+
+impl PathMatch {
+    /// Checks whether attribute `path` matches attribute `name` according to
+    /// the policy
+    pub fn matches(self, path: &Path, name: &str) -> bool {
+        match self {
+            PathMatch::Exact => path_ident_name(path).as_deref() == Some(name),
+            PathMatch::Suffix => path
+                .segments
+                .last()
+                .map(|segment| segment.arguments.is_empty() && segment.ident.unraw() == name)
+                .unwrap_or(false),
         }
     }
 }
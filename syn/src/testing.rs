@@ -0,0 +1,217 @@
+// Rust language amplification derive library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Helpers for unit-testing derive macro implementations, available with
+//! `testing` feature.
+//!
+//! The helpers run derive macro implementation function (the one which is
+//! called by the `#[proc_macro_derive]` entry point and takes parsed
+//! [`DeriveInput`]) directly, without compiling a separate crate for each
+//! test case as `trybuild` does:
+//! - [`assert_derive_err`] checks that the derivation fails with an error
+//!   containing the expected message and that the error points to the
+//!   expected part of the item source;
+//! - [`assert_derive_ok`] checks that the derivation succeeds and produces
+//!   valid rust code.
+//!
+//! ```
+//! use amplify_syn::testing::{assert_derive_err, assert_derive_ok};
+//! use proc_macro2::TokenStream;
+//! use syn::{Data, DeriveInput, Error};
+//!
+//! // Implementation of the derive macro, which is usually called by
+//! // `#[proc_macro_derive]` entry point
+//! fn derive_unit(input: DeriveInput) -> Result<TokenStream, Error> {
+//!     match input.data {
+//!         Data::Struct(_) => {
+//!             let name = &input.ident;
+//!             Ok(quote::quote! { impl #name { pub fn unit() {} } })
+//!         }
+//!         _ => Err(Error::new_spanned(&input.ident, "only structs are supported")),
+//!     }
+//! }
+//!
+//! assert_derive_ok(quote::quote! { struct Data; }, derive_unit);
+//! assert_derive_err(
+//!     quote::quote! { enum Data { A } },
+//!     derive_unit,
+//!     "only structs",
+//!     "Data",
+//! );
+//! ```
+//!
+//! # Error spans
+//!
+//! `proc_macro2` spans outside of procedural macros do not refer to any source
+//! file, so the source text covered by an error span is reconstructed from
+//! the item tokens: they are printed (with the spacing used by the
+//! [`TokenStream`] `Display` implementation, like `# [display ("...")]`) and
+//! parsed back, such that the line and column information of the spans
+//! (provided by `span-locations` feature of `proc_macro2`, which is enabled
+//! by the `testing` feature) points into the printed text. Thus:
+//! - whitespace is ignored when the expected snippet is compared with the
+//!   source text covered by the span;
+//! - spans pointing into string literals (produced with
+//!   `Literal::subspan`, which is supported only by nightly compilers inside
+//!   procedural macros) fall back to the whole literal;
+//! - errors reported at [`Span::call_site`] have no source text; an empty
+//!   expected snippet must be used for them, which skips the span check.
+
+use proc_macro2::{LineColumn, Span, TokenStream};
+use syn::{DeriveInput, File};
+
+/// Signature of derive macro implementation functions tested with the
+/// helpers from this module
+pub type DeriveFn = fn(DeriveInput) -> Result<TokenStream, syn::Error>;
+
+/// Runs derive macro implementation `derive` on the `item` and checks that it
+/// fails with an error which message contains `expected_msg` and which span
+/// covers source text containing `expected_span` snippet; see
+/// [module docs](self) on how the source text is reconstructed.
+///
+/// Errors combining multiple messages (see [`syn::Error::combine`]) pass the
+/// check if any of the messages matches.
+///
+/// # Panics
+///
+/// If the item is not a valid derive macro input, the derivation succeeds or
+/// none of the error messages matches the expectations. The panic message
+/// lists all produced errors together with the source text under their spans.
+pub fn assert_derive_err(
+    item: TokenStream,
+    derive: DeriveFn,
+    expected_msg: &str,
+    expected_span: &str,
+) {
+    let (source, input) = relocate(item);
+    let err = match derive(input) {
+        Ok(expansion) => panic!(
+            "derivation expected to fail with `{}` error succeeded with:\n{}",
+            expected_msg, expansion
+        ),
+        Err(err) => err,
+    };
+
+    let expected_span = strip_whitespace(expected_span);
+    let mut errors = Vec::new();
+    for err in err {
+        let msg = err.to_string();
+        let text = span_text(&source, err.span());
+        let span_matches = expected_span.is_empty()
+            || text
+                .map(|text| strip_whitespace(text).contains(&expected_span))
+                .unwrap_or_default();
+        if msg.contains(expected_msg) && span_matches {
+            return;
+        }
+        errors.push(format!(
+            "- `{}` at {}",
+            msg,
+            text.map(|text| format!("`{}`", text))
+                .unwrap_or_else(|| "unknown location".to_owned())
+        ));
+    }
+    panic!(
+        "derivation did not produce error `{}` at `{}`; produced errors:\n{}",
+        expected_msg,
+        expected_span,
+        errors.join("\n")
+    );
+}
+
+/// Runs derive macro implementation `derive` on the `item`, checking that it
+/// succeeds and produces valid rust code, which is returned for further
+/// inspection.
+///
+/// # Panics
+///
+/// If the item is not a valid derive macro input, the derivation fails or
+/// its result can't be parsed as a rust source file.
+pub fn assert_derive_ok(item: TokenStream, derive: DeriveFn) -> File {
+    let (_, input) = relocate(item);
+    let expansion = derive(input)
+        .unwrap_or_else(|err| panic!("derivation expected to succeed failed with `{}`", err));
+    syn::parse2::<File>(expansion.clone()).unwrap_or_else(|err| {
+        panic!(
+            "derivation produced invalid rust code ({}):\n{}",
+            err, expansion
+        )
+    })
+}
+
+/// Prints item tokens and parses them back, such that the spans of the
+/// resulting tokens point into the printed text, which is returned together
+/// with the parsed item
+fn relocate(item: TokenStream) -> (String, DeriveInput) {
+    let source = item.to_string();
+    let tokens = source
+        .parse::<TokenStream>()
+        .expect("printed tokens are always valid token stream");
+    let input = syn::parse2::<DeriveInput>(tokens)
+        .unwrap_or_else(|err| panic!("`{}` is not a valid derive input: {}", source, err));
+    (source, input)
+}
+
+/// Returns part of the `source` covered by the span, if the span has non-empty
+/// location within the source
+fn span_text(source: &str, span: Span) -> Option<&str> {
+    let start = byte_offset(source, span.start())?;
+    let end = byte_offset(source, span.end())?;
+    if start >= end {
+        return None;
+    }
+    Some(&source[start..end])
+}
+
+/// Converts line (1-based) and column (0-based, in characters) position into
+/// byte offset within the source
+fn byte_offset(source: &str, pos: LineColumn) -> Option<usize> {
+    let line_start = if pos.line <= 1 {
+        0
+    } else {
+        source
+            .match_indices('\n')
+            .nth(pos.line - 2)
+            .map(|(offset, _)| offset + 1)?
+    };
+    let line = &source[line_start..];
+    let line = &line[..line.find('\n').unwrap_or(line.len())];
+    line.char_indices()
+        .map(|(offset, _)| offset)
+        .chain(Some(line.len()))
+        .nth(pos.column)
+        .map(|offset| line_start + offset)
+}
+
+fn strip_whitespace(s: &str) -> String {
+    s.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn offsets() {
+        let source = "ab\nçd\n";
+        let pos = |line, column| LineColumn { line, column };
+        assert_eq!(byte_offset(source, pos(1, 0)), Some(0));
+        assert_eq!(byte_offset(source, pos(1, 2)), Some(2));
+        assert_eq!(byte_offset(source, pos(2, 1)), Some(5));
+        assert_eq!(byte_offset(source, pos(2, 2)), Some(6));
+        assert_eq!(byte_offset(source, pos(2, 3)), None);
+        assert_eq!(byte_offset(source, pos(4, 0)), None);
+    }
+}
@@ -0,0 +1,226 @@
+// Rust language amplification derive library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::{BTreeMap, HashMap};
+use proc_macro2::Span;
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Ident, Lit, LitInt, Meta, NestedMeta};
+
+use crate::{Error, ExtractAttr, LiteralClass};
+
+/// Integer values assigned to enum variants with an attribute in
+/// `#[attr = value]` form, like `#[repr_value = 5]`.
+///
+/// Variants without the attribute receive the value of the previous variant
+/// incremented by one (or zero for the first variant), following the rules
+/// for the native enum discriminants.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate syn;
+/// # extern crate amplify_syn;
+/// use amplify_syn::{LiteralClass, VariantValues};
+/// use syn::DeriveInput;
+///
+/// let input: DeriveInput = parse_quote! {
+///     #[repr(u8)]
+///     enum Opcode {
+///         Nop,
+///         #[code = 0x10]
+///         Push,
+///         Pop,
+///     }
+/// };
+/// let values = VariantValues::parse(&input, "code", LiteralClass::Int).unwrap();
+/// let values = values
+///     .iter()
+///     .map(|(ident, value)| format!("{} = {}", ident, value))
+///     .collect::<Vec<_>>();
+/// assert_eq!(values, vec!["Nop = 0", "Pop = 17", "Push = 0x10"]);
+/// ```
+pub struct VariantValues;
+
+impl VariantValues {
+    /// Collects values of the attribute `attr_name` for all variants of the
+    /// enum `input`, returning them indexed by the variant names.
+    ///
+    /// The attribute values must be literals of the provided `class`, which
+    /// must be one of [`LiteralClass::Int`], [`LiteralClass::Byte`] or
+    /// [`LiteralClass::Char`]; byte and character literals are converted into
+    /// integers. Each value must fit the integer type declared with the
+    /// `#[repr(...)]` attribute of the enum (`isize` if it is absent;
+    /// pointer-sized types are assumed to be 64 bit).
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::EnumRequired`] if `input` is not an enum;
+    /// - [`Error::UnsupportedLiteral`] if `class` is not an integer-convertible
+    ///   literal class;
+    /// - [`Error::ArgValueTypeMismatch`] if some attribute value does not
+    ///   match the `class`;
+    /// - [`Error::VariantValueOutOfRange`] if some value does not fit the enum
+    ///   representation;
+    /// - [`Error::VariantValueNotUnique`] if two variants have the same value.
+    ///
+    /// All errors (except for the one on wrong `class`) are returned in form of
+    /// [`Error::Parse`] spanned to the offending code; for the duplicated
+    /// values the error combines the spans of both variants.
+    pub fn parse(
+        input: &DeriveInput,
+        attr_name: &str,
+        class: LiteralClass,
+    ) -> Result<BTreeMap<Ident, LitInt>, Error> {
+        let data = match input.data {
+            Data::Enum(ref data) => data,
+            Data::Struct(_) | Data::Union(_) => {
+                return Err(spanned(
+                    input.ident.span(),
+                    Error::EnumRequired {
+                        attr: attr_name.to_owned(),
+                        ty: input.ident.to_string(),
+                    },
+                ))
+            }
+        };
+        match class {
+            LiteralClass::Int | LiteralClass::Byte | LiteralClass::Char => {}
+            _ => return Err(Error::UnsupportedLiteral(attr_name.to_owned())),
+        }
+        let (repr, max) = repr_max(input)?;
+
+        let mut values = BTreeMap::new();
+        let mut known = HashMap::<u128, &Ident>::new();
+        let mut next = Some(0u128);
+        for variant in &data.variants {
+            let ident = &variant.ident;
+            let (value, lit) = match variant.singular_attr(attr_name)? {
+                Some(attr) => {
+                    let lit = attr
+                        .literal_value()
+                        .map_err(|err| spanned(variant.span(), err))?;
+                    class
+                        .check(&lit, attr_name, ident)
+                        .map_err(|err| spanned(lit.span(), err))?;
+                    let value = match lit {
+                        Lit::Int(ref lit) => lit.base10_parse::<u128>()?,
+                        Lit::Byte(ref lit) => lit.value() as u128,
+                        Lit::Char(ref lit) => lit.value() as u128,
+                        _ => unreachable!("literal class is checked above"),
+                    };
+                    let lit = match lit {
+                        Lit::Int(lit) => lit,
+                        lit => LitInt::new(&value.to_string(), lit.span()),
+                    };
+                    (Some(value), lit)
+                }
+                None => (
+                    next,
+                    LitInt::new(
+                        &next.map(|value| value.to_string()).unwrap_or_default(),
+                        ident.span(),
+                    ),
+                ),
+            };
+
+            let value = match value {
+                Some(value) if value <= max => value,
+                _ => {
+                    let value = match value {
+                        Some(value) => value.to_string(),
+                        None => format!("{} + 1", !0u128),
+                    };
+                    return Err(spanned(
+                        lit.span(),
+                        Error::VariantValueOutOfRange {
+                            attr: attr_name.to_owned(),
+                            variant: ident.to_string(),
+                            value,
+                            repr,
+                        },
+                    ));
+                }
+            };
+            if let Some(first) = known.insert(value, ident) {
+                let msg = Error::VariantValueNotUnique {
+                    attr: attr_name.to_owned(),
+                    first: first.to_string(),
+                    second: ident.to_string(),
+                    value: value.to_string(),
+                };
+                let mut err = syn::Error::new(first.span(), &msg);
+                err.combine(syn::Error::new(ident.span(), &msg));
+                return Err(Error::Parse(err));
+            }
+            next = value.checked_add(1);
+            values.insert(ident.clone(), lit);
+        }
+        Ok(values)
+    }
+}
+
+/// Wraps error into [`Error::Parse`] with the provided span
+fn spanned(span: Span, err: Error) -> Error {
+    match err {
+        Error::Parse(_) => err,
+        err => Error::Parse(syn::Error::new(span, err)),
+    }
+}
+
+/// Detects enum representation from `#[repr(...)]` attribute, returning its
+/// name and maximal value
+fn repr_max(input: &DeriveInput) -> Result<(String, u128), Error> {
+    for attr in input.attrs.iter().filter(|attr| attr.path.is_ident("repr")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            _ => continue,
+        };
+        for nested in list.nested {
+            let ident = match nested {
+                NestedMeta::Meta(Meta::Path(ref path)) => match path.get_ident() {
+                    Some(ident) => ident.to_string(),
+                    None => continue,
+                },
+                _ => continue,
+            };
+            if let Some(max) = int_max(&ident) {
+                return Ok((ident, max));
+            }
+        }
+    }
+    let repr = "isize";
+    Ok((
+        repr.to_owned(),
+        int_max(repr).expect("isize is a primitive integer"),
+    ))
+}
+
+/// Returns maximal value for a primitive integer type with the given name
+fn int_max(name: &str) -> Option<u128> {
+    let (bits, signed) = match name {
+        "u8" => (8, false),
+        "u16" => (16, false),
+        "u32" => (32, false),
+        "u64" | "usize" => (64, false),
+        "u128" => (128, false),
+        "i8" => (8, true),
+        "i16" => (16, true),
+        "i32" => (32, true),
+        "i64" | "isize" => (64, true),
+        "i128" => (128, true),
+        _ => return None,
+    };
+    Some(!0u128 >> (128 - bits + signed as u32))
+}
@@ -0,0 +1,52 @@
+// Rust language amplification derive library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Example attribute procedural macro parsing its arguments with
+//! [`amplify_syn::ParametrizedAttr::from_tokens`]
+
+extern crate proc_macro;
+
+#[path = "../../common/mod.rs"]
+mod common;
+
+use amplify_syn::ParametrizedAttr;
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Implements `describe` method for the type, returning description of the
+/// attribute arguments, validated against [`common::req`]
+#[proc_macro_attribute]
+pub fn describe(args: TokenStream, item: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(item as DeriveInput);
+    let attr = match ParametrizedAttr::from_tokens(common::NAME, args)
+        .and_then(|attr| attr.checked(common::req()))
+    {
+        Ok(attr) => attr,
+        Err(err) => return syn::Error::from(err).to_compile_error().into(),
+    };
+    let description = common::describe(&attr);
+    let ident = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+    quote::quote!(
+        #item
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            pub fn describe() -> &'static str {
+                #description
+            }
+        }
+    )
+    .into()
+}
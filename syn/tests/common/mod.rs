@@ -0,0 +1,72 @@
+// Rust language amplification derive library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+//! Code shared by the example attribute macro and the tests comparing it with
+//! the attributes parsed from items
+
+use std::collections::HashMap;
+use std::iter::FromIterator;
+
+use amplify_syn::{ArgValueReq, AttrReq, ListReq, ParametrizedAttr, ValueClass, ValueReq};
+use quote::ToTokens;
+
+pub const NAME: &str = "describe";
+
+pub fn req() -> AttrReq {
+    let mut req = AttrReq::with(HashMap::from_iter(vec![
+        ("name", ArgValueReq::Optional(ValueClass::str())),
+        ("verbose", ArgValueReq::Prohibited),
+        ("limit", ArgValueReq::Optional(ValueClass::int())),
+    ]));
+    req.path_req = ListReq::Many {
+        whitelist: None,
+        required: false,
+        max_no: None,
+    };
+    req.integer_req = ListReq::Many {
+        whitelist: None,
+        required: false,
+        max_no: None,
+    };
+    req.string_req = ValueReq::Optional;
+    req.bool_req = ValueReq::Optional;
+    req
+}
+
+/// Deterministic description of the parsed attribute
+pub fn describe(attr: &ParametrizedAttr) -> String {
+    let mut args = attr
+        .args
+        .iter()
+        .map(|(name, value)| format!("{} = {}", name, value.to_token_stream()))
+        .collect::<Vec<_>>();
+    args.sort();
+    format!(
+        "args: [{}]; paths: [{}]; string: {:?}; integers: [{}]; bool: {:?}",
+        args.join(", "),
+        attr.paths
+            .iter()
+            .map(|path| path.to_token_stream().to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+        attr.string.as_ref().map(|lit| lit.value()),
+        attr.integers
+            .iter()
+            .map(|lit| lit.to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+        attr.bool.as_ref().map(|lit| lit.value),
+    )
+}
@@ -0,0 +1,314 @@
+// Rust language amplification derive library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+mod common;
+
+use amplify_syn::{ArgValue, Error, ExtractAttr, FusePolicy, PathMatch};
+use quote::ToTokens;
+use syn::{parse_quote, Attribute, Data, DeriveInput, Field, Variant};
+
+fn describe(item: &(impl ExtractAttr + ?Sized)) -> Option<String> {
+    item.parametrized_attr(common::NAME, &common::req())
+        .unwrap()
+        .map(|attr| common::describe(&attr))
+}
+
+fn error(item: &impl ExtractAttr) -> String {
+    syn::Error::from(
+        item.parametrized_attr(common::NAME, &common::req())
+            .unwrap_err(),
+    )
+    .to_string()
+}
+
+#[test]
+fn absent() {
+    let input: DeriveInput = parse_quote! {
+        #[other(verbose)]
+        struct Data {
+            field: u8,
+        }
+    };
+    assert_eq!(describe(&input), None);
+    assert!(input.singular_attr(common::NAME).unwrap().is_none());
+
+    let field: Field = match input.data {
+        Data::Struct(ref data) => data.fields.iter().next().unwrap().clone(),
+        _ => unreachable!(),
+    };
+    assert_eq!(describe(&field), None);
+
+    let attrs: Vec<Attribute> = vec![];
+    assert_eq!(describe(&attrs[..]), None);
+}
+
+#[test]
+fn empty() {
+    let input: DeriveInput = parse_quote! {
+        #[describe()]
+        struct Data;
+    };
+    assert_eq!(
+        describe(&input).unwrap(),
+        "args: []; paths: []; string: None; integers: []; bool: None"
+    );
+}
+
+#[test]
+fn items() {
+    let input: DeriveInput = parse_quote! {
+        #[describe(verbose)]
+        enum Data {
+            #[describe(name = "first")]
+            First {
+                #[describe(limit = 5)]
+                field: u8,
+            },
+        }
+    };
+    assert_eq!(
+        describe(&input).unwrap(),
+        "args: [verbose = !]; paths: []; string: None; integers: []; bool: None"
+    );
+
+    let variant: Variant = match input.data {
+        Data::Enum(ref data) => data.variants.iter().next().unwrap().clone(),
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        describe(&variant).unwrap(),
+        "args: [name = \"first\"]; paths: []; string: None; integers: []; bool: None"
+    );
+
+    let field = variant.fields.iter().next().unwrap();
+    assert_eq!(
+        describe(field).unwrap(),
+        "args: [limit = 5]; paths: []; string: None; integers: []; bool: None"
+    );
+    assert_eq!(describe(&field.attrs[..]), describe(field));
+}
+
+#[test]
+fn fused() {
+    let single: DeriveInput = parse_quote! {
+        #[describe(verbose, name = "data", "doc", 1)]
+        struct Data;
+    };
+    let multiple: DeriveInput = parse_quote! {
+        #[describe(verbose)]
+        #[doc = "Data structure"]
+        #[describe(name = "data", "doc")]
+        #[describe(1)]
+        struct Data;
+    };
+    assert_eq!(describe(&multiple), describe(&single));
+}
+
+#[test]
+fn fuse_denied() {
+    let input: DeriveInput = parse_quote! {
+        #[describe(verbose)]
+        #[describe(name = "data")]
+        struct Data;
+    };
+    let mut req = common::req();
+    req.fuse_policy = FusePolicy::Deny;
+    let err = input.parametrized_attr(common::NAME, &req).unwrap_err();
+    assert_eq!(
+        syn::Error::from(err).to_string(),
+        "Attribute `describe` can be given only once"
+    );
+
+    let input: DeriveInput = parse_quote! {
+        #[describe(verbose)]
+        struct Data;
+    };
+    assert!(input
+        .parametrized_attr(common::NAME, &req)
+        .unwrap()
+        .is_some());
+}
+
+fn describe_scoped(item: &(impl ExtractAttr + ?Sized)) -> Option<String> {
+    let mut req = common::req();
+    req.path_match = PathMatch::Suffix;
+    item.parametrized_attr(common::NAME, &req)
+        .unwrap()
+        .map(|attr| common::describe(&attr))
+}
+
+#[test]
+fn scoped() {
+    let input: DeriveInput = parse_quote! {
+        #[tool::describe(verbose)]
+        enum Data {
+            #[::tool::describe(name = "first")]
+            First {
+                #[tool::describe(limit = 5)]
+                #[tool::other(limit = 6)]
+                field: u8,
+            },
+        }
+    };
+    assert_eq!(describe(&input), None);
+    assert_eq!(
+        describe_scoped(&input).unwrap(),
+        "args: [verbose = !]; paths: []; string: None; integers: []; bool: None"
+    );
+
+    let variant: Variant = match input.data {
+        Data::Enum(ref data) => data.variants.iter().next().unwrap().clone(),
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        describe_scoped(&variant).unwrap(),
+        "args: [name = \"first\"]; paths: []; string: None; integers: []; bool: None"
+    );
+
+    let field = variant.fields.iter().next().unwrap();
+    assert_eq!(
+        describe_scoped(field).unwrap(),
+        "args: [limit = 5]; paths: []; string: None; integers: []; bool: None"
+    );
+
+    let input: DeriveInput = parse_quote! {
+        #[tool::describe = "data"]
+        #[describe]
+        struct Data;
+    };
+    assert!(input
+        .singular_attr_matching(common::NAME, PathMatch::Exact)
+        .unwrap()
+        .is_some());
+    let attr = input
+        .singular_attr_matching(common::NAME, PathMatch::Suffix)
+        .unwrap()
+        .unwrap();
+    assert_eq!(attr.name, common::NAME);
+    assert_eq!(
+        attr.value.to_token_stream().to_string(),
+        ArgValue::from("data").to_token_stream().to_string()
+    );
+}
+
+#[test]
+fn scoped_fused() {
+    let input: DeriveInput = parse_quote! {
+        #[describe(verbose)]
+        #[tool::describe(name = "data")]
+        struct Data;
+    };
+    assert_eq!(
+        describe_scoped(&input).unwrap(),
+        "args: [name = \"data\", verbose = !]; paths: []; string: None; integers: []; bool: \
+         None"
+    );
+
+    let conflicting: DeriveInput = parse_quote! {
+        #[describe(name = "first")]
+        #[tool::describe(name = "second")]
+        struct Data;
+    };
+    let mut req = common::req();
+    req.path_match = PathMatch::Suffix;
+    let err = conflicting
+        .parametrized_attr(common::NAME, &req)
+        .unwrap_err();
+    assert_eq!(
+        syn::Error::from(err).to_string(),
+        "Argument names must be unique, while attribute `describe` contains multiple arguments \
+         with name`name`"
+    );
+
+    req.fuse_policy = FusePolicy::Deny;
+    let err = input.parametrized_attr(common::NAME, &req).unwrap_err();
+    assert_eq!(
+        syn::Error::from(err).to_string(),
+        "Attribute `describe` can be given only once"
+    );
+
+    let input: DeriveInput = parse_quote! {
+        #[describe = "first"]
+        #[tool::describe = "second"]
+        struct Data;
+    };
+    assert_eq!(
+        syn::Error::from(
+            input
+                .singular_attr_matching(common::NAME, PathMatch::Suffix)
+                .unwrap_err()
+        )
+        .to_string(),
+        "Multiple values assigned to `describe` attribute"
+    );
+}
+
+#[test]
+fn errors() {
+    let unknown: DeriveInput = parse_quote! {
+        #[describe(verbose)]
+        #[describe(unknown = 1)]
+        struct Data;
+    };
+    assert_eq!(
+        error(&unknown),
+        "Attribute `describe` has an unknown argument `unknown`"
+    );
+
+    let duplicate: DeriveInput = parse_quote! {
+        #[describe(name = "first")]
+        #[describe(name = "second")]
+        struct Data;
+    };
+    assert_eq!(
+        error(&duplicate),
+        "Argument names must be unique, while attribute `describe` contains multiple arguments \
+         with name`name`"
+    );
+
+    let invalid: DeriveInput = parse_quote! {
+        #[describe(name = )]
+        struct Data;
+    };
+    match invalid.parametrized_attr(common::NAME, &common::req()) {
+        Err(Error::Parse(_)) => {}
+        _ => panic!("attribute parse error expected"),
+    }
+}
+
+#[test]
+fn singular() {
+    let input: DeriveInput = parse_quote! {
+        #[describe = "data"]
+        #[describe]
+        struct Data;
+    };
+    let attr = input.singular_attr(common::NAME).unwrap().unwrap();
+    assert_eq!(attr.name, common::NAME);
+    assert_eq!(
+        attr.value.to_token_stream().to_string(),
+        ArgValue::from("data").to_token_stream().to_string()
+    );
+
+    let input: DeriveInput = parse_quote! {
+        #[describe = "first"]
+        #[describe = "second"]
+        struct Data;
+    };
+    assert_eq!(
+        syn::Error::from(input.singular_attr(common::NAME).unwrap_err()).to_string(),
+        "Multiple values assigned to `describe` attribute"
+    );
+}
@@ -0,0 +1,129 @@
+// Rust language amplification derive library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+#[macro_use]
+extern crate amplify_syn_attr_macro;
+
+mod common;
+
+use amplify_syn::ParametrizedAttr;
+use proc_macro2::TokenStream;
+use syn::{parse_quote, Attribute};
+
+/// Describes attribute arguments parsed in the same way as derive macros do
+/// for the attributes on the items
+fn describe_attribute(attr: Attribute) -> String {
+    let attr = ParametrizedAttr::with(common::NAME, &[attr])
+        .and_then(|attr| attr.checked(common::req()))
+        .unwrap();
+    common::describe(&attr)
+}
+
+/// Describes attribute arguments parsed in the same way as attribute macros do
+fn describe_tokens(tokens: TokenStream) -> String {
+    let attr = ParametrizedAttr::from_tokens(common::NAME, tokens)
+        .and_then(|attr| attr.checked(common::req()))
+        .unwrap();
+    common::describe(&attr)
+}
+
+#[describe]
+struct Empty;
+
+#[describe("literal")]
+struct Literal;
+
+#[describe(
+    name = "value",
+    verbose,
+    limit = 5,
+    1,
+    2,
+    "text",
+    true,
+    some::path,
+    other
+)]
+struct Mixed;
+
+#[test]
+fn empty_args() {
+    assert_eq!(
+        Empty::describe(),
+        "args: []; paths: []; string: None; integers: []; bool: None"
+    );
+    assert_eq!(
+        Empty::describe(),
+        describe_attribute(parse_quote!(#[describe()]))
+    );
+    assert_eq!(Empty::describe(), describe_tokens(quote::quote! {}));
+}
+
+#[test]
+fn single_literal() {
+    assert_eq!(
+        Literal::describe(),
+        "args: []; paths: []; string: Some(\"literal\"); integers: []; bool: None"
+    );
+    assert_eq!(
+        Literal::describe(),
+        describe_attribute(parse_quote!(#[describe("literal")]))
+    );
+    assert_eq!(
+        Literal::describe(),
+        describe_tokens(quote::quote! { "literal" })
+    );
+}
+
+#[test]
+fn mixed_args() {
+    assert_eq!(
+        Mixed::describe(),
+        "args: [limit = 5, name = \"value\", verbose = !]; paths: [some :: path, other]; \
+         string: Some(\"text\"); integers: [1, 2]; bool: Some(true)"
+    );
+    assert_eq!(
+        Mixed::describe(),
+        describe_attribute(parse_quote!(
+            #[describe(name = "value", verbose, limit = 5, 1, 2, "text", true, some::path, other)]
+        ))
+    );
+    assert_eq!(
+        Mixed::describe(),
+        describe_tokens(quote::quote! {
+            name = "value", verbose, limit = 5, 1, 2, "text", true, some::path, other,
+        })
+    );
+}
+
+#[test]
+fn parse_impl() {
+    let attr: ParametrizedAttr = syn::parse_quote! { verbose, name = "value" };
+    assert_eq!(attr.name, "");
+    assert_eq!(attr.paths.len(), 1);
+    assert_eq!(attr.args.len(), 1);
+}
+
+#[test]
+fn errors() {
+    let err = ParametrizedAttr::from_tokens(common::NAME, quote::quote! { name = 1, name = 2 })
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("contains multiple arguments with name`name`"));
+    assert!(ParametrizedAttr::from_tokens(common::NAME, quote::quote! { name = }).is_err());
+    assert!(ParametrizedAttr::from_tokens(common::NAME, quote::quote! { a::b = 1 }).is_err());
+    assert!(ParametrizedAttr::from_tokens(common::NAME, quote::quote! { true, false }).is_err());
+}
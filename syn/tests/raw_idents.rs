@@ -0,0 +1,98 @@
+// Rust language amplification derive library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::HashMap;
+use std::iter::FromIterator;
+
+use amplify_syn::{
+    ArgValueReq, AttrReq, ExtractAttr, ListReq, ParametrizedAttr, PathMatch, TypeClass, ValueClass,
+};
+use quote::ToTokens;
+use syn::{parse_quote, Attribute};
+
+fn req() -> AttrReq {
+    let mut req = AttrReq::with(HashMap::from_iter(vec![
+        (
+            "type",
+            ArgValueReq::Optional(ValueClass::Type(TypeClass::Path)),
+        ),
+        ("ref", ArgValueReq::Prohibited),
+        ("inner", ArgValueReq::Optional(ValueClass::str())),
+    ]));
+    req.path_req = ListReq::Many {
+        whitelist: None,
+        required: false,
+        max_no: None,
+    };
+    req
+}
+
+fn describe(attr: Attribute) -> String {
+    let attr = ParametrizedAttr::with("attr", &[attr])
+        .and_then(|attr| attr.checked(req()))
+        .unwrap();
+    let mut args = attr
+        .args
+        .iter()
+        .map(|(name, value)| format!("{} = {}", name, value.to_token_stream()))
+        .collect::<Vec<_>>();
+    args.sort();
+    format!("args: [{}]; verbatim: {:?}", args.join(", "), {
+        let mut verbatim = attr.verbatim().into_iter().collect::<Vec<_>>();
+        verbatim.sort();
+        verbatim
+    })
+}
+
+#[test]
+fn keyword_arg_names() {
+    let expected = "args: [inner = \"x\", ref = !, type = u8]; verbatim: [\"mut\"]";
+    assert_eq!(
+        describe(parse_quote!(#[attr(type = u8, ref, inner = "x", mut)])),
+        expected
+    );
+    assert_eq!(
+        describe(parse_quote!(#[attr(r#type = u8, r#ref, r#inner = "x", r#mut)])),
+        expected
+    );
+}
+
+#[test]
+fn raw_verbatim() {
+    let attr: Attribute = parse_quote!(#[attr(r#inner, r#mut, plain)]);
+    let attr = ParametrizedAttr::with("attr", &[attr]).unwrap();
+    assert!(attr.has_verbatim("inner"));
+    assert!(attr.has_verbatim("mut"));
+    assert!(attr.has_verbatim("plain"));
+    assert!(!attr.has_verbatim("r#inner"));
+}
+
+#[test]
+fn duplicate_raw_arg() {
+    let attr: Attribute = parse_quote!(#[attr(inner = "a", r#inner = "b")]);
+    assert!(ParametrizedAttr::with("attr", &[attr]).is_err());
+}
+
+#[test]
+fn raw_path_match() {
+    let attrs: Vec<Attribute> = vec![parse_quote!(#[attr(r#type = u16)])];
+    let attr = attrs[..]
+        .parametrized_attr("attr", &req())
+        .unwrap()
+        .unwrap();
+    assert_eq!(attr.args["type"].to_token_stream().to_string(), "u16");
+    assert!(PathMatch::Exact.matches(&parse_quote!(r#type), "type"));
+    assert!(PathMatch::Suffix.matches(&parse_quote!(some::r#type), "type"));
+}
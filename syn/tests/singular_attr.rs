@@ -0,0 +1,156 @@
+// Rust language amplification derive library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+mod common;
+
+use std::convert::TryInto;
+
+use amplify_syn::{ArgValue, Attr, Error, SingularAttr, ValueReq};
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::spanned::Spanned;
+use syn::{parse_quote, Attribute, LitInt, LitStr, Path};
+
+fn normalized(attr: Attribute) -> Result<String, Error> {
+    Attr::from_attribute(&attr)?
+        .normalize("name")
+        .checked(common::req())
+        .map(|attr| common::describe(&attr))
+}
+
+#[test]
+fn normalize() {
+    let valued: Attribute = parse_quote! { #[describe = "data"] };
+    let bare: Attribute = parse_quote! { #[describe] };
+    let parametrized: Attribute = parse_quote! { #[describe(name = "data")] };
+    let empty: Attribute = parse_quote! { #[describe()] };
+
+    assert_eq!(
+        normalized(valued.clone()).unwrap(),
+        normalized(parametrized).unwrap()
+    );
+    assert_eq!(
+        normalized(valued).unwrap(),
+        "args: [name = \"data\"]; paths: []; string: None; integers: []; bool: None"
+    );
+    assert_eq!(normalized(bare).unwrap(), normalized(empty).unwrap());
+
+    let mismatch: Attribute = parse_quote! { #[describe = 5] };
+    assert_eq!(
+        normalized(mismatch).unwrap_err().to_string(),
+        "Type mismatch in attribute `describe` argument `name`"
+    );
+}
+
+#[test]
+fn into_parametrized() {
+    let attr: Attribute = parse_quote! { #[describe = 5] };
+    let singular = SingularAttr::from_attribute(&attr).unwrap();
+    let span = singular.value.span();
+    let parametrized = singular.into_parametrized("limit");
+    assert_eq!(parametrized.name, common::NAME);
+    assert_eq!(parametrized.args.len(), 1);
+    let value = &parametrized.args["limit"];
+    assert_eq!(format!("{:?}", value.span()), format!("{:?}", span));
+
+    let singular = SingularAttr::with_type(common::NAME, parse_quote! { std::io::Error });
+    let parametrized = singular.into_parametrized("ty");
+    let path: Path = parametrized.args["ty"].clone().try_into().unwrap();
+    assert_eq!(path.to_token_stream().to_string(), "std :: io :: Error");
+
+    let parametrized = SingularAttr::new(common::NAME).into_parametrized("limit");
+    assert_eq!(parametrized.name, common::NAME);
+    assert!(parametrized.args.is_empty());
+}
+
+#[test]
+fn typed_value() {
+    let attr: Attribute = parse_quote! { #[describe = "data"] };
+    let singular = SingularAttr::from_attribute(&attr).unwrap();
+    assert_eq!(singular.value::<String>().unwrap(), "data");
+    assert_eq!(
+        singular
+            .opt_value::<LitStr>()
+            .unwrap()
+            .map(|lit| lit.value()),
+        Some("data".to_owned())
+    );
+    assert_eq!(
+        singular
+            .value::<LitInt>()
+            .err()
+            .expect("error expected")
+            .to_string(),
+        Error::ArgValueMustBeLiteral.to_string()
+    );
+
+    let bare = SingularAttr::new(common::NAME);
+    assert!(bare.opt_value::<String>().unwrap().is_none());
+    assert_eq!(
+        bare.value::<String>().unwrap_err().to_string(),
+        "Attribute `describe` requires value for argument `describe`"
+    );
+}
+
+#[test]
+fn checked_value() {
+    let mut bare = SingularAttr::new(common::NAME);
+    assert!(bare
+        .checked_value::<String>(&ValueReq::Optional)
+        .unwrap()
+        .is_none());
+    assert!(bare
+        .checked_value::<String>(&ValueReq::Prohibited)
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        bare.checked_value::<String>(&ValueReq::Required)
+            .unwrap_err()
+            .to_string(),
+        "Attribute `describe` requires value for argument `describe`"
+    );
+    let default = ValueReq::Default(ArgValue::from("default"));
+    assert_eq!(
+        bare.checked_value::<String>(&default).unwrap().as_deref(),
+        Some("default")
+    );
+    assert_eq!(bare.value::<String>().unwrap(), "default");
+
+    let mut valued =
+        SingularAttr::with_literal(common::NAME, LitInt::new("5", Span::call_site()).into());
+    assert_eq!(
+        valued
+            .checked_value::<LitInt>(&ValueReq::Required)
+            .unwrap()
+            .map(|lit| lit.base10_digits().to_owned()),
+        Some("5".to_owned())
+    );
+    assert_eq!(
+        valued
+            .checked_value::<LitInt>(&default)
+            .unwrap()
+            .map(|lit| lit.base10_digits().to_owned()),
+        Some("5".to_owned())
+    );
+    assert_eq!(
+        valued
+            .checked_value::<LitInt>(&ValueReq::Prohibited)
+            .err()
+            .expect("error expected")
+            .to_string(),
+        "Argument describe in `describe` attribute must not have a value"
+    );
+    assert!(valued.checked_value::<String>(&ValueReq::Required).is_err());
+}
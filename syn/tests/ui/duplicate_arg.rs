@@ -0,0 +1,7 @@
+#[macro_use]
+extern crate amplify_syn_attr_macro;
+
+#[describe(name = "a", name = "b")]
+struct Described;
+
+fn main() {}
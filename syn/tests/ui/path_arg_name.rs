@@ -0,0 +1,7 @@
+#[macro_use]
+extern crate amplify_syn_attr_macro;
+
+#[describe(some::name = 1)]
+struct Described;
+
+fn main() {}
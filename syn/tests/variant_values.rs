@@ -0,0 +1,173 @@
+// Rust language amplification derive library providing multiple generic trait
+// implementations, type wrappers, derive macros and other language enhancements
+//
+// Written in 2019-2021 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use amplify_syn::{Error, LiteralClass, VariantValues};
+use syn::{parse_quote, DeriveInput};
+
+fn values(input: &DeriveInput, class: LiteralClass) -> Vec<String> {
+    VariantValues::parse(input, "code", class)
+        .unwrap()
+        .iter()
+        .map(|(ident, value)| format!("{} = {}", ident, value.base10_digits()))
+        .collect()
+}
+
+fn error(input: &DeriveInput) -> String {
+    syn::Error::from(
+        VariantValues::parse(input, "code", LiteralClass::Int)
+            .err()
+            .expect("error expected"),
+    )
+    .to_string()
+}
+
+#[test]
+fn auto_increment() {
+    let input: DeriveInput = parse_quote! {
+        enum Data {
+            First,
+            #[code = 5]
+            Second,
+            Third,
+            #[code = 2]
+            Fourth,
+            Fifth,
+        }
+    };
+    assert_eq!(
+        values(&input, LiteralClass::Int),
+        vec![
+            "Fifth = 3",
+            "First = 0",
+            "Fourth = 2",
+            "Second = 5",
+            "Third = 6"
+        ]
+    );
+}
+
+#[test]
+fn byte_and_char() {
+    let input: DeriveInput = parse_quote! {
+        enum Data {
+            #[code = b'a']
+            First,
+            Second,
+        }
+    };
+    assert_eq!(
+        values(&input, LiteralClass::Byte),
+        vec!["First = 97", "Second = 98"]
+    );
+
+    let input: DeriveInput = parse_quote! {
+        #[repr(u32)]
+        enum Data {
+            #[code = 'ы']
+            First,
+        }
+    };
+    assert_eq!(values(&input, LiteralClass::Char), vec!["First = 1099"]);
+}
+
+#[test]
+fn repr_range() {
+    let input: DeriveInput = parse_quote! {
+        #[derive(Clone)]
+        #[repr(C, u8)]
+        enum Data {
+            #[code = 255]
+            Max,
+        }
+    };
+    assert_eq!(values(&input, LiteralClass::Int), vec!["Max = 255"]);
+
+    let input: DeriveInput = parse_quote! {
+        #[repr(u8)]
+        enum Data {
+            #[code = 255]
+            Max,
+            Overflow,
+        }
+    };
+    assert_eq!(
+        error(&input),
+        "Value 256 of attribute `code` for variant `Overflow` does not fit into `u8` enum \
+         representation"
+    );
+
+    let input: DeriveInput = parse_quote! {
+        #[repr(i8)]
+        enum Data {
+            #[code = 128]
+            Max,
+        }
+    };
+    assert_eq!(
+        error(&input),
+        "Value 128 of attribute `code` for variant `Max` does not fit into `i8` enum \
+         representation"
+    );
+}
+
+#[test]
+fn errors() {
+    let duplicate: DeriveInput = parse_quote! {
+        enum Data {
+            #[code = 1]
+            First,
+            Second,
+            #[code = 2]
+            Third,
+        }
+    };
+    let err = syn::Error::from(
+        VariantValues::parse(&duplicate, "code", LiteralClass::Int)
+            .err()
+            .expect("error expected"),
+    );
+    assert_eq!(err.into_iter().count(), 2);
+    assert_eq!(
+        error(&duplicate),
+        "Variants `Second` and `Third` have the same value 2 of attribute `code`"
+    );
+
+    let structure: DeriveInput = parse_quote! {
+        struct Data {
+            field: u8,
+        }
+    };
+    assert_eq!(
+        error(&structure),
+        "Attribute `code` assigns values to enum variants and can't be used with `Data`, which \
+         is not an enum"
+    );
+
+    let mismatch: DeriveInput = parse_quote! {
+        enum Data {
+            #[code = "first"]
+            First,
+        }
+    };
+    assert_eq!(
+        error(&mismatch),
+        "Type mismatch in attribute `code` argument `First`"
+    );
+
+    match VariantValues::parse(&mismatch, "code", LiteralClass::Str) {
+        Err(Error::UnsupportedLiteral(attr)) => assert_eq!(attr, "code"),
+        _ => panic!("unsupported literal error expected"),
+    }
+}